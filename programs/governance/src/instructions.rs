@@ -1,24 +1,138 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        hash::hashv,
+        instruction::{AccountMeta, Instruction},
+        program::{invoke, invoke_signed},
+        sysvar::instructions::{
+            self as sysvar_instructions, load_current_index_checked, load_instruction_at_checked,
+        },
+    },
+    Discriminator, ZeroCopy,
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{Mint, TokenAccount, TokenInterface},
+    token_2022::spl_token_2022::{
+        extension::{
+            non_transferable::NonTransferable, BaseStateWithExtensions, StateWithExtensions,
+        },
+        onchain::invoke_transfer_checked,
+        state::Mint as MintState,
+    },
+    token_interface::{Mint, Token2022, TokenAccount, TokenInterface},
+};
+use std::{cell::RefMut, cmp::Reverse};
+use ttt_token::program::TokenExtensions;
+
+use crate::{
+    allowlist_entry_address, blocklist_entry_address, election_seed, eligibility_leaf, hex_encode,
+    isqrt, project_address, push_json_string, receipt_authority_address, verify_eligibility_proof,
+    vote_authorization_message, ADMIN_ACTION_SEED, ADMIN_COUNCIL_SEED, ALLOWLIST_SEED, BALLOT_SEED,
+    BLOCKLIST_SEED, CAMPAIGN_SEED, CATEGORY_REGISTRY_SEED, FEE_DISCOUNT_SEED,
+    GOVERNANCE_AUTHORITY_SEED, HOLDING_ATTESTATION_SEED, LEADERBOARD_SEED, OPERATION_MARKER_SEED,
+    PROJECT_SEED, PROPOSAL_SEED, PROPOSAL_VOTE_SEED, RANKED_TALLY_SEED, RECEIPT_AUTHORITY_SEED,
+    REFERENDUM_SEED, REFERENDUM_VOTE_SEED, REWARD_VAULT_SEED, ROLE_SEED, ROUND_ARCHIVE_SEED,
+    ROUND_ATTESTATION_SEED, ROUND_RESULT_SEED, ROUND_VOTES_SEED, SPONSOR_RECORD_SEED,
+    SPONSOR_VAULT_SEED, STAKE_POSITION_SEED, STEALTH_VOTER_SEED, TALLY_STATE_SEED, TREASURY_SEED,
+    VOTER_SEED, VOTE_COMMIT_SEED, VOTE_LOCK_SEED, VOTE_MANAGER_SEED, VOTE_NONCE_SEED,
+    VOTE_TREE_SEED, WEIGHT_PRECISION,
 };
 
 pub const PROJECT_ID_MAX_LEN: usize = 50;
-pub const VOTER_NAMESPACE: &str = "voter";
+pub const PROJECT_BATCH_MAX_LEN: usize = 20;
+pub const PROJECT_NAME_MAX_LEN: usize = 64;
+pub const PROJECT_URI_MAX_LEN: usize = 200;
+pub const LEADERBOARD_LEN: usize = 10;
+pub const REFERENDUM_URI_MAX_LEN: usize = 200;
+pub const DEFAULT_MAX_VOTES_PER_ROUND: u16 = 1;
+pub const DEFAULT_PROPOSAL_VOTING_PERIOD: i64 = 3 * 24 * 60 * 60;
+pub const PROPOSAL_MAX_INSTRUCTIONS: usize = 4;
+pub const PROPOSAL_MAX_ACCOUNTS: usize = 8;
+pub const PROPOSAL_MAX_DATA_LEN: usize = 128;
+pub const CAMPAIGN_MAX_ROUNDS: usize = 12;
+pub const CAMPAIGN_THEME_MAX_LEN: usize = 64;
+pub const RANKED_MAX_CHOICES: usize = 5;
+pub const RANKED_MAX_CANDIDATES: usize = 16;
+pub const COUNCIL_MAX_MEMBERS: usize = 10;
+pub const FEE_DISCOUNT_MAX_TIERS: usize = 8;
+pub const INVALID_REASON_MAX_LEN: usize = 200;
+pub const CATEGORY_MAX_COUNT: usize = 16;
+pub const CATEGORY_NAME_MAX_LEN: usize = 32;
+/// The spl-account-compression program holding the concurrent Merkle trees of compressed votes.
+pub const ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+/// The spl-noop program the compression program logs tree changes through.
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+// Anchor discriminators of the spl-account-compression instructions invoked for compressed votes.
+const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+const APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+const REPLACE_LEAF_DISCRIMINATOR: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
 
 pub fn initialize_vote(
-    ctx: Context<Admin>,
-    token_mint: Pubkey,
-    token_program: Pubkey,
+    ctx: Context<Initialize>,
+    election_id: u64,
     init_vote_fee: u64,
 ) -> Result<()> {
     // Set the initial state of the VoteManager.
+    ctx.accounts.vote_data.version = VoteManager::VERSION;
     ctx.accounts.vote_data.vote_round = 1;
     ctx.accounts.vote_data.admin = ctx.accounts.owner.key();
-    ctx.accounts.vote_data.tk_mint = token_mint;
-    ctx.accounts.vote_data.tk_program = token_program;
+    ctx.accounts.vote_data.creator = ctx.accounts.owner.key();
+    ctx.accounts.vote_data.election_id = election_id;
+    ctx.accounts.vote_data.pending_admin = None;
+    ctx.accounts.vote_data.tk_mint = ctx.accounts.mint.key();
+    ctx.accounts.vote_data.tk_program = ctx.accounts.token_program.key();
     ctx.accounts.vote_data.vote_fee = init_vote_fee;
+    ctx.accounts.vote_data.max_votes_per_round = DEFAULT_MAX_VOTES_PER_ROUND;
+    ctx.accounts.vote_data.max_votes_per_project = 0;
+    ctx.accounts.vote_data.round_start_ts = Clock::get()?.unix_timestamp;
+    ctx.accounts.vote_data.round_duration = 0;
+    ctx.accounts.vote_data.weight_curve = WeightCurve::default();
+    ctx.accounts.vote_data.commit_reveal = false;
+    ctx.accounts.vote_data.reveal_duration = 0;
+    ctx.accounts.vote_data.stealth_voting = false;
+    ctx.accounts.vote_data.ranked_choice = false;
+    ctx.accounts.vote_data.quadratic_pricing = false;
+    ctx.accounts.vote_data.eligibility_root = [0; 32];
+    ctx.accounts.vote_data.reward_claim_window = 0;
+    ctx.accounts.vote_data.proposal_count = 0;
+    ctx.accounts.vote_data.proposal_deposit = 0;
+    ctx.accounts.vote_data.proposal_voting_period = DEFAULT_PROPOSAL_VOTING_PERIOD;
+    ctx.accounts.vote_data.proposal_quorum = 0;
+    ctx.accounts.vote_data.project_deposit = 0;
+    ctx.accounts.vote_data.round_state = RoundState::Pending;
+    ctx.accounts.vote_data.receipt_mint = Pubkey::default();
+    ctx.accounts.vote_data.vote_receipts = false;
+    ctx.accounts.vote_data.escrow_mode = false;
+    ctx.accounts.vote_data.max_lock_duration = 0;
+    ctx.accounts.vote_data.paused = false;
+    ctx.accounts.vote_data.fee_mode = FeeMode::Collect;
+    ctx.accounts.vote_data.eligible_weight = 0;
+    ctx.accounts.vote_data.retract_refund = false;
+    ctx.accounts.vote_data.round_leaderboard = false;
+    ctx.accounts.vote_data.referendum_count = 0;
+    ctx.accounts.vote_data.deposit_tranche_votes = 0;
+    ctx.accounts.vote_data.deposit_refund_votes = 0;
+    ctx.accounts.vote_data.min_holding_balance = 0;
+    ctx.accounts.vote_data.min_holding_slots = 0;
+    ctx.accounts.vote_data.max_projects = 0;
+    ctx.accounts.vote_data.project_count = 0;
+    ctx.accounts.vote_data.tie_break = TieBreakPolicy::default();
+    ctx.accounts.vote_data.runoff_duration = 0;
+    ctx.accounts.vote_data.round_leader_created_slot = 0;
+    ctx.accounts.vote_data.round_tied = false;
+    ctx.accounts.vote_data.runoff_weight = 0;
+    ctx.accounts.vote_data.sol_vote_fee = 0;
+    ctx.accounts.vote_data.round_fees = 0;
+    ctx.accounts.vote_data.owed_fees = 0;
+    ctx.accounts.vote_data.access_mode = AccessMode::Open;
+    ctx.accounts.vote_data.bump = ctx.bumps.vote_data;
+
+    // Record the rules of the first round.
+    let round_start_ts = ctx.accounts.vote_data.round_start_ts;
+    let slot = Clock::get()?.slot;
+    ctx.accounts.vote_data.open_round_config(round_start_ts, slot);
     Ok(())
 }
 
@@ -27,9 +141,101 @@ pub fn initialize_vote(
 /// **Business Logic:**
 /// - Allows the admin to progress the voting cycle to the next round.
 /// - Updates the `vote_round` state in the VoteManager.
-pub fn increment_vote_round(ctx: Context<Admin>) -> Result<()> {
-    // Increment the voting round.
-    ctx.accounts.vote_data.vote_round += 1;
+/// - Starts the new round's schedule at the current cluster time.
+/// - Emits a `RoundIncremented` event.
+pub fn increment_vote_round(ctx: Context<IncrementRound>) -> Result<()> {
+    // Increment the voting round, starting its schedule now.
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    ctx.accounts.vote_data.start_next_round(now, clock.slot)?;
+
+    emit!(RoundIncremented {
+        vote_manager: ctx.accounts.vote_data.key(),
+        round: ctx.accounts.vote_data.vote_round,
+        round_start_ts: now,
+    });
+
+    Ok(())
+}
+
+/// Proposes a new admin for the VoteManager.
+///
+/// **Business Logic:**
+/// - Only the current admin can propose a successor.
+/// - Updates the `pending_admin` state in the VoteManager.
+pub fn propose_vote_admin(ctx: Context<Admin>, new_admin: Pubkey) -> Result<()> {
+    // Record the proposed admin.
+    ctx.accounts.vote_data.pending_admin = Some(new_admin);
+    Ok(())
+}
+
+/// Accepts a pending admin proposal.
+///
+/// **Business Logic:**
+/// - Only the proposed admin can accept.
+/// - Moves `pending_admin` into `admin` in the VoteManager.
+pub fn accept_vote_admin(ctx: Context<Admin>) -> Result<()> {
+    // Hand over admin rights to the signer.
+    ctx.accounts.vote_data.admin = ctx.accounts.owner.key();
+    ctx.accounts.vote_data.pending_admin = None;
+    Ok(())
+}
+
+/// Sets the time box of the current voting round.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the schedule.
+/// - Updates the `round_start_ts` and `round_duration` state in the VoteManager.
+/// - A zero `round_duration` leaves the round open until the admin increments it.
+pub fn set_vote_round_schedule(
+    ctx: Context<Admin>,
+    round_start_ts: i64,
+    round_duration: i64,
+) -> Result<()> {
+    // Update the round schedule.
+    ctx.accounts.vote_data.round_start_ts = round_start_ts;
+    ctx.accounts.vote_data.round_duration = round_duration;
+    Ok(())
+}
+
+/// Opens the current round for voting.
+///
+/// **Business Logic:**
+/// - Moves the round from `Pending` to `Open`, freezing its project list.
+/// - Freezes the fee, weight curve and deadline in `round_config` and records the hash of the
+///   round's parameters, so the rules configured while the round was pending are the ones it is
+///   voted and finalized under.
+/// - Emits a `RoundStateChanged` event.
+pub fn open_vote_round(ctx: Context<Admin>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vote_manager = &mut ctx.accounts.vote_data;
+    vote_manager.round_state = RoundState::Open;
+    vote_manager.open_round_config(clock.unix_timestamp, clock.slot);
+
+    emit!(RoundStateChanged {
+        vote_manager: vote_manager.key(),
+        round: vote_manager.vote_round,
+        state: RoundState::Open,
+    });
+
+    Ok(())
+}
+
+/// Closes the current round to new votes.
+///
+/// **Business Logic:**
+/// - Moves the round from `Open` to `Closed`; the schedule and tallies are left untouched.
+/// - Emits a `RoundStateChanged` event.
+pub fn close_vote_round(ctx: Context<Admin>) -> Result<()> {
+    let vote_manager = &mut ctx.accounts.vote_data;
+    vote_manager.round_state = RoundState::Closed;
+
+    emit!(RoundStateChanged {
+        vote_manager: vote_manager.key(),
+        round: vote_manager.vote_round,
+        state: RoundState::Closed,
+    });
+
     Ok(())
 }
 
@@ -37,271 +243,11641 @@ pub fn increment_vote_round(ctx: Context<Admin>) -> Result<()> {
 ///
 /// **Business Logic:**
 /// - Only the admin can modify the voting fee.
-/// - Updates the `vote_fee` state in the VoteManager.
+/// - Updates the `vote_fee` state in the VoteManager; a round already open keeps charging the fee
+///   frozen in its `round_config`.
+/// - Emits a `FeeChanged` event.
 pub fn change_vote_fee(ctx: Context<Admin>, new_vote_fee: u64) -> Result<()> {
     // Update the voting fee.
+    let old_vote_fee = ctx.accounts.vote_data.vote_fee;
     ctx.accounts.vote_data.vote_fee = new_vote_fee;
+
+    emit!(FeeChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        old_vote_fee,
+        new_vote_fee,
+    });
+
     Ok(())
 }
 
-/// Adds a new project to the current voting round.
+/// Changes the lamports a vote paid in SOL costs.
 ///
 /// **Business Logic:**
-/// - Allows the admin to introduce new projects for voting.
-/// - Initializes the project's vote count and associates it with the current round and fee.
-pub fn add_vote_project(ctx: Context<NewVoteProject>, id: String) -> Result<()> {
-    // Initialize project data with reference to the VoteManager.
-    ctx.accounts.project_data.vote_manager = ctx.accounts.vote_manager.admin;
-    ctx.accounts.project_data.id = id;
-    ctx.accounts.project_data.vote_count = 0;
-    ctx.accounts.project_data.vote_round = ctx.accounts.vote_manager.vote_round;
+/// - Updates the `sol_vote_fee` state in the VoteManager; a round already open keeps charging the
+///   fee frozen in its `round_config`.
+/// - Emits a `SolFeeChanged` event.
+pub fn change_sol_vote_fee(ctx: Context<Admin>, new_sol_vote_fee: u64) -> Result<()> {
+    let old_sol_vote_fee = ctx.accounts.vote_data.sol_vote_fee;
+    ctx.accounts.vote_data.sol_vote_fee = new_sol_vote_fee;
+
+    emit!(SolFeeChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        old_sol_vote_fee,
+        new_sol_vote_fee,
+    });
 
     Ok(())
 }
 
-/// Facilitates the voting process for a project.
+/// Changes the maximum number of votes a voter may cast per round.
 ///
 /// **Business Logic:**
-/// - Ensures the vote is cast in the correct round.
-/// - Validates that the voter has sufficient tokens to cover the voting fee.
-/// - Updates the vote count for both the project and the voter.
-/// - Transfers the voting fee from the voter to the admin's fee account using Token-2022 CPI.
-pub fn _do_vote(ctx: Context<Voter>) -> Result<()> {
-    // Prepare the CPI context for transferring the voting fee.
-    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
-        mint: ctx.accounts.mint.to_account_info(),
-        from: ctx.accounts.token.to_account_info(),
-        to: ctx.accounts.admin_token_account.to_account_info(),
-        authority: ctx.accounts.signer.to_account_info(), /* The voter must authorize this
-                                                           * transfer. */
-    };
+/// - Only the admin can modify the limit.
+/// - Updates the `max_votes_per_round` state in the VoteManager.
+pub fn change_vote_max_votes(ctx: Context<Admin>, max_votes_per_round: u16) -> Result<()> {
+    // Update the per-round vote limit.
+    ctx.accounts.vote_data.max_votes_per_round = max_votes_per_round;
+    Ok(())
+}
 
-    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+/// Changes the maximum number of votes a voter may cast for a single project.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the cap.
+/// - Updates the `max_votes_per_project` state in the VoteManager; zero removes the cap.
+pub fn change_vote_max_votes_per_project(
+    ctx: Context<Admin>,
+    max_votes_per_project: u16,
+) -> Result<()> {
+    // Update the per-project vote cap.
+    ctx.accounts.vote_data.max_votes_per_project = max_votes_per_project;
+    Ok(())
+}
 
-    // Execute the transfer of the voting fee.
-    anchor_spl::token_interface::transfer_checked(
-        cpi_ctx,
-        ctx.accounts.vote_manager.vote_fee,
-        0, // No decimal places for the fee.
-    )?;
+/// Changes the maximum number of projects a round may hold.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the cap.
+/// - Updates the `max_projects` state in the VoteManager; zero removes the cap.
+/// - Projects already in the round stay, even above a lowered cap.
+pub fn change_vote_max_projects(ctx: Context<Admin>, max_projects: u16) -> Result<()> {
+    // Update the per-round project cap.
+    ctx.accounts.vote_data.max_projects = max_projects;
+    Ok(())
+}
 
-    // Increment vote counts for the project and the voter.
-    ctx.accounts.project.vote_count += 1;
-    ctx.accounts.voter_data.vote_count += 1;
-    ctx.accounts.voter_data.last_voted_round = ctx.accounts.project.vote_round;
-    ctx.accounts.voter_data.voter = ctx.accounts.signer.key();
-    ctx.accounts.voter_data.project_name = (*ctx.accounts.project.id).to_string();
+/// Sets how a round whose leading projects tie is decided.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the policy.
+/// - Updates the `tie_break` and `runoff_duration` state in the VoteManager.
+pub fn set_vote_tie_break(
+    ctx: Context<Admin>,
+    tie_break: TieBreakPolicy,
+    runoff_duration: i64,
+) -> Result<()> {
+    // Update the tie-break policy.
+    ctx.accounts.vote_data.tie_break = tie_break;
+    ctx.accounts.vote_data.runoff_duration = runoff_duration;
+    Ok(())
+}
 
+/// Sets the curve that turns a voter's token balance into vote weight.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the curve.
+/// - Updates the `weight_curve` state in the VoteManager.
+pub fn set_vote_weight_curve(ctx: Context<Admin>, weight_curve: WeightCurve) -> Result<()> {
+    // Update the weight curve.
+    ctx.accounts.vote_data.weight_curve = weight_curve;
     Ok(())
 }
 
-/// Defines the accounts required for administrative actions.
+/// Switches the commit–reveal voting mode.
 ///
 /// **Business Logic:**
-/// - Manages the VoteManager account using PDA derivation with seeds.
-/// - Ensures the admin is the signer and has authority over the VoteManager.
-#[derive(Accounts)]
-pub struct Admin<'info> {
-    #[account(
-            init_if_needed,
-            payer = owner,
-            space = 8 + VoteManager::INIT_SPACE,
-            seeds = [
-                b"vote_manager",
-                owner.key().as_ref()
-            ],
-            bump
-        )]
-    pub vote_data: Account<'info, VoteManager>, /* The VoteManager account managing the
-                                                 * voting process. */
-    #[account(mut)]
-    pub owner: Signer<'info>, // The admin's signer account.
-    pub system_program: Program<'info, System>, // Solana System program.
+/// - Only the admin can switch the mode.
+/// - Updates the `commit_reveal` and `reveal_duration` state in the VoteManager.
+pub fn set_vote_commit_reveal(
+    ctx: Context<Admin>,
+    commit_reveal: bool,
+    reveal_duration: i64,
+) -> Result<()> {
+    // Update the voting mode.
+    ctx.accounts.vote_data.commit_reveal = commit_reveal;
+    ctx.accounts.vote_data.reveal_duration = reveal_duration;
+    Ok(())
 }
 
-/// Defines the accounts required to add a new project for voting.
+/// Switches ranked-choice voting.
 ///
 /// **Business Logic:**
-/// - Initializes a new ProjectData account with PDA derivation ensuring uniqueness.
-/// - Associates the project with the current voting round and fee.
-#[derive(Accounts)]
-#[instruction(id:String)]
-pub struct NewVoteProject<'info> {
-    #[account(
-            // Initialize a new ProjectData account with unique PDA seeds.
-            init,
-            payer = owner,
-            space = 8 + ProjectData::INIT_SPACE,
-            seeds = [
-                id.as_bytes(),                         // Unique project identifier.
-                &vote_manager.vote_round.to_le_bytes(), // Current voting round to ensure uniqueness across rounds.
-                owner.key().as_ref()                    // Admin's public key for authorization.
-            ],
-            bump)]
-    pub project_data: Account<'info, ProjectData>, // The new project's data account.
-    #[account(
-            mut,
-            constraint = vote_manager.admin == owner.key() // Ensure only the admin can add projects.
-        )]
-    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
-    #[account(mut)]
-    pub owner: Signer<'info>, // The admin's signer account.
-    pub system_program: Program<'info, System>, // Solana System program.
+/// - Only the admin can switch the mode.
+/// - Updates the `ranked_choice` state in the VoteManager.
+pub fn set_vote_ranked_choice(ctx: Context<Admin>, ranked_choice: bool) -> Result<()> {
+    // Update the voting mode.
+    ctx.accounts.vote_data.ranked_choice = ranked_choice;
+    Ok(())
 }
 
-/// Defines the accounts required for casting a vote.
+/// Switches quadratic vote pricing.
 ///
 /// **Business Logic:**
-/// - Initializes a VoterData account to track the voter's activity in the current round.
-/// - Ensures the voter's token account is authorized and has sufficient balance.
-/// - Facilitates the transfer of voting fees from the voter's token account to the admin's fee
-///   account.
-#[derive(Accounts)]
-pub struct Voter<'info> {
-    #[account(
-            init_if_needed,
-            payer = signer,
-            space = 8 + VoterData::INIT_SPACE,
-            seeds = [
-                VOTER_NAMESPACE.as_bytes(),
-                &[project.vote_round, 1, 1, 1, 1], // Seed combining theround number with padding for uniqueness.
-                signer.key().as_ref(),     // Voter's public key to ensure unique PDA per voter per round.
-                project.id.as_ref(),
-            ],
-            bump,
-            constraint = project.vote_round == vote_manager.vote_round @ VoteError::WrongRound
-            )]
-    pub voter_data: Account<'info, VoterData>, // Tracks the voter's voting activity.
-    #[account(mut)]
-    pub signer: Signer<'info>, // The voter's signer account.
-    #[account(mut)]
-    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
-    #[account(
-            mut,
-            associated_token::token_program = token_program,
-            associated_token::mint = vote_manager.tk_mint,
-            associated_token::authority = vote_manager.admin,
-        )]
-    pub admin_token_account: InterfaceAccount<'info, TokenAccount>, /* Account which store
-                                                                     * initial supply of ttt
-                                                                     * and which is used by
-                                                                     * a program to deduct
-                                                                     * voting fee. */
-    #[account(mut)]
-    pub project: Account<'info, ProjectData>, // The project being voted for.
-    #[account(
-      mut,
-      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
-    )]
-    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
-    #[account(mut)]
-    pub token: InterfaceAccount<'info, TokenAccount>, /* Voter's token account holding ttt
-                                                       * tokens. */
-    pub token_program: Interface<'info, TokenInterface>, /* Token program interface for
-                                                          * token operations. */
-    pub system_program: Program<'info, System>, // Solana System program.
+/// - Only the admin can switch the pricing.
+/// - Updates the `quadratic_pricing` state in the VoteManager.
+pub fn set_vote_quadratic_pricing(ctx: Context<Admin>, quadratic_pricing: bool) -> Result<()> {
+    // Update the vote pricing.
+    ctx.accounts.vote_data.quadratic_pricing = quadratic_pricing;
+    Ok(())
 }
 
-/// Represents the VoteManager account responsible for managing voting rounds and projects.
+/// Sets the eligibility snapshot root and the snapshot's total eligible weight.
 ///
-/// **Fields:**
-/// - `admin`: The admin's public key with authority over the VoteManager.
-/// - `tk_mint`: The token mint associated with the governance token.
-/// - `tk_program`: The SPL Token program ID.
-/// - `vote_round`: The current active voting round.
-/// - `vote_fee`: The fee required to cast a vote.
-#[account]
-#[derive(InitSpace)]
-pub struct VoteManager {
-    pub admin: Pubkey,      // Admin's public key.
-    pub tk_mint: Pubkey,    // Token mint for governance token (ttt).
-    pub tk_program: Pubkey, // SPL Token program ID.
-    pub vote_round: u8,     // Current voting round.
-    pub vote_fee: u64,      // Fee required to cast a vote.
+/// **Business Logic:**
+/// - Only the admin can set the root.
+/// - Updates the `eligibility_root` state in the VoteManager; zero disables the snapshot and
+///   clears the `eligible_weight`.
+pub fn set_vote_eligibility_root(
+    ctx: Context<Admin>,
+    root: [u8; 32],
+    eligible_weight: u64,
+) -> Result<()> {
+    // Update the eligibility snapshot.
+    ctx.accounts.vote_data.eligibility_root = root;
+    ctx.accounts.vote_data.eligible_weight = if root == [0; 32] { 0 } else { eligible_weight };
+    Ok(())
 }
 
-/// Represents the ProjectData account for each project under governance.
+/// Switches vote-escrow mode.
 ///
-/// **Fields:**
-/// - `vote_manager`: Reference to the VoteManager's admin.
-/// - `id`: Unique identifier for the project.
-/// - `name`: Name of the project.
-/// - `vote_round`: The voting round in which the project is active.
-/// - `vote_count`: Total number of votes the project has received.
-/// - `vote_fee`: The fee associated with voting for this project.
-#[account]
-#[derive(InitSpace)]
-pub struct ProjectData {
-    pub vote_manager: Pubkey, // Reference to the VoteManager's admin.
-    #[max_len(PROJECT_ID_MAX_LEN)]
-    pub id: String, // Unique project identifier.
-    pub vote_round: u8,       // Voting round associated with the project.
-    pub vote_count: u64,      // Total votes received.
+/// **Business Logic:**
+/// - Only the admin can switch the mode.
+/// - Enabling requires a positive `max_lock_duration`, the lock earning full voting power.
+/// - Updates the `escrow_mode` and `max_lock_duration` state in the VoteManager.
+pub fn set_vote_escrow_mode(
+    ctx: Context<Admin>,
+    escrow_mode: bool,
+    max_lock_duration: i64,
+) -> Result<()> {
+    if escrow_mode {
+        require!(max_lock_duration > 0, VoteError::InvalidLockDuration);
+    }
+
+    // Update the voting mode.
+    ctx.accounts.vote_data.escrow_mode = escrow_mode;
+    ctx.accounts.vote_data.max_lock_duration = max_lock_duration;
+    Ok(())
 }
 
-/// Represents the VoterData account tracking a voter's activity.
+/// Pauses or resumes the VoteManager.
 ///
-/// **Fields:**
-/// - `voter`: The voter's public key.
-/// - `project_name`: The name of the project the voter last voted for.
-/// - `last_voted_round`: The last round in which the voter cast a vote.
-/// - `vote_count`: Total number of votes the voter has cast.
-#[account]
-#[derive(InitSpace)]
-pub struct VoterData {
-    pub voter: Pubkey, // Voter's public key.
-    #[max_len(50)]
-    pub project_name: String, // Name of the project voted for.
-    pub last_voted_round: u8, // Last round the voter participated in.
-    pub vote_count: u64, // Total votes cast by the voter.
+/// **Business Logic:**
+/// - Only the admin can pause.
+/// - Updates the `paused` state in the VoteManager.
+/// - Emits a `PausedChanged` event, so monitors notice the incident switch.
+pub fn set_vote_paused(ctx: Context<Admin>, paused: bool) -> Result<()> {
+    ctx.accounts.vote_data.paused = paused;
+
+    emit!(PausedChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        paused,
+    });
+
+    Ok(())
 }
 
-/// Defines custom error codes for the VoteProject program.
-/// Provides clear and descriptive error messages for various failure scenarios.
-#[error_code]
-pub enum VoteError {
-    #[msg("Vote program with admin: do not initialize!")]
-    NotAdmin, // Triggered when a non-admin attempts an admin-only action.
-    #[msg("Wrong vote round.")]
-    WrongRound, // Triggered when a vote is cast in an incorrect round.
-    #[msg("Admin account already initialized.")]
-    InsufficientTokens, // Triggered when a voter lacks sufficient tokens to cast a vote.
-    #[msg("ProjectIdTooLong")]
-    ProjectIdTooLong,
-    #[msg("IncorrectVoteFee")]
-    IncorrectVoteFee,
-    #[msg("WrongMint")]
-    WrongMint,
+/// Sets whether vote fees are collected by the treasury or burned.
+///
+/// **Business Logic:**
+/// - Only the admin can switch the mode, and only while the round is pending.
+/// - Updates the `fee_mode` in the VoteManager.
+/// - Emits a `FeeModeChanged` event.
+pub fn set_vote_fee_mode(ctx: Context<Admin>, fee_mode: FeeMode) -> Result<()> {
+    ctx.accounts.vote_data.fee_mode = fee_mode;
+
+    emit!(FeeModeChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        fee_mode,
+    });
+
+    Ok(())
 }
 
-/// Type which is used by CLI.
-#[derive(Accounts)]
-#[instruction(vote_fee:u64)]
-pub struct EnsureCanVote<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>, // The voter's signer account.
-    #[account(
-            mut,
-            associated_token::token_program = token_program,
-            associated_token::mint = mint,
-            associated_token::authority = admin_authority,
-        )]
-    pub admin_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
-    pub admin_authority: Signer<'info>, // The explicit authority for admin_token_account.
-    pub mint: InterfaceAccount<'info, Mint>, /* The governance
-                                         * token mint
-                                         * (ttt). */
-    #[account(
-           init_if_needed,
-           payer = signer,
-           associated_token::token_program = token_program,
-           associated_token::mint = mint,
-           associated_token::authority = signer,
-           constraint = user_ata.owner == signer.key(),
-           constraint = user_ata.mint == mint.key()
-        )]
-    pub user_ata: InterfaceAccount<'info, TokenAccount>,
-    pub token_program: Interface<'info, TokenInterface>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+/// Sets which wallets may vote.
+///
+/// **Business Logic:**
+/// - Only the admin can switch the mode, and only while the round is pending.
+/// - Updates the `access_mode` in the VoteManager.
+/// - Emits an `AccessModeChanged` event.
+pub fn set_vote_access_mode(ctx: Context<Admin>, access_mode: AccessMode) -> Result<()> {
+    ctx.accounts.vote_data.access_mode = access_mode;
+
+    emit!(AccessModeChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        access_mode,
+    });
+
+    Ok(())
+}
+
+/// Replaces the VoteManager's fee discount schedule.
+///
+/// **Business Logic:**
+/// - Only the admin can change it, and only while the round is pending.
+/// - Creates the FeeDiscountSchedule on first use, the admin paying its rent.
+/// - `tiers` must have ascending `min_balance` and non-decreasing discounts, so a larger balance
+///   never pays more; an empty list removes every discount.
+/// - Emits a `FeeDiscountsChanged` event.
+pub fn set_fee_discount_schedule(
+    ctx: Context<SetFeeDiscounts>,
+    tiers: Vec<DiscountTier>,
+) -> Result<()> {
+    let schedule = &mut ctx.accounts.fee_discounts;
+    schedule.vote_manager = ctx.accounts.vote_manager.key();
+    schedule.tiers = tiers.clone();
+    schedule.bump = ctx.bumps.fee_discounts;
+
+    emit!(FeeDiscountsChanged {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        tiers,
+    });
+
+    Ok(())
+}
+
+/// Replaces the names of the VoteManager's project categories.
+///
+/// **Business Logic:**
+/// - Only the admin can change them, and only while the round is pending.
+/// - Creates the CategoryRegistry on first use, the admin paying its rent.
+/// - `names[i]` names category code `i + 1`; code 0 stays uncategorized.
+/// - Emits a `CategoriesChanged` event.
+pub fn set_category_names(ctx: Context<SetCategories>, names: Vec<String>) -> Result<()> {
+    let registry = &mut ctx.accounts.category_registry;
+    registry.vote_manager = ctx.accounts.vote_manager.key();
+    registry.names = names.clone();
+    registry.bump = ctx.bumps.category_registry;
+
+    emit!(CategoriesChanged {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        names,
+    });
+
+    Ok(())
+}
+
+/// Sets whether retracted votes get their fee refunded.
+///
+/// **Business Logic:**
+/// - Only the admin can change it, and only while the round is pending.
+/// - Updates the `retract_refund` in the VoteManager.
+/// - Emits a `RetractRefundChanged` event.
+pub fn set_vote_retract_refund(ctx: Context<Admin>, retract_refund: bool) -> Result<()> {
+    ctx.accounts.vote_data.retract_refund = retract_refund;
+
+    emit!(RetractRefundChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        retract_refund,
+    });
+
+    Ok(())
+}
+
+/// Sets the minimum token holding age votes require.
+///
+/// **Business Logic:**
+/// - Updates the `min_holding_balance` and `min_holding_slots` state in the VoteManager; zero
+///   for both lifts the requirement.
+/// - Emits a `HoldingRequirementChanged` event.
+pub fn set_vote_holding_requirement(
+    ctx: Context<Admin>,
+    min_balance: u64,
+    min_holding_slots: u64,
+) -> Result<()> {
+    ctx.accounts.vote_data.min_holding_balance = min_balance;
+    ctx.accounts.vote_data.min_holding_slots = min_holding_slots;
+
+    emit!(HoldingRequirementChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        min_balance,
+        min_holding_slots,
+    });
+
+    Ok(())
+}
+
+/// Switches stealth voting.
+///
+/// **Business Logic:**
+/// - Only the admin can switch the mode.
+/// - Updates the `stealth_voting` state in the VoteManager.
+pub fn set_vote_stealth_voting(ctx: Context<Admin>, stealth_voting: bool) -> Result<()> {
+    // Update the voting mode.
+    ctx.accounts.vote_data.stealth_voting = stealth_voting;
+    Ok(())
+}
+
+/// Switches vote receipts and records their mint.
+///
+/// **Business Logic:**
+/// - Only the admin can switch receipts.
+/// - Rejects mints without the NonTransferable extension: receipts are souvenirs, not tokens.
+/// - Updates the `receipt_mint` and `vote_receipts` state in the VoteManager.
+pub fn set_vote_receipt_minting(ctx: Context<SetVoteReceipts>, vote_receipts: bool) -> Result<()> {
+    // Check the mint keeps receipts in the voter's wallet.
+    let receipt_mint = ctx.accounts.receipt_mint.to_account_info();
+    let data = receipt_mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    require!(
+        mint.get_extension::<NonTransferable>().is_ok(),
+        VoteError::ReceiptMintTransferable
+    );
+
+    // Update the receipt settings.
+    ctx.accounts.vote_data.receipt_mint = receipt_mint.key();
+    ctx.accounts.vote_data.vote_receipts = vote_receipts;
+    Ok(())
+}
+
+/// Sets how long rewards stay claimable after a round is finalized.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the window.
+/// - Updates the `reward_claim_window` state in the VoteManager.
+/// - Applies to reward vaults created afterwards; existing vaults keep their window.
+pub fn set_vote_reward_claim_window(ctx: Context<Admin>, reward_claim_window: i64) -> Result<()> {
+    // Update the claim window.
+    ctx.accounts.vote_data.reward_claim_window = reward_claim_window;
+    Ok(())
+}
+
+/// Sets the deposit, voting period and quorum of new proposals.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the proposal configuration.
+/// - Applies to proposals created afterwards; open proposals keep their deadline and quorum.
+pub fn set_vote_proposal_config(
+    ctx: Context<Admin>,
+    proposal_deposit: u64,
+    proposal_voting_period: i64,
+    proposal_quorum: u64,
+) -> Result<()> {
+    // Update the proposal configuration.
+    ctx.accounts.vote_data.proposal_deposit = proposal_deposit;
+    ctx.accounts.vote_data.proposal_voting_period = proposal_voting_period;
+    ctx.accounts.vote_data.proposal_quorum = proposal_quorum;
+    Ok(())
+}
+
+/// Adds a new project to the current voting round.
+///
+/// **Business Logic:**
+/// - Allows the admin to introduce new projects for voting.
+/// - Initializes the project's vote count and associates it with the current round and fee.
+/// - Emits a `ProjectAdded` event.
+pub fn add_vote_project(ctx: Context<NewVoteProject>, id: String) -> Result<()> {
+    // Initialize project data with reference to the VoteManager; the new account is zeroed, so
+    // the counters, flags, deposit and metadata start empty.
+    let mut project = ctx.accounts.project_data.load_init()?;
+    project.version = ProjectData::VERSION;
+    project.vote_manager = ctx.accounts.vote_manager.key();
+    project.set_id(&id);
+    project.payer = ctx.accounts.owner.key();
+    project.vote_round = ctx.accounts.vote_manager.vote_round;
+    project.created_slot = Clock::get()?.slot;
+    project.bump = ctx.bumps.project_data;
+
+    // Take a slot of the round.
+    ctx.accounts.vote_manager.count_projects(1)?;
+
+    emit!(ProjectAdded {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        project: ctx.accounts.project_data.key(),
+        id,
+        round: project.vote_round,
+    });
+
+    Ok(())
+}
+
+/// Adds several projects to the current voting round in one instruction.
+///
+/// **Business Logic:**
+/// - `remaining_accounts` are the ProjectData PDAs of `ids`, in the same order; each must match
+///   the seeds `add_project` derives for its id.
+/// - Creates each account with the signer as payer, signed with the PDA's seeds, and initializes
+///   it like `add_project`.
+/// - Takes a slot of the round per project, rejecting the whole batch past `max_projects`.
+/// - Emits a `ProjectAdded` event per project.
+pub fn add_vote_projects<'info>(
+    ctx: Context<'_, '_, 'info, 'info, NewVoteProjects<'info>>,
+    ids: Vec<String>,
+) -> Result<()> {
+    ctx.accounts.vote_manager.count_projects(ids.len())?;
+
+    let vote_manager = &ctx.accounts.vote_manager;
+    let owner = ctx.accounts.owner.key();
+    let admin = vote_manager.admin;
+    let round_bytes = vote_manager.vote_round.to_le_bytes();
+    let election = vote_manager.election_seed();
+    let space = 8 + std::mem::size_of::<ProjectData>();
+    let lamports = Rent::get()?.minimum_balance(space);
+    let slot = Clock::get()?.slot;
+
+    for (id, info) in ids.into_iter().zip(ctx.remaining_accounts) {
+        let (address, bump) = project_address(
+            &id,
+            vote_manager.vote_round,
+            &admin,
+            vote_manager.election_id,
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), address, VoteError::IncorrectProjectAccounts);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            PROJECT_SEED,
+            id.as_bytes(),
+            &round_bytes,
+            admin.as_ref(),
+            &election,
+            &[bump],
+        ]];
+        let cpi_accounts = anchor_lang::system_program::CreateAccount {
+            from: ctx.accounts.owner.to_account_info(),
+            to: info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        anchor_lang::system_program::create_account(cpi_ctx, lamports, space as u64, &crate::ID)?;
+
+        let mut project = ProjectData {
+            version: ProjectData::VERSION,
+            vote_manager: vote_manager.key(),
+            vote_round: vote_manager.vote_round,
+            payer: owner,
+            created_slot: slot,
+            bump,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        project.set_id(&id);
+        let mut data = info.try_borrow_mut_data()?;
+        project.try_serialize(&mut &mut data[..])?;
+
+        emit!(ProjectAdded {
+            vote_manager: project.vote_manager,
+            project: address,
+            id,
+            round: project.vote_round,
+        });
+    }
+
+    Ok(())
+}
+
+/// Sets the TTT deposit escrowed by permissionless project submissions.
+///
+/// **Business Logic:**
+/// - Only the admin can modify the deposit.
+/// - Applies to projects submitted afterwards; pending submissions keep their deposit.
+pub fn set_vote_project_deposit(ctx: Context<Admin>, project_deposit: u64) -> Result<()> {
+    // Update the project deposit.
+    ctx.accounts.vote_data.project_deposit = project_deposit;
+    Ok(())
+}
+
+/// Sets how approved submissions earn their deposit back.
+///
+/// **Business Logic:**
+/// - Every `tranche_votes` votes a project receives refund `tranche_votes / refund_votes` of its
+///   deposit, and `refund_votes` votes refund all of it; zero for both refunds deposits on
+///   approval.
+/// - Applies to projects submitted afterwards, which freeze it.
+/// - Emits a `DepositRefundChanged` event.
+pub fn set_vote_deposit_refund(
+    ctx: Context<Admin>,
+    tranche_votes: u64,
+    refund_votes: u64,
+) -> Result<()> {
+    ctx.accounts.vote_data.deposit_tranche_votes = tranche_votes;
+    ctx.accounts.vote_data.deposit_refund_votes = refund_votes;
+
+    emit!(DepositRefundChanged {
+        vote_manager: ctx.accounts.vote_data.key(),
+        tranche_votes,
+        refund_votes,
+    });
+
+    Ok(())
+}
+
+/// Submits a project to the current voting round for the admin to review.
+///
+/// **Business Logic:**
+/// - Creates the project at the same address `add_project` would, marked `pending` so it rejects
+///   votes until approved.
+/// - Escrows `project_deposit` TTT from the submitter in the treasury, and freezes the VoteManager's
+///   deposit refund tranches for it.
+/// - Emits a `ProjectSubmitted` event.
+pub fn submit_vote_project<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitProject<'info>>,
+    id: String,
+) -> Result<()> {
+    let deposit = ctx.accounts.vote_manager.project_deposit;
+
+    // Initialize project data with reference to the VoteManager; the new account is zeroed, so
+    // the counters and metadata start empty.
+    let mut project = ctx.accounts.project_data.load_init()?;
+    project.version = ProjectData::VERSION;
+    project.vote_manager = ctx.accounts.vote_manager.key();
+    project.set_id(&id);
+    project.payer = ctx.accounts.submitter.key();
+    project.vote_round = ctx.accounts.vote_manager.vote_round;
+    project.pending = 1;
+    project.deposit = deposit;
+    project.tranche_votes = ctx.accounts.vote_manager.deposit_tranche_votes;
+    project.refund_votes = ctx.accounts.vote_manager.deposit_refund_votes;
+    project.created_slot = Clock::get()?.slot;
+    project.bump = ctx.bumps.project_data;
+
+    // Escrow the deposit in the treasury.
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.submitter_token_account.to_account_info(),
+        to: ctx.accounts.treasury_token_account.to_account_info(),
+        authority: ctx.accounts.submitter.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        deposit,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_escrowed =
+        treasury.total_escrowed.checked_add(deposit).ok_or(VoteError::Overflow)?;
+
+    emit!(ProjectSubmitted {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        project: ctx.accounts.project_data.key(),
+        id,
+        round: project.vote_round,
+        submitter: ctx.accounts.submitter.key(),
+        deposit,
+    });
+
+    Ok(())
+}
+
+/// Activates a submitted project and refunds its deposit.
+///
+/// **Business Logic:**
+/// - Clears `pending`, so the project accepts votes like one added by the admin.
+/// - Returns the escrowed deposit to the submitter, signed by the Treasury PDA, unless the project
+///   was submitted with refund tranches: its deposit then stays escrowed as a listing bond, which
+///   `claim_deposit_tranche` refunds as votes come in.
+/// - Takes a slot of the round; pending submissions do not count against `max_projects`.
+/// - Emits a `ProjectAdded` event.
+pub fn approve_vote_project<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ApproveProject<'info>>,
+) -> Result<()> {
+    ctx.accounts.vote_manager.count_projects(1)?;
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.pending = 0;
+
+    if project.refund_votes == 0 {
+        let deposit = project.deposit;
+        refund_project_deposit(
+            &ctx.accounts.treasury,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.submitter_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.vote_manager.key(),
+            ctx.remaining_accounts,
+            deposit,
+        )?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_escrowed =
+            treasury.total_escrowed.checked_sub(deposit).ok_or(VoteError::Overflow)?;
+        project.deposit = 0;
+    }
+
+    emit!(ProjectAdded {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        project: ctx.accounts.project.key(),
+        id: project.id().to_string(),
+        round: project.vote_round,
+    });
+
+    Ok(())
+}
+
+/// Rejects a submitted project, refunding or slashing its deposit.
+///
+/// **Business Logic:**
+/// - With `slash`, the deposit stays in the treasury as collected revenue; otherwise it is
+///   returned to the submitter.
+/// - Closing is done by the `close = payer` constraint, returning the rent to the submitter.
+/// - Emits a `ProjectRejected` event.
+pub fn reject_vote_project<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RejectProject<'info>>,
+    slash: bool,
+) -> Result<()> {
+    let project = *ctx.accounts.project.load()?;
+    let deposit = project.deposit;
+    if slash {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected =
+            treasury.total_collected.checked_add(deposit).ok_or(VoteError::Overflow)?;
+    } else {
+        refund_project_deposit(
+            &ctx.accounts.treasury,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.submitter_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.vote_manager.key(),
+            ctx.remaining_accounts,
+            deposit,
+        )?;
+    }
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_escrowed =
+        treasury.total_escrowed.checked_sub(deposit).ok_or(VoteError::Overflow)?;
+
+    emit!(ProjectRejected {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        project: ctx.accounts.project.key(),
+        id: project.id().to_string(),
+        deposit,
+        slashed: slash,
+    });
+
+    Ok(())
+}
+
+/// Refunds the tranches of a listing bond the project's votes earned since the last claim.
+///
+/// **Business Logic:**
+/// - Transfers `amount` from the treasury to the submitter, signed by the Treasury PDA, and moves
+///   it from the project's `deposit` to its `deposit_refunded`.
+/// - Emits a `DepositTrancheClaimed` event.
+pub fn claim_vote_deposit_tranche<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimDepositTranche<'info>>,
+    amount: u64,
+) -> Result<()> {
+    refund_project_deposit(
+        &ctx.accounts.treasury,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.submitter_token_account,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.vote_manager.key(),
+        ctx.remaining_accounts,
+        amount,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_escrowed =
+        treasury.total_escrowed.checked_sub(amount).ok_or(VoteError::Overflow)?;
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.deposit = project.deposit.checked_sub(amount).ok_or(VoteError::Overflow)?;
+    project.deposit_refunded =
+        project.deposit_refunded.checked_add(amount).ok_or(VoteError::Overflow)?;
+
+    emit!(DepositTrancheClaimed {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        project: ctx.accounts.project.key(),
+        id: project.id().to_string(),
+        submitter: ctx.accounts.submitter.key(),
+        amount,
+        refunded: project.deposit_refunded,
+        remaining: project.deposit,
+    });
+
+    Ok(())
+}
+
+/// Transfers a project's escrowed `deposit` from the treasury back to its submitter.
+#[allow(clippy::too_many_arguments)]
+fn refund_project_deposit<'info>(
+    treasury: &Account<'info, Treasury>,
+    treasury_token_account: &InterfaceAccount<'info, TokenAccount>,
+    submitter_token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    vote_manager_key: &Pubkey,
+    hook_accounts: &[AccountInfo<'info>],
+    deposit: u64,
+) -> Result<()> {
+    let bump = treasury.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, vote_manager_key.as_ref(), &[bump]]];
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: mint.to_account_info(),
+        from: treasury_token_account.to_account_info(),
+        to: submitter_token_account.to_account_info(),
+        authority: treasury.to_account_info(), // The treasury PDA signs the refund.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(cpi_ctx, hook_accounts, deposit, mint.decimals)
+}
+
+/// Removes a project that has not received any votes.
+///
+/// **Business Logic:**
+/// - Closing is done by the `close = owner` constraint, returning the rent to the admin.
+pub fn remove_vote_project(_ctx: Context<RemoveProject>) -> Result<()> {
+    Ok(())
+}
+
+/// Sets the display metadata of a project.
+///
+/// **Business Logic:**
+/// - Writes `name` and `uri` into the project's fixed-size fields, which fit the longest ones
+///   `update_project_metadata` accepts.
+/// - Emits a `ProjectMetadataUpdated` event.
+pub fn update_vote_project_metadata(
+    ctx: Context<UpdateProjectMetadata>,
+    name: String,
+    uri: String,
+    category: u8,
+) -> Result<()> {
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.set_metadata(&name, &uri);
+    project.category = category;
+
+    emit!(ProjectMetadataUpdated {
+        vote_manager: project.vote_manager,
+        project: ctx.accounts.project.key(),
+        name,
+        uri,
+        category,
+    });
+
+    Ok(())
+}
+
+/// Disqualifies a project so it can no longer receive votes.
+///
+/// **Business Logic:**
+/// - Marks the project as `disqualified`; its account and vote history are kept.
+/// - Removes the project's votes and weight from the running round tallies. If it was leading,
+///   the leader is cleared and `finalize_round` recounts it from the round's projects.
+/// - Its voters stay counted among the round's participants, having taken part in it.
+pub fn disqualify_vote_project(ctx: Context<DisqualifyProject>) -> Result<()> {
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.disqualified = 1;
+
+    // Only projects of the current round contribute to the running tallies.
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    if project.vote_round == vote_manager.vote_round {
+        vote_manager.round_total_votes = vote_manager
+            .round_total_votes
+            .checked_sub(project.vote_count)
+            .ok_or(VoteError::Overflow)?;
+        vote_manager.round_total_weight = vote_manager
+            .round_total_weight
+            .checked_sub(project.vote_weight)
+            .ok_or(VoteError::Overflow)?;
+        if vote_manager.round_leader == ctx.accounts.project.key() {
+            vote_manager.clear_leader();
+        }
+    }
+
+    Ok(())
+}
+
+/// Refunds the fees a voter paid for a disqualified project.
+///
+/// **Business Logic:**
+/// - Transfers the record's `fees_paid` from the treasury's fee account back to the voter, signed
+///   by the Treasury PDA, and its `sol_fees_paid` from the Treasury PDA's lamports.
+/// - Flags the record as `refunded`, so the refund cannot be claimed twice.
+/// - Emits a `VoteRefunded` event.
+pub fn claim_vote_refund<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimRefund<'info>>,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let mut voter_data = ctx.accounts.voter_data.load_mut()?;
+    let amount = voter_data.fees_paid;
+    let bump = ctx.accounts.treasury.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, vote_manager_key.as_ref(), &[bump]]];
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.treasury_token_account.to_account_info(),
+        to: ctx.accounts.token.to_account_info(),
+        authority: ctx.accounts.treasury.to_account_info(), // The treasury PDA signs the refund.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // The program owns the Treasury PDA, so SOL fees are moved directly rather than by CPI.
+    let lamports = voter_data.sol_fees_paid;
+    ctx.accounts.treasury.sub_lamports(lamports)?;
+    ctx.accounts.signer.add_lamports(lamports)?;
+
+    // A refunded fee no longer counts as collected.
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_sub(amount)
+        .ok_or(VoteError::Overflow)?;
+    treasury.total_collected_sol = treasury
+        .total_collected_sol
+        .checked_sub(lamports)
+        .ok_or(VoteError::Overflow)?;
+    // Nor towards the reward pool while the project's round is running, nor as owed once it ended.
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    if ctx.accounts.project.load()?.vote_round == vote_manager.vote_round {
+        vote_manager.round_fees = vote_manager.round_fees.saturating_sub(amount);
+    } else {
+        vote_manager.owed_fees = vote_manager.owed_fees.saturating_sub(amount);
+    }
+    voter_data.refunded = 1;
+
+    emit!(VoteRefunded {
+        vote_manager: vote_manager_key,
+        project: ctx.accounts.project.key(),
+        voter: ctx.accounts.signer.key(),
+        amount,
+        lamports,
+    });
+
+    Ok(())
+}
+
+/// Pays a voter of a finalized round's winning project their share of the round's fees.
+///
+/// **Business Logic:**
+/// - The share is `RoundResult::reward_share` of the record's weight, transferred from the
+///   treasury's fee account to the voter, signed by the Treasury PDA.
+/// - Flags the record as `reward_claimed`, so the reward cannot be claimed twice.
+/// - Emits a `VoteRewardClaimed` event.
+pub fn claim_vote_reward<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimReward<'info>>,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let mut voter_data = ctx.accounts.voter_data.load_mut()?;
+    let amount = voter_data.reward_due(&ctx.accounts.round_result);
+    let bump = ctx.accounts.treasury.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, vote_manager_key.as_ref(), &[bump]]];
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.treasury_token_account.to_account_info(),
+        to: ctx.accounts.token.to_account_info(),
+        authority: ctx.accounts.treasury.to_account_info(), // The treasury PDA signs the reward.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_rewarded = treasury
+        .total_rewarded
+        .checked_add(amount)
+        .ok_or(VoteError::Overflow)?;
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    vote_manager.owed_fees = vote_manager.owed_fees.saturating_sub(amount);
+    let round_result = &mut ctx.accounts.round_result;
+    round_result.rewards_claimed = round_result
+        .rewards_claimed
+        .checked_add(amount)
+        .ok_or(VoteError::Overflow)?;
+    voter_data.reward_claimed = 1;
+
+    emit!(VoteRewardClaimed {
+        vote_manager: vote_manager_key,
+        round: ctx.accounts.round_result.round,
+        project: ctx.accounts.round_result.winner,
+        voter: ctx.accounts.signer.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Retracts the voter's votes for a project of the open round.
+///
+/// **Business Logic:**
+/// - A record already retracted is left as it is, so a retried retraction succeeds without
+///   counting anything twice.
+/// - Removes the record's votes and weight from the project and the running round tallies, and
+///   the voter from the participants when these were their only votes of the round. If the
+///   project was leading, the leader is cleared and `finalize_round` recounts it.
+/// - With `retract_refund`, transfers the record's `fees_paid` from the treasury's fee account
+///   back to the voter, signed by the Treasury PDA, and its `sol_fees_paid` from the Treasury
+///   PDA's lamports; otherwise the treasury keeps them.
+/// - Clears the record's votes, weight and fees and flags it `retracted`; voting again reuses it.
+/// - Moves the project down the round's leaderboard, if the round has one. It stays listed, so
+///   a project below the top ten only enters it again with its next vote.
+/// - Emits a `VoteRetracted` event.
+pub fn retract_cast_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RetractVote<'info>>,
+) -> Result<()> {
+    let mut voter_data = ctx.accounts.voter_data.load_mut()?;
+    if voter_data.retracted != 0 {
+        return Ok(());
+    }
+
+    let votes = voter_data.votes_for_project;
+    let weight = voter_data.vote_weight;
+    let fees_paid = voter_data.fees_paid;
+    let sol_fees_paid = voter_data.sol_fees_paid;
+
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.vote_count = project
+        .vote_count
+        .checked_sub(u64::from(votes))
+        .ok_or(VoteError::Overflow)?;
+    project.vote_weight = project.vote_weight.checked_sub(weight).ok_or(VoteError::Overflow)?;
+    project.voter_records = project.voter_records.checked_sub(1).ok_or(VoteError::Overflow)?;
+    // The voter leaves the round's participants when this was their last vote in it.
+    let left_round = ctx.accounts.round_votes.votes == u64::from(votes);
+    ctx.accounts.vote_manager.retract_vote(
+        ctx.accounts.project.key(),
+        u64::from(votes),
+        weight,
+        left_round,
+    )?;
+    update_leaderboard(
+        &ctx.accounts.vote_manager,
+        ctx.accounts.leaderboard.as_ref(),
+        ctx.accounts.project.key(),
+        &project,
+    )?;
+
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let refunded = if ctx.accounts.vote_manager.retract_refund && fees_paid > 0 {
+        let bump = ctx.accounts.treasury.bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, vote_manager_key.as_ref(), &[bump]]];
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.treasury_token_account.to_account_info(),
+            to: ctx.accounts.token.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(), // The treasury PDA signs the refund.
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        transfer_checked_with_hook(
+            cpi_ctx,
+            ctx.remaining_accounts,
+            fees_paid,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        // A refunded fee no longer counts as collected, nor towards the round's reward pool.
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = treasury
+            .total_collected
+            .checked_sub(fees_paid)
+            .ok_or(VoteError::Overflow)?;
+        let vote_manager = &mut ctx.accounts.vote_manager;
+        vote_manager.round_fees = vote_manager.round_fees.saturating_sub(fees_paid);
+        fees_paid
+    } else {
+        0
+    };
+    let refunded_lamports = if ctx.accounts.vote_manager.retract_refund && sol_fees_paid > 0 {
+        // The program owns the Treasury PDA, so SOL fees are moved directly rather than by CPI.
+        ctx.accounts.treasury.sub_lamports(sol_fees_paid)?;
+        ctx.accounts.signer.add_lamports(sol_fees_paid)?;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected_sol = treasury
+            .total_collected_sol
+            .checked_sub(sol_fees_paid)
+            .ok_or(VoteError::Overflow)?;
+        sol_fees_paid
+    } else {
+        0
+    };
+
+    // The retracted votes no longer count towards the voter's per-round limit.
+    let round_votes = &mut ctx.accounts.round_votes;
+    round_votes.votes = round_votes
+        .votes
+        .checked_sub(u64::from(votes))
+        .ok_or(VoteError::Overflow)?;
+
+    voter_data.vote_count = 0;
+    voter_data.votes_for_project = 0;
+    voter_data.vote_weight = 0;
+    voter_data.fees_paid = 0;
+    voter_data.sol_fees_paid = 0;
+    voter_data.retracted = 1;
+
+    emit!(VoteRetracted {
+        vote_manager: vote_manager_key,
+        project: ctx.accounts.project.key(),
+        voter: ctx.accounts.signer.key(),
+        round: project.vote_round,
+        votes,
+        weight,
+        refunded,
+        refunded_lamports,
+    });
+
+    Ok(())
+}
+
+/// Facilitates the voting process for a project.
+///
+/// **Business Logic:**
+/// - Ensures the vote is cast in the correct round.
+/// - Validates that the voter has sufficient tokens to cover the voting fee.
+/// - Updates the vote count for both the project and the voter.
+/// - Weighs the vote with `fixed_weight` when the voter proved a snapshot entry or holds a stake
+///   position in escrow mode, on the `weight_curve` otherwise.
+/// - Prices the vote with `VoteManager::vote_price` on the voter's prior votes for the project,
+///   and transfers that fee from the voter to the treasury's fee account using Token-2022 CPI,
+///   or burns it from the voter's account under `FeeMode::Burn`.
+/// - A vote paid in `FeeCurrency::Sol` is priced on the `sol_vote_fee` instead and transfers the
+///   lamports to the Treasury PDA through the System Program, recorded in the VoterData's
+///   `sol_fees_paid`.
+/// - With the VoteManager's FeeDiscountSchedule passed, either price is reduced by the discount
+///   of the highest tier the voter's token balance reaches.
+/// - Moves the project to its new standing on the round's leaderboard, if the round has one.
+/// - Emits a `VoteCast` event.
+pub fn _do_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Voter<'info>>,
+    fixed_weight: Option<u64>,
+    fee_currency: FeeCurrency,
+) -> Result<()> {
+    // Take the snapshot or staked weight, or evaluate it on the balance held before the fee is
+    // deducted.
+    let weight = fixed_weight.unwrap_or_else(|| {
+        ctx.accounts
+            .vote_manager
+            .round_config
+            .weight_curve
+            .weight(ctx.accounts.token.amount)
+    });
+
+    // Execute the transfer of the voting fee, priced on the voter's prior votes and discounted
+    // on the balance they hold.
+    let mut voter_data = load_or_init(&ctx.accounts.voter_data)?;
+    let mut price = ctx
+        .accounts
+        .vote_manager
+        .vote_price_in(fee_currency, voter_data.votes_for_project)?;
+    if let Some(fee_discounts) = &ctx.accounts.fee_discounts {
+        price = fee_discounts.discounted(price, ctx.accounts.token.amount);
+    }
+    let (collected, collected_sol) = match fee_currency {
+        FeeCurrency::Ttt => {
+            let collected = charge_vote_fee(
+                ctx.accounts.vote_manager.fee_mode,
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                &ctx.accounts.token,
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.signer,
+                ctx.remaining_accounts,
+                price,
+            )?;
+            (collected, 0)
+        }
+        FeeCurrency::Sol => {
+            // The Treasury PDA holds SOL fees in its own lamports, they are never burned.
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, price)?;
+            (0, price)
+        }
+    };
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+    treasury.total_collected_sol = treasury
+        .total_collected_sol
+        .checked_add(collected_sol)
+        .ok_or(VoteError::Overflow)?;
+
+    let mut project = ctx.accounts.project.load_mut()?;
+    count_vote(
+        &mut ctx.accounts.vote_manager,
+        ctx.accounts.project.key(),
+        &mut project,
+        &mut voter_data,
+        &mut ctx.accounts.round_votes,
+        weight,
+        collected,
+    )?;
+    voter_data.sol_fees_paid = voter_data
+        .sol_fees_paid
+        .checked_add(collected_sol)
+        .ok_or(VoteError::Overflow)?;
+    voter_data.voter = ctx.accounts.signer.key();
+    voter_data.blinded_voter = [0; 32];
+    voter_data.bump = ctx.bumps.voter_data;
+    ctx.accounts.round_votes.bump = ctx.bumps.round_votes;
+    update_leaderboard(
+        &ctx.accounts.vote_manager,
+        ctx.accounts.leaderboard.as_ref(),
+        ctx.accounts.project.key(),
+        &project,
+    )?;
+
+    // Lock the voter's TTT for the rest of the round, once the mint's transfer hook is on.
+    ctx.accounts.vote_lock.round = project.vote_round;
+    ctx.accounts.vote_lock.bump = ctx.bumps.vote_lock;
+
+    emit!(VoteCast {
+        voter: ctx.accounts.signer.key(),
+        project: ctx.accounts.project.key(),
+        round: project.vote_round,
+        weight,
+    });
+
+    if ctx.accounts.vote_manager.vote_receipts {
+        // In escrow mode the StakePosition precedes the receipt accounts.
+        let receipt_accounts = if ctx.accounts.vote_manager.escrow_mode {
+            ctx.remaining_accounts.get(1..).unwrap_or_default()
+        } else {
+            ctx.remaining_accounts
+        };
+        mint_vote_receipt(
+            &ctx.accounts.vote_manager,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            receipt_accounts,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Records a counted or retracted vote on the round's leaderboard.
+///
+/// **Business Logic:**
+/// - Sets the project's entry to its current `vote_count` and `vote_weight` and moves it to its
+///   rank.
+/// - Once the round has a leaderboard, votes that do not pass it fail with
+///   `LeaderboardMissing`, so the standings never miss one.
+pub fn update_leaderboard(
+    vote_manager: &VoteManager,
+    leaderboard: Option<&AccountLoader<Leaderboard>>,
+    project_key: Pubkey,
+    project: &ProjectData,
+) -> Result<()> {
+    let Some(leaderboard) = leaderboard else {
+        require!(
+            !vote_manager.round_leaderboard,
+            VoteError::LeaderboardMissing
+        );
+        return Ok(());
+    };
+    leaderboard.load_mut()?.record(
+        project_key,
+        project.id(),
+        project.vote_count,
+        project.vote_weight,
+    );
+    Ok(())
+}
+
+/// Creates the leaderboard of the current round.
+///
+/// **Business Logic:**
+/// - Records the VoteManager and round the standings belong to, with no entries yet.
+/// - Flags the round as having a leaderboard, so every vote of the round updates it.
+/// - Emits a `LeaderboardCreated` event.
+pub fn init_round_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    let mut leaderboard = ctx.accounts.leaderboard.load_init()?;
+    leaderboard.vote_manager = vote_manager.key();
+    leaderboard.round = vote_manager.vote_round;
+    leaderboard.len = 0;
+    leaderboard.bump = ctx.bumps.leaderboard;
+    vote_manager.round_leaderboard = true;
+
+    emit!(LeaderboardCreated {
+        vote_manager: vote_manager.key(),
+        leaderboard: ctx.accounts.leaderboard.key(),
+        round: vote_manager.vote_round,
+    });
+
+    Ok(())
+}
+
+/// Mints a vote receipt to `voter` through the ttt_token program.
+///
+/// **Business Logic:**
+/// - `receipt_accounts` are, in order: the receipt mint, the receipt authority PDA, the voter's
+///   receipt account, the Token-2022 program, the Associated Token program and the ttt_token
+///   program; the transfer hook's accounts may follow.
+/// - Signs as the receipt authority, which ttt_token checks is the receipt mint's authority.
+fn mint_vote_receipt<'info>(
+    vote_manager: &Account<'info, VoteManager>,
+    voter: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    receipt_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let [
+        receipt_mint,
+        receipt_authority,
+        receipt_token_account,
+        token_program,
+        associated_token_program,
+        ttt_token_program,
+        ..
+    ] = receipt_accounts
+    else {
+        return err!(VoteError::IncorrectReceiptAccounts);
+    };
+    require_keys_eq!(
+        receipt_mint.key(),
+        vote_manager.receipt_mint,
+        VoteError::IncorrectReceiptAccounts
+    );
+    let vote_manager_key = vote_manager.key();
+    let (authority, authority_bump) = receipt_authority_address(&vote_manager_key, &crate::ID);
+    require_keys_eq!(
+        receipt_authority.key(),
+        authority,
+        VoteError::IncorrectReceiptAccounts
+    );
+    // The receipt authority's signature is only extended to the real ttt_token program.
+    let ttt_token_program = Program::<TokenExtensions>::try_from(ttt_token_program)?;
+
+    let authority_seeds: &[&[&[u8]]] = &[&[
+        RECEIPT_AUTHORITY_SEED,
+        vote_manager_key.as_ref(),
+        &[authority_bump],
+    ]];
+    let cpi_accounts = ttt_token::cpi::accounts::MintVoteReceipt {
+        voter: voter.to_account_info(),
+        authority: receipt_authority.clone(),
+        receipt_mint: receipt_mint.clone(),
+        receipt_token_account: receipt_token_account.clone(),
+        system_program: system_program.to_account_info(),
+        associated_token_program: associated_token_program.clone(),
+        token_program: token_program.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ttt_token_program.to_account_info(),
+        cpi_accounts,
+        authority_seeds,
+    );
+    ttt_token::cpi::mint_vote_receipt(cpi_ctx)
+}
+
+/// Casts a vote recorded under a blinded voter id instead of the wallet key.
+///
+/// **Business Logic:**
+/// - Same fee transfer and tallies as `do_vote`.
+/// - The VoterData account stores `blinded_voter` and a default `voter`, so the wallet behind a
+///   record cannot be read from account data.
+/// - Emits a `VoteCast` event carrying the blinded id in place of the voter.
+pub fn _stealth_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StealthVoter<'info>>,
+    blinded_voter: [u8; 32],
+) -> Result<()> {
+    // Evaluate the vote weight on the balance held before the fee is deducted.
+    let weight = ctx
+        .accounts
+        .vote_manager
+        .round_config
+        .weight_curve
+        .weight(ctx.accounts.token.amount);
+
+    // Execute the transfer of the voting fee, priced on the voter's prior votes.
+    let mut voter_data = load_or_init(&ctx.accounts.voter_data)?;
+    let price = ctx
+        .accounts
+        .vote_manager
+        .vote_price(voter_data.votes_for_project)?;
+    let collected = charge_vote_fee(
+        ctx.accounts.vote_manager.fee_mode,
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.signer,
+        ctx.remaining_accounts,
+        price,
+    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+
+    let mut project = ctx.accounts.project.load_mut()?;
+    count_vote(
+        &mut ctx.accounts.vote_manager,
+        ctx.accounts.project.key(),
+        &mut project,
+        &mut voter_data,
+        &mut ctx.accounts.round_votes,
+        weight,
+        collected,
+    )?;
+    voter_data.voter = Pubkey::default();
+    voter_data.blinded_voter = blinded_voter;
+    voter_data.bump = ctx.bumps.voter_data;
+    ctx.accounts.round_votes.bump = ctx.bumps.round_votes;
+    update_leaderboard(
+        &ctx.accounts.vote_manager,
+        ctx.accounts.leaderboard.as_ref(),
+        ctx.accounts.project.key(),
+        &project,
+    )?;
+
+    emit!(VoteCast {
+        voter: Pubkey::new_from_array(blinded_voter),
+        project: ctx.accounts.project.key(),
+        round: project.vote_round,
+        weight,
+    });
+
+    Ok(())
+}
+
+/// Casts a vote whose VoterData rent is reimbursed from the sponsor vault.
+///
+/// **Business Logic:**
+/// - Same fee transfer and tallies as `do_vote`.
+/// - When the vote creates the VoterData account, and the voter's SponsorRecord with it, their
+///   rent is paid back to the voter from the sponsor vault.
+/// - Rejects the vote if the reimbursement would exceed the vault's `max_per_wallet` or its
+///   balance above rent exemption.
+/// - Emits a `VoteCast` event, and a `VoteSponsored` event when rent was reimbursed.
+pub fn _sponsored_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SponsoredVoter<'info>>,
+) -> Result<()> {
+    // Evaluate the vote weight on the balance held before the fee is deducted.
+    let weight = ctx
+        .accounts
+        .vote_manager
+        .round_config
+        .weight_curve
+        .weight(ctx.accounts.token.amount);
+
+    // Work out the rent paid for accounts this vote creates.
+    let rent = Rent::get()?;
+    let mut reimbursement: u64 = 0;
+    let mut voter_data = load_or_init(&ctx.accounts.voter_data)?;
+    if voter_data.vote_count == 0 {
+        reimbursement = rent.minimum_balance(8 + std::mem::size_of::<VoterData>());
+    }
+    let sponsor_record = &mut ctx.accounts.sponsor_record;
+    if sponsor_record.wallet == Pubkey::default() {
+        sponsor_record.vote_manager = ctx.accounts.vote_manager.key();
+        sponsor_record.wallet = ctx.accounts.signer.key();
+        sponsor_record.reimbursed = 0;
+        sponsor_record.bump = ctx.bumps.sponsor_record;
+        reimbursement = reimbursement
+            .checked_add(rent.minimum_balance(8 + SponsorRecord::INIT_SPACE))
+            .ok_or(VoteError::Overflow)?;
+    }
+
+    // Execute the transfer of the voting fee, priced on the voter's prior votes.
+    let price = ctx
+        .accounts
+        .vote_manager
+        .vote_price(voter_data.votes_for_project)?;
+    let collected = charge_vote_fee(
+        ctx.accounts.vote_manager.fee_mode,
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.signer,
+        ctx.remaining_accounts,
+        price,
+    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+
+    let mut project = ctx.accounts.project.load_mut()?;
+    count_vote(
+        &mut ctx.accounts.vote_manager,
+        ctx.accounts.project.key(),
+        &mut project,
+        &mut voter_data,
+        &mut ctx.accounts.round_votes,
+        weight,
+        collected,
+    )?;
+    voter_data.voter = ctx.accounts.signer.key();
+    voter_data.blinded_voter = [0; 32];
+    voter_data.bump = ctx.bumps.voter_data;
+    ctx.accounts.round_votes.bump = ctx.bumps.round_votes;
+    update_leaderboard(
+        &ctx.accounts.vote_manager,
+        ctx.accounts.leaderboard.as_ref(),
+        ctx.accounts.project.key(),
+        &project,
+    )?;
+
+    emit!(VoteCast {
+        voter: ctx.accounts.signer.key(),
+        project: ctx.accounts.project.key(),
+        round: project.vote_round,
+        weight,
+    });
+
+    if reimbursement == 0 {
+        return Ok(());
+    }
+
+    // Reimburse the rent within the per-wallet limit and the vault's spare balance.
+    let reimbursed = ctx
+        .accounts
+        .sponsor_record
+        .reimbursed
+        .checked_add(reimbursement)
+        .ok_or(VoteError::Overflow)?;
+    require!(
+        reimbursed <= ctx.accounts.sponsor_vault.max_per_wallet,
+        VoteError::SponsorLimitReached
+    );
+    require!(
+        reimbursement <= sponsor_vault_available(&ctx.accounts.sponsor_vault)?,
+        VoteError::InsufficientSponsorFunds
+    );
+
+    ctx.accounts.sponsor_vault.sub_lamports(reimbursement)?;
+    ctx.accounts.signer.add_lamports(reimbursement)?;
+    ctx.accounts.sponsor_record.reimbursed = reimbursed;
+    let sponsor_vault = &mut ctx.accounts.sponsor_vault;
+    sponsor_vault.total_reimbursed = sponsor_vault
+        .total_reimbursed
+        .checked_add(reimbursement)
+        .ok_or(VoteError::Overflow)?;
+
+    emit!(VoteSponsored {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        voter: ctx.accounts.signer.key(),
+        amount: reimbursement,
+        reimbursed,
+    });
+
+    Ok(())
+}
+
+/// Casts a vote a relayer sends on the voter's signed authorization.
+///
+/// **Business Logic:**
+/// - The Ed25519 program instruction right before this one must verify the voter's signature of
+///   `vote_authorization_message(voter, project, round, nonce)`, with `nonce` their next one;
+///   the nonce is consumed, so the authorization cannot be replayed.
+/// - Applies the same weight, fee and tallies as `do_vote`, the fee pulled from the voter's
+///   allowance to the VoteManager PDA, which signs the transfer or burn.
+/// - The relayer pays the transaction and the rent of the accounts it creates.
+/// - Emits a `VoteCast` and a `VoteRelayed` event.
+pub fn _vote_with_authorization<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AuthorizedVoter<'info>>,
+    nonce: u64,
+) -> Result<()> {
+    let voter = ctx.accounts.voter.key();
+
+    // Load the instruction preceding this one from the instructions sysvar.
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current = load_current_index_checked(&instructions)?;
+    require!(current > 0, VoteError::InvalidVoteAuthorization);
+    let ed25519_ix = load_instruction_at_checked(current as usize - 1, &instructions)?;
+    let message = vote_authorization_message(
+        &voter,
+        &ctx.accounts.project.key(),
+        ctx.accounts.project.load()?.vote_round,
+        nonce,
+    );
+    verify_ed25519_instruction(&ed25519_ix, &voter, &message)
+        .map_err(|_| error!(VoteError::InvalidVoteAuthorization))?;
+
+    let vote_nonce = &mut ctx.accounts.vote_nonce;
+    vote_nonce.vote_manager = ctx.accounts.vote_manager.key();
+    vote_nonce.voter = voter;
+    vote_nonce.next_nonce = nonce.checked_add(1).ok_or(VoteError::Overflow)?;
+    vote_nonce.bump = ctx.bumps.vote_nonce;
+
+    // Evaluate the vote weight on the balance held before the fee is deducted.
+    let weight = ctx
+        .accounts
+        .vote_manager
+        .round_config
+        .weight_curve
+        .weight(ctx.accounts.token.amount);
+
+    // Pull the voting fee from the voter's allowance, priced on their prior votes.
+    let mut voter_data = load_or_init(&ctx.accounts.voter_data)?;
+    let price = ctx
+        .accounts
+        .vote_manager
+        .vote_price(voter_data.votes_for_project)?;
+    let vote_manager = &ctx.accounts.vote_manager;
+    let election = vote_manager.election_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VOTE_MANAGER_SEED,
+        vote_manager.creator.as_ref(),
+        &election,
+        &[vote_manager.bump],
+    ]];
+    let collected = charge_vote_fee_as(
+        vote_manager.fee_mode,
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token,
+        &ctx.accounts.treasury_token_account,
+        vote_manager.to_account_info(), // The VoteManager PDA is the voter's delegate.
+        signer_seeds,
+        ctx.remaining_accounts,
+        price,
+    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+
+    let mut project = ctx.accounts.project.load_mut()?;
+    count_vote(
+        &mut ctx.accounts.vote_manager,
+        ctx.accounts.project.key(),
+        &mut project,
+        &mut voter_data,
+        &mut ctx.accounts.round_votes,
+        weight,
+        collected,
+    )?;
+    voter_data.voter = voter;
+    voter_data.blinded_voter = [0; 32];
+    voter_data.bump = ctx.bumps.voter_data;
+    ctx.accounts.round_votes.bump = ctx.bumps.round_votes;
+    update_leaderboard(
+        &ctx.accounts.vote_manager,
+        ctx.accounts.leaderboard.as_ref(),
+        ctx.accounts.project.key(),
+        &project,
+    )?;
+
+    emit!(VoteCast {
+        voter,
+        project: ctx.accounts.project.key(),
+        round: project.vote_round,
+        weight,
+    });
+    emit!(VoteRelayed {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        voter,
+        relayer: ctx.accounts.relayer.key(),
+        project: ctx.accounts.project.key(),
+        nonce,
+    });
+
+    Ok(())
+}
+
+/// Creates the VoteManager's VoteTree and initializes the concurrent Merkle tree it records
+/// compressed votes in.
+///
+/// **Business Logic:**
+/// - `merkle_tree` is allocated beforehand, owned by the spl-account-compression program and sized
+///   for `max_depth` and `max_buffer_size`; the compression program rejects other sizes.
+/// - The VoteTree PDA becomes the tree's authority, so only the program appends and replaces its
+///   leaves.
+/// - Emits a `VoteTreeInitialized` event.
+pub fn init_compressed_vote_tree(
+    ctx: Context<InitVoteTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let vote_tree = &mut ctx.accounts.vote_tree;
+    vote_tree.vote_manager = ctx.accounts.vote_manager.key();
+    vote_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+    vote_tree.max_depth = max_depth;
+    vote_tree.max_buffer_size = max_buffer_size;
+    vote_tree.leaf_count = 0;
+    vote_tree.bump = ctx.bumps.vote_tree;
+
+    let data = [
+        INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR.as_slice(),
+        &max_depth.to_le_bytes(),
+        &max_buffer_size.to_le_bytes(),
+    ]
+    .concat();
+    invoke_vote_tree(
+        &ctx.accounts.vote_tree,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.noop_program,
+        &[],
+        data,
+    )?;
+
+    emit!(VoteTreeInitialized {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        max_depth,
+        max_buffer_size,
+    });
+
+    Ok(())
+}
+
+/// Casts a vote recorded as a leaf of the VoteManager's Merkle tree instead of a VoterData
+/// account, so it costs no rent.
+///
+/// **Business Logic:**
+/// - Same weight, fee transfer and tallies as `do_vote`, the vote priced as the voter's first for
+///   the project; every compressed vote counts as a new voter record of the round.
+/// - Appends the vote's `CompressedVoteRecord::leaf` to the tree at index `leaf_count`, signed by
+///   the VoteTree PDA.
+/// - Emits a `VoteCast` and a `CompressedVoteRecorded` event, from which clients rebuild the tree
+///   to prove the vote.
+pub fn _compressed_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CompressedVoter<'info>>,
+) -> Result<()> {
+    // Evaluate the vote weight on the balance held before the fee is deducted.
+    let weight = ctx
+        .accounts
+        .vote_manager
+        .round_config
+        .weight_curve
+        .weight(ctx.accounts.token.amount);
+
+    // Execute the transfer of the voting fee, with no prior votes on record.
+    let price = ctx.accounts.vote_manager.vote_price(0)?;
+    let collected = charge_vote_fee(
+        ctx.accounts.vote_manager.fee_mode,
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.signer,
+        ctx.remaining_accounts,
+        price,
+    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+
+    let mut project = ctx.accounts.project.load_mut()?;
+    tally_vote(
+        &mut ctx.accounts.vote_manager,
+        ctx.accounts.project.key(),
+        &mut project,
+        weight,
+        collected,
+        true,
+    )?;
+    update_leaderboard(
+        &ctx.accounts.vote_manager,
+        ctx.accounts.leaderboard.as_ref(),
+        ctx.accounts.project.key(),
+        &project,
+    )?;
+
+    // Append the vote receipt to the tree.
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let vote = CompressedVoteRecord {
+        voter: ctx.accounts.signer.key(),
+        project: ctx.accounts.project.key(),
+        round: project.vote_round,
+        weight,
+        fees_paid: collected,
+        reward_claimed: false,
+    };
+    let leaf = vote.leaf(&vote_manager_key);
+    invoke_vote_tree(
+        &ctx.accounts.vote_tree,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.noop_program,
+        &[],
+        [APPEND_DISCRIMINATOR.as_slice(), &leaf].concat(),
+    )?;
+    let vote_tree = &mut ctx.accounts.vote_tree;
+    let index = u32::try_from(vote_tree.leaf_count).map_err(|_| VoteError::Overflow)?;
+    vote_tree.leaf_count = vote_tree
+        .leaf_count
+        .checked_add(1)
+        .ok_or(VoteError::Overflow)?;
+
+    emit!(VoteCast {
+        voter: vote.voter,
+        project: vote.project,
+        round: vote.round,
+        weight,
+    });
+    emit!(CompressedVoteRecorded {
+        vote_manager: vote_manager_key,
+        merkle_tree: vote_tree.merkle_tree,
+        index,
+        leaf,
+        vote,
+    });
+
+    Ok(())
+}
+
+/// Verifies that a compressed vote is recorded at `index` of the VoteManager's tree.
+///
+/// **Business Logic:**
+/// - The compression program checks that the sibling nodes passed as remaining accounts link the
+///   vote's leaf to `root`, a recent root of the tree.
+/// - Emits a `CompressedVoteVerified` event.
+pub fn verify_compressed_vote<'info>(
+    ctx: Context<'_, '_, '_, 'info, VerifyVote<'info>>,
+    vote: CompressedVoteRecord,
+    root: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_tree.vote_manager;
+    let leaf = vote.leaf(&vote_manager_key);
+
+    let mut accounts = vec![AccountMeta::new_readonly(
+        ctx.accounts.merkle_tree.key(),
+        false,
+    )];
+    accounts.extend(
+        ctx.remaining_accounts
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+    let instruction = Instruction {
+        program_id: ACCOUNT_COMPRESSION_ID,
+        accounts,
+        data: [
+            VERIFY_LEAF_DISCRIMINATOR.as_slice(),
+            &root,
+            &leaf,
+            &index.to_le_bytes(),
+        ]
+        .concat(),
+    };
+    let mut account_infos = vec![ctx.accounts.merkle_tree.to_account_info()];
+    account_infos.extend_from_slice(ctx.remaining_accounts);
+    invoke(&instruction, &account_infos)?;
+
+    emit!(CompressedVoteVerified {
+        vote_manager: vote_manager_key,
+        index,
+        voter: vote.voter,
+        project: vote.project,
+        round: vote.round,
+        weight: vote.weight,
+    });
+
+    Ok(())
+}
+
+/// Pays the voter of a compressed vote for a finalized round's winner their share of the round's
+/// fees.
+///
+/// **Business Logic:**
+/// - The share is `RoundResult::reward_share` of the vote's weight, transferred from the
+///   treasury's fee account to the voter, signed by the Treasury PDA.
+/// - Replaces the vote's leaf with its `reward_claimed` version, the compression program checking
+///   the proof passed as remaining accounts against `root`; the old leaf no longer proves, so the
+///   reward cannot be claimed twice. The transfer hook's accounts may follow the proof.
+/// - Emits a `VoteRewardClaimed` and a `CompressedVoteRecorded` event.
+pub fn claim_compressed_vote_reward<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimCompressedReward<'info>>,
+    vote: CompressedVoteRecord,
+    root: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let amount = ctx.accounts.round_result.reward_share(vote.weight);
+    require!(amount > 0, VoteError::NoRewardDue);
+    let (proof, hook_accounts) = split_hook_accounts(ctx.remaining_accounts);
+
+    // Mark the leaf as claimed before paying, failing on a stale or forged proof.
+    let previous_leaf = vote.leaf(&vote_manager_key);
+    let claimed = CompressedVoteRecord {
+        reward_claimed: true,
+        ..vote
+    };
+    let new_leaf = claimed.leaf(&vote_manager_key);
+    invoke_vote_tree(
+        &ctx.accounts.vote_tree,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.noop_program,
+        proof,
+        [
+            REPLACE_LEAF_DISCRIMINATOR.as_slice(),
+            &root,
+            &previous_leaf,
+            &new_leaf,
+            &index.to_le_bytes(),
+        ]
+        .concat(),
+    )?;
+
+    let bump = ctx.accounts.treasury.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, vote_manager_key.as_ref(), &[bump]]];
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.treasury_token_account.to_account_info(),
+        to: ctx.accounts.token.to_account_info(),
+        authority: ctx.accounts.treasury.to_account_info(), // The treasury PDA signs the reward.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(cpi_ctx, hook_accounts, amount, ctx.accounts.mint.decimals)?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_rewarded = treasury
+        .total_rewarded
+        .checked_add(amount)
+        .ok_or(VoteError::Overflow)?;
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    vote_manager.owed_fees = vote_manager.owed_fees.saturating_sub(amount);
+    let round_result = &mut ctx.accounts.round_result;
+    round_result.rewards_claimed = round_result
+        .rewards_claimed
+        .checked_add(amount)
+        .ok_or(VoteError::Overflow)?;
+
+    emit!(VoteRewardClaimed {
+        vote_manager: vote_manager_key,
+        round: ctx.accounts.round_result.round,
+        project: ctx.accounts.round_result.winner,
+        voter: ctx.accounts.signer.key(),
+        amount,
+    });
+    emit!(CompressedVoteRecorded {
+        vote_manager: vote_manager_key,
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        index,
+        leaf: new_leaf,
+        vote: claimed,
+    });
+
+    Ok(())
+}
+
+/// Invokes the spl-account-compression instruction encoded in `data` on `merkle_tree`, signed by
+/// the VoteTree PDA as the tree's authority.
+///
+/// `proof` holds the sibling nodes of the leaf the instruction modifies, if any, passed as the
+/// compression program reads them: as accounts whose addresses are the node hashes.
+fn invoke_vote_tree<'info>(
+    vote_tree: &Account<'info, VoteTree>,
+    merkle_tree: &UncheckedAccount<'info>,
+    noop_program: &UncheckedAccount<'info>,
+    proof: &[AccountInfo<'info>],
+    data: Vec<u8>,
+) -> Result<()> {
+    let mut accounts = vec![
+        AccountMeta::new(merkle_tree.key(), false),
+        AccountMeta::new_readonly(vote_tree.key(), true),
+        AccountMeta::new_readonly(noop_program.key(), false),
+    ];
+    accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+    let instruction = Instruction {
+        program_id: ACCOUNT_COMPRESSION_ID,
+        accounts,
+        data,
+    };
+
+    let mut account_infos = vec![
+        merkle_tree.to_account_info(),
+        vote_tree.to_account_info(),
+        noop_program.to_account_info(),
+    ];
+    account_infos.extend_from_slice(proof);
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VOTE_TREE_SEED,
+        vote_tree.vote_manager.as_ref(),
+        &[vote_tree.bump],
+    ]];
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+    Ok(())
+}
+
+/// Counts a paid vote of `weight` on the project, the voter record, the voter's RoundVotes and
+/// the round tallies.
+fn count_vote(
+    vote_manager: &mut VoteManager,
+    project_key: Pubkey,
+    project: &mut ProjectData,
+    voter_data: &mut VoterData,
+    round_votes: &mut RoundVotes,
+    weight: u64,
+    fee: u64,
+) -> Result<()> {
+    // Count a new voter record on the voter's first vote for this project, and a new round
+    // participant on their first vote of the round.
+    if voter_data.vote_count == 0 {
+        project.voter_records = project.voter_records.checked_add(1).ok_or(VoteError::Overflow)?;
+    }
+    let new_participant = round_votes.votes == 0;
+
+    // Increment vote counts for the voter, then the project and the round.
+    voter_data.vote_count = voter_data.vote_count.checked_add(1).ok_or(VoteError::Overflow)?;
+    voter_data.votes_for_project =
+        voter_data.votes_for_project.checked_add(1).ok_or(VoteError::Overflow)?;
+    voter_data.vote_weight = voter_data.vote_weight.checked_add(weight).ok_or(VoteError::Overflow)?;
+    voter_data.fees_paid = voter_data.fees_paid.checked_add(fee).ok_or(VoteError::Overflow)?;
+    round_votes.votes = round_votes
+        .votes
+        .checked_add(1)
+        .ok_or(VoteError::Overflow)?;
+    tally_vote(
+        vote_manager,
+        project_key,
+        project,
+        weight,
+        fee,
+        new_participant,
+    )?;
+    voter_data.last_voted_round = project.vote_round;
+    voter_data.set_project_name(project.id());
+    voter_data.retracted = 0;
+    voter_data.version = VoterData::VERSION;
+    Ok(())
+}
+
+/// Adds a paid vote of `weight` to the project and the running round tallies and leader,
+/// `new_participant` counting a new participant of the round.
+fn tally_vote(
+    vote_manager: &mut VoteManager,
+    project_key: Pubkey,
+    project: &mut ProjectData,
+    weight: u64,
+    fee: u64,
+    new_participant: bool,
+) -> Result<()> {
+    // A runoff only takes votes for the projects it is held between.
+    require!(
+        project.vote_weight >= vote_manager.runoff_weight,
+        VoteError::NotInRunoff
+    );
+
+    project.vote_count = project
+        .vote_count
+        .checked_add(1)
+        .ok_or(VoteError::Overflow)?;
+    project.vote_weight = project
+        .vote_weight
+        .checked_add(weight)
+        .ok_or(VoteError::Overflow)?;
+    vote_manager.round_fees = vote_manager
+        .round_fees
+        .checked_add(fee)
+        .ok_or(VoteError::Overflow)?;
+    vote_manager.record_vote(
+        project_key,
+        project.vote_weight,
+        project.created_slot,
+        weight,
+        new_participant,
+    )
+}
+
+/// Commits a hidden vote for the current round.
+///
+/// **Business Logic:**
+/// - Stores `hash(voter || project_id || salt)` so the choice stays hidden until the reveal window.
+/// - Charges the voting fee and fixes the vote weight at commit time.
+pub fn commit_hidden_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CommitVote<'info>>,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let weight = ctx
+        .accounts
+        .vote_manager
+        .round_config
+        .weight_curve
+        .weight(ctx.accounts.token.amount);
+
+    // Execute the transfer of the voting fee.
+    let collected = charge_vote_fee(
+        ctx.accounts.vote_manager.fee_mode,
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.signer,
+        ctx.remaining_accounts,
+        ctx.accounts.vote_manager.round_config.vote_fee,
+    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    vote_manager.round_fees = vote_manager
+        .round_fees
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+
+    // Record the commitment.
+    let vote_commit = &mut ctx.accounts.vote_commit;
+    vote_commit.voter = ctx.accounts.signer.key();
+    vote_commit.round = ctx.accounts.vote_manager.vote_round;
+    vote_commit.commitment = commitment;
+    vote_commit.weight = weight;
+    vote_commit.revealed = false;
+    vote_commit.bump = ctx.bumps.vote_commit;
+
+    Ok(())
+}
+
+/// Reveals a committed vote and counts it.
+///
+/// **Business Logic:**
+/// - Marks the commitment as revealed so it cannot be counted twice.
+/// - Adds the committed weight to the project and the running round tallies.
+/// - Emits a `VoteCast` event, the hidden vote only becomes observable here.
+pub fn reveal_hidden_vote(ctx: Context<RevealVote>) -> Result<()> {
+    let weight = ctx.accounts.vote_commit.weight;
+    ctx.accounts.vote_commit.revealed = true;
+
+    // Increment vote counts for the project.
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.vote_count = project.vote_count.checked_add(1).ok_or(VoteError::Overflow)?;
+    project.vote_weight = project.vote_weight.checked_add(weight).ok_or(VoteError::Overflow)?;
+
+    // Update the running round tallies and leader.
+    ctx.accounts.vote_manager.record_vote(
+        ctx.accounts.project.key(),
+        project.vote_weight,
+        project.created_slot,
+        weight,
+        true,
+    )?;
+
+    emit!(VoteCast {
+        voter: ctx.accounts.vote_commit.voter,
+        project: ctx.accounts.project.key(),
+        round: ctx.accounts.vote_commit.round,
+        weight,
+    });
+
+    Ok(())
+}
+
+/// Computes the blinded voter id used by `stealth_vote`: `hash("stealth_voter" || voter || nonce)`.
+///
+/// Revealing `nonce` later proves ownership of the stealth VoterData records made with it.
+pub fn stealth_voter_id(voter: &Pubkey, nonce: &[u8; 32]) -> [u8; 32] {
+    hashv(&[STEALTH_VOTER_SEED, voter.as_ref(), nonce]).to_bytes()
+}
+
+/// Computes the commitment stored by `commit_vote`: `hash(voter || project_id || salt)`.
+///
+/// Binding the voter's key means a commitment copied by another wallet never reveals.
+pub fn vote_commitment(voter: &Pubkey, project_id: &str, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[voter.as_ref(), project_id.as_bytes(), salt]).to_bytes()
+}
+
+/// Checks `proof` against the eligibility snapshot and returns the voter's snapshot weight.
+///
+/// **Business Logic:**
+/// - Returns `None` without a snapshot; the proof is then ignored.
+/// - With a snapshot, fails with `NotEligible` unless the proof links `(voter, weight)` to
+///   `eligibility_root`.
+pub fn check_eligibility(
+    vote_manager: &VoteManager,
+    voter: &Pubkey,
+    proof: Option<&EligibilityProof>,
+) -> Result<Option<u64>> {
+    if !vote_manager.eligibility_snapshot() {
+        return Ok(None);
+    }
+    let proof = proof.ok_or(VoteError::NotEligible)?;
+    require!(
+        verify_eligibility_proof(
+            &vote_manager.eligibility_root,
+            eligibility_leaf(voter, proof.weight),
+            &proof.path,
+        ),
+        VoteError::NotEligible
+    );
+    Ok(Some(proof.weight))
+}
+
+/// Returns the staked weight of a `do_vote` in escrow mode.
+///
+/// **Business Logic:**
+/// - Returns `None` outside escrow mode; no StakePosition is read then.
+/// - In escrow mode, `stake_position` must be the voter's StakePosition, still locked, and its
+///   `voting_power` weighs the vote.
+pub fn stake_weight(
+    vote_manager: &Account<VoteManager>,
+    voter: &Pubkey,
+    stake_position: Option<&AccountInfo>,
+) -> Result<Option<u64>> {
+    if !vote_manager.escrow_mode {
+        return Ok(None);
+    }
+    let stake_position = stake_position.ok_or(VoteError::IncorrectStakePosition)?;
+    require_keys_eq!(
+        *stake_position.owner,
+        crate::ID,
+        VoteError::IncorrectStakePosition
+    );
+    let data = stake_position.try_borrow_data()?;
+    let stake_position = StakePosition::try_deserialize(&mut &data[..])?;
+    require_keys_eq!(
+        stake_position.vote_manager,
+        vote_manager.key(),
+        VoteError::IncorrectStakePosition
+    );
+    require_keys_eq!(
+        stake_position.owner,
+        *voter,
+        VoteError::IncorrectStakePosition
+    );
+    require!(
+        stake_position.locked(Clock::get()?.unix_timestamp),
+        VoteError::StakeExpired
+    );
+    Ok(Some(stake_position.voting_power))
+}
+
+/// Checks a `do_vote` against the holding requirement.
+///
+/// **Business Logic:**
+/// - Passes without a holding requirement; no HoldingAttestation is read then.
+/// - Otherwise `attestation` must be the voter's, registered for the voting token account at
+///   least `min_holding_slots` slots before the round opened, and the account must still hold
+///   `min_holding_balance`.
+/// - The balance is only seen at registration and at the vote, not in between.
+pub fn check_holding(
+    vote_manager: &VoteManager,
+    attestation: Option<&HoldingAttestation>,
+    token: &InterfaceAccount<TokenAccount>,
+) -> Result<()> {
+    if !vote_manager.holding_requirement() {
+        return Ok(());
+    }
+    let attestation = attestation.ok_or(VoteError::HoldingNotRegistered)?;
+    require_keys_eq!(
+        attestation.token_account,
+        token.key(),
+        VoteError::HoldingNotRegistered
+    );
+    let held_since = attestation
+        .registered_slot
+        .checked_add(vote_manager.min_holding_slots)
+        .ok_or(VoteError::Overflow)?;
+    require!(
+        held_since <= vote_manager.round_config.opened_slot,
+        VoteError::HoldingTooRecent
+    );
+    require!(
+        token.amount >= vote_manager.min_holding_balance,
+        VoteError::InsufficientHolding
+    );
+    Ok(())
+}
+
+/// Checks a `do_vote` against the access mode.
+///
+/// **Business Logic:**
+/// - Passes in `Open` mode; no entry is read then.
+/// - Otherwise `entry` must be the address of the voter's entry on the active list, passed whether
+///   or not the entry exists: the voter must be listed on the allowlist and not on the blocklist.
+pub fn check_access(
+    vote_manager: &Account<VoteManager>,
+    voter: &Pubkey,
+    entry: Option<&AccountInfo>,
+) -> Result<()> {
+    let (address, _) = match vote_manager.access_mode {
+        AccessMode::Open => return Ok(()),
+        AccessMode::Allowlist => allowlist_entry_address(&vote_manager.key(), voter, &crate::ID),
+        AccessMode::Blocklist => blocklist_entry_address(&vote_manager.key(), voter, &crate::ID),
+    };
+    let entry = entry.ok_or(VoteError::WrongAccessEntry)?;
+    require_keys_eq!(entry.key(), address, VoteError::WrongAccessEntry);
+
+    // Only the program can create accounts at its PDAs, so any data there is the entry.
+    let listed = entry.owner == &crate::ID && !entry.data_is_empty();
+    match vote_manager.access_mode {
+        AccessMode::Allowlist => require!(listed, VoteError::NotAllowlisted),
+        _ => require!(!listed, VoteError::Blocklisted),
+    }
+    Ok(())
+}
+
+/// Charges the voting fee under `fee_mode`, returning the amount the treasury collected.
+///
+/// Collected fees are transferred from the voter to the treasury's fee account, burned fees are
+/// burned from the voter's account and collect nothing, so they are never refunded.
+#[allow(clippy::too_many_arguments)]
+fn charge_vote_fee<'info>(
+    fee_mode: FeeMode,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<u64> {
+    // The voter must authorize the transfer or burn from their own account.
+    charge_vote_fee_as(
+        fee_mode,
+        token_program,
+        mint,
+        from,
+        to,
+        authority.to_account_info(),
+        &[],
+        hook_accounts,
+        amount,
+    )
+}
+
+/// Charges the voting fee like `charge_vote_fee`, on the authority of `authority` signing with
+/// `signer_seeds`, such as a PDA the voter delegated their tokens to.
+#[allow(clippy::too_many_arguments)]
+fn charge_vote_fee_as<'info>(
+    fee_mode: FeeMode,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<u64> {
+    if fee_mode == FeeMode::Burn {
+        let cpi_accounts = anchor_spl::token_interface::Burn {
+            mint: mint.to_account_info(),
+            from: from.to_account_info(),
+            authority,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        anchor_spl::token_interface::burn(cpi_ctx, amount)?;
+        return Ok(0);
+    }
+
+    // Prepare the CPI context for transferring the voting fee.
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: mint.to_account_info(),
+        from: from.to_account_info(),
+        to: to.to_account_info(),
+        authority,
+    };
+
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+
+    transfer_checked_with_hook(cpi_ctx, hook_accounts, amount, mint.decimals)?;
+    Ok(amount)
+}
+
+/// Runs a `transfer_checked` CPI, adding the accounts of the mint's transfer hook.
+///
+/// Mints without a transfer hook ignore `hook_accounts`. Once the TTT transfer lock is on, they
+/// must hold the hook program, its extra account metas and the accounts those resolve to, which
+/// clients pass after the instruction's other remaining accounts.
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, anchor_spl::token_interface::TransferChecked<'info>>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        hook_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Splits remaining accounts read positionally from the transfer hook's accounts that follow them.
+///
+/// The hook's accounts start at its program, the first executable account; ProjectData accounts
+/// and Merkle proof nodes never are.
+pub fn split_hook_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> (&'a [AccountInfo<'info>], &'a [AccountInfo<'info>]) {
+    let hook_start = accounts
+        .iter()
+        .position(|info| info.executable)
+        .unwrap_or(accounts.len());
+    accounts.split_at(hook_start)
+}
+
+/// Locks TTT in the voter's stake vault in exchange for voting power.
+///
+/// **Business Logic:**
+/// - Moves `amount` from the voter's token account into the StakePosition's vault, owned by the
+///   StakePosition PDA so only `unstake` can release it.
+/// - Locks it for `lock_duration` seconds, at most the VoteManager's `max_lock_duration`.
+/// - The position's voting power is `amount × lock_duration / max_lock_duration`, fixed until
+///   the lock expires.
+/// - Emits a `StakeLocked` event.
+pub fn stake_tokens_for_power<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StakeForPower<'info>>,
+    amount: u64,
+    lock_duration: i64,
+) -> Result<()> {
+    let vote_manager = &ctx.accounts.vote_manager;
+    require!(amount > 0, VoteError::InvalidStakeAmount);
+    require!(
+        lock_duration > 0 && lock_duration <= vote_manager.max_lock_duration,
+        VoteError::InvalidLockDuration
+    );
+    let voting_power = vote_manager.escrow_power(amount, lock_duration)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let stake_position = &mut ctx.accounts.stake_position;
+    stake_position.vote_manager = vote_manager.key();
+    stake_position.owner = ctx.accounts.owner.key();
+    stake_position.amount = amount;
+    stake_position.locked_at = now;
+    stake_position.unlocks_at = now.checked_add(lock_duration).ok_or(VoteError::Overflow)?;
+    stake_position.voting_power = voting_power;
+    stake_position.bump = ctx.bumps.stake_position;
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.owner_token_account.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(StakeLocked {
+        vote_manager: stake_position.vote_manager,
+        owner: stake_position.owner,
+        amount,
+        unlocks_at: stake_position.unlocks_at,
+        voting_power,
+    });
+
+    Ok(())
+}
+
+/// Releases an expired stake position.
+///
+/// **Business Logic:**
+/// - Fails with `StakeStillLocked` before the position's `unlocks_at`.
+/// - Transfers the staked TTT back to the owner, signed by the StakePosition PDA, then closes the
+///   vault and, by the `close = owner` constraint, the position, returning their rent.
+/// - Emits a `StakeUnlocked` event.
+pub fn unstake_tokens<'info>(ctx: Context<'_, '_, 'info, 'info, Unstake<'info>>) -> Result<()> {
+    let stake_position = &ctx.accounts.stake_position;
+    require!(
+        !stake_position.locked(Clock::get()?.unix_timestamp),
+        VoteError::StakeStillLocked
+    );
+
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let owner_key = ctx.accounts.owner.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        STAKE_POSITION_SEED,
+        vote_manager_key.as_ref(),
+        owner_key.as_ref(),
+        &[stake_position.bump],
+    ]];
+
+    let amount = ctx.accounts.stake_vault.amount;
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.stake_vault.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: stake_position.to_account_info(), // The position PDA signs the release.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let cpi_accounts = anchor_spl::token_interface::CloseAccount {
+        account: ctx.accounts.stake_vault.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: stake_position.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    anchor_spl::token_interface::close_account(cpi_ctx)?;
+
+    emit!(StakeUnlocked {
+        vote_manager: vote_manager_key,
+        owner: owner_key,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Registers a wallet's token holding, starting the age the holding requirement counts.
+///
+/// **Business Logic:**
+/// - The `init` constraint has already created the HoldingAttestation at the address derived
+///   from the owner, so a holding is registered once and its age cannot be restarted.
+/// - Records the token account, its balance and the current slot.
+/// - Emits a `HoldingRegistered` event.
+pub fn register_token_holding(ctx: Context<RegisterHolding>) -> Result<()> {
+    let attestation = &mut ctx.accounts.holding_attestation;
+    attestation.vote_manager = ctx.accounts.vote_manager.key();
+    attestation.owner = ctx.accounts.owner.key();
+    attestation.token_account = ctx.accounts.token.key();
+    attestation.balance = ctx.accounts.token.amount;
+    attestation.registered_slot = Clock::get()?.slot;
+    attestation.bump = ctx.bumps.holding_attestation;
+
+    emit!(HoldingRegistered {
+        vote_manager: attestation.vote_manager,
+        owner: attestation.owner,
+        token_account: attestation.token_account,
+        balance: attestation.balance,
+        registered_slot: attestation.registered_slot,
+    });
+
+    Ok(())
+}
+
+/// Grants `role` on the VoteManager to `holder`.
+///
+/// **Business Logic:**
+/// - The `init` constraint has already created the RoleGrant at the address derived from
+///   `holder`, so a wallet holds at most one role; changing it takes a revoke first.
+/// - Emits a `RoleGranted` event.
+pub fn grant_vote_role(ctx: Context<GrantRole>, holder: Pubkey, role: Role) -> Result<()> {
+    let grant = &mut ctx.accounts.role;
+    grant.vote_manager = ctx.accounts.vote_manager.key();
+    grant.holder = holder;
+    grant.role = role;
+    grant.granted_at = Clock::get()?.unix_timestamp;
+    grant.bump = ctx.bumps.role;
+
+    emit!(RoleGranted {
+        vote_manager: grant.vote_manager,
+        holder,
+        role,
+    });
+
+    Ok(())
+}
+
+/// Revokes a wallet's role on the VoteManager.
+///
+/// **Business Logic:**
+/// - The `close` constraint closes the RoleGrant, returning its rent to the admin.
+/// - Emits a `RoleRevoked` event.
+pub fn revoke_vote_role(ctx: Context<RevokeRole>) -> Result<()> {
+    emit!(RoleRevoked {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        holder: ctx.accounts.role.holder,
+        role: ctx.accounts.role.role,
+    });
+
+    Ok(())
+}
+
+/// Puts `voter` on the allowlist or the blocklist of the VoteManager.
+///
+/// **Business Logic:**
+/// - The `init` constraint has already created the AccessListEntry at the list's address, so a
+///   wallet is listed once per list.
+/// - Emits an `AccessListUpdated` event.
+pub fn add_access_list_entry(
+    entry: &mut Account<AccessListEntry>,
+    vote_manager: Pubkey,
+    voter: Pubkey,
+    list: AccessMode,
+    bump: u8,
+) -> Result<()> {
+    entry.vote_manager = vote_manager;
+    entry.voter = voter;
+    entry.list = list;
+    entry.added_at = Clock::get()?.unix_timestamp;
+    entry.bump = bump;
+
+    emit!(AccessListUpdated {
+        vote_manager,
+        voter,
+        list,
+        listed: true,
+    });
+
+    Ok(())
+}
+
+/// Takes a wallet off the list its AccessListEntry is on.
+///
+/// **Business Logic:**
+/// - The `close` constraint closes the AccessListEntry, returning its rent to the admin.
+/// - Emits an `AccessListUpdated` event.
+pub fn remove_access_list_entry(ctx: Context<RemoveAccessListEntry>) -> Result<()> {
+    emit!(AccessListUpdated {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        voter: ctx.accounts.entry.voter,
+        list: ctx.accounts.entry.list,
+        listed: false,
+    });
+
+    Ok(())
+}
+
+/// Creates the VoteManager's AdminCouncil.
+///
+/// **Business Logic:**
+/// - Stores the members and threshold; the council acts as the admin only once its PDA is made
+///   the VoteManager's admin.
+/// - Emits an `AdminCouncilCreated` event.
+pub fn create_vote_admin_council(
+    ctx: Context<CreateAdminCouncil>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let council = &mut ctx.accounts.council;
+    council.vote_manager = ctx.accounts.vote_manager.key();
+    council.members = members;
+    council.threshold = threshold;
+    council.action_count = 0;
+    council.bump = ctx.bumps.council;
+
+    emit!(AdminCouncilCreated {
+        vote_manager: council.vote_manager,
+        council: council.key(),
+        members: council.members.len() as u8,
+        threshold,
+    });
+
+    Ok(())
+}
+
+/// Records an admin action proposed by a council member.
+///
+/// **Business Logic:**
+/// - Takes the council's next `action_count` as the action id.
+/// - Records the proposer as the action's first approval.
+/// - Emits an `AdminActionProposed` event.
+pub fn propose_council_action(
+    ctx: Context<ProposeAdminAction>,
+    instructions: Vec<ProposalInstruction>,
+) -> Result<()> {
+    let council = &mut ctx.accounts.council;
+    let action = &mut ctx.accounts.action;
+
+    action.council = council.key();
+    action.id = council.action_count;
+    action.proposer = ctx.accounts.signer.key();
+    action.instructions = instructions;
+    action.approvals = vec![action.proposer];
+    action.executed = false;
+    action.bump = ctx.bumps.action;
+
+    council.action_count = council.action_count.checked_add(1).ok_or(VoteError::Overflow)?;
+
+    emit!(AdminActionProposed {
+        council: action.council,
+        action: action.key(),
+        id: action.id,
+        proposer: action.proposer,
+    });
+
+    Ok(())
+}
+
+/// Records a council member's approval of an admin action.
+///
+/// **Business Logic:**
+/// - Appends the signer to the action's approvals.
+/// - Emits an `AdminActionApproved` event.
+pub fn approve_council_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+    let action = &mut ctx.accounts.action;
+    action.approvals.push(ctx.accounts.signer.key());
+
+    emit!(AdminActionApproved {
+        council: action.council,
+        action: action.key(),
+        id: action.id,
+        approver: ctx.accounts.signer.key(),
+        approvals: action.approvals.len() as u8,
+    });
+
+    Ok(())
+}
+
+/// Executes the instructions of an approved admin action.
+///
+/// **Business Logic:**
+/// - Marks the action executed before any CPI, so a stored instruction cannot re-enter it.
+/// - Invokes every stored instruction in order, signed by the AdminCouncil PDA; the accounts they
+///   reference are passed as remaining accounts.
+/// - Emits an `AdminActionExecuted` event.
+pub fn execute_council_action<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteAdminAction<'info>>,
+) -> Result<()> {
+    ctx.accounts.action.executed = true;
+
+    let vote_manager_key = ctx.accounts.council.vote_manager;
+    let council_bump = ctx.accounts.council.bump;
+    let council_seeds: &[&[&[u8]]] = &[&[
+        ADMIN_COUNCIL_SEED,
+        vote_manager_key.as_ref(),
+        &[council_bump],
+    ]];
+
+    invoke_stored_instructions(
+        &ctx.accounts.action.instructions,
+        &ctx.accounts.council.key(),
+        ctx.remaining_accounts,
+        council_seeds,
+    )?;
+
+    emit!(AdminActionExecuted {
+        council: ctx.accounts.council.key(),
+        action: ctx.accounts.action.key(),
+        id: ctx.accounts.action.id,
+        instructions: ctx.accounts.action.instructions.len() as u8,
+    });
+
+    Ok(())
+}
+
+/// Records that the admin applied an operation.
+///
+/// **Business Logic:**
+/// - The `init` constraint has already created the OperationMarker at the address derived from
+///   `key`, and fails when it exists; sent in the same transaction as the operation, the marker
+///   makes a repeat of it fail as a whole.
+pub fn record_vote_operation(ctx: Context<RecordOperation>, key: [u8; 32]) -> Result<()> {
+    let marker = &mut ctx.accounts.marker;
+    marker.vote_manager = ctx.accounts.vote_manager.key();
+    marker.key = key;
+    marker.recorded_at = Clock::get()?.unix_timestamp;
+    marker.bump = ctx.bumps.marker;
+    Ok(())
+}
+
+/// Creates the treasury collecting the voting fees.
+///
+/// **Business Logic:**
+/// - The treasury's token account is created by the `associated_token` constraint, owned by the
+///   Treasury PDA so only the program can move the collected fees.
+/// - Stores the PDA bump, used to sign withdrawals.
+pub fn init_vote_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.vote_manager = ctx.accounts.vote_manager.key();
+    treasury.total_collected = 0;
+    treasury.total_withdrawn = 0;
+    treasury.total_escrowed = 0;
+    treasury.total_collected_sol = 0;
+    treasury.total_rewarded = 0;
+    treasury.bump = ctx.bumps.treasury;
+    Ok(())
+}
+
+/// Withdraws voting fees from the treasury.
+///
+/// **Business Logic:**
+/// - Transfers `amount` from the treasury's fee account, signed by the Treasury PDA; the entrypoint
+///   has checked it is within `treasury_available`.
+/// - Records the withdrawal and emits a `TreasuryWithdrawn` event.
+pub fn withdraw_from_treasury<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawTreasury<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let bump = ctx.accounts.treasury.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        TREASURY_SEED,
+        vote_manager_key.as_ref(),
+        &[bump],
+    ]];
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.treasury_token_account.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.treasury.to_account_info(), // The treasury PDA signs the transfer.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_withdrawn =
+        treasury.total_withdrawn.checked_add(amount).ok_or(VoteError::Overflow)?;
+
+    emit!(TreasuryWithdrawn {
+        vote_manager: vote_manager_key,
+        destination: ctx.accounts.destination.key(),
+        amount,
+        remaining: ctx.accounts.treasury_token_account.amount.saturating_sub(amount),
+    });
+
+    Ok(())
+}
+
+/// Withdraws voting fees paid in SOL from the treasury to the admin.
+///
+/// **Business Logic:**
+/// - The Treasury PDA is owned by the program, so lamports are moved directly rather than by CPI.
+/// - Emits a `TreasurySolWithdrawn` event.
+pub fn withdraw_sol_from_treasury(ctx: Context<WithdrawTreasurySol>, amount: u64) -> Result<()> {
+    ctx.accounts.treasury.sub_lamports(amount)?;
+    ctx.accounts.owner.add_lamports(amount)?;
+
+    emit!(TreasurySolWithdrawn {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        amount,
+        remaining: treasury_sol_available(&ctx.accounts.treasury)?,
+    });
+
+    Ok(())
+}
+
+/// Returns the TTT the treasury's fee account holds above the submission deposits in escrow, the
+/// current round's fees and the fees owed to the voters of ended rounds: the fees left to withdraw.
+pub fn treasury_available(treasury: &Treasury, vote_manager: &VoteManager, balance: u64) -> u64 {
+    balance
+        .saturating_sub(treasury.total_escrowed)
+        .saturating_sub(vote_manager.round_fees)
+        .saturating_sub(vote_manager.owed_fees)
+}
+
+/// Returns the lamports the Treasury PDA holds above its rent exemption, the SOL fees left to
+/// withdraw.
+pub fn treasury_sol_available(treasury: &Account<Treasury>) -> Result<u64> {
+    let info = treasury.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+    Ok(info.lamports().saturating_sub(rent_exempt))
+}
+
+/// Funds the VoteManager's top-up pool.
+///
+/// **Business Logic:**
+/// - Transfers `amount` from the admin's token account to the pool, created on first funding.
+/// - Emits a `TopUpsFunded` event.
+pub fn fund_top_up_pool<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FundTopUps<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.source.to_account_info(),
+        to: ctx.accounts.top_up_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(TopUpsFunded {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        amount,
+        balance: ctx
+            .accounts
+            .top_up_account
+            .amount
+            .checked_add(amount)
+            .ok_or(VoteError::Overflow)?,
+    });
+
+    Ok(())
+}
+
+/// Tops up the voter's token account from the VoteManager's top-up pool.
+///
+/// **Business Logic:**
+/// - The next instruction of the transaction must be the vote `reason` names, paying in TTT,
+///   signed by the same voter in the same VoteManager and recorded on the passed VoterData, so
+///   the pool only ever funds the vote it was priced for.
+/// - Marks the voter's RoundVotes, so a voter is topped up at most once per round.
+/// - Transfers `amount` from the pool, signed by the VoteManager PDA; the admin does not take part.
+/// - Thaws a frozen voter account first, for mints whose accounts start frozen and whose freeze
+///   authority is the VoteManager PDA. The vote the top-up funds still checks the voter's access.
+/// - Emits a `VoterToppedUp` event.
+pub fn top_up_voter<'info>(
+    ctx: Context<'_, '_, 'info, 'info, EnsureCanVote<'info>>,
+    amount: u64,
+    reason: TopUpReason,
+) -> Result<()> {
+    let voter = ctx.accounts.signer.key();
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current = load_current_index_checked(&instructions)? as usize;
+    // Loading past the last instruction fails, so a trailing top-up funds nothing.
+    let funds_vote = load_instruction_at_checked(current + 1, &instructions)
+        .is_ok_and(|ix| is_ttt_vote_by(&ix, ctx.accounts, reason));
+    require!(funds_vote, VoteError::TopUpWithoutVote);
+    ctx.accounts.round_votes.topped_up = true;
+
+    let vote_manager = &ctx.accounts.vote_manager;
+    let election = vote_manager.election_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VOTE_MANAGER_SEED,
+        vote_manager.creator.as_ref(),
+        &election,
+        &[vote_manager.bump],
+    ]];
+
+    if ctx.accounts.user_ata.is_frozen() {
+        let cpi_accounts = anchor_spl::token_interface::ThawAccount {
+            account: ctx.accounts.user_ata.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: vote_manager.to_account_info(), // The VoteManager PDA, freeze authority.
+        };
+        anchor_spl::token_interface::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ))?;
+    }
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.top_up_account.to_account_info(),
+        to: ctx.accounts.user_ata.to_account_info(),
+        authority: vote_manager.to_account_info(), // The VoteManager PDA signs the transfer.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(VoterToppedUp {
+        vote_manager: vote_manager.key(),
+        voter,
+        amount,
+        remaining: ctx.accounts.top_up_account.amount.saturating_sub(amount),
+    });
+
+    Ok(())
+}
+
+/// Checks that `ix` is the vote of this program `reason` funds, a `do_vote` paying in TTT or a
+/// `sponsored_vote`, signed by the top-up's signer in its VoteManager and recorded on its
+/// `voter_data`.
+fn is_ttt_vote_by(ix: &Instruction, accounts: &EnsureCanVote, reason: TopUpReason) -> bool {
+    if ix.program_id != crate::ID {
+        return false;
+    }
+    // Positions of `signer` and `vote_manager` in `Voter` and `SponsoredVoter`; `voter_data`
+    // comes first in both.
+    let (signer_index, vote_manager_index) = match reason {
+        TopUpReason::Vote
+            if ix.data.starts_with(&crate::instruction::DoVote::DISCRIMINATOR)
+                && ix.data.last() == Some(&(FeeCurrency::Ttt as u8)) =>
+        {
+            (1, 2)
+        }
+        TopUpReason::SponsoredVote
+            if ix.data.starts_with(&crate::instruction::SponsoredVote::DISCRIMINATOR) =>
+        {
+            (3, 4)
+        }
+        _ => return false,
+    };
+    let is_account = |index: usize, key: Pubkey| {
+        ix.accounts
+            .get(index)
+            .is_some_and(|meta| meta.pubkey == key)
+    };
+    is_account(0, accounts.voter_data.key())
+        && is_account(vote_manager_index, accounts.vote_manager.key())
+        && ix
+            .accounts
+            .get(signer_index)
+            .is_some_and(|meta| meta.pubkey == accounts.signer.key() && meta.is_signer)
+}
+
+/// Returns the votes the VoterData at `voter_data` recorded for its project, 0 until the vote
+/// creates it.
+pub fn prior_votes(voter_data: &AccountInfo) -> Result<u16> {
+    if voter_data.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(
+        *voter_data.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let data = voter_data.try_borrow_data()?;
+    Ok(VoterData::try_deserialize_versioned(&data)?.votes_for_project)
+}
+
+/// Withdraws tokens from the VoteManager's top-up pool.
+///
+/// **Business Logic:**
+/// - Transfers `amount` from the pool, signed by the VoteManager PDA.
+/// - Emits a `TopUpsWithdrawn` event.
+pub fn withdraw_from_top_ups<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawTopUps<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let vote_manager = &ctx.accounts.vote_manager;
+    let election = vote_manager.election_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VOTE_MANAGER_SEED,
+        vote_manager.creator.as_ref(),
+        &election,
+        &[vote_manager.bump],
+    ]];
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.top_up_account.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: vote_manager.to_account_info(), // The VoteManager PDA signs the transfer.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(TopUpsWithdrawn {
+        vote_manager: vote_manager.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        remaining: ctx.accounts.top_up_account.amount.saturating_sub(amount),
+    });
+
+    Ok(())
+}
+
+/// Thaws a voter's token account once the admin has cleared them.
+///
+/// **Business Logic:**
+/// - Signed by the VoteManager PDA, which must be the mint's freeze authority.
+/// - Emits a `VoterAccountThawed` event.
+pub fn thaw_voter_token_account(ctx: Context<ThawVoterAccount>) -> Result<()> {
+    let vote_manager = &ctx.accounts.vote_manager;
+    let election = vote_manager.election_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VOTE_MANAGER_SEED,
+        vote_manager.creator.as_ref(),
+        &election,
+        &[vote_manager.bump],
+    ]];
+
+    let cpi_accounts = anchor_spl::token_interface::ThawAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: vote_manager.to_account_info(), // The VoteManager PDA, freeze authority.
+    };
+    anchor_spl::token_interface::thaw_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    ))?;
+
+    emit!(VoterAccountThawed {
+        vote_manager: vote_manager.key(),
+        voter: ctx.accounts.token_account.owner,
+        token_account: ctx.accounts.token_account.key(),
+    });
+
+    Ok(())
+}
+
+/// Adds rewards to the reward vault of a round.
+///
+/// **Business Logic:**
+/// - The first funding creates the vault and fixes its claim window from the VoteManager.
+/// - Transfers `amount` from the admin's token account to the vault's token account.
+/// - Emits a `RewardVaultFunded` event.
+pub fn fund_vote_reward_vault<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FundRewardVault<'info>>,
+    round: u64,
+    amount: u64,
+) -> Result<()> {
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    if reward_vault.vote_manager == Pubkey::default() {
+        reward_vault.vote_manager = ctx.accounts.vote_manager.key();
+        reward_vault.round = round;
+        reward_vault.claim_window = ctx.accounts.vote_manager.reward_claim_window;
+        reward_vault.bump = ctx.bumps.reward_vault;
+    }
+
+    // Move the rewards into the vault.
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.source.to_account_info(),
+        to: ctx.accounts.vault_token_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    reward_vault.total_funded =
+        reward_vault.total_funded.checked_add(amount).ok_or(VoteError::Overflow)?;
+
+    emit!(RewardVaultFunded {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        round,
+        amount,
+        total_funded: ctx.accounts.reward_vault.total_funded,
+    });
+
+    Ok(())
+}
+
+/// Returns the unclaimed rewards of a round to the admin once its claim window has closed.
+///
+/// **Business Logic:**
+/// - Transfers the vault's whole token balance, signed by the RewardVault PDA.
+/// - Marks the vault as swept so it can neither be funded nor claimed from again.
+/// - Emits a `RewardsSwept` event.
+pub fn sweep_unclaimed_vote_rewards<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SweepUnclaimedRewards<'info>>,
+    round: u64,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let bump = ctx.accounts.reward_vault.bump;
+    let round_bytes = round.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        REWARD_VAULT_SEED,
+        vote_manager_key.as_ref(),
+        &round_bytes,
+        &[bump],
+    ]];
+
+    let amount = ctx.accounts.vault_token_account.amount;
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.reward_vault.to_account_info(), // The vault PDA signs the transfer.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    ctx.accounts.reward_vault.swept = true;
+
+    emit!(RewardsSwept {
+        vote_manager: vote_manager_key,
+        round,
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Creates the sponsor vault that reimburses first-time voters' rent.
+///
+/// **Business Logic:**
+/// - The vault holds SOL in the SponsorVault PDA itself, above its own rent exemption.
+/// - `max_per_wallet` caps the lamports a single wallet can be reimbursed over its lifetime.
+pub fn init_vote_sponsor_vault(ctx: Context<InitSponsorVault>, max_per_wallet: u64) -> Result<()> {
+    let sponsor_vault = &mut ctx.accounts.sponsor_vault;
+    sponsor_vault.vote_manager = ctx.accounts.vote_manager.key();
+    sponsor_vault.max_per_wallet = max_per_wallet;
+    sponsor_vault.total_deposited = 0;
+    sponsor_vault.total_reimbursed = 0;
+    sponsor_vault.bump = ctx.bumps.sponsor_vault;
+    Ok(())
+}
+
+/// Deposits `amount` lamports from the admin into the sponsor vault.
+///
+/// **Business Logic:**
+/// - Transfers with a System program CPI and records the deposit.
+/// - Emits a `SponsorVaultFunded` event.
+pub fn fund_vote_sponsor_vault(ctx: Context<ManageSponsorVault>, amount: u64) -> Result<()> {
+    let cpi_accounts = anchor_lang::system_program::Transfer {
+        from: ctx.accounts.owner.to_account_info(),
+        to: ctx.accounts.sponsor_vault.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+
+    anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+    let sponsor_vault = &mut ctx.accounts.sponsor_vault;
+    sponsor_vault.total_deposited = sponsor_vault
+        .total_deposited
+        .checked_add(amount)
+        .ok_or(VoteError::Overflow)?;
+
+    emit!(SponsorVaultFunded {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        amount,
+        available: sponsor_vault_available(&ctx.accounts.sponsor_vault)?,
+    });
+
+    Ok(())
+}
+
+/// Withdraws `amount` spare lamports from the sponsor vault to the admin.
+///
+/// **Business Logic:**
+/// - The vault is owned by the program, so lamports are moved directly rather than by CPI.
+/// - Emits a `SponsorVaultWithdrawn` event.
+pub fn withdraw_vote_sponsor_vault(ctx: Context<ManageSponsorVault>, amount: u64) -> Result<()> {
+    ctx.accounts.sponsor_vault.sub_lamports(amount)?;
+    ctx.accounts.owner.add_lamports(amount)?;
+
+    emit!(SponsorVaultWithdrawn {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        amount,
+        available: sponsor_vault_available(&ctx.accounts.sponsor_vault)?,
+    });
+
+    Ok(())
+}
+
+/// Sets the lifetime reimbursement limit of a single wallet.
+pub fn set_vote_sponsor_limit(ctx: Context<ManageSponsorVault>, max_per_wallet: u64) -> Result<()> {
+    ctx.accounts.sponsor_vault.max_per_wallet = max_per_wallet;
+    Ok(())
+}
+
+/// Returns the lamports the sponsor vault holds above its rent exemption.
+pub fn sponsor_vault_available(sponsor_vault: &Account<SponsorVault>) -> Result<u64> {
+    let info = sponsor_vault.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+    Ok(info.lamports().saturating_sub(rent_exempt))
+}
+
+/// Creates a proposal to execute `instructions` once TTT holders approve it.
+///
+/// **Business Logic:**
+/// - Takes the next `proposal_count` as the proposal id.
+/// - Snapshots the voting deadline and quorum from the VoteManager.
+/// - Locks `proposal_deposit` TTT from the proposer in the proposal's token account.
+/// - Emits a `ProposalCreated` event.
+pub fn create_governance_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateProposal<'info>>,
+    instructions: Vec<ProposalInstruction>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    let proposal = &mut ctx.accounts.proposal;
+
+    proposal.vote_manager = vote_manager.key();
+    proposal.id = vote_manager.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.deposit = vote_manager.proposal_deposit;
+    proposal.instructions = instructions;
+    proposal.voting_ends_at = now
+        .checked_add(vote_manager.proposal_voting_period)
+        .ok_or(VoteError::Overflow)?;
+    proposal.yes_weight = 0;
+    proposal.no_weight = 0;
+    proposal.quorum = vote_manager.proposal_quorum;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    vote_manager.proposal_count =
+        vote_manager.proposal_count.checked_add(1).ok_or(VoteError::Overflow)?;
+
+    // Lock the deposit until the proposal is executed or closed.
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.proposer_token_account.to_account_info(),
+        to: ctx.accounts.deposit_token_account.to_account_info(),
+        authority: ctx.accounts.proposer.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        ctx.accounts.proposal.deposit,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(ProposalCreated {
+        vote_manager: ctx.accounts.proposal.vote_manager,
+        proposal: ctx.accounts.proposal.key(),
+        id: ctx.accounts.proposal.id,
+        proposer: ctx.accounts.proposal.proposer,
+        voting_ends_at: ctx.accounts.proposal.voting_ends_at,
+    });
+
+    Ok(())
+}
+
+/// Records a vote for or against a proposal.
+///
+/// **Business Logic:**
+/// - Moves `amount` from the voter's token account into the ballot's vault, owned by the
+///   ProposalVote PDA so only `release_proposal_vote` can return it once voting ended; the same
+///   tokens cannot weigh a vote from another wallet meanwhile.
+/// - Weighs the vote with the VoteManager's weight curve applied to the locked amount.
+/// - The `ProposalVote` PDA is initialized once per voter and proposal, preventing double voting.
+/// - Emits a `ProposalVoted` event.
+pub fn vote_on_governance_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProposalVoter<'info>>,
+    approve: bool,
+    amount: u64,
+) -> Result<()> {
+    let weight = ctx.accounts.vote_manager.weight_curve.weight(amount);
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token.to_account_info(),
+        to: ctx.accounts.vote_vault.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+
+    if approve {
+        proposal.yes_weight = proposal.yes_weight.checked_add(weight).ok_or(VoteError::Overflow)?;
+    } else {
+        proposal.no_weight = proposal.no_weight.checked_add(weight).ok_or(VoteError::Overflow)?;
+    }
+
+    let proposal_vote = &mut ctx.accounts.proposal_vote;
+    proposal_vote.proposal = proposal.key();
+    proposal_vote.voter = ctx.accounts.signer.key();
+    proposal_vote.approve = approve;
+    proposal_vote.weight = weight;
+    proposal_vote.locked = amount;
+    proposal_vote.unlocks_at = proposal.voting_ends_at;
+    proposal_vote.bump = ctx.bumps.proposal_vote;
+
+    emit!(ProposalVoted {
+        proposal: proposal.key(),
+        voter: proposal_vote.voter,
+        approve,
+        weight,
+        locked: amount,
+    });
+
+    Ok(())
+}
+
+/// Returns the TTT a proposal vote locked once the proposal's voting ended.
+///
+/// **Business Logic:**
+/// - Transfers the vault's TTT back to the voter, signed by the ProposalVote PDA, then closes the
+///   vault and, by the `close = voter` constraint, the ballot, returning their rent.
+/// - The ballot no longer prevents double voting, which the ended voting period does.
+/// - Emits a `ProposalVoteReleased` event.
+pub fn release_proposal_vote_tokens<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReleaseProposalVote<'info>>,
+) -> Result<()> {
+    let proposal_vote = &ctx.accounts.proposal_vote;
+    let voter_key = ctx.accounts.voter.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        PROPOSAL_VOTE_SEED,
+        proposal_vote.proposal.as_ref(),
+        voter_key.as_ref(),
+        &[proposal_vote.bump],
+    ]];
+
+    let amount = ctx.accounts.vote_vault.amount;
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.vote_vault.to_account_info(),
+        to: ctx.accounts.token.to_account_info(),
+        authority: proposal_vote.to_account_info(), // The ballot PDA signs the release.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let cpi_accounts = anchor_spl::token_interface::CloseAccount {
+        account: ctx.accounts.vote_vault.to_account_info(),
+        destination: ctx.accounts.voter.to_account_info(),
+        authority: proposal_vote.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    anchor_spl::token_interface::close_account(cpi_ctx)?;
+
+    emit!(ProposalVoteReleased {
+        proposal: proposal_vote.proposal,
+        voter: voter_key,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Creates a referendum asking TTT holders a yes/no question.
+///
+/// **Business Logic:**
+/// - Initializes the Referendum PDA under the VoteManager's next `referendum_count`.
+/// - Freezes the VoteManager's current `vote_fee` and `weight_curve` as the referendum's rules,
+///   so later changes for the project rounds do not apply to it.
+/// - Emits a `ReferendumCreated` event.
+pub fn create_vote_referendum(
+    ctx: Context<CreateReferendum>,
+    question_uri: String,
+    deadline: i64,
+) -> Result<()> {
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    let referendum = &mut ctx.accounts.referendum;
+
+    referendum.vote_manager = vote_manager.key();
+    referendum.id = vote_manager.referendum_count;
+    referendum.question_uri = question_uri;
+    referendum.deadline = deadline;
+    referendum.vote_fee = vote_manager.vote_fee;
+    referendum.weight_curve = vote_manager.weight_curve;
+    referendum.yes_weight = 0;
+    referendum.no_weight = 0;
+    referendum.abstain_weight = 0;
+    referendum.voters = 0;
+    referendum.bump = ctx.bumps.referendum;
+
+    vote_manager.referendum_count =
+        vote_manager.referendum_count.checked_add(1).ok_or(VoteError::Overflow)?;
+
+    emit!(ReferendumCreated {
+        vote_manager: referendum.vote_manager,
+        referendum: referendum.key(),
+        id: referendum.id,
+        question_uri: referendum.question_uri.clone(),
+        deadline,
+    });
+
+    Ok(())
+}
+
+/// Records a voter's answer to a referendum.
+///
+/// **Business Logic:**
+/// - Weighs the vote with `fixed_weight` when the voter proved a snapshot entry or holds a stake
+///   position in escrow mode, on the referendum's `weight_curve` otherwise.
+/// - Charges the referendum's `vote_fee` like a project vote: transferred to the treasury's fee
+///   account, or burned under `FeeMode::Burn`.
+/// - Adds the weight to the tally of the chosen answer and counts the voter.
+/// - The `ReferendumVote` PDA is initialized once per voter and referendum, preventing double
+///   voting.
+/// - Emits a `ReferendumVoted` event.
+pub fn vote_on_referendum<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReferendumVoter<'info>>,
+    choice: ReferendumChoice,
+    fixed_weight: Option<u64>,
+) -> Result<()> {
+    // Take the snapshot or staked weight, or evaluate it on the balance held before the fee is
+    // deducted.
+    let weight = fixed_weight.unwrap_or_else(|| {
+        ctx.accounts
+            .referendum
+            .weight_curve
+            .weight(ctx.accounts.token.amount)
+    });
+
+    let collected = charge_vote_fee(
+        ctx.accounts.vote_manager.fee_mode,
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.signer,
+        ctx.remaining_accounts,
+        ctx.accounts.referendum.vote_fee,
+    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+
+    let referendum = &mut ctx.accounts.referendum;
+    let tally = match choice {
+        ReferendumChoice::Yes => &mut referendum.yes_weight,
+        ReferendumChoice::No => &mut referendum.no_weight,
+        ReferendumChoice::Abstain => &mut referendum.abstain_weight,
+    };
+    *tally = tally.checked_add(weight).ok_or(VoteError::Overflow)?;
+    referendum.voters = referendum.voters.checked_add(1).ok_or(VoteError::Overflow)?;
+
+    let referendum_vote = &mut ctx.accounts.referendum_vote;
+    referendum_vote.referendum = referendum.key();
+    referendum_vote.voter = ctx.accounts.signer.key();
+    referendum_vote.choice = choice;
+    referendum_vote.weight = weight;
+    referendum_vote.fee_paid = collected;
+    referendum_vote.bump = ctx.bumps.referendum_vote;
+
+    emit!(ReferendumVoted {
+        referendum: referendum.key(),
+        voter: referendum_vote.voter,
+        choice,
+        weight,
+    });
+
+    Ok(())
+}
+
+/// Executes the instructions of a passed proposal.
+///
+/// **Business Logic:**
+/// - Marks the proposal executed before any CPI, so a stored instruction cannot re-enter it.
+/// - Returns the deposit to the proposer.
+/// - Invokes every stored instruction in order, signed by the governance authority PDA; the
+///   accounts they reference are passed as remaining accounts.
+/// - Emits a `ProposalExecuted` event.
+pub fn execute_governance_proposal<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+) -> Result<()> {
+    ctx.accounts.proposal.executed = true;
+
+    // Return the deposit, signed by the proposal PDA.
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let id_bytes = ctx.accounts.proposal.id.to_le_bytes();
+    let proposal_bump = ctx.accounts.proposal.bump;
+    let proposal_seeds: &[&[&[u8]]] = &[&[
+        PROPOSAL_SEED,
+        vote_manager_key.as_ref(),
+        &id_bytes,
+        &[proposal_bump],
+    ]];
+
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.deposit_token_account.to_account_info(),
+        to: ctx.accounts.proposer_token_account.to_account_info(),
+        authority: ctx.accounts.proposal.to_account_info(), // The proposal PDA signs the refund.
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        proposal_seeds,
+    );
+
+    transfer_checked_with_hook(
+        cpi_ctx,
+        ctx.remaining_accounts,
+        ctx.accounts.deposit_token_account.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // Run the stored instructions as the governance authority.
+    let authority_bump = ctx.bumps.governance_authority;
+    let authority_seeds: &[&[&[u8]]] = &[&[
+        GOVERNANCE_AUTHORITY_SEED,
+        vote_manager_key.as_ref(),
+        &[authority_bump],
+    ]];
+
+    invoke_stored_instructions(
+        &ctx.accounts.proposal.instructions,
+        &ctx.accounts.governance_authority.key(),
+        ctx.remaining_accounts,
+        authority_seeds,
+    )?;
+
+    emit!(ProposalExecuted {
+        vote_manager: vote_manager_key,
+        proposal: ctx.accounts.proposal.key(),
+        id: ctx.accounts.proposal.id,
+        instructions: ctx.accounts.proposal.instructions.len() as u8,
+    });
+
+    Ok(())
+}
+
+/// Invokes `instructions` in order, signed by `authority` with `authority_seeds`.
+///
+/// `authority` is marked as a signer of every instruction it appears in, whatever the stored
+/// `is_signer` flag; the accounts the instructions reference come from `accounts`.
+fn invoke_stored_instructions<'info>(
+    instructions: &[ProposalInstruction],
+    authority: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    authority_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for stored in instructions {
+        let instruction = Instruction {
+            program_id: stored.program_id,
+            accounts: stored
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer || meta.pubkey == *authority,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: stored.data.clone(),
+        };
+
+        invoke_signed(&instruction, accounts, authority_seeds)?;
+    }
+
+    Ok(())
+}
+
+/// Closes a proposal that was executed or failed to pass.
+///
+/// **Business Logic:**
+/// - A deposit still locked in the proposal (a failed proposal's) is forfeited to the treasury.
+/// - Closes the proposal's token account and, by the `close = proposer` constraint, the proposal,
+///   returning the rent to the proposer.
+pub fn close_governance_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseProposal<'info>>,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let id_bytes = ctx.accounts.proposal.id.to_le_bytes();
+    let bump = ctx.accounts.proposal.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        PROPOSAL_SEED,
+        vote_manager_key.as_ref(),
+        &id_bytes,
+        &[bump],
+    ]];
+
+    let forfeited = ctx.accounts.deposit_token_account.amount;
+    if forfeited > 0 {
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.deposit_token_account.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.proposal.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked_with_hook(
+            cpi_ctx,
+            ctx.remaining_accounts,
+            forfeited,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected =
+            treasury.total_collected.checked_add(forfeited).ok_or(VoteError::Overflow)?;
+    }
+
+    let cpi_accounts = anchor_spl::token_interface::CloseAccount {
+        account: ctx.accounts.deposit_token_account.to_account_info(),
+        destination: ctx.accounts.proposer.to_account_info(),
+        authority: ctx.accounts.proposal.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    anchor_spl::token_interface::close_account(cpi_ctx)
+}
+
+/// Seals the current voting round into a `RoundResult` and opens the next one.
+///
+/// **Business Logic:**
+/// - Snapshots the round's running tallies and the leading project into the `RoundResult` PDA.
+/// - Creates the round's `RoundArchive` and archives the ProjectData accounts passed as remaining
+///   accounts as its first page; `archive_projects` archives the rest.
+/// - Increments `vote_round` in the same instruction so no vote can land between the snapshot and
+///   the next round.
+/// - Emits a `RoundFinalized` event, and a `ProjectsArchived` event if projects were archived.
+pub fn finalize_vote_round<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeRound<'info>>,
+) -> Result<()> {
+    let winner = match &ctx.accounts.winner {
+        Some(winner) => Some((winner, winner.load()?.vote_weight)),
+        None => None,
+    };
+    seal_round(
+        &mut ctx.accounts.vote_manager,
+        &mut ctx.accounts.round_result,
+        ctx.bumps.round_result,
+        &mut ctx.accounts.round_archive,
+        ctx.bumps.round_archive,
+        winner,
+    )?;
+
+    if !ctx.remaining_accounts.is_empty() {
+        archive_project_page(
+            &mut ctx.accounts.round_archive,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Archives a page of a finalized round's projects into its `RoundArchive`.
+///
+/// **Business Logic:**
+/// - The ProjectData accounts are passed as remaining accounts, in any order; each must belong to
+///   the archived round and not be archived yet, so pages cannot overlap.
+/// - Emits a `ProjectsArchived` event.
+pub fn archive_round_projects<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ArchiveProjects<'info>>,
+) -> Result<()> {
+    archive_project_page(
+        &mut ctx.accounts.round_archive,
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+    )
+}
+
+/// Inserts the final tallies of the ProjectData accounts `projects` into `round_archive`, keeping
+/// it ordered, updates the leaders of their categories, and resizes the account to fit, `payer`
+/// topping up its rent exemption.
+fn archive_project_page<'info>(
+    round_archive: &mut Account<'info, RoundArchive>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    projects: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    for info in projects {
+        let account = AccountLoader::<ProjectData>::try_from(info)?;
+        let project = account.load()?;
+        require_keys_eq!(
+            project.vote_manager,
+            round_archive.vote_manager,
+            VoteError::WrongVoteManager
+        );
+        require!(
+            project.vote_round == round_archive.round,
+            VoteError::WrongRound
+        );
+        require!(project.pending == 0, VoteError::ProjectPending);
+        require!(
+            !round_archive.is_archived(project.id()),
+            VoteError::ProjectAlreadyArchived
+        );
+        require!(
+            !round_archive.is_complete(),
+            VoteError::RoundArchiveComplete
+        );
+
+        // Most votes first, ties by id, so the order does not depend on how pages were split.
+        let position = round_archive.entries.partition_point(|entry| {
+            (Reverse(entry.vote_count), entry.project_id.as_str())
+                < (Reverse(project.vote_count), project.id())
+        });
+        round_archive.entries.insert(
+            position,
+            ArchivedProject {
+                project_id: project.id().to_string(),
+                vote_count: project.vote_count,
+            },
+        );
+        round_archive.record_category_leader(&project);
+    }
+
+    // Top up the rent exemption for the new entries.
+    let account = round_archive.to_account_info();
+    let space = round_archive.space();
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent > 0 {
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, rent)?;
+    }
+    account.realloc(space, false)?;
+
+    emit!(ProjectsArchived {
+        vote_manager: round_archive.vote_manager,
+        round: round_archive.round,
+        archived: projects.len() as u16,
+        complete: round_archive.is_complete(),
+    });
+
+    Ok(())
+}
+
+/// Re-establishes the current round's running leader from `projects`, after a disqualification or
+/// a retraction cleared it.
+///
+/// **Business Logic:**
+/// - `projects` must be every ProjectData of the round, pending submissions aside, in ascending
+///   address order, so no project is left out or counted twice.
+/// - Disqualified projects are counted with no weight, like `tally_page` counts them.
+/// - The highest weight leads; ties are marked and decided by `tie_break` as during the round.
+pub fn recount_round_leader<'info>(
+    vote_manager: &mut VoteManager,
+    vote_manager_key: Pubkey,
+    projects: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        projects.len() == usize::from(vote_manager.project_count),
+        VoteError::IncorrectProjectAccounts
+    );
+
+    vote_manager.clear_leader();
+    let mut last_project = Pubkey::default();
+    for info in projects {
+        let account = AccountLoader::<ProjectData>::try_from(info)?;
+        let project = account.load()?;
+        require_keys_eq!(
+            project.vote_manager,
+            vote_manager_key,
+            VoteError::WrongVoteManager
+        );
+        require!(
+            project.vote_round == vote_manager.vote_round,
+            VoteError::WrongRound
+        );
+        require!(project.pending == 0, VoteError::ProjectPending);
+        require!(
+            info.key() > last_project,
+            VoteError::IncorrectProjectAccounts
+        );
+        last_project = info.key();
+
+        if project.disqualified == 0 {
+            vote_manager.update_leader(info.key(), project.vote_weight, project.created_slot);
+        }
+    }
+
+    Ok(())
+}
+
+/// Counts a page of the current round's projects into its `TallyState`.
+///
+/// **Business Logic:**
+/// - The ProjectData accounts are passed as remaining accounts in ascending address order, each
+///   page continuing after the last project counted, so no project can be counted twice.
+/// - `start_index` must be the number of projects counted so far, so a resent page fails instead
+///   of being counted again.
+/// - The highest weight leads; a project drawing level marks the tally tied and takes the lead if
+///   `tie_break` prefers it. Disqualified projects are counted with no weight.
+/// - A tally started before a runoff moved the round deadline starts over.
+/// - Emits a `TallyPageCounted` event.
+pub fn count_tally_page<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TallyPage<'info>>,
+    start_index: u16,
+) -> Result<()> {
+    let vote_manager = &ctx.accounts.vote_manager;
+    let deadline = vote_manager
+        .tally_deadline()
+        .ok_or(VoteError::RoundNotEnded)?;
+    let tally = &mut ctx.accounts.tally_state;
+
+    // Start a new tally, or start over if the deadline moved since the first page.
+    if tally.vote_manager == Pubkey::default() || tally.deadline != deadline {
+        tally.vote_manager = vote_manager.key();
+        tally.round = vote_manager.vote_round;
+        tally.deadline = deadline;
+        tally.counted = 0;
+        tally.last_project = Pubkey::default();
+        tally.leader = Pubkey::default();
+        tally.leader_weight = 0;
+        tally.leader_created_slot = 0;
+        tally.tied = false;
+        tally.bump = ctx.bumps.tally_state;
+    }
+    require!(start_index == tally.counted, VoteError::TallyPageOutOfOrder);
+
+    for info in ctx.remaining_accounts {
+        let account = AccountLoader::<ProjectData>::try_from(info)?;
+        let project = account.load()?;
+        require_keys_eq!(
+            project.vote_manager,
+            tally.vote_manager,
+            VoteError::WrongVoteManager
+        );
+        require!(project.vote_round == tally.round, VoteError::WrongRound);
+        require!(project.pending == 0, VoteError::ProjectPending);
+        require!(
+            info.key() > tally.last_project,
+            VoteError::TallyPageOutOfOrder
+        );
+
+        let weight = if project.disqualified != 0 {
+            0
+        } else {
+            project.vote_weight
+        };
+        tally.count_project(
+            info.key(),
+            weight,
+            project.created_slot,
+            vote_manager.tie_break,
+        )?;
+    }
+
+    emit!(TallyPageCounted {
+        vote_manager: tally.vote_manager,
+        round: tally.round,
+        counted: tally.counted,
+        leader: tally.leader,
+        leader_weight: tally.leader_weight,
+    });
+
+    Ok(())
+}
+
+/// Seals the current round with the winner of its completed `TallyState`.
+///
+/// **Business Logic:**
+/// - Replaces the VoteManager's running leader with the tally's, which a retracted vote or a
+///   disqualification can no longer leave unset.
+/// - A tie the `Runoff` policy settles still goes to `open_runoff` first.
+/// - Seals the round like `finalize_round`; the TallyState is closed by the `close = signer`
+///   constraint, its rent rewarding whoever cranks the commit.
+pub fn commit_round_tally(ctx: Context<TallyCommit>) -> Result<()> {
+    let tally = &ctx.accounts.tally_state;
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    vote_manager.round_leader = tally.leader;
+    vote_manager.round_leader_weight = tally.leader_weight;
+    vote_manager.round_leader_created_slot = tally.leader_created_slot;
+    vote_manager.round_tied = tally.tied;
+    require!(!vote_manager.runoff_due(), VoteError::RunoffRequired);
+
+    // Ensure the passed winner is the tally's leader.
+    let winner = match &ctx.accounts.winner {
+        Some(winner) => {
+            require_keys_eq!(winner.key(), tally.leader, VoteError::WrongWinner);
+            Some((winner, tally.leader_weight))
+        }
+        None => {
+            require_keys_eq!(tally.leader, Pubkey::default(), VoteError::WrongWinner);
+            None
+        }
+    };
+    seal_round(
+        &mut ctx.accounts.vote_manager,
+        &mut ctx.accounts.round_result,
+        ctx.bumps.round_result,
+        &mut ctx.accounts.round_archive,
+        ctx.bumps.round_archive,
+        winner,
+    )
+}
+
+/// Snapshots the current round into `round_result`, starts its `round_archive`, emits a
+/// `RoundFinalized` event and opens the next round.
+///
+/// `winner` is the round's winning project and the weight it won with, if anyone voted.
+fn seal_round(
+    vote_manager: &mut Account<VoteManager>,
+    round_result: &mut RoundResult,
+    bump: u8,
+    round_archive: &mut RoundArchive,
+    archive_bump: u8,
+    winner: Option<(&AccountLoader<ProjectData>, u64)>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // Snapshot the round tallies.
+    round_result.vote_manager = vote_manager.key();
+    round_result.round = vote_manager.vote_round;
+    round_result.total_votes = vote_manager.round_total_votes;
+    round_result.total_weight = vote_manager.round_total_weight;
+    round_result.participation_count = vote_manager.round_participants;
+    round_result.finalized_at = now;
+    round_result.config_hash = vote_manager.round_config.config_hash;
+    round_result.eligible_weight = vote_manager.eligible_weight;
+    round_result.turnout_bps = vote_manager.turnout_bps()?;
+    round_result.rewards_claimed = 0;
+    round_result.bump = bump;
+
+    // Start the archive; its entries are written from the round's ProjectData accounts.
+    round_archive.vote_manager = round_result.vote_manager;
+    round_archive.round = round_result.round;
+    round_archive.project_count = vote_manager.project_count;
+    round_archive.bump = archive_bump;
+
+    // Record the winner, if anyone voted, and set the round's fees aside for its voters.
+    if let Some((winner, winner_weight)) = winner {
+        round_result.winner = winner.key();
+        round_result.winner_id = winner.load()?.id().to_string();
+        round_result.winner_weight = winner_weight;
+        round_result.reward_pool = vote_manager.round_fees;
+    }
+
+    emit!(RoundFinalized {
+        vote_manager: round_result.vote_manager,
+        round: round_result.round,
+        total_votes: round_result.total_votes,
+        total_weight: round_result.total_weight,
+        participation_count: round_result.participation_count,
+        winner: round_result.winner,
+        winner_id: round_result.winner_id.clone(),
+        turnout_bps: round_result.turnout_bps,
+        tied: vote_manager.round_tied,
+    });
+    emit!(RoundStateChanged {
+        vote_manager: round_result.vote_manager,
+        round: round_result.round,
+        state: RoundState::Finalized,
+    });
+
+    // Open the next round.
+    vote_manager.start_next_round(now, clock.slot)?;
+
+    Ok(())
+}
+
+/// Voids the current round, recording it as invalid in its `RoundResult`, and opens the next one.
+///
+/// **Business Logic:**
+/// - Snapshots the round tallies like `seal_round`, but records no winner and no reward pool, and
+///   flags the result `invalidated` with the admin's `reason`.
+/// - The `RoundResult` PDA of the round now exists, so it can no longer be finalized or tallied.
+/// - Starts the round's `RoundArchive`, so its projects can still be archived and closed.
+/// - Emits a `RoundInvalidated` event.
+pub fn invalidate_vote_round(ctx: Context<InvalidateRound>, reason: String) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let vote_manager = &mut ctx.accounts.vote_manager;
+
+    let round_result = &mut ctx.accounts.round_result;
+    round_result.vote_manager = vote_manager.key();
+    round_result.round = vote_manager.vote_round;
+    round_result.total_votes = vote_manager.round_total_votes;
+    round_result.total_weight = vote_manager.round_total_weight;
+    round_result.participation_count = vote_manager.round_participants;
+    round_result.finalized_at = now;
+    round_result.config_hash = vote_manager.round_config.config_hash;
+    round_result.eligible_weight = vote_manager.eligible_weight;
+    round_result.turnout_bps = vote_manager.turnout_bps()?;
+    round_result.invalidated = true;
+    round_result.invalid_reason = reason;
+    round_result.bump = ctx.bumps.round_result;
+
+    let round_archive = &mut ctx.accounts.round_archive;
+    round_archive.vote_manager = round_result.vote_manager;
+    round_archive.round = round_result.round;
+    round_archive.project_count = vote_manager.project_count;
+    round_archive.bump = ctx.bumps.round_archive;
+
+    emit!(RoundInvalidated {
+        vote_manager: round_result.vote_manager,
+        round: round_result.round,
+        state: vote_manager.round_state,
+        total_votes: round_result.total_votes,
+        refundable_fees: vote_manager.round_fees,
+        reason: round_result.invalid_reason.clone(),
+        invalidated_by: ctx.accounts.owner.key(),
+    });
+
+    // Open the next round.
+    vote_manager.start_next_round(now, clock.slot)?;
+
+    Ok(())
+}
+
+/// Opens a runoff between the projects tied for the lead of the ended round.
+///
+/// **Business Logic:**
+/// - Records the tied weight as `runoff_weight`, so only the projects holding it take votes.
+/// - Moves the round deadline `runoff_duration` seconds past now, reopening a closed round, and
+///   re-hashes the round's rules with the new deadline so it can still be finalized.
+/// - Emits a `RunoffOpened` event, and a `RoundStateChanged` event if the round was closed.
+pub fn open_round_runoff(ctx: Context<OpenRunoff>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    let deadline = now
+        .checked_add(vote_manager.runoff_duration)
+        .ok_or(VoteError::Overflow)?;
+
+    vote_manager.runoff_weight = vote_manager.round_leader_weight;
+    vote_manager.round_config.deadline = Some(deadline);
+    vote_manager.round_config.config_hash = vote_manager.config_hash();
+
+    if vote_manager.round_state == RoundState::Closed {
+        vote_manager.round_state = RoundState::Open;
+        emit!(RoundStateChanged {
+            vote_manager: vote_manager_key,
+            round: vote_manager.vote_round,
+            state: RoundState::Open,
+        });
+    }
+
+    emit!(RunoffOpened {
+        vote_manager: vote_manager_key,
+        round: vote_manager.vote_round,
+        weight: vote_manager.runoff_weight,
+        deadline,
+    });
+
+    Ok(())
+}
+
+/// Records the admin's signature over a finalized round's canonical JSON.
+///
+/// **Business Logic:**
+/// - The signature itself is checked by the Ed25519 program instruction that must immediately
+///   precede this one; this handler only checks that instruction signs the right message.
+/// - Stores the signature in a `RoundAttestation` PDA, so off-chain consumers can verify the result
+///   without trusting the RPC node.
+/// - Emits a `RoundAttested` event.
+pub fn attest_round_result(ctx: Context<AttestRound>) -> Result<()> {
+    let round_result = &ctx.accounts.round_result;
+    let attester = ctx.accounts.owner.key();
+
+    // Load the instruction preceding this one from the instructions sysvar.
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current = load_current_index_checked(&instructions)?;
+    require!(current > 0, VoteError::MissingAttestation);
+    let ed25519_ix = load_instruction_at_checked(current as usize - 1, &instructions)?;
+
+    let signature = verify_ed25519_instruction(
+        &ed25519_ix,
+        &attester,
+        round_result.canonical_json().as_bytes(),
+    )?;
+
+    let attestation = &mut ctx.accounts.round_attestation;
+    attestation.round_result = round_result.key();
+    attestation.attester = attester;
+    attestation.signature = signature;
+    attestation.attested_at = Clock::get()?.unix_timestamp;
+    attestation.bump = ctx.bumps.round_attestation;
+
+    emit!(RoundAttested {
+        vote_manager: round_result.vote_manager,
+        round: round_result.round,
+        attester,
+        signature,
+    });
+
+    Ok(())
+}
+
+/// Checks that `ix` is an Ed25519 program instruction verifying one signature of `message` by
+/// `signer`, and returns that signature.
+///
+/// Every offset must point into `ix` itself, otherwise the verified bytes could live in another
+/// instruction of the transaction.
+fn verify_ed25519_instruction(
+    ix: &Instruction,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<[u8; 64]> {
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        VoteError::MissingAttestation
+    );
+
+    // Layout: signature count, padding, then seven little-endian `u16` offsets.
+    let data = &ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        VoteError::InvalidAttestation
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    require!(
+        [4, 8, 14].iter().all(|&at| read_u16(at) == u16::MAX),
+        VoteError::InvalidAttestation
+    );
+    let field = |offset: u16, len: u16| {
+        let start = offset as usize;
+        data.get(start..start + len as usize)
+            .ok_or_else(|| error!(VoteError::InvalidAttestation))
+    };
+    let signature = field(read_u16(2), 64)?;
+    let pubkey = field(read_u16(6), 32)?;
+    let signed = field(read_u16(10), read_u16(12))?;
+
+    require!(pubkey == signer.as_ref(), VoteError::InvalidAttestation);
+    require!(signed == message, VoteError::InvalidAttestation);
+
+    let mut out = [0u8; 64];
+    out.copy_from_slice(signature);
+    Ok(out)
+}
+
+/// Pre-registers a sequence of scheduled rounds in the VoteManager's Campaign PDA.
+///
+/// **Business Logic:**
+/// - Stores the rounds as given; `open_campaign_round` applies them one after the other.
+/// - Emits a `CampaignCreated` event.
+pub fn create_vote_campaign(
+    ctx: Context<CreateCampaign>,
+    rounds: Vec<CampaignRound>,
+) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.vote_manager = ctx.accounts.vote_manager.key();
+    campaign.rounds = rounds;
+    campaign.next_round = 0;
+    campaign.last_opened_round = 0;
+    campaign.bump = ctx.bumps.campaign;
+
+    emit!(CampaignCreated {
+        vote_manager: campaign.vote_manager,
+        rounds: campaign.rounds.len() as u8,
+        starts_at: campaign.rounds[0].start_ts,
+        ends_at: campaign.rounds[campaign.rounds.len() - 1].end_ts,
+    });
+
+    Ok(())
+}
+
+/// Opens the campaign's next scheduled round on the current voting round.
+///
+/// **Business Logic:**
+/// - Skips scheduled rounds whose end has already passed.
+/// - Sets the round's schedule to the scheduled start and end, and records its rules again, since
+///   the schedule is part of them.
+/// - Emits a `CampaignRoundOpened` event.
+pub fn open_vote_campaign_round(ctx: Context<OpenCampaignRound>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let campaign = &mut ctx.accounts.campaign;
+
+    // Find the first scheduled round that has not ended yet.
+    let index = (campaign.next_round as usize..campaign.rounds.len())
+        .find(|&i| campaign.rounds[i].end_ts > now)
+        .ok_or(VoteError::CampaignFinished)?;
+    let scheduled = campaign.rounds[index].clone();
+
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    vote_manager.round_start_ts = scheduled.start_ts;
+    vote_manager.round_duration = scheduled
+        .end_ts
+        .checked_sub(scheduled.start_ts)
+        .ok_or(VoteError::Overflow)?;
+    vote_manager.open_round_config(now, clock.slot);
+
+    campaign.next_round = (index + 1) as u8;
+    campaign.last_opened_round = vote_manager.vote_round;
+
+    emit!(CampaignRoundOpened {
+        vote_manager: vote_manager.key(),
+        round: vote_manager.vote_round,
+        index: index as u8,
+        theme: scheduled.theme,
+        start_ts: scheduled.start_ts,
+        end_ts: scheduled.end_ts,
+    });
+
+    Ok(())
+}
+
+/// Closes the campaign, cancelling the rounds it has not opened yet.
+///
+/// **Business Logic:**
+/// - Rounds already opened keep their schedule.
+/// - Closing is done by the `close = owner` constraint, returning the rent to the admin.
+pub fn close_vote_campaign(_ctx: Context<CloseCampaign>) -> Result<()> {
+    Ok(())
+}
+
+/// Casts a ranked ballot for the current round.
+///
+/// **Business Logic:**
+/// - The ranked projects are passed as remaining accounts, most preferred first; each must be an
+///   approved, not disqualified project of the current round, ranked once. The transfer hook's
+///   accounts may follow them.
+/// - Charges the voting fee and fixes the ballot weight at cast time, like `commit_vote`.
+/// - Registers the ranked projects as candidates of the round's `RankedTally`.
+/// - Counts the ballot as one vote of the round; project tallies are left to `tally_ranked_round`.
+/// - Emits a `BallotCast` event.
+pub fn cast_ranked_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CastRankedBallot<'info>>,
+) -> Result<()> {
+    let vote_manager_key = ctx.accounts.vote_manager.key();
+    let round = ctx.accounts.vote_manager.vote_round;
+    let weight = ctx
+        .accounts
+        .vote_manager
+        .round_config
+        .weight_curve
+        .weight(ctx.accounts.token.amount);
+
+    // Validate the rankings.
+    let (ranked, hook_accounts) = split_hook_accounts(ctx.remaining_accounts);
+    let mut rankings = Vec::with_capacity(ranked.len());
+    for info in ranked {
+        let account = AccountLoader::<ProjectData>::try_from(info)?;
+        let project = account.load()?;
+        require_keys_eq!(
+            project.vote_manager,
+            vote_manager_key,
+            VoteError::WrongVoteManager
+        );
+        require!(project.vote_round == round, VoteError::WrongRound);
+        require!(project.pending == 0, VoteError::ProjectPending);
+        require!(project.disqualified == 0, VoteError::ProjectDisqualified);
+        require!(!rankings.contains(&info.key()), VoteError::IncorrectBallot);
+        rankings.push(info.key());
+    }
+
+    // Execute the transfer of the voting fee.
+    let collected = charge_vote_fee(
+        ctx.accounts.vote_manager.fee_mode,
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.token,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.signer,
+        hook_accounts,
+        ctx.accounts.vote_manager.round_config.vote_fee,
+    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    vote_manager.round_fees = vote_manager
+        .round_fees
+        .checked_add(collected)
+        .ok_or(VoteError::Overflow)?;
+
+    // Register the ranked projects as candidates of the round.
+    let tally = &mut ctx.accounts.ranked_tally;
+    if tally.vote_manager == Pubkey::default() {
+        tally.vote_manager = vote_manager_key;
+        tally.round = round;
+        tally.pass = 1;
+        tally.bump = ctx.bumps.ranked_tally;
+    }
+    for project in &rankings {
+        tally.add_candidate(*project)?;
+    }
+    tally.ballots = tally.ballots.checked_add(1).ok_or(VoteError::Overflow)?;
+
+    // Record the ballot.
+    let ballot = &mut ctx.accounts.ballot;
+    ballot.vote_manager = vote_manager_key;
+    ballot.round = round;
+    ballot.voter = ctx.accounts.signer.key();
+    ballot.rankings = rankings.clone();
+    ballot.weight = weight;
+    ballot.counted_pass = 0;
+    ballot.bump = ctx.bumps.ballot;
+
+    // Update the running round tallies.
+    let vote_manager = &mut ctx.accounts.vote_manager;
+    vote_manager.round_participants = vote_manager
+        .round_participants
+        .checked_add(1)
+        .ok_or(VoteError::Overflow)?;
+    vote_manager.round_total_votes = vote_manager
+        .round_total_votes
+        .checked_add(1)
+        .ok_or(VoteError::Overflow)?;
+    vote_manager.round_total_weight = vote_manager
+        .round_total_weight
+        .checked_add(weight)
+        .ok_or(VoteError::Overflow)?;
+
+    emit!(BallotCast {
+        vote_manager: vote_manager_key,
+        round,
+        voter: ballot.voter,
+        rankings,
+        weight,
+    });
+
+    Ok(())
+}
+
+/// Counts a page of ranked ballots for the current instant-runoff pass, and seals the round once
+/// the winner is known.
+///
+/// **Business Logic:**
+/// - The ballots are passed as writable remaining accounts; each counts its full weight for its
+///   highest-ranked candidate still running, and ballots already counted in the current pass are
+///   skipped, so pages can overlap or be resent.
+/// - Once every ballot was counted, a candidate holding a strict majority of the counted weight,
+///   or the last one running, wins; otherwise the weakest candidate is eliminated and the next
+///   pass counts every ballot again.
+/// - Emits a `RankedRoundTallied` event when the winner is found.
+/// - Passing `round_result` seals the round like `finalize_round`, with the winner's final
+///   instant-runoff weight; it fails until the tally is complete.
+pub fn tally_ranked_vote_round<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TallyRankedRound<'info>>,
+) -> Result<()> {
+    let tally = &mut ctx.accounts.ranked_tally;
+
+    // Count the page for the current pass.
+    if !tally.complete {
+        for info in ctx.remaining_accounts {
+            let mut ballot = Account::<Ballot>::try_from(info)?;
+            require_keys_eq!(
+                ballot.vote_manager,
+                tally.vote_manager,
+                VoteError::WrongVoteManager
+            );
+            require!(ballot.round == tally.round, VoteError::WrongRound);
+            require!(
+                info.is_writable,
+                anchor_lang::error::ErrorCode::ConstraintMut
+            );
+            if ballot.counted_pass >= tally.pass {
+                continue;
+            }
+
+            tally.count_ballot(&ballot.rankings, ballot.weight)?;
+            ballot.counted_pass = tally.pass;
+            ballot.exit(&crate::ID)?;
+        }
+
+        // Resolve the pass once every ballot was counted.
+        if tally.counted == tally.ballots {
+            tally.resolve_pass()?;
+            if tally.complete {
+                let vote_manager = &mut ctx.accounts.vote_manager;
+                vote_manager.round_leader = tally.winner;
+                vote_manager.round_leader_weight = tally.winner_weight;
+
+                emit!(RankedRoundTallied {
+                    vote_manager: tally.vote_manager,
+                    round: tally.round,
+                    passes: tally.pass,
+                    winner: tally.winner,
+                    winner_weight: tally.winner_weight,
+                });
+            }
+        }
+    }
+
+    // Seal the round once the winner is known.
+    if let Some(round_result) = ctx.accounts.round_result.as_deref_mut() {
+        require!(tally.complete, VoteError::RankedTallyIncomplete);
+        let Some(round_archive) = ctx.accounts.round_archive.as_deref_mut() else {
+            return err!(VoteError::RoundArchiveMissing);
+        };
+        // Both optional accounts are present here, so Anchor resolved their bumps.
+        let (Some(result_bump), Some(archive_bump)) =
+            (ctx.bumps.round_result, ctx.bumps.round_archive)
+        else {
+            return err!(VoteError::RoundArchiveMissing);
+        };
+        let winner = match &ctx.accounts.winner {
+            Some(winner) => {
+                require_keys_eq!(winner.key(), tally.winner, VoteError::WrongWinner);
+                Some((winner, tally.winner_weight))
+            }
+            None => {
+                require_keys_eq!(tally.winner, Pubkey::default(), VoteError::WrongWinner);
+                None
+            }
+        };
+        seal_round(
+            &mut ctx.accounts.vote_manager,
+            round_result,
+            result_bump,
+            round_archive,
+            archive_bump,
+            winner,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Decodes a VoteManager account still in the layout preceding `u64` round numbers.
+///
+/// Fails with `AlreadyMigrated` if the account does not have the legacy size.
+pub fn read_legacy_vote_manager(vote_manager: &AccountInfo) -> Result<LegacyVoteManager> {
+    let data = vote_manager.try_borrow_data()?;
+    require!(
+        data.len() == 8 + LegacyVoteManager::INIT_SPACE,
+        VoteError::AlreadyMigrated
+    );
+    require!(
+        data[..8] == VoteManager::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    LegacyVoteManager::deserialize(&mut &data[8..])
+        .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+/// Rewrites a legacy VoteManager in the current layout.
+///
+/// **Business Logic:**
+/// - Widens `vote_round` and `round_config.round` to `u64`; every other field is copied as is.
+/// - Freezes the current fee, weight curve and deadline in `round_config`, which the legacy
+///   layout read from the VoteManager directly.
+/// - Leaves the per-project vote cap, ranked-choice voting, quadratic pricing and the eligibility
+///   snapshot, which the legacy layout lacks, disabled.
+/// - Leaves the current round `Open`, as legacy rounds accepted votes without being opened.
+/// - Grows the account to the current size, the admin topping up its rent exemption.
+pub fn migrate_vote_manager_layout(
+    ctx: Context<MigrateVoteManager>,
+    legacy: LegacyVoteManager,
+) -> Result<()> {
+    let vote_manager = VoteManager {
+        version: VoteManager::VERSION,
+        admin: legacy.admin,
+        creator: legacy.creator,
+        election_id: 0,
+        pending_admin: legacy.pending_admin,
+        tk_mint: legacy.tk_mint,
+        tk_program: legacy.tk_program,
+        vote_round: u64::from(legacy.vote_round),
+        vote_fee: legacy.vote_fee,
+        max_votes_per_round: legacy.max_votes_per_round,
+        max_votes_per_project: 0,
+        round_start_ts: legacy.round_start_ts,
+        round_duration: legacy.round_duration,
+        weight_curve: legacy.weight_curve,
+        round_total_votes: legacy.round_total_votes,
+        round_total_weight: legacy.round_total_weight,
+        round_participants: legacy.round_participants,
+        round_leader: legacy.round_leader,
+        round_leader_weight: legacy.round_leader_weight,
+        commit_reveal: legacy.commit_reveal,
+        reveal_duration: legacy.reveal_duration,
+        stealth_voting: legacy.stealth_voting,
+        ranked_choice: false,
+        quadratic_pricing: false,
+        eligibility_root: [0; 32],
+        reward_claim_window: legacy.reward_claim_window,
+        proposal_count: legacy.proposal_count,
+        proposal_deposit: legacy.proposal_deposit,
+        proposal_voting_period: legacy.proposal_voting_period,
+        proposal_quorum: legacy.proposal_quorum,
+        project_deposit: legacy.project_deposit,
+        round_config: RoundConfig {
+            round: u64::from(legacy.round_config.round),
+            config_hash: legacy.round_config.config_hash,
+            opened_at: legacy.round_config.opened_at,
+            opened_slot: 0,
+            vote_fee: legacy.vote_fee,
+            sol_vote_fee: 0,
+            weight_curve: legacy.weight_curve,
+            deadline: match legacy.round_duration {
+                0 => None,
+                duration => legacy.round_start_ts.checked_add(duration),
+            },
+        },
+        round_state: RoundState::Open,
+        receipt_mint: Pubkey::default(),
+        vote_receipts: false,
+        escrow_mode: false,
+        max_lock_duration: 0,
+        paused: false,
+        fee_mode: FeeMode::Collect,
+        eligible_weight: 0,
+        retract_refund: false,
+        round_leaderboard: false,
+        referendum_count: 0,
+        deposit_tranche_votes: 0,
+        deposit_refund_votes: 0,
+        min_holding_balance: 0,
+        min_holding_slots: 0,
+        max_projects: 0,
+        project_count: 0,
+        tie_break: TieBreakPolicy::default(),
+        runoff_duration: 0,
+        round_leader_created_slot: 0,
+        round_tied: false,
+        runoff_weight: 0,
+        sol_vote_fee: 0,
+        round_fees: 0,
+        owed_fees: 0,
+        access_mode: AccessMode::Open,
+        bump: legacy.bump,
+    };
+
+    // Top up the rent exemption for the wider layout.
+    let account = ctx.accounts.vote_manager.to_account_info();
+    let space = 8 + VoteManager::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if rent > 0 {
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.owner.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, rent)?;
+    }
+
+    account.realloc(space, false)?;
+    let mut data = account.try_borrow_mut_data()?;
+    vote_manager.try_serialize(&mut &mut data[..])
+}
+
+/// Upgrades a VoteManager, ProjectData or VoterData account to the current layout version.
+///
+/// **Business Logic:**
+/// - Picks the account type by its discriminator and decodes it with
+///   `Versioned::try_deserialize_versioned`; every field keeps its value.
+/// - Fails with `AlreadyMigrated` if the account already has the current version.
+/// - Resizes the account to the current layout, the payer topping up its rent exemption.
+/// - Emits an `AccountMigrated` event.
+pub fn migrate_versioned_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let discriminator: [u8; 8] = account
+        .try_borrow_data()?
+        .get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+    let (from_version, to_version) = match discriminator {
+        VoteManager::DISCRIMINATOR => upgrade_account::<VoteManager>(&ctx, &account)?,
+        ProjectData::DISCRIMINATOR => upgrade_account::<ProjectData>(&ctx, &account)?,
+        VoterData::DISCRIMINATOR => upgrade_account::<VoterData>(&ctx, &account)?,
+        _ => return err!(ErrorCode::AccountDiscriminatorMismatch),
+    };
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version,
+        to_version,
+    });
+
+    Ok(())
+}
+
+/// Rewrites `account` as a `T` of the current version, returning the version it had and the one
+/// it has now.
+fn upgrade_account<'info, T: Versioned>(
+    ctx: &Context<MigrateAccount<'info>>,
+    account: &AccountInfo<'info>,
+) -> Result<(u8, u8)> {
+    let mut upgraded = T::try_deserialize_versioned(&account.try_borrow_data()?)?;
+    let from_version = upgraded.version();
+    require!(from_version < T::VERSION, VoteError::AlreadyMigrated);
+    upgraded.set_version(T::VERSION);
+
+    // Top up the rent exemption for the current layout.
+    let space = upgraded.space();
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent > 0 {
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, rent)?;
+    }
+
+    account.realloc(space, false)?;
+    let mut data = account.try_borrow_mut_data()?;
+    upgraded.try_serialize(&mut &mut data[..])?;
+    Ok((from_version, T::VERSION))
+}
+
+/// Defines the accounts required to initialize the VoteManager.
+///
+/// **Business Logic:**
+/// - Creates the VoteManager account using PDA derivation with seeds.
+/// - The initializing admin's key becomes the VoteManager's permanent `creator` seed, and
+///   `election_id` its election seed, so one admin can run any number of elections side by side.
+/// - Takes the mint and token program as accounts, so a misconfigured mint fails here rather
+///   than at the first vote: the mint must be owned by the token program and have
+///   the admin's fee account must already exist. Transfers pass the mint's own decimals, so it
+///   may have any.
+/// - Uses `init`, so an existing VoteManager cannot be initialized again; later changes go through
+///   `Admin` and the other admin contexts.
+#[derive(Accounts)]
+#[instruction(election_id: u64)]
+pub struct Initialize<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + VoteManager::INIT_SPACE,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                owner.key().as_ref(),
+                &election_seed(election_id)
+            ],
+            bump
+        )]
+    pub vote_data: Account<'info, VoteManager>, /* The VoteManager account managing the
+                                                 * voting process. */
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    #[account(
+            mint::token_program = token_program
+        )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = owner,
+        )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>, // Admin's fee account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program of the mint.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to migrate a legacy VoteManager.
+///
+/// **Business Logic:**
+/// - Takes the VoteManager unchecked, since `Account` cannot decode the legacy layout; the seeds
+///   and owner are checked here, the layout and admin by the instruction.
+/// - The admin pays for the extra space.
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct MigrateVoteManager<'info> {
+    /// CHECK: Legacy VoteManager layout, decoded by `read_legacy_vote_manager`.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                creator.as_ref()
+            ],
+            bump,
+            owner = crate::ID
+        )]
+    pub vote_manager: UncheckedAccount<'info>, // The VoteManager account to migrate.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to upgrade an account to the current layout version.
+///
+/// **Business Logic:**
+/// - Takes the account unchecked, since `Account` cannot decode an earlier layout; it must be
+///   owned by the program, its type and layout are checked by the instruction.
+/// - Permissionless: the upgrade keeps every field, only the payer's rent top-up changes hands.
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: VoteManager, ProjectData or VoterData of any version, decoded by
+    /// `Versioned::try_deserialize_versioned`.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>, // The account to upgrade.
+    #[account(mut)]
+    pub payer: Signer<'info>, // Anyone paying the extra rent.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to increment the voting round.
+///
+/// **Business Logic:**
+/// - Re-derives the VoteManager PDA from its `creator`, like `Admin`.
+/// - `role` is the signer's RoleGrant, passed by Operators; the handler checks that the signer is
+///   the admin or holds the Operator role.
+#[derive(Accounts)]
+pub struct IncrementRound<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_data.creator.as_ref(),
+                &vote_data.election_seed()
+            ],
+            bump = vote_data.bump
+        )]
+    pub vote_data: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's or an Operator's signer account.
+    #[account(
+            seeds = [ROLE_SEED, vote_data.key().as_ref(), owner.key().as_ref()],
+            bump = role.bump
+        )]
+    pub role: Option<Account<'info, RoleGrant>>, // The signer's role, none for the admin.
+}
+
+/// Defines the accounts required for administrative actions.
+///
+/// **Business Logic:**
+/// - Re-derives the VoteManager PDA from its `creator`, so it stays reachable after an admin
+///   transfer.
+/// - The handler checks that the signer is the current (or pending) admin.
+#[derive(Accounts)]
+pub struct Admin<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_data.creator.as_ref(),
+                &vote_data.election_seed()
+            ],
+            bump = vote_data.bump
+        )]
+    pub vote_data: Account<'info, VoteManager>, /* The VoteManager account managing the
+                                                 * voting process. */
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+}
+
+/// Defines the accounts required to switch vote receipts.
+///
+/// **Business Logic:**
+/// - The receipt mint must be a 0-decimal Token-2022 mint whose mint authority is the
+///   VoteManager's receipt authority PDA, so receipts are only minted by `do_vote`.
+/// - The handler checks that the signer is the admin and that the mint is non-transferable.
+#[derive(Accounts)]
+pub struct SetVoteReceipts<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_data.creator.as_ref(),
+                &vote_data.election_seed()
+            ],
+            bump = vote_data.bump
+        )]
+    pub vote_data: Account<'info, VoteManager>, /* The VoteManager account managing the
+                                                 * voting process. */
+    pub owner: Signer<'info>, // The admin's signer account.
+    /// CHECK: PDA signing receipt mints; holds no data.
+    #[account(
+            seeds = [
+                RECEIPT_AUTHORITY_SEED,
+                vote_data.key().as_ref()
+            ],
+            bump
+        )]
+    pub receipt_authority: UncheckedAccount<'info>,
+    #[account(
+            mint::token_program = token_program,
+            mint::authority = receipt_authority,
+            mint::decimals = 0
+        )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>, // Mint of the vote receipts.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program.
+}
+
+/// Closes a VoterData account of a finalized round.
+///
+/// **Business Logic:**
+/// - Closing is done by the `close = voter` constraint, returning the rent to the voter who paid
+///   for the account.
+/// - Decrements the project's `voter_records` so the project can be closed once all are gone.
+pub fn close_vote_voter_data(ctx: Context<CloseVoterData>) -> Result<()> {
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.voter_records = project.voter_records.checked_sub(1).ok_or(VoteError::Overflow)?;
+    Ok(())
+}
+
+/// Closes a stealth VoterData account of a finalized round.
+///
+/// **Business Logic:**
+/// - Closing is done by the `close = signer` constraint, returning the rent to the voter who
+///   proved ownership of the blinded id.
+/// - Decrements the project's `voter_records` so the project can be closed once all are gone.
+pub fn close_stealth_vote_voter_data(ctx: Context<CloseStealthVoterData>) -> Result<()> {
+    let mut project = ctx.accounts.project.load_mut()?;
+    project.voter_records = project.voter_records.checked_sub(1).ok_or(VoteError::Overflow)?;
+    Ok(())
+}
+
+/// Closes a ProjectData account of a finalized round.
+///
+/// **Business Logic:**
+/// - A listing bond still escrowed is forfeited: the treasury keeps it as collected revenue and a
+///   `DepositForfeited` event is emitted.
+/// - Closing is done by the `close = payer` constraint, returning the rent to the admin who added
+///   the project.
+pub fn close_vote_project_data(ctx: Context<CloseProjectData>) -> Result<()> {
+    let project = ctx.accounts.project.load()?;
+    let forfeited = project.deposit;
+    if forfeited == 0 {
+        return Ok(());
+    }
+
+    let Some(treasury) = ctx.accounts.treasury.as_mut() else {
+        return err!(VoteError::TreasuryMissing);
+    };
+    treasury.total_escrowed =
+        treasury.total_escrowed.checked_sub(forfeited).ok_or(VoteError::Overflow)?;
+    treasury.total_collected =
+        treasury.total_collected.checked_add(forfeited).ok_or(VoteError::Overflow)?;
+
+    emit!(DepositForfeited {
+        vote_manager: ctx.accounts.vote_manager.key(),
+        project: ctx.accounts.project.key(),
+        id: project.id().to_string(),
+        amount: forfeited,
+    });
+
+    Ok(())
+}
+
+/// Defines the accounts required to add a new project for voting.
+///
+/// **Business Logic:**
+/// - Initializes a new ProjectData account with PDA derivation ensuring uniqueness.
+/// - Associates the project with the current voting round and fee.
+#[derive(Accounts)]
+#[instruction(id:String)]
+pub struct NewVoteProject<'info> {
+    #[account(
+            // Initialize a new ProjectData account with unique PDA seeds.
+            init,
+            payer = owner,
+            space = 8 + std::mem::size_of::<ProjectData>(),
+            seeds = [
+                PROJECT_SEED,
+                id.as_bytes(),                         // Unique project identifier.
+                &vote_manager.vote_round.to_le_bytes(), // Current voting round to ensure uniqueness across rounds.
+                vote_manager.admin.as_ref(),            // Admin's public key, whoever adds it.
+                &vote_manager.election_seed()           // Election the project runs in.
+            ],
+            bump)]
+    pub project_data: AccountLoader<'info, ProjectData>, // The new project's data account.
+    #[account(mut)]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's or an Operator's signer account.
+    #[account(
+            seeds = [ROLE_SEED, vote_manager.key().as_ref(), owner.key().as_ref()],
+            bump = role.bump
+        )]
+    pub role: Option<Account<'info, RoleGrant>>, // The signer's role, none for the admin.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to add a batch of projects for voting.
+///
+/// **Business Logic:**
+/// - The ProjectData PDAs are passed as remaining accounts and created by the handler, as their
+///   number varies with the batch.
+#[derive(Accounts)]
+pub struct NewVoteProjects<'info> {
+    #[account(mut)]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's or an Operator's signer account.
+    #[account(
+            seeds = [ROLE_SEED, vote_manager.key().as_ref(), owner.key().as_ref()],
+            bump = role.bump
+        )]
+    pub role: Option<Account<'info, RoleGrant>>, // The signer's role, none for the admin.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to submit a project without admin rights.
+///
+/// **Business Logic:**
+/// - Initializes the ProjectData account under the admin's key, like `add_project`, so voters
+///   find submitted and added projects at the same addresses.
+/// - The submitter pays the rent and the deposit.
+#[derive(Accounts)]
+#[instruction(id:String)]
+pub struct SubmitProject<'info> {
+    #[account(
+            init,
+            payer = submitter,
+            space = 8 + std::mem::size_of::<ProjectData>(),
+            seeds = [
+                PROJECT_SEED,
+                id.as_bytes(),
+                &vote_manager.vote_round.to_le_bytes(),
+                vote_manager.admin.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump)]
+    pub project_data: AccountLoader<'info, ProjectData>, // The submitted project's data account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury escrowing the deposit.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = submitter_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = submitter_token_account.owner == submitter.key() @ VoteError::WrongTokenOwner
+        )]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>, // Submitter's TTT account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub submitter: Signer<'info>, // The submitter's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to approve a submitted project.
+///
+/// **Business Logic:**
+/// - Only pending projects of the current round can be approved.
+/// - The deposit is refunded to the submitter's token account.
+#[derive(Accounts)]
+pub struct ApproveProject<'info> {
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.vote_round == vote_manager.vote_round @ VoteError::WrongRound,
+            constraint = project.load()?.pending != 0 @ VoteError::ProjectNotPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being approved.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury escrowing the deposit.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = submitter_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = submitter_token_account.owner == project.load()?.payer @ VoteError::WrongTokenOwner
+        )]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>, // Receives the deposit.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to reject a submitted project.
+///
+/// **Business Logic:**
+/// - Pending projects of any round can be rejected, so stale submissions never strand deposits.
+/// - Closes the ProjectData account, returning the rent to the submitter.
+#[derive(Accounts)]
+pub struct RejectProject<'info> {
+    #[account(
+            mut,
+            close = payer,
+            has_one = payer @ VoteError::WrongPayer,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.pending != 0 @ VoteError::ProjectNotPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being rejected.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury escrowing the deposit.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = submitter_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = submitter_token_account.owner == project.load()?.payer @ VoteError::WrongTokenOwner
+        )]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>, // Receives a refund.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub payer: SystemAccount<'info>, // The submitter receiving the rent.
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to claim a listing bond's refund tranches.
+///
+/// **Business Logic:**
+/// - Only the submitter can claim, into their token account.
+/// - Approved projects of any round can claim, disqualified ones cannot.
+#[derive(Accounts)]
+pub struct ClaimDepositTranche<'info> {
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.payer == submitter.key() @ VoteError::WrongPayer,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The bonded project.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury escrowing the bond.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = submitter_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = submitter_token_account.owner == project.load()?.payer @ VoteError::WrongTokenOwner
+        )]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>, // Receives the tranches.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    pub submitter: Signer<'info>, // The submitter's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to remove a project.
+///
+/// **Business Logic:**
+/// - Closes the ProjectData account and returns its rent to the admin.
+/// - Only projects of this VoteManager without votes can be removed.
+/// - Bonded listings stay until their round is cleaned up, which forfeits the bond.
+#[derive(Accounts)]
+pub struct RemoveProject<'info> {
+    #[account(
+            mut,
+            close = owner,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.vote_count == 0 @ VoteError::ProjectHasVotes,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending,
+            constraint = project.load()?.deposit == 0 @ VoteError::ProjectBonded
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being removed.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+}
+
+/// Defines the accounts required to update a project's metadata.
+///
+/// **Business Logic:**
+/// - Only projects of this VoteManager can be described, by its admin.
+#[derive(Accounts)]
+pub struct UpdateProjectMetadata<'info> {
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being described.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    pub owner: Signer<'info>, // The admin's signer account.
+}
+
+/// Defines the accounts required to disqualify a project.
+///
+/// **Business Logic:**
+/// - Only projects of this VoteManager can be disqualified, and only once.
+#[derive(Accounts)]
+pub struct DisqualifyProject<'info> {
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being disqualified.
+    #[account(
+            mut,
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    pub owner: Signer<'info>, // The admin's signer account.
+}
+
+/// Defines the accounts required to claim a refund for a disqualified project or invalidated round.
+///
+/// **Business Logic:**
+/// - The VoterData PDA is re-derived from the signer and the project, so voters only claim their
+///   own record; stealth records cannot be claimed.
+/// - The project must be disqualified, or `round_result` passed and its round invalidated, and the
+///   record must hold unrefunded fees.
+/// - The refund is paid into the voter's token account of the governance mint, fees paid in SOL
+///   to the voter's wallet.
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTER_SEED,
+                &vote_manager.election_seed(),
+                &project.load()?.vote_round.to_le_bytes(),
+                signer.key().as_ref(),
+                project.load()?.id().as_ref(),
+            ],
+            bump = voter_data.load()?.bump,
+            constraint = voter_data.load()?.refund_due() @ VoteError::NoRefundDue
+        )]
+    pub voter_data: AccountLoader<'info, VoterData>, // The voter record being refunded.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account, receiving SOL fee refunds.
+    #[account(
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.disqualified != 0
+                || round_result.as_ref().is_some_and(|result| result.invalidated)
+                @ VoteError::ProjectNotDisqualified
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The disqualified project, or one of a voided round.
+    #[account(
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = round_result.bump
+        )]
+    pub round_result: Option<Account<'info, RoundResult>>, // The project's round, if invalidated.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury holding the fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account receiving the refund.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to claim a reward for backing a round's winner.
+///
+/// **Business Logic:**
+/// - The VoterData PDA is re-derived from the signer and the winner's id in the `RoundResult`, so
+///   voters only claim their own record of the winning project, even once the project's
+///   account was closed.
+/// - The `RoundResult` must exist and not be invalidated, i.e. the round be finalized, and the
+///   record must hold an unclaimed, non-zero share.
+/// - The reward is paid into the voter's token account of the governance mint.
+#[derive(Accounts)]
+pub struct ClaimReward<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTER_SEED,
+                &vote_manager.election_seed(),
+                &round_result.round.to_le_bytes(),
+                signer.key().as_ref(),
+                round_result.winner_id.as_ref(),
+            ],
+            bump = voter_data.load()?.bump,
+            constraint = voter_data.load()?.reward_due(&round_result) > 0 @ VoteError::NoRewardDue
+        )]
+    pub voter_data: AccountLoader<'info, VoterData>, // The voter record of the winning project.
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            mut,
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &round_result.round.to_le_bytes()
+            ],
+            bump = round_result.bump,
+            constraint = !round_result.invalidated @ VoteError::RoundInvalid
+        )]
+    pub round_result: Account<'info, RoundResult>, // The finalized round.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury holding the fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account receiving the reward.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required for retracting a vote.
+///
+/// **Business Logic:**
+/// - Ensures the voter retracts their own record for a project of the current round.
+/// - Facilitates the refund of the record's fees from the treasury's fee account to the voter's
+///   token account when the VoteManager refunds retractions.
+#[derive(Accounts)]
+pub struct RetractVote<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTER_SEED,
+                &vote_manager.election_seed(),
+                &project.load()?.vote_round.to_le_bytes(),
+                signer.key().as_ref(),
+                project.load()?.id().as_ref(),
+            ],
+            bump = voter_data.load()?.bump,
+            constraint = project.load()?.vote_round == vote_manager.vote_round @ VoteError::WrongRound
+        )]
+    pub voter_data: AccountLoader<'info, VoterData>, // The voter record being retracted.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account, receiving SOL fee refunds.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project the votes are retracted from.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury holding the fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account receiving the refund.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    #[account(
+            mut,
+            seeds = [
+                LEADERBOARD_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = leaderboard.load()?.bump
+        )]
+    pub leaderboard: Option<AccountLoader<'info, Leaderboard>>, // The round's standings, if any.
+    #[account(
+            mut,
+            seeds = [
+                ROUND_VOTES_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes(),
+                signer.key().as_ref()
+            ],
+            bump = round_votes.bump
+        )]
+    pub round_votes: Account<'info, RoundVotes>, // The voter's votes in the round.
+}
+
+/// Defines the accounts required for casting a vote.
+///
+/// **Business Logic:**
+/// - Initializes a VoterData account to track the voter's activity in the current round.
+/// - Initializes the voter's VoteLock on their first vote of the election.
+/// - Ensures the voter's token account is authorized and has sufficient balance.
+/// - Facilitates the transfer of voting fees from the voter's token account to the treasury's fee
+///   account.
+#[derive(Accounts)]
+pub struct Voter<'info> {
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + std::mem::size_of::<VoterData>(),
+            seeds = [
+                VOTER_SEED,
+                &vote_manager.election_seed(),     // Election the vote is cast in.
+                &project.load()?.vote_round.to_le_bytes(), // Round the vote is cast in.
+                signer.key().as_ref(),             // Voter's public key to ensure unique PDA per voter per round.
+                project.load()?.id().as_ref(),
+            ],
+            bump,
+            constraint = project.load()?.vote_round == vote_manager.vote_round @ VoteError::WrongRound
+            )]
+    pub voter_data: AccountLoader<'info, VoterData>, // Tracks the voter's voting activity.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being voted for.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, /* Voter's token account holding ttt
+                                                       * tokens. */
+    pub token_program: Interface<'info, TokenInterface>, /* Token program interface for
+                                                          * token operations. */
+    pub system_program: Program<'info, System>, // Solana System program.
+    #[account(
+            mut,
+            seeds = [
+                LEADERBOARD_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = leaderboard.load()?.bump
+        )]
+    pub leaderboard: Option<AccountLoader<'info, Leaderboard>>, // The round's standings, if any.
+    #[account(
+            seeds = [
+                HOLDING_ATTESTATION_SEED,
+                vote_manager.key().as_ref(),
+                signer.key().as_ref()
+            ],
+            bump = holding_attestation.bump
+        )]
+    pub holding_attestation: Option<Account<'info, HoldingAttestation>>, // Voter's holding, if required.
+    /// CHECK: Address of the voter's AccessListEntry on the active list, which may not exist;
+    /// verified by `check_access`.
+    pub access_entry: Option<UncheckedAccount<'info>>, // Voter's access list entry, if required.
+    #[account(
+            seeds = [FEE_DISCOUNT_SEED, vote_manager.key().as_ref()],
+            bump = fee_discounts.bump
+        )]
+    pub fee_discounts: Option<Account<'info, FeeDiscountSchedule>>, // Discounts the fee, if passed.
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + VoteLock::INIT_SPACE,
+            seeds = [
+                VOTE_LOCK_SEED,
+                vote_manager.key().as_ref(),
+                signer.key().as_ref()
+            ],
+            bump
+        )]
+    pub vote_lock: Account<'info, VoteLock>, // The round the voter last voted in.
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + RoundVotes::INIT_SPACE,
+            seeds = [
+                ROUND_VOTES_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes(),
+                signer.key().as_ref()
+            ],
+            bump
+        )]
+    pub round_votes: Account<'info, RoundVotes>, // The voter's votes in the round.
+}
+
+/// Defines the accounts required for casting a stealth vote.
+///
+/// **Business Logic:**
+/// - Initializes a VoterData account derived from the voter-provided `blinded_voter` instead of
+///   the signer, under its own namespace so it never collides with wallet-derived records.
+/// - Ensures the voter's token account is authorized and has sufficient balance.
+/// - Facilitates the transfer of voting fees from the voter's token account to the treasury's fee
+///   account.
+#[derive(Accounts)]
+#[instruction(blinded_voter: [u8; 32])]
+pub struct StealthVoter<'info> {
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + std::mem::size_of::<VoterData>(),
+            seeds = [
+                STEALTH_VOTER_SEED,
+                &vote_manager.election_seed(),     // Election the vote is cast in.
+                &project.load()?.vote_round.to_le_bytes(), // Round the vote is cast in.
+                blinded_voter.as_ref(),            // Blinded voter id, unlinkable to the signer.
+                project.load()?.id().as_ref(),
+            ],
+            bump,
+            constraint = project.load()?.vote_round == vote_manager.vote_round @ VoteError::WrongRound
+            )]
+    pub voter_data: AccountLoader<'info, VoterData>, // Tracks the blinded voter's voting activity.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being voted for.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, /* Voter's token account holding ttt
+                                                       * tokens. */
+    pub token_program: Interface<'info, TokenInterface>, /* Token program interface for
+                                                          * token operations. */
+    pub system_program: Program<'info, System>, // Solana System program.
+    #[account(
+            mut,
+            seeds = [
+                LEADERBOARD_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = leaderboard.load()?.bump
+        )]
+    pub leaderboard: Option<AccountLoader<'info, Leaderboard>>, // The round's standings, if any.
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + RoundVotes::INIT_SPACE,
+            seeds = [
+                ROUND_VOTES_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes(),
+                blinded_voter.as_ref()
+            ],
+            bump
+        )]
+    pub round_votes: Account<'info, RoundVotes>, // The voter's votes in the round.
+}
+
+/// Defines the accounts required for casting a sponsored vote.
+///
+/// **Business Logic:**
+/// - Uses the same accounts and constraints as `Voter`.
+/// - Adds the sponsor vault paying the reimbursement, and the voter's SponsorRecord, created on
+///   the wallet's first sponsored vote to track its reimbursements.
+#[derive(Accounts)]
+pub struct SponsoredVoter<'info> {
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + std::mem::size_of::<VoterData>(),
+            seeds = [
+                VOTER_SEED,
+                &vote_manager.election_seed(),     // Election the vote is cast in.
+                &project.load()?.vote_round.to_le_bytes(), // Round the vote is cast in.
+                signer.key().as_ref(),             // Voter's public key to ensure unique PDA per voter per round.
+                project.load()?.id().as_ref(),
+            ],
+            bump,
+            constraint = project.load()?.vote_round == vote_manager.vote_round @ VoteError::WrongRound
+            )]
+    pub voter_data: AccountLoader<'info, VoterData>, // Tracks the voter's voting activity.
+    #[account(
+            mut,
+            seeds = [
+                SPONSOR_VAULT_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = sponsor_vault.bump
+        )]
+    pub sponsor_vault: Account<'info, SponsorVault>, // Vault reimbursing the rent.
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + SponsorRecord::INIT_SPACE,
+            seeds = [
+                SPONSOR_RECORD_SEED,
+                vote_manager.key().as_ref(),
+                signer.key().as_ref()
+            ],
+            bump
+        )]
+    pub sponsor_record: Account<'info, SponsorRecord>, // Reimbursements of the voter's wallet.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being voted for.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account holding ttt.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+    #[account(
+            mut,
+            seeds = [
+                LEADERBOARD_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = leaderboard.load()?.bump
+        )]
+    pub leaderboard: Option<AccountLoader<'info, Leaderboard>>, // The round's standings, if any.
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + RoundVotes::INIT_SPACE,
+            seeds = [
+                ROUND_VOTES_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes(),
+                signer.key().as_ref()
+            ],
+            bump
+        )]
+    pub round_votes: Account<'info, RoundVotes>, // The voter's votes in the round.
+}
+
+/// Defines the accounts required for a vote relayed on the voter's signed authorization.
+///
+/// **Business Logic:**
+/// - Uses the same constraints as `Voter`, with the voter as a plain account: the relayer signs,
+///   sends and pays for the transaction, including the rent of the accounts it creates.
+/// - Initializes the voter's VoteNonce on their first relayed vote.
+/// - `token` must have delegated an allowance to the VoteManager PDA, which signs the fee.
+/// - `instructions` is the instructions sysvar, read to find the Ed25519 verification.
+#[derive(Accounts)]
+pub struct AuthorizedVoter<'info> {
+    #[account(
+            init_if_needed,
+            payer = relayer,
+            space = 8 + std::mem::size_of::<VoterData>(),
+            seeds = [
+                VOTER_SEED,
+                &vote_manager.election_seed(),     // Election the vote is cast in.
+                &project.load()?.vote_round.to_le_bytes(), // Round the vote is cast in.
+                voter.key().as_ref(),              // Voter's public key to ensure unique PDA per voter per round.
+                project.load()?.id().as_ref(),
+            ],
+            bump,
+            constraint = project.load()?.vote_round == vote_manager.vote_round @ VoteError::WrongRound
+            )]
+    pub voter_data: AccountLoader<'info, VoterData>, // Tracks the voter's voting activity.
+    #[account(
+            init_if_needed,
+            payer = relayer,
+            space = 8 + VoteNonce::INIT_SPACE,
+            seeds = [
+                VOTE_NONCE_SEED,
+                vote_manager.key().as_ref(),
+                voter.key().as_ref()
+            ],
+            bump
+        )]
+    pub vote_nonce: Account<'info, VoteNonce>, // The voter's next authorization nonce.
+    /// CHECK: Wallet whose Ed25519 signature authorizes the vote, verified by the handler.
+    pub voter: UncheckedAccount<'info>, // The voter, who does not sign the transaction.
+    #[account(mut)]
+    pub relayer: Signer<'info>, // Anyone sending the vote and paying for it.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being voted for.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == voter.key() @ VoteError::WrongTokenOwner,
+            constraint = token.delegate == Some(vote_manager.key()).into() @ VoteError::VoteNotDelegated
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account holding ttt.
+    /// CHECK: Instructions sysvar, verified by its address.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>, // Instructions of the current transaction.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+    #[account(
+            mut,
+            seeds = [
+                LEADERBOARD_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = leaderboard.load()?.bump
+        )]
+    pub leaderboard: Option<AccountLoader<'info, Leaderboard>>, // The round's standings, if any.
+    #[account(
+            init_if_needed,
+            payer = relayer,
+            space = 8 + RoundVotes::INIT_SPACE,
+            seeds = [
+                ROUND_VOTES_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes(),
+                voter.key().as_ref()
+            ],
+            bump
+        )]
+    pub round_votes: Account<'info, RoundVotes>, // The voter's votes in the round.
+}
+
+/// Defines the accounts required to set up compressed votes.
+///
+/// **Business Logic:**
+/// - Initializes the VoteTree PDA, one per VoteManager, paid by the admin.
+/// - `merkle_tree` must be a new account owned by the spl-account-compression program; the
+///   compression program initializes it with the VoteTree PDA as its authority.
+#[derive(Accounts)]
+pub struct InitVoteTree<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + VoteTree::INIT_SPACE,
+            seeds = [VOTE_TREE_SEED, vote_manager.key().as_ref()],
+            bump
+        )]
+    pub vote_tree: Account<'info, VoteTree>, // The VoteManager's vote tree.
+    /// CHECK: Zeroed tree account, initialized by the compression program.
+    #[account(mut, owner = ACCOUNT_COMPRESSION_ID)]
+    pub merkle_tree: UncheckedAccount<'info>, // The concurrent Merkle tree.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    /// CHECK: spl-account-compression program, verified by its address.
+    #[account(address = ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>, // Program maintaining the tree.
+    /// CHECK: spl-noop program, verified by its address.
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>, // Program logging the tree changes.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required for a compressed vote.
+///
+/// **Business Logic:**
+/// - Uses the same constraints as `Voter`, without a VoterData account: the vote is appended to
+///   the VoteManager's tree instead.
+#[derive(Accounts)]
+pub struct CompressedVoter<'info> {
+    #[account(
+            mut,
+            seeds = [VOTE_TREE_SEED, vote_manager.key().as_ref()],
+            bump = vote_tree.bump
+        )]
+    pub vote_tree: Account<'info, VoteTree>, // The VoteManager's vote tree.
+    /// CHECK: Concurrent Merkle tree, verified against the VoteTree.
+    #[account(mut, address = vote_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>, // The tree the vote is appended to.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.vote_round == vote_manager.vote_round @ VoteError::WrongRound,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being voted for.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account holding ttt.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    /// CHECK: spl-account-compression program, verified by its address.
+    #[account(address = ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>, // Program maintaining the tree.
+    /// CHECK: spl-noop program, verified by its address.
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>, // Program logging the tree changes.
+    #[account(
+            mut,
+            seeds = [
+                LEADERBOARD_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = leaderboard.load()?.bump
+        )]
+    pub leaderboard: Option<AccountLoader<'info, Leaderboard>>, // The round's standings, if any.
+}
+
+/// Defines the accounts required to verify a compressed vote.
+///
+/// **Business Logic:**
+/// - Anyone can verify; the proof's sibling nodes follow as remaining accounts.
+#[derive(Accounts)]
+pub struct VerifyVote<'info> {
+    #[account(
+            seeds = [VOTE_TREE_SEED, vote_tree.vote_manager.as_ref()],
+            bump = vote_tree.bump
+        )]
+    pub vote_tree: Account<'info, VoteTree>, // The VoteManager's vote tree.
+    /// CHECK: Concurrent Merkle tree, verified against the VoteTree.
+    #[account(address = vote_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>, // The tree holding the vote.
+    /// CHECK: spl-account-compression program, verified by its address.
+    #[account(address = ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>, // Program verifying the proof.
+}
+
+/// Defines the accounts required to claim a reward for a compressed vote backing a round's
+/// winner.
+///
+/// **Business Logic:**
+/// - The vote must be the signer's, cast in the `RoundResult`'s round for its winner; the
+///   `RoundResult` must exist and not be invalidated, i.e. the round be finalized.
+/// - The proof's sibling nodes follow as remaining accounts.
+/// - The reward is paid into the voter's token account of the governance mint.
+#[derive(Accounts)]
+#[instruction(vote: CompressedVoteRecord)]
+pub struct ClaimCompressedReward<'info> {
+    #[account(
+            seeds = [VOTE_TREE_SEED, vote_manager.key().as_ref()],
+            bump = vote_tree.bump
+        )]
+    pub vote_tree: Account<'info, VoteTree>, // The VoteManager's vote tree.
+    /// CHECK: Concurrent Merkle tree, verified against the VoteTree.
+    #[account(mut, address = vote_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>, // The tree holding the vote.
+    #[account(
+            constraint = vote.voter == signer.key() @ VoteError::CompressedVoteMismatch
+        )]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            mut,
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &round_result.round.to_le_bytes()
+            ],
+            bump = round_result.bump,
+            constraint = !round_result.invalidated @ VoteError::RoundInvalid,
+            constraint = vote.round == round_result.round @ VoteError::CompressedVoteMismatch,
+            constraint = vote.project == round_result.winner @ VoteError::CompressedVoteMismatch,
+            constraint = !vote.reward_claimed @ VoteError::NoRewardDue
+        )]
+    pub round_result: Account<'info, RoundResult>, // The finalized round.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury holding the fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account receiving the reward.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    /// CHECK: spl-account-compression program, verified by its address.
+    #[account(address = ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>, // Program maintaining the tree.
+    /// CHECK: spl-noop program, verified by its address.
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>, // Program logging the tree changes.
+}
+
+/// Defines the accounts required to create the leaderboard of the current round.
+///
+/// **Business Logic:**
+/// - Initializes the Leaderboard PDA, unique per VoteManager and round, paid by the signer.
+#[derive(Accounts)]
+pub struct InitLeaderboard<'info> {
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + std::mem::size_of::<Leaderboard>(),
+            seeds = [
+                LEADERBOARD_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>, // The leaderboard being created.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub signer: Signer<'info>, // Anyone paying the leaderboard's rent.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to commit a hidden vote.
+///
+/// **Business Logic:**
+/// - Initializes a VoteCommit account, unique per voter per round.
+/// - Uses the same token account constraints as `Voter` for the fee transfer.
+#[derive(Accounts)]
+pub struct CommitVote<'info> {
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + VoteCommit::INIT_SPACE,
+            seeds = [
+                VOTE_COMMIT_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes(),
+                signer.key().as_ref(),
+            ],
+            bump
+        )]
+    pub vote_commit: Account<'info, VoteCommit>, // The voter's commitment for this round.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account holding ttt.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to reveal a committed vote.
+///
+/// **Business Logic:**
+/// - Anyone holding the preimage may reveal, so relayers can reveal on the voter's behalf.
+/// - The project must belong to the same VoteManager and round as the commitment.
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTE_COMMIT_SEED,
+                vote_manager.key().as_ref(),
+                &vote_commit.round.to_le_bytes(),
+                vote_commit.voter.as_ref(),
+            ],
+            bump = vote_commit.bump,
+            constraint = vote_commit.round == vote_manager.vote_round @ VoteError::WrongRound
+        )]
+    pub vote_commit: Account<'info, VoteCommit>, // The commitment being revealed.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.vote_round == vote_commit.round @ VoteError::WrongRound,
+            constraint = project.load()?.disqualified == 0 @ VoteError::ProjectDisqualified,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project named in the commitment.
+    pub signer: Signer<'info>, // The revealer's signer account.
+}
+
+/// Defines the accounts required to close a VoterData account.
+///
+/// **Business Logic:**
+/// - Permissionless: the rent always goes back to the voter, so anyone may sweep.
+/// - The VoterData PDA is re-derived from the project, binding it to this VoteManager.
+/// - The project's round must have a `RoundResult`, i.e. be finalized.
+/// - Records of a disqualified project or an invalidated round stay open until the voter claimed
+///   the refund, and records of the round's winner until the voter claimed their reward, so
+///   sweeping cannot forfeit them.
+#[derive(Accounts)]
+pub struct CloseVoterData<'info> {
+    #[account(
+            mut,
+            close = voter,
+            seeds = [
+                VOTER_SEED,
+                &vote_manager.election_seed(),
+                &project.load()?.vote_round.to_le_bytes(),
+                voter.key().as_ref(),
+                project.load()?.id().as_ref(),
+            ],
+            bump = voter_data.load()?.bump,
+            constraint = !(project.load()?.disqualified != 0 || round_result.invalidated)
+                || !voter_data.load()?.refund_due() @ VoteError::RefundUnclaimed,
+            constraint = project.key() != round_result.winner
+                || voter_data.load()?.reward_due(&round_result) == 0 @ VoteError::RewardUnclaimed
+        )]
+    pub voter_data: AccountLoader<'info, VoterData>, // The voter record being closed.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project the voter voted for.
+    #[account(
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = round_result.bump
+        )]
+    pub round_result: Account<'info, RoundResult>, // Proof that the round is finalized.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub voter: SystemAccount<'info>, // The voter receiving the rent.
+    pub signer: Signer<'info>, // Anyone sweeping the account.
+}
+
+/// Defines the accounts required to close a stealth VoterData account.
+///
+/// **Business Logic:**
+/// - Only the voter can close the record; the handler checks the ownership proof
+///   `stealth_voter_id(signer, nonce) == blinded_voter`.
+/// - The project's round must have a `RoundResult`, i.e. be finalized.
+#[derive(Accounts)]
+pub struct CloseStealthVoterData<'info> {
+    #[account(
+            mut,
+            close = signer,
+            seeds = [
+                STEALTH_VOTER_SEED,
+                &vote_manager.election_seed(),
+                &project.load()?.vote_round.to_le_bytes(),
+                voter_data.load()?.blinded_voter.as_ref(),
+                project.load()?.id().as_ref(),
+            ],
+            bump = voter_data.load()?.bump
+        )]
+    pub voter_data: AccountLoader<'info, VoterData>, // The stealth voter record being closed.
+    #[account(
+            mut,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project the voter voted for.
+    #[account(
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = round_result.bump
+        )]
+    pub round_result: Account<'info, RoundResult>, // Proof that the round is finalized.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter, receiving the rent.
+}
+
+/// Defines the accounts required to close a ProjectData account.
+///
+/// **Business Logic:**
+/// - Permissionless: the rent always goes back to the project's original payer.
+/// - All VoterData accounts of the project must be closed first.
+/// - The project's round must have a `RoundResult`, i.e. be finalized, and its tally must be in the
+///   round's `RoundArchive`.
+/// - A project keeps a listing bond's earned tranches open until the submitter claimed them, so
+///   sweeping cannot forfeit them; a disqualified project forfeits its whole bond.
+/// - The treasury is only needed to forfeit a remaining bond.
+#[derive(Accounts)]
+pub struct CloseProjectData<'info> {
+    #[account(
+            mut,
+            close = payer,
+            has_one = payer @ VoteError::WrongPayer,
+            constraint = project.load()?.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager,
+            constraint = project.load()?.voter_records == 0 @ VoteError::VoterRecordsRemaining,
+            constraint = project.load()?.pending == 0 @ VoteError::ProjectPending,
+            constraint = project.load()?.disqualified != 0 || project.load()?.claimable_deposit() == 0 @ VoteError::DepositTrancheUnclaimed
+        )]
+    pub project: AccountLoader<'info, ProjectData>, // The project being closed.
+    #[account(
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = round_result.bump
+        )]
+    pub round_result: Account<'info, RoundResult>, // Proof that the round is finalized.
+    #[account(
+            seeds = [
+                ROUND_ARCHIVE_SEED,
+                vote_manager.key().as_ref(),
+                &project.load()?.vote_round.to_le_bytes()
+            ],
+            bump = round_archive.bump,
+            constraint = round_archive.is_archived(project.load()?.id()) @ VoteError::ProjectNotArchived
+        )]
+    pub round_archive: Account<'info, RoundArchive>, // Proof that the project's tally is kept.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub payer: SystemAccount<'info>, // The original payer receiving the rent.
+    pub signer: Signer<'info>, // Anyone sweeping the account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Option<Account<'info, Treasury>>, // Treasury keeping a remaining bond.
+}
+
+/// Defines the accounts required to stake TTT for voting power.
+///
+/// **Business Logic:**
+/// - Initializes the StakePosition PDA, unique per VoteManager and owner, so a wallet holds one
+///   position at a time.
+/// - Creates the position's vault, the associated token account of the StakePosition PDA.
+/// - Only allowed while the VoteManager is in escrow mode.
+#[derive(Accounts)]
+pub struct StakeForPower<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + StakePosition::INIT_SPACE,
+            seeds = [
+                STAKE_POSITION_SEED,
+                vote_manager.key().as_ref(),
+                owner.key().as_ref()
+            ],
+            bump
+        )]
+    pub stake_position: Account<'info, StakePosition>, // The new stake position.
+    #[account(
+            init,
+            payer = owner,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = stake_position,
+        )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>, // Vault holding the staked TTT.
+    #[account(
+            mut,
+            constraint = owner_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = owner_token_account.owner == owner.key() @ VoteError::WrongTokenOwner
+        )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>, // Owner's TTT account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = vote_manager.escrow_mode @ VoteError::EscrowDisabled,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub owner: Signer<'info>, // The staker's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to release an expired stake position.
+///
+/// **Business Logic:**
+/// - Only the position's owner can unstake; the TTT and the rent go back to them.
+/// - Allowed whether or not escrow mode is still enabled.
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+            mut,
+            close = owner,
+            has_one = owner,
+            seeds = [
+                STAKE_POSITION_SEED,
+                vote_manager.key().as_ref(),
+                owner.key().as_ref()
+            ],
+            bump = stake_position.bump
+        )]
+    pub stake_position: Account<'info, StakePosition>, // The position being released.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = stake_position,
+        )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>, // Vault holding the staked TTT.
+    #[account(
+            mut,
+            constraint = owner_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = owner_token_account.owner == owner.key() @ VoteError::WrongTokenOwner
+        )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>, // Owner's TTT account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub owner: Signer<'info>, // The staker's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to register a token holding.
+///
+/// **Business Logic:**
+/// - Initializes the HoldingAttestation PDA, unique per VoteManager and owner.
+/// - Only allowed while the VoteManager has a holding requirement, for a token account of the
+///   owner holding at least its `min_holding_balance`.
+#[derive(Accounts)]
+pub struct RegisterHolding<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + HoldingAttestation::INIT_SPACE,
+            seeds = [
+                HOLDING_ATTESTATION_SEED,
+                vote_manager.key().as_ref(),
+                owner.key().as_ref()
+            ],
+            bump
+        )]
+    pub holding_attestation: Account<'info, HoldingAttestation>, // The new attestation.
+    #[account(
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == owner.key() @ VoteError::WrongTokenOwner,
+            constraint = token.amount >= vote_manager.min_holding_balance @ VoteError::InsufficientHolding
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Owner's TTT account, the one voting.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = vote_manager.holding_requirement() @ VoteError::HoldingRequirementDisabled
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The holder's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to grant a role.
+///
+/// **Business Logic:**
+/// - Only the admin grants roles, and pays for the RoleGrant.
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + RoleGrant::INIT_SPACE,
+            seeds = [ROLE_SEED, vote_manager.key().as_ref(), holder.as_ref()],
+            bump
+        )]
+    pub role: Account<'info, RoleGrant>, // The new role grant.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to revoke a role.
+///
+/// **Business Logic:**
+/// - Only the admin revokes roles; the RoleGrant's rent goes to the admin.
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+            mut,
+            close = owner,
+            seeds = [ROLE_SEED, vote_manager.key().as_ref(), role.holder.as_ref()],
+            bump = role.bump
+        )]
+    pub role: Account<'info, RoleGrant>, // The revoked role grant.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+}
+
+/// Defines the accounts required to set the fee discount schedule.
+///
+/// **Business Logic:**
+/// - Initializes the FeeDiscountSchedule PDA, one per VoteManager, on first use.
+/// - Only the admin sets the schedule, and pays for it.
+#[derive(Accounts)]
+pub struct SetFeeDiscounts<'info> {
+    #[account(
+            init_if_needed,
+            payer = owner,
+            space = 8 + FeeDiscountSchedule::INIT_SPACE,
+            seeds = [FEE_DISCOUNT_SEED, vote_manager.key().as_ref()],
+            bump
+        )]
+    pub fee_discounts: Account<'info, FeeDiscountSchedule>, // The VoteManager's discount schedule.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to name the project categories.
+///
+/// **Business Logic:**
+/// - Initializes the CategoryRegistry PDA, one per VoteManager, on first use.
+/// - Only the admin names the categories, and pays for the registry.
+#[derive(Accounts)]
+pub struct SetCategories<'info> {
+    #[account(
+            init_if_needed,
+            payer = owner,
+            space = 8 + CategoryRegistry::INIT_SPACE,
+            seeds = [CATEGORY_REGISTRY_SEED, vote_manager.key().as_ref()],
+            bump
+        )]
+    pub category_registry: Account<'info, CategoryRegistry>, // The VoteManager's categories.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to add a wallet to the allowlist.
+///
+/// **Business Logic:**
+/// - Initializes the allowlist AccessListEntry PDA, unique per VoteManager and voter.
+/// - Only the admin lists wallets, and pays for the entry.
+#[derive(Accounts)]
+#[instruction(voter: Pubkey)]
+pub struct AddToAllowlist<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + AccessListEntry::INIT_SPACE,
+            seeds = [ALLOWLIST_SEED, vote_manager.key().as_ref(), voter.as_ref()],
+            bump
+        )]
+    pub entry: Account<'info, AccessListEntry>, // The new allowlist entry.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to add a wallet to the blocklist.
+///
+/// **Business Logic:**
+/// - Initializes the blocklist AccessListEntry PDA, unique per VoteManager and voter.
+/// - Only the admin lists wallets, and pays for the entry.
+#[derive(Accounts)]
+#[instruction(voter: Pubkey)]
+pub struct AddToBlocklist<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + AccessListEntry::INIT_SPACE,
+            seeds = [BLOCKLIST_SEED, vote_manager.key().as_ref(), voter.as_ref()],
+            bump
+        )]
+    pub entry: Account<'info, AccessListEntry>, // The new blocklist entry.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to take a wallet off an access list.
+///
+/// **Business Logic:**
+/// - Works for entries of either list; the entry's rent goes to the admin.
+#[derive(Accounts)]
+pub struct RemoveAccessListEntry<'info> {
+    #[account(
+            mut,
+            close = owner,
+            constraint = entry.vote_manager == vote_manager.key() @ VoteError::WrongVoteManager
+        )]
+    pub entry: Account<'info, AccessListEntry>, // The removed entry.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+}
+
+/// Defines the accounts required to create the admin council.
+///
+/// **Business Logic:**
+/// - Initializes the AdminCouncil PDA, one per VoteManager.
+/// - Only the admin creates the council, and pays for it.
+#[derive(Accounts)]
+pub struct CreateAdminCouncil<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + AdminCouncil::INIT_SPACE,
+            seeds = [ADMIN_COUNCIL_SEED, vote_manager.key().as_ref()],
+            bump
+        )]
+    pub council: Account<'info, AdminCouncil>, // The new admin council.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to propose an admin action.
+///
+/// **Business Logic:**
+/// - Initializes the AdminAction PDA under the council's next `action_count`.
+/// - The proposing member pays for the action; the handler checks the membership.
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + AdminAction::INIT_SPACE,
+            seeds = [
+                ADMIN_ACTION_SEED,
+                council.key().as_ref(),
+                &council.action_count.to_le_bytes()
+            ],
+            bump
+        )]
+    pub action: Account<'info, AdminAction>, // The new admin action.
+    #[account(
+            mut,
+            seeds = [ADMIN_COUNCIL_SEED, council.vote_manager.as_ref()],
+            bump = council.bump
+        )]
+    pub council: Account<'info, AdminCouncil>, // The council the action is proposed to.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The proposing member's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to approve an admin action.
+///
+/// **Business Logic:**
+/// - The handler checks that the signer is a member who has not approved the action yet.
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
+    #[account(
+            mut,
+            seeds = [
+                ADMIN_ACTION_SEED,
+                council.key().as_ref(),
+                &action.id.to_le_bytes()
+            ],
+            bump = action.bump
+        )]
+    pub action: Account<'info, AdminAction>, // The admin action being approved.
+    #[account(
+            seeds = [ADMIN_COUNCIL_SEED, council.vote_manager.as_ref()],
+            bump = council.bump
+        )]
+    pub council: Account<'info, AdminCouncil>, // The council the action belongs to.
+    pub signer: Signer<'info>, // The approving member's signer account.
+}
+
+/// Defines the accounts required to execute an admin action.
+///
+/// **Business Logic:**
+/// - Anyone can execute an approved action; the handler checks the threshold.
+/// - The AdminCouncil PDA signs the stored instructions; to exercise admin-only instructions it
+///   must be made the VoteManager's admin.
+/// - The council is not writable here, so stored instructions may modify it without this
+///   instruction overwriting their changes on exit.
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    #[account(
+            mut,
+            seeds = [
+                ADMIN_ACTION_SEED,
+                council.key().as_ref(),
+                &action.id.to_le_bytes()
+            ],
+            bump = action.bump
+        )]
+    pub action: Account<'info, AdminAction>, // The admin action being executed.
+    #[account(
+            seeds = [ADMIN_COUNCIL_SEED, council.vote_manager.as_ref()],
+            bump = council.bump
+        )]
+    pub council: Account<'info, AdminCouncil>, // The council signing the instructions.
+    pub signer: Signer<'info>, // The executor's signer account.
+}
+
+/// Defines the accounts required to record an operation marker.
+///
+/// **Business Logic:**
+/// - Only the admin records markers, so nobody can pre-create one to block an operation.
+#[derive(Accounts)]
+#[instruction(key: [u8; 32])]
+pub struct RecordOperation<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + OperationMarker::INIT_SPACE,
+            seeds = [
+                OPERATION_MARKER_SEED,
+                vote_manager.key().as_ref(),
+                &key
+            ],
+            bump
+        )]
+    pub marker: Account<'info, OperationMarker>, // The new operation marker.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to create the treasury.
+///
+/// **Business Logic:**
+/// - Initializes the Treasury PDA, unique per VoteManager.
+/// - Creates the treasury's associated token account for the governance mint.
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + Treasury::INIT_SPACE,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump
+        )]
+    pub treasury: Account<'info, Treasury>, // The treasury being created.
+    #[account(
+            init,
+            payer = owner,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to withdraw from the treasury.
+///
+/// **Business Logic:**
+/// - The treasury's fee account is re-derived from the Treasury PDA.
+/// - The destination can be any token account of the governance mint.
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            mut,
+            constraint = destination.mint == vote_manager.tk_mint @ VoteError::WrongMint
+        )]
+    pub destination: InterfaceAccount<'info, TokenAccount>, // Account receiving the fees.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to withdraw the voting fees paid in SOL.
+///
+/// **Business Logic:**
+/// - The fees are paid from the Treasury PDA's lamports to the admin.
+/// - The handler checks that the signer is the admin.
+#[derive(Accounts)]
+pub struct WithdrawTreasurySol<'info> {
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury holding the SOL fees.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account, receiving the fees.
+}
+
+/// Defines the accounts required to fund a round's reward vault.
+///
+/// **Business Logic:**
+/// - Initializes the RewardVault PDA and its token account on first funding, unique per
+///   VoteManager and round.
+/// - Rejects rounds that are already finalized and vaults that were swept.
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct FundRewardVault<'info> {
+    #[account(
+            init_if_needed,
+            payer = owner,
+            space = 8 + RewardVault::INIT_SPACE,
+            seeds = [
+                REWARD_VAULT_SEED,
+                vote_manager.key().as_ref(),
+                &round.to_le_bytes()
+            ],
+            bump,
+            constraint = !reward_vault.swept @ VoteError::RewardsSwept
+        )]
+    pub reward_vault: Account<'info, RewardVault>, // The round's reward vault.
+    #[account(
+            init_if_needed,
+            payer = owner,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = reward_vault,
+        )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>, // Vault's token account.
+    #[account(
+            mut,
+            constraint = source.mint == vote_manager.tk_mint @ VoteError::WrongMint
+        )]
+    pub source: InterfaceAccount<'info, TokenAccount>, // Admin's token account funding the vault.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = round >= vote_manager.vote_round @ VoteError::WrongRound,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to sweep a round's unclaimed rewards.
+///
+/// **Business Logic:**
+/// - The round must have a `RoundResult`, whose `finalized_at` starts the claim window.
+/// - The destination can be any token account of the governance mint.
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct SweepUnclaimedRewards<'info> {
+    #[account(
+            mut,
+            seeds = [
+                REWARD_VAULT_SEED,
+                vote_manager.key().as_ref(),
+                &round.to_le_bytes()
+            ],
+            bump = reward_vault.bump
+        )]
+    pub reward_vault: Account<'info, RewardVault>, // The round's reward vault.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = reward_vault,
+        )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>, // Vault's token account.
+    #[account(
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &round.to_le_bytes()
+            ],
+            bump = round_result.bump
+        )]
+    pub round_result: Account<'info, RoundResult>, // Proof that the round is finalized.
+    #[account(
+            mut,
+            constraint = destination.mint == vote_manager.tk_mint @ VoteError::WrongMint
+        )]
+    pub destination: InterfaceAccount<'info, TokenAccount>, // Account receiving the rewards.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to create the sponsor vault.
+///
+/// **Business Logic:**
+/// - Initializes the SponsorVault PDA, one per VoteManager.
+/// - The handler checks that the signer is the admin.
+#[derive(Accounts)]
+pub struct InitSponsorVault<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + SponsorVault::INIT_SPACE,
+            seeds = [
+                SPONSOR_VAULT_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump
+        )]
+    pub sponsor_vault: Account<'info, SponsorVault>, // The sponsor vault being created.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to fund, drain or configure the sponsor vault.
+///
+/// **Business Logic:**
+/// - The admin pays deposits and receives withdrawals.
+/// - The handler checks that the signer is the admin.
+#[derive(Accounts)]
+pub struct ManageSponsorVault<'info> {
+    #[account(
+            mut,
+            seeds = [
+                SPONSOR_VAULT_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = sponsor_vault.bump
+        )]
+    pub sponsor_vault: Account<'info, SponsorVault>, // The sponsor vault.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to create a proposal.
+///
+/// **Business Logic:**
+/// - Initializes the Proposal PDA under the VoteManager's next `proposal_count`.
+/// - Initializes the proposal's token account holding the deposit.
+/// - Anyone holding the deposit can propose.
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+            init,
+            payer = proposer,
+            space = 8 + Proposal::INIT_SPACE,
+            seeds = [
+                PROPOSAL_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.proposal_count.to_le_bytes()
+            ],
+            bump
+        )]
+    pub proposal: Account<'info, Proposal>, // The new proposal.
+    #[account(
+            init,
+            payer = proposer,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = proposal,
+        )]
+    pub deposit_token_account: InterfaceAccount<'info, TokenAccount>, // Proposal's deposit account.
+    #[account(
+            mut,
+            constraint = proposer_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = proposer_token_account.owner == proposer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub proposer_token_account: InterfaceAccount<'info, TokenAccount>, // Proposer's TTT account.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub proposer: Signer<'info>, // The proposer's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to vote on a proposal.
+///
+/// **Business Logic:**
+/// - Initializes a `ProposalVote` PDA, unique per proposal and voter.
+/// - Creates the ballot's vault, the associated token account of the ProposalVote PDA, locking
+///   the TTT weighing the vote; they come from the signer's TTT account.
+#[derive(Accounts)]
+pub struct ProposalVoter<'info> {
+    #[account(
+            mut,
+            seeds = [
+                PROPOSAL_SEED,
+                vote_manager.key().as_ref(),
+                &proposal.id.to_le_bytes()
+            ],
+            bump = proposal.bump
+        )]
+    pub proposal: Account<'info, Proposal>, // The proposal being voted on.
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + ProposalVote::INIT_SPACE,
+            seeds = [
+                PROPOSAL_VOTE_SEED,
+                proposal.key().as_ref(),
+                signer.key().as_ref()
+            ],
+            bump
+        )]
+    pub proposal_vote: Account<'info, ProposalVote>, // The voter's ballot.
+    #[account(
+            init,
+            payer = signer,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = proposal_vote,
+        )]
+    pub vote_vault: InterfaceAccount<'info, TokenAccount>, // Vault holding the locked TTT.
+    #[account(
+            mut,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's TTT account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to release the TTT locked by a proposal vote.
+///
+/// **Business Logic:**
+/// - Only the ballot's voter can release it; the TTT and the rent go back to them.
+/// - The ballot is found from its stored proposal, so it can be released after the proposal
+///   closed.
+#[derive(Accounts)]
+pub struct ReleaseProposalVote<'info> {
+    #[account(
+            mut,
+            close = voter,
+            has_one = voter,
+            seeds = [
+                PROPOSAL_VOTE_SEED,
+                proposal_vote.proposal.as_ref(),
+                voter.key().as_ref()
+            ],
+            bump = proposal_vote.bump
+        )]
+    pub proposal_vote: Account<'info, ProposalVote>, // The ballot being released.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = proposal_vote,
+        )]
+    pub vote_vault: InterfaceAccount<'info, TokenAccount>, // Vault holding the locked TTT.
+    #[account(
+            mut,
+            constraint = token.mint == mint.key() @ VoteError::WrongMint,
+            constraint = token.owner == voter.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's TTT account.
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub voter: Signer<'info>, // The voter's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to create a referendum.
+///
+/// **Business Logic:**
+/// - Initializes the Referendum PDA under the VoteManager's next `referendum_count`.
+/// - Only the admin can create referenda; the handler checks the signer.
+#[derive(Accounts)]
+pub struct CreateReferendum<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + Referendum::INIT_SPACE,
+            seeds = [
+                REFERENDUM_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.referendum_count.to_le_bytes()
+            ],
+            bump
+        )]
+    pub referendum: Account<'info, Referendum>, // The new referendum.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to vote on a referendum.
+///
+/// **Business Logic:**
+/// - Initializes a `ReferendumVote` PDA, unique per referendum and voter.
+/// - Ensures the voter's token account is authorized and has sufficient balance.
+/// - Facilitates the transfer of the voting fee from the voter's token account to the treasury's
+///   fee account.
+#[derive(Accounts)]
+pub struct ReferendumVoter<'info> {
+    #[account(
+            mut,
+            seeds = [
+                REFERENDUM_SEED,
+                vote_manager.key().as_ref(),
+                &referendum.id.to_le_bytes()
+            ],
+            bump = referendum.bump
+        )]
+    pub referendum: Account<'info, Referendum>, // The referendum being voted on.
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + ReferendumVote::INIT_SPACE,
+            seeds = [
+                REFERENDUM_VOTE_SEED,
+                referendum.key().as_ref(),
+                signer.key().as_ref()
+            ],
+            bump
+        )]
+    pub referendum_vote: Account<'info, ReferendumVote>, // The voter's answer.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account holding ttt.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to execute a proposal.
+///
+/// **Business Logic:**
+/// - Anyone can execute a passed proposal; the handler checks the deadline and the outcome.
+/// - The governance authority PDA signs the stored instructions; to govern admin-only settings it
+///   must be made the VoteManager's admin.
+/// - The VoteManager is not writable here, so stored instructions may modify it without this
+///   instruction overwriting their changes on exit.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+            mut,
+            seeds = [
+                PROPOSAL_SEED,
+                vote_manager.key().as_ref(),
+                &proposal.id.to_le_bytes()
+            ],
+            bump = proposal.bump
+        )]
+    pub proposal: Account<'info, Proposal>, // The proposal being executed.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = proposal,
+        )]
+    pub deposit_token_account: InterfaceAccount<'info, TokenAccount>, // Proposal's deposit account.
+    #[account(
+            mut,
+            constraint = proposer_token_account.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = proposer_token_account.owner == proposal.proposer @ VoteError::WrongTokenOwner
+        )]
+    pub proposer_token_account: InterfaceAccount<'info, TokenAccount>, // Receives the deposit.
+    /// CHECK: Signer of the stored instructions, verified by its seeds; it holds no data.
+    #[account(
+            seeds = [
+                GOVERNANCE_AUTHORITY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump
+        )]
+    pub governance_authority: UncheckedAccount<'info>,
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    pub signer: Signer<'info>, // The executor's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to close a proposal.
+///
+/// **Business Logic:**
+/// - Anyone can close a finished proposal; the rent always goes back to the proposer.
+/// - Forfeited deposits go to the VoteManager's treasury.
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(
+            mut,
+            close = proposer,
+            has_one = proposer,
+            seeds = [
+                PROPOSAL_SEED,
+                vote_manager.key().as_ref(),
+                &proposal.id.to_le_bytes()
+            ],
+            bump = proposal.bump
+        )]
+    pub proposal: Account<'info, Proposal>, // The proposal being closed.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = proposal,
+        )]
+    pub deposit_token_account: InterfaceAccount<'info, TokenAccount>, // Proposal's deposit account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury receiving forfeited deposits.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>, // Receives the proposal's rent.
+    pub signer: Signer<'info>, // The closer's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to finalize a voting round.
+///
+/// **Business Logic:**
+/// - Initializes the `RoundResult` and `RoundArchive` PDAs, unique per VoteManager and round.
+/// - Anyone may pay for and sign the finalization once the round deadline has passed.
+/// - `winner` must be the round's leading project, or absent if nobody voted.
+#[derive(Accounts)]
+pub struct FinalizeRound<'info> {
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + RoundResult::INIT_SPACE,
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_result: Account<'info, RoundResult>, // Snapshot of the round being finalized.
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + RoundArchive::INIT_SPACE,
+            seeds = [
+                ROUND_ARCHIVE_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_archive: Account<'info, RoundArchive>, // Standings of the round being finalized.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    pub winner: Option<AccountLoader<'info, ProjectData>>, // The round's leading project.
+    #[account(mut)]
+    pub signer: Signer<'info>, // Anyone finalizing the round.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to invalidate the current voting round.
+///
+/// **Business Logic:**
+/// - Initializes the round's `RoundResult` and `RoundArchive` PDAs, like `FinalizeRound`, so the
+///   round cannot be finalized afterwards.
+/// - The handler checks that the signer is the admin, who pays for both accounts.
+#[derive(Accounts)]
+pub struct InvalidateRound<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + RoundResult::INIT_SPACE,
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_result: Account<'info, RoundResult>, // Record of the voided round.
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + RoundArchive::INIT_SPACE,
+            seeds = [
+                ROUND_ARCHIVE_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_archive: Account<'info, RoundArchive>, // Standings of the voided round.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to archive a page of a finalized round's projects.
+///
+/// **Business Logic:**
+/// - Permissionless: anyone may pay for the archive's growth, the entries come from the projects.
+#[derive(Accounts)]
+pub struct ArchiveProjects<'info> {
+    #[account(
+            mut,
+            seeds = [
+                ROUND_ARCHIVE_SEED,
+                round_archive.vote_manager.as_ref(),
+                &round_archive.round.to_le_bytes()
+            ],
+            bump = round_archive.bump
+        )]
+    pub round_archive: Account<'info, RoundArchive>, // The archive being written.
+    #[account(mut)]
+    pub signer: Signer<'info>, // Anyone archiving the page.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to tally a page of the current round's projects.
+///
+/// **Business Logic:**
+/// - Initializes the `TallyState` PDA, unique per VoteManager and round, on the first page.
+/// - Permissionless: anyone may pay for the tally state and crank the pages.
+#[derive(Accounts)]
+pub struct TallyPage<'info> {
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + TallyState::INIT_SPACE,
+            seeds = [
+                TALLY_STATE_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub tally_state: Account<'info, TallyState>, // The round's paginated tally.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub signer: Signer<'info>, // Anyone cranking the tally.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to seal a round from its paginated tally.
+///
+/// **Business Logic:**
+/// - Closes the `TallyState` to the signer and initializes the `RoundResult` and `RoundArchive`
+///   PDAs, like `FinalizeRound`.
+/// - `winner` must be the tally's leader, or absent if no project has weight.
+#[derive(Accounts)]
+pub struct TallyCommit<'info> {
+    #[account(
+            mut,
+            close = signer,
+            seeds = [
+                TALLY_STATE_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump = tally_state.bump
+        )]
+    pub tally_state: Account<'info, TallyState>, // The round's completed tally.
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + RoundResult::INIT_SPACE,
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_result: Account<'info, RoundResult>, // Snapshot of the round being finalized.
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + RoundArchive::INIT_SPACE,
+            seeds = [
+                ROUND_ARCHIVE_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_archive: Account<'info, RoundArchive>, // Standings of the round being finalized.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    pub winner: Option<AccountLoader<'info, ProjectData>>, // The tally's leading project.
+    #[account(mut)]
+    pub signer: Signer<'info>, // Anyone committing the tally.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to open a runoff.
+///
+/// **Business Logic:**
+/// - Anyone may sign, like `finalize_round`; the handler checks that a runoff is due.
+#[derive(Accounts)]
+pub struct OpenRunoff<'info> {
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    pub signer: Signer<'info>, // Anyone opening the runoff.
+}
+
+/// Defines the accounts required to attest a finalized round.
+///
+/// **Business Logic:**
+/// - Initializes the `RoundAttestation` PDA, so each round can be attested once; invalidated rounds
+///   cannot be attested.
+/// - The admin pays for and signs the attestation; the handler checks that the signer is the admin.
+/// - `instructions` is the instructions sysvar, read to find the Ed25519 verification.
+#[derive(Accounts)]
+pub struct AttestRound<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + RoundAttestation::INIT_SPACE,
+            seeds = [
+                ROUND_ATTESTATION_SEED,
+                round_result.key().as_ref()
+            ],
+            bump
+        )]
+    pub round_attestation: Account<'info, RoundAttestation>, // The attestation being recorded.
+    #[account(
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &round_result.round.to_le_bytes()
+            ],
+            bump = round_result.bump,
+            constraint = !round_result.invalidated @ VoteError::RoundInvalid
+        )]
+    pub round_result: Account<'info, RoundResult>, // The finalized round being attested.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    /// CHECK: Instructions sysvar, verified by its address.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>, // Instructions of the current transaction.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to create a campaign.
+///
+/// **Business Logic:**
+/// - Initializes the `Campaign` PDA, so a VoteManager runs one campaign at a time.
+/// - The admin pays for the campaign; the handler checks that the signer is the admin.
+#[derive(Accounts)]
+pub struct CreateCampaign<'info> {
+    #[account(
+            init,
+            payer = owner,
+            space = 8 + Campaign::INIT_SPACE,
+            seeds = [
+                CAMPAIGN_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump
+        )]
+    pub campaign: Account<'info, Campaign>, // The campaign being created.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to open a campaign's next round.
+///
+/// **Business Logic:**
+/// - Permissionless, so a crank can open the rounds on schedule.
+#[derive(Accounts)]
+pub struct OpenCampaignRound<'info> {
+    #[account(
+            mut,
+            seeds = [
+                CAMPAIGN_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = campaign.bump
+        )]
+    pub campaign: Account<'info, Campaign>, // The campaign whose next round is opened.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    pub signer: Signer<'info>, // Anyone cranking the campaign.
+}
+
+/// Defines the accounts required to close a campaign.
+///
+/// **Business Logic:**
+/// - Only the admin can close the campaign and receives its rent.
+#[derive(Accounts)]
+pub struct CloseCampaign<'info> {
+    #[account(
+            mut,
+            close = owner,
+            seeds = [
+                CAMPAIGN_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = campaign.bump
+        )]
+    pub campaign: Account<'info, Campaign>, // The campaign being closed.
+    #[account(
+            constraint = vote_manager.admin == owner.key() @ VoteError::NotAdmin
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+}
+
+/// Defines the accounts required to cast a ranked ballot.
+///
+/// **Business Logic:**
+/// - Initializes a Ballot account, unique per voter per round.
+/// - Creates the round's RankedTally with its first ballot.
+/// - Uses the same token account constraints as `Voter` for the fee transfer.
+#[derive(Accounts)]
+pub struct CastRankedBallot<'info> {
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + Ballot::INIT_SPACE,
+            seeds = [
+                BALLOT_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes(),
+                signer.key().as_ref(),
+            ],
+            bump
+        )]
+    pub ballot: Account<'info, Ballot>, // The voter's ballot for this round.
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + RankedTally::INIT_SPACE,
+            seeds = [
+                RANKED_TALLY_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub ranked_tally: Account<'info, RankedTally>, // The round's instant-runoff tally.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            seeds = [
+                TREASURY_SEED,
+                vote_manager.key().as_ref()
+            ],
+            bump = treasury.bump
+        )]
+    pub treasury: Account<'info, Treasury>, // Treasury collecting the voting fees.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = vote_manager.tk_mint,
+            associated_token::authority = treasury,
+        )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>, // Treasury's fee account.
+    #[account(
+      mut,
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            constraint = *token.to_account_info().owner == vote_manager.tk_program @ VoteError::WrongTokenProgram,
+            constraint = token.mint == vote_manager.tk_mint @ VoteError::WrongMint,
+            constraint = token.owner == signer.key() @ VoteError::WrongTokenOwner
+        )]
+    pub token: InterfaceAccount<'info, TokenAccount>, // Voter's token account holding ttt.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to tally a ranked round.
+///
+/// **Business Logic:**
+/// - Permissionless: anyone can run the tally once the round deadline has passed.
+/// - `round_result`, `round_archive` and `winner` are only passed by the call sealing the round,
+///   with the same constraints as `FinalizeRound`.
+#[derive(Accounts)]
+pub struct TallyRankedRound<'info> {
+    #[account(
+            mut,
+            seeds = [
+                RANKED_TALLY_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump = ranked_tally.bump
+        )]
+    pub ranked_tally: Account<'info, RankedTally>, // The round's instant-runoff tally.
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + RoundResult::INIT_SPACE,
+            seeds = [
+                ROUND_RESULT_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_result: Option<Account<'info, RoundResult>>, // Snapshot sealing the round.
+    #[account(
+            init,
+            payer = signer,
+            space = 8 + RoundArchive::INIT_SPACE,
+            seeds = [
+                ROUND_ARCHIVE_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes()
+            ],
+            bump
+        )]
+    pub round_archive: Option<Account<'info, RoundArchive>>, // Standings of the sealed round.
+    #[account(
+            mut,
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    pub winner: Option<AccountLoader<'info, ProjectData>>, // The instant-runoff winner.
+    #[account(mut)]
+    pub signer: Signer<'info>, // Anyone running the tally.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Represents the VoteManager account responsible for managing voting rounds and projects.
+///
+/// **Fields:**
+/// - `version`: Layout version of the account, see `Versioned`.
+/// - `admin`: The admin's public key with authority over the VoteManager.
+/// - `creator`: The initializing admin's public key, used as the PDA seed.
+/// - `election_id`: The election the VoteManager runs, used as the PDA seed next to `creator`.
+/// - `pending_admin`: The proposed successor admin, if any.
+/// - `tk_mint`: The token mint associated with the governance token.
+/// - `tk_program`: The SPL Token program ID.
+/// - `vote_round`: The current active voting round.
+/// - `vote_fee`: The fee required to cast a vote.
+/// - `max_votes_per_round`: The maximum number of votes a voter may cast per round.
+/// - `max_votes_per_project`: The maximum number of votes a voter may cast for one project; zero
+///   means no cap.
+/// - `round_start_ts`: Unix timestamp at which the current round started.
+/// - `round_duration`: Length of a round in seconds; zero means no deadline.
+/// - `weight_curve`: Curve turning a voter's token balance into vote weight.
+/// - `round_total_votes`: Votes cast in the current round.
+/// - `round_total_weight`: Weight cast in the current round.
+/// - `round_participants`: Voters who cast a vote in the current round, each counted once however
+///   many projects they voted for; every compressed vote counts as a participant of its own.
+/// - `round_leader`: The project with the highest weight in the current round.
+/// - `round_leader_weight`: The weight of `round_leader`.
+/// - `commit_reveal`: Whether votes are cast with `commit_vote` / `reveal_vote`.
+/// - `reveal_duration`: Length in seconds of the reveal window following the round deadline.
+/// - `stealth_voting`: Whether votes may be cast with `stealth_vote`.
+/// - `ranked_choice`: Whether votes are cast as ranked ballots with `cast_ranked_ballot`.
+/// - `quadratic_pricing`: Whether a voter's Nth vote on a project costs `N² × vote_fee`.
+/// - `eligibility_root`: Merkle root of the `(voter, weight)` snapshot `do_vote` checks; zero
+///   disables the snapshot.
+/// - `reward_claim_window`: Seconds new reward vaults stay claimable after finalization.
+/// - `proposal_count`: Number of proposals created, the id of the next one.
+/// - `proposal_deposit`: TTT locked by the proposer of a new proposal.
+/// - `proposal_voting_period`: Seconds new proposals stay open for voting.
+/// - `proposal_quorum`: Minimum total weight new proposals need to pass.
+/// - `project_deposit`: TTT escrowed by a permissionless project submission.
+/// - `round_config`: The fee, weight curve and deadline frozen when the current round opened, and
+///   the hash of the economic parameters.
+/// - `round_state`: Where the current round is in its lifecycle.
+/// - `receipt_mint`: Mint `do_vote` mints vote receipts of.
+/// - `vote_receipts`: Whether `do_vote` mints a vote receipt to the voter.
+/// - `escrow_mode`: Whether `do_vote` weighs votes with the voter's StakePosition.
+/// - `max_lock_duration`: Seconds of the longest stake lock, earning full voting power.
+/// - `paused`: Whether votes and project additions are suspended by the admin.
+/// - `fee_mode`: Whether vote fees are collected by the treasury or burned.
+/// - `eligible_weight`: Total weight of the eligibility snapshot, zero without a snapshot.
+/// - `retract_refund`: Whether `retract_vote` refunds the retracted votes' fees.
+/// - `round_leaderboard`: Whether the current round has a Leaderboard every vote must update.
+/// - `referendum_count`: Number of referenda created, the id of the next one.
+/// - `deposit_tranche_votes`: Votes earning an approved submission one refund tranche of its
+///   deposit, zero to refund deposits on approval.
+/// - `deposit_refund_votes`: Votes refunding an approved submission's whole deposit.
+/// - `min_holding_balance`: TTT a voter's token account must hold to register and vote under the
+///   holding requirement.
+/// - `min_holding_slots`: Slots a voter's holding must be registered before the round opens, zero
+///   for no holding requirement.
+/// - `max_projects`: The maximum number of projects a round may hold; zero means no cap.
+/// - `project_count`: Projects added to the current round, submissions counting once approved.
+/// - `tie_break`: How a round whose leading projects tie is decided.
+/// - `runoff_duration`: Seconds a runoff between tied projects lasts under the `Runoff` policy.
+/// - `round_leader_created_slot`: The slot `round_leader` was created in.
+/// - `round_tied`: Whether another project of the current round has the weight of `round_leader`.
+/// - `runoff_weight`: The tied weight while the current round holds a runoff, restricting votes to
+///   the projects that reached it; zero outside a runoff.
+/// - `sol_vote_fee`: The lamports a vote paid in SOL costs, next to the TTT `vote_fee`; zero means
+///   votes cannot be paid in SOL.
+/// - `round_fees`: Voting fees the treasury collected in the current round, net of refunds; the
+///   round's finalization turns them into the reward pool of the winner's voters.
+/// - `owed_fees`: Voting fees of ended rounds not yet paid back to their voters as rewards or
+///   refunds, which the admin cannot withdraw.
+/// - `access_mode`: Whether `do_vote` is open to every wallet, or restricted by the allowlist or
+///   the blocklist.
+/// - `bump`: The VoteManager PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteManager {
+    pub version: u8,                   // Layout version.
+    pub admin: Pubkey,                 // Admin's public key.
+    pub creator: Pubkey,               // Initializing admin, PDA seed.
+    pub election_id: u64,              // Election run by this VoteManager, PDA seed.
+    pub pending_admin: Option<Pubkey>, // Proposed successor admin.
+    pub tk_mint: Pubkey,               // Token mint for governance token (ttt).
+    pub tk_program: Pubkey,            // SPL Token program ID.
+    pub vote_round: u64,               // Current voting round.
+    pub vote_fee: u64,                 // Fee required to cast a vote.
+    pub max_votes_per_round: u16,      // Maximum votes a voter may cast per round.
+    pub max_votes_per_project: u16,    // Maximum votes a voter may cast per project, zero for none.
+    pub round_start_ts: i64,           // Start of the current round (unix timestamp).
+    pub round_duration: i64,           // Round length in seconds, zero for no deadline.
+    pub weight_curve: WeightCurve,     // Balance-to-weight curve applied on every vote.
+    pub round_total_votes: u64,        // Votes cast in the current round.
+    pub round_total_weight: u64,       // Weight cast in the current round.
+    pub round_participants: u64,       // Voters who cast a vote in the current round.
+    pub round_leader: Pubkey,          // Leading project of the current round.
+    pub round_leader_weight: u64,      // Weight of the leading project.
+    pub commit_reveal: bool,           // Commit–reveal voting mode.
+    pub reveal_duration: i64,          // Reveal window length in seconds.
+    pub stealth_voting: bool,          // Stealth voting mode.
+    pub ranked_choice: bool,           // Ranked-choice voting mode.
+    pub quadratic_pricing: bool,       // Quadratic vote pricing.
+    pub eligibility_root: [u8; 32],    // Eligibility snapshot root, zero for none.
+    pub reward_claim_window: i64,      // Reward claim window in seconds.
+    pub proposal_count: u64,           // Proposals created.
+    pub proposal_deposit: u64,         // Deposit required to propose.
+    pub proposal_voting_period: i64,   // Proposal voting period in seconds.
+    pub proposal_quorum: u64,          // Weight required for a proposal to pass.
+    pub project_deposit: u64,          // Deposit required to submit a project.
+    pub round_config: RoundConfig,     // Rules of the current round.
+    pub round_state: RoundState,       // Lifecycle state of the current round.
+    pub receipt_mint: Pubkey,          // Mint of the vote receipts, default until set.
+    pub vote_receipts: bool,           // Whether do_vote mints a receipt to the voter.
+    pub escrow_mode: bool,             // Vote-escrow weighting mode.
+    pub max_lock_duration: i64,        // Longest stake lock in seconds.
+    pub paused: bool,                  // Emergency pause switch.
+    pub fee_mode: FeeMode,             // What happens to vote fees.
+    pub eligible_weight: u64,          // Total weight of the eligibility snapshot.
+    pub retract_refund: bool,          // Whether retracted votes are refunded.
+    pub round_leaderboard: bool,       // Whether the current round has a Leaderboard.
+    pub referendum_count: u64,         // Referenda created.
+    pub deposit_tranche_votes: u64,    // Votes per deposit refund tranche, zero for none.
+    pub deposit_refund_votes: u64,     // Votes refunding the whole deposit.
+    pub min_holding_balance: u64,      // Balance required by the holding requirement.
+    pub min_holding_slots: u64,        // Holding age in slots, zero for no requirement.
+    pub max_projects: u16,             // Maximum projects per round, zero for none.
+    pub project_count: u16,            // Projects added to the current round.
+    pub tie_break: TieBreakPolicy,     // How ties for the lead are decided.
+    pub runoff_duration: i64,          // Runoff length in seconds.
+    pub round_leader_created_slot: u64, // Creation slot of the leading project.
+    pub round_tied: bool,              // Whether the lead is tied.
+    pub runoff_weight: u64,            // Tied weight of the running runoff, zero for none.
+    pub sol_vote_fee: u64,             // Fee of a vote paid in SOL, zero for none.
+    pub round_fees: u64,               // Fees collected in the current round.
+    pub owed_fees: u64,                // Fees of ended rounds owed to their voters.
+    pub access_mode: AccessMode,       // Which wallets may vote.
+    pub bump: u8,                      // VoteManager PDA bump.
+}
+
+impl VoteManager {
+    /// Returns the unix timestamp after which the current round no longer accepts votes, or `None`
+    /// if the round has no deadline.
+    ///
+    /// Reads the deadline frozen in `round_config`; `scheduled_deadline` is the one the next
+    /// opening freezes.
+    pub fn round_deadline(&self) -> Option<i64> {
+        self.round_config.deadline
+    }
+
+    /// Returns the deadline of the configured schedule: `round_start_ts + round_duration`, or
+    /// `None` for a zero `round_duration`.
+    pub fn scheduled_deadline(&self) -> Option<i64> {
+        if self.round_duration == 0 {
+            return None;
+        }
+        self.round_start_ts.checked_add(self.round_duration)
+    }
+
+    /// Returns the unix timestamp after which the round's tally is final: the round deadline, or
+    /// the end of the reveal window in commit–reveal mode.
+    pub fn tally_deadline(&self) -> Option<i64> {
+        let deadline = self.round_deadline()?;
+        if self.commit_reveal {
+            deadline.checked_add(self.reveal_duration)
+        } else {
+            Some(deadline)
+        }
+    }
+
+    /// Adds a counted vote of `weight` to the running round tallies.
+    ///
+    /// `project_weight` is the project's total weight including this vote and `created_slot` the
+    /// slot it was created in; `new_participant` counts a new voter of the round. The running
+    /// leader is updated unless it is unknown, see `leader_unknown`.
+    pub fn record_vote(
+        &mut self,
+        project: Pubkey,
+        project_weight: u64,
+        created_slot: u64,
+        weight: u64,
+        new_participant: bool,
+    ) -> Result<()> {
+        let leader_unknown = self.leader_unknown();
+        if new_participant {
+            self.round_participants =
+                self.round_participants.checked_add(1).ok_or(VoteError::Overflow)?;
+        }
+        self.round_total_votes = self.round_total_votes.checked_add(1).ok_or(VoteError::Overflow)?;
+        self.round_total_weight =
+            self.round_total_weight.checked_add(weight).ok_or(VoteError::Overflow)?;
+        if !leader_unknown {
+            self.update_leader(project, project_weight, created_slot);
+        }
+        Ok(())
+    }
+
+    /// Makes `project`, of total `project_weight` and created in `created_slot`, the running
+    /// leader if it outweighs it. A project drawing level with the leader marks the round tied and
+    /// takes the lead if `tie_break` prefers it.
+    pub fn update_leader(&mut self, project: Pubkey, project_weight: u64, created_slot: u64) {
+        if project_weight > self.round_leader_weight {
+            self.round_leader = project;
+            self.round_leader_weight = project_weight;
+            self.round_leader_created_slot = created_slot;
+            self.round_tied = false;
+        } else if project_weight == self.round_leader_weight
+            && project_weight > 0
+            && project != self.round_leader
+        {
+            self.round_tied = true;
+            let leader = (self.round_leader_created_slot, self.round_leader);
+            if self.tie_break.prefers((created_slot, project), leader) {
+                self.round_leader = project;
+                self.round_leader_created_slot = created_slot;
+            }
+        }
+    }
+
+    /// Clears the running leader and tie, for `update_leader` to establish them again.
+    pub fn clear_leader(&mut self) {
+        self.round_leader = Pubkey::default();
+        self.round_leader_weight = 0;
+        self.round_leader_created_slot = 0;
+        self.round_tied = false;
+    }
+
+    /// Whether a disqualification or a retraction cleared the running leader while the round still
+    /// holds weight, so only a recount of the round's projects can tell it. Votes counted in the
+    /// meantime leave the leader unset rather than crown a project without comparing the others.
+    pub fn leader_unknown(&self) -> bool {
+        self.round_leader == Pubkey::default() && self.round_total_weight > 0
+    }
+
+    /// Whether the round ended in a tie the `Runoff` policy settles with a runoff, which has not
+    /// been held yet.
+    pub fn runoff_due(&self) -> bool {
+        self.tie_break == TieBreakPolicy::Runoff && self.round_tied && self.runoff_weight == 0
+    }
+
+    /// Removes a retracted voter record's `votes` of total `weight` from the running round tallies,
+    /// and the voter from the participants if `left_round`, the record holding their last votes of
+    /// the round.
+    ///
+    /// A retracted leader is cleared, see `leader_unknown`.
+    pub fn retract_vote(
+        &mut self,
+        project: Pubkey,
+        votes: u64,
+        weight: u64,
+        left_round: bool,
+    ) -> Result<()> {
+        if left_round {
+            self.round_participants =
+                self.round_participants.checked_sub(1).ok_or(VoteError::Overflow)?;
+        }
+        self.round_total_votes = self.round_total_votes.checked_sub(votes).ok_or(VoteError::Overflow)?;
+        self.round_total_weight =
+            self.round_total_weight.checked_sub(weight).ok_or(VoteError::Overflow)?;
+        if self.round_leader == project {
+            self.clear_leader();
+        }
+        Ok(())
+    }
+
+    /// Returns the fee of a voter's next vote on a project they already voted `prior_votes` times
+    /// for in the round: the round's frozen fee, or `(prior_votes + 1)²` times it with quadratic
+    /// pricing.
+    pub fn vote_price(&self, prior_votes: u16) -> Result<u64> {
+        self.vote_price_in(FeeCurrency::Ttt, prior_votes)
+    }
+
+    /// Returns the fee of a voter's next vote like `vote_price`, in `currency`: base units of TTT,
+    /// or lamports priced on the round's frozen `sol_vote_fee`.
+    pub fn vote_price_in(&self, currency: FeeCurrency, prior_votes: u16) -> Result<u64> {
+        let vote_fee = match currency {
+            FeeCurrency::Ttt => self.round_config.vote_fee,
+            FeeCurrency::Sol => self.round_config.sol_vote_fee,
+        };
+        if !self.quadratic_pricing {
+            return Ok(vote_fee);
+        }
+        let n = u64::from(prior_votes) + 1;
+        let price = n
+            .checked_mul(n)
+            .and_then(|n_squared| n_squared.checked_mul(vote_fee))
+            .ok_or(VoteError::Overflow)?;
+        Ok(price)
+    }
+
+    /// Returns the voting power of `amount` TTT locked for `lock_duration` seconds:
+    /// `amount × lock_duration / max_lock_duration`, rounded down.
+    pub fn escrow_power(&self, amount: u64, lock_duration: i64) -> Result<u64> {
+        let (Ok(lock_duration), Ok(max_lock_duration)) = (
+            u128::try_from(lock_duration),
+            u128::try_from(self.max_lock_duration),
+        ) else {
+            return err!(VoteError::InvalidLockDuration);
+        };
+        require!(max_lock_duration > 0, VoteError::InvalidLockDuration);
+        let power = u128::from(amount) * lock_duration / max_lock_duration;
+        u64::try_from(power).map_err(|_| error!(VoteError::Overflow))
+    }
+
+    /// Returns the seed of the election, which the VoteManager, project and voter PDAs end with.
+    pub fn election_seed(&self) -> Vec<u8> {
+        election_seed(self.election_id)
+    }
+
+    /// Returns whether votes are restricted to the wallets of an eligibility snapshot.
+    pub fn eligibility_snapshot(&self) -> bool {
+        self.eligibility_root != [0; 32]
+    }
+
+    /// Returns whether votes require a HoldingAttestation registered before the round opened.
+    pub fn holding_requirement(&self) -> bool {
+        self.min_holding_slots > 0
+    }
+
+    /// Returns the current round's turnout in basis points: the weight cast over the snapshot's
+    /// `eligible_weight`, zero without a snapshot.
+    ///
+    /// Exceeds 10,000 when voters cast several votes each, as every vote counts its weight.
+    pub fn turnout_bps(&self) -> Result<u64> {
+        if self.eligible_weight == 0 {
+            return Ok(0);
+        }
+        let turnout =
+            u128::from(self.round_total_weight) * 10_000 / u128::from(self.eligible_weight);
+        u64::try_from(turnout).map_err(|_| error!(VoteError::Overflow))
+    }
+
+    /// Moves to the next round starting at `now` and clears the running round tallies, adding the
+    /// ended round's fees to `owed_fees`.
+    ///
+    /// The new round is `Pending` until the admin opens it.
+    pub fn start_next_round(&mut self, now: i64, slot: u64) -> Result<()> {
+        self.vote_round = self.vote_round.checked_add(1).ok_or(VoteError::Overflow)?;
+        self.round_state = RoundState::Pending;
+        self.round_start_ts = now;
+        self.round_total_votes = 0;
+        self.round_total_weight = 0;
+        self.round_participants = 0;
+        self.round_leader = Pubkey::default();
+        self.round_leader_weight = 0;
+        self.round_leaderboard = false;
+        self.project_count = 0;
+        self.round_leader_created_slot = 0;
+        self.round_tied = false;
+        self.runoff_weight = 0;
+        // The ended round's fees stay owed to its voters, as rewards or refunds.
+        self.owed_fees = self
+            .owed_fees
+            .checked_add(self.round_fees)
+            .ok_or(VoteError::Overflow)?;
+        self.round_fees = 0;
+        self.open_round_config(now, slot);
+        Ok(())
+    }
+
+    /// Counts `added` more projects in the current round.
+    ///
+    /// Fails with `TooManyProjects` if they would take the round past `max_projects`.
+    pub fn count_projects(&mut self, added: usize) -> Result<()> {
+        let project_count = u16::try_from(added)
+            .ok()
+            .and_then(|added| self.project_count.checked_add(added))
+            .ok_or(VoteError::TooManyProjects)?;
+        require!(
+            self.max_projects == 0 || project_count <= self.max_projects,
+            VoteError::TooManyProjects
+        );
+        self.project_count = project_count;
+        Ok(())
+    }
+
+    /// Freezes the current fee, weight curve and deadline as the rules of the round opened at
+    /// `now` in `slot`, and records the hash of the parameters.
+    pub fn open_round_config(&mut self, now: i64, slot: u64) {
+        self.round_config = RoundConfig {
+            round: self.vote_round,
+            config_hash: [0; 32],
+            opened_at: now,
+            opened_slot: slot,
+            vote_fee: self.vote_fee,
+            sol_vote_fee: self.sol_vote_fee,
+            weight_curve: self.weight_curve,
+            deadline: self.scheduled_deadline(),
+        };
+        // The hash covers the frozen rules, so it is taken once they are in place.
+        self.round_config.config_hash = self.config_hash();
+    }
+
+    /// Returns the hash of every parameter that decides how votes are charged, weighed and
+    /// counted, how the round's rewards are paid out, and the weight its turnout is reported
+    /// against.
+    ///
+    /// The fee, weight curve and deadline are taken from `round_config`, so changing them for the
+    /// next round does not invalidate the round in progress.
+    pub fn config_hash(&self) -> [u8; 32] {
+        let round = &self.round_config;
+        hashv(&[
+            &round.vote_fee.to_le_bytes(),
+            &self.max_votes_per_round.to_le_bytes(),
+            &self.max_votes_per_project.to_le_bytes(),
+            &self.round_start_ts.to_le_bytes(),
+            &round.deadline.unwrap_or(0).to_le_bytes(),
+            &[round.weight_curve.mode as u8],
+            &round.weight_curve.multiplier.to_le_bytes(),
+            &round.weight_curve.cap.to_le_bytes(),
+            &[u8::from(self.commit_reveal)],
+            &self.reveal_duration.to_le_bytes(),
+            &[u8::from(self.stealth_voting)],
+            &[u8::from(self.ranked_choice)],
+            &[u8::from(self.quadratic_pricing)],
+            &self.eligibility_root,
+            &self.eligible_weight.to_le_bytes(),
+            &self.reward_claim_window.to_le_bytes(),
+            &[u8::from(self.escrow_mode)],
+            &self.max_lock_duration.to_le_bytes(),
+            &self.min_holding_balance.to_le_bytes(),
+            &self.min_holding_slots.to_le_bytes(),
+            &[self.tie_break as u8],
+            &self.runoff_duration.to_le_bytes(),
+            &round.sol_vote_fee.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+/// The rules a round opened with, so a finalized round proves they did not change.
+///
+/// The fee, weight curve and deadline are frozen here when the round opens and read by the vote
+/// instructions, so changing them in the VoteManager only affects the next round.
+///
+/// **Fields:**
+/// - `round`: The round the rules apply to.
+/// - `config_hash`: `VoteManager::config_hash` when the round opened.
+/// - `opened_at`: Unix timestamp of the round's opening.
+/// - `opened_slot`: Slot of the round's opening, which holding attestations must predate.
+/// - `vote_fee`: Fee the round's votes are charged, before quadratic pricing.
+/// - `sol_vote_fee`: Lamports the round's votes paid in SOL are charged, before quadratic pricing.
+/// - `weight_curve`: Balance-to-weight curve the round's votes are weighed with.
+/// - `deadline`: Unix timestamp after which the round no longer accepts votes, `None` for none.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct RoundConfig {
+    pub round: u64,                // Round the rules apply to.
+    pub config_hash: [u8; 32],     // Hash of the economic parameters.
+    pub opened_at: i64,            // Opening timestamp.
+    pub opened_slot: u64,          // Opening slot.
+    pub vote_fee: u64,             // Frozen voting fee.
+    pub sol_vote_fee: u64,         // Frozen voting fee in lamports.
+    pub weight_curve: WeightCurve, // Frozen weight curve.
+    pub deadline: Option<i64>,     // Frozen round deadline.
+}
+
+/// Lifecycle of a voting round.
+///
+/// **Variants:**
+/// - `Pending`: The round is being configured; projects can be added, votes are rejected.
+/// - `Open`: Votes are accepted within the round's schedule; the project list is frozen.
+/// - `Closed`: The admin stopped voting; the round waits to be finalized.
+/// - `Finalized`: The round was sealed into its `RoundResult`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum RoundState {
+    #[default]
+    Pending,
+    Open,
+    Closed,
+    Finalized,
+}
+
+/// Role a wallet holds on a VoteManager.
+///
+/// **Variants:**
+/// - `Admin`: Every permission; held by the VoteManager's `admin` key and handed over with
+///   `propose_admin`, never granted.
+/// - `Operator`: Adds projects and increments rounds.
+/// - `Auditor`: No on-chain permissions; identifies the wallets off-chain tools trust to review
+///   the VoteManager.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    Admin,
+    Operator,
+    Auditor,
+}
+
+/// What happens to the fee a vote pays.
+///
+/// **Variants:**
+/// - `Collect`: The fee is transferred to the treasury's fee account, refundable and withdrawable.
+/// - `Burn`: The fee is burned from the voter's token account, shrinking the TTT supply.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum FeeMode {
+    #[default]
+    Collect,
+    Burn,
+}
+
+/// Which wallets `do_vote` accepts votes from.
+///
+/// **Variants:**
+/// - `Open`: Every wallet.
+/// - `Allowlist`: Only wallets the admin added to the allowlist.
+/// - `Blocklist`: Every wallet but those the admin added to the blocklist.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum AccessMode {
+    #[default]
+    Open,
+    Allowlist,
+    Blocklist,
+}
+
+/// What a vote's fee is paid in.
+///
+/// **Variants:**
+/// - `Ttt`: The round's `vote_fee` in TTT, collected or burned under the `FeeMode`.
+/// - `Sol`: The round's `sol_vote_fee` in lamports, transferred to the Treasury PDA through the
+///   System Program; always collected, whatever the `FeeMode`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeCurrency {
+    #[default]
+    Ttt,
+    Sol,
+}
+
+/// Why `ensure_user_can_vote` is called, sent as the `reason` code of `TopUpArgs`.
+///
+/// **Variants:**
+/// - `Vote` (0): Funds a `do_vote` paying in TTT later in the transaction.
+/// - `SponsoredVote` (1): Funds a `sponsored_vote` later in the transaction.
+/// - `AccountOnly` (2): Only creates the voter's token account, for a vote paid in SOL; the amount
+///   must be 0.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum TopUpReason {
+    Vote,
+    SponsoredVote,
+    AccountOnly,
+}
+
+/// Arguments of `ensure_user_can_vote`.
+///
+/// **Fields:**
+/// - `amount`: The vote fee the voter's balance must cover once topped up.
+/// - `reason`: A `TopUpReason` code, checked on-chain against `amount` and the funded vote.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TopUpArgs {
+    pub amount: u64,
+    pub reason: u8,
+}
+
+impl TopUpArgs {
+    pub fn new(amount: u64, reason: TopUpReason) -> Self {
+        TopUpArgs {
+            amount,
+            reason: reason as u8,
+        }
+    }
+
+    /// Decodes `reason`, rejecting unknown codes and an account-only top-up carrying an amount.
+    pub fn reason(&self) -> Result<TopUpReason> {
+        let reason = match self.reason {
+            0 => TopUpReason::Vote,
+            1 => TopUpReason::SponsoredVote,
+            2 => TopUpReason::AccountOnly,
+            _ => return err!(VoteError::InvalidTopUpReason),
+        };
+        require!(
+            reason != TopUpReason::AccountOnly || self.amount == 0,
+            VoteError::InvalidTopUpReason
+        );
+        Ok(reason)
+    }
+}
+
+/// How a round whose leading projects have the same weight is decided.
+///
+/// **Variants:**
+/// - `EarliestCreated`: The project created in the earliest slot wins, the lowest PDA among
+///   projects of the same slot.
+/// - `LowestPda`: The project with the lowest PDA wins.
+/// - `Runoff`: The tied projects go to a runoff of `runoff_duration` seconds taking votes for them
+///   only; a runoff ending tied again is decided like `EarliestCreated`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum TieBreakPolicy {
+    #[default]
+    EarliestCreated,
+    LowestPda,
+    Runoff,
+}
+
+impl TieBreakPolicy {
+    /// Whether `challenger` wins a tie against `leader`, both given as `(created_slot, project)`.
+    pub fn prefers(self, challenger: (u64, Pubkey), leader: (u64, Pubkey)) -> bool {
+        match self {
+            TieBreakPolicy::LowestPda => challenger.1 < leader.1,
+            TieBreakPolicy::EarliestCreated | TieBreakPolicy::Runoff => challenger < leader,
+        }
+    }
+}
+
+/// Shape of the curve evaluated on a voter's token balance.
+///
+/// **Variants:**
+/// - `Flat`: Every vote weighs the same regardless of balance.
+/// - `Linear`: Weight grows proportionally with balance.
+/// - `Sqrt`: Weight grows with the square root of balance, dampening large holders.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum WeightMode {
+    #[default]
+    Flat,
+    Linear,
+    Sqrt,
+}
+
+/// A voter's entry in the eligibility snapshot with its Merkle proof.
+///
+/// **Fields:**
+/// - `weight`: The vote weight frozen in the snapshot.
+/// - `path`: The sibling hashes from the voter's leaf up to the root.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct EligibilityProof {
+    pub weight: u64,         // Snapshot vote weight.
+    pub path: Vec<[u8; 32]>, // Sibling hashes, leaf to root.
+}
+
+/// Admin-defined curve turning a voter's token balance into vote weight.
+///
+/// **Fields:**
+/// - `mode`: Base shape of the curve.
+/// - `multiplier`: Fixed-point scale applied to the base shape (`WEIGHT_PRECISION` == 1.0).
+/// - `cap`: Maximum weight of a single vote; zero means uncapped.
+///
+/// Combining `mode` and `cap` yields the piecewise curves, e.g. a capped linear curve is linear up
+/// to the balance where it reaches `cap` and flat afterwards.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct WeightCurve {
+    pub mode: WeightMode, // Base shape of the curve.
+    pub multiplier: u64,  // Fixed-point scale, `WEIGHT_PRECISION` == 1.0.
+    pub cap: u64,         // Maximum weight of a single vote, zero for uncapped.
+}
+
+impl Default for WeightCurve {
+    /// One vote, one weight.
+    fn default() -> Self {
+        Self {
+            mode: WeightMode::Flat,
+            multiplier: WEIGHT_PRECISION,
+            cap: 0,
+        }
+    }
+}
+
+impl WeightCurve {
+    /// Evaluates the curve for a token `balance` in base units.
+    ///
+    /// All math is done in `u128` and the result saturates at `u64::MAX` before the cap is applied.
+    pub fn weight(&self, balance: u64) -> u64 {
+        let base: u128 = match self.mode {
+            WeightMode::Flat => 1,
+            WeightMode::Linear => u128::from(balance),
+            WeightMode::Sqrt => isqrt(u128::from(balance)),
+        };
+        let scaled = base * u128::from(self.multiplier) / u128::from(WEIGHT_PRECISION);
+        let weight = u64::try_from(scaled).unwrap_or(u64::MAX);
+
+        if self.cap == 0 {
+            weight
+        } else {
+            weight.min(self.cap)
+        }
+    }
+}
+
+/// The VoteManager layout preceding `u64` round numbers, read by `migrate_vote_manager`.
+///
+/// **Fields:**
+/// - Those of `VoteManager`, with a `u8` `vote_round` and a `LegacyRoundConfig`.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyVoteManager {
+    pub admin: Pubkey,                   // Admin's public key.
+    pub creator: Pubkey,                 // Initializing admin, PDA seed.
+    pub pending_admin: Option<Pubkey>,   // Proposed successor admin.
+    pub tk_mint: Pubkey,                 // Token mint for governance token (ttt).
+    pub tk_program: Pubkey,              // SPL Token program ID.
+    pub vote_round: u8,                  // Current voting round.
+    pub vote_fee: u64,                   // Fee required to cast a vote.
+    pub max_votes_per_round: u16,        // Maximum votes a voter may cast per round.
+    pub round_start_ts: i64,             // Start of the current round (unix timestamp).
+    pub round_duration: i64,             // Round length in seconds, zero for no deadline.
+    pub weight_curve: WeightCurve,       // Balance-to-weight curve applied on every vote.
+    pub round_total_votes: u64,          // Votes cast in the current round.
+    pub round_total_weight: u64,         // Weight cast in the current round.
+    pub round_participants: u64,         // Voter records created in the current round.
+    pub round_leader: Pubkey,            // Leading project of the current round.
+    pub round_leader_weight: u64,        // Weight of the leading project.
+    pub commit_reveal: bool,             // Commit–reveal voting mode.
+    pub reveal_duration: i64,            // Reveal window length in seconds.
+    pub stealth_voting: bool,            // Stealth voting mode.
+    pub reward_claim_window: i64,        // Reward claim window in seconds.
+    pub proposal_count: u64,             // Proposals created.
+    pub proposal_deposit: u64,           // Deposit required to propose.
+    pub proposal_voting_period: i64,     // Proposal voting period in seconds.
+    pub proposal_quorum: u64,            // Weight required for a proposal to pass.
+    pub project_deposit: u64,            // Deposit required to submit a project.
+    pub round_config: LegacyRoundConfig, // Rules of the current round.
+    pub bump: u8,                        // VoteManager PDA bump.
+}
+
+/// The `RoundConfig` layout preceding `u64` round numbers.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyRoundConfig {
+    pub round: u8,             // Round the rules apply to.
+    pub config_hash: [u8; 32], // Hash of the economic parameters.
+    pub opened_at: i64,        // Opening timestamp.
+}
+
+/// Accounts whose layout is versioned by a `version` byte right after the discriminator, so
+/// `migrate_account` can tell a changed layout from the one it replaces and upgrade it.
+///
+/// Version 0 is the unversioned layout preceding the byte: the same fields without `version`.
+/// Bump `VERSION` and extend `upgrade_layout` with every layout change of an implementor.
+pub trait Versioned: AccountSerialize + AccountDeserialize + Discriminator {
+    /// The layout version the program writes.
+    const VERSION: u8;
+
+    /// Returns the layout version the account was decoded from.
+    fn version(&self) -> u8;
+
+    /// Sets the layout version the account is written with.
+    fn set_version(&mut self, version: u8);
+
+    /// Returns the size the account takes in the current layout, discriminator included.
+    fn space(&self) -> usize;
+
+    /// Rewrites `data`, an account in the layout of version `from`, into the layout of version
+    /// `from + 1`, keeping the `version` byte; called for every `from` from 1 up to `VERSION - 1`.
+    fn upgrade_layout(_data: &mut Vec<u8>, _from: u8) {}
+
+    /// Decodes the account from `data` in the current layout or any one preceding it, reporting
+    /// the version it was written in; the unversioned layout is version 0.
+    ///
+    /// The version byte overlaps the first field of an unversioned account, so the layouts are
+    /// told apart by their size.
+    fn try_deserialize_versioned(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= 8, ErrorCode::AccountDiscriminatorNotFound);
+        if let Ok(account) = Self::try_deserialize(&mut &data[..]) {
+            if account.version() == Self::VERSION && account.space() == data.len() {
+                return Ok(account);
+            }
+        }
+
+        // Read older layouts as the current one, newest first.
+        for version in (0..Self::VERSION).rev() {
+            let mut upgraded = data.to_vec();
+            if version == 0 {
+                upgraded.insert(8, 0);
+            }
+            for from in version.max(1)..Self::VERSION {
+                Self::upgrade_layout(&mut upgraded, from);
+            }
+            if let Ok(account) = Self::try_deserialize(&mut upgraded.as_slice()) {
+                if account.version() == version && account.space() == upgraded.len() {
+                    return Ok(account);
+                }
+            }
+        }
+        err!(ErrorCode::AccountDidNotDeserialize)
+    }
+}
+
+impl Versioned for VoteManager {
+    const VERSION: u8 = 2;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn space(&self) -> usize {
+        8 + VoteManager::INIT_SPACE
+    }
+
+    fn upgrade_layout(data: &mut Vec<u8>, from: u8) {
+        // Version 2 added `access_mode` before `bump`, the last serialized field. An unset
+        // `pending_admin` serializes 32 bytes short of its space, leaving them as padding after
+        // `bump`.
+        if from == 1 {
+            let pending_admin_tag = 8 + 1 + 32 + 32 + 8;
+            let padding = if data.get(pending_admin_tag) == Some(&0) {
+                32
+            } else {
+                0
+            };
+            let bump = data.len().saturating_sub(padding + 1);
+            data.insert(bump, AccessMode::Open as u8);
+        }
+    }
+}
+
+impl Versioned for ProjectData {
+    const VERSION: u8 = 2;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn space(&self) -> usize {
+        8 + std::mem::size_of::<ProjectData>()
+    }
+
+    fn try_deserialize_versioned(data: &[u8]) -> Result<Self> {
+        // Version 2 replaced the Borsh layouts of `LegacyProjectData` with a zero-copy one.
+        match read_zero_copy(data, Self::VERSION) {
+            Some(project) => Ok(project),
+            None => LegacyProjectData::try_deserialize_versioned(data).map(ProjectData::from),
+        }
+    }
+}
+
+impl AccountSerialize for ProjectData {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        write_zero_copy(self, writer)
+    }
+}
+
+impl Versioned for VoterData {
+    const VERSION: u8 = 2;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn space(&self) -> usize {
+        8 + std::mem::size_of::<VoterData>()
+    }
+
+    fn try_deserialize_versioned(data: &[u8]) -> Result<Self> {
+        // Version 2 replaced the Borsh layouts of `LegacyVoterData` with a zero-copy one.
+        match read_zero_copy(data, Self::VERSION) {
+            Some(voter_data) => Ok(voter_data),
+            None => LegacyVoterData::try_deserialize_versioned(data).map(VoterData::from),
+        }
+    }
+}
+
+impl AccountSerialize for VoterData {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        write_zero_copy(self, writer)
+    }
+}
+
+/// Decodes a zero-copy account of layout `version` from `data`, or `None` if `data` is not one.
+///
+/// Reads the struct unaligned, since the data of a fetched account need not be aligned for it.
+fn read_zero_copy<T: ZeroCopy>(data: &[u8], version: u8) -> Option<T> {
+    let body = data.strip_prefix(&T::DISCRIMINATOR[..])?;
+    (body.len() == std::mem::size_of::<T>() && body.first() == Some(&version))
+        .then(|| bytemuck::pod_read_unaligned(body))
+}
+
+/// Writes the discriminator and bytes of a zero-copy account, as `AccountLoader` lays it out.
+fn write_zero_copy<T: ZeroCopy, W: std::io::Write>(account: &T, writer: &mut W) -> Result<()> {
+    writer
+        .write_all(&T::DISCRIMINATOR)
+        .and_then(|()| writer.write_all(bytemuck::bytes_of(account)))
+        .map_err(|_| error!(ErrorCode::AccountDidNotSerialize))
+}
+
+/// Returns the first `len` bytes of the zero-padded `bytes` as a string.
+fn fixed_str(bytes: &[u8], len: u8) -> &str {
+    let len = usize::from(len).min(bytes.len());
+    std::str::from_utf8(&bytes[..len]).unwrap_or_default()
+}
+
+/// Copies `text` into `bytes`, zero-padding the rest, and returns its length.
+///
+/// The handlers check that `text` fits before it gets here.
+fn write_fixed_str(bytes: &mut [u8], text: &str) -> u8 {
+    bytes.fill(0);
+    bytes[..text.len()].copy_from_slice(text.as_bytes());
+    text.len() as u8
+}
+
+/// Loads a zero-copy account `init_if_needed` may have created in this instruction.
+///
+/// Anchor only writes the discriminator of a new account on exit, so it is initialized here
+/// instead; hold on to the returned reference, a second load would fail before exit.
+fn load_or_init<'a, T: ZeroCopy + Owner>(
+    account: &'a AccountLoader<'_, T>,
+) -> Result<RefMut<'a, T>> {
+    if is_created(account.as_ref())? {
+        account.load_init()
+    } else {
+        account.load_mut()
+    }
+}
+
+/// Reads a VoterData account `init_if_needed` may have created in this instruction, all zero
+/// if it did.
+pub fn read_voter_data(voter_data: &AccountLoader<VoterData>) -> Result<VoterData> {
+    if is_created(voter_data.as_ref())? {
+        return Ok(bytemuck::Zeroable::zeroed());
+    }
+    Ok(*voter_data.load()?)
+}
+
+/// Returns whether the zero-copy account `info` was created in this instruction and still has
+/// no discriminator.
+fn is_created(info: &AccountInfo) -> Result<bool> {
+    Ok(info.try_borrow_data()?.get(..8) == Some(&[0; 8][..]))
+}
+
+/// Represents the ProjectData account for each project under governance.
+///
+/// Zero-copy, so a vote reads and updates the fields in place instead of decoding the account
+/// and reallocating its strings: the id, name and URI are zero-padded byte arrays with their
+/// length kept alongside, read with `id()`, `name()` and `uri()`.
+///
+/// **Fields:**
+/// - `version`: Layout version of the account, see `Versioned`.
+/// - `id_len`, `name_len`, `uri_len`: Lengths of `id`, `name` and `uri`.
+/// - `category`: Category code grouping the project into a track of the round, 0 if
+///   uncategorized; named in the CategoryRegistry, each category has its own winner.
+/// - `disqualified`: 1 if the admin disqualified the project.
+/// - `pending`: 1 while the project was submitted and awaits the admin's approval.
+/// - `bump`: The ProjectData PDA bump.
+/// - `vote_manager`: Reference to the VoteManager account.
+/// - `payer`: The account that paid the project's rent.
+/// - `id`: Unique identifier for the project.
+/// - `name`: Display name of the project, empty until the admin sets the metadata.
+/// - `uri`: URI of the project's off-chain JSON description.
+/// - `padding`: Keeps the layout free of implicit padding.
+/// - `vote_round`: The voting round in which the project is active.
+/// - `vote_count`: Total number of votes the project has received.
+/// - `vote_weight`: Sum of the weights of all votes the project has received.
+/// - `voter_records`: Number of open VoterData accounts for the project.
+/// - `deposit`: TTT escrowed in the treasury by the submitter until review, or, for a bonded
+///   listing, until its votes earn it back.
+/// - `deposit_refunded`: Part of the deposit already refunded to the submitter in tranches.
+/// - `tranche_votes`: Votes earning one refund tranche, frozen from the VoteManager on submission.
+/// - `refund_votes`: Votes refunding the whole deposit, zero if approval refunds it.
+/// - `created_slot`: The slot the project was added or submitted in, breaking ties under the
+///   `EarliestCreated` policy.
+#[account(zero_copy)]
+pub struct ProjectData {
+    pub version: u8,                      // Layout version.
+    pub id_len: u8,                       // Length of the id.
+    pub name_len: u8,                     // Length of the name.
+    pub uri_len: u8,                      // Length of the URI.
+    pub category: u8,                     // Category code, 0 if uncategorized.
+    pub disqualified: u8,                 // Disqualified projects reject votes.
+    pub pending: u8,                      // Submitted projects reject votes until approved.
+    pub bump: u8,                         // ProjectData PDA bump.
+    pub vote_manager: Pubkey,             // Reference to the VoteManager account.
+    pub payer: Pubkey,                    // Rent payer, refunded on close.
+    pub id: [u8; PROJECT_ID_MAX_LEN],     // Unique project identifier, zero-padded.
+    pub name: [u8; PROJECT_NAME_MAX_LEN], // Display name, zero-padded.
+    pub uri: [u8; PROJECT_URI_MAX_LEN],   // Off-chain JSON description, zero-padded.
+    pub padding: [u8; 6],                 // Keeps the layout free of implicit padding.
+    pub vote_round: u64,                  // Voting round associated with the project.
+    pub vote_count: u64,                  // Total votes received.
+    pub vote_weight: u64,                 // Total weight received.
+    pub voter_records: u64,               // Open VoterData accounts.
+    pub deposit: u64,                     // Escrowed submission deposit.
+    pub deposit_refunded: u64,            // Deposit refunded in tranches.
+    pub tranche_votes: u64,               // Votes per refund tranche.
+    pub refund_votes: u64,                // Votes refunding the whole deposit.
+    pub created_slot: u64,                // Slot the project was created in.
+}
+
+/// Represents the VoterData account tracking a voter's activity.
+///
+/// Zero-copy like ProjectData, `project_name` being zero-padded with its length alongside.
+///
+/// **Fields:**
+/// - `version`: Layout version of the account, see `Versioned`.
+/// - `project_name_len`: Length of `project_name`.
+/// - `refunded`: 1 once `fees_paid` and `sol_fees_paid` were refunded after the project was
+///   disqualified.
+/// - `retracted`: 1 while the votes are retracted with `retract_vote` and not cast again.
+/// - `reward_claimed`: 1 once the voter claimed their share of the round's reward pool, for a
+///   record of the winning project.
+/// - `bump`: The VoterData PDA bump.
+/// - `votes_for_project`: Votes the voter has cast for the record's project, checked against
+///   `max_votes_per_project`.
+/// - `voter`: The voter's public key, default for stealth records.
+/// - `blinded_voter`: `stealth_voter_id(voter, nonce)` for stealth records, zero otherwise.
+/// - `project_name`: The id of the project the voter last voted for.
+/// - `padding`: Keeps the layout free of implicit padding.
+/// - `last_voted_round`: The last round in which the voter cast a vote.
+/// - `vote_count`: Total number of votes the voter has cast.
+/// - `vote_weight`: Total weight of the votes the voter has cast.
+/// - `fees_paid`: Voting fees paid for the votes on the record's project.
+/// - `sol_fees_paid`: Lamports paid for the votes on the record's project paid in SOL.
+#[account(zero_copy)]
+pub struct VoterData {
+    pub version: u8,                            // Layout version.
+    pub project_name_len: u8,                   // Length of the project id.
+    pub refunded: u8,                           // Whether the fees were refunded.
+    pub retracted: u8,                          // Whether the votes were retracted.
+    pub reward_claimed: u8,                     // Whether the reward share was claimed.
+    pub bump: u8,                               // VoterData PDA bump.
+    pub votes_for_project: u16,                 // Votes cast for the project.
+    pub voter: Pubkey,                          // Voter's public key.
+    pub blinded_voter: [u8; 32],                // Blinded voter id of stealth records.
+    pub project_name: [u8; PROJECT_ID_MAX_LEN], // Id of the project voted for, zero-padded.
+    pub padding: [u8; 6],                       // Keeps the layout free of implicit padding.
+    pub last_voted_round: u64,                  // Last round the voter participated in.
+    pub vote_count: u64,                        // Total votes cast by the voter.
+    pub vote_weight: u64,                       // Total weight cast by the voter.
+    pub fees_paid: u64,                         // Fees paid for the votes on the project.
+    pub sol_fees_paid: u64,                     // Lamports paid for the votes on the project.
+}
+
+impl ProjectData {
+    /// Returns the project id.
+    pub fn id(&self) -> &str {
+        fixed_str(&self.id, self.id_len)
+    }
+
+    /// Returns the display name, empty until the admin sets the metadata.
+    pub fn name(&self) -> &str {
+        fixed_str(&self.name, self.name_len)
+    }
+
+    /// Returns the URI of the off-chain JSON description.
+    pub fn uri(&self) -> &str {
+        fixed_str(&self.uri, self.uri_len)
+    }
+
+    /// Sets the project id, at most `PROJECT_ID_MAX_LEN` bytes.
+    pub fn set_id(&mut self, id: &str) {
+        self.id_len = write_fixed_str(&mut self.id, id);
+    }
+
+    /// Sets the display name and URI, at most `PROJECT_NAME_MAX_LEN` and `PROJECT_URI_MAX_LEN`
+    /// bytes.
+    pub fn set_metadata(&mut self, name: &str, uri: &str) {
+        self.name_len = write_fixed_str(&mut self.name, name);
+        self.uri_len = write_fixed_str(&mut self.uri, uri);
+    }
+
+    /// Returns the part of the deposit the project's votes earned back that is still escrowed.
+    ///
+    /// Every `tranche_votes` votes earn `tranche_votes / refund_votes` of the original deposit, so
+    /// the whole deposit is refunded once the project reaches `refund_votes` votes.
+    pub fn claimable_deposit(&self) -> u64 {
+        if self.pending != 0 || self.refund_votes == 0 {
+            return 0;
+        }
+        let earned_votes =
+            (self.vote_count / self.tranche_votes * self.tranche_votes).min(self.refund_votes);
+        let bond = self.deposit as u128 + self.deposit_refunded as u128;
+        let earned = bond * earned_votes as u128 / self.refund_votes as u128;
+        (earned as u64).saturating_sub(self.deposit_refunded)
+    }
+}
+
+impl VoterData {
+    /// Returns the id of the project the voter last voted for.
+    pub fn project_name(&self) -> &str {
+        fixed_str(&self.project_name, self.project_name_len)
+    }
+
+    /// Sets the id of the project the voter last voted for.
+    pub fn set_project_name(&mut self, id: &str) {
+        self.project_name_len = write_fixed_str(&mut self.project_name, id);
+    }
+
+    /// Returns whether the record holds fees the voter can still claim back once its project is
+    /// disqualified.
+    pub fn refund_due(&self) -> bool {
+        (self.fees_paid > 0 || self.sol_fees_paid > 0) && self.refunded == 0
+    }
+
+    /// Returns the reward share the voter can still claim from `round_result`, assuming the record
+    /// is one of the round's winning project.
+    pub fn reward_due(&self, round_result: &RoundResult) -> u64 {
+        if self.reward_claimed != 0 {
+            return 0;
+        }
+        round_result.reward_share(self.vote_weight)
+    }
+}
+
+/// The Borsh layouts of ProjectData preceding the zero-copy one, read by `migrate_account`.
+///
+/// **Fields:**
+/// - Those of `ProjectData`, in the order they were serialized, with `String` ids, names and
+///   URIs and `bool` flags.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyProjectData {
+    pub version: u8,          // Layout version.
+    pub vote_manager: Pubkey, // Reference to the VoteManager account.
+    #[max_len(PROJECT_ID_MAX_LEN)]
+    pub id: String, // Unique project identifier.
+    pub vote_round: u64,      // Voting round associated with the project.
+    pub vote_count: u64,      // Total votes received.
+    pub vote_weight: u64,     // Total weight received.
+    pub disqualified: bool,   // Disqualified projects reject votes.
+    pub payer: Pubkey,        // Rent payer, refunded on close.
+    pub voter_records: u64,   // Open VoterData accounts.
+    pub pending: bool,        // Submitted projects reject votes until approved.
+    pub deposit: u64,         // Escrowed submission deposit.
+    pub deposit_refunded: u64, // Deposit refunded in tranches.
+    pub tranche_votes: u64,   // Votes per refund tranche.
+    pub refund_votes: u64,    // Votes refunding the whole deposit.
+    #[max_len(0)]
+    pub name: String, // Display name.
+    #[max_len(0)]
+    pub uri: String, // Off-chain JSON description.
+    pub category: u8,         // Category code, 0 if uncategorized.
+    pub created_slot: u64,    // Slot the project was created in.
+    pub bump: u8,             // ProjectData PDA bump.
+}
+
+/// The Borsh layouts of VoterData preceding the zero-copy one, read by `migrate_account`.
+///
+/// **Fields:**
+/// - Those of `VoterData`, in the order they were serialized, with a `String` project id and
+///   `bool` flags.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyVoterData {
+    pub version: u8,   // Layout version.
+    pub voter: Pubkey, // Voter's public key.
+    #[max_len(50)]
+    pub project_name: String, // Name of the project voted for.
+    pub last_voted_round: u64, // Last round the voter participated in.
+    pub vote_count: u64, // Total votes cast by the voter.
+    pub votes_for_project: u16, // Votes cast for the project.
+    pub vote_weight: u64, // Total weight cast by the voter.
+    pub blinded_voter: [u8; 32], // Blinded voter id of stealth records.
+    pub fees_paid: u64, // Fees paid for the votes on the project.
+    pub refunded: bool, // Whether the fees were refunded.
+    pub retracted: bool, // Whether the votes were retracted.
+    pub sol_fees_paid: u64, // Lamports paid for the votes on the project.
+    pub reward_claimed: bool, // Whether the reward share was claimed.
+    pub bump: u8, // VoterData PDA bump.
+}
+
+impl Versioned for LegacyProjectData {
+    const VERSION: u8 = 1;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn space(&self) -> usize {
+        // `update_project_metadata` grew the account to fit the name and URI.
+        8 + LegacyProjectData::INIT_SPACE + self.name.len() + self.uri.len()
+    }
+}
+
+impl Versioned for LegacyVoterData {
+    const VERSION: u8 = 1;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn space(&self) -> usize {
+        8 + LegacyVoterData::INIT_SPACE
+    }
+}
+
+impl Discriminator for LegacyProjectData {
+    const DISCRIMINATOR: [u8; 8] = ProjectData::DISCRIMINATOR;
+}
+
+impl Discriminator for LegacyVoterData {
+    const DISCRIMINATOR: [u8; 8] = VoterData::DISCRIMINATOR;
+}
+
+impl AccountSerialize for LegacyProjectData {}
+
+impl AccountSerialize for LegacyVoterData {}
+
+impl AccountDeserialize for LegacyProjectData {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        read_legacy(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        Self::deserialize(&mut buf.get(8..).unwrap_or_default())
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+}
+
+impl AccountDeserialize for LegacyVoterData {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        read_legacy(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        Self::deserialize(&mut buf.get(8..).unwrap_or_default())
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+}
+
+/// Decodes a legacy Borsh layout sharing the discriminator of the zero-copy account replacing it.
+fn read_legacy<T: AccountDeserialize + Discriminator>(buf: &mut &[u8]) -> Result<T> {
+    require!(buf.len() >= 8, ErrorCode::AccountDiscriminatorNotFound);
+    require!(
+        buf[..8] == T::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    T::try_deserialize_unchecked(buf)
+}
+
+impl From<LegacyProjectData> for ProjectData {
+    fn from(legacy: LegacyProjectData) -> Self {
+        let mut project = ProjectData {
+            version: legacy.version,
+            category: legacy.category,
+            disqualified: u8::from(legacy.disqualified),
+            pending: u8::from(legacy.pending),
+            bump: legacy.bump,
+            vote_manager: legacy.vote_manager,
+            payer: legacy.payer,
+            vote_round: legacy.vote_round,
+            vote_count: legacy.vote_count,
+            vote_weight: legacy.vote_weight,
+            voter_records: legacy.voter_records,
+            deposit: legacy.deposit,
+            deposit_refunded: legacy.deposit_refunded,
+            tranche_votes: legacy.tranche_votes,
+            refund_votes: legacy.refund_votes,
+            created_slot: legacy.created_slot,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        project.set_id(&legacy.id);
+        project.set_metadata(&legacy.name, &legacy.uri);
+        project
+    }
+}
+
+impl From<LegacyVoterData> for VoterData {
+    fn from(legacy: LegacyVoterData) -> Self {
+        let mut voter_data = VoterData {
+            version: legacy.version,
+            refunded: u8::from(legacy.refunded),
+            retracted: u8::from(legacy.retracted),
+            reward_claimed: u8::from(legacy.reward_claimed),
+            bump: legacy.bump,
+            votes_for_project: legacy.votes_for_project,
+            voter: legacy.voter,
+            blinded_voter: legacy.blinded_voter,
+            last_voted_round: legacy.last_voted_round,
+            vote_count: legacy.vote_count,
+            vote_weight: legacy.vote_weight,
+            fees_paid: legacy.fees_paid,
+            sol_fees_paid: legacy.sol_fees_paid,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        voter_data.set_project_name(&legacy.project_name);
+        voter_data
+    }
+}
+
+/// Represents the Leaderboard account holding the top projects of a round, so clients read the
+/// standings from one account instead of every ProjectData.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the round belongs to.
+/// - `round`: The round ranked.
+/// - `entries`: The first `len` entries are the standings, heaviest project first; projects of
+///   equal weight keep the order they reached it in.
+/// - `len`: Number of entries in use.
+/// - `bump`: The Leaderboard PDA bump.
+#[account(zero_copy)]
+pub struct Leaderboard {
+    pub vote_manager: Pubkey,                         // VoteManager the round belongs to.
+    pub round: u64,                                   // Round ranked.
+    pub entries: [LeaderboardEntry; LEADERBOARD_LEN], // Standings, heaviest first.
+    pub len: u8,                                      // Entries in use.
+    pub bump: u8,                                     // Leaderboard PDA bump.
+    pub padding: [u8; 6],                             // Keeps the layout free of implicit padding.
+}
+
+impl Leaderboard {
+    /// Sets the project's entry to `votes` and `weight` and moves it to its rank.
+    ///
+    /// A project outside a full leaderboard enters it only by outweighing the last entry, which
+    /// drops off.
+    pub fn record(&mut self, project: Pubkey, id: &str, votes: u64, weight: u64) {
+        let len = usize::from(self.len);
+        let position = self.entries[..len]
+            .iter()
+            .position(|entry| entry.project == project);
+        let mut i = match position {
+            Some(i) => i,
+            None if len < LEADERBOARD_LEN => {
+                self.len += 1;
+                len
+            }
+            None if weight > self.entries[len - 1].weight => len - 1,
+            None => return,
+        };
+        self.entries[i] = LeaderboardEntry::new(project, id, votes, weight);
+
+        // Insertion sort: every entry but the updated one is already in order.
+        let len = usize::from(self.len);
+        while i > 0 && self.entries[i - 1].weight < weight {
+            self.entries.swap(i - 1, i);
+            i -= 1;
+        }
+        while i + 1 < len && self.entries[i + 1].weight > weight {
+            self.entries.swap(i, i + 1);
+            i += 1;
+        }
+    }
+}
+
+/// A project's standing on a Leaderboard.
+///
+/// **Fields:**
+/// - `project`: The ProjectData account.
+/// - `id`: The project id, zero-padded.
+/// - `votes`: Votes the project received.
+/// - `weight`: Weight the project received, which ranks it.
+#[zero_copy]
+pub struct LeaderboardEntry {
+    pub project: Pubkey, // ProjectData account.
+    pub id: [u8; 64],    // Project id, zero-padded.
+    pub votes: u64,      // Votes received.
+    pub weight: u64,     // Weight received.
+}
+
+impl LeaderboardEntry {
+    pub fn new(project: Pubkey, id: &str, votes: u64, weight: u64) -> Self {
+        let mut padded = [0; 64];
+        padded[..id.len()].copy_from_slice(id.as_bytes());
+        LeaderboardEntry {
+            project,
+            id: padded,
+            votes,
+            weight,
+        }
+    }
+
+    /// Returns the project id without its padding.
+    pub fn id(&self) -> &str {
+        let len = self.id.iter().take_while(|&&b| b != 0).count();
+        std::str::from_utf8(&self.id[..len]).unwrap_or_default()
+    }
+}
+
+/// Represents the VoteCommit account holding a hidden vote in commit–reveal mode.
+///
+/// **Fields:**
+/// - `voter`: The voter's public key.
+/// - `round`: The round the commitment was made in.
+/// - `commitment`: `hash(voter || project_id || salt)`.
+/// - `weight`: The vote weight fixed at commit time.
+/// - `revealed`: Whether the vote has been revealed and counted.
+/// - `bump`: The VoteCommit PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteCommit {
+    pub voter: Pubkey,        // Voter's public key.
+    pub round: u64,           // Round of the commitment.
+    pub commitment: [u8; 32], // hash(voter || project_id || salt).
+    pub weight: u64,          // Weight fixed at commit time.
+    pub revealed: bool,       // Whether the vote was counted.
+    pub bump: u8,             // VoteCommit PDA bump.
+}
+
+/// Represents the Ballot account holding a voter's ranking in ranked-choice mode.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the ballot belongs to.
+/// - `voter`: The voter's public key.
+/// - `round`: The round the ballot was cast in.
+/// - `rankings`: The ranked ProjectData accounts, most preferred first.
+/// - `weight`: The ballot weight fixed at cast time.
+/// - `counted_pass`: The last instant-runoff pass that counted the ballot, zero before the tally.
+/// - `bump`: The Ballot PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct Ballot {
+    pub vote_manager: Pubkey, // VoteManager the ballot belongs to.
+    pub voter: Pubkey,        // Voter's public key.
+    pub round: u64,           // Round of the ballot.
+    #[max_len(RANKED_MAX_CHOICES)]
+    pub rankings: Vec<Pubkey>, // Ranked projects, most preferred first.
+    pub weight: u64,          // Weight fixed at cast time.
+    pub counted_pass: u8,     // Last pass counting the ballot.
+    pub bump: u8,             // Ballot PDA bump.
+}
+
+/// Represents the RankedTally account running a round's instant-runoff elimination.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the round belongs to.
+/// - `round`: The tallied round.
+/// - `candidates`: The projects ranked on at least one ballot, in order of first appearance.
+/// - `counts`: Weight counted for each candidate in the current pass.
+/// - `eliminated`: Whether each candidate was eliminated.
+/// - `ballots`: Ballots cast in the round.
+/// - `counted`: Ballots counted in the current pass.
+/// - `pass`: The current pass, starting at one.
+/// - `complete`: Whether the winner is known.
+/// - `winner`: The winning project, once `complete`.
+/// - `winner_weight`: The weight counted for `winner` in the final pass.
+/// - `bump`: The RankedTally PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct RankedTally {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Tallied round.
+    #[max_len(RANKED_MAX_CANDIDATES)]
+    pub candidates: Vec<Pubkey>, // Ranked projects.
+    #[max_len(RANKED_MAX_CANDIDATES)]
+    pub counts: Vec<u64>, // Weight per candidate in the current pass.
+    #[max_len(RANKED_MAX_CANDIDATES)]
+    pub eliminated: Vec<bool>, // Eliminated candidates.
+    pub ballots: u64,         // Ballots cast.
+    pub counted: u64,         // Ballots counted in the current pass.
+    pub pass: u8,             // Current pass.
+    pub complete: bool,       // Whether the winner is known.
+    pub winner: Pubkey,       // Winning project.
+    pub winner_weight: u64,   // Final weight of the winner.
+    pub bump: u8,             // RankedTally PDA bump.
+}
+
+impl RankedTally {
+    /// Registers `project` as a candidate, unless it already is one.
+    pub fn add_candidate(&mut self, project: Pubkey) -> Result<()> {
+        if self.candidates.contains(&project) {
+            return Ok(());
+        }
+        require!(
+            self.candidates.len() < RANKED_MAX_CANDIDATES,
+            VoteError::TooManyCandidates
+        );
+        self.candidates.push(project);
+        self.counts.push(0);
+        self.eliminated.push(false);
+        Ok(())
+    }
+
+    /// Counts a ballot of `weight` for its highest-ranked candidate still running; a ballot whose
+    /// candidates were all eliminated is exhausted and counts for nobody.
+    pub fn count_ballot(&mut self, rankings: &[Pubkey], weight: u64) -> Result<()> {
+        let choice = rankings.iter().find_map(|project| {
+            self.candidates
+                .iter()
+                .position(|candidate| candidate == project)
+                .filter(|&index| !self.eliminated[index])
+        });
+        if let Some(index) = choice {
+            self.counts[index] = self.counts[index]
+                .checked_add(weight)
+                .ok_or(VoteError::Overflow)?;
+        }
+        self.counted = self.counted.checked_add(1).ok_or(VoteError::Overflow)?;
+        Ok(())
+    }
+
+    /// Resolves the current pass once every ballot was counted.
+    ///
+    /// The strongest candidate wins if it holds a strict majority of the counted weight or is the
+    /// last one running, the earliest registered candidate winning ties. Otherwise the weakest
+    /// candidate, the latest registered one on ties, is eliminated and the next pass starts.
+    pub fn resolve_pass(&mut self) -> Result<()> {
+        let running: Vec<usize> = (0..self.candidates.len())
+            .filter(|&index| !self.eliminated[index])
+            .collect();
+        let counted_weight = running
+            .iter()
+            .try_fold(0u64, |sum, &index| sum.checked_add(self.counts[index]))
+            .ok_or(VoteError::Overflow)?;
+
+        let Some(leader) = running.iter().copied().reduce(|best, index| {
+            if self.counts[index] > self.counts[best] {
+                index
+            } else {
+                best
+            }
+        }) else {
+            // Every ballot ranks at least one candidate, so this only guards an empty tally.
+            self.complete = true;
+            return Ok(());
+        };
+
+        let leader_weight = self.counts[leader];
+        if running.len() == 1 || leader_weight > counted_weight - leader_weight {
+            self.complete = true;
+            self.winner = self.candidates[leader];
+            self.winner_weight = leader_weight;
+            return Ok(());
+        }
+
+        // Eliminate the weakest candidate and start the next pass.
+        if let Some(weakest) = running.iter().copied().reduce(|worst, index| {
+            if self.counts[index] <= self.counts[worst] {
+                index
+            } else {
+                worst
+            }
+        }) {
+            self.eliminated[weakest] = true;
+        }
+        self.counts.iter_mut().for_each(|count| *count = 0);
+        self.counted = 0;
+        self.pass = self.pass.checked_add(1).ok_or(VoteError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Represents the RoundResult account sealing the outcome of a finalized round.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the round belongs to.
+/// - `round`: The finalized round number.
+/// - `total_votes`: Votes cast in the round.
+/// - `total_weight`: Weight cast in the round.
+/// - `participation_count`: Voters who cast a vote in the round.
+/// - `winner`: The winning project's account, default if nobody voted.
+/// - `winner_id`: The winning project's identifier.
+/// - `winner_weight`: The winning project's weight.
+/// - `finalized_at`: Unix timestamp of the finalization.
+/// - `config_hash`: Hash of the economic parameters the round ran with.
+/// - `eligible_weight`: Total weight of the round's eligibility snapshot, zero without one.
+/// - `turnout_bps`: `total_weight` over `eligible_weight` in basis points, zero without a snapshot.
+/// - `reward_pool`: Voting fees the round collected, shared by the winner's voters pro rata to the
+///   weight they gave it; zero without a winner.
+/// - `rewards_claimed`: Part of `reward_pool` claimed so far.
+/// - `invalidated`: Whether the admin voided the round with `invalidate_round` instead of it being
+///   finalized; its voters are refunded and nobody wins.
+/// - `invalid_reason`: Why the round was voided, empty for finalized rounds.
+/// - `bump`: The RoundResult PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct RoundResult {
+    pub vote_manager: Pubkey,     // VoteManager the round belongs to.
+    pub round: u64,               // Finalized round number.
+    pub total_votes: u64,         // Votes cast in the round.
+    pub total_weight: u64,        // Weight cast in the round.
+    pub participation_count: u64, // Voters who cast a vote in the round.
+    pub winner: Pubkey,           // Winning project account.
+    #[max_len(PROJECT_ID_MAX_LEN)]
+    pub winner_id: String, // Winning project identifier.
+    pub winner_weight: u64,       // Winning project weight.
+    pub finalized_at: i64,        // Finalization timestamp.
+    pub config_hash: [u8; 32],    // Hash of the round's rules.
+    pub eligible_weight: u64,     // Snapshot's total eligible weight.
+    pub turnout_bps: u64,         // Weighted turnout in basis points.
+    pub reward_pool: u64,         // Fees shared by the winner's voters.
+    pub rewards_claimed: u64,     // Rewards paid out.
+    pub invalidated: bool,        // Whether the round was voided.
+    #[max_len(INVALID_REASON_MAX_LEN)]
+    pub invalid_reason: String, // Why the round was voided.
+    pub bump: u8,                 // RoundResult PDA bump.
+}
+
+impl RoundResult {
+    /// Returns the canonical JSON encoding of the result, the message signed by attestations.
+    ///
+    /// **Business Logic:**
+    /// - Keys are sorted and no whitespace is emitted, so every client derives the same bytes.
+    /// - Integers are written as exact decimals, pubkeys in base58 and `config_hash` in hex.
+    /// - The PDA bump and the reward bookkeeping are left out, they are not part of the result.
+    pub fn canonical_json(&self) -> String {
+        let mut winner_id = String::new();
+        push_json_string(&mut winner_id, &self.winner_id);
+        format!(
+            "{{\"config_hash\":\"{}\",\"eligible_weight\":{},\"finalized_at\":{},\
+             \"participation_count\":{},\"round\":{},\"total_votes\":{},\"total_weight\":{},\
+             \"turnout_bps\":{},\"vote_manager\":\"{}\",\"winner\":\"{}\",\"winner_id\":{},\
+             \"winner_weight\":{}}}",
+            hex_encode(&self.config_hash),
+            self.eligible_weight,
+            self.finalized_at,
+            self.participation_count,
+            self.round,
+            self.total_votes,
+            self.total_weight,
+            self.turnout_bps,
+            self.vote_manager,
+            self.winner,
+            winner_id,
+            self.winner_weight,
+        )
+    }
+
+    /// Returns the part of `reward_pool` due to a voter who gave the winner `vote_weight`.
+    ///
+    /// Shares are rounded down, so the rounding dust stays in the treasury.
+    pub fn reward_share(&self, vote_weight: u64) -> u64 {
+        if self.winner_weight == 0 {
+            return 0;
+        }
+        (self.reward_pool as u128 * vote_weight as u128 / self.winner_weight as u128) as u64
+    }
+}
+
+/// Represents the RoundArchive account keeping a finalized round's standings once its ProjectData
+/// accounts are closed.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the round belongs to.
+/// - `round`: The archived round number.
+/// - `project_count`: Projects the round had, so the archive is complete once it holds them all.
+/// - `entries`: The archived projects, most votes first and ties by id.
+/// - `category_winners`: The winner of each project category, by category code; final once the
+///   archive is complete.
+/// - `bump`: The RoundArchive PDA bump.
+///
+/// `entries` and `category_winners` take no space in `INIT_SPACE`; each archived page reallocates
+/// the account to fit.
+#[account]
+#[derive(InitSpace)]
+pub struct RoundArchive {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Archived round number.
+    pub project_count: u16,   // Projects the round had.
+    #[max_len(0)]
+    pub entries: Vec<ArchivedProject>, // Final standings.
+    #[max_len(0)]
+    pub category_winners: Vec<CategoryWinner>, // Per-category winners.
+    pub bump: u8,             // RoundArchive PDA bump.
+}
+
+impl RoundArchive {
+    /// Returns the account size needed to hold the current entries.
+    pub fn space(&self) -> usize {
+        8 + Self::INIT_SPACE
+            + self
+                .entries
+                .iter()
+                .map(|entry| 4 + entry.project_id.len() + 8)
+                .sum::<usize>()
+            + self
+                .category_winners
+                .iter()
+                .map(|winner| 1 + 4 + winner.project_id.len() + 8)
+                .sum::<usize>()
+    }
+
+    /// Returns whether every project of the round was archived.
+    pub fn is_complete(&self) -> bool {
+        self.entries.len() == usize::from(self.project_count)
+    }
+
+    /// Returns whether the project `project_id` was archived.
+    pub fn is_archived(&self, project_id: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.project_id == project_id)
+    }
+
+    /// Records `project` as the winner of its category if it leads it so far.
+    ///
+    /// **Business Logic:**
+    /// - Categories are won by weight, ties by id, so the winners do not depend on how pages were
+    ///   split.
+    /// - Uncategorized, disqualified and weightless projects win nothing, so a category nobody
+    ///   voted in has no winner.
+    pub fn record_category_leader(&mut self, project: &ProjectData) {
+        if project.category == 0 || project.disqualified != 0 || project.vote_weight == 0 {
+            return;
+        }
+        let leader = CategoryWinner {
+            category: project.category,
+            project_id: project.id().to_string(),
+            weight: project.vote_weight,
+        };
+        match self
+            .category_winners
+            .binary_search_by_key(&project.category, |winner| winner.category)
+        {
+            Ok(index) => {
+                let winner = &mut self.category_winners[index];
+                if (leader.weight, Reverse(leader.project_id.as_str()))
+                    > (winner.weight, Reverse(winner.project_id.as_str()))
+                {
+                    *winner = leader;
+                }
+            }
+            Err(index) => self.category_winners.insert(index, leader),
+        }
+    }
+
+    /// Returns the winner of category `category`, if the archived projects of it had votes.
+    pub fn category_winner(&self, category: u8) -> Option<&CategoryWinner> {
+        self.category_winners
+            .iter()
+            .find(|winner| winner.category == category)
+    }
+}
+
+/// A project's final tally in a `RoundArchive`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ArchivedProject {
+    #[max_len(PROJECT_ID_MAX_LEN)]
+    pub project_id: String, // Project identifier.
+    pub vote_count: u64, // Votes the project received.
+}
+
+/// The project leading a category in a `RoundArchive`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct CategoryWinner {
+    pub category: u8, // Category code.
+    #[max_len(PROJECT_ID_MAX_LEN)]
+    pub project_id: String, // Winning project identifier.
+    pub weight: u64,  // Weight the project won with.
+}
+
+/// Represents the CategoryRegistry account naming a VoteManager's project categories.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the categories belong to.
+/// - `names`: Category names, `names[i]` naming category code `i + 1`; code 0 is uncategorized.
+/// - `bump`: The CategoryRegistry PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct CategoryRegistry {
+    pub vote_manager: Pubkey, // VoteManager the categories belong to.
+    #[max_len(CATEGORY_MAX_COUNT, CATEGORY_NAME_MAX_LEN)]
+    pub names: Vec<String>, // Names of codes 1 and up.
+    pub bump: u8,             // CategoryRegistry PDA bump.
+}
+
+impl CategoryRegistry {
+    /// Returns the name of category code `category`, if the admin named it.
+    pub fn name(&self, category: u8) -> Option<&str> {
+        let index = usize::from(category).checked_sub(1)?;
+        self.names.get(index).map(String::as_str)
+    }
+
+    /// Returns whether `names` can be registered: up to `CATEGORY_MAX_COUNT` distinct, non-empty
+    /// names of at most `CATEGORY_NAME_MAX_LEN` bytes.
+    pub fn is_valid(names: &[String]) -> bool {
+        names.len() <= CATEGORY_MAX_COUNT
+            && names.iter().enumerate().all(|(i, name)| {
+                !name.is_empty()
+                    && name.len() <= CATEGORY_NAME_MAX_LEN
+                    && !names[..i].contains(name)
+            })
+    }
+}
+
+/// Represents the TallyState account accumulating a round's winner over pages of projects.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the round belongs to.
+/// - `round`: The tallied round number.
+/// - `deadline`: The round's tally deadline when the tally started; a runoff moving it restarts
+///   the tally.
+/// - `counted`: Projects counted so far.
+/// - `last_project`: Address of the last project counted, pages continuing above it.
+/// - `leader`: The leading project so far, default until a project has weight.
+/// - `leader_weight`: The weight of `leader`.
+/// - `leader_created_slot`: The slot `leader` was created in.
+/// - `tied`: Whether another counted project has the weight of `leader`.
+/// - `bump`: The TallyState PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct TallyState {
+    pub vote_manager: Pubkey,     // VoteManager the round belongs to.
+    pub round: u64,               // Tallied round number.
+    pub deadline: i64,            // Tally deadline the tally started with.
+    pub counted: u16,             // Projects counted so far.
+    pub last_project: Pubkey,     // Last project counted.
+    pub leader: Pubkey,           // Leading project so far.
+    pub leader_weight: u64,       // Weight of the leading project.
+    pub leader_created_slot: u64, // Slot the leading project was created in.
+    pub tied: bool,               // Whether the lead is tied.
+    pub bump: u8,                 // TallyState PDA bump.
+}
+
+impl TallyState {
+    /// Counts `project`, of total `weight` and created in `created_slot`, like
+    /// `VoteManager::record_vote` updates the running leader.
+    pub fn count_project(
+        &mut self,
+        project: Pubkey,
+        weight: u64,
+        created_slot: u64,
+        tie_break: TieBreakPolicy,
+    ) -> Result<()> {
+        self.counted = self.counted.checked_add(1).ok_or(VoteError::Overflow)?;
+        self.last_project = project;
+        if weight > self.leader_weight {
+            self.leader = project;
+            self.leader_weight = weight;
+            self.leader_created_slot = created_slot;
+            self.tied = false;
+        } else if weight == self.leader_weight && weight > 0 {
+            self.tied = true;
+            let leader = (self.leader_created_slot, self.leader);
+            if tie_break.prefers((created_slot, project), leader) {
+                self.leader = project;
+                self.leader_created_slot = created_slot;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Represents the RoundAttestation account holding the admin's signature over a round result.
+///
+/// **Fields:**
+/// - `round_result`: The attested RoundResult account.
+/// - `attester`: The admin who signed the result.
+/// - `signature`: Ed25519 signature over `RoundResult::canonical_json`.
+/// - `attested_at`: Unix timestamp of the attestation.
+/// - `bump`: The RoundAttestation PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct RoundAttestation {
+    pub round_result: Pubkey, // Attested round result.
+    pub attester: Pubkey,     // Signing admin.
+    pub signature: [u8; 64],  // Signature over the canonical JSON.
+    pub attested_at: i64,     // Attestation timestamp.
+    pub bump: u8,             // RoundAttestation PDA bump.
+}
+
+/// Represents the Campaign account holding a VoteManager's pre-registered round schedule.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the campaign belongs to.
+/// - `rounds`: The scheduled rounds, in order.
+/// - `next_round`: Index in `rounds` of the next round to open.
+/// - `last_opened_round`: The voting round the last scheduled round was opened on, zero if none.
+/// - `bump`: The Campaign PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct Campaign {
+    pub vote_manager: Pubkey, // VoteManager the campaign belongs to.
+    #[max_len(CAMPAIGN_MAX_ROUNDS)]
+    pub rounds: Vec<CampaignRound>, // Scheduled rounds.
+    pub next_round: u8,         // Next scheduled round to open.
+    pub last_opened_round: u64, // Voting round of the last opened one.
+    pub bump: u8,               // Campaign PDA bump.
+}
+
+impl Campaign {
+    /// Returns whether every scheduled round was opened or skipped.
+    pub fn finished(&self) -> bool {
+        self.next_round as usize >= self.rounds.len()
+    }
+}
+
+/// A round scheduled by a campaign.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct CampaignRound {
+    pub start_ts: i64, // Round start (unix timestamp).
+    pub end_ts: i64,   // Round end (unix timestamp).
+    #[max_len(CAMPAIGN_THEME_MAX_LEN)]
+    pub theme: String, // Theme announced for the round.
+}
+
+/// Represents the Treasury account collecting a VoteManager's voting fees in its token account.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager whose fees the treasury collects.
+/// - `total_collected`: Voting fees received since the treasury was created.
+/// - `total_withdrawn`: Fees withdrawn by the admin since the treasury was created.
+/// - `total_escrowed`: Project submission deposits currently held, which cannot be withdrawn.
+/// - `total_collected_sol`: Lamports of voting fees paid in SOL since the treasury was created,
+///   held by the Treasury PDA itself.
+/// - `total_rewarded`: Fees paid out to the voters of winning projects by `claim_reward`.
+/// - `bump`: The Treasury PDA bump, used to sign withdrawals.
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub vote_manager: Pubkey,     // VoteManager the treasury belongs to.
+    pub total_collected: u64,     // Fees received.
+    pub total_withdrawn: u64,     // Fees withdrawn.
+    pub total_escrowed: u64,      // Deposits held.
+    pub total_collected_sol: u64, // Lamports of fees received.
+    pub total_rewarded: u64,      // Fees paid out as voter rewards.
+    pub bump: u8,                 // Treasury PDA bump.
+}
+
+/// Represents the SponsorVault account holding SOL that reimburses voters' rent.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the vault belongs to.
+/// - `max_per_wallet`: Lamports a single wallet can be reimbursed over its lifetime.
+/// - `total_deposited`: Lamports deposited by the admin.
+/// - `total_reimbursed`: Lamports paid back to voters.
+/// - `bump`: The SponsorVault PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorVault {
+    pub vote_manager: Pubkey,  // VoteManager the vault belongs to.
+    pub max_per_wallet: u64,   // Lifetime reimbursement limit per wallet.
+    pub total_deposited: u64,  // Lamports deposited.
+    pub total_reimbursed: u64, // Lamports reimbursed.
+    pub bump: u8,              // SponsorVault PDA bump.
+}
+
+/// Represents the SponsorRecord account tracking a wallet's reimbursements.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the record belongs to.
+/// - `wallet`: The reimbursed voter.
+/// - `reimbursed`: Lamports reimbursed to the wallet so far.
+/// - `bump`: The SponsorRecord PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorRecord {
+    pub vote_manager: Pubkey, // VoteManager the record belongs to.
+    pub wallet: Pubkey,       // Reimbursed voter.
+    pub reimbursed: u64,      // Lamports reimbursed.
+    pub bump: u8,             // SponsorRecord PDA bump.
+}
+
+/// Represents a VoteNonce account, the next nonce a wallet's vote authorizations must carry.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the votes are cast in.
+/// - `voter`: The wallet signing the authorizations.
+/// - `next_nonce`: Nonce of the next relayed vote; each relayed vote consumes one.
+/// - `bump`: The VoteNonce PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteNonce {
+    pub vote_manager: Pubkey, // VoteManager the votes are cast in.
+    pub voter: Pubkey,        // Wallet signing the authorizations.
+    pub next_nonce: u64,      // Nonce of the next relayed vote.
+    pub bump: u8,             // VoteNonce PDA bump.
+}
+
+/// Represents a VoteLock account, the latest round a wallet voted in with `do_vote`.
+///
+/// The TTT transfer hook reads it to refuse transfers out of the wallet while that round is open.
+///
+/// **Fields:**
+/// - `round`: The latest round the wallet voted in.
+/// - `bump`: The VoteLock PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteLock {
+    pub round: u64, // Latest round the wallet voted in.
+    pub bump: u8,   // VoteLock PDA bump.
+}
+
+/// Represents the RoundVotes account counting a voter's votes in one round, across all projects.
+///
+/// `check_can_vote` bounds it by `max_votes_per_round`; stealth votes count per blinded id.
+///
+/// **Fields:**
+/// - `votes`: Votes the voter cast in the round and has not retracted.
+/// - `topped_up`: Whether the voter was topped up from the pool in the round, at most once.
+/// - `bump`: The RoundVotes PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct RoundVotes {
+    pub votes: u64,      // Votes cast in the round.
+    pub topped_up: bool, // Topped up in the round.
+    pub bump: u8,        // RoundVotes PDA bump.
+}
+
+/// Represents the VoteTree account recording a VoteManager's compressed votes.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the votes are cast in.
+/// - `merkle_tree`: The spl-account-compression concurrent Merkle tree holding a leaf per
+///   compressed vote; the VoteTree PDA is its authority.
+/// - `max_depth`: Depth of the tree, which holds up to `2^max_depth` votes.
+/// - `max_buffer_size`: Changes the tree keeps roots of, so proofs against a recent root still
+///   verify.
+/// - `leaf_count`: Votes appended so far, the index of the next one.
+/// - `bump`: The VoteTree PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteTree {
+    pub vote_manager: Pubkey, // VoteManager the votes are cast in.
+    pub merkle_tree: Pubkey,  // Concurrent Merkle tree of the votes.
+    pub max_depth: u32,       // Depth of the tree.
+    pub max_buffer_size: u32, // Changelog buffer size of the tree.
+    pub leaf_count: u64,      // Votes appended.
+    pub bump: u8,             // VoteTree PDA bump.
+}
+
+/// A vote recorded as a leaf of a VoteTree, passed back to prove it.
+///
+/// **Fields:**
+/// - `voter`: The wallet that cast the vote.
+/// - `project`: The ProjectData account voted for.
+/// - `round`: The round the vote was cast in.
+/// - `weight`: The vote's weight.
+/// - `fees_paid`: The voting fee collected for the vote.
+/// - `reward_claimed`: Whether the voter claimed their share of the round's reward pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CompressedVoteRecord {
+    pub voter: Pubkey,
+    pub project: Pubkey,
+    pub round: u64,
+    pub weight: u64,
+    pub fees_paid: u64,
+    pub reward_claimed: bool,
+}
+
+impl CompressedVoteRecord {
+    /// Returns the vote's leaf in the tree of `vote_manager`: `hash(vote_manager || voter ||
+    /// project || round || weight || fees_paid || reward_claimed)`.
+    pub fn leaf(&self, vote_manager: &Pubkey) -> [u8; 32] {
+        hashv(&[
+            vote_manager.as_ref(),
+            self.voter.as_ref(),
+            self.project.as_ref(),
+            &self.round.to_le_bytes(),
+            &self.weight.to_le_bytes(),
+            &self.fees_paid.to_le_bytes(),
+            &[u8::from(self.reward_claimed)],
+        ])
+        .to_bytes()
+    }
+}
+
+/// Represents a StakePosition account locking a wallet's TTT for voting power.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the position counts in.
+/// - `owner`: The staking wallet.
+/// - `amount`: TTT locked in the position's vault.
+/// - `locked_at`: Unix timestamp the TTT was locked at.
+/// - `unlocks_at`: Unix timestamp from which the TTT can be unstaked.
+/// - `voting_power`: Weight of the owner's votes in escrow mode until `unlocks_at`.
+/// - `bump`: The StakePosition PDA bump, used to sign the release.
+#[account]
+#[derive(InitSpace)]
+pub struct StakePosition {
+    pub vote_manager: Pubkey, // VoteManager the position counts in.
+    pub owner: Pubkey,        // Staking wallet.
+    pub amount: u64,          // TTT locked.
+    pub locked_at: i64,       // Lock timestamp.
+    pub unlocks_at: i64,      // Unlock timestamp.
+    pub voting_power: u64,    // Weight of the owner's votes.
+    pub bump: u8,             // StakePosition PDA bump.
+}
+
+impl StakePosition {
+    /// Returns whether the TTT is still locked at `now`, and so counts as voting power.
+    pub fn locked(&self, now: i64) -> bool {
+        now < self.unlocks_at
+    }
+}
+
+/// Represents a HoldingAttestation account, dating a wallet's token holding for the holding
+/// requirement.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the holding is registered with.
+/// - `owner`: The holding wallet.
+/// - `token_account`: The token account registered, the one the owner votes with.
+/// - `balance`: The account's balance at registration.
+/// - `registered_slot`: Slot the holding was registered in.
+/// - `bump`: The HoldingAttestation PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct HoldingAttestation {
+    pub vote_manager: Pubkey,  // VoteManager the holding is registered with.
+    pub owner: Pubkey,         // Holding wallet.
+    pub token_account: Pubkey, // Registered token account.
+    pub balance: u64,          // Balance at registration.
+    pub registered_slot: u64,  // Registration slot.
+    pub bump: u8,              // HoldingAttestation PDA bump.
+}
+
+/// Represents an OperationMarker account, proving an admin operation was applied.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the operation was applied to.
+/// - `key`: The operation key, `operation_key(idempotency_key, operation)` for the CLI.
+/// - `recorded_at`: Unix timestamp the operation was applied at.
+/// - `bump`: The OperationMarker PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct OperationMarker {
+    pub vote_manager: Pubkey, // VoteManager the operation was applied to.
+    pub key: [u8; 32],        // Operation key.
+    pub recorded_at: i64,     // Application timestamp.
+    pub bump: u8,             // OperationMarker PDA bump.
+}
+
+/// Represents a RoleGrant account, granting a wallet a role on a VoteManager.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the role applies to.
+/// - `holder`: The wallet holding the role.
+/// - `role`: The granted role, `Operator` or `Auditor`.
+/// - `granted_at`: Unix timestamp of the grant.
+/// - `bump`: The RoleGrant PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct RoleGrant {
+    pub vote_manager: Pubkey, // VoteManager the role applies to.
+    pub holder: Pubkey,       // Wallet holding the role.
+    pub role: Role,           // Granted role.
+    pub granted_at: i64,      // Grant timestamp.
+    pub bump: u8,             // RoleGrant PDA bump.
+}
+
+/// Represents an AccessListEntry account, putting a wallet on a VoteManager's allowlist or
+/// blocklist.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the list belongs to.
+/// - `voter`: The listed wallet.
+/// - `list`: The list the entry is on, `Allowlist` or `Blocklist`.
+/// - `added_at`: Unix timestamp the wallet was listed at.
+/// - `bump`: The AccessListEntry PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct AccessListEntry {
+    pub vote_manager: Pubkey, // VoteManager the list belongs to.
+    pub voter: Pubkey,        // Listed wallet.
+    pub list: AccessMode,     // List the entry is on.
+    pub added_at: i64,        // Listing timestamp.
+    pub bump: u8,             // AccessListEntry PDA bump.
+}
+
+/// Represents a FeeDiscountSchedule account, discounting the vote fee of long-term holders.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager whose votes are discounted.
+/// - `tiers`: Holding tiers, by ascending `min_balance`; a voter gets the discount of the highest
+///   tier their balance reaches.
+/// - `bump`: The FeeDiscountSchedule PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeDiscountSchedule {
+    pub vote_manager: Pubkey, // VoteManager whose votes are discounted.
+    #[max_len(FEE_DISCOUNT_MAX_TIERS)]
+    pub tiers: Vec<DiscountTier>, // Holding tiers, by ascending balance.
+    pub bump: u8,             // FeeDiscountSchedule PDA bump.
+}
+
+impl FeeDiscountSchedule {
+    /// Discount, in basis points, of a voter holding `balance`.
+    pub fn discount_bps(&self, balance: u64) -> u16 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| balance >= tier.min_balance)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+
+    /// `price` less the discount of a voter holding `balance`, rounded up.
+    pub fn discounted(&self, price: u64, balance: u64) -> u64 {
+        let bps = u128::from(10_000 - self.discount_bps(balance));
+        (u128::from(price) * bps).div_ceil(10_000) as u64
+    }
+
+    /// Whether `tiers` is a valid schedule: at most `FEE_DISCOUNT_MAX_TIERS` tiers, by strictly
+    /// ascending `min_balance`, with non-decreasing discounts of at most 100%.
+    pub fn is_valid(tiers: &[DiscountTier]) -> bool {
+        tiers.len() <= FEE_DISCOUNT_MAX_TIERS
+            && tiers.iter().all(|tier| tier.discount_bps <= 10_000)
+            && tiers.windows(2).all(|pair| {
+                pair[0].min_balance < pair[1].min_balance
+                    && pair[0].discount_bps <= pair[1].discount_bps
+            })
+    }
+}
+
+/// A holding tier of a FeeDiscountSchedule.
+///
+/// **Fields:**
+/// - `min_balance`: Token balance, in base units, a voter must hold to reach the tier.
+/// - `discount_bps`: Discount on the vote fee, in basis points.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct DiscountTier {
+    pub min_balance: u64,  // Balance reaching the tier.
+    pub discount_bps: u16, // Discount in basis points.
+}
+
+/// Represents the AdminCouncil account, whose members act as a VoteManager's admin M-of-N.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the council administers, PDA seed.
+/// - `members`: The members' public keys.
+/// - `threshold`: Approvals an admin action needs to be executed.
+/// - `action_count`: Admin actions proposed, the next action's id.
+/// - `bump`: The AdminCouncil PDA bump, used to sign executed actions.
+#[account]
+#[derive(InitSpace)]
+pub struct AdminCouncil {
+    pub vote_manager: Pubkey, // VoteManager the council administers.
+    #[max_len(COUNCIL_MAX_MEMBERS)]
+    pub members: Vec<Pubkey>, // Council members.
+    pub threshold: u8,        // Approvals required.
+    pub action_count: u64,    // Actions proposed.
+    pub bump: u8,             // AdminCouncil PDA bump.
+}
+
+impl AdminCouncil {
+    /// Returns whether `key` is a member of the council.
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.members.contains(key)
+    }
+}
+
+/// Represents an AdminAction account, instructions the admin council executes once approved.
+///
+/// **Fields:**
+/// - `council`: The council the action was proposed to.
+/// - `id`: Sequential action id, PDA seed.
+/// - `proposer`: The member who proposed the action and paid for the account.
+/// - `instructions`: The instructions to execute once approved.
+/// - `approvals`: The members who approved the action, the proposer first.
+/// - `executed`: Whether the instructions were executed.
+/// - `bump`: The AdminAction PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct AdminAction {
+    pub council: Pubkey,  // Council the action belongs to.
+    pub id: u64,          // Action id.
+    pub proposer: Pubkey, // Proposing member.
+    #[max_len(PROPOSAL_MAX_INSTRUCTIONS)]
+    pub instructions: Vec<ProposalInstruction>, // Instructions to execute.
+    #[max_len(COUNCIL_MAX_MEMBERS)]
+    pub approvals: Vec<Pubkey>, // Approving members.
+    pub executed: bool,   // Instructions executed.
+    pub bump: u8,         // AdminAction PDA bump.
+}
+
+/// Represents the RewardVault account holding a round's rewards in its token account.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the round belongs to.
+/// - `round`: The round the rewards are for.
+/// - `total_funded`: Rewards deposited into the vault.
+/// - `total_claimed`: Rewards paid out to claimants.
+/// - `claim_window`: Seconds the rewards stay claimable after the round is finalized.
+/// - `swept`: Whether the unclaimed rewards were returned to the admin.
+/// - `bump`: The RewardVault PDA bump, used to sign transfers.
+#[account]
+#[derive(InitSpace)]
+pub struct RewardVault {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Round of the rewards.
+    pub total_funded: u64,    // Rewards deposited.
+    pub total_claimed: u64,   // Rewards paid out.
+    pub claim_window: i64,    // Claim window in seconds.
+    pub swept: bool,          // Unclaimed rewards returned.
+    pub bump: u8,             // RewardVault PDA bump.
+}
+
+impl RewardVault {
+    /// Returns the unix timestamp after which the rewards of a round finalized at `finalized_at`
+    /// can no longer be claimed.
+    pub fn claim_deadline(&self, finalized_at: i64) -> i64 {
+        finalized_at.saturating_add(self.claim_window)
+    }
+}
+
+/// Represents a proposal to execute arbitrary instructions on behalf of the governance authority.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the proposal belongs to.
+/// - `id`: Sequential proposal id, PDA seed.
+/// - `proposer`: The account that created the proposal and locked the deposit.
+/// - `deposit`: TTT locked in the proposal's token account.
+/// - `instructions`: The instructions to execute once the proposal passes.
+/// - `voting_ends_at`: Unix timestamp after which votes are rejected.
+/// - `yes_weight`: Weight of the votes in favour.
+/// - `no_weight`: Weight of the votes against.
+/// - `quorum`: Minimum total weight required to pass.
+/// - `executed`: Whether the instructions were executed.
+/// - `bump`: The Proposal PDA bump, used to sign the deposit transfers.
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub vote_manager: Pubkey, // VoteManager the proposal belongs to.
+    pub id: u64,              // Proposal id.
+    pub proposer: Pubkey,     // Proposal creator.
+    pub deposit: u64,         // Locked deposit.
+    #[max_len(PROPOSAL_MAX_INSTRUCTIONS)]
+    pub instructions: Vec<ProposalInstruction>, // Instructions to execute.
+    pub voting_ends_at: i64,  // Voting deadline (unix timestamp).
+    pub yes_weight: u64,      // Weight in favour.
+    pub no_weight: u64,       // Weight against.
+    pub quorum: u64,          // Required total weight.
+    pub executed: bool,       // Instructions executed.
+    pub bump: u8,             // Proposal PDA bump.
+}
+
+impl Proposal {
+    /// Returns whether the proposal has more weight in favour than against and reached its quorum.
+    pub fn passed(&self) -> bool {
+        self.yes_weight > self.no_weight
+            && self.yes_weight.saturating_add(self.no_weight) >= self.quorum
+    }
+}
+
+/// An instruction stored in a proposal.
+///
+/// The governance authority PDA is marked as a signer when the instruction is executed, whatever
+/// its stored `is_signer` flag.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ProposalInstruction {
+    pub program_id: Pubkey, // Program to invoke.
+    #[max_len(PROPOSAL_MAX_ACCOUNTS)]
+    pub accounts: Vec<ProposalAccountMeta>, // Accounts of the instruction.
+    #[max_len(PROPOSAL_MAX_DATA_LEN)]
+    pub data: Vec<u8>, // Instruction data.
+}
+
+/// An account of a stored proposal instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,    // Account address.
+    pub is_signer: bool,   // Account must sign.
+    pub is_writable: bool, // Account is writable.
+}
+
+/// Represents a voter's ballot on a proposal.
+///
+/// **Fields:**
+/// - `proposal`: The proposal voted on.
+/// - `voter`: The voter's public key.
+/// - `approve`: Whether the vote is in favour.
+/// - `weight`: The weight of the vote.
+/// - `locked`: TTT locked in the ballot's vault, weighing the vote.
+/// - `unlocks_at`: The proposal's voting deadline, after which `release_proposal_vote` returns them.
+/// - `bump`: The ProposalVote PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalVote {
+    pub proposal: Pubkey, // Proposal voted on.
+    pub voter: Pubkey,    // Voter's public key.
+    pub approve: bool,    // Vote in favour.
+    pub weight: u64,      // Vote weight.
+    pub locked: u64,      // TTT locked in the vault.
+    pub unlocks_at: i64,  // Release timestamp.
+    pub bump: u8,         // ProposalVote PDA bump.
+}
+
+/// Represents a yes/no question put to TTT holders next to the project rounds.
+///
+/// **Fields:**
+/// - `vote_manager`: The VoteManager the referendum belongs to.
+/// - `id`: Sequential referendum id, PDA seed.
+/// - `question_uri`: Off-chain document stating the question.
+/// - `deadline`: Unix timestamp after which votes are rejected.
+/// - `vote_fee`: The fee of a vote, frozen when the referendum was created.
+/// - `weight_curve`: The curve weighing votes, frozen when the referendum was created.
+/// - `yes_weight`: Weight of the votes in favour.
+/// - `no_weight`: Weight of the votes against.
+/// - `abstain_weight`: Weight of the abstentions.
+/// - `voters`: Number of voters who answered.
+/// - `bump`: The Referendum PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct Referendum {
+    pub vote_manager: Pubkey, // VoteManager the referendum belongs to.
+    pub id: u64,              // Referendum id.
+    #[max_len(REFERENDUM_URI_MAX_LEN)]
+    pub question_uri: String, // Off-chain question document.
+    pub deadline: i64,        // Voting deadline (unix timestamp).
+    pub vote_fee: u64,        // Fee of a vote.
+    pub weight_curve: WeightCurve, // Balance-to-weight curve of the votes.
+    pub yes_weight: u64,      // Weight in favour.
+    pub no_weight: u64,       // Weight against.
+    pub abstain_weight: u64,  // Weight abstaining.
+    pub voters: u64,          // Voters who answered.
+    pub bump: u8,             // Referendum PDA bump.
+}
+
+/// An answer to a referendum.
+///
+/// **Variants:**
+/// - `Yes`: In favour of the question.
+/// - `No`: Against the question.
+/// - `Abstain`: Counted as turnout, for neither side.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ReferendumChoice {
+    Yes,
+    No,
+    Abstain,
+}
+
+/// Represents a voter's answer to a referendum.
+///
+/// **Fields:**
+/// - `referendum`: The referendum answered.
+/// - `voter`: The voter's public key.
+/// - `choice`: The voter's answer.
+/// - `weight`: The weight of the vote.
+/// - `fee_paid`: The fee the treasury collected for the vote.
+/// - `bump`: The ReferendumVote PDA bump.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferendumVote {
+    pub referendum: Pubkey,       // Referendum answered.
+    pub voter: Pubkey,            // Voter's public key.
+    pub choice: ReferendumChoice, // The answer.
+    pub weight: u64,              // Vote weight.
+    pub fee_paid: u64,            // Fee collected for the vote.
+    pub bump: u8,                 // ReferendumVote PDA bump.
+}
+
+/// Emitted when the admin moves the VoteManager to the next round.
+#[event]
+pub struct RoundIncremented {
+    pub vote_manager: Pubkey, // VoteManager whose round changed.
+    pub round: u64,           // The new voting round.
+    pub round_start_ts: i64,  // Start of the new round (unix timestamp).
+}
+
+/// Emitted when a round is opened, closed or finalized.
+///
+/// Rounds start `Pending`, so no event is emitted when a new round begins.
+#[event]
+pub struct RoundStateChanged {
+    pub vote_manager: Pubkey, // VoteManager whose round changed state.
+    pub round: u64,           // The round that changed state.
+    pub state: RoundState,    // The round's new state.
+}
+
+/// Emitted when the admin grants a role.
+#[event]
+pub struct RoleGranted {
+    pub vote_manager: Pubkey, // VoteManager the role applies to.
+    pub holder: Pubkey,       // Wallet granted the role.
+    pub role: Role,           // Granted role.
+}
+
+/// Emitted when the admin revokes a role.
+#[event]
+pub struct RoleRevoked {
+    pub vote_manager: Pubkey, // VoteManager the role applied to.
+    pub holder: Pubkey,       // Wallet the role was revoked from.
+    pub role: Role,           // Revoked role.
+}
+
+/// Emitted when the admin creates the admin council.
+#[event]
+pub struct AdminCouncilCreated {
+    pub vote_manager: Pubkey, // VoteManager the council administers.
+    pub council: Pubkey,      // The AdminCouncil account.
+    pub members: u8,          // Number of members.
+    pub threshold: u8,        // Approvals required per action.
+}
+
+/// Emitted when a council member proposes an admin action.
+#[event]
+pub struct AdminActionProposed {
+    pub council: Pubkey,  // Council the action belongs to.
+    pub action: Pubkey,   // The AdminAction account.
+    pub id: u64,          // Action id.
+    pub proposer: Pubkey, // Proposing member.
+}
+
+/// Emitted when a council member approves an admin action.
+#[event]
+pub struct AdminActionApproved {
+    pub council: Pubkey,  // Council the action belongs to.
+    pub action: Pubkey,   // The AdminAction account.
+    pub id: u64,          // Action id.
+    pub approver: Pubkey, // Approving member.
+    pub approvals: u8,    // Approvals so far.
+}
+
+/// Emitted when an approved admin action is executed.
+#[event]
+pub struct AdminActionExecuted {
+    pub council: Pubkey,  // Council the action belongs to.
+    pub action: Pubkey,   // The AdminAction account.
+    pub id: u64,          // Action id.
+    pub instructions: u8, // Number of instructions executed.
+}
+
+/// Emitted when the admin pauses or resumes a VoteManager.
+#[event]
+pub struct PausedChanged {
+    pub vote_manager: Pubkey, // VoteManager paused or resumed.
+    pub paused: bool,         // Whether it is now paused.
+}
+
+/// Emitted when the admin switches between collecting and burning vote fees.
+#[event]
+pub struct FeeModeChanged {
+    pub vote_manager: Pubkey, // VoteManager whose fee mode changed.
+    pub fee_mode: FeeMode,    // The new fee mode.
+}
+
+/// Emitted when the admin changes which wallets may vote.
+#[event]
+pub struct AccessModeChanged {
+    pub vote_manager: Pubkey,    // VoteManager whose access mode changed.
+    pub access_mode: AccessMode, // The new access mode.
+}
+
+/// Emitted when the admin replaces the fee discount schedule.
+#[event]
+pub struct FeeDiscountsChanged {
+    pub vote_manager: Pubkey,     // VoteManager whose schedule changed.
+    pub tiers: Vec<DiscountTier>, // The new holding tiers.
+}
+
+/// Emitted when the admin renames the project categories.
+#[event]
+pub struct CategoriesChanged {
+    pub vote_manager: Pubkey, // VoteManager whose categories changed.
+    pub names: Vec<String>,   // Names of category codes 1 and up.
+}
+
+/// Emitted when the admin adds a wallet to or removes it from an access list.
+#[event]
+pub struct AccessListUpdated {
+    pub vote_manager: Pubkey, // VoteManager the list belongs to.
+    pub voter: Pubkey,        // Wallet added or removed.
+    pub list: AccessMode,     // `Allowlist` or `Blocklist`.
+    pub listed: bool,         // Whether the wallet is now on the list.
+}
+
+/// Emitted when the admin switches whether retracted votes are refunded.
+#[event]
+pub struct RetractRefundChanged {
+    pub vote_manager: Pubkey, // VoteManager whose retraction refunds changed.
+    pub retract_refund: bool, // Whether retracted votes are refunded.
+}
+
+/// Emitted when a round's leaderboard is created.
+#[event]
+pub struct LeaderboardCreated {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub leaderboard: Pubkey,  // The new Leaderboard account.
+    pub round: u64,           // Round ranked.
+}
+
+/// Emitted when the admin adds a project to the current round.
+#[event]
+pub struct ProjectAdded {
+    pub vote_manager: Pubkey, // VoteManager the project belongs to.
+    pub project: Pubkey,      // The new ProjectData account.
+    pub id: String,           // Project identifier.
+    pub round: u64,           // Round the project runs in.
+}
+
+/// Emitted when a project is submitted for review.
+#[event]
+pub struct ProjectSubmitted {
+    pub vote_manager: Pubkey, // VoteManager the project belongs to.
+    pub project: Pubkey,      // The new ProjectData account.
+    pub id: String,           // Project identifier.
+    pub round: u64,           // Round the project runs in.
+    pub submitter: Pubkey,    // Account that submitted the project.
+    pub deposit: u64,         // Escrowed deposit.
+}
+
+/// Emitted when the admin rejects a submitted project.
+#[event]
+pub struct ProjectRejected {
+    pub vote_manager: Pubkey, // VoteManager the project belonged to.
+    pub project: Pubkey,      // The closed ProjectData account.
+    pub id: String,           // Project identifier.
+    pub deposit: u64,         // The submission deposit.
+    pub slashed: bool,        // Deposit kept by the treasury.
+}
+
+/// Emitted when the admin changes how approved submissions earn their deposit back.
+#[event]
+pub struct DepositRefundChanged {
+    pub vote_manager: Pubkey, // VoteManager whose deposit refunds changed.
+    pub tranche_votes: u64,   // Votes per refund tranche, zero for refunds on approval.
+    pub refund_votes: u64,    // Votes refunding the whole deposit.
+}
+
+/// Emitted when a submitter claims refund tranches of a listing bond.
+#[event]
+pub struct DepositTrancheClaimed {
+    pub vote_manager: Pubkey, // VoteManager the project belongs to.
+    pub project: Pubkey,      // The bonded project.
+    pub id: String,           // Project identifier.
+    pub submitter: Pubkey,    // Submitter receiving the refund.
+    pub amount: u64,          // TTT refunded by this claim.
+    pub refunded: u64,        // TTT refunded so far.
+    pub remaining: u64,       // TTT still escrowed.
+}
+
+/// Emitted when a closed project forfeits the rest of its listing bond to the treasury.
+#[event]
+pub struct DepositForfeited {
+    pub vote_manager: Pubkey, // VoteManager the project belonged to.
+    pub project: Pubkey,      // The closed ProjectData account.
+    pub id: String,           // Project identifier.
+    pub amount: u64,          // TTT kept by the treasury.
+}
+
+/// Emitted when the admin updates a project's metadata.
+#[event]
+pub struct ProjectMetadataUpdated {
+    pub vote_manager: Pubkey, // VoteManager the project belongs to.
+    pub project: Pubkey,      // The ProjectData account.
+    pub name: String,         // New display name.
+    pub uri: String,          // New off-chain JSON description URI.
+    pub category: u8,         // New category code.
+}
+
+/// Emitted when a vote is counted, by `do_vote` or by `reveal_vote`.
+#[event]
+pub struct VoteCast {
+    pub voter: Pubkey,   // Voter's public key.
+    pub project: Pubkey, // Project voted for.
+    pub round: u64,      // Round of the vote.
+    pub weight: u64,     // Weight added to the project.
+}
+
+/// Emitted when the admin changes the voting fee.
+#[event]
+pub struct FeeChanged {
+    pub vote_manager: Pubkey, // VoteManager whose fee changed.
+    pub old_vote_fee: u64,    // Previous voting fee.
+    pub new_vote_fee: u64,    // New voting fee.
+}
+
+/// Emitted when the admin changes the voting fee paid in SOL.
+#[event]
+pub struct SolFeeChanged {
+    pub vote_manager: Pubkey,  // VoteManager whose fee changed.
+    pub old_sol_vote_fee: u64, // Previous fee in lamports.
+    pub new_sol_vote_fee: u64, // New fee in lamports.
+}
+
+/// Emitted when an account is upgraded to the current layout version.
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,  // Upgraded account.
+    pub from_version: u8, // Layout version it had.
+    pub to_version: u8,   // Layout version it has now.
+}
+
+/// Emitted when a round is finalized, mirroring its `RoundResult`.
+#[event]
+pub struct RoundFinalized {
+    pub vote_manager: Pubkey,     // VoteManager the round belongs to.
+    pub round: u64,               // Finalized round number.
+    pub total_votes: u64,         // Votes cast in the round.
+    pub total_weight: u64,        // Weight cast in the round.
+    pub participation_count: u64, // Voters who cast a vote in the round.
+    pub winner: Pubkey,           // Winning project account, default if nobody voted.
+    pub winner_id: String,        // Winning project identifier.
+    pub turnout_bps: u64,         // Weighted turnout in basis points, zero without a snapshot.
+    pub tied: bool,               // Whether the winner was picked by the tie-break policy.
+}
+
+/// Emitted when the admin voids a round with `invalidate_round`.
+#[event]
+pub struct RoundInvalidated {
+    pub vote_manager: Pubkey,   // VoteManager the round belongs to.
+    pub round: u64,             // Voided round number.
+    pub state: RoundState,      // State the round was voided in.
+    pub total_votes: u64,       // Votes cast in the round, now refundable.
+    pub refundable_fees: u64,   // TTT fees the round collected, now refundable.
+    pub reason: String,         // Why the round was voided.
+    pub invalidated_by: Pubkey, // The admin who voided the round.
+}
+
+/// Emitted when a page of a finalized round's projects is archived.
+#[event]
+pub struct ProjectsArchived {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Archived round number.
+    pub archived: u16,        // Projects archived by this page.
+    pub complete: bool,       // Whether every project of the round is archived.
+}
+
+/// Emitted when a page of a round's projects is tallied.
+#[event]
+pub struct TallyPageCounted {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Tallied round number.
+    pub counted: u16,         // Projects counted so far.
+    pub leader: Pubkey,       // Leading project so far.
+    pub leader_weight: u64,   // Weight of the leading project.
+}
+
+/// Emitted when a round's tied projects go to a runoff.
+#[event]
+pub struct RunoffOpened {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // The round holding the runoff.
+    pub weight: u64,          // Tied weight projects need to take runoff votes.
+    pub deadline: i64,        // End of the runoff (unix timestamp).
+}
+
+/// Emitted when the admin attests a finalized round.
+#[event]
+pub struct RoundAttested {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Attested round number.
+    pub attester: Pubkey,     // Signing admin.
+    pub signature: [u8; 64],  // Signature over the round's canonical JSON.
+}
+
+/// Emitted when the admin creates a campaign.
+#[event]
+pub struct CampaignCreated {
+    pub vote_manager: Pubkey, // VoteManager the campaign belongs to.
+    pub rounds: u8,           // Scheduled rounds.
+    pub starts_at: i64,       // Start of the first scheduled round.
+    pub ends_at: i64,         // End of the last scheduled round.
+}
+
+/// Emitted when a campaign's scheduled round is opened.
+#[event]
+pub struct CampaignRoundOpened {
+    pub vote_manager: Pubkey, // VoteManager the campaign belongs to.
+    pub round: u64,           // Voting round the scheduled round was opened on.
+    pub index: u8,            // Index of the scheduled round in the campaign.
+    pub theme: String,        // Theme of the round.
+    pub start_ts: i64,        // Round start (unix timestamp).
+    pub end_ts: i64,          // Round end (unix timestamp).
+}
+
+/// Emitted when a ranked ballot is cast.
+#[event]
+pub struct BallotCast {
+    pub vote_manager: Pubkey,  // VoteManager the round belongs to.
+    pub round: u64,            // Round of the ballot.
+    pub voter: Pubkey,         // Voter's public key.
+    pub rankings: Vec<Pubkey>, // Ranked projects, most preferred first.
+    pub weight: u64,           // Ballot weight.
+}
+
+/// Emitted when a ranked round's instant-runoff tally finds its winner.
+#[event]
+pub struct RankedRoundTallied {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Tallied round.
+    pub passes: u8,           // Passes needed to find the winner.
+    pub winner: Pubkey,       // Winning project.
+    pub winner_weight: u64,   // Final weight of the winner.
+}
+
+/// Emitted when the admin funds a round's reward vault.
+#[event]
+pub struct RewardVaultFunded {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Round of the rewards.
+    pub amount: u64,          // Amount deposited.
+    pub total_funded: u64,    // Rewards deposited so far.
+}
+
+/// Emitted when the unclaimed rewards of a round are returned to the admin.
+#[event]
+pub struct RewardsSwept {
+    pub vote_manager: Pubkey, // VoteManager the round belongs to.
+    pub round: u64,           // Round of the rewards.
+    pub destination: Pubkey,  // Token account receiving the rewards.
+    pub amount: u64,          // Amount swept.
+}
+
+/// Emitted when the admin deposits SOL into the sponsor vault.
+#[event]
+pub struct SponsorVaultFunded {
+    pub vote_manager: Pubkey, // VoteManager the vault belongs to.
+    pub amount: u64,          // Lamports deposited.
+    pub available: u64,       // Lamports available for reimbursements.
+}
+
+/// Emitted when the admin withdraws SOL from the sponsor vault.
+#[event]
+pub struct SponsorVaultWithdrawn {
+    pub vote_manager: Pubkey, // VoteManager the vault belongs to.
+    pub amount: u64,          // Lamports withdrawn.
+    pub available: u64,       // Lamports left for reimbursements.
+}
+
+/// Emitted when a sponsored vote reimburses the voter's rent.
+#[event]
+pub struct VoteSponsored {
+    pub vote_manager: Pubkey, // VoteManager the vault belongs to.
+    pub voter: Pubkey,        // Reimbursed voter.
+    pub amount: u64,          // Lamports reimbursed by this vote.
+    pub reimbursed: u64,      // Lamports reimbursed to the voter so far.
+}
+
+/// Emitted when a relayer casts a vote the voter authorized by signature.
+#[event]
+pub struct VoteRelayed {
+    pub vote_manager: Pubkey, // VoteManager the vote is cast in.
+    pub voter: Pubkey,        // Wallet that signed the authorization.
+    pub relayer: Pubkey,      // Wallet that sent and paid for the transaction.
+    pub project: Pubkey,      // Project voted for.
+    pub nonce: u64,           // Nonce the authorization consumed.
+}
+
+/// Emitted when the admin sets up compressed votes.
+#[event]
+pub struct VoteTreeInitialized {
+    pub vote_manager: Pubkey, // VoteManager the votes are cast in.
+    pub merkle_tree: Pubkey,  // Concurrent Merkle tree of the votes.
+    pub max_depth: u32,       // Depth of the tree.
+    pub max_buffer_size: u32, // Changelog buffer size of the tree.
+}
+
+/// Emitted when a compressed vote's leaf is appended to or replaced in the vote tree.
+#[event]
+pub struct CompressedVoteRecorded {
+    pub vote_manager: Pubkey,       // VoteManager the vote is cast in.
+    pub merkle_tree: Pubkey,        // Tree holding the vote.
+    pub index: u32,                 // Index of the leaf.
+    pub leaf: [u8; 32],             // The leaf now at `index`.
+    pub vote: CompressedVoteRecord, // The vote the leaf hashes.
+}
+
+/// Emitted when a compressed vote is proven to be in the vote tree.
+#[event]
+pub struct CompressedVoteVerified {
+    pub vote_manager: Pubkey, // VoteManager the vote is cast in.
+    pub index: u32,           // Index of the leaf.
+    pub voter: Pubkey,        // Wallet that cast the vote.
+    pub project: Pubkey,      // Project voted for.
+    pub round: u64,           // Round the vote was cast in.
+    pub weight: u64,          // Weight of the vote.
+}
+
+/// Emitted when the admin withdraws voting fees from the treasury.
+#[event]
+pub struct TreasuryWithdrawn {
+    pub vote_manager: Pubkey, // VoteManager the treasury belongs to.
+    pub destination: Pubkey,  // Token account receiving the fees.
+    pub amount: u64,          // Amount withdrawn.
+    pub remaining: u64,       // Treasury balance after the withdrawal.
+}
+
+/// Emitted when the admin withdraws voting fees paid in SOL from the treasury.
+#[event]
+pub struct TreasurySolWithdrawn {
+    pub vote_manager: Pubkey, // VoteManager the treasury belongs to.
+    pub amount: u64,          // Lamports withdrawn.
+    pub remaining: u64,       // Lamports left above the rent exemption.
+}
+
+/// Emitted when the admin funds the top-up pool.
+#[event]
+pub struct TopUpsFunded {
+    pub vote_manager: Pubkey, // VoteManager the pool belongs to.
+    pub amount: u64,          // Amount added to the pool.
+    pub balance: u64,         // Pool balance after the funding.
+}
+
+/// Emitted when the top-up pool funds a voter's vote.
+#[event]
+pub struct VoterToppedUp {
+    pub vote_manager: Pubkey, // VoteManager the pool belongs to.
+    pub voter: Pubkey,        // Voter whose token account was topped up.
+    pub amount: u64,          // Amount transferred to the voter.
+    pub remaining: u64,       // Pool balance after the top-up.
+}
+
+/// Emitted when the admin withdraws tokens from the top-up pool.
+#[event]
+pub struct TopUpsWithdrawn {
+    pub vote_manager: Pubkey, // VoteManager the pool belongs to.
+    pub destination: Pubkey,  // Token account receiving the tokens.
+    pub amount: u64,          // Amount withdrawn.
+    pub remaining: u64,       // Pool balance after the withdrawal.
+}
+
+/// Emitted when the admin thaws a voter's token account.
+#[event]
+pub struct VoterAccountThawed {
+    pub vote_manager: Pubkey,  // VoteManager holding the freeze authority.
+    pub voter: Pubkey,         // Owner of the thawed account.
+    pub token_account: Pubkey, // Thawed token account.
+}
+
+/// Emitted when a wallet locks TTT for voting power.
+#[event]
+pub struct StakeLocked {
+    pub vote_manager: Pubkey, // VoteManager the position counts in.
+    pub owner: Pubkey,        // Staking wallet.
+    pub amount: u64,          // TTT locked.
+    pub unlocks_at: i64,      // Unlock timestamp.
+    pub voting_power: u64,    // Voting power granted.
+}
+
+/// Emitted when an expired stake position is released.
+#[event]
+pub struct StakeUnlocked {
+    pub vote_manager: Pubkey, // VoteManager the position counted in.
+    pub owner: Pubkey,        // Staking wallet.
+    pub amount: u64,          // TTT released.
+}
+
+/// Emitted when the admin changes the minimum token holding age votes require.
+#[event]
+pub struct HoldingRequirementChanged {
+    pub vote_manager: Pubkey,   // VoteManager whose requirement changed.
+    pub min_balance: u64,       // Balance required, zero without a requirement.
+    pub min_holding_slots: u64, // Holding age in slots, zero without a requirement.
+}
+
+/// Emitted when a wallet registers its token holding.
+#[event]
+pub struct HoldingRegistered {
+    pub vote_manager: Pubkey,  // VoteManager the holding is registered with.
+    pub owner: Pubkey,         // Holding wallet.
+    pub token_account: Pubkey, // Registered token account.
+    pub balance: u64,          // Balance at registration.
+    pub registered_slot: u64,  // Registration slot.
+}
+
+/// Emitted when a voter claims back the fees paid for a disqualified project.
+#[event]
+pub struct VoteRefunded {
+    pub vote_manager: Pubkey, // VoteManager the treasury belongs to.
+    pub project: Pubkey,      // The disqualified project.
+    pub voter: Pubkey,        // Refunded voter.
+    pub amount: u64,          // Fees refunded.
+    pub lamports: u64,        // SOL fees refunded.
+}
+
+/// Emitted when a voter of a round's winning project claims their share of the round's fees.
+#[event]
+pub struct VoteRewardClaimed {
+    pub vote_manager: Pubkey, // VoteManager the treasury belongs to.
+    pub round: u64,           // Finalized round.
+    pub project: Pubkey,      // The winning project.
+    pub voter: Pubkey,        // Rewarded voter.
+    pub amount: u64,          // Reward paid.
+}
+
+/// Emitted when a voter retracts their votes for a project.
+#[event]
+pub struct VoteRetracted {
+    pub vote_manager: Pubkey,   // VoteManager the round belongs to.
+    pub project: Pubkey,        // Project the votes were retracted from.
+    pub voter: Pubkey,          // Retracting voter.
+    pub round: u64,             // Round of the votes.
+    pub votes: u16,             // Votes retracted.
+    pub weight: u64,            // Weight retracted.
+    pub refunded: u64,          // Fees refunded, zero when the treasury keeps them.
+    pub refunded_lamports: u64, // SOL fees refunded, zero when the treasury keeps them.
+}
+
+/// Emitted when a proposal is created.
+#[event]
+pub struct ProposalCreated {
+    pub vote_manager: Pubkey, // VoteManager the proposal belongs to.
+    pub proposal: Pubkey,     // The new Proposal account.
+    pub id: u64,              // Proposal id.
+    pub proposer: Pubkey,     // Proposal creator.
+    pub voting_ends_at: i64,  // Voting deadline (unix timestamp).
+}
+
+/// Emitted when a vote is cast on a proposal.
+#[event]
+pub struct ProposalVoted {
+    pub proposal: Pubkey, // The Proposal account.
+    pub voter: Pubkey,    // Voter's public key.
+    pub approve: bool,    // Vote in favour.
+    pub weight: u64,      // Vote weight.
+    pub locked: u64,      // TTT locked until voting ends.
+}
+
+/// Emitted when the TTT locked by a proposal vote is returned.
+#[event]
+pub struct ProposalVoteReleased {
+    pub proposal: Pubkey, // The Proposal account.
+    pub voter: Pubkey,    // Voter's public key.
+    pub amount: u64,      // TTT released.
+}
+
+/// Emitted when the admin creates a referendum.
+#[event]
+pub struct ReferendumCreated {
+    pub vote_manager: Pubkey, // VoteManager the referendum belongs to.
+    pub referendum: Pubkey,   // The new Referendum account.
+    pub id: u64,              // Referendum id.
+    pub question_uri: String, // Off-chain question document.
+    pub deadline: i64,        // Voting deadline (unix timestamp).
+}
+
+/// Emitted when a voter answers a referendum.
+#[event]
+pub struct ReferendumVoted {
+    pub referendum: Pubkey,       // The Referendum account.
+    pub voter: Pubkey,            // Voter's public key.
+    pub choice: ReferendumChoice, // The answer.
+    pub weight: u64,              // Vote weight.
+}
+
+/// Emitted when a passed proposal is executed.
+#[event]
+pub struct ProposalExecuted {
+    pub vote_manager: Pubkey, // VoteManager the proposal belongs to.
+    pub proposal: Pubkey,     // The Proposal account.
+    pub id: u64,              // Proposal id.
+    pub instructions: u8,     // Number of instructions executed.
+}
+
+/// Defines custom error codes for the VoteProject program.
+/// Provides clear and descriptive error messages for various failure scenarios.
+#[error_code]
+pub enum VoteError {
+    #[msg("Vote program with admin: do not initialize!")]
+    NotAdmin, // Triggered when a non-admin attempts an admin-only action.
+    #[msg("Wrong vote round.")]
+    WrongRound, // Triggered when a vote is cast in an incorrect round.
+    #[msg("Admin account already initialized.")]
+    InsufficientTokens, // Triggered when a voter lacks sufficient tokens to cast a vote.
+    #[msg("ProjectIdTooLong")]
+    ProjectIdTooLong,
+    #[msg("IncorrectVoteFee")]
+    IncorrectVoteFee,
+    #[msg("WrongMint")]
+    WrongMint,
+    #[msg("Voter has already used all votes for this round.")]
+    AlreadyVoted, // Triggered when a voter exceeds `max_votes_per_round`.
+    #[msg("IncorrectMaxVotes")]
+    IncorrectMaxVotes,
+    #[msg("WrongTokenProgram")]
+    WrongTokenProgram, // Triggered when a token account or program differs from `tk_program`.
+    #[msg("WrongTokenOwner")]
+    WrongTokenOwner, // Triggered when the voter's token account is not owned by the signer.
+    #[msg("WrongVoteManager")]
+    WrongVoteManager, // Triggered when a project belongs to another VoteManager.
+    #[msg("IncorrectRoundSchedule")]
+    IncorrectRoundSchedule,
+    #[msg("Voting round has ended.")]
+    RoundEnded, // Triggered when a vote is cast after the round deadline.
+    #[msg("IncorrectWeightCurve")]
+    IncorrectWeightCurve,
+    #[msg("Voting round has not ended yet.")]
+    RoundNotEnded, // Triggered when finalizing a round before its deadline.
+    #[msg("WrongWinner")]
+    WrongWinner, // Triggered when the passed winner is not the round's leading project.
+    #[msg("NotPendingAdmin")]
+    NotPendingAdmin, // Triggered when accepting an admin transfer that was not proposed.
+    #[msg("Votes must be committed and revealed in this mode.")]
+    CommitRevealMode, // Triggered when calling `do_vote` in commit–reveal mode.
+    #[msg("Commit–reveal mode is disabled.")]
+    CommitRevealDisabled, // Triggered when committing outside commit–reveal mode.
+    #[msg("Not in the reveal window.")]
+    NotRevealWindow, // Triggered when revealing before the deadline or after the window.
+    #[msg("Vote already revealed.")]
+    AlreadyRevealed,
+    #[msg("Revealed vote does not match the commitment.")]
+    InvalidReveal,
+    #[msg("Project is disqualified.")]
+    ProjectDisqualified, // Triggered when voting for or re-disqualifying a disqualified project.
+    #[msg("Project has votes, disqualify it instead.")]
+    ProjectHasVotes, // Triggered when removing a project that has received votes.
+    #[msg("WrongPayer")]
+    WrongPayer, // Triggered when rent would be returned to someone other than the payer.
+    #[msg("Close the project's voter records first.")]
+    VoterRecordsRemaining, // Triggered when closing a project with open VoterData accounts.
+    #[msg("IncorrectWithdrawAmount")]
+    IncorrectWithdrawAmount,
+    #[msg("Insufficient treasury balance.")]
+    InsufficientTreasuryBalance, // Triggered when withdrawing more than the treasury holds.
+    #[msg("Stealth voting is disabled.")]
+    StealthVotingDisabled,
+    #[msg("Invalid ownership proof.")]
+    InvalidOwnershipProof, // Triggered when a nonce does not match a stealth record's blinded id.
+    #[msg("IncorrectClaimWindow")]
+    IncorrectClaimWindow,
+    #[msg("IncorrectRewardAmount")]
+    IncorrectRewardAmount,
+    #[msg("Rewards of this round were already swept.")]
+    RewardsSwept, // Triggered when funding or sweeping a swept reward vault.
+    #[msg("Rewards are still claimable.")]
+    ClaimWindowOpen, // Triggered when sweeping before the claim deadline.
+    #[msg("IncorrectProposalConfig")]
+    IncorrectProposalConfig,
+    #[msg("Proposal instructions are empty or exceed the size limits.")]
+    InvalidProposal,
+    #[msg("Proposal voting has ended.")]
+    ProposalVotingClosed, // Triggered when voting on a proposal after its deadline.
+    #[msg("Proposal voting has not ended yet.")]
+    ProposalVotingOpen, // Triggered when acting on a proposal or its votes before its deadline.
+    #[msg("Proposal did not pass.")]
+    ProposalNotPassed,
+    #[msg("Proposal already executed.")]
+    ProposalExecuted,
+    #[msg("Execute the passed proposal before closing it.")]
+    ProposalNotExecuted,
+    #[msg("Round parameters changed since the round opened.")]
+    ConfigChanged, // Triggered when finalizing a round whose rules no longer match its RoundConfig.
+    #[msg("Project awaits approval.")]
+    ProjectPending, // Triggered when voting for, removing or closing a pending project.
+    #[msg("Project is not pending.")]
+    ProjectNotPending,
+    #[msg("Arithmetic overflow.")]
+    Overflow, // Triggered when a counter, tally or balance would overflow or underflow.
+    #[msg("Account already uses the current layout.")]
+    AlreadyMigrated, // Triggered by migrating an account already in the current layout.
+    #[msg("Sponsor amount must be positive.")]
+    IncorrectSponsorAmount,
+    #[msg("Insufficient funds in the sponsor vault.")]
+    InsufficientSponsorFunds, // Triggered when the vault's spare balance cannot cover a payout.
+    #[msg("Wallet reached its sponsorship limit.")]
+    SponsorLimitReached, // Triggered when a reimbursement would exceed `max_per_wallet`.
+    #[msg("Missing Ed25519 signature verification.")]
+    MissingAttestation, // Triggered when the preceding instruction is not an Ed25519 verification.
+    #[msg("Signature does not attest this round result.")]
+    InvalidAttestation, // Triggered when the verified key, message or layout does not match.
+    #[msg("Voter has already used all votes for this project.")]
+    ProjectVoteCapReached, // Triggered when a voter exceeds `max_votes_per_project`.
+    #[msg("Voting round has not started yet.")]
+    RoundNotStarted,
+    #[msg("Campaign rounds must be non-empty, ordered and non-overlapping.")]
+    IncorrectCampaign, // Triggered by empty, too long or overlapping schedules and long themes.
+    #[msg("Campaign has no rounds left to open.")]
+    CampaignFinished,
+    #[msg("The last opened campaign round has not been closed yet.")]
+    CampaignRoundActive,
+    #[msg("Voting round already has votes.")]
+    RoundHasVotes, // Triggered when opening a campaign round on a round that was voted on.
+    #[msg("Votes are cast as ranked ballots in ranked-choice mode.")]
+    RankedChoiceMode, // Triggered by direct votes and finalization of rounds with ballots.
+    #[msg("Ranked-choice voting is disabled.")]
+    RankedChoiceDisabled,
+    #[msg("Ballot must rank between one and five distinct projects.")]
+    IncorrectBallot,
+    #[msg("Too many projects ranked in this round.")]
+    TooManyCandidates, // Triggered when a round's ballots rank more than `RANKED_MAX_CANDIDATES`.
+    #[msg("Ranked tally has not found a winner yet.")]
+    RankedTallyIncomplete,
+    #[msg("Voter is not in the eligibility snapshot.")]
+    NotEligible, // Triggered by missing or invalid eligibility proofs.
+    #[msg("Votes go through do_vote with an eligibility proof while a snapshot is set.")]
+    EligibilitySnapshotMode,
+    #[msg("Voting round is not pending.")]
+    RoundNotPending, // Triggered by adding projects to, opening or changing fee rules of, or creating the leaderboard of an opened round.
+    #[msg("Voting round is not open.")]
+    RoundNotOpen, // Triggered by votes and closing while the round is pending or closed.
+    #[msg("Vote fee exceeds the token supply, it must be in base units of the mint.")]
+    VoteFeeExceedsSupply, // Triggered by initializing with a fee above the mint's supply.
+    #[msg("Vote receipt mint is transferable.")]
+    ReceiptMintTransferable, // Triggered by receipt mints without the NonTransferable extension.
+    #[msg("Vote receipt accounts are missing or incorrect.")]
+    IncorrectReceiptAccounts, // Triggered by do_vote while receipts are enabled.
+    #[msg("Project is not disqualified.")]
+    ProjectNotDisqualified, // Triggered by refund claims for projects still running.
+    #[msg("No refund is due on this voter record.")]
+    NoRefundDue, // Triggered by claims on refunded records or records without fees.
+    #[msg("Voter record holds an unclaimed refund.")]
+    RefundUnclaimed, // Triggered by closing records of disqualified projects before the refund.
+    #[msg("Escrow mode is disabled.")]
+    EscrowDisabled, // Triggered by stake_for_power outside escrow mode.
+    #[msg("Votes go through do_vote with a stake position in escrow mode.")]
+    EscrowMode, // Triggered by the other voting instructions and snapshots in escrow mode.
+    #[msg("Lock duration must be positive and within the maximum lock.")]
+    InvalidLockDuration, // Triggered by set_escrow_mode and stake_for_power.
+    #[msg("Stake amount must be positive.")]
+    InvalidStakeAmount, // Triggered by stake_for_power.
+    #[msg("Stake position is missing or belongs to another voter.")]
+    IncorrectStakePosition, // Triggered by do_vote in escrow mode.
+    #[msg("Stake position has expired.")]
+    StakeExpired, // Triggered by do_vote with a position past its unlock time.
+    #[msg("Stake position is still locked.")]
+    StakeStillLocked, // Triggered by unstake before the unlock time.
+    #[msg("Project accounts do not match the project ids.")]
+    IncorrectProjectAccounts, // Triggered by add_projects and archive_projects.
+    #[msg("Project name or URI is too long.")]
+    ProjectMetadataTooLong, // Triggered by update_project_metadata.
+    #[msg("The VoteManager is paused.")]
+    Paused, // Triggered by votes, project additions and ensure_user_can_vote while paused.
+    #[msg("The Admin role cannot be granted; transfer it with propose_admin.")]
+    RoleNotGrantable, // Triggered by grant_role.
+    #[msg("Signer's role does not allow this instruction.")]
+    MissingRole, // Triggered by an Auditor adding projects or incrementing the round.
+    #[msg("Council members must be distinct and the threshold at most their number.")]
+    InvalidCouncil, // Triggered by create_admin_council.
+    #[msg("Signer is not a member of the admin council.")]
+    NotCouncilMember, // Triggered by propose_admin_action and approve_admin_action.
+    #[msg("Member already approved this admin action.")]
+    AdminActionAlreadyApproved, // Triggered by approve_admin_action.
+    #[msg("Admin action has already been executed.")]
+    AdminActionExecuted, // Triggered by approve_admin_action and execute_admin_action.
+    #[msg("Admin action has fewer approvals than the council's threshold.")]
+    AdminActionNotApproved, // Triggered by execute_admin_action.
+    #[msg("The round has a leaderboard, which must be passed to update it.")]
+    LeaderboardMissing, // Triggered by votes and retractions omitting the round's leaderboard.
+    #[msg("Referendum question URI is too long or its deadline has passed.")]
+    IncorrectReferendum, // Triggered by create_referendum.
+    #[msg("Referendum voting has closed.")]
+    ReferendumClosed, // Triggered by vote_referendum after the deadline.
+    #[msg("Deposit refund tranches must be positive and at most the full refund's votes.")]
+    IncorrectDepositRefund, // Triggered by set_deposit_refund.
+    #[msg("The project's votes have not earned a new deposit tranche.")]
+    NoDepositTranche, // Triggered by claim_deposit_tranche.
+    #[msg("The submitter has not claimed the deposit tranches the project earned.")]
+    DepositTrancheUnclaimed, // Triggered by closing a project before its earned bond is claimed.
+    #[msg("The project still escrows a listing bond.")]
+    ProjectBonded, // Triggered by remove_project on a bonded listing.
+    #[msg("The treasury must be passed to forfeit the project's listing bond.")]
+    TreasuryMissing, // Triggered by close_project_data on a bonded project without the treasury.
+    #[msg("Holding requirements need both a positive balance and holding age, or neither.")]
+    IncorrectHoldingRequirement, // Triggered by set_holding_requirement.
+    #[msg("The VoteManager has no holding requirement.")]
+    HoldingRequirementDisabled, // Triggered by register_holding without a requirement.
+    #[msg("Votes go through do_vote with a holding attestation while a holding requirement is set.")]
+    HoldingRequirementMode, // Triggered by the other voting instructions under a holding requirement.
+    #[msg("Voter's token holding is not registered for this token account.")]
+    HoldingNotRegistered, // Triggered by do_vote without the voter's HoldingAttestation.
+    #[msg("Voter's token holding was registered too recently for this round.")]
+    HoldingTooRecent, // Triggered by do_vote with an attestation younger than the holding age at opening.
+    #[msg("Voter's token account holds less than the required balance.")]
+    InsufficientHolding, // Triggered by register_holding and do_vote below `min_holding_balance`.
+    #[msg("The round already holds the maximum number of projects.")]
+    TooManyProjects, // Triggered by add_project, add_projects and approve_project past `max_projects`.
+    #[msg("Runoffs need a positive duration and cannot be held in commit–reveal mode.")]
+    IncorrectTieBreak, // Triggered by set_tie_break and set_commit_reveal.
+    #[msg("The round is tied and must go to a runoff before it is finalized.")]
+    RunoffRequired, // Triggered by finalize_round on a tied round under the Runoff policy.
+    #[msg("The round has no tie to settle with a runoff.")]
+    NoRunoffDue, // Triggered by open_runoff without a pending tie.
+    #[msg("The project is not part of the round's runoff.")]
+    NotInRunoff, // Triggered by votes for a project below the runoff's tied weight.
+    #[msg("Votes cannot be retracted during a runoff.")]
+    RunoffInProgress, // Triggered by retract_vote during a runoff.
+    #[msg("Votes cannot be paid in SOL.")]
+    SolFeesDisabled, // Triggered by do_vote paying in SOL while the round's `sol_vote_fee` is zero.
+    #[msg("The voter lacks the lamports to pay the vote in SOL.")]
+    InsufficientLamports, // Triggered by do_vote paying in SOL with a balance below the price.
+    #[msg("No reward is due on this voter record.")]
+    NoRewardDue, // Triggered by claim_reward on claimed records or shares rounding to zero.
+    #[msg("Voter record holds an unclaimed reward.")]
+    RewardUnclaimed, // Triggered by closing records of the winner before the reward is claimed.
+    #[msg("A top-up must be directly followed by the TTT vote it funds.")]
+    TopUpWithoutVote, // Triggered by ensure_user_can_vote not directly followed by the voter's vote.
+    #[msg("Top-up exceeds the price of the vote it funds.")]
+    TopUpTooLarge, // Triggered by ensure_user_can_vote with a fee above the vote's price.
+    #[msg("Top-up pool amount must be positive.")]
+    IncorrectTopUpAmount, // Triggered by fund_top_ups or withdraw_top_ups with a zero amount.
+    #[msg("Voter is not on the allowlist.")]
+    NotAllowlisted, // Triggered by do_vote in Allowlist mode for a wallet without an entry.
+    #[msg("Voter is on the blocklist.")]
+    Blocklisted, // Triggered by do_vote in Blocklist mode for a wallet with an entry.
+    #[msg("Wrong or missing access list entry account.")]
+    WrongAccessEntry, // Triggered by do_vote without the voter's entry address on the active list.
+    #[msg("Voting with an access list requires do_vote.")]
+    AccessListMode, // Triggered by the other voting instructions while an access list is active.
+    #[msg("Project is already archived.")]
+    ProjectAlreadyArchived, // Triggered when a page archives a project twice.
+    #[msg("Round archive already holds every project of the round.")]
+    RoundArchiveComplete,
+    #[msg("Sealing a round requires its RoundArchive.")]
+    RoundArchiveMissing, // Triggered by tally_ranked_round passing round_result without round_archive.
+    #[msg("Archive the project before closing it.")]
+    ProjectNotArchived, // Triggered when closing a project missing from its round's archive.
+    #[msg("Tally pages must follow each other in ascending project address order.")]
+    TallyPageOutOfOrder, // Triggered by a resent, overlapping or unsorted tally_page.
+    #[msg("Tally has not counted every project of the round.")]
+    TallyIncomplete, // Triggered by tally_commit before the last page, or after the deadline moved.
+    #[msg("Unknown top-up reason, or an account-only top-up with an amount.")]
+    InvalidTopUpReason, // Triggered by ensure_user_can_vote.
+    #[msg("Discount tiers must ascend in balance and discount, up to 100% and 8 tiers.")]
+    IncorrectFeeDiscounts, // Triggered by set_fee_discounts.
+    #[msg("Vote authorization nonce is not the voter's next nonce.")]
+    StaleVoteNonce, // Triggered by vote_with_authorization replaying or skipping a nonce.
+    #[msg("Voter's signature does not authorize this vote.")]
+    InvalidVoteAuthorization, // Triggered when the preceding Ed25519 verification is missing or signs another vote.
+    #[msg("Voter's token account has not delegated its tokens to the VoteManager.")]
+    VoteNotDelegated, // Triggered by vote_with_authorization without an approved allowance.
+    #[msg("Invalidation reason must be 1 to 200 bytes.")]
+    IncorrectInvalidReason, // Triggered by invalidate_round.
+    #[msg("Round was invalidated.")]
+    RoundInvalid, // Triggered by claiming rewards of or attesting an invalidated round.
+    #[msg("Categories must be up to 16 distinct names of 1 to 32 bytes.")]
+    IncorrectCategories, // Triggered by set_categories.
+    #[msg("Compressed votes cannot be priced or capped per voter.")]
+    CompressedVotesUnsupported, // Triggered by compressed_vote under quadratic pricing or a vote cap.
+    #[msg("Compressed vote does not match the signer or the round's winner.")]
+    CompressedVoteMismatch, // Triggered by claim_compressed_reward.
+    #[msg("VoteManager is already initialized.")]
+    AlreadyInitialized, // Triggered by initialize on a VoteManager that has an admin.
+    #[msg("Voter was already topped up in this round.")]
+    AlreadyToppedUp, // Triggered by a second ensure_user_can_vote transfer to a voter in a round.
+}
+
+/// Defines the accounts required to fund the VoteManager's top-up pool.
+///
+/// **Business Logic:**
+/// - Initializes the pool, the VoteManager PDA's token account, on first funding.
+/// - The handler checks that the signer is the admin.
+#[derive(Accounts)]
+pub struct FundTopUps<'info> {
+    #[account(
+            init_if_needed,
+            payer = owner,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = vote_manager,
+        )]
+    pub top_up_account: InterfaceAccount<'info, TokenAccount>, // VoteManager's top-up pool.
+    #[account(
+            mut,
+            constraint = source.mint == vote_manager.tk_mint @ VoteError::WrongMint
+        )]
+    pub source: InterfaceAccount<'info, TokenAccount>, // Admin's token account funding the pool.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Defines the accounts required to top up a voter from the VoteManager's top-up pool.
+///
+/// **Business Logic:**
+/// - The pool is the VoteManager PDA's token account; the admin funds it with plain transfers.
+/// - Only the voter signs; the VoteManager PDA authorizes the transfer out of the pool.
+/// - `voter_data` is the VoterData of the funded vote, which validates it, read for the vote's
+///   price; the voter's RoundVotes is created on their first vote of the round.
+#[derive(Accounts)]
+#[instruction(vote_fee:u64)]
+pub struct EnsureCanVote<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter's signer account.
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = vote_manager,
+        )]
+    pub top_up_account: InterfaceAccount<'info, TokenAccount>, // VoteManager's top-up pool.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+           init_if_needed,
+           payer = signer,
+           associated_token::token_program = token_program,
+           associated_token::mint = mint,
+           associated_token::authority = signer,
+           constraint = user_ata.owner == signer.key(),
+           constraint = user_ata.mint == mint.key()
+        )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Instructions sysvar, verified by its address.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>, // Instructions of the current transaction.
+    /// CHECK: Must be the VoterData of the funded vote, which checks its seeds.
+    pub voter_data: UncheckedAccount<'info>, // VoterData of the vote the top-up funds.
+    #[account(
+            init_if_needed,
+            payer = signer,
+            space = 8 + RoundVotes::INIT_SPACE,
+            seeds = [
+                ROUND_VOTES_SEED,
+                vote_manager.key().as_ref(),
+                &vote_manager.vote_round.to_le_bytes(),
+                signer.key().as_ref()
+            ],
+            bump
+        )]
+    pub round_votes: Account<'info, RoundVotes>, // The voter's votes and top-up in the round.
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Defines the accounts required to thaw a voter's token account.
+///
+/// **Business Logic:**
+/// - The handler checks that the signer is the admin.
+#[derive(Accounts)]
+pub struct ThawVoterAccount<'info> {
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(
+            mut,
+            token::mint = mint,
+            token::token_program = token_program,
+        )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>, // Voter's account to thaw.
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
+}
+
+/// Defines the accounts required to withdraw tokens from the VoteManager's top-up pool.
+///
+/// **Business Logic:**
+/// - The handler checks that the signer is the admin.
+#[derive(Accounts)]
+pub struct WithdrawTopUps<'info> {
+    #[account(
+            seeds = [
+                VOTE_MANAGER_SEED,
+                vote_manager.creator.as_ref(),
+                &vote_manager.election_seed()
+            ],
+            bump = vote_manager.bump,
+            constraint = token_program.key() == vote_manager.tk_program @ VoteError::WrongTokenProgram
+        )]
+    pub vote_manager: Account<'info, VoteManager>, // Reference to the VoteManager account.
+    #[account(
+            mut,
+            associated_token::token_program = token_program,
+            associated_token::mint = mint,
+            associated_token::authority = vote_manager,
+        )]
+    pub top_up_account: InterfaceAccount<'info, TokenAccount>, // VoteManager's top-up pool.
+    #[account(
+            mut,
+            constraint = destination.mint == vote_manager.tk_mint @ VoteError::WrongMint
+        )]
+    pub destination: InterfaceAccount<'info, TokenAccount>, // Account receiving the tokens.
+    #[account(
+      constraint = mint.key() == vote_manager.tk_mint @ VoteError::WrongMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    pub owner: Signer<'info>, // The admin's signer account.
+    pub token_program: Interface<'info, TokenInterface>, // Token program interface.
 }
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 
 pub mod instructions;
+pub mod seeds;
+pub mod utils;
 pub use instructions::*;
+pub use seeds::*;
+pub use utils::*;
 
 // Declare the unique program ID that associates this Rust program with its deployed counterpart on
 // Solana.
@@ -21,102 +25,2444 @@ pub mod governance {
     /// **Business Logic:**
     /// - Ensures that only the designated admin can perform initialization.
     /// - Sets up the initial voting round, token mint, token program, and voting fee.
-    /// - Prevents re-initialization by checking if the admin is already set.
+    /// - Verifies the mint against its token program and requires the admin's fee account,
+    ///   instead of trusting pubkeys passed as arguments.
+    /// - `init_vote_fee` is in base units of the mint, whatever its decimals: a fee above the
+    ///   mint's supply cannot be, and fails with `VoteFeeExceedsSupply`.
+    /// - Prevents re-initialization: the VoteManager is created with `init`, and an admin already
+    ///   set fails with `AlreadyInitialized`.
+    /// - Creates the VoteManager of `election_id`: each election has its own VoteManager,
+    ///   treasury, projects and voter records. Election 0 is the one created before elections
+    ///   were numbered.
     pub fn initialize(
-        ctx: Context<Admin>,
-        token_mint: Pubkey,
-        token_program: Pubkey,
+        ctx: Context<Initialize>,
+        election_id: u64,
         init_vote_fee: u64,
     ) -> Result<()> {
         check_is_admin(&ADMIN_PUBKEY, &ctx.accounts.owner.key())?;
-        instructions::initialize_vote(ctx, token_mint, token_program, init_vote_fee)
+        require!(
+            ctx.accounts.vote_data.admin == Pubkey::default(),
+            VoteError::AlreadyInitialized
+        );
+        require!(
+            init_vote_fee <= ctx.accounts.mint.supply,
+            VoteError::VoteFeeExceedsSupply
+        );
+        instructions::initialize_vote(ctx, election_id, init_vote_fee)
     }
 
     /// Increments the current voting round by one.
     ///
     /// **Business Logic:**
-    /// - Allows the admin to progress the voting cycle to the next round.
+    /// - Allows the admin, or an Operator passing their RoleGrant, to progress the voting cycle to
+    ///   the next round.
     /// - Updates the `vote_round` state in the VoteManager.
-    pub fn increment_round(ctx: Context<Admin>) -> Result<()> {
-        check_is_admin(&ADMIN_PUBKEY, &ctx.accounts.owner.key())?;
+    /// - Starts the new round's schedule at the current cluster time.
+    /// - Records the hash of the new round's parameters in `round_config`.
+    /// - The new round is `Pending`: projects can be added until the admin opens it.
+    /// - Emits a `RoundIncremented` event.
+    pub fn increment_round(ctx: Context<IncrementRound>) -> Result<()> {
+        check_is_operator(
+            &ctx.accounts.vote_data.admin,
+            &ctx.accounts.owner.key(),
+            ctx.accounts.role.as_deref(),
+        )?;
         instructions::increment_vote_round(ctx)
     }
 
+    /// Migrates a VoteManager created before round numbers were widened to `u64`.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin recorded in the legacy account can migrate it.
+    /// - Rewrites the VoteManager in the current layout and grows the account, the admin paying
+    ///   the extra rent.
+    /// - Accounts of earlier rounds keep their legacy layout and seeds: close them with `cleanup`
+    ///   and migrate before any project is added to the current round.
+    pub fn migrate_vote_manager(ctx: Context<MigrateVoteManager>, _creator: Pubkey) -> Result<()> {
+        let legacy = instructions::read_legacy_vote_manager(&ctx.accounts.vote_manager)?;
+        check_is_admin(&legacy.admin, &ctx.accounts.owner.key())?;
+        instructions::migrate_vote_manager_layout(ctx, legacy)
+    }
+
+    /// Upgrades a VoteManager, ProjectData or VoterData account to the current layout version.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can migrate: every field keeps its value, only the `version` byte and the size
+    ///   change, the signer paying the extra rent.
+    /// - Accounts of the unversioned layout, which lacks the `version` byte, decode as version 0.
+    /// - Instructions cannot load an account until it is migrated.
+    /// - Emits an `AccountMigrated` event.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_versioned_account(ctx)
+    }
+
+    /// Proposes a new admin for the VoteManager.
+    ///
+    /// **Business Logic:**
+    /// - Only the current admin can propose a successor.
+    /// - Stores the proposal in `pending_admin`; the admin does not change until it is accepted.
+    /// - Proposing again replaces the previous proposal.
+    pub fn propose_admin(ctx: Context<Admin>, new_admin: Pubkey) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::propose_vote_admin(ctx, new_admin)
+    }
+
+    /// Accepts a pending admin proposal.
+    ///
+    /// **Business Logic:**
+    /// - Only the proposed admin can accept, proving control of the new key.
+    /// - Moves `pending_admin` into `admin` and clears the proposal.
+    pub fn accept_admin(ctx: Context<Admin>) -> Result<()> {
+        require!(
+            ctx.accounts.vote_data.pending_admin == Some(ctx.accounts.owner.key()),
+            VoteError::NotPendingAdmin
+        );
+        instructions::accept_vote_admin(ctx)
+    }
+
+    /// Sets the time box of the current voting round.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the schedule.
+    /// - Votes cast after `round_start_ts + round_duration` are rejected; the deadline is frozen
+    ///   when the round opens, so changing it in an open round applies from the next one.
+    /// - A zero `round_duration` leaves the round open until the admin increments it.
+    pub fn set_round_schedule(
+        ctx: Context<Admin>,
+        round_start_ts: i64,
+        round_duration: i64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            round_start_ts >= 0
+                && round_duration >= 0
+                && round_start_ts.checked_add(round_duration).is_some(),
+            VoteError::IncorrectRoundSchedule
+        );
+
+        instructions::set_vote_round_schedule(ctx, round_start_ts, round_duration)
+    }
+
+    /// Opens the current round for voting.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can open a round, and only while it is `Pending`.
+    /// - Votes are accepted from then on, within the round's schedule, and projects can no longer
+    ///   be added.
+    /// - Freezes the fee, weight curve and deadline in `round_config` and records the hash of the
+    ///   round's parameters there.
+    /// - Emits a `RoundStateChanged` event.
+    pub fn open_round(ctx: Context<Admin>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            ctx.accounts.vote_data.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+
+        instructions::open_vote_round(ctx)
+    }
+
+    /// Closes the current round to new votes.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can close a round, and only while it is `Open`.
+    /// - Votes already cast stay counted, and committed votes can still be revealed.
+    /// - The round is then sealed by `finalize_round` once its tally deadline passes, or moved on
+    ///   from without a result by `increment_round`.
+    /// - Emits a `RoundStateChanged` event.
+    pub fn close_round(ctx: Context<Admin>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            ctx.accounts.vote_data.round_state == RoundState::Open,
+            VoteError::RoundNotOpen
+        );
+
+        instructions::close_vote_round(ctx)
+    }
+
     /// Changes the voting fee to a new specified amount.
     ///
     /// **Business Logic:**
     /// - Only the admin can modify the voting fee.
-    /// - Updates the `vote_fee` state in the VoteManager.
+    /// - Updates the `vote_fee` state in the VoteManager; it applies from the next round opened,
+    ///   as votes are charged the fee frozen in `round_config` when their round opened.
+    /// - Emits a `FeeChanged` event.
     pub fn change_fee(ctx: Context<Admin>, new_vote_fee: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
         require!(new_vote_fee > 0, VoteError::IncorrectVoteFee);
 
         instructions::change_vote_fee(ctx, new_vote_fee)
     }
 
+    /// Changes the lamports a vote paid in SOL costs, the alternative to the TTT `vote_fee`.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the fee.
+    /// - Updates the `sol_vote_fee` state in the VoteManager; it applies from the next round
+    ///   opened, like `vote_fee`.
+    /// - Zero stops `do_vote` from accepting SOL, so voters pay in TTT only.
+    /// - Emits a `SolFeeChanged` event.
+    pub fn change_sol_fee(ctx: Context<Admin>, new_sol_vote_fee: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::change_sol_vote_fee(ctx, new_sol_vote_fee)
+    }
+
+    /// Changes the maximum number of votes a voter may cast per round.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the limit.
+    /// - Updates the `max_votes_per_round` state in the VoteManager.
+    /// - The limit applies to the votes a voter casts in a round across all projects; retracted
+    ///   votes no longer count towards it.
+    pub fn change_max_votes(ctx: Context<Admin>, max_votes_per_round: u16) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(max_votes_per_round > 0, VoteError::IncorrectMaxVotes);
+
+        instructions::change_vote_max_votes(ctx, max_votes_per_round)
+    }
+
+    /// Changes the maximum number of votes a voter may cast for a single project.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the cap.
+    /// - Zero removes the cap; otherwise it applies on top of `max_votes_per_round`.
+    /// - The cap is part of the round's rules, so changing it mid-round blocks `finalize_round`.
+    pub fn change_max_votes_per_project(
+        ctx: Context<Admin>,
+        max_votes_per_project: u16,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::change_vote_max_votes_per_project(ctx, max_votes_per_project)
+    }
+
+    /// Changes the maximum number of projects a round may hold, keeping rounds small enough to
+    /// tally.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the cap.
+    /// - Zero removes the cap.
+    /// - `add_project`, `add_projects` and `approve_project` count against it; projects already in
+    ///   the round stay, even above a lowered cap.
+    pub fn change_max_projects(ctx: Context<Admin>, max_projects: u16) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::change_vote_max_projects(ctx, max_projects)
+    }
+
+    /// Sets how a round whose leading projects tie for the lead is decided.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the policy.
+    /// - `EarliestCreated` and `LowestPda` pick the winner among the tied projects by their
+    ///   `created_slot` or PDA; `Runoff` holds a runoff of `runoff_duration` seconds between them
+    ///   first, which needs a positive duration and is not available in commit–reveal mode.
+    /// - The policy is part of the round's rules, so changing it mid-round blocks
+    ///   `finalize_round`.
+    pub fn set_tie_break(
+        ctx: Context<Admin>,
+        tie_break: TieBreakPolicy,
+        runoff_duration: i64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            tie_break != TieBreakPolicy::Runoff
+                || (runoff_duration > 0 && !ctx.accounts.vote_data.commit_reveal),
+            VoteError::IncorrectTieBreak
+        );
+        instructions::set_vote_tie_break(ctx, tie_break, runoff_duration)
+    }
+
+    /// Sets the curve that turns a voter's token balance into vote weight.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the curve.
+    /// - Updates the `weight_curve` state in the VoteManager.
+    /// - Takes effect from the next round opened, which freezes it in `round_config`.
+    pub fn set_weight_curve(ctx: Context<Admin>, weight_curve: WeightCurve) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(weight_curve.multiplier > 0, VoteError::IncorrectWeightCurve);
+
+        instructions::set_vote_weight_curve(ctx, weight_curve)
+    }
+
     /// Adds a new project to the current voting round.
     ///
     /// **Business Logic:**
-    /// - Allows the admin to introduce new projects for voting.
+    /// - Allows the admin, or an Operator passing their RoleGrant, to introduce new projects for
+    ///   voting; the project is seeded by the admin's key either way.
+    /// - Only allowed while the round is `Pending`, before it is opened for voting.
     /// - Initializes the project's vote count and associates it with the current round and fee.
+    /// - The project is seeded by the VoteManager's election, so the same id can run in several
+    ///   elections at once.
+    /// - Rejected once the round holds `max_projects` projects.
+    /// - Emits a `ProjectAdded` event.
     pub fn add_project(ctx: Context<NewVoteProject>, id: String) -> Result<()> {
-        check_is_admin(&ADMIN_PUBKEY, &ctx.accounts.owner.key())?;
+        check_is_operator(
+            &ctx.accounts.vote_manager.admin,
+            &ctx.accounts.owner.key(),
+            ctx.accounts.role.as_deref(),
+        )?;
+        require!(!ctx.accounts.vote_manager.paused, VoteError::Paused);
 
         require!(
             id.len() <= PROJECT_ID_MAX_LEN,
             VoteError::ProjectIdTooLong
         );
+        require!(
+            ctx.accounts.vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
 
         instructions::add_vote_project(ctx, id)
     }
 
+    /// Adds up to `PROJECT_BATCH_MAX_LEN` projects to the current voting round in one
+    /// transaction.
+    ///
+    /// **Business Logic:**
+    /// - Same rules as `add_project`, applied to every id.
+    /// - The ProjectData PDAs are passed as remaining accounts, one per id and in the same order;
+    ///   each is checked against the seed derivation of its id.
+    /// - Emits a `ProjectAdded` event per project.
+    pub fn add_projects<'info>(
+        ctx: Context<'_, '_, 'info, 'info, NewVoteProjects<'info>>,
+        ids: Vec<String>,
+    ) -> Result<()> {
+        check_is_operator(
+            &ctx.accounts.vote_manager.admin,
+            &ctx.accounts.owner.key(),
+            ctx.accounts.role.as_deref(),
+        )?;
+        require!(!ctx.accounts.vote_manager.paused, VoteError::Paused);
+
+        require!(
+            !ids.is_empty()
+                && ids.len() <= PROJECT_BATCH_MAX_LEN
+                && ids.len() == ctx.remaining_accounts.len(),
+            VoteError::IncorrectProjectAccounts
+        );
+        require!(
+            ids.iter().all(|id| id.len() <= PROJECT_ID_MAX_LEN),
+            VoteError::ProjectIdTooLong
+        );
+        require!(
+            ctx.accounts.vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+
+        instructions::add_vote_projects(ctx, ids)
+    }
+
+    /// Sets the TTT deposit a permissionless project submission escrows.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the deposit.
+    /// - A deposit of 0 lets anyone submit projects for free; they still need approval.
+    pub fn set_project_deposit(ctx: Context<Admin>, project_deposit: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::set_vote_project_deposit(ctx, project_deposit)
+    }
+
+    /// Sets how approved submissions earn their deposit back, turning deposits into listing bonds.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify it.
+    /// - Every `tranche_votes` votes a project receives refund `tranche_votes / refund_votes` of
+    ///   its deposit through `claim_deposit_tranche`, and `refund_votes` votes refund all of it.
+    /// - Zero for both refunds deposits on approval, as before; otherwise `tranche_votes` must be
+    ///   positive and at most `refund_votes`.
+    /// - Emits a `DepositRefundChanged` event.
+    pub fn set_deposit_refund(
+        ctx: Context<Admin>,
+        tranche_votes: u64,
+        refund_votes: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            (tranche_votes == 0 && refund_votes == 0)
+                || (tranche_votes > 0 && tranche_votes <= refund_votes),
+            VoteError::IncorrectDepositRefund
+        );
+        instructions::set_vote_deposit_refund(ctx, tranche_votes, refund_votes)
+    }
+
+    /// Submits a project for the current voting round without admin rights.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can submit; the project is `pending` and rejects votes until the admin approves it.
+    /// - Only allowed while the round is `Pending`.
+    /// - Escrows `project_deposit` TTT in the treasury.
+    /// - Emits a `ProjectSubmitted` event.
+    pub fn submit_project<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitProject<'info>>,
+        id: String,
+    ) -> Result<()> {
+        require!(!ctx.accounts.vote_manager.paused, VoteError::Paused);
+        require!(
+            id.len() <= PROJECT_ID_MAX_LEN,
+            VoteError::ProjectIdTooLong
+        );
+        require!(
+            ctx.accounts.vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+
+        instructions::submit_vote_project(ctx, id)
+    }
+
+    /// Approves a submitted project.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can approve projects, and only during the round they were submitted for.
+    /// - Only allowed while the round is `Pending`, like `add_project`.
+    /// - Refunds the deposit to the submitter, or keeps it as a listing bond if the project was
+    ///   submitted with deposit refund tranches.
+    /// - Rejected once the round holds `max_projects` projects, like `add_project`.
+    /// - Emits a `ProjectAdded` event.
+    pub fn approve_project<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApproveProject<'info>>,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(
+            ctx.accounts.vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+
+        instructions::approve_vote_project(ctx)
+    }
+
+    /// Rejects a submitted project.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can reject projects.
+    /// - With `slash`, the deposit is kept as treasury revenue; otherwise it is refunded.
+    /// - Closes the ProjectData account and returns its rent to the submitter.
+    /// - Emits a `ProjectRejected` event.
+    pub fn reject_project<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RejectProject<'info>>,
+        slash: bool,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::reject_vote_project(ctx, slash)
+    }
+
+    /// Refunds the tranches of an approved project's listing bond its votes earned.
+    ///
+    /// **Business Logic:**
+    /// - Only the submitter can claim, in any round, as long as the project is not disqualified.
+    /// - Refunds the part of the bond earned by the project's current vote count and not refunded
+    ///   yet; votes retracted after a claim are not clawed back.
+    /// - Fails with `NoDepositTranche` until another tranche is earned.
+    /// - Emits a `DepositTrancheClaimed` event.
+    pub fn claim_deposit_tranche<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimDepositTranche<'info>>,
+    ) -> Result<()> {
+        let amount = ctx.accounts.project.load()?.claimable_deposit();
+        require!(amount > 0, VoteError::NoDepositTranche);
+        instructions::claim_vote_deposit_tranche(ctx, amount)
+    }
+
+    /// Removes a project from its voting round.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can remove projects.
+    /// - Closes the ProjectData account and returns its rent to the admin.
+    /// - Projects that already received votes must be disqualified instead, and bonded listings
+    ///   stay until their round is cleaned up.
+    pub fn remove_project(ctx: Context<RemoveProject>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::remove_vote_project(ctx)
+    }
+
+    /// Sets a project's display metadata: its name, the URI of an off-chain JSON description and
+    /// a category code.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can update metadata, in any round state, so listings can be corrected.
+    /// - `name` is at most `PROJECT_NAME_MAX_LEN` bytes and `uri` at most `PROJECT_URI_MAX_LEN`.
+    /// - The strings are written into the ProjectData account's fixed-size fields in place.
+    /// - Emits a `ProjectMetadataUpdated` event.
+    pub fn update_project_metadata(
+        ctx: Context<UpdateProjectMetadata>,
+        name: String,
+        uri: String,
+        category: u8,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(
+            name.len() <= PROJECT_NAME_MAX_LEN && uri.len() <= PROJECT_URI_MAX_LEN,
+            VoteError::ProjectMetadataTooLong
+        );
+
+        instructions::update_vote_project_metadata(ctx, name, uri, category)
+    }
+
+    /// Disqualifies a project.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can disqualify projects.
+    /// - Keeps the ProjectData account but makes `do_vote` reject further votes for it.
+    /// - Removes the project's votes from the running round tallies. If it was leading,
+    ///   `finalize_round` recounts the leader from the round's projects.
+    /// - Its voters can claim back their fees with `claim_refund`.
+    pub fn disqualify_project(ctx: Context<DisqualifyProject>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::disqualify_vote_project(ctx)
+    }
+
+    /// Refunds the fees a voter paid for a project that was disqualified, or in a round that was
+    /// invalidated.
+    ///
+    /// **Business Logic:**
+    /// - Only the voter can claim, for their own VoterData of a disqualified project, or of any
+    ///   project of an invalidated round, passing its `RoundResult`.
+    /// - Transfers the fees recorded on the VoterData back from the treasury, whichever round
+    ///   the project belongs to.
+    /// - Each record is refunded once; the refund is flagged on the VoterData.
+    /// - Stealth records hold no wallet key and cannot be refunded.
+    /// - Fees burned under `FeeMode::Burn` are not recorded, so only collected fees are refunded.
+    /// - Fees paid in SOL are refunded in lamports from the Treasury PDA to the voter's wallet.
+    /// - Emits a `VoteRefunded` event.
+    pub fn claim_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRefund<'info>>,
+    ) -> Result<()> {
+        instructions::claim_vote_refund(ctx)
+    }
+
+    /// Pays a voter who backed a finalized round's winner their share of the round's fees.
+    ///
+    /// **Business Logic:**
+    /// - The round's `reward_pool`, the TTT fees it collected net of refunds, is shared by the
+    ///   winner's voters pro rata to the weight they gave it.
+    /// - Only the voter can claim, for their own VoterData of the winning project; stealth records
+    ///   and ranked ballots hold no wallet key and cannot claim.
+    /// - Each record is rewarded once; the claim is flagged on the VoterData.
+    /// - Fees burned under `FeeMode::Burn` and fees paid in SOL are not part of the pool.
+    /// - Emits a `VoteRewardClaimed` event.
+    pub fn claim_reward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimReward<'info>>,
+    ) -> Result<()> {
+        instructions::claim_vote_reward(ctx)
+    }
+
     /// Facilitates the voting process for a project.
     ///
     /// **Business Logic:**
-    /// - Ensures the vote is cast in the correct round.
-    /// - Validates that the voter has sufficient tokens to cover the voting fee.
+    /// - Ensures the vote is cast in the correct round, and only while it is `Open`.
+    /// - Records the vote under the VoteManager's election, independently of the voter's votes in
+    ///   other elections.
+    /// - Validates that the voter has sufficient tokens to cover the vote's price: the voting fee,
+    ///   or `N² × vote_fee` for their Nth vote on the project with quadratic pricing.
+    /// - Rejects voters who already cast `max_votes_per_round` votes in this round, counted across
+    ///   all projects in their RoundVotes account.
+    /// - Rejects votes cast after the round deadline.
+    /// - While an eligibility snapshot is set, rejects voters whose `proof` does not link
+    ///   `(signer, weight)` to the `eligibility_root`.
     /// - Updates the vote count for both the project and the voter.
-    /// - Transfers the voting fee from the voter to the admin's fee account using Token-2022 CPI.
-    pub fn do_vote(ctx: Context<Voter>) -> Result<()> {
-        // Ensure the voter has enough tokens to cover the voting fee.
+    /// - Adds the voter's weight, evaluated on the `weight_curve` or frozen in the snapshot, to the
+    ///   project's tally.
+    /// - Transfers the voting fee from the voter to the treasury's fee account using Token-2022 CPI.
+    /// - With `fee_currency` set to `Sol`, charges the round's `sol_vote_fee` in lamports instead,
+    ///   transferred to the Treasury PDA through the System Program; rejected while the round's
+    ///   `sol_vote_fee` is zero. The vote is weighed the same way whatever it is paid in.
+    /// - In escrow mode, weighs the vote with the `voting_power` of the voter's StakePosition,
+    ///   passed as the first remaining account, and rejects expired positions.
+    /// - While `vote_receipts` is enabled, mints a non-transferable receipt to the voter through
+    ///   the ttt_token program; the receipt accounts are passed as remaining accounts, after the
+    ///   StakePosition in escrow mode.
+    /// - Once the round has a leaderboard, the vote must pass it and moves the project to its new
+    ///   standing on it.
+    /// - Under a holding requirement, the voter's HoldingAttestation must have been registered
+    ///   `min_holding_slots` before the round opened, and the token account must still hold
+    ///   `min_holding_balance`.
+    /// - Outside `AccessMode::Open`, the voter must be on the allowlist, or off the blocklist; the
+    ///   address of their entry on the active list is passed even if it does not exist.
+    /// - During a runoff, only counts votes for the projects tied for the lead.
+    /// - Records the round in the voter's VoteLock: once the TTT mint's transfer hook is on, their
+    ///   TTT cannot leave the wallet until the round closes, except to pay further vote fees. The
+    ///   hook's accounts are passed after the other remaining accounts.
+    /// - Emits a `VoteCast` event.
+    pub fn do_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Voter<'info>>,
+        proof: Option<EligibilityProof>,
+        fee_currency: FeeCurrency,
+    ) -> Result<()> {
+        let snapshot_weight = instructions::check_eligibility(
+            &ctx.accounts.vote_manager,
+            &ctx.accounts.signer.key(),
+            proof.as_ref(),
+        )?;
+        let stake_weight = instructions::stake_weight(
+            &ctx.accounts.vote_manager,
+            &ctx.accounts.signer.key(),
+            ctx.remaining_accounts.first(),
+        )?;
+        instructions::check_holding(
+            &ctx.accounts.vote_manager,
+            ctx.accounts.holding_attestation.as_deref(),
+            &ctx.accounts.token,
+        )?;
+        instructions::check_access(
+            &ctx.accounts.vote_manager,
+            &ctx.accounts.signer.key(),
+            ctx.accounts.access_entry.as_deref(),
+        )?;
+        let balance = match fee_currency {
+            FeeCurrency::Ttt => ctx.accounts.token.amount,
+            FeeCurrency::Sol => ctx.accounts.signer.lamports(),
+        };
+        check_can_vote(
+            &ctx.accounts.vote_manager,
+            &instructions::read_voter_data(&ctx.accounts.voter_data)?,
+            &ctx.accounts.round_votes,
+            fee_currency,
+            balance,
+        )?;
+        instructions::_do_vote(ctx, snapshot_weight.or(stake_weight), fee_currency)
+    }
+
+    /// Retracts the voter's votes for a project, so they can vote for another one instead.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed while the round is `Open` and its deadline has not passed, and not while
+    ///   the VoteManager is paused.
+    /// - Only the voter can retract their record; stealth records cannot be retracted.
+    /// - Rejected in commit–reveal and ranked-choice modes, whose votes live in other accounts,
+    ///   and for disqualified projects, whose votes are already out of the tallies.
+    /// - Rejected during a runoff, which is held between the weights the round ended with.
+    /// - Removes all of the record's votes and weight from the project and round tallies, and
+    ///   from the voter's RoundVotes, so they count no more towards `max_votes_per_round`.
+    /// - Refunds the fees paid for them when the admin enabled `retract_refund`; otherwise the
+    ///   treasury keeps them. Burned fees are never refunded.
+    /// - Idempotent: retracting a retracted record again succeeds and changes nothing.
+    /// - Vote receipts minted for the retracted votes are kept.
+    /// - Emits a `VoteRetracted` event.
+    pub fn retract_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RetractVote<'info>>,
+    ) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(!vote_manager.paused, VoteError::Paused);
+        require!(!vote_manager.commit_reveal, VoteError::CommitRevealMode);
+        require!(!vote_manager.ranked_choice, VoteError::RankedChoiceMode);
+        require!(vote_manager.runoff_weight == 0, VoteError::RunoffInProgress);
         require!(
-            ctx.accounts.token.amount >= ctx.accounts.vote_manager.vote_fee,
-            VoteError::InsufficientTokens
+            vote_manager.round_state == RoundState::Open,
+            VoteError::RoundNotOpen
+        );
+        if let Some(deadline) = vote_manager.round_deadline() {
+            require!(
+                Clock::get()?.unix_timestamp <= deadline,
+                VoteError::RoundEnded
+            );
+        }
+        instructions::retract_cast_vote(ctx)
+    }
+
+    /// Creates the leaderboard of the current round, holding its top ten projects by weight.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can create it, paying its rent, while the round is `Pending`, so it sees every
+    ///   vote of the round.
+    /// - Rejected in commit–reveal and ranked-choice modes, whose votes are only counted after the
+    ///   round closes.
+    /// - From then on every vote, sponsored vote, stealth vote and retraction of the round must
+    ///   pass the leaderboard and updates it, so clients read the standings with a single
+    ///   account fetch.
+    /// - Emits a `LeaderboardCreated` event.
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(!vote_manager.commit_reveal, VoteError::CommitRevealMode);
+        require!(!vote_manager.ranked_choice, VoteError::RankedChoiceMode);
+        require!(
+            vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+        instructions::init_round_leaderboard(ctx)
+    }
+
+    /// Casts a vote recorded under a blinded voter id.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed when the admin enabled `stealth_voting`.
+    /// - Applies the same checks, fee and tallies as `do_vote`.
+    /// - Derives the VoterData PDA from `blinded_voter = stealth_voter_id(voter, nonce)` instead of
+    ///   the wallet, so observers cannot enumerate votes per wallet from account data.
+    /// - The per-voter limit applies per blinded id; voters choosing several nonces pay a fee for
+    ///   every vote like separate wallets would.
+    /// - Disabled while an eligibility snapshot, a holding requirement or an access list is set.
+    pub fn stealth_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StealthVoter<'info>>,
+        blinded_voter: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.vote_manager.stealth_voting,
+            VoteError::StealthVotingDisabled
+        );
+        require!(
+            !ctx.accounts.vote_manager.eligibility_snapshot(),
+            VoteError::EligibilitySnapshotMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.holding_requirement(),
+            VoteError::HoldingRequirementMode
+        );
+        require!(
+            ctx.accounts.vote_manager.access_mode == AccessMode::Open,
+            VoteError::AccessListMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.escrow_mode,
+            VoteError::EscrowMode
+        );
+        check_can_vote(
+            &ctx.accounts.vote_manager,
+            &instructions::read_voter_data(&ctx.accounts.voter_data)?,
+            &ctx.accounts.round_votes,
+            FeeCurrency::Ttt,
+            ctx.accounts.token.amount,
+        )?;
+        instructions::_stealth_vote(ctx, blinded_voter)
+    }
+
+    /// Switches ranked-choice voting.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch the mode.
+    /// - When enabled, `do_vote` and `stealth_vote` are disabled and votes go through
+    ///   `cast_ranked_ballot` / `tally_ranked_round`.
+    /// - Cannot be enabled together with commit–reveal voting.
+    pub fn set_ranked_choice(ctx: Context<Admin>, ranked_choice: bool) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            !ranked_choice || !ctx.accounts.vote_data.commit_reveal,
+            VoteError::CommitRevealMode
         );
+        instructions::set_vote_ranked_choice(ctx, ranked_choice)
+    }
 
-        instructions::_do_vote(ctx)
+    /// Switches quadratic vote pricing.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch the pricing.
+    /// - When enabled, a voter's Nth vote on the same project in a round costs `N² × vote_fee`
+    ///   in `do_vote`, `stealth_vote` and `sponsored_vote`.
+    /// - The pricing is part of the round's rules, so switching it mid-round blocks
+    ///   `finalize_round`.
+    pub fn set_quadratic_pricing(ctx: Context<Admin>, quadratic_pricing: bool) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::set_vote_quadratic_pricing(ctx, quadratic_pricing)
     }
 
-    /// Only for CLI purposes. Kept here because in order to access accounts_data (account_info)
-    /// accounts should be passed through the program's Context.
-    pub fn ensure_user_can_vote(
-        ctx: Context<EnsureCanVote>,
-        vote_fee: u64,
+    /// Sets the Merkle root of the eligibility snapshot.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can set the root.
+    /// - The snapshot freezes eligible wallets and their vote weights, e.g. at a block height: each
+    ///   leaf is `eligibility_leaf(voter, weight)`, and sibling nodes are hashed in sorted order.
+    /// - While set, `do_vote` requires a proof of the signer's leaf and uses its weight; the other
+    ///   ways of voting are disabled. A zero root disables the snapshot.
+    /// - The root is part of the round's rules, so changing it mid-round blocks `finalize_round`.
+    /// - Snapshots weigh votes themselves, so a root cannot be set in escrow mode.
+    /// - `eligible_weight` is the sum of the snapshot's weights; finalization reports the round's
+    ///   turnout against it. Like the root, it is part of the round's rules.
+    pub fn set_eligibility_root(
+        ctx: Context<Admin>,
+        root: [u8; 32],
+        eligible_weight: u64,
     ) -> Result<()> {
-        check_is_admin(&ADMIN_PUBKEY, &ctx.accounts.admin_authority.key())?;
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            root == [0; 32] || !ctx.accounts.vote_data.escrow_mode,
+            VoteError::EscrowMode
+        );
+        instructions::set_vote_eligibility_root(ctx, root, eligible_weight)
+    }
 
-        let user_ttt_amount = ctx.accounts.user_ata.amount;
+    /// Switches vote-escrow mode, in which votes weigh the voting power of staked TTT.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch the mode.
+    /// - While enabled, wallets lock TTT with `stake_for_power` for up to `max_lock_duration`
+    ///   seconds, and `do_vote` weighs their votes with the position's voting power instead of the
+    ///   `weight_curve`; the other ways of voting are disabled.
+    /// - Cannot be enabled while an eligibility snapshot is set.
+    /// - The mode is part of the round's rules, so switching it mid-round blocks `finalize_round`.
+    pub fn set_escrow_mode(
+        ctx: Context<Admin>,
+        escrow_mode: bool,
+        max_lock_duration: i64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            !escrow_mode || !ctx.accounts.vote_data.eligibility_snapshot(),
+            VoteError::EligibilitySnapshotMode
+        );
+        instructions::set_vote_escrow_mode(ctx, escrow_mode, max_lock_duration)
+    }
 
-        if user_ttt_amount >= vote_fee {
-            return Ok(());
-        }
+    /// Locks TTT in a program-owned vault for voting power.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed in escrow mode, one position per wallet and VoteManager.
+    /// - Locks `amount` TTT for `lock_duration` seconds, at most `max_lock_duration`.
+    /// - Grants `amount × lock_duration / max_lock_duration` voting power, which weighs the
+    ///   wallet's votes until the lock expires.
+    /// - Emits a `StakeLocked` event.
+    pub fn stake_for_power<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakeForPower<'info>>,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        instructions::stake_tokens_for_power(ctx, amount, lock_duration)
+    }
 
-        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
-            mint: ctx.accounts.mint.to_account_info(),
-            from: ctx.accounts.admin_token_account.to_account_info(),
-            to: ctx.accounts.user_ata.to_account_info(),
-            authority: ctx.accounts.admin_authority.to_account_info(),
-        };
+    /// Releases the staked TTT of an expired position.
+    ///
+    /// **Business Logic:**
+    /// - Only the owner can unstake, once the lock expired.
+    /// - Returns the TTT and closes the position and its vault, so the wallet can stake again.
+    /// - Emits a `StakeUnlocked` event.
+    pub fn unstake<'info>(ctx: Context<'_, '_, 'info, 'info, Unstake<'info>>) -> Result<()> {
+        instructions::unstake_tokens(ctx)
+    }
+
+    /// Sets the minimum token holding age votes require, so tokens bought right before a round
+    /// opens cannot vote in it.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the requirement.
+    /// - While set, `do_vote` requires the voter's HoldingAttestation, registered with
+    ///   `register_holding` at least `min_holding_slots` slots before the round opened, and a
+    ///   balance of at least `min_balance`; the other ways of voting are disabled.
+    /// - Zero for both lifts the requirement; otherwise both must be positive.
+    /// - The requirement is part of the round's rules, so changing it mid-round blocks
+    ///   `finalize_round`.
+    /// - Emits a `HoldingRequirementChanged` event.
+    pub fn set_holding_requirement(
+        ctx: Context<Admin>,
+        min_balance: u64,
+        min_holding_slots: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            (min_balance == 0) == (min_holding_slots == 0),
+            VoteError::IncorrectHoldingRequirement
+        );
+        instructions::set_vote_holding_requirement(ctx, min_balance, min_holding_slots)
+    }
+
+    /// Registers the signer's token holding, starting the age the holding requirement counts.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed under a holding requirement, for a token account of the signer holding at
+    ///   least `min_holding_balance`.
+    /// - Each wallet registers once per VoteManager, so the holding age cannot be restarted; its
+    ///   votes must use the registered token account.
+    /// - Emits a `HoldingRegistered` event.
+    pub fn register_holding(ctx: Context<RegisterHolding>) -> Result<()> {
+        instructions::register_token_holding(ctx)
+    }
 
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    /// Grants `role` on the VoteManager to `holder`.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can grant roles.
+    /// - Grants `Operator`, allowed to add projects and increment rounds, or `Auditor`, which has
+    ///   no on-chain permissions. Fees, the treasury and every other setting stay admin-only.
+    /// - The `Admin` role is not granted: it is the VoteManager's `admin` key, handed over with
+    ///   `propose_admin` and `accept_admin`.
+    /// - A wallet holds one role per VoteManager; revoke it to grant another.
+    /// - Emits a `RoleGranted` event.
+    pub fn grant_role(ctx: Context<GrantRole>, holder: Pubkey, role: Role) -> Result<()> {
+        require!(role != Role::Admin, VoteError::RoleNotGrantable);
+        instructions::grant_vote_role(ctx, holder, role)
+    }
+
+    /// Revokes a wallet's role on the VoteManager.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can revoke roles.
+    /// - Closes the RoleGrant, returning its rent to the admin.
+    /// - Emits a `RoleRevoked` event.
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        instructions::revoke_vote_role(ctx)
+    }
 
-        anchor_spl::token_interface::transfer_checked(cpi_ctx, vote_fee, 0)?;
+    /// Creates the admin council, whose members act as the admin once `threshold` of them approve.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can create the council, once per VoteManager.
+    /// - Between one and `COUNCIL_MAX_MEMBERS` distinct members, and a threshold between one and
+    ///   the number of members.
+    /// - The council signs as the AdminCouncil PDA, so it takes over once the admin hands over the
+    ///   VoteManager: `propose_admin` to the PDA, then an executed admin action whose only
+    ///   instruction is `accept_admin`.
+    /// - Emits an `AdminCouncilCreated` event.
+    pub fn create_admin_council(
+        ctx: Context<CreateAdminCouncil>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            !members.is_empty()
+                && members.len() <= COUNCIL_MAX_MEMBERS
+                && threshold > 0
+                && usize::from(threshold) <= members.len()
+                && (1..members.len()).all(|i| !members[..i].contains(&members[i])),
+            VoteError::InvalidCouncil
+        );
 
-        Ok(())
+        instructions::create_vote_admin_council(ctx, members, threshold)
     }
-}
 
-/// Check if signer is Admin.
-fn check_is_admin(admin_key: &Pubkey, signer_key: &Pubkey) -> Result<()> {
-    require!(signer_key == admin_key, VoteError::NotAdmin);
+    /// Proposes instructions for the admin council to execute, e.g. a fee change, a round
+    /// increment or a treasury withdrawal.
+    ///
+    /// **Business Logic:**
+    /// - Only council members can propose, and the proposal counts as the proposer's approval.
+    /// - Between one and `PROPOSAL_MAX_INSTRUCTIONS` instructions, each within the account and
+    ///   data limits of proposals.
+    /// - Emits an `AdminActionProposed` event.
+    pub fn propose_admin_action(
+        ctx: Context<ProposeAdminAction>,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.council.is_member(&ctx.accounts.signer.key()),
+            VoteError::NotCouncilMember
+        );
+        check_stored_instructions(&instructions)?;
+
+        instructions::propose_council_action(ctx, instructions)
+    }
+
+    /// Approves an admin action.
+    ///
+    /// **Business Logic:**
+    /// - Only council members can approve, once each, until the action is executed.
+    /// - Emits an `AdminActionApproved` event.
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        require!(
+            ctx.accounts.council.is_member(&signer),
+            VoteError::NotCouncilMember
+        );
+        require!(
+            !ctx.accounts.action.executed,
+            VoteError::AdminActionExecuted
+        );
+        require!(
+            !ctx.accounts.action.approvals.contains(&signer),
+            VoteError::AdminActionAlreadyApproved
+        );
+
+        instructions::approve_council_action(ctx)
+    }
+
+    /// Executes an admin action approved by the council's threshold of members.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can execute once the threshold is reached; the action can be executed only once.
+    /// - CPIs the stored instructions signed by the AdminCouncil PDA; the accounts they reference
+    ///   are passed as remaining accounts.
+    /// - Emits an `AdminActionExecuted` event.
+    pub fn execute_admin_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteAdminAction<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.action.executed,
+            VoteError::AdminActionExecuted
+        );
+        require!(
+            ctx.accounts.action.approvals.len() >= usize::from(ctx.accounts.council.threshold),
+            VoteError::AdminActionNotApproved
+        );
+
+        instructions::execute_council_action(ctx)
+    }
+
+    /// Records an operation marker, guarding an admin operation sent in the same transaction.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can record markers.
+    /// - The marker's PDA is derived from `key`, and recording the same key twice fails, so a
+    ///   re-sent transaction carrying the marker cannot apply its operation again.
+    pub fn record_operation(ctx: Context<RecordOperation>, key: [u8; 32]) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::record_vote_operation(ctx, key)
+    }
+
+    /// Sets which wallets may vote, for private community elections.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch the mode, and only between rounds, while the round is `Pending`.
+    /// - Under `AccessMode::Allowlist` only wallets added with `add_to_allowlist` can vote; under
+    ///   `AccessMode::Blocklist` wallets added with `add_to_blocklist` cannot. Both lists are kept
+    ///   across mode switches.
+    /// - While a list is active, votes go through `do_vote` only; the other ways of voting are
+    ///   disabled.
+    /// - Emits an `AccessModeChanged` event.
+    pub fn set_access_mode(ctx: Context<Admin>, access_mode: AccessMode) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            ctx.accounts.vote_data.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+        instructions::set_vote_access_mode(ctx, access_mode)
+    }
+
+    /// Replaces the fee discount schedule, rewarding long-term holders with cheaper votes.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can set the schedule, and only between rounds, while the round is
+    ///   `Pending`.
+    /// - Each tier maps a minimum token balance to a discount in basis points; `do_vote` passing
+    ///   the schedule charges the fee less the discount of the highest tier the voter's balance
+    ///   reaches.
+    /// - Up to `FEE_DISCOUNT_MAX_TIERS` tiers, by strictly ascending balance and non-decreasing
+    ///   discount of at most 10,000 bps; an empty list removes every discount.
+    /// - Emits a `FeeDiscountsChanged` event.
+    pub fn set_fee_discounts(
+        ctx: Context<SetFeeDiscounts>,
+        tiers: Vec<DiscountTier>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+        require!(
+            FeeDiscountSchedule::is_valid(&tiers),
+            VoteError::IncorrectFeeDiscounts
+        );
+        instructions::set_fee_discount_schedule(ctx, tiers)
+    }
+
+    /// Names the VoteManager's project categories, so a round can run parallel tracks.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can name the categories, and only between rounds, while the round is
+    ///   `Pending`.
+    /// - `names[i]` names category code `i + 1`, the code set on projects with
+    ///   `update_project_metadata`; code 0 leaves a project uncategorized.
+    /// - Up to `CATEGORY_MAX_COUNT` distinct names of 1 to `CATEGORY_NAME_MAX_LEN` bytes; an empty
+    ///   list removes every name.
+    /// - Emits a `CategoriesChanged` event.
+    pub fn set_categories(ctx: Context<SetCategories>, names: Vec<String>) -> Result<()> {
+        require!(
+            ctx.accounts.vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+        require!(
+            CategoryRegistry::is_valid(&names),
+            VoteError::IncorrectCategories
+        );
+        instructions::set_category_names(ctx, names)
+    }
+
+    /// Adds `voter` to the VoteManager's allowlist.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can list wallets, at any time; the admin pays the entry's rent.
+    /// - Emits an `AccessListUpdated` event.
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, voter: Pubkey) -> Result<()> {
+        instructions::add_access_list_entry(
+            &mut ctx.accounts.entry,
+            ctx.accounts.vote_manager.key(),
+            voter,
+            AccessMode::Allowlist,
+            ctx.bumps.entry,
+        )
+    }
+
+    /// Adds `voter` to the VoteManager's blocklist.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can list wallets, at any time; the admin pays the entry's rent.
+    /// - Emits an `AccessListUpdated` event.
+    pub fn add_to_blocklist(ctx: Context<AddToBlocklist>, voter: Pubkey) -> Result<()> {
+        instructions::add_access_list_entry(
+            &mut ctx.accounts.entry,
+            ctx.accounts.vote_manager.key(),
+            voter,
+            AccessMode::Blocklist,
+            ctx.bumps.entry,
+        )
+    }
+
+    /// Takes a wallet off the allowlist or the blocklist.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can remove entries.
+    /// - Closes the AccessListEntry, returning its rent to the admin.
+    /// - Emits an `AccessListUpdated` event.
+    pub fn remove_from_access_list(ctx: Context<RemoveAccessListEntry>) -> Result<()> {
+        instructions::remove_access_list_entry(ctx)
+    }
+
+    /// Sets whether vote fees are collected by the treasury or burned, a deflationary option.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch the mode, and only between rounds, while the round is `Pending`,
+    ///   so every vote of a round is charged the same way.
+    /// - Under `FeeMode::Burn` every vote burns its fee from the voter's token account instead of
+    ///   transferring it to the treasury; burned fees are neither withdrawable nor refundable.
+    /// - Emits a `FeeModeChanged` event.
+    pub fn set_fee_mode(ctx: Context<Admin>, fee_mode: FeeMode) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            ctx.accounts.vote_data.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+        instructions::set_vote_fee_mode(ctx, fee_mode)
+    }
+
+    /// Sets whether `retract_vote` refunds the fees of retracted votes or the treasury keeps them.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can change it, and only between rounds, while the round is `Pending`, so
+    ///   every retraction of a round is treated the same way.
+    /// - Emits a `RetractRefundChanged` event.
+    pub fn set_retract_refund(ctx: Context<Admin>, retract_refund: bool) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            ctx.accounts.vote_data.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+        instructions::set_vote_retract_refund(ctx, retract_refund)
+    }
+
+    /// Pauses or resumes the VoteManager, containing incidents without a redeploy.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can pause.
+    /// - While paused, every way of voting, adding or submitting projects and
+    ///   `ensure_user_can_vote` are rejected; round administration, closing accounts, refunds and
+    ///   reads keep working.
+    /// - Not part of the round's rules, so pausing does not block `finalize_round`.
+    /// - Emits a `PausedChanged` event.
+    pub fn set_paused(ctx: Context<Admin>, paused: bool) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::set_vote_paused(ctx, paused)
+    }
+
+    /// Switches stealth voting.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch the mode.
+    /// - When enabled, voters may record votes with `stealth_vote` next to `do_vote`.
+    pub fn set_stealth_voting(ctx: Context<Admin>, stealth_voting: bool) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::set_vote_stealth_voting(ctx, stealth_voting)
+    }
+
+    /// Switches the vote receipts minted by `do_vote`.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch receipts.
+    /// - Records the receipt mint, created with ttt_token's `create_receipt_mint` and minted by
+    ///   the VoteManager's receipt authority PDA. Set a new mint each round to give every round
+    ///   its own "I voted in round N" souvenir.
+    /// - The mint must be non-transferable and have 0 decimals.
+    pub fn set_vote_receipts(ctx: Context<SetVoteReceipts>, vote_receipts: bool) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        instructions::set_vote_receipt_minting(ctx, vote_receipts)
+    }
+
+    /// Creates the treasury that collects the voting fees.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can create the treasury.
+    /// - Creates the Treasury PDA and its token account for the governance mint.
+    /// - Must run before the first vote, since votes pay their fee into the treasury.
+    pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::init_vote_treasury(ctx)
+    }
+
+    /// Withdraws collected voting fees from the treasury.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can withdraw.
+    /// - Transfers `amount` to any token account of the governance mint, signed by the Treasury
+    ///   PDA.
+    /// - Project submission deposits held in escrow cannot be withdrawn, nor the fees owed to
+    ///   voters: the current round's, which retractions refund and finalization shares among the
+    ///   winner's voters, and those of ended rounds not yet claimed as rewards or refunds.
+    /// - Emits a `TreasuryWithdrawn` event.
+    pub fn withdraw_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawTreasury<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(amount > 0, VoteError::IncorrectWithdrawAmount);
+        let available = instructions::treasury_available(
+            &ctx.accounts.treasury,
+            &ctx.accounts.vote_manager,
+            ctx.accounts.treasury_token_account.amount,
+        );
+        require!(amount <= available, VoteError::InsufficientTreasuryBalance);
+
+        instructions::withdraw_from_treasury(ctx, amount)
+    }
+
+    /// Withdraws voting fees paid in SOL from the treasury to the admin.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can withdraw.
+    /// - The Treasury PDA keeps its own rent exemption; only the balance above it can be
+    ///   withdrawn.
+    /// - Emits a `TreasurySolWithdrawn` event.
+    pub fn withdraw_treasury_sol(ctx: Context<WithdrawTreasurySol>, amount: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(amount > 0, VoteError::IncorrectWithdrawAmount);
+        require!(
+            amount <= instructions::treasury_sol_available(&ctx.accounts.treasury)?,
+            VoteError::InsufficientTreasuryBalance
+        );
+
+        instructions::withdraw_sol_from_treasury(ctx, amount)
+    }
+
+    /// Creates the sponsor vault reimbursing first-time voters' rent.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can create the vault.
+    /// - `max_per_wallet` caps the lamports one wallet can be reimbursed over its lifetime.
+    pub fn init_sponsor_vault(ctx: Context<InitSponsorVault>, max_per_wallet: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::init_vote_sponsor_vault(ctx, max_per_wallet)
+    }
+
+    /// Deposits SOL into the sponsor vault.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can fund the vault.
+    /// - Emits a `SponsorVaultFunded` event.
+    pub fn fund_sponsor_vault(ctx: Context<ManageSponsorVault>, amount: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(amount > 0, VoteError::IncorrectSponsorAmount);
+
+        instructions::fund_vote_sponsor_vault(ctx, amount)
+    }
+
+    /// Withdraws SOL from the sponsor vault to the admin.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can withdraw.
+    /// - The vault keeps its own rent exemption; only the balance above it can be withdrawn.
+    /// - Emits a `SponsorVaultWithdrawn` event.
+    pub fn withdraw_sponsor_vault(ctx: Context<ManageSponsorVault>, amount: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(amount > 0, VoteError::IncorrectSponsorAmount);
+        require!(
+            amount <= instructions::sponsor_vault_available(&ctx.accounts.sponsor_vault)?,
+            VoteError::InsufficientSponsorFunds
+        );
+
+        instructions::withdraw_vote_sponsor_vault(ctx, amount)
+    }
+
+    /// Sets the lifetime reimbursement limit of a single wallet.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the limit.
+    /// - Wallets already above a lowered limit receive no further reimbursements.
+    pub fn set_sponsor_limit(ctx: Context<ManageSponsorVault>, max_per_wallet: u64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::set_vote_sponsor_limit(ctx, max_per_wallet)
+    }
+
+    /// Casts a vote whose account rent is reimbursed from the sponsor vault.
+    ///
+    /// **Business Logic:**
+    /// - Applies the same checks, fee and tallies as `do_vote`.
+    /// - Pays back the rent of the VoterData (and SponsorRecord) accounts the vote creates, so
+    ///   first-time voters only need SOL for the duration of the transaction.
+    /// - Fails with `SponsorLimitReached` once the wallet's reimbursements would exceed
+    ///   `max_per_wallet`; such voters can still use `do_vote`.
+    /// - Disabled while an eligibility snapshot, a holding requirement or an access list is set.
+    /// - Emits a `VoteCast` event and, when rent is reimbursed, a `VoteSponsored` event.
+    pub fn sponsored_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SponsoredVoter<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.vote_manager.eligibility_snapshot(),
+            VoteError::EligibilitySnapshotMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.holding_requirement(),
+            VoteError::HoldingRequirementMode
+        );
+        require!(
+            ctx.accounts.vote_manager.access_mode == AccessMode::Open,
+            VoteError::AccessListMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.escrow_mode,
+            VoteError::EscrowMode
+        );
+        check_can_vote(
+            &ctx.accounts.vote_manager,
+            &instructions::read_voter_data(&ctx.accounts.voter_data)?,
+            &ctx.accounts.round_votes,
+            FeeCurrency::Ttt,
+            ctx.accounts.token.amount,
+        )?;
+        instructions::_sponsored_vote(ctx)
+    }
+
+    /// Casts a vote sent by a relayer on the voter's ed25519-signed authorization, so wallets
+    /// without SOL can vote.
+    ///
+    /// **Business Logic:**
+    /// - The transaction must verify the voter's signature of `vote_authorization_message(voter,
+    ///   project, round, nonce)` with an Ed25519 program instruction placed right before this one.
+    /// - `nonce` must be the voter's next one, kept in their VoteNonce PDA; each relayed vote
+    ///   consumes it.
+    /// - Applies the same checks, weight and tallies as `do_vote`; the TTT fee comes out of the
+    ///   allowance the voter's token account delegated to the VoteManager PDA.
+    /// - The relayer pays the transaction fee and the rent of the accounts the vote creates.
+    /// - Disabled while an eligibility snapshot, a holding requirement, an access list or escrow
+    ///   mode is set.
+    /// - Emits a `VoteCast` and a `VoteRelayed` event.
+    pub fn vote_with_authorization<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AuthorizedVoter<'info>>,
+        nonce: u64,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.vote_manager.eligibility_snapshot(),
+            VoteError::EligibilitySnapshotMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.holding_requirement(),
+            VoteError::HoldingRequirementMode
+        );
+        require!(
+            ctx.accounts.vote_manager.access_mode == AccessMode::Open,
+            VoteError::AccessListMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.escrow_mode,
+            VoteError::EscrowMode
+        );
+        require!(
+            nonce == ctx.accounts.vote_nonce.next_nonce,
+            VoteError::StaleVoteNonce
+        );
+        check_can_vote(
+            &ctx.accounts.vote_manager,
+            &instructions::read_voter_data(&ctx.accounts.voter_data)?,
+            &ctx.accounts.round_votes,
+            FeeCurrency::Ttt,
+            ctx.accounts.token.amount,
+        )?;
+        instructions::_vote_with_authorization(ctx, nonce)
+    }
+
+    /// Sets up compressed votes, recorded as leaves of a concurrent Merkle tree instead of
+    /// VoterData accounts.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can set up the VoteTree, once per VoteManager.
+    /// - The transaction first allocates `merkle_tree`, owned by the spl-account-compression
+    ///   program and sized for `max_depth` and `max_buffer_size`; the tree holds up to
+    ///   `2^max_depth` votes.
+    /// - Emits a `VoteTreeInitialized` event.
+    pub fn init_vote_tree(
+        ctx: Context<InitVoteTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::init_compressed_vote_tree(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Casts a vote recorded as a leaf of the VoteManager's Merkle tree, so it costs no rent.
+    ///
+    /// **Business Logic:**
+    /// - Applies the same round checks, weight, fee and tallies as `do_vote`, the fee paid in TTT.
+    /// - Keeps no per-voter account, so `max_votes_per_round` is not enforced and the vote is
+    ///   priced as the voter's first; disabled under quadratic pricing or `max_votes_per_project`.
+    /// - Disabled while an eligibility snapshot, a holding requirement, an access list or escrow
+    ///   mode is set.
+    /// - Compressed votes cannot be retracted or refunded; the voters of a round's winner claim
+    ///   their reward with `claim_compressed_reward`.
+    /// - Emits a `VoteCast` and a `CompressedVoteRecorded` event.
+    pub fn compressed_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompressedVoter<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.vote_manager.eligibility_snapshot(),
+            VoteError::EligibilitySnapshotMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.holding_requirement(),
+            VoteError::HoldingRequirementMode
+        );
+        require!(
+            ctx.accounts.vote_manager.access_mode == AccessMode::Open,
+            VoteError::AccessListMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.escrow_mode,
+            VoteError::EscrowMode
+        );
+        require!(
+            !ctx.accounts.vote_manager.quadratic_pricing
+                && ctx.accounts.vote_manager.max_votes_per_project == 0,
+            VoteError::CompressedVotesUnsupported
+        );
+        check_can_cast(
+            &ctx.accounts.vote_manager,
+            FeeCurrency::Ttt,
+            0,
+            ctx.accounts.token.amount,
+        )?;
+        instructions::_compressed_vote(ctx)
+    }
+
+    /// Proves that a compressed vote is recorded at `index` of the VoteManager's Merkle tree.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can verify a vote, passing the proof's sibling nodes as remaining accounts and a
+    ///   `root` among the tree's recent ones.
+    /// - Fails unless the proof links the vote's leaf to `root`.
+    /// - Emits a `CompressedVoteVerified` event.
+    pub fn verify_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, VerifyVote<'info>>,
+        vote: CompressedVoteRecord,
+        root: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        instructions::verify_compressed_vote(ctx, vote, root, index)
+    }
+
+    /// Pays the voter of a compressed vote for a finalized round's winner their share of the
+    /// round's fees.
+    ///
+    /// **Business Logic:**
+    /// - Only the voter can claim, proving their vote at `index` of the tree like `verify_vote`.
+    /// - Shares the round's `reward_pool` like `claim_reward`, pro rata to the vote's weight.
+    /// - Each vote is rewarded once: its leaf is replaced with a claimed one, so the unclaimed
+    ///   leaf no longer proves.
+    /// - Emits a `VoteRewardClaimed` and a `CompressedVoteRecorded` event.
+    pub fn claim_compressed_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimCompressedReward<'info>>,
+        vote: CompressedVoteRecord,
+        root: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        instructions::claim_compressed_vote_reward(ctx, vote, root, index)
+    }
+
+    /// Sets how long rewards stay claimable after a round is finalized.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the window.
+    /// - Each reward vault keeps the window in effect when it was created.
+    pub fn set_reward_claim_window(ctx: Context<Admin>, reward_claim_window: i64) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(reward_claim_window >= 0, VoteError::IncorrectClaimWindow);
+
+        instructions::set_vote_reward_claim_window(ctx, reward_claim_window)
+    }
+
+    /// Funds the reward vault of a round.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can fund rewards.
+    /// - Only the current or a future round can be funded, and not once the vault was swept.
+    /// - Creates the per-round RewardVault PDA and its token account on first funding.
+    /// - Emits a `RewardVaultFunded` event.
+    pub fn fund_reward_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FundRewardVault<'info>>,
+        round: u64,
+        amount: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(amount > 0, VoteError::IncorrectRewardAmount);
+
+        instructions::fund_vote_reward_vault(ctx, round, amount)
+    }
+
+    /// Returns a round's unclaimed rewards to the admin.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can sweep.
+    /// - Only allowed once the round is finalized and its claim deadline has passed.
+    /// - A vault can be swept once; afterwards it accepts neither funding nor claims.
+    /// - Emits a `RewardsSwept` event.
+    pub fn sweep_unclaimed_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepUnclaimedRewards<'info>>,
+        round: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(!ctx.accounts.reward_vault.swept, VoteError::RewardsSwept);
+
+        let deadline = ctx
+            .accounts
+            .reward_vault
+            .claim_deadline(ctx.accounts.round_result.finalized_at);
+        require!(
+            Clock::get()?.unix_timestamp > deadline,
+            VoteError::ClaimWindowOpen
+        );
+
+        instructions::sweep_unclaimed_vote_rewards(ctx, round)
+    }
+
+    /// Sets the deposit, voting period and quorum of new proposals.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can modify the proposal configuration.
+    /// - The voting period must be positive so proposals can be voted on at all.
+    pub fn set_proposal_config(
+        ctx: Context<Admin>,
+        proposal_deposit: u64,
+        proposal_voting_period: i64,
+        proposal_quorum: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            proposal_voting_period > 0,
+            VoteError::IncorrectProposalConfig
+        );
+
+        instructions::set_vote_proposal_config(
+            ctx,
+            proposal_deposit,
+            proposal_voting_period,
+            proposal_quorum,
+        )
+    }
+
+    /// Creates a proposal holding instructions to execute once TTT holders approve it.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can propose by locking the VoteManager's `proposal_deposit`.
+    /// - Between one and `PROPOSAL_MAX_INSTRUCTIONS` instructions, each within the account and
+    ///   data limits.
+    /// - Emits a `ProposalCreated` event.
+    pub fn create_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateProposal<'info>>,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<()> {
+        check_stored_instructions(&instructions)?;
+
+        instructions::create_governance_proposal(ctx, instructions)
+    }
+
+    /// Votes for or against a proposal.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed until the proposal's voting deadline.
+    /// - Locks `amount` of the voter's TTT until the deadline; the weight curve is applied to it.
+    /// - Each voter can vote once per proposal.
+    /// - Emits a `ProposalVoted` event.
+    pub fn vote_on_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProposalVoter<'info>>,
+        approve: bool,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp <= ctx.accounts.proposal.voting_ends_at,
+            VoteError::ProposalVotingClosed
+        );
+        require!(
+            amount > 0 && amount <= ctx.accounts.token.amount,
+            VoteError::InsufficientTokens
+        );
+
+        instructions::vote_on_governance_proposal(ctx, approve, amount)
+    }
+
+    /// Returns the TTT a proposal vote locked.
+    ///
+    /// **Business Logic:**
+    /// - Only the voter can release, once the proposal's voting deadline has passed.
+    /// - Returns the TTT and closes the ballot and its vault.
+    /// - Emits a `ProposalVoteReleased` event.
+    pub fn release_proposal_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReleaseProposalVote<'info>>,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > ctx.accounts.proposal_vote.unlocks_at,
+            VoteError::ProposalVotingOpen
+        );
+
+        instructions::release_proposal_vote_tokens(ctx)
+    }
+
+    /// Executes a passed proposal's instructions.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can execute once the voting deadline has passed.
+    /// - The proposal must have passed and can be executed only once.
+    /// - Refunds the deposit and CPIs the stored instructions signed by the governance authority.
+    /// - Emits a `ProposalExecuted` event.
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > ctx.accounts.proposal.voting_ends_at,
+            VoteError::ProposalVotingOpen
+        );
+        require!(
+            !ctx.accounts.proposal.executed,
+            VoteError::ProposalExecuted
+        );
+        require!(ctx.accounts.proposal.passed(), VoteError::ProposalNotPassed);
+
+        instructions::execute_governance_proposal(ctx)
+    }
+
+    /// Closes a finished proposal.
+    ///
+    /// **Business Logic:**
+    /// - Anyone can close once the voting deadline has passed.
+    /// - A passed proposal must be executed first; a failed one forfeits its deposit to the
+    ///   treasury.
+    /// - Returns the rent of the proposal and its token account to the proposer.
+    pub fn close_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseProposal<'info>>,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > ctx.accounts.proposal.voting_ends_at,
+            VoteError::ProposalVotingOpen
+        );
+        require!(
+            ctx.accounts.proposal.executed || !ctx.accounts.proposal.passed(),
+            VoteError::ProposalNotExecuted
+        );
+
+        instructions::close_governance_proposal(ctx)
+    }
+
+    /// Creates a referendum putting a yes/no question to TTT holders next to the project rounds.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can create referenda.
+    /// - `question_uri` points at the off-chain document stating the question, at most
+    ///   `REFERENDUM_URI_MAX_LEN` bytes.
+    /// - `deadline` is the unix timestamp after which votes are rejected and must be in the
+    ///   future.
+    /// - The referendum keeps the VoteManager's current `vote_fee` and `weight_curve`; changing
+    ///   them later does not affect it.
+    /// - Independent of the round lifecycle: referenda are voted on in pending, open and
+    ///   finalized rounds alike.
+    /// - Emits a `ReferendumCreated` event.
+    pub fn create_referendum(
+        ctx: Context<CreateReferendum>,
+        question_uri: String,
+        deadline: i64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(
+            question_uri.len() <= REFERENDUM_URI_MAX_LEN && deadline > Clock::get()?.unix_timestamp,
+            VoteError::IncorrectReferendum
+        );
+
+        instructions::create_vote_referendum(ctx, question_uri, deadline)
+    }
+
+    /// Answers a referendum with yes, no or abstain.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed until the referendum's deadline, and not while the VoteManager is paused.
+    /// - Each voter can answer once per referendum.
+    /// - Weighed like `do_vote`: on the referendum's weight curve applied to the balance, by the
+    ///   `proof` of the voter's snapshot entry while an eligibility snapshot is set, or by the
+    ///   voter's StakePosition, passed as the first remaining account, in escrow mode.
+    /// - Charges the referendum's fee under the VoteManager's `fee_mode`, collected by the
+    ///   treasury or burned.
+    /// - Abstentions count as turnout for neither side.
+    /// - Emits a `ReferendumVoted` event.
+    pub fn vote_referendum<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReferendumVoter<'info>>,
+        choice: ReferendumChoice,
+        proof: Option<EligibilityProof>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.vote_manager.paused, VoteError::Paused);
+        require!(
+            Clock::get()?.unix_timestamp <= ctx.accounts.referendum.deadline,
+            VoteError::ReferendumClosed
+        );
+        require!(
+            ctx.accounts.token.amount >= ctx.accounts.referendum.vote_fee,
+            VoteError::InsufficientTokens
+        );
+        let snapshot_weight = instructions::check_eligibility(
+            &ctx.accounts.vote_manager,
+            &ctx.accounts.signer.key(),
+            proof.as_ref(),
+        )?;
+        let stake_weight = instructions::stake_weight(
+            &ctx.accounts.vote_manager,
+            &ctx.accounts.signer.key(),
+            ctx.remaining_accounts.first(),
+        )?;
+        instructions::vote_on_referendum(ctx, choice, snapshot_weight.or(stake_weight))
+    }
+
+    /// Commits a hidden vote in commit–reveal mode.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed while the round is `Open` and its voting window is open.
+    /// - Stores `hash(voter || project_id || salt)` in a per-voter VoteCommit PDA, hiding the
+    ///   choice; the voter's key keeps another wallet from copying the commitment and reusing the
+    ///   revealed salt.
+    /// - Transfers the voting fee and fixes the vote weight at commit time.
+    /// - Disabled while an eligibility snapshot, a holding requirement or an access list is set.
+    pub fn commit_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CommitVote<'info>>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(!vote_manager.paused, VoteError::Paused);
+        require!(vote_manager.commit_reveal, VoteError::CommitRevealDisabled);
+        require!(
+            !vote_manager.eligibility_snapshot(),
+            VoteError::EligibilitySnapshotMode
+        );
+        require!(
+            !vote_manager.holding_requirement(),
+            VoteError::HoldingRequirementMode
+        );
+        require!(
+            vote_manager.access_mode == AccessMode::Open,
+            VoteError::AccessListMode
+        );
+        require!(!vote_manager.escrow_mode, VoteError::EscrowMode);
+
+        // Ensure the voter has enough tokens to cover the voting fee.
+        require!(
+            ctx.accounts.token.amount >= vote_manager.round_config.vote_fee,
+            VoteError::InsufficientTokens
+        );
+
+        // Commitments are only accepted in an open round, between its start and deadline.
+        require!(
+            vote_manager.round_state == RoundState::Open,
+            VoteError::RoundNotOpen
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= vote_manager.round_start_ts,
+            VoteError::RoundNotStarted
+        );
+        let deadline = vote_manager
+            .round_deadline()
+            .ok_or(VoteError::IncorrectRoundSchedule)?;
+        require!(now <= deadline, VoteError::RoundEnded);
+
+        instructions::commit_hidden_vote(ctx, commitment)
+    }
+
+    /// Reveals a committed vote and adds it to the project's tally.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed during the reveal window, after the round deadline.
+    /// - Verifies that `hash(voter || project.id || salt)` matches the stored commitment.
+    /// - Each commitment can be revealed once.
+    /// - Emits a `VoteCast` event.
+    pub fn reveal_vote(ctx: Context<RevealVote>, salt: [u8; 32]) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        let vote_commit = &ctx.accounts.vote_commit;
+
+        // Ensure the reveal window is open.
+        let now = Clock::get()?.unix_timestamp;
+        let (deadline, reveal_end) = vote_manager
+            .round_deadline()
+            .zip(vote_manager.tally_deadline())
+            .ok_or(VoteError::NotRevealWindow)?;
+        require!(
+            now > deadline && now <= reveal_end,
+            VoteError::NotRevealWindow
+        );
+
+        require!(!vote_commit.revealed, VoteError::AlreadyRevealed);
+        require!(
+            instructions::vote_commitment(
+                &vote_commit.voter,
+                ctx.accounts.project.load()?.id(),
+                &salt
+            ) == vote_commit.commitment,
+            VoteError::InvalidReveal
+        );
+
+        instructions::reveal_hidden_vote(ctx)
+    }
+
+    /// Switches the commit–reveal voting mode.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can switch the mode.
+    /// - When enabled, `do_vote` is disabled and votes go through `commit_vote` / `reveal_vote`.
+    /// - The reveal window lasts `reveal_duration` seconds after the round deadline.
+    /// - Cannot be enabled together with ranked-choice voting or the `Runoff` tie-break policy.
+    pub fn set_commit_reveal(
+        ctx: Context<Admin>,
+        commit_reveal: bool,
+        reveal_duration: i64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_data.admin, &ctx.accounts.owner.key())?;
+        require!(
+            !commit_reveal || reveal_duration > 0,
+            VoteError::IncorrectRoundSchedule
+        );
+        require!(
+            !commit_reveal || !ctx.accounts.vote_data.ranked_choice,
+            VoteError::RankedChoiceMode
+        );
+        require!(
+            !commit_reveal || ctx.accounts.vote_data.tie_break != TieBreakPolicy::Runoff,
+            VoteError::IncorrectTieBreak
+        );
+
+        instructions::set_vote_commit_reveal(ctx, commit_reveal, reveal_duration)
+    }
+
+    /// Finalizes the current voting round and records its result.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless: anyone can call it once the round deadline (and, in commit–reveal mode,
+    ///   the reveal window) has passed.
+    /// - Rejects sealing if the economic parameters differ from the `round_config` hash recorded
+    ///   when the round opened; such a round can only be closed with `increment_round`.
+    /// - In ranked-choice mode only rounds without ballots are sealed here, the others are sealed
+    ///   by `tally_ranked_round`.
+    /// - A tie for the lead is decided by the `tie_break` policy; under `Runoff`, the round must
+    ///   go through `open_runoff` first.
+    /// - Snapshots total votes, the winning project, and participation into a `RoundResult` PDA,
+    ///   with the weighted turnout against the eligibility snapshot's total weight.
+    /// - Creates the round's `RoundArchive`, archiving the ProjectData accounts passed as remaining
+    ///   accounts; `archive_projects` archives those that did not fit.
+    /// - When a disqualification or a retraction cleared the running leader, the remaining
+    ///   accounts must hold every project of the round in ascending address order, and the
+    ///   leader is recounted from them; `tally_page` handles rounds too large for one transaction.
+    /// - Records the winner of each project category in the archive, by weight and ties by id,
+    ///   from the projects archived; the category winners are final once the archive is complete.
+    /// - Increments `vote_round` atomically with the snapshot; the new round is `Pending`.
+    /// - Emits a `RoundFinalized` event, and a `RoundStateChanged` event marking the round
+    ///   `Finalized`.
+    pub fn finalize_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeRound<'info>>,
+    ) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(
+            !vote_manager.ranked_choice || vote_manager.round_participants == 0,
+            VoteError::RankedChoiceMode
+        );
+
+        // Ensure the round is time-boxed and its tally is final.
+        let deadline = vote_manager
+            .tally_deadline()
+            .ok_or(VoteError::RoundNotEnded)?;
+        require!(
+            Clock::get()?.unix_timestamp > deadline,
+            VoteError::RoundNotEnded
+        );
+
+        // Ensure the rules did not change since the round opened.
+        require!(
+            vote_manager.config_hash() == vote_manager.round_config.config_hash,
+            VoteError::ConfigChanged
+        );
+
+        // Recount a leader cleared by a disqualification or a retraction.
+        if vote_manager.leader_unknown() {
+            let vote_manager_key = vote_manager.key();
+            instructions::recount_round_leader(
+                &mut ctx.accounts.vote_manager,
+                vote_manager_key,
+                ctx.remaining_accounts,
+            )?;
+        }
+        let vote_manager = &ctx.accounts.vote_manager;
+
+        // Ensure a tie the policy settles with a runoff had it.
+        require!(!vote_manager.runoff_due(), VoteError::RunoffRequired);
+
+        // Ensure the passed winner is the round's leading project.
+        match &ctx.accounts.winner {
+            Some(winner) => require_keys_eq!(
+                winner.key(),
+                vote_manager.round_leader,
+                VoteError::WrongWinner
+            ),
+            None => require_keys_eq!(
+                vote_manager.round_leader,
+                Pubkey::default(),
+                VoteError::WrongWinner
+            ),
+        }
+
+        instructions::finalize_vote_round(ctx)
+    }
+
+    /// Archives a page of a finalized round's projects, keeping their final vote counts once the
+    /// ProjectData accounts are closed.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless: the entries are read from the ProjectData accounts passed as remaining
+    ///   accounts, which must belong to the archived round.
+    /// - Entries stay ordered by vote count, most first, whatever the pages; a project is only
+    ///   archived once.
+    /// - Each project may take the lead of its category's winners.
+    /// - The signer pays for the archive's growth.
+    /// - Emits a `ProjectsArchived` event.
+    pub fn archive_projects<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ArchiveProjects<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.remaining_accounts.is_empty(),
+            VoteError::IncorrectProjectAccounts
+        );
+        instructions::archive_round_projects(ctx)
+    }
+
+    /// Counts a page of the current round's projects towards its winner, for rounds with more
+    /// projects than one transaction can read.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless, like `finalize_round`, once the round deadline (and, in commit–reveal
+    ///   mode, the reveal window) has passed; not for ranked rounds with ballots.
+    /// - `count` ProjectData accounts are passed as remaining accounts, in ascending address order
+    ///   across pages; `start_index` is the number of projects already counted.
+    /// - Partial maxima accumulate in the round's `TallyState` PDA, created by the first page.
+    /// - Emits a `TallyPageCounted` event.
+    pub fn tally_page<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TallyPage<'info>>,
+        start_index: u16,
+        count: u16,
+    ) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(
+            !vote_manager.ranked_choice || vote_manager.round_participants == 0,
+            VoteError::RankedChoiceMode
+        );
+        require!(
+            count > 0 && usize::from(count) == ctx.remaining_accounts.len(),
+            VoteError::IncorrectProjectAccounts
+        );
+
+        // Ensure the round is time-boxed and its tally is final.
+        let deadline = vote_manager
+            .tally_deadline()
+            .ok_or(VoteError::RoundNotEnded)?;
+        require!(
+            Clock::get()?.unix_timestamp > deadline,
+            VoteError::RoundNotEnded
+        );
+
+        instructions::count_tally_page(ctx, start_index)
+    }
+
+    /// Finalizes the current round with the winner found by its paginated tally.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless, once `tally_page` counted every project of the round since its deadline
+    ///   last moved.
+    /// - Rejects sealing if the economic parameters differ from the `round_config` hash recorded
+    ///   when the round opened.
+    /// - Writes the tally's winner into the VoteManager and seals the round into its `RoundResult`
+    ///   and `RoundArchive`, like `finalize_round`; a tie under `Runoff` goes to `open_runoff`.
+    /// - Closes the `TallyState`, paying its rent to the signer.
+    /// - Emits a `RoundFinalized` event, and a `RoundStateChanged` event marking the round
+    ///   `Finalized`.
+    pub fn tally_commit(ctx: Context<TallyCommit>) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        let tally = &ctx.accounts.tally_state;
+        require!(
+            tally.counted == vote_manager.project_count
+                && Some(tally.deadline) == vote_manager.tally_deadline(),
+            VoteError::TallyIncomplete
+        );
+        require!(
+            Clock::get()?.unix_timestamp > tally.deadline,
+            VoteError::RoundNotEnded
+        );
+
+        // Ensure the rules did not change since the round opened.
+        require!(
+            vote_manager.config_hash() == vote_manager.round_config.config_hash,
+            VoteError::ConfigChanged
+        );
+
+        instructions::commit_round_tally(ctx)
+    }
+
+    /// Sends the projects tied for the lead of an ended round to a runoff.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless, like `finalize_round`, once the round deadline has passed and the rules
+    ///   are unchanged.
+    /// - Only for a tied round under the `Runoff` tie-break policy, once per round; a runoff ending
+    ///   tied again is decided like `EarliestCreated`.
+    /// - Extends the round by `runoff_duration` seconds, reopening it if it was closed, and only
+    ///   counts votes for the projects holding the tied weight until it is finalized.
+    /// - When a disqualification or a retraction cleared the running leader, it is recounted
+    ///   from every project of the round passed as remaining accounts, like `finalize_round`.
+    /// - Emits a `RunoffOpened` event.
+    pub fn open_runoff<'info>(ctx: Context<'_, '_, 'info, 'info, OpenRunoff<'info>>) -> Result<()> {
+        if ctx.accounts.vote_manager.leader_unknown() {
+            let vote_manager_key = ctx.accounts.vote_manager.key();
+            instructions::recount_round_leader(
+                &mut ctx.accounts.vote_manager,
+                vote_manager_key,
+                ctx.remaining_accounts,
+            )?;
+        }
+        let vote_manager = &ctx.accounts.vote_manager;
+        let deadline = vote_manager
+            .tally_deadline()
+            .ok_or(VoteError::RoundNotEnded)?;
+        require!(
+            Clock::get()?.unix_timestamp > deadline,
+            VoteError::RoundNotEnded
+        );
+        require!(
+            vote_manager.config_hash() == vote_manager.round_config.config_hash,
+            VoteError::ConfigChanged
+        );
+        require!(vote_manager.runoff_due(), VoteError::NoRunoffDue);
+
+        instructions::open_round_runoff(ctx)
+    }
+
+    /// Voids the current round for incident response, e.g. after a vote-buying attack or a
+    /// misconfigured round.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can invalidate a round, whatever its state, with a `reason` of 1 to
+    ///   `INVALID_REASON_MAX_LEN` bytes.
+    /// - Records the round's tallies in its `RoundResult`, flagged `invalidated` with the reason and
+    ///   without a winner, so the round can no longer be finalized, tallied or attested.
+    /// - Every voter of the round can claim back their fees with `claim_refund`; no rewards are
+    ///   paid.
+    /// - Creates the round's `RoundArchive`, so its projects can be archived and closed.
+    /// - Increments `vote_round`; the new round is `Pending`.
+    /// - Emits a `RoundInvalidated` event.
+    pub fn invalidate_round(ctx: Context<InvalidateRound>, reason: String) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(
+            !reason.is_empty() && reason.len() <= INVALID_REASON_MAX_LEN,
+            VoteError::IncorrectInvalidReason
+        );
+
+        instructions::invalidate_vote_round(ctx, reason)
+    }
+
+    /// Records the admin's ed25519 signature over a finalized round result.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can attest a round, and only once.
+    /// - The transaction must verify the signature with an Ed25519 program instruction placed
+    ///   right before this one, signing `RoundResult::canonical_json` with the admin key.
+    /// - Emits a `RoundAttested` event.
+    pub fn attest_round(ctx: Context<AttestRound>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        instructions::attest_round_result(ctx)
+    }
+
+    /// Pre-registers a campaign of scheduled rounds with start and end timestamps and themes.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can create a campaign, and only one exists at a time.
+    /// - Rounds must start before they end and must not overlap, in order, with themes up to
+    ///   `CAMPAIGN_THEME_MAX_LEN` bytes.
+    /// - Emits a `CampaignCreated` event.
+    pub fn create_campaign(ctx: Context<CreateCampaign>, rounds: Vec<CampaignRound>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(
+            !rounds.is_empty() && rounds.len() <= CAMPAIGN_MAX_ROUNDS,
+            VoteError::IncorrectCampaign
+        );
+        for (i, round) in rounds.iter().enumerate() {
+            require!(
+                round.start_ts >= 0
+                    && round.start_ts < round.end_ts
+                    && round.theme.len() <= CAMPAIGN_THEME_MAX_LEN,
+                VoteError::IncorrectCampaign
+            );
+            if i > 0 {
+                require!(
+                    round.start_ts >= rounds[i - 1].end_ts,
+                    VoteError::IncorrectCampaign
+                );
+            }
+        }
+
+        instructions::create_vote_campaign(ctx, rounds)
+    }
+
+    /// Opens the campaign's next scheduled round on the current voting round.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless, so the crank can open the rounds on schedule.
+    /// - The previous campaign round must have been closed by `finalize_round` or
+    ///   `increment_round`, and the current round must not have any votes yet.
+    /// - Votes are rejected until the scheduled start, so the round can be opened early.
+    /// - Only applies the schedule: the round stays `Pending` for its projects to be added, and
+    ///   the admin opens it for voting with `open_round`.
+    pub fn open_campaign_round(ctx: Context<OpenCampaignRound>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(!campaign.finished(), VoteError::CampaignFinished);
+        if campaign.next_round > 0 {
+            require!(
+                vote_manager.vote_round > campaign.last_opened_round,
+                VoteError::CampaignRoundActive
+            );
+        }
+        require!(
+            vote_manager.round_total_votes == 0 && vote_manager.round_participants == 0,
+            VoteError::RoundHasVotes
+        );
+        require!(
+            vote_manager.round_state == RoundState::Pending,
+            VoteError::RoundNotPending
+        );
+
+        instructions::open_vote_campaign_round(ctx)
+    }
+
+    /// Closes the campaign, cancelling the rounds it has not opened yet.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can close the campaign, and receives its rent.
+    pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
+        instructions::close_vote_campaign(ctx)
+    }
+
+    /// Casts a ranked ballot for the current round.
+    ///
+    /// **Business Logic:**
+    /// - Only allowed in ranked-choice mode while the round is `Open`, between its start and
+    ///   deadline; the round must have a deadline so the tally knows when ballots are final.
+    /// - Ranks one to `RANKED_MAX_CHOICES` projects, passed as remaining accounts in order of
+    ///   preference.
+    /// - Validates that the voter has sufficient tokens to cover the voting fee, charged once per
+    ///   ballot; each voter casts one ballot per round.
+    /// - Disabled while an eligibility snapshot, a holding requirement or an access list is set.
+    /// - Emits a `BallotCast` event.
+    pub fn cast_ranked_ballot<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastRankedBallot<'info>>,
+    ) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(!vote_manager.paused, VoteError::Paused);
+        require!(vote_manager.ranked_choice, VoteError::RankedChoiceDisabled);
+        require!(
+            !vote_manager.eligibility_snapshot(),
+            VoteError::EligibilitySnapshotMode
+        );
+        require!(
+            !vote_manager.holding_requirement(),
+            VoteError::HoldingRequirementMode
+        );
+        require!(
+            vote_manager.access_mode == AccessMode::Open,
+            VoteError::AccessListMode
+        );
+        require!(!vote_manager.escrow_mode, VoteError::EscrowMode);
+        require!(
+            !ctx.remaining_accounts.is_empty()
+                && ctx.remaining_accounts.len() <= instructions::RANKED_MAX_CHOICES,
+            VoteError::IncorrectBallot
+        );
+
+        // Ensure the voter has enough tokens to cover the voting fee.
+        require!(
+            ctx.accounts.token.amount >= vote_manager.round_config.vote_fee,
+            VoteError::InsufficientTokens
+        );
+
+        // Ballots are only accepted in an open round, between its start and deadline.
+        require!(
+            vote_manager.round_state == RoundState::Open,
+            VoteError::RoundNotOpen
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= vote_manager.round_start_ts,
+            VoteError::RoundNotStarted
+        );
+        let deadline = vote_manager
+            .round_deadline()
+            .ok_or(VoteError::IncorrectRoundSchedule)?;
+        require!(now <= deadline, VoteError::RoundEnded);
+
+        instructions::cast_ranked_vote(ctx)
+    }
+
+    /// Runs the instant-runoff tally of a ranked round, one page of ballots at a time.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless: anyone can call it once the round deadline has passed.
+    /// - Each call counts the ballots passed as remaining accounts; callers resend every ballot
+    ///   until the tally eliminated enough candidates to find a majority winner.
+    /// - Rejects sealing if the economic parameters differ from the `round_config` hash recorded
+    ///   when the round opened.
+    /// - Passing `round_result`, `round_archive` and the winner seals the round into a
+    ///   `RoundResult`, like `finalize_round`, and increments `vote_round`; its projects are
+    ///   archived with `archive_projects`.
+    pub fn tally_ranked_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TallyRankedRound<'info>>,
+    ) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+
+        // Ensure no ballot can still be cast.
+        let deadline = vote_manager
+            .tally_deadline()
+            .ok_or(VoteError::RoundNotEnded)?;
+        require!(
+            Clock::get()?.unix_timestamp > deadline,
+            VoteError::RoundNotEnded
+        );
+
+        // Ensure the rules did not change since the round opened.
+        require!(
+            vote_manager.config_hash() == vote_manager.round_config.config_hash,
+            VoteError::ConfigChanged
+        );
+
+        instructions::tally_ranked_vote_round(ctx)
+    }
+
+    /// Closes a VoterData account of a finalized round and returns its rent to the voter.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless: anyone can sweep, the rent always goes back to the voter.
+    /// - Only allowed once the voter's round has a `RoundResult`.
+    /// - Records of disqualified projects can only be closed once their refund was claimed.
+    pub fn close_voter_data(ctx: Context<CloseVoterData>) -> Result<()> {
+        instructions::close_vote_voter_data(ctx)
+    }
+
+    /// Closes a stealth VoterData account of a finalized round and returns its rent to the voter.
+    ///
+    /// **Business Logic:**
+    /// - Only the voter can close it, proving ownership by revealing the `nonce` behind the
+    ///   blinded id.
+    /// - Only allowed once the voter's round has a `RoundResult`.
+    pub fn close_stealth_voter_data(
+        ctx: Context<CloseStealthVoterData>,
+        nonce: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            instructions::stealth_voter_id(&ctx.accounts.signer.key(), &nonce)
+                == ctx.accounts.voter_data.load()?.blinded_voter,
+            VoteError::InvalidOwnershipProof
+        );
+        instructions::close_stealth_vote_voter_data(ctx)
+    }
+
+    /// Closes a ProjectData account of a finalized round and returns its rent to its payer.
+    ///
+    /// **Business Logic:**
+    /// - Permissionless: anyone can sweep, the rent always goes back to the original payer.
+    /// - Only allowed once the project's round has a `RoundResult`, the project is in the round's
+    ///   `RoundArchive` and all of its VoterData accounts are closed.
+    /// - A listing bond's earned tranches must be claimed first; the rest of the bond is forfeited
+    ///   to the treasury.
+    pub fn close_project_data(ctx: Context<CloseProjectData>) -> Result<()> {
+        instructions::close_vote_project_data(ctx)
+    }
+
+    /// Funds the VoteManager's top-up pool, from which voters are topped up to pay their votes.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can fund the pool.
+    /// - Creates the pool, the VoteManager PDA's token account, on first funding.
+    /// - Emits a `TopUpsFunded` event.
+    pub fn fund_top_ups<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FundTopUps<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(amount > 0, VoteError::IncorrectTopUpAmount);
+
+        instructions::fund_top_up_pool(ctx, amount)
+    }
+
+    /// Tops up a voter's token account from the VoteManager's top-up pool so they can pay their
+    /// next vote.
+    ///
+    /// **Business Logic:**
+    /// - Only the voter signs: the VoteManager PDA authorizes the transfer, so the admin keypair
+    ///   does not need to be online for voting.
+    /// - `args.reason` must be a known `TopUpReason`; an `AccountOnly` call, for a vote paid in
+    ///   SOL, carries no amount and only creates the token account.
+    /// - Transfers only the shortfall between the voter's balance and `args.amount`, which may not
+    ///   exceed the price of the vote being funded, priced on the prior votes in `voter_data`.
+    /// - When a transfer is needed, the next instruction of the transaction must be the voter's
+    ///   vote of the kind `args.reason` names, paying in TTT, and the voter must not have been
+    ///   topped up in the round yet.
+    pub fn ensure_user_can_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EnsureCanVote<'info>>,
+        args: TopUpArgs,
+    ) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        require!(!vote_manager.paused, VoteError::Paused);
+        let reason = args.reason()?;
+        let prior_votes = instructions::prior_votes(&ctx.accounts.voter_data)?;
+        require!(
+            args.amount <= vote_manager.vote_price(prior_votes)?,
+            VoteError::TopUpTooLarge
+        );
+        // Set on the voter's first vote of the round, which creates the RoundVotes here.
+        ctx.accounts.round_votes.bump = ctx.bumps.round_votes;
+
+        let user_ttt_amount = ctx.accounts.user_ata.amount;
+
+        if user_ttt_amount >= args.amount {
+            return Ok(());
+        }
+        require!(
+            !ctx.accounts.round_votes.topped_up,
+            VoteError::AlreadyToppedUp
+        );
+
+        instructions::top_up_voter(ctx, args.amount - user_ttt_amount, reason)
+    }
+
+    /// Thaws a voter's token account, e.g. after KYC on a mint whose accounts start frozen.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can thaw accounts; the VoteManager PDA, the mint's freeze authority,
+    ///   signs the thaw.
+    /// - Emits a `VoterAccountThawed` event.
+    pub fn thaw_voter_account(ctx: Context<ThawVoterAccount>) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+
+        instructions::thaw_voter_token_account(ctx)
+    }
+
+    /// Withdraws tokens from the VoteManager's top-up pool.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can withdraw.
+    /// - Emits a `TopUpsWithdrawn` event.
+    pub fn withdraw_top_ups<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawTopUps<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        check_is_admin(&ctx.accounts.vote_manager.admin, &ctx.accounts.owner.key())?;
+        require!(amount > 0, VoteError::IncorrectTopUpAmount);
+
+        instructions::withdraw_from_top_ups(ctx, amount)
+    }
+}
+
+/// Checks that a paid vote may be recorded on `voter_data` and the voter's `round_votes`, the
+/// voter holding `balance` of the `fee_currency` they pay in.
+fn check_can_vote(
+    vote_manager: &VoteManager,
+    voter_data: &VoterData,
+    round_votes: &RoundVotes,
+    fee_currency: FeeCurrency,
+    balance: u64,
+) -> Result<()> {
+    check_can_cast(
+        vote_manager,
+        fee_currency,
+        voter_data.votes_for_project,
+        balance,
+    )?;
+
+    // Ensure the voter has not exhausted the per-round vote limit, across all projects.
+    require!(
+        round_votes.votes < u64::from(vote_manager.max_votes_per_round),
+        VoteError::AlreadyVoted
+    );
+
+    // Ensure the voter has not exhausted the per-project vote cap.
+    if vote_manager.max_votes_per_project > 0 {
+        require!(
+            voter_data.votes_for_project < vote_manager.max_votes_per_project,
+            VoteError::ProjectVoteCapReached
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that a paid vote may be cast in the open round, the voter holding `balance` of the
+/// `fee_currency` they pay in and having cast `prior_votes` for the project.
+fn check_can_cast(
+    vote_manager: &VoteManager,
+    fee_currency: FeeCurrency,
+    prior_votes: u16,
+    balance: u64,
+) -> Result<()> {
+    require!(!vote_manager.paused, VoteError::Paused);
+
+    // Direct votes would expose interim tallies in commit–reveal mode.
+    require!(!vote_manager.commit_reveal, VoteError::CommitRevealMode);
+    require!(!vote_manager.ranked_choice, VoteError::RankedChoiceMode);
+
+    // Ensure the admin opened the round and did not close it yet.
+    require!(
+        vote_manager.round_state == RoundState::Open,
+        VoteError::RoundNotOpen
+    );
+
+    // Ensure the voter can cover the price of this vote in the currency they pay in.
+    let price = vote_manager.vote_price_in(fee_currency, prior_votes)?;
+    match fee_currency {
+        FeeCurrency::Ttt => require!(balance >= price, VoteError::InsufficientTokens),
+        FeeCurrency::Sol => {
+            require!(
+                vote_manager.round_config.sol_vote_fee > 0,
+                VoteError::SolFeesDisabled
+            );
+            require!(balance >= price, VoteError::InsufficientLamports);
+        }
+    }
+
+    // Ensure the round has started and its deadline has not passed.
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= vote_manager.round_start_ts,
+        VoteError::RoundNotStarted
+    );
+    if let Some(deadline) = vote_manager.round_deadline() {
+        require!(now <= deadline, VoteError::RoundEnded);
+    }
+
+    Ok(())
+}
+
+/// Check if signer is Admin.
+fn check_is_admin(admin_key: &Pubkey, signer_key: &Pubkey) -> Result<()> {
+    require!(signer_key == admin_key, VoteError::NotAdmin);
+    Ok(())
+}
+
+/// Check that instructions to store in a proposal or admin action fit their account.
+fn check_stored_instructions(instructions: &[ProposalInstruction]) -> Result<()> {
+    require!(
+        !instructions.is_empty()
+            && instructions.len() <= PROPOSAL_MAX_INSTRUCTIONS
+            && instructions.iter().all(|ix| {
+                ix.accounts.len() <= PROPOSAL_MAX_ACCOUNTS && ix.data.len() <= PROPOSAL_MAX_DATA_LEN
+            }),
+        VoteError::InvalidProposal
+    );
+    Ok(())
+}
+
+/// Check if signer is Admin, or holds the Operator role in `role`, their RoleGrant.
+///
+/// The RoleGrant's seeds tie it to the VoteManager and the signer.
+fn check_is_operator(
+    admin_key: &Pubkey,
+    signer_key: &Pubkey,
+    role: Option<&RoleGrant>,
+) -> Result<()> {
+    if signer_key == admin_key {
+        return Ok(());
+    }
+    let grant = role.ok_or(VoteError::NotAdmin)?;
+    require!(grant.role == Role::Operator, VoteError::MissingRole);
     Ok(())
 }
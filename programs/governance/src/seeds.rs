@@ -0,0 +1,505 @@
+use anchor_lang::prelude::*;
+
+// PDA seeds shared by the program's account constraints and off-chain clients.
+//
+// Every PDA starts with its namespace, integers are encoded with `to_le_bytes`, and the canonical
+// bump is stored on the account, so constraints re-check it instead of searching for it again.
+//
+// The accounts of an election (VoteManager, projects and voters) also take the election seed.
+// Election 0 encodes to no bytes, which leaves the seeds, and so the addresses, of the single
+// election created before elections were numbered unchanged.
+
+pub const VOTE_MANAGER_SEED: &[u8] = b"vote_manager";
+pub const PROJECT_SEED: &[u8] = b"project";
+pub const VOTER_SEED: &[u8] = b"voter";
+pub const STEALTH_VOTER_SEED: &[u8] = b"stealth_voter";
+pub const ROUND_RESULT_SEED: &[u8] = b"round_result";
+pub const ROUND_ARCHIVE_SEED: &[u8] = b"round_archive";
+pub const TALLY_STATE_SEED: &[u8] = b"tally_state";
+pub const VOTE_COMMIT_SEED: &[u8] = b"vote_commit";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const REWARD_VAULT_SEED: &[u8] = b"reward_vault";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const PROPOSAL_VOTE_SEED: &[u8] = b"proposal_vote";
+pub const GOVERNANCE_AUTHORITY_SEED: &[u8] = b"governance_authority";
+pub const SPONSOR_VAULT_SEED: &[u8] = b"sponsor_vault";
+pub const SPONSOR_RECORD_SEED: &[u8] = b"sponsor_record";
+pub const ROUND_ATTESTATION_SEED: &[u8] = b"round_attestation";
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+pub const BALLOT_SEED: &[u8] = b"ballot";
+pub const RANKED_TALLY_SEED: &[u8] = b"ranked_tally";
+pub const RECEIPT_AUTHORITY_SEED: &[u8] = b"receipt_authority";
+pub const STAKE_POSITION_SEED: &[u8] = b"stake_position";
+pub const OPERATION_MARKER_SEED: &[u8] = b"operation_marker";
+pub const ROLE_SEED: &[u8] = b"role";
+pub const ADMIN_COUNCIL_SEED: &[u8] = b"admin_council";
+pub const ADMIN_ACTION_SEED: &[u8] = b"admin_action";
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+pub const REFERENDUM_SEED: &[u8] = b"referendum";
+pub const REFERENDUM_VOTE_SEED: &[u8] = b"referendum_vote";
+pub const HOLDING_ATTESTATION_SEED: &[u8] = b"holding_attestation";
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+pub const BLOCKLIST_SEED: &[u8] = b"blocklist";
+pub const FEE_DISCOUNT_SEED: &[u8] = b"fee_discount";
+pub const VOTE_NONCE_SEED: &[u8] = b"vote_nonce";
+pub const CATEGORY_REGISTRY_SEED: &[u8] = b"category_registry";
+pub const VOTE_TREE_SEED: &[u8] = b"vote_tree";
+pub const VOTE_LOCK_SEED: &[u8] = b"vote_lock";
+pub const ROUND_VOTES_SEED: &[u8] = b"round_votes";
+
+/// Encodes an election id as a seed: empty for election 0, `to_le_bytes` otherwise.
+pub fn election_seed(election_id: u64) -> Vec<u8> {
+    if election_id == 0 {
+        Vec::new()
+    } else {
+        election_id.to_le_bytes().to_vec()
+    }
+}
+
+/// Derives the VoteManager PDA: `[VOTE_MANAGER_SEED, creator, election]`.
+pub fn vote_manager_address(
+    creator: &Pubkey,
+    election_id: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VOTE_MANAGER_SEED,
+            creator.as_ref(),
+            &election_seed(election_id),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a ProjectData PDA: `[PROJECT_SEED, project_id, round, admin, election]`.
+pub fn project_address(
+    project_id: &str,
+    round: u64,
+    admin: &Pubkey,
+    election_id: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROJECT_SEED,
+            project_id.as_bytes(),
+            &round.to_le_bytes(),
+            admin.as_ref(),
+            &election_seed(election_id),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a VoterData PDA: `[VOTER_SEED, election, round, voter, project_id]`.
+pub fn voter_address(
+    election_id: u64,
+    round: u64,
+    voter: &Pubkey,
+    project_id: &str,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VOTER_SEED,
+            &election_seed(election_id),
+            &round.to_le_bytes(),
+            voter.as_ref(),
+            project_id.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a stealth VoterData PDA:
+/// `[STEALTH_VOTER_SEED, election, round, blinded_voter, project_id]`.
+pub fn stealth_voter_address(
+    election_id: u64,
+    round: u64,
+    blinded_voter: &[u8; 32],
+    project_id: &str,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            STEALTH_VOTER_SEED,
+            &election_seed(election_id),
+            &round.to_le_bytes(),
+            blinded_voter,
+            project_id.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a RoundResult PDA: `[ROUND_RESULT_SEED, vote_manager, round]`.
+pub fn round_result_address(
+    vote_manager: &Pubkey,
+    round: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ROUND_RESULT_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a RoundArchive PDA: `[ROUND_ARCHIVE_SEED, vote_manager, round]`.
+pub fn round_archive_address(
+    vote_manager: &Pubkey,
+    round: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ROUND_ARCHIVE_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a TallyState PDA: `[TALLY_STATE_SEED, vote_manager, round]`.
+pub fn tally_state_address(vote_manager: &Pubkey, round: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            TALLY_STATE_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a VoteCommit PDA: `[VOTE_COMMIT_SEED, vote_manager, round, voter]`.
+pub fn vote_commit_address(
+    vote_manager: &Pubkey,
+    round: u64,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VOTE_COMMIT_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+            voter.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives the Treasury PDA: `[TREASURY_SEED, vote_manager]`.
+pub fn treasury_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives a RewardVault PDA: `[REWARD_VAULT_SEED, vote_manager, round]`.
+pub fn reward_vault_address(
+    vote_manager: &Pubkey,
+    round: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REWARD_VAULT_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a Proposal PDA: `[PROPOSAL_SEED, vote_manager, proposal_id]`.
+pub fn proposal_address(
+    vote_manager: &Pubkey,
+    proposal_id: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROPOSAL_SEED,
+            vote_manager.as_ref(),
+            &proposal_id.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a ProposalVote PDA: `[PROPOSAL_VOTE_SEED, proposal, voter]`.
+pub fn proposal_vote_address(
+    proposal: &Pubkey,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROPOSAL_VOTE_SEED, proposal.as_ref(), voter.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives the governance authority PDA that signs executed proposals:
+/// `[GOVERNANCE_AUTHORITY_SEED, vote_manager]`.
+pub fn governance_authority_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GOVERNANCE_AUTHORITY_SEED, vote_manager.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives the SponsorVault PDA: `[SPONSOR_VAULT_SEED, vote_manager]`.
+pub fn sponsor_vault_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SPONSOR_VAULT_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives a SponsorRecord PDA: `[SPONSOR_RECORD_SEED, vote_manager, wallet]`.
+pub fn sponsor_record_address(
+    vote_manager: &Pubkey,
+    wallet: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SPONSOR_RECORD_SEED, vote_manager.as_ref(), wallet.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives a RoundAttestation PDA: `[ROUND_ATTESTATION_SEED, round_result]`.
+pub fn round_attestation_address(round_result: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROUND_ATTESTATION_SEED, round_result.as_ref()], program_id)
+}
+
+/// Derives the Campaign PDA: `[CAMPAIGN_SEED, vote_manager]`.
+pub fn campaign_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CAMPAIGN_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives a Ballot PDA: `[BALLOT_SEED, vote_manager, round, voter]`.
+pub fn ballot_address(
+    vote_manager: &Pubkey,
+    round: u64,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            BALLOT_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+            voter.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a RankedTally PDA: `[RANKED_TALLY_SEED, vote_manager, round]`.
+pub fn ranked_tally_address(
+    vote_manager: &Pubkey,
+    round: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            RANKED_TALLY_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives the receipt authority PDA that mints vote receipts:
+/// `[RECEIPT_AUTHORITY_SEED, vote_manager]`.
+pub fn receipt_authority_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_AUTHORITY_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives a StakePosition PDA: `[STAKE_POSITION_SEED, vote_manager, owner]`.
+pub fn stake_position_address(
+    vote_manager: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[STAKE_POSITION_SEED, vote_manager.as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives a RoleGrant PDA: `[ROLE_SEED, vote_manager, holder]`.
+pub fn role_address(vote_manager: &Pubkey, holder: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ROLE_SEED, vote_manager.as_ref(), holder.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives the AdminCouncil PDA, which signs executed admin actions:
+/// `[ADMIN_COUNCIL_SEED, vote_manager]`.
+pub fn admin_council_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_COUNCIL_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives an AdminAction PDA: `[ADMIN_ACTION_SEED, council, action_id]`.
+pub fn admin_action_address(council: &Pubkey, action_id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ADMIN_ACTION_SEED,
+            council.as_ref(),
+            &action_id.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives an OperationMarker PDA: `[OPERATION_MARKER_SEED, vote_manager, key]`.
+pub fn operation_marker_address(
+    vote_manager: &Pubkey,
+    key: &[u8; 32],
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OPERATION_MARKER_SEED, vote_manager.as_ref(), key],
+        program_id,
+    )
+}
+
+/// Derives a round's Leaderboard PDA: `[LEADERBOARD_SEED, vote_manager, round]`.
+pub fn leaderboard_address(vote_manager: &Pubkey, round: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            LEADERBOARD_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a Referendum PDA: `[REFERENDUM_SEED, vote_manager, referendum_id]`.
+pub fn referendum_address(
+    vote_manager: &Pubkey,
+    referendum_id: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REFERENDUM_SEED,
+            vote_manager.as_ref(),
+            &referendum_id.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Derives a ReferendumVote PDA: `[REFERENDUM_VOTE_SEED, referendum, voter]`.
+pub fn referendum_vote_address(
+    referendum: &Pubkey,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFERENDUM_VOTE_SEED, referendum.as_ref(), voter.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives a HoldingAttestation PDA: `[HOLDING_ATTESTATION_SEED, vote_manager, owner]`.
+pub fn holding_attestation_address(
+    vote_manager: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[HOLDING_ATTESTATION_SEED, vote_manager.as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives an allowlist AccessListEntry PDA: `[ALLOWLIST_SEED, vote_manager, voter]`.
+pub fn allowlist_entry_address(
+    vote_manager: &Pubkey,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ALLOWLIST_SEED, vote_manager.as_ref(), voter.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives a blocklist AccessListEntry PDA: `[BLOCKLIST_SEED, vote_manager, voter]`.
+pub fn blocklist_entry_address(
+    vote_manager: &Pubkey,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BLOCKLIST_SEED, vote_manager.as_ref(), voter.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives a FeeDiscountSchedule PDA: `[FEE_DISCOUNT_SEED, vote_manager]`.
+pub fn fee_discount_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_DISCOUNT_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives a VoteNonce PDA: `[VOTE_NONCE_SEED, vote_manager, voter]`.
+pub fn vote_nonce_address(
+    vote_manager: &Pubkey,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_NONCE_SEED, vote_manager.as_ref(), voter.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives a CategoryRegistry PDA: `[CATEGORY_REGISTRY_SEED, vote_manager]`.
+pub fn category_registry_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CATEGORY_REGISTRY_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives a VoteTree PDA: `[VOTE_TREE_SEED, vote_manager]`.
+pub fn vote_tree_address(vote_manager: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTE_TREE_SEED, vote_manager.as_ref()], program_id)
+}
+
+/// Derives a VoteLock PDA: `[VOTE_LOCK_SEED, vote_manager, voter]`.
+pub fn vote_lock_address(
+    vote_manager: &Pubkey,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_LOCK_SEED, vote_manager.as_ref(), voter.as_ref()],
+        program_id,
+    )
+}
+
+/// Derives a voter's RoundVotes PDA: `[ROUND_VOTES_SEED, vote_manager, round, voter]`, `voter`
+/// being the blinded id for stealth votes.
+pub fn round_votes_address(
+    vote_manager: &Pubkey,
+    round: u64,
+    voter: &[u8],
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ROUND_VOTES_SEED,
+            vote_manager.as_ref(),
+            &round.to_le_bytes(),
+            voter,
+        ],
+        program_id,
+    )
+}
@@ -0,0 +1,166 @@
+use anchor_lang::solana_program::{hash::hashv, keccak, pubkey::Pubkey};
+
+/// Fixed-point precision used by weight curve multipliers (`1_000_000` == 1.0).
+pub const WEIGHT_PRECISION: u64 = 1_000_000;
+
+/// Computes the integer square root of `value`, rounded down.
+///
+/// **Business Logic:**
+/// - Backs the `Sqrt` weight curve without relying on floating point, which is non-deterministic
+///   across validators.
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method starting from an upper bound of the root.
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Appends `value` to `out` as a JSON string literal.
+///
+/// **Business Logic:**
+/// - Escapes quotes, backslashes and control characters the way RFC 8785 does, so the output is
+///   byte-for-byte reproducible by any canonical JSON encoder.
+pub fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Encodes `bytes` as lowercase hex.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Builds the message a voter signs to authorize `vote_with_authorization`:
+/// `"ttt:vote_authorization" || voter || project || round || nonce`, integers little-endian.
+///
+/// The project PDA already commits to the election and round, the round and nonce keep a
+/// signature from being replayed in a later round or twice in the same one.
+pub fn vote_authorization_message(
+    voter: &Pubkey,
+    project: &Pubkey,
+    round: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    [
+        b"ttt:vote_authorization".as_slice(),
+        voter.as_ref(),
+        project.as_ref(),
+        &round.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// Computes the key of an `operation` guarded by `idempotency_key`: `hash(idempotency_key || 0 ||
+/// operation)`.
+///
+/// The zero byte cannot appear in either string when they come from command-line arguments, so
+/// distinct pairs never share a key.
+pub fn operation_key(idempotency_key: &str, operation: &str) -> [u8; 32] {
+    hashv(&[idempotency_key.as_bytes(), &[0], operation.as_bytes()]).to_bytes()
+}
+
+/// Computes the eligibility snapshot leaf of `voter`: `hash(voter || weight)`.
+pub fn eligibility_leaf(voter: &Pubkey, weight: u64) -> [u8; 32] {
+    hashv(&[voter.as_ref(), &weight.to_le_bytes()]).to_bytes()
+}
+
+/// Hashes two sibling nodes of an eligibility tree in sorted order, so proofs need no left/right
+/// flags.
+///
+/// Leaves hash 40 bytes and nodes 64, so a node can never be passed off as a leaf.
+pub fn eligibility_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+/// Returns whether `proof` links `leaf` to `root`.
+pub fn verify_eligibility_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| eligibility_node(&node, sibling));
+    computed == *root
+}
+
+/// Builds the levels of an eligibility tree, from `leaves` up to the root.
+///
+/// **Business Logic:**
+/// - An unpaired node at the end of a level moves up unchanged.
+/// - Only used off-chain to publish snapshots; the program merely verifies proofs.
+pub fn eligibility_tree(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while levels[levels.len() - 1].len() > 1 {
+        let next = levels[levels.len() - 1]
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => eligibility_node(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Returns the proof of the leaf at `index` in the tree built by `eligibility_tree`.
+pub fn eligibility_proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+/// Returns the root of a compressed vote tree of `max_depth` holding `leaves`, and the proof of
+/// the leaf at `index`.
+///
+/// **Business Logic:**
+/// - Follows the spl-account-compression layout: nodes hash `keccak(left || right)`, and leaves
+///   past the last vote are zero, as are the nodes above them.
+/// - Only used off-chain to prove compressed votes; the compression program verifies proofs.
+pub fn vote_tree_proof(
+    leaves: &[[u8; 32]],
+    max_depth: u32,
+    mut index: usize,
+) -> ([u8; 32], Vec<[u8; 32]>) {
+    let mut level = leaves.to_vec();
+    let mut empty = [0; 32];
+    let mut proof = Vec::new();
+    for _ in 0..max_depth {
+        proof.push(level.get(index ^ 1).copied().unwrap_or(empty));
+        level = level
+            .chunks(2)
+            .map(|pair| keccak::hashv(&[&pair[0], pair.get(1).unwrap_or(&empty)]).to_bytes())
+            .collect();
+        empty = keccak::hashv(&[&empty, &empty]).to_bytes();
+        index /= 2;
+    }
+    (level.first().copied().unwrap_or(empty), proof)
+}
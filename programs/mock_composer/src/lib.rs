@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use governance::{cpi::accounts::Voter, program::Governance, FeeCurrency, VoterData};
+
+// Declare the program ID to associate this Rust program with the deployed Solana program.
+declare_id!("");
+
+/// Stand-in for a third-party program composing with the governance program.
+///
+/// INFO: Deployed only to localnet by the integration tests.
+#[program]
+pub mod mock_composer {
+    use super::*;
+
+    /// Relays `votes` consecutive `do_vote` CPIs for the signer.
+    ///
+    /// **Business Logic:**
+    /// - Forwards the accounts unchecked and in the order they were passed, leaving every check to
+    ///   the governance program.
+    /// - The voter's signature is extended to the CPI; the relay holds no authority of its own.
+    /// - Re-reads the VoterData after every CPI and fails unless the vote was recorded, so callers
+    ///   can rely on the callee's writes as soon as the CPI returns.
+    pub fn relay_vote(ctx: Context<RelayVote>, votes: u8) -> Result<()> {
+        for _ in 0..votes {
+            let recorded = vote_count(&ctx.accounts.voter_data)?;
+            governance::cpi::do_vote(ctx.accounts.do_vote_ctx(), None, FeeCurrency::Ttt)?;
+            require!(
+                vote_count(&ctx.accounts.voter_data)? == recorded + 1,
+                ComposerError::VoteNotRecorded
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Accounts forwarded to `do_vote`, declared in the governance program's order.
+///
+/// **Business Logic:**
+/// - Only the signer and the governance program are checked here; the relay must not pre-validate
+///   what the callee is responsible for.
+#[derive(Accounts)]
+pub struct RelayVote<'info> {
+    /// CHECK: Created and validated by the governance program.
+    #[account(mut)]
+    pub voter_data: UncheckedAccount<'info>, // Voter's VoterData PDA.
+    #[account(mut)]
+    pub signer: Signer<'info>, // The voter, paying for the VoterData.
+    /// CHECK: Validated by the governance program.
+    #[account(mut)]
+    pub vote_manager: UncheckedAccount<'info>, // VoteManager PDA.
+    /// CHECK: Validated by the governance program.
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>, // Treasury PDA collecting the fee.
+    /// CHECK: Validated by the governance program.
+    #[account(mut)]
+    pub treasury_token_account: UncheckedAccount<'info>, // Treasury's fee account.
+    /// CHECK: Validated by the governance program.
+    #[account(mut)]
+    pub project: UncheckedAccount<'info>, // The project being voted for.
+    /// CHECK: Validated by the governance program.
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>, // The governance token mint (ttt).
+    /// CHECK: Validated by the governance program.
+    #[account(mut)]
+    pub token: UncheckedAccount<'info>, // Voter's token account.
+    /// CHECK: Validated by the governance program.
+    pub token_program: UncheckedAccount<'info>, // Token program of the mint.
+    pub system_program: Program<'info, System>, // Solana System program.
+    /// CHECK: Validated by the governance program.
+    #[account(mut)]
+    pub leaderboard: Option<UncheckedAccount<'info>>, // The round's standings, if any.
+    /// CHECK: Validated by the governance program.
+    pub holding_attestation: Option<UncheckedAccount<'info>>, // Voter's holding, if required.
+    /// CHECK: Validated by the governance program.
+    pub access_entry: Option<UncheckedAccount<'info>>, // Voter's access list entry, if required.
+    /// CHECK: Validated by the governance program.
+    pub fee_discounts: Option<UncheckedAccount<'info>>, // Discounts the fee, if passed.
+    /// CHECK: Created and validated by the governance program.
+    #[account(mut)]
+    pub vote_lock: UncheckedAccount<'info>, // The round the voter last voted in.
+    /// CHECK: Created and validated by the governance program.
+    #[account(mut)]
+    pub round_votes: UncheckedAccount<'info>, // The voter's votes in the round.
+    pub governance_program: Program<'info, Governance>, // The governance program.
+}
+
+impl<'info> RelayVote<'info> {
+    /// Builds the `do_vote` CPI context from the relayed accounts.
+    fn do_vote_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Voter<'info>> {
+        let cpi_accounts = Voter {
+            voter_data: self.voter_data.to_account_info(),
+            signer: self.signer.to_account_info(),
+            vote_manager: self.vote_manager.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            treasury_token_account: self.treasury_token_account.to_account_info(),
+            project: self.project.to_account_info(),
+            mint: self.mint.to_account_info(),
+            token: self.token.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            leaderboard: self.leaderboard.as_ref().map(|a| a.to_account_info()),
+            holding_attestation: self
+                .holding_attestation
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            access_entry: self.access_entry.as_ref().map(|a| a.to_account_info()),
+            fee_discounts: self.fee_discounts.as_ref().map(|a| a.to_account_info()),
+            vote_lock: self.vote_lock.to_account_info(),
+            round_votes: self.round_votes.to_account_info(),
+        };
+        CpiContext::new(self.governance_program.to_account_info(), cpi_accounts)
+    }
+}
+
+/// Returns the votes recorded in `voter_data`, 0 before the account is created.
+fn vote_count(voter_data: &AccountInfo) -> Result<u64> {
+    if voter_data.data_is_empty() {
+        return Ok(0);
+    }
+    let data = voter_data.try_borrow_data()?;
+    Ok(VoterData::try_deserialize(&mut &data[..])?.vote_count)
+}
+
+#[error_code]
+pub enum ComposerError {
+    #[msg("The relayed vote was not recorded")]
+    VoteNotRecorded,
+}
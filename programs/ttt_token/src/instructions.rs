@@ -1,20 +1,52 @@
-use anchor_lang::{prelude::*, solana_program::entrypoint::ProgramResult};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, program::invoke, program_option::COption},
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token_2022::spl_token_2022::extension::{
-        group_member_pointer::GroupMemberPointer, metadata_pointer::MetadataPointer,
-        mint_close_authority::MintCloseAuthority, permanent_delegate::PermanentDelegate,
+    associated_token::{
+        create, get_associated_token_address_with_program_id, AssociatedToken, Create,
+    },
+    token_2022::{
+        initialize_mint2,
+        spl_token_2022::{
+            extension::{
+                default_account_state::DefaultAccountState,
+                group_member_pointer::GroupMemberPointer,
+                interest_bearing_mint::InterestBearingConfig, metadata_pointer::MetadataPointer,
+                mint_close_authority::MintCloseAuthority, permanent_delegate::PermanentDelegate,
+                transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, ExtensionType,
+            },
+            state::{AccountState, Mint as MintState},
+        },
+        InitializeMint2,
     },
     token_interface::{
-        spl_token_metadata_interface::state::TokenMetadata, token_metadata_initialize, Mint,
-        Token2022, TokenAccount, TokenMetadataInitialize,
+        burn, close_account, default_account_state_initialize, freeze_account,
+        group_member_pointer_initialize, harvest_withheld_tokens_to_mint,
+        interest_bearing_mint_initialize, interest_bearing_mint_update_rate,
+        metadata_pointer_initialize, mint_close_authority_initialize,
+        non_transferable_mint_initialize, permanent_delegate_initialize,
+        spl_token_metadata_interface::{
+            instruction::remove_key,
+            state::{Field, TokenMetadata},
+        },
+        thaw_account, token_group_initialize, token_member_initialize, token_metadata_initialize,
+        token_metadata_update_field, transfer_fee_initialize, transfer_hook_initialize,
+        withdraw_withheld_tokens_from_mint, Burn, CloseAccount, DefaultAccountStateInitialize,
+        FreezeAccount, GroupMemberPointerInitialize, HarvestWithheldTokensToMint,
+        InterestBearingMintInitialize, InterestBearingMintUpdateRate, MetadataPointerInitialize,
+        Mint, MintCloseAuthorityInitialize, NonTransferableMintInitialize,
+        PermanentDelegateInitialize, ThawAccount, Token2022, TokenAccount, TokenGroupInitialize,
+        TokenMemberInitialize, TokenMetadataInitialize, TokenMetadataUpdateField,
+        TransferFeeInitialize, TransferHookInitialize, WithdrawWithheldTokensFromMint,
     },
 };
 use spl_pod::optional_keys::OptionalNonZeroPubkey;
 
 use crate::{
     get_meta_list_size, get_mint_extensible_extension_data, get_mint_extension_data,
-    update_account_lamports_to_minimum_balance, META_LIST_ACCOUNT_SEED,
+    is_non_transferable, mint_authority_address, update_account_lamports_to_minimum_balance,
+    TokenError, META_LIST_ACCOUNT_SEED, MINT_AUTHORITY_SEED, MINT_CONFIG_SEED, VESTING_SEED,
 };
 
 /// Arguments required to create a new mint account.
@@ -22,12 +54,60 @@ use crate::{
 /// **Business Logic:**
 /// - Encapsulates all necessary metadata and initial supply information for token creation.
 /// - Ensures consistency and integrity of token properties upon initialization.
+/// - `transfer_fee_basis_points` adds the TransferFeeConfig extension, withholding that share of
+///   every transfer up to `max_fee` (uncapped when `None`); `max_fee` alone is rejected.
+/// - `interest_rate` adds the InterestBearingConfig extension at that rate in basis points, which
+///   the admin changes with `update_interest_rate`.
+/// - `soulbound` adds the NonTransferable extension: TTT becomes a voting credential that cannot
+///   be traded, and the admin keeps the mint authority to issue it to voters.
+/// - `max_supply` caps the total supply, the initial supply included; it is enforced by
+///   `mint_more` and `scheduled_mint` while the mint authority is kept.
+/// - Unless `revoke_mint_authority` is set, the mint authority moves to a program PDA and
+///   `scheduled_mint` can mint once every `mint_timelock` seconds. A soulbound token keeps it with
+///   the authority instead, so it cannot be revoked.
+/// - `default_frozen` adds the DefaultAccountState extension, so new token accounts start frozen
+///   until thawed, e.g. after KYC. `freeze_authority` takes over the freeze authority once the
+///   authority's own account is thawed; the VoteManager PDA lets governance thaw voters.
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct CreateMintAccountArgs {
-    pub name: String,        // Name of the token.
-    pub symbol: String,      // Symbol representing the token.
-    pub uri: String,         // URI pointing to the token's metadata.
-    pub initial_supply: u64, // Initial number of tokens to mint.
+    pub name: String,                           // Name of the token.
+    pub symbol: String,                         // Symbol representing the token.
+    pub uri: String,                            // URI pointing to the token's metadata.
+    pub decimals: u8,                           // Decimals of the token.
+    pub initial_supply: u64,                    // Initial number of tokens to mint.
+    pub max_supply: Option<u64>,                // Cap on the total supply, none if uncapped.
+    pub revoke_mint_authority: bool,            // Whether the total supply is fixed.
+    pub mint_timelock: u32,                     // Seconds between scheduled mints.
+    pub transfer_fee_basis_points: Option<u16>, // Transfer fee in basis points, none by default.
+    pub max_fee: Option<u64>,                   // Largest fee withheld from one transfer.
+    pub interest_rate: Option<i16>,             // Interest rate in basis points, none by default.
+    pub soulbound: bool,                        // Whether the token is non-transferable.
+    pub default_frozen: bool,                   // Whether new token accounts start frozen.
+    pub freeze_authority: Option<Pubkey>,       // Freeze authority, the authority if none.
+}
+
+/// Supply settings of a TTT mint, kept for minting after its creation.
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+    pub mint: Pubkey,            // The configured mint.
+    pub max_supply: Option<u64>, // Cap on the total supply, none if uncapped.
+    pub mint_timelock: u32,      // Seconds between scheduled mints.
+    pub next_mint_ts: i64,       // Earliest time of the next scheduled mint.
+    pub bump: u8,                // MintConfig PDA bump.
+}
+
+impl MintConfig {
+    /// Fails with `SupplyCapExceeded` if minting `amount` on top of `supply` exceeds the cap.
+    pub fn check_cap(&self, supply: u64, amount: u64) -> Result<()> {
+        if let Some(max_supply) = self.max_supply {
+            let supply = supply
+                .checked_add(amount)
+                .ok_or(TokenError::SupplyCapExceeded)?;
+            require!(supply <= max_supply, TokenError::SupplyCapExceeded);
+        }
+        Ok(())
+    }
 }
 
 /// Accounts required to create a new mint account with extensions and associated metadata.
@@ -35,8 +115,16 @@ pub struct CreateMintAccountArgs {
 /// **Business Logic:**
 /// - Initializes a new token mint with specific extensions like MetadataPointer and
 ///   GroupMemberPointer.
+/// - Sets the TransferHook authority without a hook program; `ttt_transfer_hook` is enabled later.
+/// - Adds the TransferFeeConfig extension when the arguments set a transfer fee, and the
+///   InterestBearingConfig extension when they set an interest rate.
+/// - Adds the NonTransferable extension for a soulbound token, and the DefaultAccountState
+///   extension for accounts starting frozen.
 /// - Sets up the associated token account and additional metadata accounts.
+/// - Records the supply cap and the minting timelock in the mint's MintConfig PDA.
 /// - Ensures proper authority settings for minting, freezing, and delegating.
+/// - The mint and its token account are created by the handler rather than by `init`, since
+///   Anchor has no constraint for the TransferFeeConfig and InterestBearingConfig extensions.
 #[derive(Accounts)]
 #[instruction(args: CreateMintAccountArgs)]
 pub struct CreateMintAccount<'info> {
@@ -45,30 +133,18 @@ pub struct CreateMintAccount<'info> {
     #[account(mut)]
     /// CHECK: can be any account
     pub authority: Signer<'info>, // Authority who controls the mint.
+    #[account(mut)]
+    pub mint: Signer<'info>, // The new mint account, signing its own creation.
+    /// CHECK: Created by the handler once the mint exists.
     #[account(
-        init,
-        signer,
-        payer = payer,
-        mint::token_program = token_program,
-        mint::decimals = 0, // Token has no decimal places.
-        mint::authority = authority, // Sets the authority for minting.
-        mint::freeze_authority = authority, // Authority that can freeze the mint.
-        extensions::metadata_pointer::authority = authority, // Sets metadata pointer authority.
-        extensions::metadata_pointer::metadata_address = mint, // Associates metadata with the mint.
-        extensions::group_member_pointer::authority = authority, // Sets group member pointer authority.
-        extensions::group_member_pointer::member_address = mint, // Associates group member pointer with the mint.
-        extensions::close_authority::authority = authority, // Authority that can close the mint.
-        extensions::permanent_delegate::delegate = authority, // Sets a permanent delegate for the mint.
-    )]
-    pub mint: Box<InterfaceAccount<'info, Mint>>, // The new mint account being created.
-    #[account(
-        init,
-        payer = payer,
-        associated_token::token_program = token_program,
-        associated_token::mint = mint,
-        associated_token::authority = authority, // Admin authority
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &authority.key(),
+            &mint.key(),
+            &token_program.key()
+        ),
     )]
-    pub mint_token_account: Box<InterfaceAccount<'info, TokenAccount>>, /* Associated Token Account for the mint. */
+    pub mint_token_account: UncheckedAccount<'info>, // Associated Token Account for the mint.
     /// CHECK: This account's data is a buffer of TLV data
     #[account(
         init,
@@ -78,6 +154,14 @@ pub struct CreateMintAccount<'info> {
         payer = payer,
     )]
     pub extra_metas_account: UncheckedAccount<'info>, // Account to hold additional metadata.
+    #[account(
+        init,
+        space = 8 + MintConfig::INIT_SPACE,
+        seeds = [MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+        payer = payer,
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>, // Supply cap of the mint.
     pub system_program: Program<'info, System>, // Solana System program.
     pub associated_token_program: Program<'info, AssociatedToken>, /* Associated Token program
                                                  * interface. */
@@ -85,6 +169,188 @@ pub struct CreateMintAccount<'info> {
 }
 
 impl<'info> CreateMintAccount<'info> {
+    /// Creates the mint with its fixed-size extensions, all controlled by the authority.
+    ///
+    /// **Business Logic:**
+    /// - Allocates the extensions that must be initialized before the mint itself: the metadata
+    ///   and group member pointers at the mint, the close authority, the permanent delegate, the
+    ///   TransferHook without a program, the TransferFeeConfig when a fee is set and the
+    ///   InterestBearingConfig when a rate is set, the NonTransferable extension when soulbound and
+    ///   the DefaultAccountState extension, frozen, when accounts start frozen.
+    /// - Initializes the mint with `decimals`, the authority as mint and freeze authority.
+    fn initialize_mint(
+        &self,
+        decimals: u8,
+        transfer_fee: Option<(u16, u64)>,
+        interest_rate: Option<i16>,
+        soulbound: bool,
+        default_frozen: bool,
+    ) -> Result<()> {
+        let token_program = self.token_program.to_account_info();
+        let mint = self.mint.to_account_info();
+        let authority = self.authority.key();
+
+        let mut extensions = vec![
+            ExtensionType::MetadataPointer,
+            ExtensionType::GroupMemberPointer,
+            ExtensionType::MintCloseAuthority,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::TransferHook,
+        ];
+        if transfer_fee.is_some() {
+            extensions.push(ExtensionType::TransferFeeConfig);
+        }
+        if interest_rate.is_some() {
+            extensions.push(ExtensionType::InterestBearingConfig);
+        }
+        if soulbound {
+            extensions.push(ExtensionType::NonTransferable);
+        }
+        if default_frozen {
+            extensions.push(ExtensionType::DefaultAccountState);
+        }
+        let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+        let cpi_accounts_create = anchor_lang::system_program::CreateAccount {
+            from: self.payer.to_account_info(),
+            to: mint.clone(),
+        };
+        anchor_lang::system_program::create_account(
+            CpiContext::new(self.system_program.to_account_info(), cpi_accounts_create),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &token_program.key(),
+        )?;
+
+        metadata_pointer_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                MetadataPointerInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            Some(authority),
+            Some(mint.key()),
+        )?;
+        group_member_pointer_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                GroupMemberPointerInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            Some(authority),
+            Some(mint.key()),
+        )?;
+        mint_close_authority_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                MintCloseAuthorityInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            Some(&authority),
+        )?;
+        permanent_delegate_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                PermanentDelegateInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            &authority,
+        )?;
+        transfer_hook_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                TransferHookInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            Some(authority),
+            None,
+        )?;
+        if let Some((basis_points, max_fee)) = transfer_fee {
+            // The authority sets the fee and withdraws the withheld fees.
+            transfer_fee_initialize(
+                CpiContext::new(
+                    token_program.clone(),
+                    TransferFeeInitialize {
+                        token_program_id: token_program.clone(),
+                        mint: mint.clone(),
+                    },
+                ),
+                Some(&authority),
+                Some(&authority),
+                basis_points,
+                max_fee,
+            )?;
+        }
+        if let Some(rate) = interest_rate {
+            // The authority updates the rate.
+            interest_bearing_mint_initialize(
+                CpiContext::new(
+                    token_program.clone(),
+                    InterestBearingMintInitialize {
+                        token_program_id: token_program.clone(),
+                        mint: mint.clone(),
+                    },
+                ),
+                Some(authority),
+                rate,
+            )?;
+        }
+        if soulbound {
+            non_transferable_mint_initialize(CpiContext::new(
+                token_program.clone(),
+                NonTransferableMintInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ))?;
+        }
+        if default_frozen {
+            default_account_state_initialize(
+                CpiContext::new(
+                    token_program.clone(),
+                    DefaultAccountStateInitialize {
+                        token_program_id: token_program.clone(),
+                        mint: mint.clone(),
+                    },
+                ),
+                &AccountState::Frozen,
+            )?;
+        }
+
+        // Token has no decimal places.
+        initialize_mint2(
+            CpiContext::new(token_program.clone(), InitializeMint2 { mint }),
+            decimals,
+            &authority,
+            Some(&authority),
+        )
+    }
+
+    /// Creates the authority's associated token account for the new mint.
+    fn create_mint_token_account(&self) -> Result<()> {
+        let cpi_accounts = Create {
+            payer: self.payer.to_account_info(),
+            associated_token: self.mint_token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+            mint: self.mint.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+        create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        ))
+    }
+
     /// Initializes token metadata using CPI with the TokenMetadataInitialize interface.
     ///
     /// **Business Logic:**
@@ -117,15 +383,70 @@ impl<'info> CreateMintAccount<'info> {
 /// Handler for creating a new mint account.
 ///
 /// **Business Logic:**
+/// - Creates the mint with its extensions and the authority's token account.
 /// - Initializes token metadata and verifies its integrity.
 /// - Sets up various extensions to enhance token functionalities.
 /// - Mints the initial supply of tokens to the associated token account.
-/// - Revokes mint authority to prevent further minting, ensuring a fixed total supply.
+/// - Revokes mint authority to prevent further minting, ensuring a fixed total supply, or hands
+///   it to the mint authority PDA for timelocked minting. A soulbound token keeps it with the
+///   authority: credentials can only reach voters by minting.
 /// - Ensures the mint account is rent-exempt by updating lamports if necessary.
+/// - A mismatching extension fails with a `TokenError` rather than panicking; with the
+///   `verbose-logs` feature, the verification and the fixed supply are logged as `TTT:` codes.
 ///
 /// **Returns:**
 /// - `Result<()>`: Indicates success or failure of the mint account creation process.
 pub fn handler(ctx: Context<CreateMintAccount>, args: CreateMintAccountArgs) -> Result<()> {
+    // The initial supply counts towards the cap.
+    require!(
+        args.initial_supply <= args.max_supply.unwrap_or(u64::MAX),
+        TokenError::SupplyCapExceeded
+    );
+
+    // Soulbound credentials only reach voters by minting.
+    require!(
+        !(args.soulbound && args.revoke_mint_authority),
+        TokenError::SoulboundMintRevoked
+    );
+
+    // A fee cap without a fee is a misconfiguration; a fee without a cap is uncapped.
+    let transfer_fee = match (args.transfer_fee_basis_points, args.max_fee) {
+        (Some(basis_points), max_fee) => Some((basis_points, max_fee.unwrap_or(u64::MAX))),
+        (None, Some(_)) => return err!(TokenError::MaxFeeWithoutTransferFee),
+        (None, None) => None,
+    };
+
+    // Create the mint with its extensions, then its token account.
+    ctx.accounts.initialize_mint(
+        args.decimals,
+        transfer_fee,
+        args.interest_rate,
+        args.soulbound,
+        args.default_frozen,
+    )?;
+    ctx.accounts.create_mint_token_account()?;
+
+    // The authority's own account starts frozen too; thaw it to receive the initial supply.
+    if args.default_frozen {
+        let cpi_accounts = ThawAccount {
+            account: ctx.accounts.mint_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        thaw_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+        ))?;
+    }
+
+    ctx.accounts.mint_config.set_inner(MintConfig {
+        mint: ctx.accounts.mint.key(),
+        max_supply: args.max_supply,
+        mint_timelock: args.mint_timelock,
+        next_mint_ts: Clock::get()?.unix_timestamp + i64::from(args.mint_timelock),
+        bump: ctx.bumps.mint_config,
+    });
+
     // Initialize token metadata by invoking the metadata initialization CPI.
     ctx.accounts.initialize_token_metadata(
         args.name.clone(),
@@ -133,55 +454,99 @@ pub fn handler(ctx: Context<CreateMintAccount>, args: CreateMintAccountArgs) ->
         args.uri.clone(),
     )?;
 
-    // Reload the mint account to ensure it's updated with the latest data.
-    ctx.accounts.mint.reload()?;
     let mint_data = &mut ctx.accounts.mint.to_account_info();
 
     // Retrieve and verify token metadata extension data.
     let metadata = get_mint_extensible_extension_data::<TokenMetadata>(mint_data)?;
-    assert_eq!(metadata.mint, ctx.accounts.mint.key());
-    assert_eq!(metadata.name, args.name);
-    assert_eq!(metadata.symbol, args.symbol);
-    assert_eq!(metadata.uri, args.uri);
+    require!(
+        metadata.mint == ctx.accounts.mint.key()
+            && metadata.name == args.name
+            && metadata.symbol == args.symbol
+            && metadata.uri == args.uri,
+        TokenError::MetadataMismatch
+    );
 
     // Verify the MetadataPointer extension to ensure correct metadata association.
     let metadata_pointer = get_mint_extension_data::<MetadataPointer>(mint_data)?;
-    let mint_key: Option<Pubkey> = Some(ctx.accounts.mint.key());
-    let authority_key: Option<Pubkey> = Some(ctx.accounts.authority.key());
-    assert_eq!(
-        metadata_pointer.metadata_address,
-        OptionalNonZeroPubkey::try_from(mint_key)?
-    );
-    assert_eq!(
-        metadata_pointer.authority,
-        OptionalNonZeroPubkey::try_from(authority_key)?
+    let mint_key = OptionalNonZeroPubkey::try_from(Some(ctx.accounts.mint.key()))?;
+    let authority_key = OptionalNonZeroPubkey::try_from(Some(ctx.accounts.authority.key()))?;
+    require!(
+        metadata_pointer.metadata_address == mint_key
+            && metadata_pointer.authority == authority_key,
+        TokenError::MetadataPointerMismatch
     );
 
     // Verify the PermanentDelegate extension to ensure the delegate is correctly set.
     let permanent_delegate = get_mint_extension_data::<PermanentDelegate>(mint_data)?;
-    assert_eq!(
-        permanent_delegate.delegate,
-        OptionalNonZeroPubkey::try_from(authority_key)?
+    require!(
+        permanent_delegate.delegate == authority_key,
+        TokenError::PermanentDelegateMismatch
     );
 
     // Verify the MintCloseAuthority extension to ensure the close authority is correctly set.
     let close_authority = get_mint_extension_data::<MintCloseAuthority>(mint_data)?;
-    assert_eq!(
-        close_authority.close_authority,
-        OptionalNonZeroPubkey::try_from(authority_key)?
+    require!(
+        close_authority.close_authority == authority_key,
+        TokenError::CloseAuthorityMismatch
     );
 
     // Verify the GroupMemberPointer extension to ensure proper group membership.
     let group_member_pointer = get_mint_extension_data::<GroupMemberPointer>(mint_data)?;
-    assert_eq!(
-        group_member_pointer.authority,
-        OptionalNonZeroPubkey::try_from(authority_key)?
+    require!(
+        group_member_pointer.authority == authority_key
+            && group_member_pointer.member_address == mint_key,
+        TokenError::GroupMemberPointerMismatch
+    );
+
+    // Verify the TransferHook extension: the authority is set and no hook runs until it enables one.
+    let transfer_hook = get_mint_extension_data::<TransferHook>(mint_data)?;
+    require!(
+        transfer_hook.authority == authority_key
+            && transfer_hook.program_id == OptionalNonZeroPubkey::default(),
+        TokenError::TransferHookMismatch
     );
-    assert_eq!(
-        group_member_pointer.member_address,
-        OptionalNonZeroPubkey::try_from(mint_key)?
+
+    // Verify the TransferFeeConfig extension, present only when a fee was requested.
+    if let Some((basis_points, max_fee)) = transfer_fee {
+        let transfer_fee_config = get_mint_extension_data::<TransferFeeConfig>(mint_data)?;
+        let fee = transfer_fee_config.newer_transfer_fee;
+        require!(
+            transfer_fee_config.transfer_fee_config_authority == authority_key
+                && transfer_fee_config.withdraw_withheld_authority == authority_key
+                && u16::from(fee.transfer_fee_basis_points) == basis_points
+                && u64::from(fee.maximum_fee) == max_fee,
+            TokenError::TransferFeeConfigMismatch
+        );
+    }
+
+    // Verify the InterestBearingConfig extension, present only when a rate was requested.
+    if let Some(rate) = args.interest_rate {
+        let interest_config = get_mint_extension_data::<InterestBearingConfig>(mint_data)?;
+        require!(
+            interest_config.rate_authority == authority_key
+                && i16::from(interest_config.current_rate) == rate,
+            TokenError::InterestBearingConfigMismatch
+        );
+    }
+
+    // Verify the NonTransferable extension matches the soulbound flag.
+    require!(
+        is_non_transferable(mint_data)? == args.soulbound,
+        TokenError::NonTransferableMismatch
     );
 
+    // Verify the DefaultAccountState extension when accounts start frozen.
+    if args.default_frozen {
+        let default_state = get_mint_extension_data::<DefaultAccountState>(mint_data)?;
+        require!(
+            default_state.state == AccountState::Frozen as u8,
+            TokenError::DefaultAccountStateMismatch
+        );
+    }
+
+    #[cfg(feature = "verbose-logs")]
+    msg!("TTT:MINT_VERIFIED");
+
     // **Mint the Initial Supply to Receiver's ATA using Token-2022 CPI**
     let cpi_accounts_mint_to = anchor_spl::token_2022::MintTo {
         mint: ctx.accounts.mint.to_account_info(),
@@ -197,23 +562,41 @@ pub fn handler(ctx: Context<CreateMintAccount>, args: CreateMintAccountArgs) ->
     // Execute the minting of tokens to the associated token account.
     anchor_spl::token_2022::mint_to(cpi_ctx_mint_to, args.initial_supply)?;
 
-    // **Revoke Mint Authority to Fix the Total Supply**
-    let cpi_accounts_set_authority = anchor_spl::token_2022::SetAuthority {
-        account_or_mint: ctx.accounts.mint.to_account_info(),
-        current_authority: ctx.accounts.authority.to_account_info(),
-    };
+    // **Revoke Mint Authority to Fix the Total Supply, or Hand It to the PDA**
+    // A soulbound token keeps it: voters can only receive credentials by minting.
+    if !args.soulbound {
+        let cpi_accounts_set_authority = anchor_spl::token_2022::SetAuthority {
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+            current_authority: ctx.accounts.authority.to_account_info(),
+        };
 
-    let cpi_ctx_set_authority = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts_set_authority,
-    );
+        let cpi_ctx_set_authority = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts_set_authority,
+        );
 
-    // Revoke the mint authority by setting it to `None`, preventing further minting.
-    anchor_spl::token_2022::set_authority(
-        cpi_ctx_set_authority,
-        anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType::MintTokens,
-        None,
-    )?;
+        // Setting the mint authority to `None` prevents further minting.
+        let new_authority = (!args.revoke_mint_authority)
+            .then(|| mint_authority_address(&ctx.accounts.mint.key()).0);
+        anchor_spl::token_2022::set_authority(
+            cpi_ctx_set_authority,
+            anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType::MintTokens,
+            new_authority,
+        )?;
+    }
+
+    // **Hand the Freeze Authority Over**
+    if let Some(freeze_authority) = args.freeze_authority {
+        let cpi_accounts = anchor_spl::token_2022::SetAuthority {
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+            current_authority: ctx.accounts.authority.to_account_info(),
+        };
+        anchor_spl::token_2022::set_authority(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType::FreezeAccount,
+            Some(freeze_authority),
+        )?;
+    }
 
     // **Update Lamports to Minimum Balance**
     update_account_lamports_to_minimum_balance(
@@ -222,6 +605,11 @@ pub fn handler(ctx: Context<CreateMintAccount>, args: CreateMintAccountArgs) ->
         ctx.accounts.system_program.to_account_info(),
     )?;
 
+    #[cfg(feature = "verbose-logs")]
+    if args.revoke_mint_authority {
+        msg!("TTT:SUPPLY_FIXED {}", args.initial_supply);
+    }
+
     Ok(())
 }
 
@@ -264,6 +652,1031 @@ pub struct CheckMintExtensionConstraints<'info> {
         extensions::group_member_pointer::member_address = mint, // Ensures GroupMemberPointer is associated with the mint.
         extensions::close_authority::authority = authority, // Ensures MintCloseAuthority is correct.
         extensions::permanent_delegate::delegate = authority, // Ensures PermanentDelegate is correctly set.
+        extensions::transfer_hook::authority = authority, // Ensures TransferHook authority is correct.
     )]
     pub mint: Box<InterfaceAccount<'info, Mint>>, // The mint account being checked.
 }
+
+/// Arguments required to create a vote receipt mint.
+///
+/// **Business Logic:**
+/// - Names the souvenir, e.g. "I voted in round 3"; each round gets its own receipt mint.
+/// - `mint_authority` is the only key able to mint receipts once the mint is created.
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CreateReceiptMintArgs {
+    pub name: String,           // Name of the receipt.
+    pub symbol: String,         // Symbol representing the receipt.
+    pub uri: String,            // URI pointing to the receipt's metadata.
+    pub mint_authority: Pubkey, // Authority minting the receipts, e.g. a governance PDA.
+}
+
+/// Accounts required to create a vote receipt mint.
+///
+/// **Business Logic:**
+/// - The mint is created by the handler rather than by `init`, since Anchor has no constraint
+///   for the NonTransferable extension.
+/// - The authority signs the metadata and hands the mint authority over afterwards.
+#[derive(Accounts)]
+pub struct CreateReceiptMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer for the mint account.
+    pub authority: Signer<'info>, // Update authority of the receipt's metadata.
+    #[account(mut)]
+    pub mint: Signer<'info>, // The new receipt mint, signing its own creation.
+    pub system_program: Program<'info, System>, // Solana System program.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for creating a vote receipt mint.
+///
+/// **Business Logic:**
+/// - Creates a 0-decimal Token-2022 mint with the NonTransferable extension, so receipts stay in
+///   the wallet they were minted to.
+/// - Stores the receipt's metadata on the mint itself.
+/// - Hands the mint authority over to `mint_authority`, which mints receipts with
+///   `mint_vote_receipt`.
+/// - Ensures the mint account is rent-exempt by updating lamports if necessary.
+pub fn create_receipt_mint_handler(
+    ctx: Context<CreateReceiptMint>,
+    args: CreateReceiptMintArgs,
+) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let mint = ctx.accounts.mint.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+
+    // Allocate the mint with the extensions that must be initialized before the mint itself.
+    let space = ExtensionType::try_calculate_account_len::<MintState>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let cpi_accounts_create = anchor_lang::system_program::CreateAccount {
+        from: ctx.accounts.payer.to_account_info(),
+        to: mint.clone(),
+    };
+    anchor_lang::system_program::create_account(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            cpi_accounts_create,
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &token_program.key(),
+    )?;
+
+    // **Make Receipts Non-Transferable and Point the Metadata at the Mint**
+    non_transferable_mint_initialize(CpiContext::new(
+        token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        },
+    ))?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(authority.key()),
+        Some(mint.key()),
+    )?;
+
+    // Receipts are whole tokens; the authority mints until handing the authority over.
+    initialize_mint2(
+        CpiContext::new(
+            token_program.clone(),
+            InitializeMint2 { mint: mint.clone() },
+        ),
+        0,
+        &authority.key(),
+        None,
+    )?;
+
+    let cpi_accounts_metadata = TokenMetadataInitialize {
+        token_program_id: token_program.clone(),
+        mint: mint.clone(),
+        metadata: mint.clone(), // Metadata account is the mint itself.
+        mint_authority: authority.clone(),
+        update_authority: authority.clone(),
+    };
+    token_metadata_initialize(
+        CpiContext::new(token_program.clone(), cpi_accounts_metadata),
+        args.name,
+        args.symbol,
+        args.uri,
+    )?;
+
+    // **Hand the Mint Authority Over to the Receipt Minter**
+    let cpi_accounts_set_authority = anchor_spl::token_2022::SetAuthority {
+        account_or_mint: mint.clone(),
+        current_authority: authority,
+    };
+    anchor_spl::token_2022::set_authority(
+        CpiContext::new(token_program, cpi_accounts_set_authority),
+        anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType::MintTokens,
+        Some(args.mint_authority),
+    )?;
+
+    // **Update Lamports to Minimum Balance**
+    update_account_lamports_to_minimum_balance(
+        mint,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+/// Accounts required to mint a vote receipt.
+///
+/// **Business Logic:**
+/// - Only the mint authority of the receipt mint can mint, so receipts cannot be minted without
+///   going through the program holding it.
+/// - Creates the voter's receipt account on their first receipt of the mint.
+#[derive(Accounts)]
+pub struct MintVoteReceipt<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>, // The voter, paying for their receipt account.
+    pub authority: Signer<'info>, // Mint authority of the receipt mint.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        mint::authority = authority,
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>, // The round's receipt mint.
+    #[account(
+        init_if_needed,
+        payer = voter,
+        associated_token::token_program = token_program,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = voter,
+    )]
+    pub receipt_token_account: Box<InterfaceAccount<'info, TokenAccount>>, /* Voter's receipt account. */
+    pub system_program: Program<'info, System>, // Solana System program.
+    pub associated_token_program: Program<'info, AssociatedToken>, /* Associated Token program
+                                                 * interface. */
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Accounts required to collect the transfer fees withheld on TTT.
+///
+/// **Business Logic:**
+/// - The authority must be the mint's withdraw-withheld authority, checked by the Token-2022
+///   program on withdrawal.
+/// - The token accounts to harvest are passed as remaining accounts.
+/// - `destination` is the governance treasury's fee account, any account of the mint is accepted.
+#[derive(Accounts)]
+pub struct HarvestFees<'info> {
+    pub authority: Signer<'info>, // Withdraw-withheld authority of the mint.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>, // Treasury's fee account.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for collecting the withheld transfer fees.
+///
+/// **Business Logic:**
+/// - Harvests the fees withheld in the remaining accounts into the mint; anyone could, so
+///   accounts already harvested or frozen are no obstacle.
+/// - Withdraws everything withheld on the mint, including earlier harvests, to `destination`.
+pub fn harvest_and_withdraw_fees_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, HarvestFees<'info>>,
+) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let mint = ctx.accounts.mint.to_account_info();
+
+    if !ctx.remaining_accounts.is_empty() {
+        harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                token_program.clone(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            ctx.remaining_accounts.to_vec(),
+        )?;
+    }
+
+    let cpi_accounts = WithdrawWithheldTokensFromMint {
+        token_program_id: token_program.clone(),
+        mint,
+        destination: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    withdraw_withheld_tokens_from_mint(CpiContext::new(token_program, cpi_accounts))
+}
+
+/// Accounts required to change the TTT interest rate.
+///
+/// **Business Logic:**
+/// - The authority must be the mint's rate authority, checked by the Token-2022 program.
+#[derive(Accounts)]
+pub struct UpdateInterestRate<'info> {
+    pub authority: Signer<'info>, // Rate authority of the mint.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for changing the interest rate.
+///
+/// **Business Logic:**
+/// - Interest accrued at the previous rate is kept; the new rate applies from now on.
+/// - Only mints created with an interest rate carry the InterestBearingConfig extension; others
+///   fail in the Token-2022 program.
+pub fn update_interest_rate_handler(ctx: Context<UpdateInterestRate>, rate: i16) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = InterestBearingMintUpdateRate {
+        token_program_id: token_program.clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        rate_authority: ctx.accounts.authority.to_account_info(),
+    };
+    interest_bearing_mint_update_rate(CpiContext::new(token_program, cpi_accounts), rate)
+}
+
+/// Accounts required to edit the TTT token metadata.
+///
+/// **Business Logic:**
+/// - The authority must be the metadata's update authority, checked by the handlers.
+/// - The payer covers the rent of metadata that grows.
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer for the additional rent.
+    pub authority: Signer<'info>, // Update authority of the metadata.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+impl<'info> UpdateTokenMetadata<'info> {
+    /// Rejects an authority other than the metadata's update authority.
+    fn check_update_authority(&self) -> Result<()> {
+        let metadata =
+            get_mint_extensible_extension_data::<TokenMetadata>(&mut self.mint.to_account_info())?;
+        let authority_key = OptionalNonZeroPubkey::try_from(Some(self.authority.key()))?;
+        require!(
+            metadata.update_authority == authority_key,
+            TokenError::Unauthorized
+        );
+        Ok(())
+    }
+
+    /// Tops up the mint to the rent-exempt minimum of its resized metadata.
+    fn reconcile_rent(&self) -> Result<()> {
+        update_account_lamports_to_minimum_balance(
+            self.mint.to_account_info(),
+            self.payer.to_account_info(),
+            self.system_program.to_account_info(),
+        )
+    }
+}
+
+/// Handler for setting a metadata field.
+///
+/// **Business Logic:**
+/// - `name`, `symbol` and `uri` update the base fields; any other field is an additional
+///   key-value pair, added if missing.
+/// - The mint grows or shrinks with the metadata; lamports are topped up to stay rent-exempt.
+pub fn update_token_metadata_handler(
+    ctx: Context<UpdateTokenMetadata>,
+    field: String,
+    value: String,
+) -> Result<()> {
+    ctx.accounts.check_update_authority()?;
+
+    let field = match field.as_str() {
+        "name" => Field::Name,
+        "symbol" => Field::Symbol,
+        "uri" => Field::Uri,
+        _ => Field::Key(field),
+    };
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = TokenMetadataUpdateField {
+        token_program_id: token_program.clone(),
+        metadata: ctx.accounts.mint.to_account_info(), // Metadata is stored on the mint itself.
+        update_authority: ctx.accounts.authority.to_account_info(),
+    };
+    token_metadata_update_field(CpiContext::new(token_program, cpi_accounts), field, value)?;
+
+    ctx.accounts.reconcile_rent()
+}
+
+/// Handler for removing an additional metadata field.
+///
+/// **Business Logic:**
+/// - Only additional key-value pairs can be removed; a missing key fails in the Token-2022
+///   program.
+/// - Lamports freed by the smaller metadata stay on the mint.
+pub fn remove_metadata_field_handler(ctx: Context<UpdateTokenMetadata>, key: String) -> Result<()> {
+    ctx.accounts.check_update_authority()?;
+
+    let mint = ctx.accounts.mint.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+    invoke(
+        &remove_key(
+            ctx.accounts.token_program.key,
+            mint.key,
+            authority.key,
+            key,
+            false,
+        ),
+        &[mint, authority],
+    )?;
+
+    ctx.accounts.reconcile_rent()
+}
+
+/// Accounts required to freeze or thaw a TTT token account.
+///
+/// **Business Logic:**
+/// - The authority must be the mint's freeze authority, checked by the Token-2022 program.
+#[derive(Accounts)]
+pub struct SetAccountFrozen<'info> {
+    pub authority: Signer<'info>, // Freeze authority of the mint.
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>, // Account to freeze or thaw.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for freezing or thawing a token account.
+///
+/// **Business Logic:**
+/// - A frozen account can neither send nor receive TTT, so its owner can no longer pay vote fees.
+/// - Freezing a frozen account, or thawing a thawed one, fails in the Token-2022 program.
+pub fn set_account_frozen_handler(ctx: Context<SetAccountFrozen>, frozen: bool) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let account = ctx.accounts.token_account.to_account_info();
+    let mint = ctx.accounts.mint.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+
+    if frozen {
+        let cpi_accounts = FreezeAccount {
+            account,
+            mint,
+            authority,
+        };
+        freeze_account(CpiContext::new(token_program, cpi_accounts))
+    } else {
+        let cpi_accounts = ThawAccount {
+            account,
+            mint,
+            authority,
+        };
+        thaw_account(CpiContext::new(token_program, cpi_accounts))
+    }
+}
+
+/// Accounts required to burn the TTT left in token accounts.
+///
+/// **Business Logic:**
+/// - The authority must be the mint's permanent delegate, which may burn from any account.
+/// - The token accounts to empty are passed as remaining accounts.
+#[derive(Accounts)]
+pub struct BurnRemainingSupply<'info> {
+    pub authority: Signer<'info>, // Permanent delegate of the mint.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for burning the TTT left in token accounts.
+///
+/// **Business Logic:**
+/// - Burns the whole balance of every remaining account; empty accounts are skipped.
+/// - Accounts of another mint fail in the Token-2022 program, as do frozen ones, which must be
+///   thawed first.
+pub fn burn_remaining_supply_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BurnRemainingSupply<'info>>,
+) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    for account_info in ctx.remaining_accounts {
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+        if token_account.amount == 0 {
+            continue;
+        }
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: account_info.clone(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        burn(
+            CpiContext::new(token_program.clone(), cpi_accounts),
+            token_account.amount,
+        )?;
+    }
+    Ok(())
+}
+
+/// Accounts required to close an empty TTT token account.
+///
+/// **Business Logic:**
+/// - Only the account's owner can close it; the rent goes to `destination`.
+#[derive(Accounts)]
+pub struct CloseAta<'info> {
+    pub owner: Signer<'info>, // Owner of the token account.
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>, // Account to close.
+    #[account(mut)]
+    pub destination: SystemAccount<'info>, // Receives the recovered rent.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for closing an empty token account.
+///
+/// **Business Logic:**
+/// - The account must hold no TTT and no withheld transfer fees, checked by the Token-2022
+///   program.
+pub fn close_ata_handler(ctx: Context<CloseAta>) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    ))
+}
+
+/// Accounts required to close the TTT mint.
+///
+/// **Business Logic:**
+/// - The authority must be the mint's close authority, checked by the Token-2022 program.
+#[derive(Accounts)]
+pub struct CloseMint<'info> {
+    pub authority: Signer<'info>, // Close authority of the mint.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(mut)]
+    pub destination: SystemAccount<'info>, // Receives the recovered rent.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for closing the mint once its supply is burned.
+///
+/// **Business Logic:**
+/// - Fails with `SupplyNotZero` while any TTT is left; burn it with `burn_remaining_supply` first.
+/// - The rent of the mint, metadata included, goes to `destination`.
+pub fn close_mint_handler(ctx: Context<CloseMint>) -> Result<()> {
+    require!(ctx.accounts.mint.supply == 0, TokenError::SupplyNotZero);
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.mint.to_account_info(),
+        destination: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    ))
+}
+
+/// Accounts required to mint TTT after the mint's creation.
+///
+/// **Business Logic:**
+/// - The authority must be the mint authority, checked by the Token-2022 program. Only soulbound
+///   mints keep it with the admin, others mint with `scheduled_mint` or not at all.
+#[derive(Accounts)]
+pub struct MintMore<'info> {
+    pub authority: Signer<'info>, // Mint authority of the mint.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(
+        seeds = [MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>, // Supply cap of the mint.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>, // Account receiving the TTT.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for minting TTT after the mint's creation.
+///
+/// **Business Logic:**
+/// - Fails with `SupplyCapExceeded` if the new supply would exceed the MintConfig's cap.
+pub fn mint_more_handler(ctx: Context<MintMore>, amount: u64) -> Result<()> {
+    ctx.accounts
+        .mint_config
+        .check_cap(ctx.accounts.mint.supply, amount)?;
+
+    let cpi_accounts = anchor_spl::token_2022::MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    anchor_spl::token_2022::mint_to(cpi_ctx, amount)
+}
+
+/// Accounts required to mint TTT with the mint authority PDA.
+///
+/// **Business Logic:**
+/// - The mint authority PDA signs the mint; the admin decides the amount and the destination.
+#[derive(Accounts)]
+pub struct ScheduledMint<'info> {
+    pub authority: Signer<'info>, // The admin.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        mint::authority = mint_authority,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    /// CHECK: Signing PDA, holding no data.
+    #[account(seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>, // Mint authority of the mint.
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>, // Supply cap and timelock of the mint.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>, // Account receiving the TTT.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for a timelocked mint.
+///
+/// **Business Logic:**
+/// - Fails with `MintTimelocked` until the MintConfig's `next_mint_ts`, and with
+///   `SupplyCapExceeded` above the cap.
+/// - Each mint pushes the next one `mint_timelock` seconds out, so emissions follow the schedule
+///   however much is minted at once.
+pub fn scheduled_mint_handler(ctx: Context<ScheduledMint>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mint_config = &mut ctx.accounts.mint_config;
+    require!(now >= mint_config.next_mint_ts, TokenError::MintTimelocked);
+    mint_config.check_cap(ctx.accounts.mint.supply, amount)?;
+    mint_config.next_mint_ts = now + i64::from(mint_config.mint_timelock);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds: &[&[&[u8]]] = &[&[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.bumps.mint_authority],
+    ]];
+    let cpi_accounts = anchor_spl::token_2022::MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        seeds,
+    );
+    anchor_spl::token_2022::mint_to(cpi_ctx, amount)
+}
+
+/// Accounts required to create the mint of a TTT collection.
+///
+/// **Business Logic:**
+/// - The group mint holds no supply; its GroupPointer points at itself, where the TokenGroup
+///   state is stored.
+#[derive(Accounts)]
+pub struct CreateGroupMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer for the group mint.
+    pub authority: Signer<'info>, // Mint and update authority of the group.
+    #[account(
+        init,
+        payer = payer,
+        mint::token_program = token_program,
+        mint::decimals = 0,
+        mint::authority = authority,
+        extensions::group_pointer::authority = authority,
+        extensions::group_pointer::group_address = group_mint,
+    )]
+    pub group_mint: Box<InterfaceAccount<'info, Mint>>, // The new group mint.
+    pub system_program: Program<'info, System>, // Solana System program.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for creating a collection mint.
+///
+/// **Business Logic:**
+/// - Initializes the TokenGroup state with the authority as update authority, accepting up to
+///   `max_size` members.
+/// - Ensures the group mint is rent-exempt by updating lamports if necessary.
+pub fn create_group_mint_handler(ctx: Context<CreateGroupMint>, max_size: u32) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let group_mint = ctx.accounts.group_mint.to_account_info();
+
+    let cpi_accounts = TokenGroupInitialize {
+        token_program_id: token_program.clone(),
+        group: group_mint.clone(), // Group state is stored on the mint itself.
+        mint: group_mint.clone(),
+        mint_authority: ctx.accounts.authority.to_account_info(),
+    };
+    token_group_initialize(
+        CpiContext::new(token_program, cpi_accounts),
+        Some(ctx.accounts.authority.key()),
+        max_size,
+    )?;
+
+    update_account_lamports_to_minimum_balance(
+        group_mint,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )
+}
+
+/// Accounts required to add a TTT mint to a collection.
+///
+/// **Business Logic:**
+/// - The authority must be the group's update authority, checked by the Token-2022 program.
+/// - The member mint's GroupMemberPointer must point at itself, as TTT mints do.
+#[derive(Accounts)]
+pub struct AddGroupMember<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer for the member state.
+    pub authority: Signer<'info>, // Update authority of the group.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        extensions::group_pointer::group_address = group_mint,
+    )]
+    pub group_mint: Box<InterfaceAccount<'info, Mint>>, // The collection's group mint.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        extensions::group_member_pointer::member_address = member_mint,
+    )]
+    pub member_mint: Box<InterfaceAccount<'info, Mint>>, // The TTT mint joining the collection.
+    /// CHECK: Signing PDA, holding no data.
+    #[account(seeds = [MINT_AUTHORITY_SEED, member_mint.key().as_ref()], bump)]
+    pub member_mint_authority: UncheckedAccount<'info>, // Mint authority PDA of the member.
+    pub system_program: Program<'info, System>, // Solana System program.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for adding a mint to a collection.
+///
+/// **Business Logic:**
+/// - The member mint's authority must sign: the authority itself for soulbound mints, the mint
+///   authority PDA for mints created without revoking it. Mints whose authority was revoked fail
+///   with `MintAuthorityRevoked`; capping the supply at the initial one keeps a season's supply
+///   fixed while letting it join.
+/// - Stores the TokenGroupMember state on the member mint and tops up its rent.
+pub fn add_group_member_handler(ctx: Context<AddGroupMember>) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let member_mint = ctx.accounts.member_mint.to_account_info();
+
+    let (member_mint_authority, sign_with_pda) = match ctx.accounts.member_mint.mint_authority {
+        COption::Some(key) if key == ctx.accounts.authority.key() => {
+            (ctx.accounts.authority.to_account_info(), false)
+        }
+        COption::Some(_) => (ctx.accounts.member_mint_authority.to_account_info(), true),
+        COption::None => return err!(TokenError::MintAuthorityRevoked),
+    };
+
+    let member_mint_key = member_mint.key();
+    let seeds: &[&[&[u8]]] = &[&[
+        MINT_AUTHORITY_SEED,
+        member_mint_key.as_ref(),
+        &[ctx.bumps.member_mint_authority],
+    ]];
+    let cpi_accounts = TokenMemberInitialize {
+        token_program_id: token_program.clone(),
+        member: member_mint.clone(), // Member state is stored on the mint itself.
+        member_mint: member_mint.clone(),
+        member_mint_authority,
+        group: ctx.accounts.group_mint.to_account_info(),
+        group_update_authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = if sign_with_pda {
+        CpiContext::new_with_signer(token_program, cpi_accounts, seeds)
+    } else {
+        CpiContext::new(token_program, cpi_accounts)
+    };
+    token_member_initialize(cpi_ctx)?;
+
+    update_account_lamports_to_minimum_balance(
+        member_mint,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )
+}
+
+/// Accounts required to airdrop TTT to many wallets at once.
+///
+/// **Business Logic:**
+/// - The recipients' token accounts are passed as remaining accounts, in the order of the amounts.
+#[derive(Accounts)]
+pub struct Airdrop<'info> {
+    pub authority: Signer<'info>, // Owner of the source account.
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub source: Box<InterfaceAccount<'info, TokenAccount>>, // Account the TTT is sent from.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for airdropping TTT.
+///
+/// **Business Logic:**
+/// - Fails with `AirdropLengthMismatch` unless there is one amount per recipient account.
+/// - Sends each amount with a checked transfer; recipient accounts of another mint fail in the
+///   Token-2022 program. Mints with an enabled transfer hook are not supported.
+pub fn airdrop_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Airdrop<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    require!(
+        amounts.len() == ctx.remaining_accounts.len(),
+        TokenError::AirdropLengthMismatch
+    );
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    for (recipient, amount) in ctx.remaining_accounts.iter().zip(amounts) {
+        let cpi_accounts = anchor_spl::token_2022::TransferChecked {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.source.to_account_info(),
+            to: recipient.clone(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        anchor_spl::token_2022::transfer_checked(
+            CpiContext::new(token_program.clone(), cpi_accounts),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+    Ok(())
+}
+
+/// TTT escrowed for a beneficiary, unlocking linearly from `start_ts` to `end_ts`.
+#[account]
+#[derive(InitSpace)]
+pub struct Vesting {
+    pub mint: Pubkey,        // The vested mint.
+    pub beneficiary: Pubkey, // Wallet the TTT is released to.
+    pub total: u64,          // TTT escrowed at creation.
+    pub released: u64,       // TTT released so far.
+    pub start_ts: i64,       // Start of the unlock (unix timestamp).
+    pub cliff_ts: i64,       // Nothing is released before this time (unix timestamp).
+    pub end_ts: i64,         // Everything is unlocked from this time (unix timestamp).
+    pub bump: u8,            // Vesting PDA bump.
+}
+
+impl Vesting {
+    /// TTT unlocked at `now`: none before the cliff, then `total` pro rata of the time elapsed
+    /// since `start_ts`, all of it from `end_ts`.
+    pub fn unlocked(&self, now: i64) -> u64 {
+        if now < self.cliff_ts {
+            0
+        } else if now >= self.end_ts {
+            self.total
+        } else {
+            let elapsed = (now - self.start_ts) as u128;
+            let duration = (self.end_ts - self.start_ts) as u128;
+            (self.total as u128 * elapsed / duration) as u64
+        }
+    }
+
+    /// TTT unlocked at `now` and not released yet.
+    pub fn releasable(&self, now: i64) -> u64 {
+        self.unlocked(now).saturating_sub(self.released)
+    }
+}
+
+/// Accounts required to escrow TTT for a beneficiary.
+///
+/// **Business Logic:**
+/// - One Vesting per beneficiary and mint, holding the TTT in its associated token account.
+/// - The TTT comes from the authority's account.
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateVesting<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer for the Vesting and its escrow.
+    pub authority: Signer<'info>, // Owner of the source account.
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub source: Box<InterfaceAccount<'info, TokenAccount>>, // Account the TTT is escrowed from.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Vesting::INIT_SPACE,
+        seeds = [VESTING_SEED, mint.key().as_ref(), beneficiary.as_ref()],
+        bump
+    )]
+    pub vesting: Box<Account<'info, Vesting>>, // Schedule of the beneficiary.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::token_program = token_program,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>, // Account holding the vested TTT.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+    pub associated_token_program: Program<'info, AssociatedToken>, // Associated Token program.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Handler for escrowing TTT under a vesting schedule.
+///
+/// **Business Logic:**
+/// - The schedule starts now; fails with `InvalidVestingSchedule` unless
+///   `now <= cliff_ts <= end_ts` with `end_ts` in the future, or if `total` is zero.
+/// - Records the TTT the escrow received as the total, net of any transfer fee.
+/// - Mints with an enabled transfer hook are not supported.
+pub fn create_vesting_handler(
+    ctx: Context<CreateVesting>,
+    beneficiary: Pubkey,
+    total: u64,
+    cliff_ts: i64,
+    end_ts: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        total > 0 && now <= cliff_ts && cliff_ts <= end_ts && now < end_ts,
+        TokenError::InvalidVestingSchedule
+    );
+
+    let cpi_accounts = anchor_spl::token_2022::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.source.to_account_info(),
+        to: ctx.accounts.escrow.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    anchor_spl::token_2022::transfer_checked(cpi_ctx, total, ctx.accounts.mint.decimals)?;
+    ctx.accounts.escrow.reload()?;
+
+    let vesting = &mut ctx.accounts.vesting;
+    vesting.set_inner(Vesting {
+        mint: ctx.accounts.mint.key(),
+        beneficiary,
+        total: ctx.accounts.escrow.amount,
+        released: 0,
+        start_ts: now,
+        cliff_ts,
+        end_ts,
+        bump: ctx.bumps.vesting,
+    });
+
+    emit!(VestingCreated {
+        vesting: vesting.key(),
+        beneficiary,
+        total: vesting.total,
+        cliff_ts,
+        end_ts,
+    });
+    Ok(())
+}
+
+/// Accounts required to release vested TTT.
+///
+/// **Business Logic:**
+/// - Anyone can release; the TTT only goes to a token account of the beneficiary.
+#[derive(Accounts)]
+pub struct ReleaseVested<'info> {
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, mint.key().as_ref(), vesting.beneficiary.as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Box<Account<'info, Vesting>>, // Schedule of the beneficiary.
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    #[account(
+        mut,
+        associated_token::token_program = token_program,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>, // Account holding the vested TTT.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vesting.beneficiary,
+        token::token_program = token_program,
+    )]
+    pub beneficiary_token_account: Box<InterfaceAccount<'info, TokenAccount>>, // Receives the TTT.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+}
+
+/// Handler for releasing vested TTT.
+///
+/// **Business Logic:**
+/// - Transfers what unlocked since the last release, signed by the Vesting PDA; fails with
+///   `NothingToRelease` if nothing did.
+pub fn release_vested_handler(ctx: Context<ReleaseVested>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vesting = &mut ctx.accounts.vesting;
+    let amount = vesting.releasable(now);
+    require!(amount > 0, TokenError::NothingToRelease);
+    vesting.released += amount;
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds: &[&[&[u8]]] = &[&[
+        VESTING_SEED,
+        mint_key.as_ref(),
+        vesting.beneficiary.as_ref(),
+        &[vesting.bump],
+    ]];
+    let cpi_accounts = anchor_spl::token_2022::TransferChecked {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.escrow.to_account_info(),
+        to: ctx.accounts.beneficiary_token_account.to_account_info(),
+        authority: vesting.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        seeds,
+    );
+    anchor_spl::token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    emit!(VestedReleased {
+        vesting: vesting.key(),
+        beneficiary: vesting.beneficiary,
+        amount,
+        released: vesting.released,
+    });
+    Ok(())
+}
+
+/// Emitted when TTT is escrowed under a vesting schedule.
+#[event]
+pub struct VestingCreated {
+    pub vesting: Pubkey,     // The new Vesting.
+    pub beneficiary: Pubkey, // Wallet the TTT is released to.
+    pub total: u64,          // TTT escrowed.
+    pub cliff_ts: i64,       // Nothing is released before this time (unix timestamp).
+    pub end_ts: i64,         // Everything is unlocked from this time (unix timestamp).
+}
+
+/// Emitted when vested TTT is released to the beneficiary.
+#[event]
+pub struct VestedReleased {
+    pub vesting: Pubkey,     // Vesting the TTT was released from.
+    pub beneficiary: Pubkey, // Wallet receiving the TTT.
+    pub amount: u64,         // TTT released by this call.
+    pub released: u64,       // TTT released so far.
+}
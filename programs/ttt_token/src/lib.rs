@@ -36,8 +36,15 @@ pub mod token_extensions {
     /// Transfers a specified amount of ttt tokens from one admin's token accounnt to another.
     /// Utilizes the Token-2022 program's CPI to ensure safe and verified transfers.
     ///
+    /// Soulbound ttt cannot move, so it is rejected up front with `SoulboundMint`.
+    ///
     /// INFO: Currently used only in tests
     pub fn transfer_tokens(ctx: Context<TransferTokens>, amount: u64) -> Result<()> {
+        require!(
+            !is_non_transferable(&ctx.accounts.mint.to_account_info())?,
+            TokenError::SoulboundMint
+        );
+
         let cpi_accounts = anchor_spl::token_2022::TransferChecked {
             mint: ctx.accounts.mint.to_account_info(), /* The token mint associated with the
                                                         * transfer. */
@@ -49,12 +56,254 @@ pub mod token_extensions {
 
         let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
 
-        // Execute the transfer with zero decimals as specified.
-        anchor_spl::token_2022::transfer_checked(cpi_ctx, amount, 0 /* decimals */)?;
+        // Execute the transfer checked against the mint's decimals.
+        anchor_spl::token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
 
         Ok(())
     }
 
+    /// Creates a non-transferable mint for vote receipts.
+    /// Minting is handed over to `args.mint_authority` once the receipt's metadata is set.
+    pub fn create_receipt_mint(
+        ctx: Context<CreateReceiptMint>,
+        args: CreateReceiptMintArgs,
+    ) -> Result<()> {
+        instructions::create_receipt_mint_handler(ctx, args)
+    }
+
+    /// Mints one vote receipt to the voter, creating their receipt account if needed.
+    ///
+    /// INFO: Called by the governance program through CPI after a vote
+    pub fn mint_vote_receipt(ctx: Context<MintVoteReceipt>) -> Result<()> {
+        let cpi_accounts = anchor_spl::token_2022::MintTo {
+            mint: ctx.accounts.receipt_mint.to_account_info(),
+            to: ctx.accounts.receipt_token_account.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+        // Receipts are whole tokens, one per vote.
+        anchor_spl::token_2022::mint_to(cpi_ctx, 1)?;
+
+        Ok(())
+    }
+
+    /// Collects the transfer fees withheld on TTT into the treasury's fee account.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the mint's withdraw-withheld authority, can collect the fees.
+    /// - Harvests the token accounts passed as remaining accounts, then withdraws all fees
+    ///   withheld on the mint to `destination`.
+    pub fn harvest_and_withdraw_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, HarvestFees<'info>>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::harvest_and_withdraw_fees_handler(ctx)
+    }
+
+    /// Changes the interest rate of an interest-bearing TTT mint.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the mint's rate authority, can change the rate.
+    /// - `rate` is in basis points per year and may be negative.
+    pub fn update_interest_rate(ctx: Context<UpdateInterestRate>, rate: i16) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::update_interest_rate_handler(ctx, rate)
+    }
+
+    /// Sets a field of the TTT token metadata, to fix a typo or rotate the URI.
+    ///
+    /// **Business Logic:**
+    /// - Only the metadata's update authority can edit it.
+    /// - `field` is `name`, `symbol`, `uri` or the key of an additional field.
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        field: String,
+        value: String,
+    ) -> Result<()> {
+        instructions::update_token_metadata_handler(ctx, field, value)
+    }
+
+    /// Removes an additional field of the TTT token metadata.
+    ///
+    /// **Business Logic:**
+    /// - Only the metadata's update authority can edit it.
+    pub fn remove_metadata_field(ctx: Context<UpdateTokenMetadata>, key: String) -> Result<()> {
+        instructions::remove_metadata_field_handler(ctx, key)
+    }
+
+    /// Freezes a TTT token account, e.g. to keep a misbehaving voter out between rounds.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the mint's freeze authority, can freeze accounts.
+    pub fn freeze_account(ctx: Context<SetAccountFrozen>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::set_account_frozen_handler(ctx, true)
+    }
+
+    /// Thaws a TTT token account frozen with `freeze_account`.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the mint's freeze authority, can thaw accounts.
+    pub fn thaw_account(ctx: Context<SetAccountFrozen>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::set_account_frozen_handler(ctx, false)
+    }
+
+    /// Burns the TTT left in the token accounts passed as remaining accounts, winding down a
+    /// finished election token.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the mint's permanent delegate, can burn other wallets' TTT.
+    pub fn burn_remaining_supply<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BurnRemainingSupply<'info>>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::burn_remaining_supply_handler(ctx)
+    }
+
+    /// Closes an empty TTT token account, returning its rent to `destination`.
+    pub fn close_ata(ctx: Context<CloseAta>) -> Result<()> {
+        instructions::close_ata_handler(ctx)
+    }
+
+    /// Closes the TTT mint once its whole supply is burned, recovering its rent.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the mint's close authority, can close it.
+    pub fn close_mint(ctx: Context<CloseMint>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::close_mint_handler(ctx)
+    }
+
+    /// Mints more TTT to `destination`, within the supply cap set at creation.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the mint authority of soulbound mints, can mint more.
+    pub fn mint_more(ctx: Context<MintMore>, amount: u64) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::mint_more_handler(ctx, amount)
+    }
+
+    /// Mints TTT with the mint authority PDA of a mint created without revoking it.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can mint, at most once per the timelock recorded in the MintConfig and
+    ///   within its supply cap.
+    pub fn scheduled_mint(ctx: Context<ScheduledMint>, amount: u64) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::scheduled_mint_handler(ctx, amount)
+    }
+
+    /// Creates the group mint of a collection of TTT mints, e.g. one per season.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can create collections, of at most `max_size` mints.
+    pub fn create_group_mint(ctx: Context<CreateGroupMint>, max_size: u32) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::create_group_mint_handler(ctx, max_size)
+    }
+
+    /// Adds a TTT mint to a collection created with `create_group_mint`.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin, the group's update authority, can add members.
+    pub fn add_group_member(ctx: Context<AddGroupMember>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+
+        instructions::add_group_member_handler(ctx)
+    }
+
+    /// Airdrops TTT from the admin's account to the token accounts passed as remaining accounts,
+    /// `amounts[i]` to the `i`-th.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can airdrop; soulbound TTT is rejected with `SoulboundMint`.
+    pub fn airdrop<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Airdrop<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+        require!(
+            !is_non_transferable(&ctx.accounts.mint.to_account_info())?,
+            TokenError::SoulboundMint
+        );
+
+        instructions::airdrop_handler(ctx, amounts)
+    }
+
+    /// Escrows `total` TTT from the admin's account for `beneficiary`, unlocking linearly until
+    /// `end_ts` with nothing released before `cliff_ts`.
+    ///
+    /// **Business Logic:**
+    /// - Only the admin can create a vesting; soulbound TTT is rejected with `SoulboundMint`.
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        beneficiary: Pubkey,
+        total: u64,
+        cliff_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ADMIN_PUBKEY,
+            TokenError::Unauthorized
+        );
+        require!(
+            !is_non_transferable(&ctx.accounts.mint.to_account_info())?,
+            TokenError::SoulboundMint
+        );
+
+        instructions::create_vesting_handler(ctx, beneficiary, total, cliff_ts, end_ts)
+    }
+
+    /// Releases the vested TTT unlocked so far to the beneficiary. Permissionless.
+    pub fn release_vested(ctx: Context<ReleaseVested>) -> Result<()> {
+        instructions::release_vested_handler(ctx)
+    }
+
     /// Placeholder function to check constraints related to mint extensions.
     /// Currently, it performs no operations but can be expanded to include validation logic.
     pub fn check_mint_extensions_constraints(
@@ -68,4 +317,44 @@ pub mod token_extensions {
 pub enum TokenError {
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Token metadata does not match the mint arguments")]
+    MetadataMismatch,
+    #[msg("MetadataPointer extension is not set to the mint and authority")]
+    MetadataPointerMismatch,
+    #[msg("PermanentDelegate extension is not set to the authority")]
+    PermanentDelegateMismatch,
+    #[msg("MintCloseAuthority extension is not set to the authority")]
+    CloseAuthorityMismatch,
+    #[msg("GroupMemberPointer extension is not set to the mint and authority")]
+    GroupMemberPointerMismatch,
+    #[msg("TransferHook extension is not set to the authority without a program")]
+    TransferHookMismatch,
+    #[msg("TransferFeeConfig extension does not match the mint arguments")]
+    TransferFeeConfigMismatch,
+    #[msg("A maximum fee requires a transfer fee")]
+    MaxFeeWithoutTransferFee,
+    #[msg("InterestBearingConfig extension does not match the mint arguments")]
+    InterestBearingConfigMismatch,
+    #[msg("NonTransferable extension does not match the soulbound flag")]
+    NonTransferableMismatch,
+    #[msg("Soulbound TTT cannot be transferred")]
+    SoulboundMint,
+    #[msg("TTT supply must be burned before the mint is closed")]
+    SupplyNotZero,
+    #[msg("Minting would exceed the TTT supply cap")]
+    SupplyCapExceeded,
+    #[msg("A soulbound mint must keep its mint authority")]
+    SoulboundMintRevoked,
+    #[msg("The next scheduled mint is still timelocked")]
+    MintTimelocked,
+    #[msg("The mint authority was revoked, so the mint cannot join a group")]
+    MintAuthorityRevoked,
+    #[msg("DefaultAccountState extension is not set to frozen")]
+    DefaultAccountStateMismatch,
+    #[msg("The airdrop needs one amount per recipient account")]
+    AirdropLengthMismatch,
+    #[msg("The vesting schedule must end in the future, with its cliff between now and the end")]
+    InvalidVestingSchedule,
+    #[msg("No vested TTT is left to release yet")]
+    NothingToRelease,
 }
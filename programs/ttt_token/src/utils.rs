@@ -7,7 +7,7 @@ use anchor_lang::{
     Lamports,
 };
 use anchor_spl::token_interface::spl_token_2022::{
-    extension::{BaseStateWithExtensions, Extension, StateWithExtensions},
+    extension::{BaseStateWithExtensions, Extension, ExtensionType, StateWithExtensions},
     solana_zk_token_sdk::zk_token_proof_instruction::Pod,
     state::Mint,
 };
@@ -17,6 +17,14 @@ use spl_type_length_value::variable_len_pack::VariableLenPack;
 // Seed constants used for deriving PDAs related to account metadata.
 pub const APPROVE_ACCOUNT_SEED: &[u8] = b"approve-account";
 pub const META_LIST_ACCOUNT_SEED: &[u8] = b"extra-account-metas";
+pub const MINT_CONFIG_SEED: &[u8] = b"mint-config";
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint-authority";
+pub const VESTING_SEED: &[u8] = b"vesting";
+
+/// Address of the PDA holding the mint authority of a mint created without revoking it.
+pub fn mint_authority_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, mint.as_ref()], &crate::ID)
+}
 
 /// Ensures that the specified account has at least the minimum required lamports.
 ///
@@ -28,8 +36,10 @@ pub fn update_account_lamports_to_minimum_balance<'info>(
     payer: AccountInfo<'info>,
     system_program: AccountInfo<'info>,
 ) -> Result<()> {
-    // Calculate the additional lamports needed to reach the minimum balance.
-    let extra_lamports = Rent::get()?.minimum_balance(account.data_len()) - account.get_lamports();
+    // Calculate the additional lamports needed to reach the minimum balance, none if it is met.
+    let extra_lamports = Rent::get()?
+        .minimum_balance(account.data_len())
+        .saturating_sub(account.get_lamports());
     if extra_lamports > 0 {
         // Invoke a system transfer to fund the account.
         invoke(
@@ -68,6 +78,18 @@ pub fn get_mint_extension_data<T: Extension + Pod>(account: &mut AccountInfo) ->
     Ok(extension_data)
 }
 
+/// Whether a mint carries the NonTransferable extension.
+///
+/// **Business Logic:**
+/// - Identifies soulbound TTT, whose tokens never leave the wallet they were minted to.
+pub fn is_non_transferable(account: &AccountInfo) -> Result<bool> {
+    let mint_data = account.data.borrow();
+    let mint_with_extension = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    Ok(mint_with_extension
+        .get_extension_types()?
+        .contains(&ExtensionType::NonTransferable))
+}
+
 /// Constructs a list of additional account metadata based on the presence of an approve account.
 ///
 /// **Business Logic:**
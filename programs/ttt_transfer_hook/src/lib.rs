@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_hook_update, Mint, Token2022, TokenAccount, TransferHookUpdate,
+};
+use governance::{
+    program::Governance, treasury_address, RoundState, VoteLock, VoteManager, VOTE_LOCK_SEED,
+};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+// Declare the program ID to associate this Rust program with the deployed Solana program.
+declare_id!("");
+
+// Seed of the ExtraAccountMetaList the Token-2022 program resolves for every transfer.
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// Transfer hook of the TTT mint, locking voters' TTT while the round they voted in is open.
+///
+/// INFO: Inactive until the mint's transfer-hook authority enables it with `set_transfer_lock`.
+#[program]
+pub mod ttt_transfer_hook {
+    use super::*;
+
+    /// Points the mint's TransferHook extension at this program, or clears it.
+    ///
+    /// **Business Logic:**
+    /// - Only the mint's transfer-hook authority may toggle the lock.
+    /// - Writes the extra account metas of `execute` for `vote_manager`: the governance program,
+    ///   the VoteManager and the source owner's VoteLock. Enabling again rewrites them, so the lock
+    ///   can follow a new election on the same mint.
+    /// - Disabling leaves the metas in place; the Token-2022 program no longer calls the hook.
+    pub fn set_transfer_lock(ctx: Context<SetTransferLock>, enabled: bool) -> Result<()> {
+        if enabled {
+            let metas = extra_account_metas(&ctx.accounts.vote_manager.key())?;
+            let meta_list = ctx.accounts.extra_account_meta_list.to_account_info();
+            let mut data = meta_list.try_borrow_mut_data()?;
+            if data.iter().all(|byte| *byte == 0) {
+                ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)?;
+            } else {
+                ExtraAccountMetaList::update::<ExecuteInstruction>(&mut data, &metas)?;
+            }
+        }
+
+        let cpi_accounts = TransferHookUpdate {
+            token_program_id: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        transfer_hook_update(cpi_ctx, enabled.then_some(crate::ID))
+    }
+
+    /// Called by the Token-2022 program on every TTT transfer once the lock is enabled.
+    ///
+    /// **Business Logic:**
+    /// - Transfers pass while the VoteManager's round is not open, and from wallets without a
+    ///   VoteLock.
+    /// - A wallet whose VoteLock holds the open round can only send TTT to the treasury, so
+    ///   further vote fees still go through; anything else fails with `TransferLocked`.
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn execute(ctx: Context<Execute>, _amount: u64) -> Result<()> {
+        let vote_manager = &ctx.accounts.vote_manager;
+        if vote_manager.round_state != RoundState::Open || ctx.accounts.vote_lock.data_is_empty() {
+            return Ok(());
+        }
+
+        let data = ctx.accounts.vote_lock.try_borrow_data()?;
+        let lock = VoteLock::try_deserialize(&mut &data[..])?;
+        if lock.round == vote_manager.vote_round {
+            let treasury = treasury_address(&vote_manager.key(), &governance::ID).0;
+            require_keys_eq!(
+                ctx.accounts.destination_token.owner,
+                treasury,
+                HookError::TransferLocked
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns the accounts `execute` needs beyond the transfer's own, in order.
+///
+/// **Business Logic:**
+/// - Index 5 is the governance program and index 6 the VoteManager, both fixed.
+/// - Index 7 is the VoteLock PDA of the source account's owner (bytes 32..64 of account 0).
+pub fn extra_account_metas(vote_manager: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&governance::ID, false, false)?,
+        ExtraAccountMeta::new_with_pubkey(vote_manager, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal {
+                    bytes: VOTE_LOCK_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 6 },
+                Seed::AccountData {
+                    account_index: 0,
+                    data_index: 32,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )?,
+    ])
+}
+
+/// Accounts required to enable or disable the transfer lock.
+///
+/// **Business Logic:**
+/// - The mint must name `authority` as its transfer-hook authority.
+/// - Creates the ExtraAccountMetaList on first use; the VoteManager must govern the mint.
+#[derive(Accounts)]
+pub struct SetTransferLock<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer for the ExtraAccountMetaList.
+    pub authority: Signer<'info>, // The mint's transfer-hook authority.
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        extensions::transfer_hook::authority = authority,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, // The governance token mint (ttt).
+    /// CHECK: TLV data written by `set_transfer_lock`.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(extra_account_metas(&vote_manager.key())?.len())?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>, // Accounts resolved for `execute`.
+    #[account(constraint = vote_manager.tk_mint == mint.key() @ HookError::WrongMint)]
+    pub vote_manager: Box<Account<'info, VoteManager>>, // Election whose rounds lock transfers.
+    pub token_program: Program<'info, Token2022>, // SPL Token-2022 program interface.
+    pub system_program: Program<'info, System>, // Solana System program.
+}
+
+/// Accounts passed by the Token-2022 program to `execute`, in the interface's order.
+///
+/// **Business Logic:**
+/// - The first five are fixed by the transfer hook interface; the rest are resolved from the
+///   ExtraAccountMetaList.
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>, // Sender's token account.
+    pub mint: InterfaceAccount<'info, Mint>, // The governance token mint (ttt).
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>, // Recipient's token account.
+    /// CHECK: Owner or delegate of the source account, already checked by the Token-2022 program.
+    pub owner: UncheckedAccount<'info>, // Authority of the transfer.
+    /// CHECK: TLV data written by `set_transfer_lock`.
+    #[account(seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>, // Accounts resolved for `execute`.
+    pub governance_program: Program<'info, Governance>, // The governance program.
+    #[account(constraint = vote_manager.tk_mint == mint.key() @ HookError::WrongMint)]
+    pub vote_manager: Box<Account<'info, VoteManager>>, // Election whose rounds lock transfers.
+    /// CHECK: Sender's VoteLock, which does not exist until their first vote.
+    #[account(
+        seeds = [VOTE_LOCK_SEED, vote_manager.key().as_ref(), source_token.owner.as_ref()],
+        seeds::program = governance_program.key(),
+        bump
+    )]
+    pub vote_lock: UncheckedAccount<'info>, // Round the sender last voted in.
+}
+
+#[error_code]
+pub enum HookError {
+    #[msg("TTT cannot leave a wallet that voted in the open round")]
+    TransferLocked,
+    #[msg("The VoteManager does not govern this mint")]
+    WrongMint,
+}
@@ -3,10 +3,23 @@ import { Program } from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { Governance } from "../target/types/governance";
 import {
+  approveChecked,
+  createAssociatedTokenAccount,
   createAssociatedTokenAccountInstruction,
+  createMint,
+  createTransferCheckedWithTransferHookInstruction,
+  getAccount,
   getAssociatedTokenAddress,
   getAssociatedTokenAddressSync,
+  getInterestBearingMintConfigState,
+  getMint,
+  getTokenGroupMemberState,
+  getTokenGroupState,
+  getTokenMetadata,
+  getTransferHook,
+  mintTo,
   TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   Connection,
@@ -17,7 +30,10 @@ import {
   SignatureResult,
 } from "@solana/web3.js";
 import { TokenExtensions } from "../target/types/token_extensions";
+import { MockComposer } from "../target/types/mock_composer";
+import { TttTransferHook } from "../target/types/ttt_transfer_hook";
 import { ASSOCIATED_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { createHash, createPublicKey, randomBytes, verify } from "crypto";
 
 // -------------------- Constants --------------------
 
@@ -32,6 +48,12 @@ const program = anchor.workspace.Governance as Program<Governance>;
 // Reference to the TokenExtensions program for handling token-related operations.
 const tokenProgram = anchor.workspace.TokenExtensions as Program<TokenExtensions>;
 
+// Reference to the mock third-party program relaying votes through CPIs.
+const composerProgram = anchor.workspace.MockComposer as Program<MockComposer>;
+
+// Reference to the transfer hook locking voters' TTT while their round is open.
+const hookProgram = anchor.workspace.TttTransferHook as Program<TttTransferHook>;
+
 // Define the initial supply of the ttt token.
 const ttt_TOKEN_INITIAL_SUPPLY = 450_000_000;
 
@@ -42,40 +64,130 @@ const VOTER_NAMESPACE = "voter";
 const DEFAULT_AIRDROP_SOL = 1;
 
 const EXTRA_ACCOUNT_METAS = "extra-account-metas";
+const MINT_CONFIG = "mint-config";
+
+// spl-account-compression and spl-noop, cloned into the test validator for compressed votes.
+const ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+const NOOP_PROGRAM_ID = new PublicKey("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
 
 // -------------------- Helper Functions --------------------
 
+/**
+ * Encodes a round number as it appears in PDA seeds.
+ * @param round - Voting round.
+ * @returns The round as 8 little-endian bytes.
+ */
+function roundSeed(round: number): Buffer {
+  return new anchor.BN(round).toArrayLike(Buffer, "le", 8);
+}
+
+/**
+ * Encodes an election id as it appears in PDA seeds.
+ * @param electionId - Election id.
+ * @returns No bytes for election 0, the id as 8 little-endian bytes otherwise.
+ */
+function electionSeed(electionId: number): Buffer {
+  return electionId === 0 ? Buffer.alloc(0) : new anchor.BN(electionId).toArrayLike(Buffer, "le", 8);
+}
+
+/**
+ * Derives the VoteManager PDA of an admin's election.
+ * @param adminPubkey - Initializing admin's public key.
+ * @param electionId - Election id, 0 by default.
+ * @returns PublicKey of the VoteManager PDA.
+ */
+function deriveVoteManagerPda(adminPubkey: PublicKey, electionId = 0): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vote_manager"), adminPubkey.toBuffer(), electionSeed(electionId)],
+    program.programId
+  )[0];
+}
+
 /**
  * Derives a Project PDA based on project index, round, and admin public key.
  * @param projectId - Unique identifier for the project.
  * @param round - Current voting round.
  * @param adminPubkey - Admin's public key.
+ * @param electionId - Election id, 0 by default.
  * @returns PublicKey of the Project PDA.
  */
-function deriveProjectPda(projectId: string, round: number, adminPubkey: PublicKey): PublicKey {
-  // Use a single-byte buffer for the round number as per the original logic.
+function deriveProjectPda(projectId: string, round: number, adminPubkey: PublicKey, electionId = 0): PublicKey {
   return PublicKey.findProgramAddressSync(
     [
+      Buffer.from("project"),
       Buffer.from(projectId),
-      Buffer.from([round]), // 1-byte round number without padding
+      roundSeed(round), // 8-byte round number, little-endian
       adminPubkey.toBuffer(),
+      electionSeed(electionId),
     ],
     program.programId
   )[0];
 }
 
+/**
+ * Derives an OperationMarker PDA from the VoteManager and the operation key.
+ * @param voteManager - VoteManager the operation applies to.
+ * @param key - 32-byte operation key.
+ * @returns PublicKey of the OperationMarker PDA.
+ */
+function deriveOperationMarkerPda(voteManager: PublicKey, key: Buffer): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("operation_marker"), voteManager.toBuffer(), key],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives a RoleGrant PDA from the VoteManager and the role holder.
+ * @param voteManager - VoteManager the role applies to.
+ * @param holder - Wallet holding the role.
+ * @returns PublicKey of the RoleGrant PDA.
+ */
+function deriveRolePda(voteManager: PublicKey, holder: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("role"), voteManager.toBuffer(), holder.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives the AdminCouncil PDA of a VoteManager.
+ * @param voteManager - VoteManager the council administers.
+ * @returns PublicKey of the AdminCouncil PDA.
+ */
+function deriveAdminCouncilPda(voteManager: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_council"), voteManager.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives an AdminAction PDA from its council and id.
+ * @param council - AdminCouncil the action is proposed to.
+ * @param actionId - Sequential action id.
+ * @returns PublicKey of the AdminAction PDA.
+ */
+function deriveAdminActionPda(council: PublicKey, actionId: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_action"), council.toBuffer(), new anchor.BN(actionId).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
+
 /**
  * Derives a Voter PDA based on round and voter's public key.
  * @param round - Current voting round.
  * @param voterPubkey - Voter's public key.
+ * @param electionId - Election id, 0 by default.
  * @returns PublicKey of the Voter PDA.
  */
-function deriveVoterPda(round: number, voterPubkey: PublicKey, projectId: string): PublicKey {
-  // Use a 6-byte buffer with padding as per the original logic.
+function deriveVoterPda(round: number, voterPubkey: PublicKey, projectId: string, electionId = 0): PublicKey {
   return PublicKey.findProgramAddressSync(
     [
       Buffer.from(VOTER_NAMESPACE),
-      Buffer.from([round, 1, 1, 1, 1]), // 1-byte round number with 5-byte padding
+      electionSeed(electionId),
+      roundSeed(round), // 8-byte round number, little-endian
       voterPubkey.toBuffer(),
       Buffer.from(projectId),
     ],
@@ -84,1038 +196,8396 @@ function deriveVoterPda(round: number, voterPubkey: PublicKey, projectId: string
 }
 
 /**
- * Derives the Mint Token Account PDA for a given token mint and admin.
- * This account holds the tokens minted and managed by the admin.
- *
- * @param mintPubkey - Token mint's public key.
- * @param adminPubkey - Admin's public key.
- * @returns PublicKey of the Mint Token Account PDA.
+ * Derives a RoundResult PDA based on the VoteManager and round.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param round - Finalized voting round.
+ * @returns PublicKey of the RoundResult PDA.
  */
-function deriveMintTokenAccount(mintPubkey: PublicKey, adminPubkey: PublicKey): PublicKey {
-  return getAssociatedTokenAddressSync(
-    mintPubkey,
-    adminPubkey,
-    true,
-    TOKEN_2022_PROGRAM_ID,
-    ASSOCIATED_PROGRAM_ID
-  );
+function deriveRoundResultPda(voteManagerPubkey: PublicKey, round: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("round_result"), voteManagerPubkey.toBuffer(), roundSeed(round)],
+    program.programId
+  )[0];
 }
 
+/**
+ * Derives the RoundArchive PDA of a VoteManager's round.
+ */
+function deriveRoundArchivePda(voteManagerPubkey: PublicKey, round: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("round_archive"), voteManagerPubkey.toBuffer(), roundSeed(round)],
+    program.programId
+  )[0];
+}
 
 /**
-* Generates project unique identifier
-*/
-function generateProjectId(length = 20): string {
-  const chars = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
-  let result = "";
-  for (let i = 0; i < length; i++) {
-    result += chars.charAt(Math.floor(Math.random() * chars.length));
-  }
-  return result;
+ * Derives the TallyState PDA of a VoteManager's round.
+ */
+function deriveTallyStatePda(voteManagerPubkey: PublicKey, round: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tally_state"), voteManagerPubkey.toBuffer(), roundSeed(round)],
+    program.programId
+  )[0];
 }
 
-// Usage:
-const doubleVoteProjectId = generateProjectId(20);
-console.log(doubleVoteProjectId); // e.g. "aB3xYz12WpQ9..." (20 random chars)
 /**
- * Airdrops SOL to a given public key if the balance is below a specified threshold.
- * Ensures that test accounts have sufficient funds to cover transaction fees.
- *
- * @param connection - Solana connection object.
- * @param publicKey - Public key to airdrop SOL to.
- * @param minBalanceInSol - Minimum balance required in SOL.
+ * Derives the RoundAttestation PDA of a RoundResult.
+ * @param roundResultPubkey - RoundResult's public key.
+ * @returns PublicKey of the RoundAttestation PDA.
  */
-async function airdropIfNeeded(
-  connection: Connection,
-  publicKey: PublicKey,
-  minBalanceInSol: number = DEFAULT_AIRDROP_SOL
-): Promise<void> {
-  try {
-    // Retrieve the current balance of the account in lamports.
-    const currentBalance = await connection.getBalance(publicKey);
-    const currentBalanceInSol = currentBalance / LAMPORTS_PER_SOL;
+function deriveRoundAttestationPda(roundResultPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("round_attestation"), roundResultPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
 
-    // Check if the current balance meets the minimum required balance.
-    if (currentBalanceInSol < minBalanceInSol) {
-      const requiredAirdrop = minBalanceInSol - currentBalanceInSol;
+/**
+ * Mirrors `RoundResult::canonical_json`: sorted keys, no whitespace, integers as decimals, pubkeys
+ * in base58 and the config hash in hex.
+ * @param result - Fetched RoundResult account.
+ * @returns The canonical JSON bytes signed by attestations.
+ */
+function canonicalRoundResultJson(result: any): Buffer {
+  return Buffer.from(
+    `{"config_hash":"${Buffer.from(result.configHash).toString("hex")}",` +
+      `"eligible_weight":${result.eligibleWeight.toString()},` +
+      `"finalized_at":${result.finalizedAt.toString()},` +
+      `"participation_count":${result.participationCount.toString()},` +
+      `"round":${result.round.toString()},` +
+      `"total_votes":${result.totalVotes.toString()},` +
+      `"total_weight":${result.totalWeight.toString()},` +
+      `"turnout_bps":${result.turnoutBps.toString()},` +
+      `"vote_manager":"${result.voteManager.toBase58()}",` +
+      `"winner":"${result.winner.toBase58()}",` +
+      `"winner_id":${JSON.stringify(result.winnerId)},` +
+      `"winner_weight":${result.winnerWeight.toString()}}`
+  );
+}
 
-      // Request an airdrop of the required amount.
-      const signature = await connection.requestAirdrop(
-        publicKey,
-        requiredAirdrop * LAMPORTS_PER_SOL
-      );
+/**
+ * Derives the Campaign PDA of a VoteManager.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @returns PublicKey of the Campaign PDA.
+ */
+function deriveCampaignPda(voteManagerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("campaign"), voteManagerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
 
-      // Confirm the airdrop transaction.
-      const latestBlockhash = await connection.getLatestBlockhash();
-      const confirmationResult: RpcResponseAndContext<SignatureResult> =
-        await connection.confirmTransaction(
-          {
-            signature,
-            blockhash: latestBlockhash.blockhash,
-            lastValidBlockHeight: latestBlockhash.lastValidBlockHeight,
-          },
-          "finalized"
-        );
+/**
+ * Derives a VoteCommit PDA based on the VoteManager, round, and voter.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param round - Round of the commitment.
+ * @param voterPubkey - Voter's public key.
+ * @returns PublicKey of the VoteCommit PDA.
+ */
+function deriveVoteCommitPda(voteManagerPubkey: PublicKey, round: number, voterPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vote_commit"), voteManagerPubkey.toBuffer(), roundSeed(round), voterPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
 
-      // Handle any errors that occurred during the airdrop.
-      if (confirmationResult.value.err) {
-        throw new Error(
-          `Airdrop transaction failed: ${JSON.stringify(confirmationResult.value.err)}`
-        );
-      }
+/**
+ * Derives a Ballot PDA based on the VoteManager, round, and voter.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param round - Round of the ballot.
+ * @param voterPubkey - Voter's public key.
+ * @returns PublicKey of the Ballot PDA.
+ */
+function deriveBallotPda(voteManagerPubkey: PublicKey, round: number, voterPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("ballot"), voteManagerPubkey.toBuffer(), roundSeed(round), voterPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
 
-      console.log(
-        `Airdropped ${requiredAirdrop.toFixed(2)} SOL to ${publicKey.toBase58()}.`
-      );
-    } else {
-      console.log(
-        `Account ${publicKey.toBase58()} already has ${currentBalanceInSol.toFixed(
-          2
-        )} SOL, no airdrop needed.`
-      );
-    }
-  } catch (error) {
-    console.error("Error in airdropIfNeeded function:", error);
-    throw error;
-  }
+/**
+ * Derives the RoundVotes PDA counting a voter's votes in a round.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param round - Round of the votes.
+ * @param voter - Voter's public key, or blinded id for stealth votes.
+ * @returns PublicKey of the RoundVotes PDA.
+ */
+function deriveRoundVotesPda(voteManagerPubkey: PublicKey, round: number, voter: Buffer): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("round_votes"), voteManagerPubkey.toBuffer(), roundSeed(round), voter],
+    program.programId
+  )[0];
 }
 
 /**
- * Fetches the token balance for a given token account.
- * This function retrieves the balance in a human-readable format (UI amount).
- *
- * @param connection - Solana connection object.
- * @param tokenAccount - Token account public key.
- * @returns Balance in UI amount.
+ * Derives the RankedTally PDA of a round.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param round - Tallied round.
+ * @returns PublicKey of the RankedTally PDA.
  */
-async function getTokenBalance(connection: Connection, tokenAccount: PublicKey): Promise<number> {
-  const balance = await connection.getTokenAccountBalance(tokenAccount);
-  return balance.value.uiAmount || 0;
+function deriveRankedTallyPda(voteManagerPubkey: PublicKey, round: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("ranked_tally"), voteManagerPubkey.toBuffer(), roundSeed(round)],
+    program.programId
+  )[0];
 }
 
-// -------------------- Test Suite --------------------
+/**
+ * Derives the Leaderboard PDA of a round.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param round - Round ranked.
+ * @returns PublicKey of the Leaderboard PDA.
+ */
+function deriveLeaderboardPda(voteManagerPubkey: PublicKey, round: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), voteManagerPubkey.toBuffer(), roundSeed(round)],
+    program.programId
+  )[0];
+}
 
-describe("ttt-labs-tests", () => {
-  // -------------------- Variable Declarations --------------------
-  let tokenMint: Keypair; // Keypair for the token mint authority.
-  let voterA: Keypair; // Keypair representing a voter.
-  let voterAAta: PublicKey; // Associated Token Account for the voter.
-  let voterB: Keypair; // Keypair representing a voter.
-  let voterBAta: PublicKey; // Associated Token Account for the voter.
-  let insufficientUser: Keypair; // Associated Token Account for the voter.
-  let mintTokenAccount: PublicKey; // Token account holding the minted tokens.
-  let adminWallet: anchor.Wallet; // Admin's wallet, used to sign transactions.
-  let admin: Keypair; // Keypair corresponding to the admin's wallet.
-  let voteManagerPda: PublicKey; // PDA for managing voting rounds and projects.
-  let extraMetasAccount: PublicKey; // Additional metadata account PDA.
-  let projectId: string; // Identifier for a specific project.
-  let unauthorizedAttacker: Keypair; // Keypair representing an unauthorized user attempting actions.
+/**
+ * Derives the Treasury PDA of a VoteManager.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @returns PublicKey of the Treasury PDA.
+ */
+function deriveTreasuryPda(voteManagerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury"), voteManagerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
 
-  // -------------------- Hooks --------------------
-  before(async () => {
-    try {
-      // Setup initial values..
-      tokenMint = Keypair.generate();
-      voterA = Keypair.generate();
-      voterB = Keypair.generate();
-      insufficientUser = Keypair.generate();
-      adminWallet = provider.wallet as anchor.Wallet;
-      admin = adminWallet.payer;
-      unauthorizedAttacker = Keypair.generate();
+/**
+ * Derives the RewardVault PDA of a round.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param round - Round of the rewards.
+ * @returns PublicKey of the RewardVault PDA.
+ */
+function deriveRewardVaultPda(voteManagerPubkey: PublicKey, round: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_vault"), voteManagerPubkey.toBuffer(), roundSeed(round)],
+    program.programId
+  )[0];
+}
 
-      // Derive the VoteManager PDA using the "vote_manager" seed and admin's public key.
-      voteManagerPda = PublicKey.findProgramAddressSync(
-        [Buffer.from("vote_manager"), adminWallet.publicKey.toBuffer()],
-        program.programId
-      )[0];
+/**
+ * Derives a Proposal PDA based on the VoteManager and proposal id.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param proposalId - Sequential proposal id.
+ * @returns PublicKey of the Proposal PDA.
+ */
+function deriveProposalPda(voteManagerPubkey: PublicKey, proposalId: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("proposal"), voteManagerPubkey.toBuffer(), new anchor.BN(proposalId).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
 
-      // Define a project identifier.
-      projectId = "projectVote1";
+/**
+ * Derives a ProposalVote PDA based on the proposal and voter.
+ * @param proposalPubkey - Proposal's public key.
+ * @param voterPubkey - Voter's public key.
+ * @returns PublicKey of the ProposalVote PDA.
+ */
+function deriveProposalVotePda(proposalPubkey: PublicKey, voterPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("proposal_vote"), proposalPubkey.toBuffer(), voterPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
 
-      // Derive the extraMetasAccount PDA using specific seeds.
-      extraMetasAccount = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from(EXTRA_ACCOUNT_METAS),
-          tokenMint.publicKey.toBuffer(),
-        ],
-        tokenProgram.programId
+/**
+ * Derives a Referendum PDA based on the VoteManager and referendum id.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param referendumId - Sequential referendum id.
+ * @returns PublicKey of the Referendum PDA.
+ */
+function deriveReferendumPda(voteManagerPubkey: PublicKey, referendumId: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referendum"), voteManagerPubkey.toBuffer(), new anchor.BN(referendumId).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives a ReferendumVote PDA based on the referendum and voter.
+ * @param referendumPubkey - Referendum's public key.
+ * @param voterPubkey - Voter's public key.
+ * @returns PublicKey of the ReferendumVote PDA.
+ */
+function deriveReferendumVotePda(referendumPubkey: PublicKey, voterPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referendum_vote"), referendumPubkey.toBuffer(), voterPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives the governance authority PDA that signs executed proposals.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @returns PublicKey of the governance authority PDA.
+ */
+function deriveGovernanceAuthorityPda(voteManagerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("governance_authority"), voteManagerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives the receipt authority PDA that mints vote receipts.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @returns PublicKey of the receipt authority PDA.
+ */
+function deriveReceiptAuthorityPda(voteManagerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("receipt_authority"), voteManagerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives a voter's StakePosition PDA.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param ownerPubkey - Staking wallet's public key.
+ * @returns PublicKey of the StakePosition PDA.
+ */
+function deriveStakePositionPda(voteManagerPubkey: PublicKey, ownerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stake_position"), voteManagerPubkey.toBuffer(), ownerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives a voter's HoldingAttestation PDA.
+ * @param voteManagerPubkey - VoteManager's public key.
+ * @param ownerPubkey - Holding wallet's public key.
+ * @returns PublicKey of the HoldingAttestation PDA.
+ */
+function deriveHoldingAttestationPda(voteManagerPubkey: PublicKey, ownerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("holding_attestation"), voteManagerPubkey.toBuffer(), ownerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives the allowlist or blocklist AccessListEntry PDA of a voter in an election.
+ */
+function deriveAccessEntryPda(
+  list: "allowlist" | "blocklist",
+  voteManagerPubkey: PublicKey,
+  voterPubkey: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(list), voteManagerPubkey.toBuffer(), voterPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives the FeeDiscountSchedule PDA of an election.
+ */
+function deriveFeeDiscountPda(voteManagerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_discount"), voteManagerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives the CategoryRegistry PDA naming a VoteManager's project categories.
+ */
+function deriveCategoryRegistryPda(voteManagerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("category_registry"), voteManagerPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Derives the VoteTree PDA recording a VoteManager's compressed votes.
+ */
+function deriveVoteTreePda(voteManagerPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("vote_tree"), voteManagerPubkey.toBuffer()], program.programId)[0];
+}
+
+/**
+ * Size of an spl-account-compression tree account without a canopy: the header, the tree's
+ * counters, its changelog buffer and its rightmost path.
+ */
+function merkleTreeSpace(maxDepth: number, maxBufferSize: number): number {
+  return 56 + 24 + (maxBufferSize + 1) * (40 + 32 * maxDepth);
+}
+
+/**
+ * Reads the current root of a concurrent Merkle tree and the proof of its rightmost leaf, the
+ * last vote appended, from the tree account's data.
+ */
+function rightmostLeafProof(data: Buffer, maxDepth: number, maxBufferSize: number) {
+  const changeLogSize = 40 + 32 * maxDepth;
+  const activeIndex = Number(data.readBigUInt64LE(56 + 8));
+  const rootOffset = 80 + activeIndex * changeLogSize;
+  const pathOffset = 80 + maxBufferSize * changeLogSize;
+  const proof = Array.from({ length: maxDepth }, (_, i) => new PublicKey(data.subarray(pathOffset + 32 * i, pathOffset + 32 * (i + 1))));
+  return {
+    root: Array.from(data.subarray(rootOffset, rootOffset + 32)),
+    proof,
+    index: data.readUInt32LE(pathOffset + 32 * maxDepth + 32) - 1,
+  };
+}
+
+/**
+ * Derives the VoteNonce PDA of a voter in an election.
+ */
+function deriveVoteNoncePda(voteManagerPubkey: PublicKey, voterPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vote_nonce"), voteManagerPubkey.toBuffer(), voterPubkey.toBuffer()],
+    program.programId
+  )[0];
+}
+
+/**
+ * Builds the message a voter signs to authorize `vote_with_authorization`.
+ */
+function voteAuthorizationMessage(voter: PublicKey, project: PublicKey, round: number, nonce: number): Buffer {
+  return Buffer.concat([
+    Buffer.from("ttt:vote_authorization"),
+    voter.toBuffer(),
+    project.toBuffer(),
+    roundSeed(round),
+    new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+/**
+ * Derives a stealth VoterData PDA based on round, blinded voter id and project.
+ * @param round - Current voting round.
+ * @param blindedVoter - Blinded voter id, see `stealthVoterId`.
+ * @param projectId - Project identifier.
+ * @param electionId - Election id, 0 by default.
+ * @returns PublicKey of the stealth VoterData PDA.
+ */
+function deriveStealthVoterPda(round: number, blindedVoter: number[], projectId: string, electionId = 0): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("stealth_voter"),
+      electionSeed(electionId),
+      roundSeed(round),
+      Buffer.from(blindedVoter),
+      Buffer.from(projectId),
+    ],
+    program.programId
+  )[0];
+}
+
+/**
+ * Computes the blinded voter id `sha256("stealth_voter" || voter || nonce)`.
+ */
+function stealthVoterId(voterPubkey: PublicKey, nonce: Buffer): number[] {
+  return Array.from(
+    createHash("sha256").update(Buffer.concat([Buffer.from("stealth_voter"), voterPubkey.toBuffer(), nonce])).digest()
+  );
+}
+
+/**
+ * Reads a zero-padded string field of a zero-copy account, e.g. ProjectData's `id` of `idLen` bytes.
+ */
+function fixedString(bytes: number[], len: number): string {
+  return Buffer.from(bytes.slice(0, len)).toString();
+}
+
+/**
+ * Computes the commit–reveal commitment `sha256(voter || projectId || salt)`.
+ */
+function voteCommitment(voterPubkey: PublicKey, projectId: string, salt: Buffer): number[] {
+  return Array.from(
+    createHash("sha256")
+      .update(Buffer.concat([voterPubkey.toBuffer(), Buffer.from(projectId), salt]))
+      .digest()
+  );
+}
+
+/**
+ * Computes the eligibility snapshot leaf `sha256(voter || weight)`.
+ */
+function eligibilityLeaf(voterPubkey: PublicKey, weight: number): Buffer {
+  return createHash("sha256")
+    .update(Buffer.concat([voterPubkey.toBuffer(), new anchor.BN(weight).toArrayLike(Buffer, "le", 8)]))
+    .digest();
+}
+
+/**
+ * Builds the levels of an eligibility tree like `eligibility_tree`: siblings are hashed in sorted
+ * order and an unpaired node moves up unchanged.
+ * @param leaves - Snapshot leaves, see `eligibilityLeaf`.
+ * @returns The tree levels, from the leaves up to the root.
+ */
+function eligibilityTree(leaves: Buffer[]): Buffer[][] {
+  const levels = [leaves];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      if (i + 1 === level.length) {
+        next.push(level[i]);
+      } else {
+        const [a, b] = [level[i], level[i + 1]].sort(Buffer.compare);
+        next.push(createHash("sha256").update(Buffer.concat([a, b])).digest());
+      }
+    }
+    levels.push(next);
+  }
+  return levels;
+}
+
+/**
+ * Returns the proof of the leaf at `index`, the sibling hashes from the leaf up to the root.
+ */
+function eligibilityProof(levels: Buffer[][], index: number): number[][] {
+  const proof: number[][] = [];
+  for (const level of levels.slice(0, -1)) {
+    const sibling = level[index ^ 1];
+    if (sibling) {
+      proof.push(Array.from(sibling));
+    }
+    index = Math.floor(index / 2);
+  }
+  return proof;
+}
+
+/**
+ * Derives the Mint Token Account PDA for a given token mint and admin.
+ * This account holds the tokens minted and managed by the admin.
+ *
+ * @param mintPubkey - Token mint's public key.
+ * @param adminPubkey - Admin's public key.
+ * @returns PublicKey of the Mint Token Account PDA.
+ */
+function deriveMintTokenAccount(mintPubkey: PublicKey, adminPubkey: PublicKey): PublicKey {
+  return getAssociatedTokenAddressSync(
+    mintPubkey,
+    adminPubkey,
+    true,
+    TOKEN_2022_PROGRAM_ID,
+    ASSOCIATED_PROGRAM_ID
+  );
+}
+
+/**
+ * Derives the MintConfig PDA holding the supply cap of a token mint.
+ *
+ * @param mintPubkey - Token mint's public key.
+ * @returns PublicKey of the MintConfig PDA.
+ */
+function deriveMintConfig(mintPubkey: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(MINT_CONFIG), mintPubkey.toBuffer()],
+    tokenProgram.programId
+  )[0];
+}
+
+
+/**
+* Generates project unique identifier
+*/
+function generateProjectId(length = 20): string {
+  const chars = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+  let result = "";
+  for (let i = 0; i < length; i++) {
+    result += chars.charAt(Math.floor(Math.random() * chars.length));
+  }
+  return result;
+}
+
+// Usage:
+const doubleVoteProjectId = generateProjectId(20);
+console.log(doubleVoteProjectId); // e.g. "aB3xYz12WpQ9..." (20 random chars)
+/**
+ * Airdrops SOL to a given public key if the balance is below a specified threshold.
+ * Ensures that test accounts have sufficient funds to cover transaction fees.
+ *
+ * @param connection - Solana connection object.
+ * @param publicKey - Public key to airdrop SOL to.
+ * @param minBalanceInSol - Minimum balance required in SOL.
+ */
+async function airdropIfNeeded(
+  connection: Connection,
+  publicKey: PublicKey,
+  minBalanceInSol: number = DEFAULT_AIRDROP_SOL
+): Promise<void> {
+  try {
+    // Retrieve the current balance of the account in lamports.
+    const currentBalance = await connection.getBalance(publicKey);
+    const currentBalanceInSol = currentBalance / LAMPORTS_PER_SOL;
+
+    // Check if the current balance meets the minimum required balance.
+    if (currentBalanceInSol < minBalanceInSol) {
+      const requiredAirdrop = minBalanceInSol - currentBalanceInSol;
+
+      // Request an airdrop of the required amount.
+      const signature = await connection.requestAirdrop(
+        publicKey,
+        requiredAirdrop * LAMPORTS_PER_SOL
+      );
+
+      // Confirm the airdrop transaction.
+      const latestBlockhash = await connection.getLatestBlockhash();
+      const confirmationResult: RpcResponseAndContext<SignatureResult> =
+        await connection.confirmTransaction(
+          {
+            signature,
+            blockhash: latestBlockhash.blockhash,
+            lastValidBlockHeight: latestBlockhash.lastValidBlockHeight,
+          },
+          "finalized"
+        );
+
+      // Handle any errors that occurred during the airdrop.
+      if (confirmationResult.value.err) {
+        throw new Error(
+          `Airdrop transaction failed: ${JSON.stringify(confirmationResult.value.err)}`
+        );
+      }
+
+      console.log(
+        `Airdropped ${requiredAirdrop.toFixed(2)} SOL to ${publicKey.toBase58()}.`
+      );
+    } else {
+      console.log(
+        `Account ${publicKey.toBase58()} already has ${currentBalanceInSol.toFixed(
+          2
+        )} SOL, no airdrop needed.`
+      );
+    }
+  } catch (error) {
+    console.error("Error in airdropIfNeeded function:", error);
+    throw error;
+  }
+}
+
+/**
+ * Fetches the token balance for a given token account.
+ * This function retrieves the balance in a human-readable format (UI amount).
+ *
+ * @param connection - Solana connection object.
+ * @param tokenAccount - Token account public key.
+ * @returns Balance in UI amount.
+ */
+async function getTokenBalance(connection: Connection, tokenAccount: PublicKey): Promise<number> {
+  const balance = await connection.getTokenAccountBalance(tokenAccount);
+  return balance.value.uiAmount || 0;
+}
+
+/**
+ * Parses the governance program events emitted by a confirmed transaction.
+ * @param signature - Transaction signature.
+ * @returns The decoded events, in emission order.
+ */
+async function fetchEvents(signature: string): Promise<anchor.Event[]> {
+  const tx = await provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new anchor.EventParser(program.programId, program.coder);
+  return Array.from(parser.parseLogs(tx?.meta?.logMessages ?? []));
+}
+
+// -------------------- Test Suite --------------------
+
+describe("ttt-labs-tests", () => {
+  // -------------------- Variable Declarations --------------------
+  let tokenMint: Keypair; // Keypair for the token mint authority.
+  let voterA: Keypair; // Keypair representing a voter.
+  let voterAAta: PublicKey; // Associated Token Account for the voter.
+  let voterB: Keypair; // Keypair representing a voter.
+  let voterBAta: PublicKey; // Associated Token Account for the voter.
+  let insufficientUser: Keypair; // Associated Token Account for the voter.
+  let mintTokenAccount: PublicKey; // Token account holding the minted tokens.
+  let treasuryPda: PublicKey; // Treasury PDA collecting the voting fees.
+  let treasuryTokenAccount: PublicKey; // Treasury's token account receiving the voting fees.
+  let adminWallet: anchor.Wallet; // Admin's wallet, used to sign transactions.
+  let admin: Keypair; // Keypair corresponding to the admin's wallet.
+  let voteManagerPda: PublicKey; // PDA for managing voting rounds and projects.
+  let extraMetasAccount: PublicKey; // Additional metadata account PDA.
+  let projectId: string; // Identifier for a specific project.
+  let unauthorizedAttacker: Keypair; // Keypair representing an unauthorized user attempting actions.
+
+  // -------------------- Hooks --------------------
+  before(async () => {
+    try {
+      // Setup initial values..
+      tokenMint = Keypair.generate();
+      voterA = Keypair.generate();
+      voterB = Keypair.generate();
+      insufficientUser = Keypair.generate();
+      adminWallet = provider.wallet as anchor.Wallet;
+      admin = adminWallet.payer;
+      unauthorizedAttacker = Keypair.generate();
+
+      // Derive the VoteManager PDA using the "vote_manager" seed and admin's public key.
+      voteManagerPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("vote_manager"), adminWallet.publicKey.toBuffer()],
+        program.programId
+      )[0];
+
+      // Define a project identifier.
+      projectId = "projectVote1";
+
+      // Derive the extraMetasAccount PDA using specific seeds.
+      extraMetasAccount = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(EXTRA_ACCOUNT_METAS),
+          tokenMint.publicKey.toBuffer(),
+        ],
+        tokenProgram.programId
       )[0];
 
-      console.log("\nPerforming necessary airdrops...\n");
+      console.log("\nPerforming necessary airdrops...\n");
+
+      // Airdrop SOL to the voter and unauthorized attacker to ensure they can cover transaction fees.
+      await airdropIfNeeded(provider.connection, voterA.publicKey, DEFAULT_AIRDROP_SOL);
+      await airdropIfNeeded(provider.connection, voterB.publicKey, DEFAULT_AIRDROP_SOL);
+      await airdropIfNeeded(provider.connection, unauthorizedAttacker.publicKey, DEFAULT_AIRDROP_SOL);
+      await airdropIfNeeded(provider.connection, insufficientUser.publicKey, DEFAULT_AIRDROP_SOL);
+
+      // Define the accounts required for creating the token mint and associated accounts.
+      const accountsStrict = {
+        payer: provider.publicKey, // The account paying for the transaction fees.
+        authority: provider.publicKey, // The authority for the mint.
+        mint: tokenMint.publicKey, // The public key for the token mint.
+        mintTokenAccount: deriveMintTokenAccount(tokenMint.publicKey, adminWallet.publicKey), // The token account PDA.
+        extraMetasAccount: extraMetasAccount, // Additional metadata account PDA.
+        mintConfig: deriveMintConfig(tokenMint.publicKey), // Supply cap PDA.
+        systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID, // Associated Token program ID.
+        tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
+      };
+
+      // Create the token mint with initial supply and associated metadata.
+      await tokenProgram.methods
+        .createMintAccount({
+          name: "TTT Labs Token", // Name of the token.
+          symbol: "ttt", // Symbol for the token.
+          uri: "https://my-token-data.com/metadata.json", // URI pointing to token metadata.
+          decimals: 0, // Whole tokens.
+          initialSupply: new anchor.BN(ttt_TOKEN_INITIAL_SUPPLY), // Initial supply of the token.
+          maxSupply: null, // Fixed supply, no cap needed.
+          revokeMintAuthority: true, // Fix the total supply.
+          mintTimelock: 0, // No scheduled mints.
+          transferFeeBasisPoints: null, // No transfer fee.
+          maxFee: null,
+          interestRate: null, // No interest.
+          soulbound: false, // Transferable.
+          defaultFrozen: false, // New accounts start usable.
+          freezeAuthority: null, // The admin stays freeze authority.
+        })
+        .accountsStrict(accountsStrict)
+        .signers([tokenMint, admin]) // Signers required for the transaction.
+        .rpc();
+
+      // Derive the mint token account PDA after mint creation.
+      mintTokenAccount = deriveMintTokenAccount(tokenMint.publicKey, adminWallet.publicKey);
+
+      // Define the accounts required to initialize the VoteManager.
+      const initializeAccounts = {
+        voteData: voteManagerPda, // PDA for the VoteManager.
+        owner: adminWallet.publicKey, // Admin's public key as the owner.
+        mint: tokenMint.publicKey, // The token mint, verified against the token program.
+        adminTokenAccount: mintTokenAccount, // Admin's fee account, which must exist.
+        tokenProgram: TOKEN_2022_PROGRAM_ID, // Token program owning the mint.
+        systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
+      };
+
+      // Initialize the VoteManager of election 0 with the token mint, token program, and vote fee.
+      await program.methods
+        .initialize(new anchor.BN(0), new anchor.BN(100))
+        .accounts(initializeAccounts)
+        .rpc();
+
+      // Fetch and assert the initial state of the VoteManager to ensure correct initialization.
+      const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      expect(voteManagerAccount.voteRound.toNumber()).to.equal(1);
+      expect(voteManagerAccount.admin.toBase58()).to.equal(adminWallet.publicKey.toBase58()); // Admin should be correctly set.
+      expect(voteManagerAccount.tkMint.toBase58()).to.equal(tokenMint.publicKey.toBase58()); // Token mint should be correctly set.
+      expect(voteManagerAccount.tkProgram.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58()); // Token program ID should be correctly set.
+      expect(voteManagerAccount.voteFee.toNumber()).to.equal(100); // Vote fee should be correctly set.
+
+      // Create the treasury that receives the voting fees.
+      treasuryPda = deriveTreasuryPda(voteManagerPda);
+      treasuryTokenAccount = deriveMintTokenAccount(tokenMint.publicKey, treasuryPda);
+      await program.methods
+        .initTreasury()
+        .accounts({
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          voteManager: voteManagerPda,
+          mint: tokenMint.publicKey,
+          owner: adminWallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      console.log("VoteManager initialized successfully.");
+    } catch (error) {
+      console.error("Error in before hook:", error);
+      throw error; // Propagate the error to fail the tests if setup fails.
+    }
+
+    try {
+      console.log("Setting up voter's ATA...");
+      // Derive the Associated Token Account (ATA) for the voter.
+      voterAAta = await getAssociatedTokenAddress(
+        tokenMint.publicKey,
+        voterA.publicKey,
+        true, // Allow PDA derivation.
+        TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
+        ASSOCIATED_PROGRAM_ID // Associated Token program ID.
+      );
+
+      voterBAta = await getAssociatedTokenAddress(
+        tokenMint.publicKey,
+        voterB.publicKey,
+        true, // Allow PDA derivation.
+        TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
+        ASSOCIATED_PROGRAM_ID // Associated Token program ID.
+      );
+
+      // Check if the voter's ATA already exists to avoid unnecessary transactions.
+      const ataAccount1 = await provider.connection.getAccountInfo(voterAAta);
+      const ataAccount2 = await provider.connection.getAccountInfo(voterBAta);
+      if (!ataAccount1) {
+        // If ATA doesn't exist, create it.
+        const ataTransaction = new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey, // Payer of the transaction fees.
+            voterAAta,           // PDA address for the ATA.
+            voterA.publicKey,    // Owner of the ATA.
+            tokenMint.publicKey,// Token mint for the ATA.
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        );
+
+        await provider.sendAndConfirm(ataTransaction); // Send and confirm the ATA creation transaction.
+        console.log("Voter A ATA created.");
+      } else {
+        console.log(`ATA for ${voterA.publicKey.toBase58()} already exists.`);
+      }
+
+      if (!ataAccount2) {
+        // If ATA doesn't exist, create it.
+        const ataTransaction = new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey, // Payer of the transaction fees.
+            voterBAta,           // PDA address for the ATA.
+            voterB.publicKey,    // Owner of the ATA.
+            tokenMint.publicKey,// Token mint for the ATA.
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        );
+
+        await provider.sendAndConfirm(ataTransaction); // Send and confirm the ATA creation transaction.
+        console.log("Voter B ATA created.");
+      } else {
+        console.log(`ATA for ${voterB.publicKey.toBase58()} already exists.`);
+      }
+
+      // Define the accounts involved in transferring ttt tokens from the mint to the voter.
+      const transferAccounts1 = {
+        fromAta: mintTokenAccount, // Source ATA holding the minted tokens.
+        toAta: voterAAta,           // Destination ATA to receive tokens.
+        mint: tokenMint.publicKey, // Token mint associated with the transfer.
+        tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
+      };
+
+      const transferAccounts2 = {
+        fromAta: mintTokenAccount, // Source ATA holding the minted tokens.
+        toAta: voterBAta,           // Destination ATA to receive tokens.
+        mint: tokenMint.publicKey, // Token mint associated with the transfer.
+        tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
+      };
+
+      // Execute the token transfer of 10,000 ttt from admin to voter A.
+      await tokenProgram.methods
+        .transferTokens(new anchor.BN(10_000)) // Amount of ttt tokens to transfer.
+        .accounts(transferAccounts1)
+        .signers([admin]) // Admin signs the transaction.
+        .rpc();
+
+      // Execute the token transfer of 10,000 ttt from admin to voter B.
+      await tokenProgram.methods
+        .transferTokens(new anchor.BN(10_000)) // Amount of ttt tokens to transfer.
+        .accounts(transferAccounts2)
+        .signers([admin]) // Admin signs the transaction.
+        .rpc();
+
+      console.log("ttt tokens transferred to test voter.");
+    } catch (error) {
+      console.error("Error in beforeEach hook:", error);
+      throw error; // Propagate the error to fail the tests if setup fails.
+    }
+  });
+
+  // -------------------- Helpers --------------------
+
+  /**
+   * Moves to the next round unless the current one is still pending, so projects can be added.
+   */
+  async function ensurePendingRound() {
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    if (!("pending" in voteManagerAccount.roundState)) {
+      await program.methods
+        .incrementRound()
+        .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+        .rpc();
+    }
+  }
+
+  /**
+   * Opens the current round for voting unless it is already open.
+   */
+  async function ensureOpenRound() {
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    if ("pending" in voteManagerAccount.roundState) {
+      await program.methods
+        .openRound()
+        .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+        .rpc();
+    }
+  }
+
+  // -------------------- Test Cases --------------------
+
+  /**
+   * Test Case: Admin Initialization with Correct Admin Key
+   * Purpose: Verify that the VoteManager is correctly initialized with the admin's key.
+   */
+  it("Admin Initialization with Correct Admin Key", async () => {
+    console.log("\n");
+    // Fetch the current state of the VoteManager account.
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+
+    // Assert that the initial voting round is set to 1.
+    expect(voteManagerAccount.voteRound.toNumber()).to.equal(1);
+
+    // Assert that the admin is correctly set.
+    expect(voteManagerAccount.admin.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+
+    // Assert that the token mint is correctly set.
+    expect(voteManagerAccount.tkMint.toBase58()).to.equal(tokenMint.publicKey.toBase58());
+
+    // Assert that the token program ID is correctly set.
+    expect(voteManagerAccount.tkProgram.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58());
+
+    // Assert that the vote fee is correctly set to 100.
+    expect(voteManagerAccount.voteFee.toNumber()).to.equal(100);
+
+    // Assert that a voter gets a single vote per round by default.
+    expect(voteManagerAccount.maxVotesPerRound).to.equal(1);
+
+    // Assert that the VoteManager runs the default election.
+    expect(voteManagerAccount.electionId.toNumber()).to.equal(0);
+
+    // Assert that the first round waits to be opened.
+    expect(voteManagerAccount.roundState).to.deep.equal({ pending: {} });
+
+    // Assert that the canonical bump is stored for later seed checks.
+    const [, canonicalBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vote_manager"), adminWallet.publicKey.toBuffer()],
+      program.programId
+    );
+    expect(voteManagerAccount.bump).to.equal(canonicalBump);
+  });
+
+  /**
+   * Test Case: Admin Initialization with Incorrect Admin Key
+   * Purpose: Ensure that initializing the VoteManager with an unauthorized admin fails as expected.
+   */
+  it("Admin Initialization with Incorrect Admin Key", async () => {
+    // Derive a VoteManager PDA using an unauthorized attacker's public key.
+    const unauthorizedVoteManagerPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("vote_manager"), unauthorizedAttacker.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    // Give the attacker a fee account, so the attempt passes the account checks.
+    const attackerTokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      admin,
+      tokenMint.publicKey,
+      unauthorizedAttacker.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // Define the accounts for the unauthorized initialization attempt.
+    const initializeAccounts = {
+      voteData: unauthorizedVoteManagerPda,
+      owner: unauthorizedAttacker.publicKey,
+      mint: tokenMint.publicKey,
+      adminTokenAccount: attackerTokenAccount,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      // Attempt to initialize the VoteManager with unauthorized admin credentials.
+      await program.methods
+        .initialize(new anchor.BN(0), new anchor.BN(100))
+        .accounts(initializeAccounts)
+        .signers([unauthorizedAttacker])
+        .rpc();
+
+      // If the above transaction succeeds, the test should fail.
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      // Assert that the error code corresponds to unauthorized admin action.
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+  });
+
+  /**
+   * Test Case: Initialization with a misconfigured mint
+   * Purpose: Ensure that `initialize` verifies the mint and the admin's fee account up front instead
+   * of recording a mint that fails at the first vote.
+   */
+  it("Initialization rejects a misconfigured mint", async () => {
+    const misconfiguredVoteManagerPda = deriveVoteManagerPda(adminWallet.publicKey, 2);
+
+    /**
+     * Sends `initialize` for election 2 and asserts it fails with the expected error code.
+     */
+    async function expectInitializeError(mint: PublicKey, tokenProgram: PublicKey, code: string) {
+      try {
+        await program.methods
+          .initialize(new anchor.BN(2), new anchor.BN(100))
+          .accounts({
+            voteData: misconfiguredVoteManagerPda,
+            owner: adminWallet.publicKey,
+            mint,
+            adminTokenAccount: getAssociatedTokenAddressSync(mint, adminWallet.publicKey, true, TOKEN_2022_PROGRAM_ID),
+            tokenProgram,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        throw new Error(`Expected ${code} error, but transaction succeeded.`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    }
+
+    // The mint is not owned by the stated token program.
+    await expectInitializeError(tokenMint.publicKey, TOKEN_PROGRAM_ID, "ConstraintMintTokenProgram");
+
+    // The fee exceeds the supply of the mint, so it cannot be in its base units; the decimals of the
+    // mint are fine.
+    const decimalMint = await createMint(
+      provider.connection,
+      admin,
+      admin.publicKey,
+      null,
+      6,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await createAssociatedTokenAccount(
+      provider.connection,
+      admin,
+      decimalMint,
+      adminWallet.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await expectInitializeError(decimalMint, TOKEN_2022_PROGRAM_ID, "VoteFeeExceedsSupply");
+
+    // The admin has no fee account for the mint.
+    const unfundedMint = await createMint(
+      provider.connection,
+      admin,
+      admin.publicKey,
+      null,
+      0,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await expectInitializeError(unfundedMint, TOKEN_2022_PROGRAM_ID, "AccountNotInitialized");
+
+    // Nothing was recorded.
+    expect(await provider.connection.getAccountInfo(misconfiguredVoteManagerPda)).to.equal(null);
+  });
+
+  /**
+   * Test Case: Re-initialization
+   * Purpose: Ensure that `initialize` cannot run again on an existing VoteManager and reset its round,
+   * fee and mint.
+   */
+  it("Initialization cannot reset an existing VoteManager", async () => {
+    const before = await program.account.voteManager.fetch(voteManagerPda);
+
+    try {
+      await program.methods
+        .initialize(new anchor.BN(0), new anchor.BN(1))
+        .accounts({
+          voteData: voteManagerPda,
+          owner: adminWallet.publicKey,
+          mint: tokenMint.publicKey,
+          adminTokenAccount: mintTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      // The VoteManager already exists, so its `init` fails before the handler runs.
+      expect(err.toString()).to.include("already in use");
+    }
+
+    // Nothing was reset.
+    const after = await program.account.voteManager.fetch(voteManagerPda);
+    expect(after.voteRound.toNumber()).to.equal(before.voteRound.toNumber());
+    expect(after.voteFee.toNumber()).to.equal(before.voteFee.toNumber());
+    expect(after.tkMint.toBase58()).to.equal(before.tkMint.toBase58());
+  });
+
+  /**
+   * Test Case: Increment Round by Admin
+   * Purpose: Ensure that the admin can successfully increment the voting round.
+   */
+  it("Increment Round by Admin", async () => {
+    // Fetch the current voting round before incrementing.
+    const voteManagerAccountBefore = await program.account.voteManager.fetch(voteManagerPda);
+    const initialRound = voteManagerAccountBefore.voteRound.toNumber();
+
+    // Define the accounts required to increment the round.
+    const incrementAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    // Execute the round increment.
+    await program.methods
+      .incrementRound()
+      .accounts(incrementAccounts)
+      .rpc();
+
+    // Fetch the voting round after incrementing.
+    const voteManagerAccountAfter = await program.account.voteManager.fetch(voteManagerPda);
+
+    // Assert that the voting round has been incremented by 1.
+    expect(voteManagerAccountAfter.voteRound.toNumber()).to.equal(initialRound + 1);
+  });
+
+  /**
+   * Test Case: Increment Round by Non-Admin
+   * Purpose: Ensure that a non-admin user cannot increment the voting round.
+   */
+  it("Increment Round by Non-Admin", async () => {
+    // Define the accounts for a non-admin attempting to increment the round.
+    const incrementAccounts = {
+      voteData: voteManagerPda,
+      owner: unauthorizedAttacker.publicKey,
+    };
+
+    try {
+      // Attempt to increment the round using an unauthorized user.
+      await program.methods
+        .incrementRound()
+        .accounts(incrementAccounts)
+        .signers([unauthorizedAttacker])
+        .rpc();
+
+      // If the above transaction succeeds, the test should fail.
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      // Assert that the error code corresponds to unauthorized admin action.
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+  });
+
+  /**
+   * Test Case: Role-based access
+   * Purpose: Ensure Operators can add projects and increment rounds, while Auditors and revoked
+   * wallets cannot, and only the admin grants roles.
+   */
+  it("Operators add projects and increment rounds, Auditors cannot", async () => {
+    await ensurePendingRound();
+    const operator = voterB;
+    const auditor = unauthorizedAttacker;
+    const operatorRole = deriveRolePda(voteManagerPda, operator.publicKey);
+    const auditorRole = deriveRolePda(voteManagerPda, auditor.publicKey);
+    const grantAccounts = (role: PublicKey) => ({
+      role,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+
+    // Only the admin grants roles, and never the Admin role.
+    try {
+      await program.methods
+        .grantRole(auditor.publicKey, { operator: {} })
+        .accounts({ ...grantAccounts(auditorRole), owner: auditor.publicKey })
+        .signers([auditor])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+    try {
+      await program.methods.grantRole(operator.publicKey, { admin: {} }).accounts(grantAccounts(operatorRole)).rpc();
+      throw new Error("Expected RoleNotGrantable error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RoleNotGrantable");
+    }
+
+    await program.methods.grantRole(operator.publicKey, { operator: {} }).accounts(grantAccounts(operatorRole)).rpc();
+    await program.methods.grantRole(auditor.publicKey, { auditor: {} }).accounts(grantAccounts(auditorRole)).rpc();
+
+    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round = voteManagerAccount.voteRound.toNumber();
+    const addProject = (projectId: string, signer: Keypair, role: PublicKey | null) =>
+      program.methods
+        .addProject(projectId)
+        .accounts({
+          // Projects are seeded by the admin's key, whoever adds them.
+          projectData: deriveProjectPda(projectId, round, adminWallet.publicKey),
+          voteManager: voteManagerPda,
+          owner: signer.publicKey,
+          role,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    const revokeAccounts = (role: PublicKey) => ({ role, voteManager: voteManagerPda, owner: adminWallet.publicKey });
+
+    try {
+      const operatorProjectId = generateProjectId(10);
+      await addProject(operatorProjectId, operator, operatorRole);
+      const project = await program.account.projectData.fetch(
+        deriveProjectPda(operatorProjectId, round, adminWallet.publicKey)
+      );
+      expect(project.payer.toBase58()).to.equal(operator.publicKey.toBase58());
+
+      try {
+        await addProject(generateProjectId(10), auditor, auditorRole);
+        throw new Error("Expected MissingRole error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MissingRole");
+      }
+
+      await program.methods
+        .incrementRound()
+        .accounts({ voteData: voteManagerPda, owner: operator.publicKey, role: operatorRole })
+        .signers([operator])
+        .rpc();
+      voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      expect(voteManagerAccount.voteRound.toNumber()).to.equal(round + 1);
+
+      // Fees stay admin-only.
+      try {
+        await program.methods
+          .changeFee(new anchor.BN(1))
+          .accounts({ voteData: voteManagerPda, owner: operator.publicKey })
+          .signers([operator])
+          .rpc();
+        throw new Error("Expected NotAdmin error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotAdmin");
+      }
+    } finally {
+      await program.methods.revokeRole().accounts(revokeAccounts(operatorRole)).rpc();
+      await program.methods.revokeRole().accounts(revokeAccounts(auditorRole)).rpc();
+    }
+
+    // A revoked Operator is back to a plain wallet.
+    expect(await provider.connection.getAccountInfo(operatorRole)).to.be.null;
+    try {
+      await program.methods
+        .incrementRound()
+        .accounts({ voteData: voteManagerPda, owner: operator.publicKey, role: null })
+        .signers([operator])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+  });
+
+  /**
+   * Test Case: Migrating a current VoteManager
+   * Purpose: Ensure the migration only accepts accounts in the legacy u8 round layout.
+   */
+  it("Migrating a VoteManager in the current layout fails with AlreadyMigrated", async () => {
+    try {
+      await program.methods
+        .migrateVoteManager(adminWallet.publicKey)
+        .accounts({
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      throw new Error("Expected AlreadyMigrated error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+    }
+  });
+
+  /**
+   * Test Case: Migrating a versioned account
+   * Purpose: Ensure accounts are created with the current layout version, which `migrate_account`
+   * refuses to upgrade again.
+   */
+  it("Migrating an account of the current version fails with AlreadyMigrated", async () => {
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.version).to.equal(2);
+
+    try {
+      await program.methods
+        .migrateAccount()
+        .accounts({
+          account: voteManagerPda,
+          payer: unauthorizedAttacker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected AlreadyMigrated error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+    }
+  });
+
+  /**
+   * Test Case: Two-step admin transfer
+   * Purpose: Ensure admin rights move only after the proposed admin accepts them.
+   */
+  it("Two-step admin transfer", async () => {
+    // Only the proposed admin can accept.
+    await program.methods
+      .proposeAdmin(voterB.publicKey)
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accounts({ voteData: voteManagerPda, owner: unauthorizedAttacker.publicKey })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotPendingAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotPendingAdmin");
+    }
+
+    await program.methods
+      .acceptAdmin()
+      .accounts({ voteData: voteManagerPda, owner: voterB.publicKey })
+      .signers([voterB])
+      .rpc();
+
+    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.admin.toBase58()).to.equal(voterB.publicKey.toBase58());
+    expect(voteManagerAccount.pendingAdmin).to.equal(null);
+
+    // The previous admin lost its rights.
+    try {
+      await program.methods
+        .incrementRound()
+        .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    // Hand the rights back for the remaining tests.
+    await program.methods
+      .proposeAdmin(adminWallet.publicKey)
+      .accounts({ voteData: voteManagerPda, owner: voterB.publicKey })
+      .signers([voterB])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+
+    voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.admin.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+  });
+
+  /**
+   * Test Case: Proposals govern the VoteManager through the governance authority
+   * Purpose: Ensure passed proposals execute their instructions signed by the governance authority PDA.
+   */
+  it("Proposals govern the VoteManager through the governance authority", async () => {
+    const adminAccounts = { voteData: voteManagerPda, owner: adminWallet.publicKey };
+    const governanceAuthority = deriveGovernanceAuthorityPda(voteManagerPda);
+
+    // Creates a proposal for `ix`, votes for it with voter A, locking `locked` TTT, and returns the
+    // accounts to execute it.
+    const locked = 100;
+    const proposeAndApprove = async (ix: anchor.web3.TransactionInstruction) => {
+      const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      const proposalPda = deriveProposalPda(voteManagerPda, voteManagerAccount.proposalCount.toNumber());
+      const depositTokenAccount = deriveMintTokenAccount(tokenMint.publicKey, proposalPda);
+
+      await program.methods
+        .createProposal([
+          {
+            programId: ix.programId,
+            accounts: ix.keys.map((key) => ({ pubkey: key.pubkey, isSigner: key.isSigner, isWritable: key.isWritable })),
+            data: ix.data,
+          },
+        ])
+        .accounts({
+          proposal: proposalPda,
+          depositTokenAccount,
+          proposerTokenAccount: mintTokenAccount,
+          voteManager: voteManagerPda,
+          mint: tokenMint.publicKey,
+          proposer: adminWallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect(await getTokenBalance(provider.connection, depositTokenAccount)).to.equal(10);
+
+      const proposalVote = deriveProposalVotePda(proposalPda, voterA.publicKey);
+      const voteVault = deriveMintTokenAccount(tokenMint.publicKey, proposalVote);
+      const voteAccounts = {
+        proposal: proposalPda,
+        proposalVote,
+        voteVault,
+        token: voterAAta,
+        voteManager: voteManagerPda,
+        mint: tokenMint.publicKey,
+        signer: voterA.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      };
+      const voterBalance = await getTokenBalance(provider.connection, voterAAta);
+      await program.methods
+        .voteOnProposal(true, new anchor.BN(locked))
+        .accounts(voteAccounts)
+        .signers([voterA])
+        .rpc();
+      // The tokens weighing the vote stay locked until voting ends.
+      expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(voterBalance - locked);
+      expect(await getTokenBalance(provider.connection, voteVault)).to.equal(locked);
+      expect((await program.account.proposalVote.fetch(proposalVote)).locked.toNumber()).to.equal(locked);
+
+      // The ballot PDA prevents a second vote.
+      try {
+        await program.methods
+          .voteOnProposal(true, new anchor.BN(locked))
+          .accounts(voteAccounts)
+          .signers([voterA])
+          .rpc();
+        throw new Error("Expected the second vote to fail, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.message).to.not.include("Expected the second vote to fail");
+      }
+
+      return {
+        proposalPda,
+        releaseAccounts: {
+          proposalVote,
+          voteVault,
+          token: voterAAta,
+          mint: tokenMint.publicKey,
+          voter: voterA.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        },
+        executeAccounts: {
+          proposal: proposalPda,
+          depositTokenAccount,
+          proposerTokenAccount: mintTokenAccount,
+          governanceAuthority,
+          voteManager: voteManagerPda,
+          mint: tokenMint.publicKey,
+          signer: adminWallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        },
+        remainingAccounts: [
+          { pubkey: ix.programId, isSigner: false, isWritable: false },
+          ...ix.keys.map((key) => ({ pubkey: key.pubkey, isSigner: false, isWritable: key.isWritable })),
+        ],
+      };
+    };
+
+    await program.methods
+      .setProposalConfig(new anchor.BN(10), new anchor.BN(2), new anchor.BN(1))
+      .accounts(adminAccounts)
+      .rpc();
+
+    try {
+      // Hand the admin rights to the governance authority.
+      await program.methods.proposeAdmin(governanceAuthority).accounts(adminAccounts).rpc();
+      const accept = await proposeAndApprove(
+        await program.methods.acceptAdmin().accounts({ voteData: voteManagerPda, owner: governanceAuthority }).instruction()
+      );
+
+      try {
+        await program.methods
+          .executeProposal()
+          .accounts(accept.executeAccounts)
+          .remainingAccounts(accept.remainingAccounts)
+          .rpc();
+        throw new Error("Expected ProposalVotingOpen error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProposalVotingOpen");
+      }
+      try {
+        await program.methods.releaseProposalVote().accounts(accept.releaseAccounts).signers([voterA]).rpc();
+        throw new Error("Expected ProposalVotingOpen error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProposalVotingOpen");
+      }
+
+      // Wait for the voting period to end.
+      await new Promise((resolve) => setTimeout(resolve, 3_000));
+
+      const tx = await program.methods
+        .executeProposal()
+        .accounts(accept.executeAccounts)
+        .remainingAccounts(accept.remainingAccounts)
+        .rpc();
+      const events = await fetchEvents(tx);
+      expect(events.map((event) => event.name)).to.include("proposalExecuted");
+
+      let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      expect(voteManagerAccount.admin.toBase58()).to.equal(governanceAuthority.toBase58());
+
+      try {
+        await program.methods
+          .executeProposal()
+          .accounts(accept.executeAccounts)
+          .remainingAccounts(accept.remainingAccounts)
+          .rpc();
+        throw new Error("Expected ProposalExecuted error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProposalExecuted");
+      }
+
+      // Hand the rights back for the remaining tests, again through a proposal.
+      const handBack = await proposeAndApprove(
+        await program.methods
+          .proposeAdmin(adminWallet.publicKey)
+          .accounts({ voteData: voteManagerPda, owner: governanceAuthority })
+          .instruction()
+      );
+      await new Promise((resolve) => setTimeout(resolve, 3_000));
+      await program.methods
+        .executeProposal()
+        .accounts(handBack.executeAccounts)
+        .remainingAccounts(handBack.remainingAccounts)
+        .rpc();
+      await program.methods.acceptAdmin().accounts(adminAccounts).rpc();
+
+      voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      expect(voteManagerAccount.admin.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+
+      // Executed proposals can be closed to reclaim their rent.
+      await program.methods
+        .closeProposal()
+        .accounts({
+          proposal: accept.proposalPda,
+          depositTokenAccount: accept.executeAccounts.depositTokenAccount,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          voteManager: voteManagerPda,
+          mint: tokenMint.publicKey,
+          proposer: adminWallet.publicKey,
+          signer: adminWallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      expect(await provider.connection.getAccountInfo(accept.proposalPda)).to.equal(null);
+
+      // Voting ended, so the locked TTT go back to the voter, even once the proposal is closed.
+      for (const { releaseAccounts } of [accept, handBack]) {
+        const voterBalance = await getTokenBalance(provider.connection, voterAAta);
+        await program.methods.releaseProposalVote().accounts(releaseAccounts).signers([voterA]).rpc();
+        expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(voterBalance + locked);
+        expect(await provider.connection.getAccountInfo(releaseAccounts.proposalVote)).to.equal(null);
+        expect(await provider.connection.getAccountInfo(releaseAccounts.voteVault)).to.equal(null);
+      }
+    } finally {
+      // Restore the default proposal configuration.
+      await program.methods
+        .setProposalConfig(new anchor.BN(0), new anchor.BN(3 * 24 * 60 * 60), new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+    }
+  });
+
+  /**
+   * Test Case: An admin council acts as the admin once its threshold approves
+   * Purpose: Ensure admin actions execute only with M-of-N member approvals, signed by the council PDA.
+   */
+  it("An admin council acts as the admin once its threshold approves", async () => {
+    const adminAccounts = { voteData: voteManagerPda, owner: adminWallet.publicKey };
+    const councilPda = deriveAdminCouncilPda(voteManagerPda);
+    const councilAccounts = { voteData: voteManagerPda, owner: councilPda };
+    const members = [adminWallet.publicKey, voterA.publicKey, voterB.publicKey];
+    const originalFee = (await program.account.voteManager.fetch(voteManagerPda)).voteFee;
+
+    const expectError = async (call: Promise<string>, code: string) => {
+      try {
+        await call;
+        throw new Error(`Expected ${code} error, but transaction succeeded.`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    };
+
+    // Proposes `ixs` as `proposer` and returns the action's PDA and the accounts to execute it.
+    const propose = async (proposer: Keypair, ixs: anchor.web3.TransactionInstruction[]) => {
+      const council = await program.account.adminCouncil.fetch(councilPda);
+      const actionPda = deriveAdminActionPda(councilPda, council.actionCount.toNumber());
+      await program.methods
+        .proposeAdminAction(
+          ixs.map((ix) => ({
+            programId: ix.programId,
+            accounts: ix.keys.map((key) => ({ pubkey: key.pubkey, isSigner: key.isSigner, isWritable: key.isWritable })),
+            data: ix.data,
+          }))
+        )
+        .accounts({
+          action: actionPda,
+          council: councilPda,
+          signer: proposer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([proposer])
+        .rpc();
+      return {
+        accounts: { action: actionPda, council: councilPda, signer: adminWallet.publicKey },
+        remainingAccounts: ixs.flatMap((ix) => [
+          { pubkey: ix.programId, isSigner: false, isWritable: false },
+          ...ix.keys.map((key) => ({ pubkey: key.pubkey, isSigner: false, isWritable: key.isWritable })),
+        ]),
+      };
+    };
+    const approve = (action: { accounts: any }, member: Keypair) =>
+      program.methods
+        .approveAdminAction()
+        .accounts({ ...action.accounts, signer: member.publicKey })
+        .signers([member])
+        .rpc();
+    const execute = (action: { accounts: any; remainingAccounts: any[] }) =>
+      program.methods.executeAdminAction().accounts(action.accounts).remainingAccounts(action.remainingAccounts).rpc();
+
+    const createAccounts = {
+      council: councilPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    await expectError(
+      program.methods.createAdminCouncil([voterA.publicKey, voterA.publicKey], 1).accounts(createAccounts).rpc(),
+      "InvalidCouncil"
+    );
+    await expectError(program.methods.createAdminCouncil(members, 4).accounts(createAccounts).rpc(), "InvalidCouncil");
+    await program.methods.createAdminCouncil(members, 2).accounts(createAccounts).rpc();
+    await program.methods.proposeAdmin(councilPda).accounts(adminAccounts).rpc();
+
+    // The proposal is voter A's approval; one more member is needed.
+    const accept = await propose(voterA, [
+      await program.methods.acceptAdmin().accounts(councilAccounts).instruction(),
+    ]);
+    await expectError(execute(accept), "AdminActionNotApproved");
+    await expectError(approve(accept, voterA), "AdminActionAlreadyApproved");
+    await expectError(approve(accept, unauthorizedAttacker), "NotCouncilMember");
+
+    await approve(accept, voterB);
+    const tx = await execute(accept);
+    const events = await fetchEvents(tx);
+    expect(events.map((event) => event.name)).to.include("adminActionExecuted");
+    expect((await program.account.voteManager.fetch(voteManagerPda)).admin.toBase58()).to.equal(councilPda.toBase58());
+    await expectError(execute(accept), "AdminActionExecuted");
+    await expectError(approve(accept, adminWallet), "AdminActionExecuted");
+
+    // The admin key alone no longer changes the fee; the council does.
+    await expectError(
+      program.methods.changeFee(originalFee.addn(5)).accounts(adminAccounts).rpc(),
+      "NotAdmin"
+    );
+    const changeFee = await propose(adminWallet, [
+      await program.methods.changeFee(originalFee.addn(5)).accounts(councilAccounts).instruction(),
+    ]);
+    await approve(changeFee, voterB);
+    await execute(changeFee);
+    expect((await program.account.voteManager.fetch(voteManagerPda)).voteFee.toNumber()).to.equal(
+      originalFee.toNumber() + 5
+    );
+
+    // Restore the fee and hand the rights back for the remaining tests, in one action.
+    const handBack = await propose(voterB, [
+      await program.methods.changeFee(originalFee).accounts(councilAccounts).instruction(),
+      await program.methods.proposeAdmin(adminWallet.publicKey).accounts(councilAccounts).instruction(),
+    ]);
+    await approve(handBack, adminWallet);
+    await execute(handBack);
+    await program.methods.acceptAdmin().accounts(adminAccounts).rpc();
+
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.admin.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+    expect(voteManagerAccount.voteFee.toNumber()).to.equal(originalFee.toNumber());
+  });
+
+  /**
+   * Test Case: Admin Changes Fee
+   * Purpose: Verify that the admin can successfully change the voting fee.
+   */
+  it("Admin Changes Fee", async () => {
+    // Fetch the current vote fee before changing it.
+    const voteManagerAccountBefore = await program.account.voteManager.fetch(voteManagerPda);
+    const initialFee = voteManagerAccountBefore.voteFee;
+
+    // Define the new fee to be set by the admin.
+    const newFee = new anchor.BN(500); // Update to 500 tokens as fee
+
+    // Define the accounts required to change the fee.
+    const changeFeeAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    // Execute the fee change.
+    const signature = await program.methods
+      .changeFee(newFee)
+      .accounts(changeFeeAccounts)
+      .rpc({ commitment: "confirmed" });
+
+    // Assert that the change was announced with a FeeChanged event.
+    const [feeChanged] = await fetchEvents(signature);
+    expect(feeChanged.name).to.equal("feeChanged");
+    expect(feeChanged.data.oldVoteFee.toNumber()).to.equal(initialFee.toNumber());
+    expect(feeChanged.data.newVoteFee.toNumber()).to.equal(newFee.toNumber());
+
+    // Fetch the vote fee after the change.
+    const voteManagerAccountAfter = await program.account.voteManager.fetch(voteManagerPda);
+
+    // Assert that the vote fee has been updated to the new value.
+    expect(voteManagerAccountAfter.voteFee.toNumber()).to.equal(newFee.toNumber());
+
+    // Assert that the vote fee has changed from its initial value.
+    expect(voteManagerAccountAfter.voteFee.toNumber()).to.not.equal(initialFee.toNumber());
+  });
+
+  /**
+   * Test Case: Idempotent fee change
+   * Purpose: Ensure a fee change sent with its operation marker cannot be applied twice.
+   */
+  it("An operation marker makes a repeated fee change fail as a whole", async () => {
+    const key = createHash("sha256").update(`fee-change-${Date.now()}`).digest();
+    const marker = deriveOperationMarkerPda(voteManagerPda, key);
+    const recordMarker = () =>
+      program.methods
+        .recordOperation([...key])
+        .accounts({
+          marker,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction();
+    const feeBefore = (await program.account.voteManager.fetch(voteManagerPda)).voteFee.toNumber();
+
+    await program.methods
+      .changeFee(new anchor.BN(feeBefore + 1))
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .preInstructions([await recordMarker()])
+      .rpc();
+    const markerAccount = await program.account.operationMarker.fetch(marker);
+    expect(Buffer.from(markerAccount.key).equals(key)).to.be.true;
+
+    try {
+      // A re-run of the same operation carries the same marker and must not apply again.
+      await program.methods
+        .changeFee(new anchor.BN(feeBefore + 2))
+        .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+        .preInstructions([await recordMarker()])
+        .rpc();
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      expect(err.toString()).to.include("already in use");
+    }
+    const feeAfter = (await program.account.voteManager.fetch(voteManagerPda)).voteFee.toNumber();
+    expect(feeAfter).to.equal(feeBefore + 1);
+
+    // Restore the fee the following tests expect.
+    await program.methods
+      .changeFee(new anchor.BN(feeBefore))
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+  });
+
+  /**
+   * Test Case: Non-Admin Tries to Change Fee
+   * Purpose: Ensure that a non-admin user cannot change the voting fee.
+   */
+  it("Non-Admin Tries to Change Fee", async () => {
+    // Define the new fee to be attempted by a non-admin.
+    const newFee = new anchor.BN(500);
+
+    // Define the accounts for the unauthorized fee change attempt.
+    const changeFeeAccounts = {
+      voteData: voteManagerPda,
+      owner: unauthorizedAttacker.publicKey,
+    };
+
+    try {
+      // Attempt to change the vote fee using an unauthorized user.
+      await program.methods
+        .changeFee(newFee)
+        .accounts(changeFeeAccounts)
+        .signers([unauthorizedAttacker])
+        .rpc();
+
+      // If the above transaction succeeds, the test should fail.
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      // Assert that the error code corresponds to unauthorized admin action.
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+  });
+
+  /**
+   * Test Case: Add Project with Unique id
+   * Purpose: Verify that adding a project with a unique identifier succeeds.
+   */
+  it("Add Project with Unique id", async () => {
+    await ensurePendingRound();
+    // Define a unique project identifier.
+    const uniqueProjectId = generateProjectId(10);
+
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    // Derive the PDA for the unique project in round 2.
+    const uniqueProjectPda = deriveProjectPda(uniqueProjectId, currentRound, adminWallet.publicKey);
+
+    // Define the accounts required to add a new project.
+    const addProjectAccounts = {
+      projectData: uniqueProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Execute the project addition.
+    await program.methods
+      .addProject(uniqueProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    // Fetch the newly added project's account data.
+    const projectAccount = await program.account.projectData.fetch(uniqueProjectPda);
+
+    // Assertions to ensure the project was added correctly.
+    expect(fixedString(projectAccount.id, projectAccount.idLen)).to.equal(uniqueProjectId); // Project identifier should match.
+    expect(projectAccount.voteCount.toNumber()).to.equal(0); // Initial vote count should be zero.
+    expect(projectAccount.voteRound.toNumber()).to.equal(currentRound); // Project should be associated with round 2.
+    expect(voteManagerAccount.voteFee.toNumber() == 500);
+
+    // Assert that the canonical bump is stored for later seed checks.
+    const [, canonicalBump] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("project"),
+        Buffer.from(uniqueProjectId),
+        roundSeed(currentRound),
+        adminWallet.publicKey.toBuffer(),
+        electionSeed(0),
+      ],
+      program.programId
+    );
+    expect(projectAccount.bump).to.equal(canonicalBump);
+  });
+
+  /**
+   * Test Case: Add Project with Duplicate id
+   * Purpose: Ensure that adding a project with a duplicate identifier in the same round fails.
+   */
+  it("Add Project with Duplicate id", async () => {
+    await ensurePendingRound();
+    // Define a duplicate project identifier.
+    const duplicateProjectId = generateProjectId(10);
+
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    // Derive the PDA for the duplicate project in round 2.
+    const duplicateProjectPda = deriveProjectPda(duplicateProjectId, currentRound, adminWallet.publicKey);
+
+    // Define the accounts required to add the duplicate project.
+    const addProjectAccounts = {
+      projectData: duplicateProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // First addition of the duplicate project should succeed.
+    await program.methods
+      .addProject(duplicateProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    try {
+      // Attempt to add the same project again in the same round.
+      await program.methods
+        .addProject(duplicateProjectId)
+        .accounts(addProjectAccounts)
+        .rpc();
+
+      // If the above transaction succeeds, the test should fail.
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      // Assert that the error message indicates the project identifier is already in use.
+      expect(err.message).to.include("already in use");
+    }
+  });
+
+  /**
+   * Test Case: Add a batch of projects
+   * Purpose: Ensure add_projects creates every listed project in one transaction and rejects
+   * project accounts that do not match the ids.
+   */
+  it("Add a batch of projects in one transaction", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const ids = [generateProjectId(10), generateProjectId(10), generateProjectId(10)];
+    const projectPdas = ids.map((id) => deriveProjectPda(id, currentRound, adminWallet.publicKey));
+    const batchAccounts = {
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Swapping two project accounts breaks their seed derivation.
+    const swapped = [projectPdas[1], projectPdas[0], projectPdas[2]];
+    try {
+      await program.methods
+        .addProjects(ids)
+        .accounts(batchAccounts)
+        .remainingAccounts(swapped.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .rpc();
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectProjectAccounts");
+    }
+
+    await program.methods
+      .addProjects(ids)
+      .accounts(batchAccounts)
+      .remainingAccounts(projectPdas.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+      .rpc();
+
+    for (const [i, id] of ids.entries()) {
+      const projectAccount = await program.account.projectData.fetch(projectPdas[i]);
+      expect(fixedString(projectAccount.id, projectAccount.idLen)).to.equal(id);
+      expect(projectAccount.voteCount.toNumber()).to.equal(0);
+      expect(projectAccount.voteRound.toNumber()).to.equal(currentRound);
+      expect(projectAccount.payer.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+    }
+  });
+
+  /**
+   * Test Case: Update project metadata
+   * Purpose: Ensure the admin can set a project's name, URI and category in the account's fixed-size
+   * fields, and that non-admins cannot.
+   */
+  it("Admin updates a project's metadata in place", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const initialSize = (await provider.connection.getAccountInfo(projectPda))!.data.length;
+
+    const name = "Community Garden";
+    const uri = "https://example.org/projects/community-garden.json";
+    await program.methods
+      .updateProjectMetadata(name, uri, 3)
+      .accounts({
+        project: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+      })
+      .rpc();
+
+    const projectAccount = await program.account.projectData.fetch(projectPda);
+    expect(fixedString(projectAccount.name, projectAccount.nameLen)).to.equal(name);
+    expect(fixedString(projectAccount.uri, projectAccount.uriLen)).to.equal(uri);
+    expect(projectAccount.category).to.equal(3);
+    const size = (await provider.connection.getAccountInfo(projectPda))!.data.length;
+    expect(size).to.equal(initialSize);
+
+    try {
+      await program.methods
+        .updateProjectMetadata("Hijacked", uri, 0)
+        .accounts({
+          project: projectPda,
+          voteManager: voteManagerPda,
+          owner: unauthorizedAttacker.publicKey,
+        })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+  });
+
+  /**
+   * Test Case: Reuse id (project name) in a New Round
+   * Purpose: Verify that a project identifier can be reused in a new voting round.
+   */
+  it("Reuse id (project name) in a New Round", async () => {
+    await ensurePendingRound();
+    // Define the current voting round.
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    // Define a project identifier to be reused.
+    const reusedProjectId = generateProjectId(10);
+
+    // Derive the PDA for the reused project in round 3.
+    const reusedProjectPda = deriveProjectPda(reusedProjectId, currentRound, adminWallet.publicKey);
+
+    // Define the accounts required to add the reused project.
+    const addProjectAccounts = {
+      projectData: reusedProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Add the reused project in round 3.
+    await program.methods
+      .addProject(reusedProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    // Fetch the added project's account data.
+    const projectAccount = await program.account.projectData.fetch(reusedProjectPda);
+
+    // Assertions to ensure the reused project was added correctly.
+    expect(fixedString(projectAccount.id, projectAccount.idLen)).to.equal(reusedProjectId); // Project identifier should match.
+    expect(projectAccount.voteRound.toNumber()).to.equal(currentRound); // Project should be associated with round 3.
+    expect(voteManagerAccount.voteFee.toNumber() == 500);
+  });
+
+  /**
+   * Test Case: Voting in the Wrong Round
+   * Purpose: Ensure that voting in a round different from the current active round fails.
+   * 
+   * ! We use project id as a part of voter's seed - Anchor treats wrong round errors as constraint violation errors in this case.
+   */
+  it("Voting in the Wrong Round", async () => {
+    await ensurePendingRound();
+
+    // const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const wrongRound = 123;
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    // Define a project identifier for the wrong round voting test.
+    const projectId = generateProjectId(10);
+
+    // Derive the PDA for the project intended for wrong round voting in round 3.
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+
+    // Define the accounts required to add the project.
+    const addProjectAccounts = {
+      projectData: projectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Add the project to the VoteManager.
+    await program.methods
+      .addProject(projectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    // Define the accounts required to perform a vote in the wrong round.
+    const doVoteWrongRoundAccounts = {
+      voterData: deriveVoterPda(wrongRound, voterA.publicKey, projectId), // Derive with round 1, assuming current round is 3.
+      signer: voterA.publicKey, // Voter's public key.
+      voteManager: voteManagerPda, // VoteManager PDA.
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: projectPda, // Project PDA being voted for.
+      mint: tokenMint.publicKey, // Token mint's public key.
+      token: voterAAta, // Voter's token account.
+      tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
+      systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
+    };
+
+    try {
+      // Attempt to vote in the wrong round by providing an incorrect round number.
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts(doVoteWrongRoundAccounts)
+        .signers([voterA])
+        .rpc();
+
+      // If the above transaction succeeds, the test should fail.
+      throw new Error("Expected transaction to fail, but it succeeded");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+  });
+
+  /**
+   * Test Case: Successful Vote + fee transfer
+   * Purpose: Verify that a valid vote correctly updates the project's vote count and the voter's voting data.
+   */
+  it("Successful Vote, fee transfer", async () => {
+    // Define a project identifier for the successful vote test.
+    const incrementAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    await program.methods
+      .incrementRound()
+      .accounts(incrementAccounts)
+      .rpc();
+
+    const successfulVoteProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const successfulVoteProjectPda = deriveProjectPda(successfulVoteProjectId, currentRound, adminWallet.publicKey);
+
+    // Define the accounts required to add the successful vote project.
+    const addProjectAccounts = {
+      projectData: successfulVoteProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Add the successful vote project to the VoteManager.
+    await program.methods
+      .addProject(successfulVoteProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    await ensureOpenRound();
+
+    // Define the accounts required to perform a vote.
+    const doVoteAccounts = {
+      voterData: deriveVoterPda(currentRound, voterA.publicKey, successfulVoteProjectId), // Voter PDA for the voter in round 5.
+      signer: voterA.publicKey, // Voter's public key.
+      voteManager: voteManagerPda, // VoteManager PDA.
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: successfulVoteProjectPda, // Project PDA being voted for.
+      mint: tokenMint.publicKey, // Token mint's public key.
+      token: voterAAta, // Voter's token account.
+      tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
+      systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
+    };
+
+    // Fetch the voter's and treasury's initial token balances before voting.
+    const initialVoterBalance = await getTokenBalance(provider.connection, voterAAta);
+    const initialTreasuryBalance = await getTokenBalance(provider.connection, treasuryTokenAccount);
+    // Perform the vote, which should update the project's vote count and voter's data.
+    const signature = await program.methods
+      .doVote(null, { ttt: {} })
+      .accounts(doVoteAccounts)
+      .signers([voterA])
+      .rpc({ commitment: "confirmed" });
+
+    // Assert that the vote was announced with a VoteCast event.
+    const [voteCast] = await fetchEvents(signature);
+    expect(voteCast.name).to.equal("voteCast");
+    expect(voteCast.data.voter.toBase58()).to.equal(voterA.publicKey.toBase58());
+    expect(voteCast.data.project.toBase58()).to.equal(successfulVoteProjectPda.toBase58());
+    expect(voteCast.data.round.toNumber()).to.equal(currentRound);
+
+    // Fetch the voter's and treasury's token balances after voting.
+    const finalVoterBalance = await getTokenBalance(provider.connection, voterAAta);
+    const finalTreasuryBalance = await getTokenBalance(provider.connection, treasuryTokenAccount);
+
+    // Fetch the project's account data after the vote.
+    const projectAccount = await program.account.projectData.fetch(successfulVoteProjectPda);
+
+    // Fetch the voter's voting data after the vote.
+    const voterAccount = await program.account.voterData.fetch(doVoteAccounts.voterData);
+
+    // Assertions to ensure the vote was successfully recorded.
+    expect(fixedString(projectAccount.id, projectAccount.idLen)).to.equal(successfulVoteProjectId); // Project identifier should match.
+    expect(projectAccount.voteRound.toNumber()).to.equal(currentRound); // Project should be associated with round 5.
+    expect(projectAccount.voteCount.toNumber()).to.be.greaterThan(0); // Project's vote count should have increased.
+
+    expect(voterAccount.voteCount.toNumber()).to.be.greaterThan(0); // Voter's vote count should have increased.
+    expect(voterAccount.lastVotedRound.toNumber()).to.equal(currentRound);
+
+    // Assert that the voter's balance decreased by the vote fee amount (500 ttt).
+    expect(finalVoterBalance).to.equal(initialVoterBalance - voteManagerAccount.voteFee.toNumber());
+
+    // Assert that the treasury's balance increased by the vote fee amount (500 ttt).
+    expect(finalTreasuryBalance).to.equal(initialTreasuryBalance + voteManagerAccount.voteFee.toNumber());
+
+    // Assert that the canonical bump is stored for later seed checks.
+    const [, canonicalBump] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(VOTER_NAMESPACE),
+        electionSeed(0),
+        roundSeed(currentRound),
+        voterA.publicKey.toBuffer(),
+        Buffer.from(successfulVoteProjectId),
+      ],
+      program.programId
+    );
+    expect(voterAccount.bump).to.equal(canonicalBump);
+  });
+
+  /**
+   * Test Case: Treasury withdrawal
+   * Purpose: Ensure only the admin can withdraw collected fees, and never the fees owed to voters.
+   */
+  it("Admin withdraws voting fees from the treasury", async () => {
+    // Tokens sent to the treasury on top of the fees it owes are the only ones left to withdraw.
+    const surplus = 1_000;
+    const donation = await createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      mintTokenAccount,
+      tokenMint.publicKey,
+      treasuryTokenAccount,
+      admin.publicKey,
+      BigInt(surplus),
+      0,
+      [],
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(donation));
+
+    const treasuryBalance = await getTokenBalance(provider.connection, treasuryTokenAccount);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const treasuryBefore = await program.account.treasury.fetch(treasuryPda);
+    const owed =
+      treasuryBefore.totalEscrowed.toNumber() +
+      voteManagerAccount.roundFees.toNumber() +
+      voteManagerAccount.owedFees.toNumber();
+    expect(voteManagerAccount.roundFees.toNumber()).to.be.greaterThan(0);
+    const available = Math.max(treasuryBalance - owed, 0);
+    expect(available).to.be.at.least(surplus);
+    const withdrawAccounts = (owner: PublicKey) => ({
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      destination: mintTokenAccount,
+      voteManager: voteManagerPda,
+      mint: tokenMint.publicKey,
+      owner,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    try {
+      await program.methods
+        .withdrawTreasury(new anchor.BN(1))
+        .accounts(withdrawAccounts(unauthorizedAttacker.publicKey))
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    // The fees of the current round are owed to its voters, as refunds or rewards.
+    try {
+      await program.methods
+        .withdrawTreasury(new anchor.BN(available + 1))
+        .accounts(withdrawAccounts(adminWallet.publicKey))
+        .rpc();
+      throw new Error("Expected InsufficientTreasuryBalance error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InsufficientTreasuryBalance");
+    }
+
+    const initialAdminBalance = await getTokenBalance(provider.connection, mintTokenAccount);
+    await program.methods
+      .withdrawTreasury(new anchor.BN(available))
+      .accounts(withdrawAccounts(adminWallet.publicKey))
+      .rpc();
+
+    expect(await getTokenBalance(provider.connection, treasuryTokenAccount)).to.equal(treasuryBalance - available);
+    expect(await getTokenBalance(provider.connection, mintTokenAccount)).to.equal(initialAdminBalance + available);
+
+    const treasuryAccount = await program.account.treasury.fetch(treasuryPda);
+    expect(treasuryAccount.totalWithdrawn.toNumber()).to.equal(treasuryBefore.totalWithdrawn.toNumber() + available);
+  });
+
+  /**
+   * Test Case: Admin funds the reward vault of the current round
+   * Purpose: Ensure funding accumulates in the vault and sweeping requires a finalized round
+   */
+  it("Admin funds the reward vault of the current round", async () => {
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round = voteManagerAccount.voteRound.toNumber();
+    const rewardVaultPda = deriveRewardVaultPda(voteManagerPda, round);
+    const vaultTokenAccount = deriveMintTokenAccount(tokenMint.publicKey, rewardVaultPda);
+    const fundAccounts = (owner: PublicKey, source: PublicKey) => ({
+      rewardVault: rewardVaultPda,
+      vaultTokenAccount,
+      source,
+      voteManager: voteManagerPda,
+      mint: tokenMint.publicKey,
+      owner,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+
+    try {
+      await program.methods
+        .fundRewardVault(new anchor.BN(round), new anchor.BN(100))
+        .accounts(fundAccounts(unauthorizedAttacker.publicKey, mintTokenAccount))
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    await program.methods
+      .fundRewardVault(new anchor.BN(round), new anchor.BN(300))
+      .accounts(fundAccounts(adminWallet.publicKey, mintTokenAccount))
+      .rpc();
+    await program.methods
+      .fundRewardVault(new anchor.BN(round), new anchor.BN(200))
+      .accounts(fundAccounts(adminWallet.publicKey, mintTokenAccount))
+      .rpc();
+
+    const rewardVault = await program.account.rewardVault.fetch(rewardVaultPda);
+    expect(rewardVault.round.toNumber()).to.equal(round);
+    expect(rewardVault.totalFunded.toNumber()).to.equal(500);
+    expect(rewardVault.totalClaimed.toNumber()).to.equal(0);
+    expect(rewardVault.claimWindow.toNumber()).to.equal(voteManagerAccount.rewardClaimWindow.toNumber());
+    expect(rewardVault.swept).to.be.false;
+    expect(await getTokenBalance(provider.connection, vaultTokenAccount)).to.equal(500);
+
+    // The round is not finalized yet, so it has no RoundResult to start the claim window.
+    try {
+      await program.methods
+        .sweepUnclaimedRewards(new anchor.BN(round))
+        .accounts({
+          rewardVault: rewardVaultPda,
+          vaultTokenAccount,
+          roundResult: deriveRoundResultPda(voteManagerPda, round),
+          destination: mintTokenAccount,
+          voteManager: voteManagerPda,
+          mint: tokenMint.publicKey,
+          owner: adminWallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      throw new Error("Expected AccountNotInitialized error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  /**
+   * Test Case: Multiple users voting on the same project in the same round
+   * Purpose: Ensure vote count stored properly per project
+   */
+  it("Multiple users voting on the same project in the same round", async () => {
+    await ensurePendingRound();
+    // Create a unique project
+    const multiUserProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const multiUserProjectPda = deriveProjectPda(multiUserProjectId, currentRound, adminWallet.publicKey);
+
+    const addProjectAccounts = {
+      projectData: multiUserProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Add the project
+    await program.methods
+      .addProject(multiUserProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    await ensureOpenRound();
+
+    // userA votes
+    const doVoteAccountsUserA = {
+      voterData: deriveVoterPda(currentRound, voterA.publicKey, multiUserProjectId),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: multiUserProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccountsUserA).signers([voterA]).rpc();
+
+    // userB votes
+    const doVoteAccountsUserB = {
+      voterData: deriveVoterPda(currentRound, voterB.publicKey, multiUserProjectId),
+      signer: voterB.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: multiUserProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterBAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccountsUserB).signers([voterB]).rpc();
+
+    // Check final result
+    const projectAccount = await program.account.projectData.fetch(multiUserProjectPda);
+    expect(projectAccount.voteCount.toNumber()).to.equal(2, "Project should have 2 total votes");
+  });
+
+  /**
+   * Test Case: Voting twice for the same project in the same round fails with AlreadyVoted
+   * Purpose: Ensure a voter cannot exceed `max_votes_per_round`.
+   */
+  it("Voting twice for the same project in the same round fails with AlreadyVoted", async () => {
+    await ensurePendingRound();
+    const doubleVoteProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const doubleVoteProjectPda = deriveProjectPda(doubleVoteProjectId, currentRound, adminWallet.publicKey);
+
+    const addProjectAccounts = {
+      projectData: doubleVoteProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.methods
+      .addProject(doubleVoteProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    await ensureOpenRound();
+
+    const doVoteAccounts = {
+      voterData: deriveVoterPda(currentRound, voterB.publicKey, doubleVoteProjectId),
+      signer: voterB.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: doubleVoteProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterBAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // First vote is within the limit.
+    await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterB]).rpc();
+
+    try {
+      // Second vote exceeds the default limit of one vote per round.
+      await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterB]).rpc();
+      throw new Error("Expected AlreadyVoted error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyVoted");
+    }
+
+    const projectAccount = await program.account.projectData.fetch(doubleVoteProjectPda);
+    expect(projectAccount.voteCount.toNumber()).to.equal(1, "Only the first vote is counted");
+  });
+
+  /**
+   * Test Case: Voting for a second project in the same round fails with AlreadyVoted
+   * Purpose: Ensure `max_votes_per_round` bounds a voter's votes across all projects of the round,
+   * and that retracted votes no longer count towards it.
+   */
+  it("Voting for a second project in the same round fails with AlreadyVoted", async () => {
+    await ensurePendingRound();
+    const currentRound = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectIds = [generateProjectId(10), generateProjectId(10)];
+    const projectPdas = projectIds.map((id) => deriveProjectPda(id, currentRound, adminWallet.publicKey));
+    for (const [i, projectId] of projectIds.entries()) {
+      await program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: projectPdas[i],
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+    await ensureOpenRound();
+
+    const roundVotesPda = deriveRoundVotesPda(voteManagerPda, currentRound, voterB.publicKey.toBuffer());
+    const vote = (i: number) =>
+      program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterB.publicKey, projectIds[i]),
+          signer: voterB.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPdas[i],
+          mint: tokenMint.publicKey,
+          token: voterBAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          roundVotes: roundVotesPda,
+        })
+        .signers([voterB])
+        .rpc();
+
+    // The first vote uses up the default limit of one vote per round.
+    await vote(0);
+    expect((await program.account.roundVotes.fetch(roundVotesPda)).votes.toNumber()).to.equal(1);
+
+    try {
+      await vote(1);
+      throw new Error("Expected AlreadyVoted error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyVoted");
+    }
+    const secondProject = await program.account.projectData.fetch(projectPdas[1]);
+    expect(secondProject.voteCount.toNumber()).to.equal(0, "The vote beyond the round limit is not counted");
+
+    // Retracting the first vote frees the voter to vote for the other project.
+    await program.methods
+      .retractVote()
+      .accounts({
+        voterData: deriveVoterPda(currentRound, voterB.publicKey, projectIds[0]),
+        signer: voterB.publicKey,
+        project: projectPdas[0],
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        mint: tokenMint.publicKey,
+        token: voterBAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        roundVotes: roundVotesPda,
+      })
+      .signers([voterB])
+      .rpc();
+    expect((await program.account.roundVotes.fetch(roundVotesPda)).votes.toNumber()).to.equal(0);
+
+    await vote(1);
+    expect((await program.account.projectData.fetch(projectPdas[1])).voteCount.toNumber()).to.equal(1);
+  });
+
+  /**
+   * Test Case: Round participants
+   * Purpose: Ensure a voter voting for several projects counts as a single participant of the round,
+   * and only leaves the participants once all their votes are retracted.
+   */
+  it("A voter voting for two projects counts as one round participant", async () => {
+    await ensurePendingRound();
+    const currentRound = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const adminAccounts = { voteData: voteManagerPda, owner: adminWallet.publicKey };
+    const projectIds = [generateProjectId(10), generateProjectId(10)];
+    const projectPdas = projectIds.map((id) => deriveProjectPda(id, currentRound, adminWallet.publicKey));
+    for (const [i, projectId] of projectIds.entries()) {
+      await program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: projectPdas[i],
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+    await program.methods.changeMaxVotes(2).accounts(adminAccounts).rpc();
+    await ensureOpenRound();
+
+    const roundVotesPda = deriveRoundVotesPda(voteManagerPda, currentRound, voterA.publicKey.toBuffer());
+    const voterAccounts = (i: number) => ({
+      voterData: deriveVoterPda(currentRound, voterA.publicKey, projectIds[i]),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: projectPdas[i],
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      roundVotes: roundVotesPda,
+    });
+    const participants = async () =>
+      (await program.account.voteManager.fetch(voteManagerPda)).roundParticipants.toNumber();
+    const retract = (i: number) =>
+      program.methods.retractVote().accounts(voterAccounts(i)).signers([voterA]).rpc();
+
+    try {
+      const before = await participants();
+      for (const i of [0, 1]) {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts({ ...voterAccounts(i), systemProgram: anchor.web3.SystemProgram.programId })
+          .signers([voterA])
+          .rpc();
+      }
+      expect(await participants()).to.equal(before + 1);
+
+      // The voter stays a participant while one of their votes is counted.
+      await retract(0);
+      expect(await participants()).to.equal(before + 1);
+      await retract(1);
+      expect(await participants()).to.equal(before);
+    } finally {
+      await program.methods.changeMaxVotes(1).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Per-project vote cap
+   * Purpose: Ensure a voter cannot exceed `max_votes_per_project`, even within `max_votes_per_round`.
+   */
+  it("Voting beyond the per-project cap fails with ProjectVoteCapReached", async () => {
+    await ensurePendingRound();
+    const cappedProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const cappedProjectPda = deriveProjectPda(cappedProjectId, currentRound, adminWallet.publicKey);
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    await program.methods
+      .addProject(cappedProjectId)
+      .accounts({
+        projectData: cappedProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Only the admin can set the cap.
+    try {
+      await program.methods
+        .changeMaxVotesPerProject(2)
+        .accounts({ voteData: voteManagerPda, owner: unauthorizedAttacker.publicKey })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    await program.methods.changeMaxVotes(3).accounts(adminAccounts).rpc();
+    await program.methods.changeMaxVotesPerProject(2).accounts(adminAccounts).rpc();
+
+    await ensureOpenRound();
+
+    const voterDataPda = deriveVoterPda(currentRound, voterB.publicKey, cappedProjectId);
+    const doVoteAccounts = {
+      voterData: voterDataPda,
+      signer: voterB.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: cappedProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterBAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterB]).rpc();
+      await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterB]).rpc();
+
+      try {
+        // The third vote is within the round limit but beyond the project cap.
+        await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterB]).rpc();
+        throw new Error("Expected ProjectVoteCapReached error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProjectVoteCapReached");
+      }
+    } finally {
+      // Restore the default limits.
+      await program.methods.changeMaxVotesPerProject(0).accounts(adminAccounts).rpc();
+      await program.methods.changeMaxVotes(1).accounts(adminAccounts).rpc();
+    }
+
+    const voterData = await program.account.voterData.fetch(voterDataPda);
+    expect(voterData.votesForProject).to.equal(2);
+    const projectAccount = await program.account.projectData.fetch(cappedProjectPda);
+    expect(projectAccount.voteCount.toNumber()).to.equal(2, "Only the votes within the cap are counted");
+  });
+
+  /**
+   * Test Case: Vote compute budget
+   * Purpose: Benchmark the compute units of `do_vote` on the zero-copy ProjectData and VoterData,
+   * for a first vote creating the VoterData and a repeat vote updating it, and guard against
+   * regressions.
+   */
+  it("Voting stays within its compute budget", async () => {
+    await ensurePendingRound();
+    const projectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods.changeMaxVotes(2).accounts(adminAccounts).rpc();
+
+    await ensureOpenRound();
+
+    const doVoteAccounts = {
+      voterData: deriveVoterPda(currentRound, voterA.publicKey, projectId),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: projectPda,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    const computeUnits = async () => {
+      const signature = await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts(doVoteAccounts)
+        .signers([voterA])
+        .rpc({ commitment: "confirmed" });
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return tx!.meta!.computeUnitsConsumed!;
+    };
+
+    try {
+      const firstVote = await computeUnits();
+      const repeatVote = await computeUnits();
+      console.log(`do_vote compute units: first vote ${firstVote}, repeat vote ${repeatVote}`);
+      expect(firstVote).to.be.below(50_000);
+      expect(repeatVote).to.be.below(40_000);
+    } finally {
+      await program.methods.changeMaxVotes(1).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Per-round project cap
+   * Purpose: Ensure projects cannot be added to a round holding `max_projects` projects.
+   */
+  it("Adding projects beyond the round's cap fails with TooManyProjects", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    const addProject = (projectId: string) =>
+      program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: deriveProjectPda(projectId, currentRound, adminWallet.publicKey),
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    // Leave room for a single project.
+    await program.methods
+      .changeMaxProjects(voteManagerAccount.projectCount + 1)
+      .accounts(adminAccounts)
+      .rpc();
+
+    try {
+      await addProject(generateProjectId(10));
+      const updatedVoteManager = await program.account.voteManager.fetch(voteManagerPda);
+      expect(updatedVoteManager.projectCount).to.equal(voteManagerAccount.projectCount + 1);
+
+      try {
+        await addProject(generateProjectId(10));
+        throw new Error("Expected TooManyProjects error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TooManyProjects");
+      }
+    } finally {
+      // Lift the cap.
+      await program.methods.changeMaxProjects(0).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Tie-break policy
+   * Purpose: Ensure projects tied for the lead mark the round tied and the policy picks the leader.
+   */
+  it("Tied projects are ordered by the tie-break policy", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    const tiedProjectIds = [generateProjectId(10), generateProjectId(10)];
+
+    // Runoffs need a duration.
+    try {
+      await program.methods
+        .setTieBreak({ runoff: {} }, new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+      throw new Error("Expected IncorrectTieBreak error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectTieBreak");
+    }
+
+    await program.methods
+      .setTieBreak({ lowestPda: {} }, new anchor.BN(0))
+      .accounts(adminAccounts)
+      .rpc();
+
+    try {
+      for (const projectId of tiedProjectIds) {
+        await program.methods
+          .addProject(projectId)
+          .accounts({
+            projectData: deriveProjectPda(projectId, currentRound, adminWallet.publicKey),
+            voteManager: voteManagerPda,
+            owner: adminWallet.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+      }
+      const [firstPda, secondPda] = tiedProjectIds.map((projectId) =>
+        deriveProjectPda(projectId, currentRound, adminWallet.publicKey)
+      );
+      const firstProject = await program.account.projectData.fetch(firstPda);
+      expect(firstProject.createdSlot.toNumber()).to.be.greaterThan(0);
+
+      await ensureOpenRound();
+
+      // One vote of the same weight for each project.
+      const voters: [Keypair, PublicKey, number][] = [
+        [voterA, voterAAta, 0],
+        [voterB, voterBAta, 1],
+      ];
+      for (const [voter, voterAta, index] of voters) {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts({
+            voterData: deriveVoterPda(currentRound, voter.publicKey, tiedProjectIds[index]),
+            signer: voter.publicKey,
+            voteManager: voteManagerPda,
+            treasury: treasuryPda,
+            treasuryTokenAccount,
+            project: [firstPda, secondPda][index],
+            mint: tokenMint.publicKey,
+            token: voterAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([voter])
+          .rpc();
+      }
+
+      const lowestPda = Buffer.compare(firstPda.toBuffer(), secondPda.toBuffer()) < 0 ? firstPda : secondPda;
+      const updatedVoteManager = await program.account.voteManager.fetch(voteManagerPda);
+      expect(updatedVoteManager.roundTied).to.equal(true);
+      expect(updatedVoteManager.roundLeader.toBase58()).to.equal(lowestPda.toBase58());
+    } finally {
+      // Restore the default policy.
+      await program.methods
+        .setTieBreak({ earliestCreated: {} }, new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+    }
+  });
+
+  /**
+   * Test Case: Vote fees paid in SOL
+   * Purpose: Ensure a vote paid in SOL moves the round's lamport fee into the Treasury PDA and
+   * leaves the voter's TTT untouched, and that the admin can withdraw it.
+   */
+  it("Votes paid in SOL transfer the lamport fee to the treasury", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    const solFee = 5_000_000;
+    const solProjectId = generateProjectId(10);
+    const solProjectPda = deriveProjectPda(solProjectId, currentRound, adminWallet.publicKey);
+    const voterDataPda = deriveVoterPda(currentRound, voterA.publicKey, solProjectId);
+
+    // The fee is frozen when the round opens, so it is set while the round is pending.
+    await program.methods.changeSolFee(new anchor.BN(solFee)).accounts(adminAccounts).rpc();
+    try {
+      await program.methods
+        .addProject(solProjectId)
+        .accounts({
+          projectData: solProjectPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await ensureOpenRound();
+
+      const treasuryBefore = await program.account.treasury.fetch(treasuryPda);
+      const treasuryLamportsBefore = await provider.connection.getBalance(treasuryPda);
+      const tokenBefore = await getAccount(provider.connection, voterAAta, undefined, TOKEN_2022_PROGRAM_ID);
+
+      await program.methods
+        .doVote(null, { sol: {} })
+        .accounts({
+          voterData: voterDataPda,
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: solProjectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+
+      const treasuryAfter = await program.account.treasury.fetch(treasuryPda);
+      const treasuryLamportsAfter = await provider.connection.getBalance(treasuryPda);
+      const tokenAfter = await getAccount(provider.connection, voterAAta, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(treasuryLamportsAfter - treasuryLamportsBefore).to.equal(solFee);
+      expect(treasuryAfter.totalCollectedSol.sub(treasuryBefore.totalCollectedSol).toNumber()).to.equal(solFee);
+      expect(treasuryAfter.totalCollected.toString()).to.equal(treasuryBefore.totalCollected.toString());
+      expect(tokenAfter.amount).to.equal(tokenBefore.amount);
+
+      const voterData = await program.account.voterData.fetch(voterDataPda);
+      expect(voterData.solFeesPaid.toNumber()).to.equal(solFee);
+      expect(voterData.feesPaid.toNumber()).to.equal(0);
+
+      // Only the admin withdraws the SOL fees.
+      const withdrawAccounts = {
+        treasury: treasuryPda,
+        voteManager: voteManagerPda,
+        owner: unauthorizedAttacker.publicKey,
+      };
+      try {
+        await program.methods
+          .withdrawTreasurySol(new anchor.BN(solFee))
+          .accounts(withdrawAccounts)
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected NotAdmin error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotAdmin");
+      }
+      await program.methods
+        .withdrawTreasurySol(new anchor.BN(solFee))
+        .accounts({ ...withdrawAccounts, owner: adminWallet.publicKey })
+        .rpc();
+      expect(await provider.connection.getBalance(treasuryPda)).to.equal(treasuryLamportsBefore);
+    } finally {
+      await program.methods.changeSolFee(new anchor.BN(0)).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Quadratic vote pricing
+   * Purpose: Ensure a voter's Nth vote on the same project costs N² × vote_fee.
+   */
+  it("Quadratic pricing charges N² × vote_fee for the Nth vote on a project", async () => {
+    await ensurePendingRound();
+    const quadraticProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const voteFee = voteManagerAccount.voteFee.toNumber();
+    const quadraticProjectPda = deriveProjectPda(quadraticProjectId, currentRound, adminWallet.publicKey);
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    await program.methods
+      .addProject(quadraticProjectId)
+      .accounts({
+        projectData: quadraticProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Only the admin can switch the pricing.
+    try {
+      await program.methods
+        .setQuadraticPricing(true)
+        .accounts({ voteData: voteManagerPda, owner: unauthorizedAttacker.publicKey })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    await program.methods.changeMaxVotes(3).accounts(adminAccounts).rpc();
+    await program.methods.setQuadraticPricing(true).accounts(adminAccounts).rpc();
+
+    await ensureOpenRound();
+
+    const doVoteAccounts = {
+      voterData: deriveVoterPda(currentRound, voterA.publicKey, quadraticProjectId),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: quadraticProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      for (const n of [1, 2, 3]) {
+        const treasuryBefore = await getTokenBalance(provider.connection, treasuryTokenAccount);
+        await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterA]).rpc({ commitment: "confirmed" });
+        const treasuryAfter = await getTokenBalance(provider.connection, treasuryTokenAccount);
+        expect(treasuryAfter - treasuryBefore).to.equal(n * n * voteFee, `Vote ${n} costs ${n}² × vote_fee`);
+      }
+    } finally {
+      // Restore flat pricing and the default limit.
+      await program.methods.setQuadraticPricing(false).accounts(adminAccounts).rpc();
+      await program.methods.changeMaxVotes(1).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Eligibility snapshot
+   * Purpose: While a snapshot root is set, only wallets with a valid proof can vote, with the weight
+   * frozen in the snapshot.
+   */
+  it("Eligibility snapshots restrict do_vote to proven wallets and weights", async () => {
+    await ensurePendingRound();
+    const snapshotProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const snapshotProjectPda = deriveProjectPda(snapshotProjectId, currentRound, adminWallet.publicKey);
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    await program.methods
+      .addProject(snapshotProjectId)
+      .accounts({
+        projectData: snapshotProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Three entries, so the last leaf moves up unpaired; voter B is not in the snapshot.
+    const entries: [PublicKey, number][] = [
+      [voterA.publicKey, 7],
+      [unauthorizedAttacker.publicKey, 3],
+      [insufficientUser.publicKey, 5],
+    ];
+    const levels = eligibilityTree(entries.map(([voter, weight]) => eligibilityLeaf(voter, weight)));
+    const root = Array.from(levels[levels.length - 1][0]);
+    const voterAProof = eligibilityProof(levels, 0);
+
+    // Only the admin can set the root.
+    try {
+      await program.methods
+        .setEligibilityRoot(root, new anchor.BN(15))
+        .accounts({ voteData: voteManagerPda, owner: unauthorizedAttacker.publicKey })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    await program.methods.setEligibilityRoot(root, new anchor.BN(15)).accounts(adminAccounts).rpc();
+    const snapshotManager = await program.account.voteManager.fetch(voteManagerPda);
+    expect(snapshotManager.eligibleWeight.toNumber()).to.equal(15);
+
+    await ensureOpenRound();
+
+    const voteAccounts = (voter: Keypair, voterAta: PublicKey) => ({
+      voterData: deriveVoterPda(currentRound, voter.publicKey, snapshotProjectId),
+      signer: voter.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: snapshotProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+    const expectNotEligible = async (proof: any, voter: Keypair, voterAta: PublicKey) => {
+      try {
+        await program.methods.doVote(proof, { ttt: {} }).accounts(voteAccounts(voter, voterAta)).signers([voter]).rpc();
+        throw new Error("Expected NotEligible error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotEligible");
+      }
+    };
+
+    try {
+      // Missing proof, inflated weight, and another wallet's proof.
+      await expectNotEligible(null, voterA, voterAAta);
+      await expectNotEligible({ weight: new anchor.BN(8), path: voterAProof }, voterA, voterAAta);
+      await expectNotEligible({ weight: new anchor.BN(7), path: voterAProof }, voterB, voterBAta);
+
+      // A valid proof counts the snapshot weight, not the current balance.
+      const signature = await program.methods
+        .doVote({ weight: new anchor.BN(7), path: voterAProof }, { ttt: {} })
+        .accounts(voteAccounts(voterA, voterAAta))
+        .signers([voterA])
+        .rpc({ commitment: "confirmed" });
+      const [voteCast] = await fetchEvents(signature);
+      expect(voteCast.data.weight.toNumber()).to.equal(7);
+      const voterAccount = await program.account.voterData.fetch(voteAccounts(voterA, voterAAta).voterData);
+      expect(voterAccount.voteWeight.toNumber()).to.equal(7);
+    } finally {
+      // Disable the snapshot.
+      await program.methods
+        .setEligibilityRoot(new Array(32).fill(0), new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+    }
+  });
+
+  /**
+   * Test Case: Vote-escrow staking
+   * Purpose: In escrow mode, votes weigh the voting power of the voter's locked stake, which is
+   * released only after the lock expires.
+   */
+  it("Escrow mode weighs votes with staked TTT and releases it after the lock", async () => {
+    await ensurePendingRound();
+    const escrowProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const escrowProjectPda = deriveProjectPda(escrowProjectId, currentRound, adminWallet.publicKey);
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    const stakePositionPda = deriveStakePositionPda(voteManagerPda, voterA.publicKey);
+    const stakeVault = getAssociatedTokenAddressSync(tokenMint.publicKey, stakePositionPda, true, TOKEN_2022_PROGRAM_ID);
+    const stakeAccounts = {
+      stakePosition: stakePositionPda,
+      stakeVault,
+      ownerTokenAccount: voterAAta,
+      voteManager: voteManagerPda,
+      mint: tokenMint.publicKey,
+      owner: voterA.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+
+    await program.methods
+      .addProject(escrowProjectId)
+      .accounts({
+        projectData: escrowProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Staking needs escrow mode.
+    try {
+      await program.methods
+        .stakeForPower(new anchor.BN(40), new anchor.BN(2))
+        .accounts(stakeAccounts)
+        .signers([voterA])
+        .rpc();
+      throw new Error("Expected EscrowDisabled error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("EscrowDisabled");
+    }
+
+    await program.methods.setEscrowMode(true, new anchor.BN(4)).accounts(adminAccounts).rpc();
+
+    try {
+      // Half the maximum lock earns half the staked amount.
+      const initialVoterBalance = await getTokenBalance(provider.connection, voterAAta);
+      await program.methods
+        .stakeForPower(new anchor.BN(40), new anchor.BN(2))
+        .accounts(stakeAccounts)
+        .signers([voterA])
+        .rpc();
+      const stakePosition = await program.account.stakePosition.fetch(stakePositionPda);
+      expect(stakePosition.votingPower.toNumber()).to.equal(20);
+      expect(await getTokenBalance(provider.connection, stakeVault)).to.equal(40);
+
+      try {
+        await program.methods.unstake().accounts(stakeAccounts).signers([voterA]).rpc();
+        throw new Error("Expected StakeStillLocked error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("StakeStillLocked");
+      }
+
+      await ensureOpenRound();
+
+      const voteAccounts = (voter: Keypair, voterAta: PublicKey) => ({
+        voterData: deriveVoterPda(currentRound, voter.publicKey, escrowProjectId),
+        signer: voter.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: escrowProjectPda,
+        mint: tokenMint.publicKey,
+        token: voterAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      });
+      const stakeMeta = [{ pubkey: stakePositionPda, isSigner: false, isWritable: false }];
+
+      // Another wallet's position does not count for the signer.
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts(voteAccounts(voterB, voterBAta))
+          .remainingAccounts(stakeMeta)
+          .signers([voterB])
+          .rpc();
+        throw new Error("Expected IncorrectStakePosition error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("IncorrectStakePosition");
+      }
+
+      const signature = await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts(voteAccounts(voterA, voterAAta))
+        .remainingAccounts(stakeMeta)
+        .signers([voterA])
+        .rpc({ commitment: "confirmed" });
+      const [voteCast] = await fetchEvents(signature);
+      expect(voteCast.data.weight.toNumber()).to.equal(20);
+
+      // Once the lock expires the stake is released and the position closed.
+      await new Promise((resolve) => setTimeout(resolve, 3_000));
+      const feesPaid = (
+        await program.account.voterData.fetch(voteAccounts(voterA, voterAAta).voterData)
+      ).feesPaid.toNumber();
+      await program.methods.unstake().accounts(stakeAccounts).signers([voterA]).rpc();
+      expect(await program.account.stakePosition.fetchNullable(stakePositionPda)).to.equal(null);
+      expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(initialVoterBalance - feesPaid);
+    } finally {
+      await program.methods.setEscrowMode(false, new anchor.BN(0)).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Minimum holding age
+   * Purpose: With a holding requirement set, only wallets that registered their holding long enough
+   * before the round opened can vote.
+   */
+  it("Holding requirement rejects unregistered and recently registered holdings", async () => {
+    await ensurePendingRound();
+    const holdingProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const holdingProjectPda = deriveProjectPda(holdingProjectId, currentRound, adminWallet.publicKey);
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    const holdingAttestationPda = deriveHoldingAttestationPda(voteManagerPda, voterA.publicKey);
+    const registerAccounts = {
+      holdingAttestation: holdingAttestationPda,
+      token: voterAAta,
+      voteManager: voteManagerPda,
+      owner: voterA.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.methods
+      .addProject(holdingProjectId)
+      .accounts({
+        projectData: holdingProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Both the balance and the age must be set.
+    try {
+      await program.methods
+        .setHoldingRequirement(new anchor.BN(10), new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+      throw new Error("Expected IncorrectHoldingRequirement error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectHoldingRequirement");
+    }
+
+    await program.methods
+      .setHoldingRequirement(new anchor.BN(10), new anchor.BN(1_000_000))
+      .accounts(adminAccounts)
+      .rpc();
+
+    try {
+      await program.methods.registerHolding().accounts(registerAccounts).signers([voterA]).rpc();
+      const attestation = await program.account.holdingAttestation.fetch(holdingAttestationPda);
+      expect(attestation.tokenAccount.toBase58()).to.equal(voterAAta.toBase58());
+
+      await ensureOpenRound();
+
+      const voteAccounts = (voter: Keypair, voterAta: PublicKey, holdingAttestation: PublicKey | null) => ({
+        voterData: deriveVoterPda(currentRound, voter.publicKey, holdingProjectId),
+        signer: voter.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: holdingProjectPda,
+        mint: tokenMint.publicKey,
+        token: voterAta,
+        holdingAttestation,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      });
+
+      // Wallets that never registered cannot vote.
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts(voteAccounts(voterB, voterBAta, null))
+          .signers([voterB])
+          .rpc();
+        throw new Error("Expected HoldingNotRegistered error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("HoldingNotRegistered");
+      }
+
+      // The holding was registered right before the round opened.
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts(voteAccounts(voterA, voterAAta, holdingAttestationPda))
+          .signers([voterA])
+          .rpc();
+        throw new Error("Expected HoldingTooRecent error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("HoldingTooRecent");
+      }
+    } finally {
+      await program.methods
+        .setHoldingRequirement(new anchor.BN(0), new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+    }
+  });
+
+  /**
+   * Test Case: Access lists
+   * Purpose: Under an allowlist only listed wallets vote, under a blocklist listed wallets cannot,
+   * and removing an entry lifts it.
+   */
+  it("Access lists restrict who votes", async () => {
+    await ensurePendingRound();
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    const allowlistEntryPda = deriveAccessEntryPda("allowlist", voteManagerPda, voterA.publicKey);
+    const blocklistEntryPda = deriveAccessEntryPda("blocklist", voteManagerPda, voterB.publicKey);
+
+    // Each round votes on a project of its own.
+    const addAccessProject = async () => {
+      const projectId = generateProjectId(10);
+      const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+      const projectPda = deriveProjectPda(projectId, round, adminWallet.publicKey);
+      await program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: projectPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return (voter: Keypair, voterAta: PublicKey, accessEntry: PublicKey | null) => ({
+        voterData: deriveVoterPda(round, voter.publicKey, projectId),
+        signer: voter.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: projectPda,
+        mint: tokenMint.publicKey,
+        token: voterAta,
+        accessEntry,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      });
+    };
+
+    // Only the admin lists wallets.
+    try {
+      await program.methods
+        .addToAllowlist(voterA.publicKey)
+        .accounts({
+          entry: allowlistEntryPda,
+          voteManager: voteManagerPda,
+          owner: unauthorizedAttacker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    await program.methods
+      .addToAllowlist(voterA.publicKey)
+      .accounts({
+        entry: allowlistEntryPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .addToBlocklist(voterB.publicKey)
+      .accounts({
+        entry: blocklistEntryPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const entry = await program.account.accessListEntry.fetch(allowlistEntryPda);
+    expect(entry.voter.toBase58()).to.equal(voterA.publicKey.toBase58());
+    expect(entry.list).to.deep.equal({ allowlist: {} });
+
+    let voteAccounts = await addAccessProject();
+    await program.methods.setAccessMode({ allowlist: {} }).accounts(adminAccounts).rpc();
+    try {
+      await ensureOpenRound();
+
+      // voterB has no allowlist entry; its (empty) PDA is passed instead.
+      const unlistedEntryPda = deriveAccessEntryPda("allowlist", voteManagerPda, voterB.publicKey);
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts(voteAccounts(voterB, voterBAta, unlistedEntryPda))
+          .signers([voterB])
+          .rpc();
+        throw new Error("Expected NotAllowlisted error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotAllowlisted");
+      }
+
+      // Another wallet's entry does not stand in for the voter's own.
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts(voteAccounts(voterB, voterBAta, allowlistEntryPda))
+          .signers([voterB])
+          .rpc();
+        throw new Error("Expected WrongAccessEntry error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("WrongAccessEntry");
+      }
+
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts(voteAccounts(voterA, voterAAta, allowlistEntryPda))
+        .signers([voterA])
+        .rpc();
+
+      // The access mode only changes between rounds.
+      try {
+        await program.methods.setAccessMode({ blocklist: {} }).accounts(adminAccounts).rpc();
+        throw new Error("Expected RoundNotPending error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RoundNotPending");
+      }
+    } finally {
+      await ensurePendingRound();
+      await program.methods.setAccessMode({ blocklist: {} }).accounts(adminAccounts).rpc();
+    }
+
+    try {
+      voteAccounts = await addAccessProject();
+      await ensureOpenRound();
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts(voteAccounts(voterB, voterBAta, blocklistEntryPda))
+          .signers([voterB])
+          .rpc();
+        throw new Error("Expected Blocklisted error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Blocklisted");
+      }
+
+      // Removing the entry lifts the block.
+      await program.methods
+        .removeFromAccessList()
+        .accounts({
+          entry: blocklistEntryPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+        })
+        .rpc();
+      expect(await program.account.accessListEntry.fetchNullable(blocklistEntryPda)).to.equal(null);
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts(voteAccounts(voterB, voterBAta, blocklistEntryPda))
+        .signers([voterB])
+        .rpc();
+    } finally {
+      await ensurePendingRound();
+      await program.methods.setAccessMode({ open: {} }).accounts(adminAccounts).rpc();
+      await program.methods
+        .removeFromAccessList()
+        .accounts({
+          entry: allowlistEntryPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+        })
+        .rpc();
+    }
+  });
+
+  /**
+   * Test Case: Fee discounts
+   * Purpose: Ensure the admin sets valid discount tiers between rounds only, and a vote passing the
+   * schedule pays the fee less the discount of the highest tier the voter's balance reaches.
+   */
+  it("Fee discounts lower the vote fee of holders", async () => {
+    await ensurePendingRound();
+    const feeDiscountPda = deriveFeeDiscountPda(voteManagerPda);
+    const setFeeDiscounts = (tiers: { minBalance: anchor.BN; discountBps: number }[]) =>
+      program.methods.setFeeDiscounts(tiers).accounts({
+        feeDiscounts: feeDiscountPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      });
+    const tiers = [
+      { minBalance: new anchor.BN(1), discountBps: 2_500 },
+      { minBalance: new anchor.BN("18446744073709551615"), discountBps: 5_000 },
+    ];
+
+    // Tiers must ascend in balance.
+    try {
+      await setFeeDiscounts([...tiers].reverse()).rpc();
+      throw new Error("Expected IncorrectFeeDiscounts error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectFeeDiscounts");
+    }
+    await setFeeDiscounts(tiers).rpc();
+
+    const projectId = generateProjectId(10);
+    const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectPda = deriveProjectPda(projectId, round, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await ensureOpenRound();
+
+    try {
+      // The schedule is fixed while the round is open.
+      try {
+        await setFeeDiscounts([]).rpc();
+        throw new Error("Expected RoundNotPending error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RoundNotPending");
+      }
+
+      const voteFee = (await program.account.voteManager.fetch(voteManagerPda)).roundConfig.voteFee;
+      const voterDataPda = deriveVoterPda(round, voterA.publicKey, projectId);
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: voterDataPda,
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          feeDiscounts: feeDiscountPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+
+      // voterA only reaches the first tier, a 25% discount rounded up.
+      const voterData = await program.account.voterData.fetch(voterDataPda);
+      expect(voterData.feesPaid.toNumber()).to.equal(Math.ceil((voteFee.toNumber() * 7_500) / 10_000));
+    } finally {
+      await ensurePendingRound();
+      await setFeeDiscounts([]).rpc();
+    }
+    expect((await program.account.feeDiscountSchedule.fetch(feeDiscountPda)).tiers).to.deep.equal([]);
+  });
+
+  /**
+   * Test Case: Multiple elections
+   * Purpose: One admin can run several elections at once, each with its own VoteManager, treasury,
+   * projects and voter records, even when project ids repeat.
+   */
+  it("One admin runs independent elections side by side", async () => {
+    await ensurePendingRound();
+    const electionId = 1;
+    const electionVoteManagerPda = deriveVoteManagerPda(adminWallet.publicKey, electionId);
+    expect(deriveVoteManagerPda(adminWallet.publicKey).toBase58()).to.equal(voteManagerPda.toBase58());
+
+    await program.methods
+      .initialize(new anchor.BN(electionId), new anchor.BN(100))
+      .accounts({
+        voteData: electionVoteManagerPda,
+        owner: adminWallet.publicKey,
+        mint: tokenMint.publicKey,
+        adminTokenAccount: mintTokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const electionTreasuryPda = deriveTreasuryPda(electionVoteManagerPda);
+    const electionTreasuryTokenAccount = deriveMintTokenAccount(tokenMint.publicKey, electionTreasuryPda);
+    await program.methods
+      .initTreasury()
+      .accounts({
+        treasury: electionTreasuryPda,
+        treasuryTokenAccount: electionTreasuryTokenAccount,
+        voteManager: electionVoteManagerPda,
+        mint: tokenMint.publicKey,
+        owner: adminWallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const electionManager = await program.account.voteManager.fetch(electionVoteManagerPda);
+    expect(electionManager.electionId.toNumber()).to.equal(electionId);
+    expect(electionManager.voteRound.toNumber()).to.equal(1);
+    const defaultRound = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+
+    // The same project id is added to both elections.
+    const sharedProjectId = generateProjectId(10);
+    const defaultProjectPda = deriveProjectPda(sharedProjectId, defaultRound, adminWallet.publicKey);
+    const electionProjectPda = deriveProjectPda(sharedProjectId, 1, adminWallet.publicKey, electionId);
+    for (const [projectData, voteManager] of [
+      [defaultProjectPda, voteManagerPda],
+      [electionProjectPda, electionVoteManagerPda],
+    ]) {
+      await program.methods
+        .addProject(sharedProjectId)
+        .accounts({
+          projectData,
+          voteManager,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+    await program.methods
+      .openRound()
+      .accounts({ voteData: electionVoteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+
+    // A vote in election 1 is charged and counted there only.
+    const electionVoterPda = deriveVoterPda(1, voterA.publicKey, sharedProjectId, electionId);
+    await program.methods
+      .doVote(null, { ttt: {} })
+      .accounts({
+        voterData: electionVoterPda,
+        signer: voterA.publicKey,
+        voteManager: electionVoteManagerPda,
+        treasury: electionTreasuryPda,
+        treasuryTokenAccount: electionTreasuryTokenAccount,
+        project: electionProjectPda,
+        mint: tokenMint.publicKey,
+        token: voterAAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([voterA])
+      .rpc({ commitment: "confirmed" });
+
+    const electionProject = await program.account.projectData.fetch(electionProjectPda);
+    const defaultProject = await program.account.projectData.fetch(defaultProjectPda);
+    expect(electionProject.voteCount.toNumber()).to.equal(1);
+    expect(electionProject.voteManager.toBase58()).to.equal(electionVoteManagerPda.toBase58());
+    expect(defaultProject.voteCount.toNumber()).to.equal(0);
+    expect(await getTokenBalance(provider.connection, electionTreasuryTokenAccount)).to.equal(100);
+    expect((await program.account.voterData.fetch(electionVoterPda)).voteCount.toNumber()).to.equal(1);
+
+    // The voter's record of election 0 is a separate account, still free to vote.
+    const defaultVoterPda = deriveVoterPda(defaultRound, voterA.publicKey, sharedProjectId);
+    expect(defaultVoterPda.toBase58()).to.not.equal(electionVoterPda.toBase58());
+    expect(await provider.connection.getAccountInfo(defaultVoterPda)).to.be.null;
+  });
+
+  /**
+   * Test Case: Round lifecycle
+   * Purpose: Ensure projects are only added while a round is pending, votes are only cast while it
+   * is open, and only the admin opens and closes rounds.
+   */
+  it("Round states gate project additions and votes", async () => {
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    await ensurePendingRound();
+
+    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round = voteManagerAccount.voteRound.toNumber();
+    expect(voteManagerAccount.roundState).to.deep.equal({ pending: {} });
+    const stateProjectId = generateProjectId(10);
+
+    const addProject = (id: string) =>
+      program.methods
+        .addProject(id)
+        .accounts({
+          projectData: deriveProjectPda(id, round, adminWallet.publicKey),
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    const voteAccounts = (voter: Keypair, voterAta: PublicKey) => ({
+      voterData: deriveVoterPda(round, voter.publicKey, stateProjectId),
+      signer: voter.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: deriveProjectPda(stateProjectId, round, adminWallet.publicKey),
+      mint: tokenMint.publicKey,
+      token: voterAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx;
+        throw new Error(`Expected ${code} error, but transaction succeeded.`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    };
+
+    await addProject(stateProjectId);
+
+    // A pending round rejects votes and cannot be closed.
+    await expectError(
+      program.methods.doVote(null, { ttt: {} }).accounts(voteAccounts(voterA, voterAAta)).signers([voterA]).rpc(),
+      "RoundNotOpen"
+    );
+    await expectError(program.methods.closeRound().accounts(adminAccounts).rpc(), "RoundNotOpen");
+
+    // Only the admin can open the round.
+    await expectError(
+      program.methods
+        .openRound()
+        .accounts({ voteData: voteManagerPda, owner: unauthorizedAttacker.publicKey })
+        .signers([unauthorizedAttacker])
+        .rpc(),
+      "NotAdmin"
+    );
+
+    const signature = await program.methods.openRound().accounts(adminAccounts).rpc({ commitment: "confirmed" });
+    const [opened] = await fetchEvents(signature);
+    expect(opened.name).to.equal("roundStateChanged");
+    expect(opened.data.round.toNumber()).to.equal(round);
+    expect(opened.data.state).to.deep.equal({ open: {} });
+
+    // An open round freezes its project list and cannot be opened again.
+    await expectError(addProject(generateProjectId(10)), "RoundNotPending");
+    await expectError(program.methods.openRound().accounts(adminAccounts).rpc(), "RoundNotPending");
+
+    await program.methods.doVote(null, { ttt: {} }).accounts(voteAccounts(voterA, voterAAta)).signers([voterA]).rpc();
+
+    // A closed round keeps its votes but rejects new ones.
+    await program.methods.closeRound().accounts(adminAccounts).rpc();
+    voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.roundState).to.deep.equal({ closed: {} });
+    expect(voteManagerAccount.roundTotalVotes.toNumber()).to.equal(1);
+    await expectError(
+      program.methods.doVote(null, { ttt: {} }).accounts(voteAccounts(voterB, voterBAta)).signers([voterB]).rpc(),
+      "RoundNotOpen"
+    );
+    await expectError(program.methods.closeRound().accounts(adminAccounts).rpc(), "RoundNotOpen");
+
+    // The next round starts pending.
+    await program.methods.incrementRound().accounts(adminAccounts).rpc();
+    voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.voteRound.toNumber()).to.equal(round + 1);
+    expect(voteManagerAccount.roundState).to.deep.equal({ pending: {} });
+  });
+
+  /**
+   * Test Case: Vote receipts
+   * Purpose: Ensure `do_vote` mints a non-transferable receipt through the ttt_token program
+   * while receipts are enabled, and only accepts non-transferable receipt mints.
+   */
+  it("Votes mint non-transferable receipts while enabled", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round = voteManagerAccount.voteRound.toNumber();
+    const receiptProjectId = generateProjectId(10);
+    const receiptAuthorityPda = deriveReceiptAuthorityPda(voteManagerPda);
+
+    // Create the round's receipt mint, minted by the VoteManager's receipt authority.
+    const receiptMint = Keypair.generate();
+    await tokenProgram.methods
+      .createReceiptMint({
+        name: `I voted in round ${round}`,
+        symbol: "VOTED",
+        uri: "https://my-token-data.com/receipt.json",
+        mintAuthority: receiptAuthorityPda,
+      })
+      .accounts({
+        payer: provider.publicKey,
+        authority: adminWallet.publicKey,
+        mint: receiptMint.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([receiptMint])
+      .rpc();
+
+    const receiptsAccounts = (mint: PublicKey) => ({
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+      receiptAuthority: receiptAuthorityPda,
+      receiptMint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    // A transferable mint would turn receipts into tradable tokens.
+    const transferableMint = await createMint(
+      provider.connection,
+      admin,
+      receiptAuthorityPda,
+      null,
+      0,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    try {
+      await program.methods.setVoteReceipts(true).accounts(receiptsAccounts(transferableMint)).rpc();
+      throw new Error("Expected ReceiptMintTransferable error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ReceiptMintTransferable");
+    }
+
+    await program.methods.setVoteReceipts(true).accounts(receiptsAccounts(receiptMint.publicKey)).rpc();
+    try {
+      await program.methods
+        .addProject(receiptProjectId)
+        .accounts({
+          projectData: deriveProjectPda(receiptProjectId, round, adminWallet.publicKey),
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await ensureOpenRound();
+
+      const voteAccounts = {
+        voterData: deriveVoterPda(round, voterA.publicKey, receiptProjectId),
+        signer: voterA.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: deriveProjectPda(receiptProjectId, round, adminWallet.publicKey),
+        mint: tokenMint.publicKey,
+        token: voterAAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      };
+      const receiptTokenAccount = getAssociatedTokenAddressSync(
+        receiptMint.publicKey,
+        voterA.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      // Votes without the receipt accounts are rejected while receipts are enabled.
+      try {
+        await program.methods.doVote(null, { ttt: {} }).accounts(voteAccounts).signers([voterA]).rpc();
+        throw new Error("Expected IncorrectReceiptAccounts error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("IncorrectReceiptAccounts");
+      }
+
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts(voteAccounts)
+        .remainingAccounts([
+          { pubkey: receiptMint.publicKey, isWritable: true, isSigner: false },
+          { pubkey: receiptAuthorityPda, isWritable: false, isSigner: false },
+          { pubkey: receiptTokenAccount, isWritable: true, isSigner: false },
+          { pubkey: TOKEN_2022_PROGRAM_ID, isWritable: false, isSigner: false },
+          { pubkey: ASSOCIATED_PROGRAM_ID, isWritable: false, isSigner: false },
+          { pubkey: tokenProgram.programId, isWritable: false, isSigner: false },
+        ])
+        .signers([voterA])
+        .rpc();
+
+      const receipt = await getAccount(provider.connection, receiptTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(Number(receipt.amount)).to.equal(1);
+      expect(receipt.owner.toBase58()).to.equal(voterA.publicKey.toBase58());
+    } finally {
+      // Disable receipts for the other tests.
+      await program.methods.setVoteReceipts(false).accounts(receiptsAccounts(receiptMint.publicKey)).rpc();
+    }
+    const disabled = await program.account.voteManager.fetch(voteManagerPda);
+    expect(disabled.voteReceipts).to.equal(false);
+    expect(disabled.receiptMint.toBase58()).to.equal(receiptMint.publicKey.toBase58());
+  });
+
+  /**
+   * Test Case: Voting after the round deadline fails with RoundEnded
+   * Purpose: Ensure `do_vote` enforces the schedule set by `set_round_schedule`.
+   */
+  it("Voting after the round deadline fails with RoundEnded", async () => {
+    await ensurePendingRound();
+    const lateProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const lateProjectPda = deriveProjectPda(lateProjectId, currentRound, adminWallet.publicKey);
+
+    await program.methods
+      .addProject(lateProjectId)
+      .accounts({
+        projectData: lateProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const scheduleAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    // Schedule a one-second round that ended long ago.
+    const pastStart = Math.floor(Date.now() / 1000) - 3600;
+    await program.methods
+      .setRoundSchedule(new anchor.BN(pastStart), new anchor.BN(1))
+      .accounts(scheduleAccounts)
+      .rpc();
+
+    await ensureOpenRound();
+
+    const doVoteAccounts = {
+      voterData: deriveVoterPda(currentRound, voterA.publicKey, lateProjectId),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: lateProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterA]).rpc();
+      throw new Error("Expected RoundEnded error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RoundEnded");
+    } finally {
+      // Remove the deadline so later tests can vote.
+      await program.methods
+        .setRoundSchedule(new anchor.BN(pastStart), new anchor.BN(0))
+        .accounts(scheduleAccounts)
+        .rpc();
+    }
+  });
+
+  /**
+   * Test Case: Capped linear weight curve
+   * Purpose: Ensure `_do_vote` adds the curve-evaluated weight to the project's tally.
+   */
+  it("Capped linear weight curve weighs votes by balance", async () => {
+    await ensurePendingRound();
+    const weightedProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const weightedProjectPda = deriveProjectPda(weightedProjectId, currentRound, adminWallet.publicKey);
+
+    await program.methods
+      .addProject(weightedProjectId)
+      .accounts({
+        projectData: weightedProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const curveAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    // Linear at half weight per token, capped at 1,000, frozen for the round when it opens.
+    const cap = 1_000;
+    await program.methods
+      .setWeightCurve({ mode: { linear: {} }, multiplier: new anchor.BN(500_000), cap: new anchor.BN(cap) })
+      .accounts(curveAccounts)
+      .rpc();
+
+    await ensureOpenRound();
+
+    const balance = await getTokenBalance(provider.connection, voterAAta);
+
+    try {
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterA.publicKey, weightedProjectId),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: weightedProjectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+    } finally {
+      // Restore one vote, one weight.
+      await program.methods
+        .setWeightCurve({ mode: { flat: {} }, multiplier: new anchor.BN(1_000_000), cap: new anchor.BN(0) })
+        .accounts(curveAccounts)
+        .rpc();
+    }
+
+    const projectAccount = await program.account.projectData.fetch(weightedProjectPda);
+    expect(projectAccount.voteCount.toNumber()).to.equal(1);
+    expect(projectAccount.voteWeight.toNumber()).to.equal(Math.min(cap, Math.floor(balance / 2)));
+  });
+
+  /**
+   * Test Case: Frozen round fee
+   * Purpose: Ensure votes pay the fee frozen when their round opened, not a fee changed mid-round.
+   */
+  it("Votes pay the fee frozen when their round opened", async () => {
+    await ensurePendingRound();
+    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const frozenFee = voteManagerAccount.voteFee;
+
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await ensureOpenRound();
+
+    const adminAccounts = { voteData: voteManagerPda, owner: adminWallet.publicKey };
+    await program.methods.changeFee(frozenFee.addn(7)).accounts(adminAccounts).rpc();
+    try {
+      voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      expect(voteManagerAccount.roundConfig.voteFee.toNumber()).to.equal(frozenFee.toNumber());
+
+      const balanceBefore = await getTokenBalance(provider.connection, voterAAta);
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterA.publicKey, projectId),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+      const balanceAfter = await getTokenBalance(provider.connection, voterAAta);
+      expect(balanceBefore - balanceAfter).to.equal(frozenFee.toNumber());
+    } finally {
+      await program.methods.changeFee(frozenFee).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Emergency pause
+   * Purpose: Ensure a paused VoteManager rejects project additions and votes until it is resumed.
+   */
+  it("Paused VoteManager rejects project additions and votes until resumed", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+    const addProject = () =>
+      program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: projectPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    const doVote = () =>
+      program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterA.publicKey, projectId),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+
+    const adminAccounts = { voteData: voteManagerPda, owner: adminWallet.publicKey };
+    await program.methods.setPaused(true).accounts(adminAccounts).rpc();
+    try {
+      try {
+        await addProject();
+        throw new Error("Expected Paused error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Paused");
+      }
+
+      await program.methods.setPaused(false).accounts(adminAccounts).rpc();
+      await addProject();
+      await ensureOpenRound();
+
+      await program.methods.setPaused(true).accounts(adminAccounts).rpc();
+      try {
+        await doVote();
+        throw new Error("Expected Paused error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Paused");
+      }
+    } finally {
+      await program.methods.setPaused(false).accounts(adminAccounts).rpc();
+    }
+
+    await doVote();
+    const projectAccount = await program.account.projectData.fetch(projectPda);
+    expect(projectAccount.voteCount.toNumber()).to.equal(1);
+  });
+
+  /**
+   * Test Case: Fee burning mode
+   * Purpose: Ensure votes burn their fee under `FeeMode::Burn`, and the mode is locked once a round opens.
+   */
+  it("Votes burn their fee in burn mode, switched only between rounds", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const fee = voteManagerAccount.voteFee.toNumber();
+
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const adminAccounts = { voteData: voteManagerPda, owner: adminWallet.publicKey };
+    await program.methods.setFeeMode({ burn: {} }).accounts(adminAccounts).rpc();
+    try {
+      await ensureOpenRound();
+      try {
+        await program.methods.setFeeMode({ collect: {} }).accounts(adminAccounts).rpc();
+        throw new Error("Expected RoundNotPending error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RoundNotPending");
+      }
+
+      const supplyBefore = (await getMint(provider.connection, tokenMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID)).supply;
+      const treasuryBefore = await getTokenBalance(provider.connection, treasuryTokenAccount);
+      const voterPda = deriveVoterPda(currentRound, voterA.publicKey, projectId);
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: voterPda,
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+
+      const supplyAfter = (await getMint(provider.connection, tokenMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID)).supply;
+      expect(Number(supplyBefore - supplyAfter)).to.equal(fee);
+      expect(await getTokenBalance(provider.connection, treasuryTokenAccount)).to.equal(treasuryBefore);
+      // Burned fees are not refundable.
+      const voterAccount = await program.account.voterData.fetch(voterPda);
+      expect(voterAccount.feesPaid.toNumber()).to.equal(0);
+    } finally {
+      await ensurePendingRound();
+      await program.methods.setFeeMode({ collect: {} }).accounts(adminAccounts).rpc();
+    }
+  });
+
+  /**
+   * Test Case: Removing and disqualifying projects
+   * Purpose: Ensure removed projects are closed and disqualified projects reject votes.
+   */
+  it("Removed projects are closed and disqualified projects reject votes", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const removedProjectId = generateProjectId(10);
+    const removedProjectPda = deriveProjectPda(removedProjectId, currentRound, adminWallet.publicKey);
+    const disqualifiedProjectId = generateProjectId(10);
+    const disqualifiedProjectPda = deriveProjectPda(disqualifiedProjectId, currentRound, adminWallet.publicKey);
+
+    for (const [id, pda] of [
+      [removedProjectId, removedProjectPda],
+      [disqualifiedProjectId, disqualifiedProjectPda],
+    ] as [string, PublicKey][]) {
+      await program.methods
+        .addProject(id)
+        .accounts({
+          projectData: pda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    // Removing closes the account.
+    await program.methods
+      .removeProject()
+      .accounts({ project: removedProjectPda, voteManager: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+    expect(await program.account.projectData.fetchNullable(removedProjectPda)).to.equal(null);
+
+    // Disqualifying keeps the account but blocks votes.
+    await program.methods
+      .disqualifyProject()
+      .accounts({ project: disqualifiedProjectPda, voteManager: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+    const projectAccount = await program.account.projectData.fetch(disqualifiedProjectPda);
+    expect(projectAccount.disqualified).to.equal(1);
+
+    try {
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterA.publicKey, disqualifiedProjectId),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: disqualifiedProjectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+      throw new Error("Expected ProjectDisqualified error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ProjectDisqualified");
+    }
+  });
+
+  /**
+   * Test Case: Refunds for disqualified projects
+   * Purpose: Ensure voters get their fees back once per record, and only for disqualified projects.
+   */
+  it("Voters claim back their fees once a project is disqualified", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const disqualifiedProjectId = generateProjectId(10);
+    const disqualifiedProjectPda = deriveProjectPda(disqualifiedProjectId, currentRound, adminWallet.publicKey);
+    const runningProjectId = generateProjectId(10);
+    const runningProjectPda = deriveProjectPda(runningProjectId, currentRound, adminWallet.publicKey);
+
+    for (const [id, pda] of [
+      [disqualifiedProjectId, disqualifiedProjectPda],
+      [runningProjectId, runningProjectPda],
+    ] as [string, PublicKey][]) {
+      await program.methods
+        .addProject(id)
+        .accounts({
+          projectData: pda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+    await ensureOpenRound();
+
+    const initialVoterBalance = await getTokenBalance(provider.connection, voterAAta);
+    for (const [id, pda] of [
+      [disqualifiedProjectId, disqualifiedProjectPda],
+      [runningProjectId, runningProjectPda],
+    ] as [string, PublicKey][]) {
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterA.publicKey, id),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: pda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+    }
+    const disqualifiedVoterPda = deriveVoterPda(currentRound, voterA.publicKey, disqualifiedProjectId);
+    const feesPaid = (await program.account.voterData.fetch(disqualifiedVoterPda)).feesPaid.toNumber();
+    expect(feesPaid).to.be.greaterThan(0);
+
+    await program.methods
+      .disqualifyProject()
+      .accounts({ project: disqualifiedProjectPda, voteManager: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+
+    const claimAccounts = (projectId: string, project: PublicKey) => ({
+      voterData: deriveVoterPda(currentRound, voterA.publicKey, projectId),
+      signer: voterA.publicKey,
+      project,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+    const balanceBeforeClaim = await getTokenBalance(provider.connection, voterAAta);
+    await program.methods
+      .claimRefund()
+      .accounts(claimAccounts(disqualifiedProjectId, disqualifiedProjectPda))
+      .signers([voterA])
+      .rpc();
+    expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(balanceBeforeClaim + feesPaid);
+    const voterAccount = await program.account.voterData.fetch(disqualifiedVoterPda);
+    expect(voterAccount.refunded).to.equal(1);
+
+    // Only the vote on the running project stays paid.
+    const runningFees = (
+      await program.account.voterData.fetch(deriveVoterPda(currentRound, voterA.publicKey, runningProjectId))
+    ).feesPaid.toNumber();
+    expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(initialVoterBalance - runningFees);
+
+    for (const [projectId, project, code] of [
+      [disqualifiedProjectId, disqualifiedProjectPda, "NoRefundDue"],
+      [runningProjectId, runningProjectPda, "ProjectNotDisqualified"],
+    ] as [string, PublicKey, string][]) {
+      try {
+        await program.methods
+          .claimRefund()
+          .accounts(claimAccounts(projectId, project))
+          .signers([voterA])
+          .rpc();
+        throw new Error(`Expected ${code} error, but transaction succeeded.`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    }
+  });
+
+  /**
+   * Test Case: Round invalidation
+   * Purpose: Ensure only the admin can void a round, that the voided round records why and
+   * cannot be attested, and that its voters claim back their fees.
+   */
+  it("Invalidated rounds refund their voters", async () => {
+    await ensurePendingRound();
+    const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, round, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await ensureOpenRound();
+
+    const voterDataPda = deriveVoterPda(round, voterA.publicKey, projectId);
+    await program.methods
+      .doVote(null, { ttt: {} })
+      .accounts({
+        voterData: voterDataPda,
+        signer: voterA.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: projectPda,
+        mint: tokenMint.publicKey,
+        token: voterAAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([voterA])
+      .rpc();
+    const feesPaid = (await program.account.voterData.fetch(voterDataPda)).feesPaid.toNumber();
+
+    const roundResultPda = deriveRoundResultPda(voteManagerPda, round);
+    const invalidateAccounts = (owner: PublicKey) => ({
+      roundResult: roundResultPda,
+      roundArchive: deriveRoundArchivePda(voteManagerPda, round),
+      voteManager: voteManagerPda,
+      owner,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+    const reason = "vote buying detected";
+
+    for (const [owner, signers, badReason, code] of [
+      [unauthorizedAttacker.publicKey, [unauthorizedAttacker], reason, "NotAdmin"],
+      [adminWallet.publicKey, [], "", "IncorrectInvalidReason"],
+    ] as [PublicKey, Keypair[], string, string][]) {
+      try {
+        await program.methods.invalidateRound(badReason).accounts(invalidateAccounts(owner)).signers(signers).rpc();
+        throw new Error(`Expected ${code} error, but transaction succeeded.`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    }
+
+    const signature = await program.methods
+      .invalidateRound(reason)
+      .accounts(invalidateAccounts(adminWallet.publicKey))
+      .rpc();
+
+    const roundResult = await program.account.roundResult.fetch(roundResultPda);
+    expect(roundResult.invalidated).to.equal(true);
+    expect(roundResult.invalidReason).to.equal(reason);
+    expect(roundResult.totalVotes.toNumber()).to.equal(1);
+    expect(roundResult.winner.toBase58()).to.equal(PublicKey.default.toBase58());
+    expect(roundResult.rewardPool.toNumber()).to.equal(0);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.voteRound.toNumber()).to.equal(round + 1);
+    expect("pending" in voteManagerAccount.roundState).to.equal(true);
+
+    const events = await fetchEvents(signature);
+    const invalidated = events.find((e) => e.name === "roundInvalidated");
+    expect(invalidated.data.round.toNumber()).to.equal(round);
+    expect(invalidated.data.reason).to.equal(reason);
+    expect(invalidated.data.invalidatedBy.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+
+    // A voided round has no result to attest.
+    try {
+      await program.methods
+        .attestRound()
+        .accounts({
+          roundAttestation: deriveRoundAttestationPda(roundResultPda),
+          roundResult: roundResultPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      throw new Error("Expected RoundInvalid error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RoundInvalid");
+    }
+
+    // The project was not disqualified, so the refund is claimed against the voided round.
+    const claimAccounts = {
+      voterData: voterDataPda,
+      signer: voterA.publicKey,
+      project: projectPda,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+    try {
+      await program.methods.claimRefund().accounts(claimAccounts).signers([voterA]).rpc();
+      throw new Error("Expected ProjectNotDisqualified error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ProjectNotDisqualified");
+    }
+
+    const balanceBeforeClaim = await getTokenBalance(provider.connection, voterAAta);
+    await program.methods
+      .claimRefund()
+      .accounts({ ...claimAccounts, roundResult: roundResultPda })
+      .signers([voterA])
+      .rpc();
+    expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(balanceBeforeClaim + feesPaid);
+    expect((await program.account.voterData.fetch(voterDataPda)).refunded).to.equal(1);
+  });
+
+  /**
+   * Test Case: Vote retraction
+   * Purpose: Ensure voters can take back their votes while the round is open, refunded when the
+   * admin enabled it, and that retrying a retraction changes nothing.
+   */
+  it("Voters retract their votes while the round is open", async () => {
+    await ensurePendingRound();
+    await program.methods
+      .setRetractRefund(true)
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+    const currentRound = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await ensureOpenRound();
+
+    const voterDataPda = deriveVoterPda(currentRound, voterA.publicKey, projectId);
+    const vote = () =>
+      program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: voterDataPda,
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+    const retract = () =>
+      program.methods
+        .retractVote()
+        .accounts({
+          voterData: voterDataPda,
+          signer: voterA.publicKey,
+          project: projectPda,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          roundVotes: deriveRoundVotesPda(voteManagerPda, currentRound, voterA.publicKey.toBuffer()),
+        })
+        .signers([voterA])
+        .rpc();
+
+    const before = await program.account.voteManager.fetch(voteManagerPda);
+    const balanceBeforeVote = await getTokenBalance(provider.connection, voterAAta);
+    await vote();
+    expect(await getTokenBalance(provider.connection, voterAAta)).to.be.lessThan(balanceBeforeVote);
+
+    const tx = await retract();
+    const events = await fetchEvents(tx);
+    expect(events.map((event) => event.name)).to.include("voteRetracted");
+    expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(balanceBeforeVote);
+
+    const voterAccount = await program.account.voterData.fetch(voterDataPda);
+    expect(voterAccount.retracted).to.equal(1);
+    expect(voterAccount.votesForProject).to.equal(0);
+    expect(voterAccount.feesPaid.toNumber()).to.equal(0);
+    const projectAccount = await program.account.projectData.fetch(projectPda);
+    expect(projectAccount.voteCount.toNumber()).to.equal(0);
+    expect(projectAccount.voteWeight.toNumber()).to.equal(0);
+    const after = await program.account.voteManager.fetch(voteManagerPda);
+    expect(after.roundTotalVotes.toNumber()).to.equal(before.roundTotalVotes.toNumber());
+    expect(after.roundTotalWeight.toNumber()).to.equal(before.roundTotalWeight.toNumber());
+    expect(after.roundParticipants.toNumber()).to.equal(before.roundParticipants.toNumber());
+
+    // A retried retraction is a no-op.
+    await retract();
+    expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(balanceBeforeVote);
+
+    // Voting again reuses the record.
+    await vote();
+    const revoted = await program.account.voterData.fetch(voterDataPda);
+    expect(revoted.retracted).to.equal(0);
+    expect(revoted.votesForProject).to.equal(1);
+
+    // Refunds can only be switched between rounds.
+    try {
+      await program.methods
+        .setRetractRefund(false)
+        .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+        .rpc();
+      throw new Error("Expected RoundNotPending error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RoundNotPending");
+    }
+    await ensurePendingRound();
+    await program.methods
+      .setRetractRefund(false)
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+
+    // Votes of a past round can no longer be retracted.
+    try {
+      await retract();
+      throw new Error("Expected WrongRound error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("WrongRound");
+    }
+  });
+
+  /**
+   * Test Case: Round leaderboard
+   * Purpose: Ensure a round's leaderboard holds its projects ranked by weight, and that votes of
+   * the round cannot skip it.
+   */
+  it("A round's leaderboard ranks its projects on every vote", async () => {
+    await ensurePendingRound();
+    const currentRound = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectIds = [generateProjectId(10), generateProjectId(10)];
+    const projectPdas = projectIds.map((id) => deriveProjectPda(id, currentRound, adminWallet.publicKey));
+    for (const [i, projectId] of projectIds.entries()) {
+      await program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: projectPdas[i],
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const leaderboardPda = deriveLeaderboardPda(voteManagerPda, currentRound);
+    const tx = await program.methods
+      .initLeaderboard()
+      .accounts({
+        leaderboard: leaderboardPda,
+        voteManager: voteManagerPda,
+        signer: voterA.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([voterA])
+      .rpc();
+    const events = await fetchEvents(tx);
+    expect(events.map((event) => event.name)).to.include("leaderboardCreated");
+    expect((await program.account.voteManager.fetch(voteManagerPda)).roundLeaderboard).to.equal(true);
+    await ensureOpenRound();
+
+    const vote = (voter: Keypair, voterAta: PublicKey, i: number, leaderboard: PublicKey | null) =>
+      program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voter.publicKey, projectIds[i]),
+          signer: voter.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPdas[i],
+          mint: tokenMint.publicKey,
+          token: voterAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          leaderboard,
+        })
+        .signers([voter])
+        .rpc();
+
+    // Votes of the round must pass its leaderboard.
+    try {
+      await vote(voterA, voterAAta, 0, null);
+      throw new Error("Expected LeaderboardMissing error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("LeaderboardMissing");
+    }
+
+    await vote(voterA, voterAAta, 0, leaderboardPda);
+    await vote(voterA, voterAAta, 1, leaderboardPda);
+    await vote(voterB, voterBAta, 1, leaderboardPda);
+
+    const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+    expect(leaderboard.round.toNumber()).to.equal(currentRound);
+    expect(leaderboard.len).to.equal(2);
+    const entries = leaderboard.entries.slice(0, leaderboard.len);
+    expect(entries[0].weight.toNumber()).to.be.at.least(entries[1].weight.toNumber());
+    for (const entry of entries) {
+      const id = Buffer.from(entry.id).toString().replace(/\0+$/, "");
+      const projectAccount = await program.account.projectData.fetch(entry.project);
+      expect(id).to.equal(fixedString(projectAccount.id, projectAccount.idLen));
+      expect(entry.votes.toNumber()).to.equal(projectAccount.voteCount.toNumber());
+      expect(entry.weight.toNumber()).to.equal(projectAccount.voteWeight.toNumber());
+    }
+
+    // The next round starts without a leaderboard.
+    await ensurePendingRound();
+    expect((await program.account.voteManager.fetch(voteManagerPda)).roundLeaderboard).to.equal(false);
+  });
+
+  /**
+   * Test Case: Referenda
+   * Purpose: Ensure referenda tally yes, no and abstain answers by weight, charge the vote fee
+   * and accept one answer per wallet.
+   */
+  it("Referenda tally token-weighted yes/no answers", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const createReferendum = async (questionUri: string, deadline: number) => {
+      const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      const referendumPda = deriveReferendumPda(voteManagerPda, voteManagerAccount.referendumCount.toNumber());
+      await program.methods
+        .createReferendum(questionUri, new anchor.BN(deadline))
+        .accounts({
+          referendum: referendumPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return referendumPda;
+    };
+
+    // A referendum must close in the future.
+    try {
+      await createReferendum("https://example.com/q/past.json", now - 60);
+      throw new Error("Expected IncorrectReferendum error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectReferendum");
+    }
+
+    const referendumPda = await createReferendum("https://example.com/q/1.json", now + 3600);
+    const referendum = await program.account.referendum.fetch(referendumPda);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(referendum.questionUri).to.equal("https://example.com/q/1.json");
+    expect(referendum.voteFee.toNumber()).to.equal(voteManagerAccount.voteFee.toNumber());
+
+    const answer = (voter: Keypair, voterAta: PublicKey, choice: any) =>
+      program.methods
+        .voteReferendum(choice, null)
+        .accounts({
+          referendum: referendumPda,
+          referendumVote: deriveReferendumVotePda(referendumPda, voter.publicKey),
+          signer: voter.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          mint: tokenMint.publicKey,
+          token: voterAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+    const treasuryBefore = await getTokenBalance(provider.connection, treasuryTokenAccount);
+    await answer(voterA, voterAAta, { yes: {} });
+    await answer(voterB, voterBAta, { abstain: {} });
+    const treasuryAfter = await getTokenBalance(provider.connection, treasuryTokenAccount);
+    expect(treasuryAfter - treasuryBefore).to.equal(2 * referendum.voteFee.toNumber());
+
+    // The ballot PDA prevents a second answer.
+    try {
+      await answer(voterA, voterAAta, { no: {} });
+      throw new Error("Expected the second answer to fail, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.message).to.not.include("Expected the second answer to fail");
+    }
+
+    const tallied = await program.account.referendum.fetch(referendumPda);
+    const ballotA = await program.account.referendumVote.fetch(deriveReferendumVotePda(referendumPda, voterA.publicKey));
+    expect(tallied.voters.toNumber()).to.equal(2);
+    expect(tallied.yesWeight.toNumber()).to.equal(ballotA.weight.toNumber());
+    expect(tallied.noWeight.toNumber()).to.equal(0);
+    expect(tallied.abstainWeight.toNumber()).to.be.greaterThan(0);
+    expect(ballotA.choice).to.deep.equal({ yes: {} });
+  });
+
+  /**
+   * Test Case: Permissionless project submission
+   * Purpose: Ensure submitted projects escrow a deposit, reject votes until approved, and refund
+   * or slash the deposit on review.
+   */
+  it("Submitted projects escrow a deposit until the admin approves or rejects them", async () => {
+    await ensurePendingRound();
+    const deposit = 50;
+    await program.methods
+      .setProjectDeposit(new anchor.BN(deposit))
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const reviewAccounts = (project: PublicKey) => ({
+      project,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      submitterTokenAccount: voterBAta,
+      voteManager: voteManagerPda,
+      mint: tokenMint.publicKey,
+      owner: adminWallet.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    const submit = async (id: string, projectData: PublicKey) =>
+      program.methods
+        .submitProject(id)
+        .accounts({
+          projectData,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          submitterTokenAccount: voterBAta,
+          voteManager: voteManagerPda,
+          mint: tokenMint.publicKey,
+          submitter: voterB.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterB])
+        .rpc();
+
+    // Submitting escrows the deposit and leaves the project pending.
+    const approvedProjectId = generateProjectId(10);
+    const approvedProjectPda = deriveProjectPda(approvedProjectId, currentRound, adminWallet.publicKey);
+    const submitterBalance = await getTokenBalance(provider.connection, voterBAta);
+    const escrowedBefore = (await program.account.treasury.fetch(treasuryPda)).totalEscrowed.toNumber();
+    await submit(approvedProjectId, approvedProjectPda);
+
+    let projectAccount = await program.account.projectData.fetch(approvedProjectPda);
+    expect(projectAccount.pending).to.equal(1);
+    expect(projectAccount.deposit.toNumber()).to.equal(deposit);
+    expect(projectAccount.payer.toBase58()).to.equal(voterB.publicKey.toBase58());
+    expect(await getTokenBalance(provider.connection, voterBAta)).to.equal(submitterBalance - deposit);
+    expect((await program.account.treasury.fetch(treasuryPda)).totalEscrowed.toNumber()).to.equal(
+      escrowedBefore + deposit
+    );
+
+    // Pending projects reject votes.
+    try {
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterA.publicKey, approvedProjectId),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: approvedProjectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc();
+      throw new Error("Expected ProjectPending error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ProjectPending");
+    }
+
+    // Only the admin can approve, which refunds the deposit.
+    try {
+      await program.methods
+        .approveProject()
+        .accounts({ ...reviewAccounts(approvedProjectPda), owner: unauthorizedAttacker.publicKey })
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    await program.methods.approveProject().accounts(reviewAccounts(approvedProjectPda)).rpc();
+
+    projectAccount = await program.account.projectData.fetch(approvedProjectPda);
+    expect(projectAccount.pending).to.equal(0);
+    expect(projectAccount.deposit.toNumber()).to.equal(0);
+    expect(await getTokenBalance(provider.connection, voterBAta)).to.equal(submitterBalance);
+
+    // Rejecting with slash closes the project and keeps the deposit as revenue.
+    const rejectedProjectId = generateProjectId(10);
+    const rejectedProjectPda = deriveProjectPda(rejectedProjectId, currentRound, adminWallet.publicKey);
+    await submit(rejectedProjectId, rejectedProjectPda);
+    const collectedBefore = (await program.account.treasury.fetch(treasuryPda)).totalCollected.toNumber();
+
+    await program.methods
+      .rejectProject(true)
+      .accounts({ ...reviewAccounts(rejectedProjectPda), payer: voterB.publicKey })
+      .rpc();
+
+    expect(await program.account.projectData.fetchNullable(rejectedProjectPda)).to.equal(null);
+    expect(await getTokenBalance(provider.connection, voterBAta)).to.equal(submitterBalance - deposit);
+    const treasuryAccount = await program.account.treasury.fetch(treasuryPda);
+    expect(treasuryAccount.totalCollected.toNumber()).to.equal(collectedBefore + deposit);
+    expect(treasuryAccount.totalEscrowed.toNumber()).to.equal(escrowedBefore);
+
+    await program.methods
+      .setProjectDeposit(new anchor.BN(0))
+      .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+  });
+
+  /**
+   * Test Case: Bonded listings
+   * Purpose: Ensure approved submissions keep their deposit as a bond and refund it in tranches as
+   * the project receives votes.
+   */
+  it("Bonded listings refund their deposit in tranches as votes come in", async () => {
+    await ensurePendingRound();
+    const adminAccounts = { voteData: voteManagerPda, owner: adminWallet.publicKey };
+    const deposit = 40;
+    await program.methods.setProjectDeposit(new anchor.BN(deposit)).accounts(adminAccounts).rpc();
+
+    // A tranche cannot take more votes than the full refund.
+    try {
+      await program.methods.setDepositRefund(new anchor.BN(3), new anchor.BN(2)).accounts(adminAccounts).rpc();
+      throw new Error("Expected IncorrectDepositRefund error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectDepositRefund");
+    }
+    await program.methods.setDepositRefund(new anchor.BN(1), new anchor.BN(2)).accounts(adminAccounts).rpc();
+
+    const currentRound = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+    const submitterBalance = await getTokenBalance(provider.connection, voterBAta);
+    await program.methods
+      .submitProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        submitterTokenAccount: voterBAta,
+        voteManager: voteManagerPda,
+        mint: tokenMint.publicKey,
+        submitter: voterB.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([voterB])
+      .rpc();
+
+    // Approval keeps the deposit escrowed as the listing's bond.
+    await program.methods
+      .approveProject()
+      .accounts({
+        project: projectPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        submitterTokenAccount: voterBAta,
+        voteManager: voteManagerPda,
+        mint: tokenMint.publicKey,
+        owner: adminWallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+    let projectAccount = await program.account.projectData.fetch(projectPda);
+    expect(projectAccount.pending).to.equal(0);
+    expect(projectAccount.deposit.toNumber()).to.equal(deposit);
+    expect(projectAccount.trancheVotes.toNumber()).to.equal(1);
+    expect(projectAccount.refundVotes.toNumber()).to.equal(2);
+    await ensureOpenRound();
+
+    const claim = () =>
+      program.methods
+        .claimDepositTranche()
+        .accounts({
+          project: projectPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          submitterTokenAccount: voterBAta,
+          voteManager: voteManagerPda,
+          mint: tokenMint.publicKey,
+          submitter: voterB.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([voterB])
+        .rpc();
+    const vote = (voter: Keypair, voterAta: PublicKey) =>
+      program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voter.publicKey, projectId),
+          signer: voter.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPda,
+          mint: tokenMint.publicKey,
+          token: voterAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+    // Nothing is refunded before the first tranche is earned.
+    try {
+      await claim();
+      throw new Error("Expected NoDepositTranche error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NoDepositTranche");
+    }
+
+    // The first vote earns half of the bond, the second the rest.
+    await vote(voterA, voterAAta);
+    await claim();
+    projectAccount = await program.account.projectData.fetch(projectPda);
+    expect(projectAccount.deposit.toNumber()).to.equal(deposit / 2);
+    expect(projectAccount.depositRefunded.toNumber()).to.equal(deposit / 2);
+
+    const balanceBeforeVote = await getTokenBalance(provider.connection, voterBAta);
+    await vote(voterB, voterBAta);
+    const voteCost = balanceBeforeVote - (await getTokenBalance(provider.connection, voterBAta));
+    await claim();
+    projectAccount = await program.account.projectData.fetch(projectPda);
+    expect(projectAccount.deposit.toNumber()).to.equal(0);
+    expect(projectAccount.depositRefunded.toNumber()).to.equal(deposit);
+    expect(await getTokenBalance(provider.connection, voterBAta)).to.equal(submitterBalance - voteCost);
+
+    try {
+      await claim();
+      throw new Error("Expected NoDepositTranche error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NoDepositTranche");
+    }
+
+    await ensurePendingRound();
+    await program.methods.setDepositRefund(new anchor.BN(0), new anchor.BN(0)).accounts(adminAccounts).rpc();
+    await program.methods.setProjectDeposit(new anchor.BN(0)).accounts(adminAccounts).rpc();
+  });
+
+  /**
+   * Test Case: Sponsored votes
+   * Purpose: Ensure the sponsor vault reimburses the rent of the accounts a first vote creates,
+   * within the per-wallet limit.
+   */
+  it("Sponsored votes reimburse the voter's rent from the sponsor vault", async () => {
+    await ensurePendingRound();
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const [sponsorVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sponsor_vault"), voteManagerPda.toBuffer()],
+      program.programId
+    );
+    const [sponsorRecordPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sponsor_record"), voteManagerPda.toBuffer(), voterA.publicKey.toBuffer()],
+      program.programId
+    );
+    const manageAccounts = (owner: PublicKey) => ({
+      sponsorVault: sponsorVaultPda,
+      voteManager: voteManagerPda,
+      owner,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+
+    const voterDataRent = await provider.connection.getMinimumBalanceForRentExemption(program.account.voterData.size);
+    const recordRent = await provider.connection.getMinimumBalanceForRentExemption(program.account.sponsorRecord.size);
+
+    await program.methods
+      .initSponsorVault(new anchor.BN(voterDataRent + recordRent))
+      .accounts({
+        sponsorVault: sponsorVaultPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Only the admin can fund the vault.
+    try {
+      await program.methods
+        .fundSponsorVault(new anchor.BN(LAMPORTS_PER_SOL))
+        .accounts(manageAccounts(unauthorizedAttacker.publicKey))
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
+
+    await program.methods
+      .fundSponsorVault(new anchor.BN(LAMPORTS_PER_SOL))
+      .accounts(manageAccounts(adminWallet.publicKey))
+      .rpc();
+
+    // Both projects are added before the round opens for voting.
+    const [firstProjectId, secondProjectId] = [generateProjectId(10), generateProjectId(10)];
+    for (const projectId of [firstProjectId, secondProjectId]) {
+      await program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: deriveProjectPda(projectId, currentRound, adminWallet.publicKey),
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+    await ensureOpenRound();
+
+    const sponsoredVote = async (projectId: string) => {
+      const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+      return program.methods
+        .sponsoredVote()
+        .accounts({
+          voterData: deriveVoterPda(currentRound, voterA.publicKey, projectId),
+          sponsorVault: sponsorVaultPda,
+          sponsorRecord: sponsorRecordPda,
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: projectPda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
+        .rpc({ commitment: "confirmed" });
+    };
+
+    // The first sponsored vote reimburses the VoterData and SponsorRecord rent.
+    const signature = await sponsoredVote(firstProjectId);
+    const voteSponsored = (await fetchEvents(signature)).find((e) => e.name === "voteSponsored");
+    expect(voteSponsored.data.amount.toNumber()).to.equal(voterDataRent + recordRent);
+
+    const sponsorRecord = await program.account.sponsorRecord.fetch(sponsorRecordPda);
+    expect(sponsorRecord.wallet.toBase58()).to.equal(voterA.publicKey.toBase58());
+    expect(sponsorRecord.reimbursed.toNumber()).to.equal(voterDataRent + recordRent);
+    const sponsorVault = await program.account.sponsorVault.fetch(sponsorVaultPda);
+    expect(sponsorVault.totalDeposited.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    expect(sponsorVault.totalReimbursed.toNumber()).to.equal(voterDataRent + recordRent);
+
+    // The wallet reached its limit, so the next reimbursement is refused.
+    try {
+      await sponsoredVote(secondProjectId);
+      throw new Error("Expected SponsorLimitReached error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("SponsorLimitReached");
+    }
+
+    // The admin can withdraw the spare balance, but not the vault's own rent.
+    const vaultBalance = await provider.connection.getBalance(sponsorVaultPda);
+    const vaultRent = await provider.connection.getMinimumBalanceForRentExemption(program.account.sponsorVault.size);
+    try {
+      await program.methods
+        .withdrawSponsorVault(new anchor.BN(vaultBalance - vaultRent + 1))
+        .accounts(manageAccounts(adminWallet.publicKey))
+        .rpc();
+      throw new Error("Expected InsufficientSponsorFunds error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InsufficientSponsorFunds");
+    }
+
+    await program.methods
+      .withdrawSponsorVault(new anchor.BN(vaultBalance - vaultRent))
+      .accounts(manageAccounts(adminWallet.publicKey))
+      .rpc();
+    expect(await provider.connection.getBalance(sponsorVaultPda)).to.equal(vaultRent);
+  });
+
+  /**
+   * Test Case: Stealth voting
+   * Purpose: Ensure stealth votes are counted while the VoterData account does not reveal the wallet.
+   */
+  it("Stealth votes are recorded under a blinded voter id", async () => {
+    await ensurePendingRound();
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const stealthProjectId = generateProjectId(10);
+    const stealthProjectPda = deriveProjectPda(stealthProjectId, currentRound, adminWallet.publicKey);
+
+    await program.methods
+      .addProject(stealthProjectId)
+      .accounts({
+        projectData: stealthProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await ensureOpenRound();
+
+    const blindedVoter = stealthVoterId(voterA.publicKey, randomBytes(32));
+    const stealthVoteAccounts = {
+      voterData: deriveStealthVoterPda(currentRound, blindedVoter, stealthProjectId),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: stealthProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Stealth votes are rejected until the admin enables the mode.
+    try {
+      await program.methods.stealthVote(blindedVoter).accounts(stealthVoteAccounts).signers([voterA]).rpc();
+      throw new Error("Expected StealthVotingDisabled error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("StealthVotingDisabled");
+    }
+
+    await program.methods.setStealthVoting(true).accounts(adminAccounts).rpc();
+    try {
+      await program.methods.stealthVote(blindedVoter).accounts(stealthVoteAccounts).signers([voterA]).rpc();
+    } finally {
+      await program.methods.setStealthVoting(false).accounts(adminAccounts).rpc();
+    }
+
+    const voterAccount = await program.account.voterData.fetch(stealthVoteAccounts.voterData);
+    expect(voterAccount.voter.toBase58()).to.equal(PublicKey.default.toBase58());
+    expect(voterAccount.blindedVoter).to.deep.equal(blindedVoter);
+    expect(voterAccount.voteCount.toNumber()).to.equal(1);
+
+    const projectAccount = await program.account.projectData.fetch(stealthProjectPda);
+    expect(projectAccount.voteCount.toNumber()).to.equal(1);
+  });
+
+  /**
+   * Test Case: Insufficient tokens for voting fee should fail
+   * Purpose: Ensure that user has enough ttt to vote
+   */
+  it("Insufficient tokens for voting fee should fail", async () => {
+    await ensurePendingRound();
+    const insufficientProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+
+    const insufficientProjectPda = deriveProjectPda(insufficientProjectId, currentRound, adminWallet.publicKey);
+
+    const addProjectAccounts = {
+      projectData: insufficientProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Add a project
+    await program.methods
+      .addProject(insufficientProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    // Create a user with no ttt tokens
+    const insufficientUserAta = await getAssociatedTokenAddress(
+      tokenMint.publicKey,
+      insufficientUser.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_PROGRAM_ID
+    );
+
+    const ataTransaction = new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(
+        provider.publicKey,
+        insufficientUserAta,
+        insufficientUser.publicKey,
+        tokenMint.publicKey,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_PROGRAM_ID
+      )
+    );
+
+    await provider.sendAndConfirm(ataTransaction); // Send and confirm the ATA creation transaction.
+
+    // (No token transfer from admin, so user has 0 ttt)
+
+    await ensureOpenRound();
+
+    // Attempt to vote
+    const doVoteAccounts = {
+      voterData: deriveVoterPda(currentRound, insufficientUser.publicKey, insufficientProjectId),
+      signer: insufficientUser.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: insufficientProjectPda,
+      mint: tokenMint.publicKey,
+      token: insufficientUserAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts(doVoteAccounts)
+        .signers([insufficientUser])
+        .rpc();
+
+      throw new Error("Expected InsufficientTokens error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InsufficientTokens");
+    }
+  });
+
+  /**
+   * Test Case: PDA-signed voter top-ups
+   * Purpose: Ensure a voter short of the fee is topped up from the VoteManager's pool without the
+   * admin signing, and only within a transaction that casts their TTT vote.
+   */
+  it("Voters short of the fee are topped up from the VoteManager's pool", async () => {
+    await ensurePendingRound();
+    const topUpProjectId = generateProjectId(10);
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const currentRound = voteManagerAccount.voteRound.toNumber();
+    const voteFee = voteManagerAccount.roundConfig.voteFee;
+    const topUpProjectPda = deriveProjectPda(topUpProjectId, currentRound, adminWallet.publicKey);
+
+    await program.methods
+      .addProject(topUpProjectId)
+      .accounts({
+        projectData: topUpProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await ensureOpenRound();
+
+    // The admin stocks the pool once; from then on votes need no admin signature.
+    const topUpAccount = getAssociatedTokenAddressSync(tokenMint.publicKey, voteManagerPda, true, TOKEN_2022_PROGRAM_ID);
+    await program.methods
+      .fundTopUps(voteFee.muln(2))
+      .accounts({
+        topUpAccount,
+        source: mintTokenAccount,
+        voteManager: voteManagerPda,
+        mint: tokenMint.publicKey,
+        owner: adminWallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const insufficientUserAta = getAssociatedTokenAddressSync(
+      tokenMint.publicKey,
+      insufficientUser.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const voterDataPda = deriveVoterPda(currentRound, insufficientUser.publicKey, topUpProjectId);
+    const roundVotesPda = deriveRoundVotesPda(voteManagerPda, currentRound, insufficientUser.publicKey.toBuffer());
+    const topUpAccounts = {
+      signer: insufficientUser.publicKey,
+      voteManager: voteManagerPda,
+      topUpAccount,
+      mint: tokenMint.publicKey,
+      userAta: insufficientUserAta,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      voterData: voterDataPda,
+      roundVotes: roundVotesPda,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Reason codes: 0 funds a do_vote, 1 a sponsored_vote, 2 only creates the token account.
+    const topUp = (amount: anchor.BN, reason = 0) => ({ amount, reason });
 
-      // Airdrop SOL to the voter and unauthorized attacker to ensure they can cover transaction fees.
-      await airdropIfNeeded(provider.connection, voterA.publicKey, DEFAULT_AIRDROP_SOL);
-      await airdropIfNeeded(provider.connection, voterB.publicKey, DEFAULT_AIRDROP_SOL);
-      await airdropIfNeeded(provider.connection, unauthorizedAttacker.publicKey, DEFAULT_AIRDROP_SOL);
-      await airdropIfNeeded(provider.connection, insufficientUser.publicKey, DEFAULT_AIRDROP_SOL);
+    // A top-up on its own would hand out tokens without a vote.
+    try {
+      await program.methods
+        .ensureUserCanVote(topUp(voteFee))
+        .accounts(topUpAccounts)
+        .signers([insufficientUser])
+        .rpc();
+      throw new Error("Expected TopUpWithoutVote error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TopUpWithoutVote");
+    }
 
-      // Define the accounts required for creating the token mint and associated accounts.
-      const accountsStrict = {
-        payer: provider.publicKey, // The account paying for the transaction fees.
-        authority: provider.publicKey, // The authority for the mint.
-        mint: tokenMint.publicKey, // The public key for the token mint.
-        mintTokenAccount: deriveMintTokenAccount(tokenMint.publicKey, adminWallet.publicKey), // The token account PDA.
-        extraMetasAccount: extraMetasAccount, // Additional metadata account PDA.
-        systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
-        associatedTokenProgram: ASSOCIATED_PROGRAM_ID, // Associated Token program ID.
-        tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
-      };
+    // Nor can a voter claim more than the vote they cast costs.
+    try {
+      await program.methods
+        .ensureUserCanVote(topUp(voteFee.muln(1000)))
+        .accounts(topUpAccounts)
+        .signers([insufficientUser])
+        .rpc();
+      throw new Error("Expected TopUpTooLarge error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TopUpTooLarge");
+    }
 
-      // Create the token mint with initial supply and associated metadata.
-      await tokenProgram.methods
-        .createMintAccount({
-          name: "TTT Labs Token", // Name of the token.
-          symbol: "ttt", // Symbol for the token.
-          uri: "https://my-token-data.com/metadata.json", // URI pointing to token metadata.
-          initialSupply: new anchor.BN(ttt_TOKEN_INITIAL_SUPPLY), // Initial supply of the token.
+    const vote = await program.methods
+      .doVote(null, { ttt: {} })
+      .accounts({
+        voterData: voterDataPda,
+        signer: insufficientUser.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: topUpProjectPda,
+        mint: tokenMint.publicKey,
+        token: insufficientUserAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    // Unknown reasons, and an account-only top-up carrying an amount, are rejected.
+    for (const args of [topUp(voteFee, 7), topUp(voteFee, 2)]) {
+      try {
+        await program.methods
+          .ensureUserCanVote(args)
+          .accounts(topUpAccounts)
+          .postInstructions([vote])
+          .signers([insufficientUser])
+          .rpc();
+        throw new Error("Expected InvalidTopUpReason error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidTopUpReason");
+      }
+    }
+
+    // A top-up for a sponsored vote does not fund a direct one.
+    try {
+      await program.methods
+        .ensureUserCanVote(topUp(voteFee, 1))
+        .accounts(topUpAccounts)
+        .postInstructions([vote])
+        .signers([insufficientUser])
+        .rpc();
+      throw new Error("Expected TopUpWithoutVote error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TopUpWithoutVote");
+    }
+
+    // Only the instruction right after the top-up may spend it.
+    try {
+      await program.methods
+        .ensureUserCanVote(topUp(voteFee))
+        .accounts(topUpAccounts)
+        .postInstructions([anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), vote])
+        .signers([insufficientUser])
+        .rpc();
+      throw new Error("Expected TopUpWithoutVote error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TopUpWithoutVote");
+    }
+
+    const poolBefore = await getAccount(provider.connection, topUpAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    const signature = await program.methods
+      .ensureUserCanVote(topUp(voteFee))
+      .accounts(topUpAccounts)
+      .postInstructions([vote])
+      .signers([insufficientUser])
+      .rpc();
+    const poolAfter = await getAccount(provider.connection, topUpAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(poolBefore.amount - poolAfter.amount)).to.equal(voteFee.toNumber());
+    expect((await program.account.roundVotes.fetch(roundVotesPda)).toppedUp).to.be.true;
+
+    // The pool still holds a vote fee, but a voter is topped up once per round.
+    try {
+      await program.methods
+        .ensureUserCanVote(topUp(voteFee))
+        .accounts(topUpAccounts)
+        .postInstructions([vote])
+        .signers([insufficientUser])
+        .rpc();
+      throw new Error("Expected AlreadyToppedUp error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyToppedUp");
+    }
+
+    const project = await program.account.projectData.fetch(topUpProjectPda);
+    expect(project.voteCount.toNumber()).to.equal(1);
+
+    const events = await fetchEvents(signature);
+    const toppedUp = events.find((e) => e.name === "voterToppedUp");
+    expect(toppedUp.data.voter.toBase58()).to.equal(insufficientUser.publicKey.toBase58());
+    expect(toppedUp.data.amount.toNumber()).to.equal(voteFee.toNumber());
+  });
+
+  /**
+   * Test Case: Vote-by-signature
+   * Purpose: Ensure a relayer can send a vote the voter signed, paying the transaction while the fee
+   * comes out of the voter's allowance, and that an authorization is only accepted once.
+   */
+  it("Relayers cast votes voters authorized by signature", async () => {
+    await ensurePendingRound();
+    const projectId = generateProjectId(10);
+    const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectPda = deriveProjectPda(projectId, round, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await ensureOpenRound();
+
+    const voteFee = (await program.account.voteManager.fetch(voteManagerPda)).roundConfig.voteFee;
+    const voteNoncePda = deriveVoteNoncePda(voteManagerPda, voterB.publicKey);
+    const relayAccounts = {
+      voterData: deriveVoterPda(round, voterB.publicKey, projectId),
+      voteNonce: voteNoncePda,
+      voter: voterB.publicKey,
+      relayer: unauthorizedAttacker.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: projectPda,
+      mint: tokenMint.publicKey,
+      token: voterBAta,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    const relay = (nonce: number, message: Buffer) =>
+      program.methods
+        .voteWithAuthorization(new anchor.BN(nonce))
+        .accounts(relayAccounts)
+        .preInstructions([
+          anchor.web3.Ed25519Program.createInstructionWithPrivateKey({ privateKey: voterB.secretKey, message }),
+        ])
+        .signers([unauthorizedAttacker])
+        .rpc();
+    const authorization = (nonce: number) => voteAuthorizationMessage(voterB.publicKey, projectPda, round, nonce);
+
+    // The VoteManager can only pull the fee from an allowance the voter approved.
+    try {
+      await relay(0, authorization(0));
+      throw new Error("Expected VoteNotDelegated error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("VoteNotDelegated");
+    }
+    await approveChecked(
+      provider.connection,
+      admin,
+      tokenMint.publicKey,
+      voterBAta,
+      voteManagerPda,
+      voterB,
+      BigInt(voteFee.toNumber()),
+      0,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // The authorization must carry the voter's next nonce and sign this very vote.
+    try {
+      await relay(1, authorization(1));
+      throw new Error("Expected StaleVoteNonce error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("StaleVoteNonce");
+    }
+    try {
+      await relay(0, voteAuthorizationMessage(voterB.publicKey, projectPda, round + 1, 0));
+      throw new Error("Expected InvalidVoteAuthorization error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidVoteAuthorization");
+    }
+
+    const voterBefore = await getAccount(provider.connection, voterBAta, undefined, TOKEN_2022_PROGRAM_ID);
+    const signature = await relay(0, authorization(0));
+    const voterAfter = await getAccount(provider.connection, voterBAta, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(voterBefore.amount - voterAfter.amount)).to.equal(voteFee.toNumber());
+    expect((await program.account.projectData.fetch(projectPda)).voteCount.toNumber()).to.equal(1);
+    expect((await program.account.voteNonce.fetch(voteNoncePda)).nextNonce.toNumber()).to.equal(1);
+
+    const events = await fetchEvents(signature);
+    const relayed = events.find((e) => e.name === "voteRelayed");
+    expect(relayed.data.voter.toBase58()).to.equal(voterB.publicKey.toBase58());
+    expect(relayed.data.relayer.toBase58()).to.equal(unauthorizedAttacker.publicKey.toBase58());
+
+    // The consumed authorization cannot be replayed.
+    try {
+      await relay(0, authorization(0));
+      throw new Error("Expected StaleVoteNonce error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("StaleVoteNonce");
+    }
+  });
+
+  /**
+   * Test Case: Voting on a previous round's project fails with WrongRound
+   * Purpose: Ensure that user is unable to vote for project from other rounds.
+   */
+  it("Voting on a previous round's project fails with WrongRound", async () => {
+    await ensurePendingRound();
+    // 1) Add a project in round1
+    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const oldRound = voteManagerAccount.voteRound.toNumber();
+    const oldRoundProjectId = generateProjectId(10);
+    const oldRoundProjectPda = deriveProjectPda(oldRoundProjectId, oldRound, adminWallet.publicKey);
+
+    const addProjectAccounts = {
+      projectData: oldRoundProjectPda,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.methods
+      .addProject(oldRoundProjectId)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    const incrementAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    // 2) Increment the round
+    await program.methods
+      .incrementRound()
+      .accounts(incrementAccounts)
+      .rpc();
+
+    // The project is from the old round. Let's attempt to vote:
+    const doVoteAccounts = {
+      voterData: deriveVoterPda(oldRound, voterA.publicKey, oldRoundProjectId),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: oldRoundProjectPda,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).signers([voterA]).rpc();
+      throw new Error("Expected WrongRound error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("WrongRound");
+    }
+  });
+
+  /**
+   * Test Case: User votes in two consecutive rounds successfully
+   * Purpose: Ensure successfull consecutive voting.
+   */
+  it("User votes in two consecutive rounds successfully", async () => {
+    await ensurePendingRound();
+    // Round #1
+    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round1 = voteManagerAccount.voteRound.toNumber();
+    const projectIdRound1 = generateProjectId(10);
+    const pdaProjectRound1 = deriveProjectPda(projectIdRound1, round1, adminWallet.publicKey);
+
+    const addProjectAccounts = {
+      projectData: pdaProjectRound1,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Add project for Round 1
+    await program.methods
+      .addProject(projectIdRound1)
+      .accounts(addProjectAccounts)
+      .rpc();
+
+    await ensureOpenRound();
+
+    // Do Vote in Round 1
+    const doVoteAccountsRound1 = {
+      voterData: deriveVoterPda(round1, voterA.publicKey, projectIdRound1),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: pdaProjectRound1,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccountsRound1).signers([voterA]).rpc();
+
+    const incrementAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    // Increment Round
+    await program.methods
+      .incrementRound()
+      .accounts(incrementAccounts)
+      .rpc();
+
+    voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round2 = voteManagerAccount.voteRound.toNumber();
+    const projectIdRound2 = generateProjectId(10);
+    const pdaProjectRound2 = deriveProjectPda(projectIdRound2, round2, adminWallet.publicKey);
+
+    const addProjectAccounts1 = {
+      projectData: pdaProjectRound2,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // Add project for Round 2
+    await program.methods
+      .addProject(projectIdRound2)
+      .accounts(addProjectAccounts1)
+      .rpc();
+
+    await ensureOpenRound();
+
+    // Do Vote in Round 2
+    const doVoteAccountsRound2 = {
+      voterData: deriveVoterPda(round2, voterA.publicKey, projectIdRound2),
+      signer: voterA.publicKey,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      project: pdaProjectRound2,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccountsRound2).signers([voterA]).rpc();
+
+    // Validate results for Round 1 and Round 2
+    const projectRound1 = await program.account.projectData.fetch(pdaProjectRound1);
+    const projectRound2 = await program.account.projectData.fetch(pdaProjectRound2);
+    expect(projectRound1.voteCount.toNumber()).to.equal(1, "Round 1 project has 1 vote");
+    expect(projectRound2.voteCount.toNumber()).to.equal(1, "Round 2 project has 1 vote");
+  });
+
+  /**
+   * Test Case: Per-category winners
+   * Purpose: Ensure the admin names categories between rounds, and that finalizing and archiving a
+   * round records the leading project of each category, whatever the pages.
+   */
+  it("Finalized rounds record a winner per category", async () => {
+    const scheduleAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    await program.methods
+      .setRoundSchedule(new anchor.BN(Math.floor(Date.now() / 1000)), new anchor.BN(4))
+      .accounts(scheduleAccounts)
+      .rpc();
+    await program.methods.incrementRound().accounts(scheduleAccounts).rpc();
+    const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+
+    const categoryAccounts = {
+      categoryRegistry: deriveCategoryRegistryPda(voteManagerPda),
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    try {
+      await program.methods.setCategories(["DeFi", "DeFi"]).accounts(categoryAccounts).rpc();
+      throw new Error("Expected IncorrectCategories error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectCategories");
+    }
+    await program.methods.setCategories(["DeFi", "Gaming"]).accounts(categoryAccounts).rpc();
+    const registry = await program.account.categoryRegistry.fetch(categoryAccounts.categoryRegistry);
+    expect(registry.names).to.deep.equal(["DeFi", "Gaming"]);
+
+    // Two DeFi projects, one of which gets no votes, and a Gaming project.
+    const projects = [1, 1, 2].map((category) => {
+      const id = generateProjectId(10);
+      return { id, category, pda: deriveProjectPda(id, round, adminWallet.publicKey) };
+    });
+    for (const project of projects) {
+      await program.methods
+        .addProject(project.id)
+        .accounts({
+          projectData: project.pda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .updateProjectMetadata(project.id, "", project.category)
+        .accounts({
+          project: project.pda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
         })
-        .accountsStrict(accountsStrict)
-        .signers([tokenMint, admin]) // Signers required for the transaction.
         .rpc();
+    }
+    const [defiWinner, defiLoser, gamingWinner] = projects;
 
-      // Derive the mint token account PDA after mint creation.
-      mintTokenAccount = deriveMintTokenAccount(tokenMint.publicKey, adminWallet.publicKey);
-
-      // Define the accounts required to initialize the VoteManager.
-      const initializeAccounts = {
-        voteData: voteManagerPda, // PDA for the VoteManager.
-        owner: adminWallet.publicKey, // Admin's public key as the owner.
-        systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
-      };
-
-      // Initialize the VoteManager with the token mint, token program ID, and vote fee.
+    await program.methods.openRound().accounts(scheduleAccounts).rpc();
+    for (const project of [defiWinner, gamingWinner]) {
       await program.methods
-        .initialize(tokenMint.publicKey, TOKEN_2022_PROGRAM_ID, new anchor.BN(100))
-        .accounts(initializeAccounts)
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(round, voterA.publicKey, project.id),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: project.pda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
         .rpc();
-
-      // Fetch and assert the initial state of the VoteManager to ensure correct initialization.
-      const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-      expect(voteManagerAccount.voteRound).to.equal(1);
-      expect(voteManagerAccount.admin.toBase58()).to.equal(adminWallet.publicKey.toBase58()); // Admin should be correctly set.
-      expect(voteManagerAccount.tkMint.toBase58()).to.equal(tokenMint.publicKey.toBase58()); // Token mint should be correctly set.
-      expect(voteManagerAccount.tkProgram.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58()); // Token program ID should be correctly set.
-      expect(voteManagerAccount.voteFee.toNumber()).to.equal(100); // Vote fee should be correctly set.
-
-      console.log("VoteManager initialized successfully.");
-    } catch (error) {
-      console.error("Error in before hook:", error);
-      throw error; // Propagate the error to fail the tests if setup fails.
     }
+    const leader = (await program.account.voteManager.fetch(voteManagerPda)).roundLeader;
 
+    const pastStart = Math.floor(Date.now() / 1000) - 3600;
+    await new Promise((resolve) => setTimeout(resolve, 5_000));
+    const roundArchivePda = deriveRoundArchivePda(voteManagerPda, round);
+    const asMeta = (pubkey: PublicKey) => ({ pubkey, isSigner: false, isWritable: false });
     try {
-      console.log("Setting up voter's ATA...");
-      // Derive the Associated Token Account (ATA) for the voter.
-      voterAAta = await getAssociatedTokenAddress(
-        tokenMint.publicKey,
-        voterA.publicKey,
-        true, // Allow PDA derivation.
-        TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
-        ASSOCIATED_PROGRAM_ID // Associated Token program ID.
-      );
-
-      voterBAta = await getAssociatedTokenAddress(
-        tokenMint.publicKey,
-        voterB.publicKey,
-        true, // Allow PDA derivation.
-        TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
-        ASSOCIATED_PROGRAM_ID // Associated Token program ID.
-      );
-
-      // Check if the voter's ATA already exists to avoid unnecessary transactions.
-      const ataAccount1 = await provider.connection.getAccountInfo(voterAAta);
-      const ataAccount2 = await provider.connection.getAccountInfo(voterBAta);
-      if (!ataAccount1) {
-        // If ATA doesn't exist, create it.
-        const ataTransaction = new anchor.web3.Transaction().add(
-          createAssociatedTokenAccountInstruction(
-            provider.publicKey, // Payer of the transaction fees.
-            voterAAta,           // PDA address for the ATA.
-            voterA.publicKey,    // Owner of the ATA.
-            tokenMint.publicKey,// Token mint for the ATA.
-            TOKEN_2022_PROGRAM_ID,
-            ASSOCIATED_PROGRAM_ID
-          )
-        );
-
-        await provider.sendAndConfirm(ataTransaction); // Send and confirm the ATA creation transaction.
-        console.log("Voter A ATA created.");
-      } else {
-        console.log(`ATA for ${voterA.publicKey.toBase58()} already exists.`);
-      }
-
-      if (!ataAccount2) {
-        // If ATA doesn't exist, create it.
-        const ataTransaction = new anchor.web3.Transaction().add(
-          createAssociatedTokenAccountInstruction(
-            provider.publicKey, // Payer of the transaction fees.
-            voterBAta,           // PDA address for the ATA.
-            voterB.publicKey,    // Owner of the ATA.
-            tokenMint.publicKey,// Token mint for the ATA.
-            TOKEN_2022_PROGRAM_ID,
-            ASSOCIATED_PROGRAM_ID
-          )
-        );
-
-        await provider.sendAndConfirm(ataTransaction); // Send and confirm the ATA creation transaction.
-        console.log("Voter B ATA created.");
-      } else {
-        console.log(`ATA for ${voterB.publicKey.toBase58()} already exists.`);
-      }
-
-      // Define the accounts involved in transferring ttt tokens from the mint to the voter.
-      const transferAccounts1 = {
-        fromAta: mintTokenAccount, // Source ATA holding the minted tokens.
-        toAta: voterAAta,           // Destination ATA to receive tokens.
-        mint: tokenMint.publicKey, // Token mint associated with the transfer.
-        tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
-      };
+      // The Gaming winner is archived with finalization, the DeFi projects in a later page.
+      await program.methods
+        .finalizeRound()
+        .accounts({
+          roundResult: deriveRoundResultPda(voteManagerPda, round),
+          roundArchive: roundArchivePda,
+          voteManager: voteManagerPda,
+          winner: leader,
+          signer: unauthorizedAttacker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([asMeta(gamingWinner.pda)])
+        .signers([unauthorizedAttacker])
+        .rpc();
+      await program.methods
+        .archiveProjects()
+        .accounts({
+          roundArchive: roundArchivePda,
+          signer: unauthorizedAttacker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([asMeta(defiLoser.pda), asMeta(defiWinner.pda)])
+        .signers([unauthorizedAttacker])
+        .rpc();
+    } finally {
+      // Remove the deadline carried over to the next round.
+      await program.methods
+        .setRoundSchedule(new anchor.BN(pastStart), new anchor.BN(0))
+        .accounts(scheduleAccounts)
+        .rpc();
+    }
 
-      const transferAccounts2 = {
-        fromAta: mintTokenAccount, // Source ATA holding the minted tokens.
-        toAta: voterBAta,           // Destination ATA to receive tokens.
-        mint: tokenMint.publicKey, // Token mint associated with the transfer.
-        tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
-      };
+    const weight = async (pda: PublicKey) => (await program.account.projectData.fetch(pda)).voteWeight.toNumber();
+    const archive = await program.account.roundArchive.fetch(roundArchivePda);
+    expect(
+      archive.categoryWinners.map((winner) => ({
+        category: winner.category,
+        projectId: winner.projectId,
+        weight: winner.weight.toNumber(),
+      }))
+    ).to.deep.equal([
+      { category: 1, projectId: defiWinner.id, weight: await weight(defiWinner.pda) },
+      { category: 2, projectId: gamingWinner.id, weight: await weight(gamingWinner.pda) },
+    ]);
+  });
 
-      // Execute the token transfer of 10,000 ttt from admin to voter A.
-      await tokenProgram.methods
-        .transferTokens(new anchor.BN(10_000)) // Amount of ttt tokens to transfer.
-        .accounts(transferAccounts1)
-        .signers([admin]) // Admin signs the transaction.
+  /**
+   * Test Case: Disqualifying the leader
+   * Purpose: Ensure disqualifying the leading project clears the leader, and that finalizing the
+   * round with all its projects recounts the leader among the remaining ones.
+   */
+  it("Finalizing recounts the leader after the leader is disqualified", async () => {
+    const scheduleAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    await program.methods
+      .setRoundSchedule(new anchor.BN(Math.floor(Date.now() / 1000)), new anchor.BN(4))
+      .accounts(scheduleAccounts)
+      .rpc();
+    await program.methods.incrementRound().accounts(scheduleAccounts).rpc();
+    const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+
+    const projects = [0, 1].map(() => {
+      const id = generateProjectId(10);
+      return { id, pda: deriveProjectPda(id, round, adminWallet.publicKey) };
+    });
+    for (const project of projects) {
+      await program.methods
+        .addProject(project.id)
+        .accounts({
+          projectData: project.pda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
         .rpc();
-
-      // Execute the token transfer of 10,000 ttt from admin to voter B.
-      await tokenProgram.methods
-        .transferTokens(new anchor.BN(10_000)) // Amount of ttt tokens to transfer.
-        .accounts(transferAccounts2)
-        .signers([admin]) // Admin signs the transaction.
+    }
+    await program.methods.openRound().accounts(scheduleAccounts).rpc();
+    for (const project of projects) {
+      await program.methods
+        .doVote(null, { ttt: {} })
+        .accounts({
+          voterData: deriveVoterPda(round, voterA.publicKey, project.id),
+          signer: voterA.publicKey,
+          voteManager: voteManagerPda,
+          treasury: treasuryPda,
+          treasuryTokenAccount,
+          project: project.pda,
+          mint: tokenMint.publicKey,
+          token: voterAAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterA])
         .rpc();
+    }
+    const leader = (await program.account.voteManager.fetch(voteManagerPda)).roundLeader;
+    const runnerUp = projects.find((project) => !project.pda.equals(leader))!;
 
-      console.log("ttt tokens transferred to test voter.");
-    } catch (error) {
-      console.error("Error in beforeEach hook:", error);
-      throw error; // Propagate the error to fail the tests if setup fails.
+    await program.methods
+      .disqualifyProject()
+      .accounts({ project: leader, voteManager: voteManagerPda, owner: adminWallet.publicKey })
+      .rpc();
+    const clearedLeader = (await program.account.voteManager.fetch(voteManagerPda)).roundLeader;
+    expect(clearedLeader.toBase58()).to.equal(PublicKey.default.toBase58());
+
+    const pastStart = Math.floor(Date.now() / 1000) - 3600;
+    await new Promise((resolve) => setTimeout(resolve, 5_000));
+    const roundResultPda = deriveRoundResultPda(voteManagerPda, round);
+    const asMeta = (pubkey: PublicKey) => ({ pubkey, isSigner: false, isWritable: false });
+    const sortedProjects = projects
+      .map((project) => project.pda)
+      .sort((a, b) => a.toBuffer().compare(b.toBuffer()));
+    try {
+      await program.methods
+        .finalizeRound()
+        .accounts({
+          roundResult: roundResultPda,
+          roundArchive: deriveRoundArchivePda(voteManagerPda, round),
+          voteManager: voteManagerPda,
+          winner: runnerUp.pda,
+          signer: unauthorizedAttacker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(sortedProjects.map(asMeta))
+        .signers([unauthorizedAttacker])
+        .rpc();
+    } finally {
+      // Remove the deadline carried over to the next round.
+      await program.methods
+        .setRoundSchedule(new anchor.BN(pastStart), new anchor.BN(0))
+        .accounts(scheduleAccounts)
+        .rpc();
     }
-  });
 
-  // -------------------- Test Cases --------------------
+    const result = await program.account.roundResult.fetch(roundResultPda);
+    expect(result.winner.toBase58()).to.equal(runnerUp.pda.toBase58());
+    expect(result.winnerId).to.equal(runnerUp.id);
+  });
 
   /**
-   * Test Case: Admin Initialization with Correct Admin Key
-   * Purpose: Verify that the VoteManager is correctly initialized with the admin's key.
+   * Test Case: Compressed votes
+   * Purpose: Ensure votes can be recorded as leaves of the admin's concurrent Merkle tree instead of
+   * VoterData accounts, verified against the tree's root, and claimed once for the round's reward.
    */
-  it("Admin Initialization with Correct Admin Key", async () => {
-    console.log("\n");
-    // Fetch the current state of the VoteManager account.
-    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+  it("Compressed votes are recorded in a Merkle tree and claimed once", async () => {
+    const maxDepth = 3;
+    const maxBufferSize = 8;
+    const voteTreePda = deriveVoteTreePda(voteManagerPda);
+    const merkleTree = Keypair.generate();
+    const space = merkleTreeSpace(maxDepth, maxBufferSize);
+    const createTree = anchor.web3.SystemProgram.createAccount({
+      fromPubkey: adminWallet.publicKey,
+      newAccountPubkey: merkleTree.publicKey,
+      lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+      space,
+      programId: ACCOUNT_COMPRESSION_PROGRAM_ID,
+    });
+    const initAccounts = {
+      voteTree: voteTreePda,
+      merkleTree: merkleTree.publicKey,
+      voteManager: voteManagerPda,
+      owner: adminWallet.publicKey,
+      compressionProgram: ACCOUNT_COMPRESSION_PROGRAM_ID,
+      noopProgram: NOOP_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
 
-    // Assert that the initial voting round is set to 1.
-    expect(voteManagerAccount.voteRound).to.equal(1);
+    // Only the admin can set up the tree.
+    try {
+      await program.methods
+        .initVoteTree(maxDepth, maxBufferSize)
+        .accounts({ ...initAccounts, owner: unauthorizedAttacker.publicKey })
+        .preInstructions([createTree])
+        .signers([unauthorizedAttacker, merkleTree])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
 
-    // Assert that the admin is correctly set.
-    expect(voteManagerAccount.admin.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+    await program.methods
+      .initVoteTree(maxDepth, maxBufferSize)
+      .accounts(initAccounts)
+      .preInstructions([createTree])
+      .signers([merkleTree])
+      .rpc();
+    const voteTree = await program.account.voteTree.fetch(voteTreePda);
+    expect(voteTree.merkleTree.toBase58()).to.equal(merkleTree.publicKey.toBase58());
+    expect(voteTree.leafCount.toNumber()).to.equal(0);
 
-    // Assert that the token mint is correctly set.
-    expect(voteManagerAccount.tkMint.toBase58()).to.equal(tokenMint.publicKey.toBase58());
+    const scheduleAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    await program.methods
+      .setRoundSchedule(new anchor.BN(Math.floor(Date.now() / 1000)), new anchor.BN(4))
+      .accounts(scheduleAccounts)
+      .rpc();
+    await program.methods.incrementRound().accounts(scheduleAccounts).rpc();
+    const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectId = generateProjectId(10);
+    const projectPda = deriveProjectPda(projectId, round, adminWallet.publicKey);
+    await program.methods
+      .addProject(projectId)
+      .accounts({
+        projectData: projectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods.openRound().accounts(scheduleAccounts).rpc();
+
+    const signature = await program.methods
+      .compressedVote()
+      .accounts({
+        voteTree: voteTreePda,
+        merkleTree: merkleTree.publicKey,
+        signer: voterA.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: projectPda,
+        mint: tokenMint.publicKey,
+        token: voterAAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        compressionProgram: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        noopProgram: NOOP_PROGRAM_ID,
+      })
+      .signers([voterA])
+      .rpc();
 
-    // Assert that the token program ID is correctly set.
-    expect(voteManagerAccount.tkProgram.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58());
+    // The vote is a leaf of the tree; no VoterData account was created for it.
+    expect(await provider.connection.getAccountInfo(deriveVoterPda(round, voterA.publicKey, projectId))).to.equal(null);
+    expect((await program.account.voteTree.fetch(voteTreePda)).leafCount.toNumber()).to.equal(1);
+    expect((await program.account.projectData.fetch(projectPda)).voteCount.toNumber()).to.equal(1);
+    const recorded = (await fetchEvents(signature)).find((e) => e.name === "compressedVoteRecorded");
+    expect(recorded.data.index).to.equal(0);
+    const vote = recorded.data.vote;
+    expect(vote.voter.toBase58()).to.equal(voterA.publicKey.toBase58());
+    expect(vote.project.toBase58()).to.equal(projectPda.toBase58());
+    expect(vote.rewardClaimed).to.equal(false);
+
+    const readProof = async () => {
+      const data = (await provider.connection.getAccountInfo(merkleTree.publicKey)).data;
+      return rightmostLeafProof(data, maxDepth, maxBufferSize);
+    };
+    const { root, proof, index } = await readProof();
+    expect(index).to.equal(0);
+    const proofMetas = proof.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+    const verifyAccounts = {
+      voteTree: voteTreePda,
+      merkleTree: merkleTree.publicKey,
+      compressionProgram: ACCOUNT_COMPRESSION_PROGRAM_ID,
+    };
+    await program.methods.verifyVote(vote, root, index).accounts(verifyAccounts).remainingAccounts(proofMetas).rpc();
 
-    // Assert that the vote fee is correctly set to 100.
-    expect(voteManagerAccount.voteFee.toNumber()).to.equal(100);
-  });
+    // A vote claiming more weight than was cast is not in the tree.
+    try {
+      await program.methods
+        .verifyVote({ ...vote, weight: vote.weight.addn(1) }, root, index)
+        .accounts(verifyAccounts)
+        .remainingAccounts(proofMetas)
+        .rpc();
+      throw new Error("Expected the tampered vote to be rejected, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.message).to.not.include("Expected the tampered vote");
+    }
 
-  /**
-   * Test Case: Admin Initialization with Incorrect Admin Key
-   * Purpose: Ensure that initializing the VoteManager with an unauthorized admin fails as expected.
-   */
-  it("Admin Initialization with Incorrect Admin Key", async () => {
-    // Derive a VoteManager PDA using an unauthorized attacker's public key.
-    const unauthorizedVoteManagerPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("vote_manager"), unauthorizedAttacker.publicKey.toBuffer()],
-      program.programId
-    )[0];
+    const pastStart = Math.floor(Date.now() / 1000) - 3600;
+    await new Promise((resolve) => setTimeout(resolve, 5_000));
+    const roundResultPda = deriveRoundResultPda(voteManagerPda, round);
+    try {
+      await program.methods
+        .finalizeRound()
+        .accounts({
+          roundResult: roundResultPda,
+          roundArchive: deriveRoundArchivePda(voteManagerPda, round),
+          voteManager: voteManagerPda,
+          winner: projectPda,
+          signer: unauthorizedAttacker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([{ pubkey: projectPda, isSigner: false, isWritable: false }])
+        .signers([unauthorizedAttacker])
+        .rpc();
+    } finally {
+      // Remove the deadline carried over to the next round.
+      await program.methods
+        .setRoundSchedule(new anchor.BN(pastStart), new anchor.BN(0))
+        .accounts(scheduleAccounts)
+        .rpc();
+    }
 
-    // Define the accounts for the unauthorized initialization attempt.
-    const initializeAccounts = {
-      voteData: unauthorizedVoteManagerPda,
-      owner: unauthorizedAttacker.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
+    const claimAccounts = {
+      voteTree: voteTreePda,
+      merkleTree: merkleTree.publicKey,
+      signer: voterA.publicKey,
+      roundResult: roundResultPda,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      compressionProgram: ACCOUNT_COMPRESSION_PROGRAM_ID,
+      noopProgram: NOOP_PROGRAM_ID,
     };
+    const roundResult = await program.account.roundResult.fetch(roundResultPda);
+    expect(roundResult.rewardPool.toNumber()).to.equal(vote.feesPaid.toNumber());
+
+    // The voter backed the only project, so their share is the whole pool.
+    const tokenBefore = await getAccount(provider.connection, voterAAta, undefined, TOKEN_2022_PROGRAM_ID);
+    await program.methods
+      .claimCompressedReward(vote, root, index)
+      .accounts(claimAccounts)
+      .remainingAccounts(proofMetas)
+      .signers([voterA])
+      .rpc();
+    const tokenAfter = await getAccount(provider.connection, voterAAta, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(tokenAfter.amount - tokenBefore.amount)).to.equal(roundResult.rewardPool.toNumber());
+    const claimedResult = await program.account.roundResult.fetch(roundResultPda);
+    expect(claimedResult.rewardsClaimed.toNumber()).to.equal(roundResult.rewardPool.toNumber());
 
+    // The leaf now records the claim, so the unclaimed vote no longer matches it.
+    const claimed = await readProof();
     try {
-      // Attempt to initialize the VoteManager with unauthorized admin credentials.
       await program.methods
-        .initialize(tokenMint.publicKey, TOKEN_2022_PROGRAM_ID, new anchor.BN(100))
-        .accounts(initializeAccounts)
-        .signers([unauthorizedAttacker])
+        .claimCompressedReward(vote, claimed.root, index)
+        .accounts(claimAccounts)
+        .remainingAccounts(proofMetas)
+        .signers([voterA])
         .rpc();
-
-      // If the above transaction succeeds, the test should fail.
-      throw new Error("Expected transaction to fail, but it succeeded");
+      throw new Error("Expected the reward to be claimed once, but transaction succeeded.");
     } catch (err: any) {
-      // Assert that the error code corresponds to unauthorized admin action.
-      expect(err.error.errorCode.code).to.equal("NotAdmin");
+      expect(err.message).to.not.include("Expected the reward");
     }
+    await program.methods
+      .verifyVote({ ...vote, rewardClaimed: true }, claimed.root, index)
+      .accounts(verifyAccounts)
+      .remainingAccounts(proofMetas)
+      .rpc();
   });
 
   /**
-   * Test Case: Increment Round by Admin
-   * Purpose: Ensure that the admin can successfully increment the voting round.
+   * Test Case: Anyone can finalize a round after its deadline
+   * Purpose: Ensure `finalize_round` records the winner, rejects changed rules and opens the next round.
    */
-  it("Increment Round by Admin", async () => {
-    // Fetch the current voting round before incrementing.
-    const voteManagerAccountBefore = await program.account.voteManager.fetch(voteManagerPda);
-    const initialRound = voteManagerAccountBefore.voteRound;
-
-    // Define the accounts required to increment the round.
-    const incrementAccounts = {
+  it("Anyone can finalize a round after its deadline", async () => {
+    const scheduleAccounts = {
       voteData: voteManagerPda,
       owner: adminWallet.publicKey,
     };
 
-    // Execute the round increment.
+    // Start from a clean round so the tallies only contain this test's vote. The round opens with a
+    // short deadline, which is part of its recorded rules.
+    await program.methods
+      .setRoundSchedule(new anchor.BN(Math.floor(Date.now() / 1000)), new anchor.BN(4))
+      .accounts(scheduleAccounts)
+      .rpc();
+    await program.methods.incrementRound().accounts(scheduleAccounts).rpc();
+
+    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round = voteManagerAccount.voteRound.toNumber();
+    const winnerProjectId = generateProjectId(10);
+    const winnerProjectPda = deriveProjectPda(winnerProjectId, round, adminWallet.publicKey);
+
     await program.methods
-      .incrementRound()
-      .accounts(incrementAccounts)
+      .addProject(winnerProjectId)
+      .accounts({
+        projectData: winnerProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
       .rpc();
 
-    // Fetch the voting round after incrementing.
-    const voteManagerAccountAfter = await program.account.voteManager.fetch(voteManagerPda);
+    await program.methods.openRound().accounts(scheduleAccounts).rpc();
+    voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
 
-    // Assert that the voting round has been incremented by 1.
-    expect(voteManagerAccountAfter.voteRound).to.equal(initialRound + 1);
-  });
+    await program.methods
+      .doVote(null, { ttt: {} })
+      .accounts({
+        voterData: deriveVoterPda(round, voterA.publicKey, winnerProjectId),
+        signer: voterA.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: winnerProjectPda,
+        mint: tokenMint.publicKey,
+        token: voterAAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([voterA])
+      .rpc();
 
-  /**
-   * Test Case: Increment Round by Non-Admin
-   * Purpose: Ensure that a non-admin user cannot increment the voting round.
-   */
-  it("Increment Round by Non-Admin", async () => {
-    // Define the accounts for a non-admin attempting to increment the round.
-    const incrementAccounts = {
-      voteData: voteManagerPda,
-      owner: unauthorizedAttacker.publicKey,
+    const finalizeAccounts = {
+      roundResult: deriveRoundResultPda(voteManagerPda, round),
+      roundArchive: deriveRoundArchivePda(voteManagerPda, round),
+      voteManager: voteManagerPda,
+      winner: winnerProjectPda,
+      signer: unauthorizedAttacker.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
     };
 
+    // Finalizing a round before its deadline must fail.
     try {
-      // Attempt to increment the round using an unauthorized user.
+      await program.methods.finalizeRound().accounts(finalizeAccounts).signers([unauthorizedAttacker]).rpc();
+      throw new Error("Expected RoundNotEnded error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RoundNotEnded");
+    }
+
+    // Let the round expire.
+    const pastStart = Math.floor(Date.now() / 1000) - 3600;
+    await new Promise((resolve) => setTimeout(resolve, 5_000));
+
+    try {
+      // A round whose rules changed after it opened cannot be sealed.
+      const maxVotes = voteManagerAccount.maxVotesPerRound;
+      await program.methods.changeMaxVotes(maxVotes + 1).accounts(scheduleAccounts).rpc();
+      try {
+        await program.methods.finalizeRound().accounts(finalizeAccounts).signers([unauthorizedAttacker]).rpc();
+        throw new Error("Expected ConfigChanged error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ConfigChanged");
+      }
+      await program.methods.changeMaxVotes(maxVotes).accounts(scheduleAccounts).rpc();
+
+      // Any wallet may finalize the expired round, archiving its projects along the way.
       await program.methods
-        .incrementRound()
-        .accounts(incrementAccounts)
+        .finalizeRound()
+        .accounts(finalizeAccounts)
+        .remainingAccounts([{ pubkey: winnerProjectPda, isSigner: false, isWritable: false }])
         .signers([unauthorizedAttacker])
         .rpc();
-
-      // If the above transaction succeeds, the test should fail.
-      throw new Error("Expected transaction to fail, but it succeeded");
-    } catch (err: any) {
-      // Assert that the error message includes "ConstraintSeeds", indicating seed constraints were violated.
-      expect(err.message).to.include("ConstraintSeeds");
+    } finally {
+      // Remove the deadline carried over to the next round.
+      await program.methods
+        .setRoundSchedule(new anchor.BN(pastStart), new anchor.BN(0))
+        .accounts(scheduleAccounts)
+        .rpc();
     }
+
+    const roundResult = await program.account.roundResult.fetch(finalizeAccounts.roundResult);
+    expect(roundResult.round.toNumber()).to.equal(round);
+    expect(roundResult.totalVotes.toNumber()).to.equal(1);
+    expect(roundResult.participationCount.toNumber()).to.equal(1);
+    expect(roundResult.winner.toBase58()).to.equal(winnerProjectPda.toBase58());
+    expect(roundResult.winnerId).to.equal(winnerProjectId);
+    expect(roundResult.configHash).to.deep.equal(voteManagerAccount.roundConfig.configHash);
+    // Without an eligibility snapshot there is no eligible weight to report turnout against.
+    expect(roundResult.eligibleWeight.toNumber()).to.equal(0);
+    expect(roundResult.turnoutBps.toNumber()).to.equal(0);
+
+    const roundArchive = await program.account.roundArchive.fetch(finalizeAccounts.roundArchive);
+    expect(roundArchive.projectCount).to.equal(1);
+    expect(roundArchive.entries.map((entry) => [entry.projectId, entry.voteCount.toNumber()])).to.deep.equal([
+      [winnerProjectId, 1],
+    ]);
+
+    voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    expect(voteManagerAccount.voteRound.toNumber()).to.equal(round + 1);
+    expect(voteManagerAccount.roundConfig.round.toNumber()).to.equal(round + 1);
+    expect(voteManagerAccount.roundTotalVotes.toNumber()).to.equal(0);
+    expect(voteManagerAccount.roundState).to.deep.equal({ pending: {} });
   });
 
   /**
-   * Test Case: Admin Changes Fee
-   * Purpose: Verify that the admin can successfully change the voting fee.
+   * Test Case: Round attestation
+   * Purpose: Ensure the admin can record an ed25519 signature over a finalized round's canonical JSON,
+   * verified by an Ed25519 program instruction right before `attest_round`.
    */
-  it("Admin Changes Fee", async () => {
-    // Fetch the current vote fee before changing it.
-    const voteManagerAccountBefore = await program.account.voteManager.fetch(voteManagerPda);
-    const initialFee = voteManagerAccountBefore.voteFee;
-
-    // Define the new fee to be set by the admin.
-    const newFee = new anchor.BN(500); // Update to 500 tokens as fee
-
-    // Define the accounts required to change the fee.
-    const changeFeeAccounts = {
-      voteData: voteManagerPda,
+  it("Admin attests a finalized round with an ed25519 signature", async () => {
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const roundResultPda = deriveRoundResultPda(voteManagerPda, voteManagerAccount.voteRound.toNumber() - 1);
+    const roundResult = await program.account.roundResult.fetch(roundResultPda);
+    const message = canonicalRoundResultJson(roundResult);
+    const attestAccounts = {
+      roundAttestation: deriveRoundAttestationPda(roundResultPda),
+      roundResult: roundResultPda,
+      voteManager: voteManagerPda,
       owner: adminWallet.publicKey,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: anchor.web3.SystemProgram.programId,
     };
+    const ed25519Verify = (signedMessage: Buffer) =>
+      anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: admin.secretKey,
+        message: signedMessage,
+      });
 
-    // Execute the fee change.
-    await program.methods
-      .changeFee(newFee)
-      .accounts(changeFeeAccounts)
-      .rpc();
+    // Without the Ed25519 verification the attestation is rejected.
+    try {
+      await program.methods.attestRound().accounts(attestAccounts).rpc();
+      throw new Error("Expected MissingAttestation error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MissingAttestation");
+    }
 
-    // Fetch the vote fee after the change.
-    const voteManagerAccountAfter = await program.account.voteManager.fetch(voteManagerPda);
+    // A valid signature over another message does not attest the round.
+    try {
+      await program.methods
+        .attestRound()
+        .accounts(attestAccounts)
+        .preInstructions([ed25519Verify(Buffer.concat([message, Buffer.from(" ")]))])
+        .rpc();
+      throw new Error("Expected InvalidAttestation error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidAttestation");
+    }
 
-    // Assert that the vote fee has been updated to the new value.
-    expect(voteManagerAccountAfter.voteFee.toNumber()).to.equal(newFee.toNumber());
+    // Only the admin can attest.
+    try {
+      await program.methods
+        .attestRound()
+        .accounts({ ...attestAccounts, owner: unauthorizedAttacker.publicKey })
+        .preInstructions([
+          anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: unauthorizedAttacker.secretKey,
+            message,
+          }),
+        ])
+        .signers([unauthorizedAttacker])
+        .rpc();
+      throw new Error("Expected NotAdmin error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAdmin");
+    }
 
-    // Assert that the vote fee has changed from its initial value.
-    expect(voteManagerAccountAfter.voteFee.toNumber()).to.not.equal(initialFee.toNumber());
+    const signature = await program.methods
+      .attestRound()
+      .accounts(attestAccounts)
+      .preInstructions([ed25519Verify(message)])
+      .rpc();
+
+    const attestation = await program.account.roundAttestation.fetch(attestAccounts.roundAttestation);
+    expect(attestation.roundResult.toBase58()).to.equal(roundResultPda.toBase58());
+    expect(attestation.attester.toBase58()).to.equal(adminWallet.publicKey.toBase58());
+
+    // The stored signature verifies the canonical JSON under the admin key.
+    const publicKey = createPublicKey({
+      key: Buffer.concat([Buffer.from("302a300506032b6570032100", "hex"), adminWallet.publicKey.toBuffer()]),
+      format: "der",
+      type: "spki",
+    });
+    expect(verify(null, message, publicKey, Buffer.from(attestation.signature))).to.equal(true);
+
+    const events = await fetchEvents(signature);
+    const attested = events.find((e) => e.name === "roundAttested");
+    expect(attested.data.round.toNumber()).to.equal(roundResult.round.toNumber());
   });
 
   /**
-   * Test Case: Non-Admin Tries to Change Fee
-   * Purpose: Ensure that a non-admin user cannot change the voting fee.
+   * Test Case: Voter rewards
+   * Purpose: Ensure voters of a finalized round's winner claim their pro-rata share of the round's
+   * fees once, and that their record cannot be swept before they do.
    */
-  it("Non-Admin Tries to Change Fee", async () => {
-    // Define the new fee to be attempted by a non-admin.
-    const newFee = new anchor.BN(500);
+  it("Voters of a round's winner claim their share of its fees", async () => {
+    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+    const round = voteManagerAccount.voteRound.toNumber() - 1;
+    const roundResultPda = deriveRoundResultPda(voteManagerPda, round);
+    const roundResult = await program.account.roundResult.fetch(roundResultPda);
+    const voterDataPda = deriveVoterPda(round, voterA.publicKey, roundResult.winnerId);
+    const voterData = await program.account.voterData.fetch(voterDataPda);
 
-    // Define the accounts for the unauthorized fee change attempt.
-    const changeFeeAccounts = {
-      voteData: voteManagerPda,
-      owner: unauthorizedAttacker.publicKey,
-    };
+    // The round's only voter backed the winner, so their share is the whole pool of fees they paid.
+    expect(roundResult.rewardPool.toNumber()).to.equal(voterData.feesPaid.toNumber());
+    expect(roundResult.rewardPool.toNumber()).to.be.greaterThan(0);
 
+    // The record holds an unclaimed reward, so it cannot be swept yet.
     try {
-      // Attempt to change the vote fee using an unauthorized user.
       await program.methods
-        .changeFee(newFee)
-        .accounts(changeFeeAccounts)
+        .closeVoterData()
+        .accounts({
+          voterData: voterDataPda,
+          project: roundResult.winner,
+          roundResult: roundResultPda,
+          voteManager: voteManagerPda,
+          voter: voterA.publicKey,
+          signer: unauthorizedAttacker.publicKey,
+        })
         .signers([unauthorizedAttacker])
         .rpc();
+      throw new Error("Expected RewardUnclaimed error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RewardUnclaimed");
+    }
 
-      // If the above transaction succeeds, the test should fail.
-      throw new Error("Expected transaction to fail, but it succeeded");
+    const claimAccounts = {
+      voterData: voterDataPda,
+      signer: voterA.publicKey,
+      roundResult: roundResultPda,
+      voteManager: voteManagerPda,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      mint: tokenMint.publicKey,
+      token: voterAAta,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+
+    const tokenBefore = await getAccount(provider.connection, voterAAta, undefined, TOKEN_2022_PROGRAM_ID);
+    const signature = await program.methods.claimReward().accounts(claimAccounts).signers([voterA]).rpc();
+    const tokenAfter = await getAccount(provider.connection, voterAAta, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(tokenAfter.amount - tokenBefore.amount)).to.equal(roundResult.rewardPool.toNumber());
+
+    const claimedResult = await program.account.roundResult.fetch(roundResultPda);
+    expect(claimedResult.rewardsClaimed.toNumber()).to.equal(roundResult.rewardPool.toNumber());
+    expect((await program.account.voterData.fetch(voterDataPda)).rewardClaimed).to.equal(1);
+
+    const events = await fetchEvents(signature);
+    const claimed = events.find((e) => e.name === "voteRewardClaimed");
+    expect(claimed.data.amount.toNumber()).to.equal(roundResult.rewardPool.toNumber());
+
+    // A reward is claimed once.
+    try {
+      await program.methods.claimReward().accounts(claimAccounts).signers([voterA]).rpc();
+      throw new Error("Expected NoRewardDue error, but transaction succeeded.");
     } catch (err: any) {
-      // Assert that the error message includes "ConstraintSeeds", indicating seed constraints were violated.
-      expect(err.message).to.include("ConstraintSeeds");
+      expect(err.error.errorCode.code).to.equal("NoRewardDue");
     }
   });
 
   /**
-   * Test Case: Add Project with Unique id
-   * Purpose: Verify that adding a project with a unique identifier succeeds.
+   * Test Case: Rent reclamation after finalization
+   * Purpose: Ensure a finalized round's voter and project accounts can be closed, voter records first.
    */
-  it("Add Project with Unique id", async () => {
-    // Define a unique project identifier.
-    const uniqueProjectId = generateProjectId(10);
-
+  it("Finalized round accounts can be closed to reclaim rent", async () => {
     const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const currentRound = voteManagerAccount.voteRound;;
-
-    // Derive the PDA for the unique project in round 2.
-    const uniqueProjectPda = deriveProjectPda(uniqueProjectId, currentRound, adminWallet.publicKey);
-
-    // Define the accounts required to add a new project.
-    const addProjectAccounts = {
-      projectData: uniqueProjectPda,
+    const round = voteManagerAccount.voteRound.toNumber() - 1;
+    const roundResultPda = deriveRoundResultPda(voteManagerPda, round);
+    const roundResult = await program.account.roundResult.fetch(roundResultPda);
+    const projectPda = roundResult.winner;
+    const voterDataPda = deriveVoterPda(round, voterA.publicKey, roundResult.winnerId);
+
+    const closeProjectAccounts = {
+      project: projectPda,
+      roundResult: roundResultPda,
+      roundArchive: deriveRoundArchivePda(voteManagerPda, round),
       voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
+      payer: adminWallet.publicKey,
+      signer: unauthorizedAttacker.publicKey,
     };
 
-    // Execute the project addition.
+    // The project cannot be closed while a voter record still points at it.
+    try {
+      await program.methods.closeProjectData().accounts(closeProjectAccounts).signers([unauthorizedAttacker]).rpc();
+      throw new Error("Expected VoterRecordsRemaining error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("VoterRecordsRemaining");
+    }
+
+    const voterBalanceBefore = await provider.connection.getBalance(voterA.publicKey);
     await program.methods
-      .addProject(uniqueProjectId)
-      .accounts(addProjectAccounts)
+      .closeVoterData()
+      .accounts({
+        voterData: voterDataPda,
+        project: projectPda,
+        roundResult: roundResultPda,
+        voteManager: voteManagerPda,
+        voter: voterA.publicKey,
+        signer: unauthorizedAttacker.publicKey,
+      })
+      .signers([unauthorizedAttacker])
       .rpc();
+    expect(await provider.connection.getAccountInfo(voterDataPda)).to.be.null;
+    expect(await provider.connection.getBalance(voterA.publicKey)).to.be.greaterThan(voterBalanceBefore);
 
-    // Fetch the newly added project's account data.
-    const projectAccount = await program.account.projectData.fetch(uniqueProjectPda);
+    await program.methods.closeProjectData().accounts(closeProjectAccounts).signers([unauthorizedAttacker]).rpc();
+    expect(await provider.connection.getAccountInfo(projectPda)).to.be.null;
 
-    // Assertions to ensure the project was added correctly.
-    expect(projectAccount.id).to.equal(uniqueProjectId); // Project identifier should match.
-    expect(projectAccount.voteCount.toNumber()).to.equal(0); // Initial vote count should be zero.
-    expect(projectAccount.voteRound).to.equal(currentRound); // Project should be associated with round 2.
-    expect(voteManagerAccount.voteFee.toNumber() == 500);
+    // The round's standings outlive the closed project.
+    const roundArchive = await program.account.roundArchive.fetch(closeProjectAccounts.roundArchive);
+    expect(roundArchive.entries.map((entry) => entry.projectId)).to.deep.equal([roundResult.winnerId]);
   });
 
   /**
-   * Test Case: Add Project with Duplicate id
-   * Purpose: Ensure that adding a project with a duplicate identifier in the same round fails.
+   * Test Case: Paginated tally
+   * Purpose: Ensure a round's projects can be counted page by page in address order and the round
+   * sealed once every page is in.
    */
-  it("Add Project with Duplicate id", async () => {
-    // Define a duplicate project identifier.
-    const duplicateProjectId = generateProjectId(10);
+  it("A round is tallied page by page and committed", async () => {
+    const scheduleAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
 
-    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const currentRound = voteManagerAccount.voteRound;;
+    await program.methods
+      .setRoundSchedule(new anchor.BN(Math.floor(Date.now() / 1000)), new anchor.BN(4))
+      .accounts(scheduleAccounts)
+      .rpc();
+    await program.methods.incrementRound().accounts(scheduleAccounts).rpc();
 
-    // Derive the PDA for the duplicate project in round 2.
-    const duplicateProjectPda = deriveProjectPda(duplicateProjectId, currentRound, adminWallet.publicKey);
+    const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+    const projectIds = [generateProjectId(10), generateProjectId(10), generateProjectId(10)];
+    const projectPdas = projectIds.map((id) => deriveProjectPda(id, round, adminWallet.publicKey));
+    for (const [i, projectId] of projectIds.entries()) {
+      await program.methods
+        .addProject(projectId)
+        .accounts({
+          projectData: projectPdas[i],
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+    await program.methods.openRound().accounts(scheduleAccounts).rpc();
 
-    // Define the accounts required to add the duplicate project.
-    const addProjectAccounts = {
-      projectData: duplicateProjectPda,
+    await program.methods
+      .doVote(null, { ttt: {} })
+      .accounts({
+        voterData: deriveVoterPda(round, voterA.publicKey, projectIds[1]),
+        signer: voterA.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: projectPdas[1],
+        mint: tokenMint.publicKey,
+        token: voterAAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([voterA])
+      .rpc();
+
+    // Pages must list the projects in ascending address order.
+    const sorted = [...projectPdas].sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()));
+    const page = (pdas: PublicKey[]) => pdas.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+    const tallyStatePda = deriveTallyStatePda(voteManagerPda, round);
+    const pageAccounts = {
+      tallyState: tallyStatePda,
       voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
+      signer: unauthorizedAttacker.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    const commitAccounts = {
+      tallyState: tallyStatePda,
+      roundResult: deriveRoundResultPda(voteManagerPda, round),
+      roundArchive: deriveRoundArchivePda(voteManagerPda, round),
+      voteManager: voteManagerPda,
+      winner: projectPdas[1],
+      signer: unauthorizedAttacker.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
     };
 
-    // First addition of the duplicate project should succeed.
-    await program.methods
-      .addProject(duplicateProjectId)
-      .accounts(addProjectAccounts)
-      .rpc();
+    const pastStart = Math.floor(Date.now() / 1000) - 3600;
+    await new Promise((resolve) => setTimeout(resolve, 5_000));
 
     try {
-      // Attempt to add the same project again in the same round.
       await program.methods
-        .addProject(duplicateProjectId)
-        .accounts(addProjectAccounts)
+        .tallyPage(0, 2)
+        .accounts(pageAccounts)
+        .remainingAccounts(page(sorted.slice(0, 2)))
+        .signers([unauthorizedAttacker])
         .rpc();
 
-      // If the above transaction succeeds, the test should fail.
-      throw new Error("Expected transaction to fail, but it succeeded");
-    } catch (err: any) {
-      // Assert that the error message indicates the project identifier is already in use.
-      expect(err.message).to.include("already in use");
+      // A page that was already counted cannot be sent again.
+      try {
+        await program.methods
+          .tallyPage(0, 2)
+          .accounts(pageAccounts)
+          .remainingAccounts(page(sorted.slice(0, 2)))
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected TallyPageOutOfOrder error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TallyPageOutOfOrder");
+      }
+
+      // The round cannot be sealed before every project is counted.
+      try {
+        await program.methods.tallyCommit().accounts(commitAccounts).signers([unauthorizedAttacker]).rpc();
+        throw new Error("Expected TallyIncomplete error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TallyIncomplete");
+      }
+
+      await program.methods
+        .tallyPage(2, 1)
+        .accounts(pageAccounts)
+        .remainingAccounts(page(sorted.slice(2)))
+        .signers([unauthorizedAttacker])
+        .rpc();
+      const tallyState = await program.account.tallyState.fetch(tallyStatePda);
+      expect(tallyState.counted).to.equal(3);
+      expect(tallyState.leader.toBase58()).to.equal(projectPdas[1].toBase58());
+
+      await program.methods.tallyCommit().accounts(commitAccounts).signers([unauthorizedAttacker]).rpc();
+    } finally {
+      // Remove the deadline carried over to the next round.
+      await program.methods
+        .setRoundSchedule(new anchor.BN(pastStart), new anchor.BN(0))
+        .accounts(scheduleAccounts)
+        .rpc();
     }
+
+    const roundResult = await program.account.roundResult.fetch(commitAccounts.roundResult);
+    expect(roundResult.winner.toBase58()).to.equal(projectPdas[1].toBase58());
+    expect(roundResult.winnerId).to.equal(projectIds[1]);
+    // The tally state is closed once the round is sealed.
+    expect(await provider.connection.getAccountInfo(tallyStatePda)).to.be.null;
+    expect((await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber()).to.equal(round + 1);
   });
 
   /**
-   * Test Case: Reuse id (project name) in a New Round
-   * Purpose: Verify that a project identifier can be reused in a new voting round.
+   * Test Case: Commit–reveal voting
+   * Purpose: Ensure hidden votes are only counted once revealed in the reveal window.
    */
-  it("Reuse id (project name) in a New Round", async () => {
-    // Define the current voting round.
+  it("Commit–reveal voting counts votes only on reveal", async () => {
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+
+    await program.methods.incrementRound().accounts(adminAccounts).rpc();
+
     const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const currentRound = voteManagerAccount.voteRound;
+    const round = voteManagerAccount.voteRound.toNumber();
+    const hiddenProjectId = generateProjectId(10);
+    const hiddenProjectPda = deriveProjectPda(hiddenProjectId, round, adminWallet.publicKey);
 
-    // Define a project identifier to be reused.
-    const reusedProjectId = generateProjectId(10);
+    await program.methods
+      .addProject(hiddenProjectId)
+      .accounts({
+        projectData: hiddenProjectPda,
+        voteManager: voteManagerPda,
+        owner: adminWallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
 
-    // Derive the PDA for the reused project in round 3.
-    const reusedProjectPda = deriveProjectPda(reusedProjectId, currentRound, adminWallet.publicKey);
+    // Voting window closes in a few seconds, followed by a one-minute reveal window.
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods.setCommitReveal(true, new anchor.BN(60)).accounts(adminAccounts).rpc();
+    await program.methods
+      .setRoundSchedule(new anchor.BN(now - 10), new anchor.BN(16))
+      .accounts(adminAccounts)
+      .rpc();
+    await program.methods.openRound().accounts(adminAccounts).rpc();
 
-    // Define the accounts required to add the reused project.
-    const addProjectAccounts = {
-      projectData: reusedProjectPda,
+    const salt = randomBytes(32);
+    const voteCommitPda = deriveVoteCommitPda(voteManagerPda, round, voterA.publicKey);
+    const revealAccounts = {
+      voteCommit: voteCommitPda,
       voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
+      project: hiddenProjectPda,
+      signer: voterA.publicKey,
     };
 
-    // Add the reused project in round 3.
-    await program.methods
-      .addProject(reusedProjectId)
-      .accounts(addProjectAccounts)
-      .rpc();
+    try {
+      // Direct votes are disabled in commit–reveal mode.
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts({
+            voterData: deriveVoterPda(round, voterA.publicKey, hiddenProjectId),
+            signer: voterA.publicKey,
+            voteManager: voteManagerPda,
+            treasury: treasuryPda,
+            treasuryTokenAccount,
+            project: hiddenProjectPda,
+            mint: tokenMint.publicKey,
+            token: voterAAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([voterA])
+          .rpc();
+        throw new Error("Expected CommitRevealMode error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CommitRevealMode");
+      }
 
-    // Fetch the added project's account data.
-    const projectAccount = await program.account.projectData.fetch(reusedProjectPda);
+      const commitment = voteCommitment(voterA.publicKey, hiddenProjectId, salt);
+      const commit = (voter: Keypair, voterAta: PublicKey) =>
+        program.methods
+          .commitVote(commitment)
+          .accounts({
+            voteCommit: deriveVoteCommitPda(voteManagerPda, round, voter.publicKey),
+            signer: voter.publicKey,
+            voteManager: voteManagerPda,
+            treasury: treasuryPda,
+            treasuryTokenAccount,
+            mint: tokenMint.publicKey,
+            token: voterAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([voter])
+          .rpc();
+      await commit(voterA, voterAAta);
+      // Another wallet copies the commitment, hoping to reuse the salt once it is revealed.
+      await commit(voterB, voterBAta);
+
+      // The commitment hides the choice from the tally.
+      let projectAccount = await program.account.projectData.fetch(hiddenProjectPda);
+      expect(projectAccount.voteCount.toNumber()).to.equal(0);
+
+      try {
+        await program.methods.revealVote(Array.from(salt)).accounts(revealAccounts).signers([voterA]).rpc();
+        throw new Error("Expected NotRevealWindow error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotRevealWindow");
+      }
 
-    // Assertions to ensure the reused project was added correctly.
-    expect(projectAccount.id).to.equal(reusedProjectId); // Project identifier should match.
-    expect(projectAccount.voteRound).to.equal(currentRound); // Project should be associated with round 3.
-    expect(voteManagerAccount.voteFee.toNumber() == 500);
-  });
+      // Wait for the voting window to close.
+      await new Promise((resolve) => setTimeout(resolve, 10_000));
+
+      try {
+        await program.methods
+          .revealVote(Array.from(randomBytes(32)))
+          .accounts(revealAccounts)
+          .signers([voterA])
+          .rpc();
+        throw new Error("Expected InvalidReveal error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidReveal");
+      }
 
-  /**
-   * Test Case: Voting in the Wrong Round
-   * Purpose: Ensure that voting in a round different from the current active round fails.
-   * 
-   * ! We use project id as a part of voter's seed - Anchor treats wrong round errors as constraint violation errors in this case.
-   */
-  it("Voting in the Wrong Round", async () => {
+      await program.methods.revealVote(Array.from(salt)).accounts(revealAccounts).signers([voterA]).rpc();
 
-    // const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const wrongRound = 123;
-    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const currentRound = voteManagerAccount.voteRound;;
-    // Define a project identifier for the wrong round voting test.
-    const projectId = generateProjectId(10);
+      try {
+        await program.methods.revealVote(Array.from(salt)).accounts(revealAccounts).signers([voterA]).rpc();
+        throw new Error("Expected AlreadyRevealed error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyRevealed");
+      }
 
-    // Derive the PDA for the project intended for wrong round voting in round 3.
-    const projectPda = deriveProjectPda(projectId, currentRound, adminWallet.publicKey);
+      // The copied commitment binds voterA's key, so the revealed salt does not open it.
+      try {
+        await program.methods
+          .revealVote(Array.from(salt))
+          .accounts({
+            ...revealAccounts,
+            voteCommit: deriveVoteCommitPda(voteManagerPda, round, voterB.publicKey),
+            signer: voterB.publicKey,
+          })
+          .signers([voterB])
+          .rpc();
+        throw new Error("Expected InvalidReveal error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidReveal");
+      }
 
-    // Define the accounts required to add the project.
-    const addProjectAccounts = {
-      projectData: projectPda,
+      projectAccount = await program.account.projectData.fetch(hiddenProjectPda);
+      expect(projectAccount.voteCount.toNumber()).to.equal(1);
+    } finally {
+      // Restore direct voting without a deadline.
+      await program.methods.setCommitReveal(false, new anchor.BN(0)).accounts(adminAccounts).rpc();
+      await program.methods
+        .setRoundSchedule(new anchor.BN(now - 10), new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+    }
+  });
+
+  /**
+   * Test Case: Scheduled campaign
+   * Purpose: Ensure campaign rounds are opened one after the other by anyone, with their scheduled
+   * start and end, and that votes wait for the scheduled start.
+   */
+  it("Campaign rounds open on schedule and reject early votes", async () => {
+    const adminAccounts = {
+      voteData: voteManagerPda,
+      owner: adminWallet.publicKey,
+    };
+    const campaignAccounts = {
+      campaign: deriveCampaignPda(voteManagerPda),
       voteManager: voteManagerPda,
       owner: adminWallet.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
     };
+    const openAccounts = {
+      campaign: campaignAccounts.campaign,
+      voteManager: voteManagerPda,
+      signer: unauthorizedAttacker.publicKey,
+    };
+    const now = Math.floor(Date.now() / 1000);
+    const scheduledRound = (start: number, end: number, theme: string) => ({
+      startTs: new anchor.BN(start),
+      endTs: new anchor.BN(end),
+      theme,
+    });
+
+    // Overlapping rounds are rejected.
+    try {
+      await program.methods
+        .createCampaign([scheduledRound(now + 60, now + 120, "DeFi"), scheduledRound(now + 100, now + 200, "Gaming")])
+        .accounts(campaignAccounts)
+        .rpc();
+      throw new Error("Expected IncorrectCampaign error, but transaction succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("IncorrectCampaign");
+    }
 
-    // Add the project to the VoteManager.
     await program.methods
-      .addProject(projectId)
-      .accounts(addProjectAccounts)
+      .createCampaign([scheduledRound(now + 60, now + 120, "DeFi"), scheduledRound(now + 120, now + 180, "Gaming")])
+      .accounts(campaignAccounts)
       .rpc();
 
-    // Define the accounts required to perform a vote in the wrong round.
-    const doVoteWrongRoundAccounts = {
-      voterData: deriveVoterPda(wrongRound, voterA.publicKey, projectId), // Derive with round 1, assuming current round is 3.
-      signer: voterA.publicKey, // Voter's public key.
-      voteManager: voteManagerPda, // VoteManager PDA.
-      adminTokenAccount: mintTokenAccount,
-      project: projectPda, // Project PDA being voted for.
-      mint: tokenMint.publicKey, // Token mint's public key.
-      token: voterAAta, // Voter's token account.
-      tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
-      systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
-    };
+    // Start from an unused round, as campaign rounds can only be opened before anyone votes.
+    await program.methods.incrementRound().accounts(adminAccounts).rpc();
 
     try {
-      // Attempt to vote in the wrong round by providing an incorrect round number.
+      // Anyone can crank the campaign.
+      const signature = await program.methods
+        .openCampaignRound()
+        .accounts(openAccounts)
+        .signers([unauthorizedAttacker])
+        .rpc();
+
+      const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      const round = voteManagerAccount.voteRound.toNumber();
+      expect(voteManagerAccount.roundStartTs.toNumber()).to.equal(now + 60);
+      expect(voteManagerAccount.roundDuration.toNumber()).to.equal(60);
+      expect(voteManagerAccount.roundState).to.deep.equal({ pending: {} });
+
+      const opened = (await fetchEvents(signature)).find((e) => e.name === "campaignRoundOpened");
+      expect(opened.data.round.toNumber()).to.equal(round);
+      expect(opened.data.theme).to.equal("DeFi");
+
+      let campaign = await program.account.campaign.fetch(campaignAccounts.campaign);
+      expect(campaign.nextRound).to.equal(1);
+      expect(campaign.lastOpenedRound.toNumber()).to.equal(round);
+
+      // The next campaign round waits for the opened one to be closed.
+      try {
+        await program.methods.openCampaignRound().accounts(openAccounts).signers([unauthorizedAttacker]).rpc();
+        throw new Error("Expected CampaignRoundActive error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CampaignRoundActive");
+      }
+
+      // Votes are rejected until the scheduled start.
+      const earlyProjectId = generateProjectId(10);
+      const earlyProjectPda = deriveProjectPda(earlyProjectId, round, adminWallet.publicKey);
       await program.methods
-        .doVote()
-        .accounts(doVoteWrongRoundAccounts)
-        .signers([voterA])
+        .addProject(earlyProjectId)
+        .accounts({
+          projectData: earlyProjectPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
         .rpc();
+      await program.methods.openRound().accounts(adminAccounts).rpc();
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts({
+            voterData: deriveVoterPda(round, voterA.publicKey, earlyProjectId),
+            signer: voterA.publicKey,
+            voteManager: voteManagerPda,
+            treasury: treasuryPda,
+            treasuryTokenAccount,
+            project: earlyProjectPda,
+            mint: tokenMint.publicKey,
+            token: voterAAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([voterA])
+          .rpc();
+        throw new Error("Expected RoundNotStarted error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RoundNotStarted");
+      }
 
-      // If the above transaction succeeds, the test should fail.
-      throw new Error("Expected transaction to fail, but it succeeded");
-    } catch (err: any) {
-      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+      // Once the round is closed, the second campaign round opens on the next one.
+      await program.methods.incrementRound().accounts(adminAccounts).rpc();
+      await program.methods.openCampaignRound().accounts(openAccounts).signers([unauthorizedAttacker]).rpc();
+
+      campaign = await program.account.campaign.fetch(campaignAccounts.campaign);
+      expect(campaign.nextRound).to.equal(2);
+      expect(campaign.lastOpenedRound.toNumber()).to.equal(round + 1);
+
+      try {
+        await program.methods.incrementRound().accounts(adminAccounts).rpc();
+        await program.methods.openCampaignRound().accounts(openAccounts).signers([unauthorizedAttacker]).rpc();
+        throw new Error("Expected CampaignFinished error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CampaignFinished");
+      }
+    } finally {
+      // Remove the campaign and restore an open-ended round that has already started.
+      await program.methods
+        .closeCampaign()
+        .accounts({ campaign: campaignAccounts.campaign, voteManager: voteManagerPda, owner: adminWallet.publicKey })
+        .rpc();
+      await program.methods.incrementRound().accounts(adminAccounts).rpc();
+      await program.methods
+        .setRoundSchedule(new anchor.BN(now - 10), new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
     }
   });
 
   /**
-   * Test Case: Successful Vote + fee transfer
-   * Purpose: Verify that a valid vote correctly updates the project's vote count and the voter's voting data.
+   * Test Case: Ranked-choice round
+   * Purpose: Ensure ranked ballots replace direct votes and that the paged instant-runoff tally
+   * eliminates the weakest candidate, transfers its ballots and seals the majority winner.
    */
-  it("Successful Vote, fee transfer", async () => {
-    // Define a project identifier for the successful vote test.
-    const incrementAccounts = {
+  it("Ranked ballots are tallied by instant-runoff elimination", async () => {
+    const adminAccounts = {
       voteData: voteManagerPda,
       owner: adminWallet.publicKey,
     };
 
-    await program.methods
-      .incrementRound()
-      .accounts(incrementAccounts)
-      .rpc();
+    await program.methods.incrementRound().accounts(adminAccounts).rpc();
 
-    const successfulVoteProjectId = generateProjectId(10);
     const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const currentRound = voteManagerAccount.voteRound;;
-
-    const successfulVoteProjectPda = deriveProjectPda(successfulVoteProjectId, currentRound, adminWallet.publicKey);
+    const round = voteManagerAccount.voteRound.toNumber();
+    const rankedTallyPda = deriveRankedTallyPda(voteManagerPda, round);
+    const roundResultPda = deriveRoundResultPda(voteManagerPda, round);
+    const roundArchivePda = deriveRoundArchivePda(voteManagerPda, round);
+
+    const [firstProjectId, secondProjectId] = [generateProjectId(10), generateProjectId(10)];
+    const firstProjectPda = deriveProjectPda(firstProjectId, round, adminWallet.publicKey);
+    const secondProjectPda = deriveProjectPda(secondProjectId, round, adminWallet.publicKey);
+    for (const [id, pda] of [
+      [firstProjectId, firstProjectPda],
+      [secondProjectId, secondProjectPda],
+    ] as [string, PublicKey][]) {
+      await program.methods
+        .addProject(id)
+        .accounts({
+          projectData: pda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
 
-    // Define the accounts required to add the successful vote project.
-    const addProjectAccounts = {
-      projectData: successfulVoteProjectPda,
+    // Ballots are accepted for a few seconds.
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods.setRankedChoice(true).accounts(adminAccounts).rpc();
+    await program.methods
+      .setRoundSchedule(new anchor.BN(now - 10), new anchor.BN(16))
+      .accounts(adminAccounts)
+      .rpc();
+    await program.methods.openRound().accounts(adminAccounts).rpc();
+
+    const ranking = (...projects: PublicKey[]) =>
+      projects.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+    const castAccounts = (voter: Keypair, token: PublicKey) => ({
+      ballot: deriveBallotPda(voteManagerPda, round, voter.publicKey),
+      rankedTally: rankedTallyPda,
+      signer: voter.publicKey,
       voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
+      treasury: treasuryPda,
+      treasuryTokenAccount,
+      mint: tokenMint.publicKey,
+      token,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+    const ballots = [voterA, voterB].map((voter) => ({
+      pubkey: deriveBallotPda(voteManagerPda, round, voter.publicKey),
+      isSigner: false,
+      isWritable: true,
+    }));
+    const tallyAccounts = {
+      rankedTally: rankedTallyPda,
+      roundResult: null,
+      roundArchive: null,
+      voteManager: voteManagerPda,
+      winner: null,
+      signer: unauthorizedAttacker.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
     };
 
-    // Add the successful vote project to the VoteManager.
-    await program.methods
-      .addProject(successfulVoteProjectId)
-      .accounts(addProjectAccounts)
-      .rpc();
+    try {
+      // Direct votes are disabled in ranked-choice mode.
+      try {
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts({
+            voterData: deriveVoterPda(round, voterA.publicKey, firstProjectId),
+            signer: voterA.publicKey,
+            voteManager: voteManagerPda,
+            treasury: treasuryPda,
+            treasuryTokenAccount,
+            project: firstProjectPda,
+            mint: tokenMint.publicKey,
+            token: voterAAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([voterA])
+          .rpc();
+        throw new Error("Expected RankedChoiceMode error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RankedChoiceMode");
+      }
 
-    // Define the accounts required to perform a vote.
-    const doVoteAccounts = {
-      voterData: deriveVoterPda(currentRound, voterA.publicKey, successfulVoteProjectId), // Voter PDA for the voter in round 5.
-      signer: voterA.publicKey, // Voter's public key.
-      voteManager: voteManagerPda, // VoteManager PDA.
-      adminTokenAccount: mintTokenAccount,
-      project: successfulVoteProjectPda, // Project PDA being voted for.
-      mint: tokenMint.publicKey, // Token mint's public key.
-      token: voterAAta, // Voter's token account.
-      tokenProgram: TOKEN_2022_PROGRAM_ID, // SPL Token program ID.
-      systemProgram: anchor.web3.SystemProgram.programId, // System program ID.
-    };
+      // A project can only be ranked once.
+      try {
+        await program.methods
+          .castRankedBallot()
+          .accounts(castAccounts(voterA, voterAAta))
+          .remainingAccounts(ranking(firstProjectPda, firstProjectPda))
+          .signers([voterA])
+          .rpc();
+        throw new Error("Expected IncorrectBallot error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("IncorrectBallot");
+      }
 
-    // Fetch the voter's and admin's initial token balances before voting.
-    const initialVoterBalance = await getTokenBalance(provider.connection, voterAAta);
-    const initialAdminBalance = await getTokenBalance(provider.connection, mintTokenAccount);
-    // Perform the vote, which should update the project's vote count and voter's data.
-    await program.methods
-      .doVote()
-      .accounts(doVoteAccounts)
-      .signers([voterA])
-      .rpc();
+      // Equal-weight ballots split the first choices evenly, so the first pass has no majority.
+      const signature = await program.methods
+        .castRankedBallot()
+        .accounts(castAccounts(voterA, voterAAta))
+        .remainingAccounts(ranking(firstProjectPda, secondProjectPda))
+        .signers([voterA])
+        .rpc();
+      await program.methods
+        .castRankedBallot()
+        .accounts(castAccounts(voterB, voterBAta))
+        .remainingAccounts(ranking(secondProjectPda, firstProjectPda))
+        .signers([voterB])
+        .rpc();
 
-    // Fetch the voter's and admin's token balances after voting.
-    const finalVoterBalance = await getTokenBalance(provider.connection, voterAAta);
-    const finalAdminBalance = await getTokenBalance(provider.connection, mintTokenAccount);
+      const cast = (await fetchEvents(signature)).find((e) => e.name === "ballotCast");
+      expect(cast.data.rankings.map((p: PublicKey) => p.toBase58())).to.deep.equal([
+        firstProjectPda.toBase58(),
+        secondProjectPda.toBase58(),
+      ]);
+      const weight = (await program.account.ballot.fetch(ballots[0].pubkey)).weight.toNumber();
+
+      try {
+        await program.methods
+          .tallyRankedRound()
+          .accounts(tallyAccounts)
+          .remainingAccounts(ballots)
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected RoundNotEnded error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RoundNotEnded");
+      }
 
-    // Fetch the project's account data after the vote.
-    const projectAccount = await program.account.projectData.fetch(successfulVoteProjectPda);
+      // Wait for the round deadline.
+      await new Promise((resolve) => setTimeout(resolve, 10_000));
+
+      // The round cannot be sealed before the runoff found a winner.
+      try {
+        await program.methods
+          .tallyRankedRound()
+          .accounts({ ...tallyAccounts, roundResult: roundResultPda })
+          .remainingAccounts(ballots)
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected RankedTallyIncomplete error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RankedTallyIncomplete");
+      }
 
-    // Fetch the voter's voting data after the vote.
-    const voterAccount = await program.account.voterData.fetch(doVoteAccounts.voterData);
+      // Pages can be resent: the first ballot is counted once per pass.
+      await program.methods
+        .tallyRankedRound()
+        .accounts(tallyAccounts)
+        .remainingAccounts(ballots.slice(0, 1))
+        .signers([unauthorizedAttacker])
+        .rpc();
+      await program.methods
+        .tallyRankedRound()
+        .accounts(tallyAccounts)
+        .remainingAccounts(ballots)
+        .signers([unauthorizedAttacker])
+        .rpc();
 
-    // Assertions to ensure the vote was successfully recorded.
-    expect(projectAccount.id).to.equal(successfulVoteProjectId); // Project identifier should match.
-    expect(projectAccount.voteRound).to.equal(currentRound); // Project should be associated with round 5.
-    expect(projectAccount.voteCount.toNumber()).to.be.greaterThan(0); // Project's vote count should have increased.
+      // The tie eliminated the later registered candidate.
+      let tally = await program.account.rankedTally.fetch(rankedTallyPda);
+      expect(tally.pass).to.equal(2);
+      expect(tally.eliminated).to.deep.equal([false, true]);
+      expect(tally.complete).to.equal(false);
 
-    expect(voterAccount.voteCount.toNumber()).to.be.greaterThan(0); // Voter's vote count should have increased.
-    expect(voterAccount.lastVotedRound).to.equal(currentRound);
+      // The second pass transfers the eliminated candidate's ballot, and the round is sealed.
+      await program.methods
+        .tallyRankedRound()
+        .accounts({
+          ...tallyAccounts,
+          roundResult: roundResultPda,
+          roundArchive: roundArchivePda,
+          winner: firstProjectPda,
+        })
+        .remainingAccounts(ballots)
+        .signers([unauthorizedAttacker])
+        .rpc();
 
-    // Assert that the voter's balance decreased by the vote fee amount (500 ttt).
-    expect(finalVoterBalance).to.equal(initialVoterBalance - voteManagerAccount.voteFee.toNumber());
+      tally = await program.account.rankedTally.fetch(rankedTallyPda);
+      expect(tally.complete).to.equal(true);
+      expect(tally.winner.toBase58()).to.equal(firstProjectPda.toBase58());
+
+      const roundResult = await program.account.roundResult.fetch(roundResultPda);
+      expect(roundResult.winner.toBase58()).to.equal(firstProjectPda.toBase58());
+      expect(roundResult.winnerWeight.toNumber()).to.equal(2 * weight);
+      expect(roundResult.participationCount.toNumber()).to.equal(2);
+
+      const updated = await program.account.voteManager.fetch(voteManagerPda);
+      expect(updated.voteRound.toNumber()).to.equal(round + 1);
+
+      // The sealed round's projects are archived afterwards, one page at a time.
+      const archivePage = (project: PublicKey) =>
+        program.methods
+          .archiveProjects()
+          .accounts({
+            roundArchive: roundArchivePda,
+            signer: unauthorizedAttacker.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts([{ pubkey: project, isSigner: false, isWritable: false }])
+          .signers([unauthorizedAttacker])
+          .rpc();
+      await archivePage(secondProjectPda);
+      try {
+        await archivePage(secondProjectPda);
+        throw new Error("Expected ProjectAlreadyArchived error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ProjectAlreadyArchived");
+      }
+      await archivePage(firstProjectPda);
 
-    // Assert that the admin's balance increased by the vote fee amount (500 ttt).
-    expect(finalAdminBalance).to.equal(initialAdminBalance + voteManagerAccount.voteFee.toNumber());
+      // Entries are ordered by votes, then id, whatever order the pages came in.
+      const standings = await Promise.all(
+        [firstProjectPda, secondProjectPda].map(async (pda) => {
+          const project = await program.account.projectData.fetch(pda);
+          return { projectId: project.id, voteCount: project.voteCount.toNumber() };
+        })
+      );
+      standings.sort((a, b) => b.voteCount - a.voteCount || (a.projectId < b.projectId ? -1 : 1));
+      const archive = await program.account.roundArchive.fetch(roundArchivePda);
+      expect(archive.round.toNumber()).to.equal(round);
+      expect(archive.projectCount).to.equal(2);
+      expect(
+        archive.entries.map((entry) => ({ projectId: entry.projectId, voteCount: entry.voteCount.toNumber() }))
+      ).to.deep.equal(standings);
+    } finally {
+      // Restore direct voting without a deadline.
+      await program.methods.setRankedChoice(false).accounts(adminAccounts).rpc();
+      await program.methods
+        .setRoundSchedule(new anchor.BN(now - 10), new anchor.BN(0))
+        .accounts(adminAccounts)
+        .rpc();
+    }
   });
 
   /**
-   * Test Case: Multiple users voting on the same project in the same round
-   * Purpose: Ensure vote count stored properly per project
+   * Negative-path matrix for `do_vote`.
+   * Purpose: Every spoofed account must be rejected with the specific `VoteError` variant.
+   *
+   * ! Projects from another VoteManager cannot be created here because `add_project` is bound to
+   * ! ADMIN_PUBKEY; the `WrongVoteManager` constraint guards that path on-chain.
    */
-  it("Multiple users voting on the same project in the same round", async () => {
-    // Create a unique project
-    const multiUserProjectId = generateProjectId(10);
-    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const currentRound = voteManagerAccount.voteRound;
+  describe("Negative-path vote matrix", () => {
+    let fakeMint: PublicKey; // Token-2022 mint that is not the governance mint.
+    let fakeMintAta: PublicKey; // Voter A's account for the fake mint.
+    let legacyMint: PublicKey; // Mint owned by the legacy SPL Token program.
+    let legacyMintAta: PublicKey; // Voter A's account for the legacy mint.
+
+    /**
+     * Adds a fresh project to a pending round and returns the `do_vote` accounts for it.
+     */
+    async function voteAccountsForNewProject(voter: Keypair, voterAta: PublicKey) {
+      await ensurePendingRound();
+      const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      const currentRound = voteManagerAccount.voteRound.toNumber();
+      const matrixProjectId = generateProjectId(10);
+      const matrixProjectPda = deriveProjectPda(matrixProjectId, currentRound, adminWallet.publicKey);
 
-    const multiUserProjectPda = deriveProjectPda(multiUserProjectId, currentRound, adminWallet.publicKey);
+      await program.methods
+        .addProject(matrixProjectId)
+        .accounts({
+          projectData: matrixProjectPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
 
-    const addProjectAccounts = {
-      projectData: multiUserProjectPda,
-      voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+      return {
+        voterData: deriveVoterPda(currentRound, voter.publicKey, matrixProjectId),
+        signer: voter.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: matrixProjectPda,
+        mint: tokenMint.publicKey,
+        token: voterAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      };
+    }
 
-    // Add the project
-    await program.methods
-      .addProject(multiUserProjectId)
-      .accounts(addProjectAccounts)
-      .rpc();
+    /**
+     * Sends the vote and asserts it fails with the expected error code.
+     */
+    async function expectVoteError(accounts: any, signer: Keypair, code: string) {
+      try {
+        await program.methods.doVote(null, { ttt: {} }).accounts(accounts).signers([signer]).rpc();
+        throw new Error(`Expected ${code} error, but transaction succeeded.`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    }
 
-    // userA votes
-    const doVoteAccountsUserA = {
-      voterData: deriveVoterPda(currentRound, voterA.publicKey, multiUserProjectId),
-      signer: voterA.publicKey,
-      voteManager: voteManagerPda,
-      adminTokenAccount: mintTokenAccount,
-      project: multiUserProjectPda,
-      mint: tokenMint.publicKey,
-      token: voterAAta,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
-    await program.methods.doVote().accounts(doVoteAccountsUserA).signers([voterA]).rpc();
+    before(async () => {
+      // Spoofed Token-2022 mint with a funded account for voter A.
+      fakeMint = await createMint(
+        provider.connection,
+        admin,
+        admin.publicKey,
+        null,
+        0,
+        Keypair.generate(),
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      fakeMintAta = await createAssociatedTokenAccount(
+        provider.connection,
+        admin,
+        fakeMint,
+        voterA.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(provider.connection, admin, fakeMint, fakeMintAta, admin, 10_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+      // Mint and account owned by the legacy SPL Token program.
+      legacyMint = await createMint(
+        provider.connection,
+        admin,
+        admin.publicKey,
+        null,
+        0,
+        Keypair.generate(),
+        undefined,
+        TOKEN_PROGRAM_ID
+      );
+      legacyMintAta = await createAssociatedTokenAccount(
+        provider.connection,
+        admin,
+        legacyMint,
+        voterA.publicKey,
+        undefined,
+        TOKEN_PROGRAM_ID
+      );
+      await mintTo(provider.connection, admin, legacyMint, legacyMintAta, admin, 10_000, [], undefined, TOKEN_PROGRAM_ID);
+    });
 
-    // userB votes
-    const doVoteAccountsUserB = {
-      voterData: deriveVoterPda(currentRound, voterB.publicKey, multiUserProjectId),
-      signer: voterB.publicKey,
-      voteManager: voteManagerPda,
-      adminTokenAccount: mintTokenAccount,
-      project: multiUserProjectPda,
-      mint: tokenMint.publicKey,
-      token: voterBAta,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+    it("Spoofed mint fails with WrongMint", async () => {
+      const accounts = await voteAccountsForNewProject(voterA, fakeMintAta);
+      await expectVoteError({ ...accounts, mint: fakeMint }, voterA, "WrongMint");
+    });
 
-    await program.methods.doVote().accounts(doVoteAccountsUserB).signers([voterB]).rpc();
+    it("Token account of another mint fails with WrongMint", async () => {
+      const accounts = await voteAccountsForNewProject(voterA, fakeMintAta);
+      await expectVoteError(accounts, voterA, "WrongMint");
+    });
 
-    // Check final result
-    const projectAccount = await program.account.projectData.fetch(multiUserProjectPda);
-    expect(projectAccount.voteCount.toNumber()).to.equal(2, "Project should have 2 total votes");
+    it("Legacy token program fails with WrongTokenProgram", async () => {
+      const accounts = await voteAccountsForNewProject(voterA, voterAAta);
+      await expectVoteError({ ...accounts, tokenProgram: TOKEN_PROGRAM_ID }, voterA, "WrongTokenProgram");
+    });
+
+    it("Token account owned by the legacy token program fails with WrongTokenProgram", async () => {
+      const accounts = await voteAccountsForNewProject(voterA, legacyMintAta);
+      await expectVoteError(accounts, voterA, "WrongTokenProgram");
+    });
+
+    it("Another voter's token account fails with WrongTokenOwner", async () => {
+      const accounts = await voteAccountsForNewProject(voterA, voterBAta);
+      await expectVoteError(accounts, voterA, "WrongTokenOwner");
+    });
+
+    it("Stale-round project fails with WrongRound", async () => {
+      const accounts = await voteAccountsForNewProject(voterA, voterAAta);
+
+      await program.methods
+        .incrementRound()
+        .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+        .rpc();
+
+      await expectVoteError(accounts, voterA, "WrongRound");
+    });
   });
 
   /**
-   * Test Case: Insufficient tokens for voting fee should fail
-   * Purpose: Ensure that user has enough ttt to vote
+   * Composability suite: a mock third-party program CPIs into `do_vote`, and TTT transfers are
+   * composed with relayed votes in one transaction.
+   * Purpose: Governance checks must hold however the vote reaches the program.
+   *
+   * ! The TTT mint has no TransferHook extension, so Token-2022 never invokes a hook program and
+   * ! the extra-account-metas PDA is not consulted; the suite asserts that this stays true.
    */
-  it("Insufficient tokens for voting fee should fail", async () => {
-    const insufficientProjectId = generateProjectId(10);
-    const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const currentRound = voteManagerAccount.voteRound;
+  describe("Composed transactions", () => {
+    /**
+     * Adds a fresh project to a pending round, opens the round and returns the `relay_vote`
+     * accounts for the project.
+     */
+    async function relayAccountsForNewProject(voter: Keypair, voterAta: PublicKey) {
+      await ensurePendingRound();
+      const voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
+      const currentRound = voteManagerAccount.voteRound.toNumber();
+      const composedProjectId = generateProjectId(10);
+      const composedProjectPda = deriveProjectPda(composedProjectId, currentRound, adminWallet.publicKey);
 
-    const insufficientProjectPda = deriveProjectPda(insufficientProjectId, currentRound, adminWallet.publicKey);
+      await program.methods
+        .addProject(composedProjectId)
+        .accounts({
+          projectData: composedProjectPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await ensureOpenRound();
+
+      return {
+        voterData: deriveVoterPda(currentRound, voter.publicKey, composedProjectId),
+        signer: voter.publicKey,
+        voteManager: voteManagerPda,
+        treasury: treasuryPda,
+        treasuryTokenAccount,
+        project: composedProjectPda,
+        mint: tokenMint.publicKey,
+        token: voterAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        leaderboard: null,
+        holdingAttestation: null,
+        accessEntry: null,
+        feeDiscounts: null,
+        voteLock: PublicKey.findProgramAddressSync(
+          [Buffer.from("vote_lock"), voteManagerPda.toBuffer(), voter.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        roundVotes: deriveRoundVotesPda(voteManagerPda, currentRound, voter.publicKey.toBuffer()),
+        governanceProgram: program.programId,
+      };
+    }
+
+    /**
+     * Sends `tx` signed by `signers` and asserts it fails with the expected error code.
+     */
+    async function expectComposedError(tx: anchor.web3.Transaction, signers: Keypair[], code: string) {
+      try {
+        await provider.sendAndConfirm(tx, signers);
+        throw new Error(`Expected ${code} error, but transaction succeeded.`);
+      } catch (err: any) {
+        expect(anchor.AnchorError.parse(err.logs)?.error.errorCode.code).to.equal(code);
+      }
+    }
+
+    it("A third-party program relays do_vote through a CPI", async () => {
+      const accounts = await relayAccountsForNewProject(voterA, voterAAta);
+      const voteFee = (await program.account.voteManager.fetch(voteManagerPda)).voteFee.toNumber();
+      const initialVoterBalance = await getTokenBalance(provider.connection, voterAAta);
+      const initialTreasuryBalance = await getTokenBalance(provider.connection, treasuryTokenAccount);
+
+      const signature = await composerProgram.methods
+        .relayVote(1)
+        .accounts(accounts)
+        .signers([voterA])
+        .rpc({ commitment: "confirmed" });
+
+      // The VoteCast event is emitted from the inner invocation.
+      const [voteCast] = await fetchEvents(signature);
+      expect(voteCast.name).to.equal("voteCast");
+      expect(voteCast.data.voter.toBase58()).to.equal(voterA.publicKey.toBase58());
+      expect(voteCast.data.project.toBase58()).to.equal(accounts.project.toBase58());
+
+      // The vote is recorded for the wallet, never for the relaying program.
+      const voterAccount = await program.account.voterData.fetch(accounts.voterData);
+      expect(voterAccount.voter.toBase58()).to.equal(voterA.publicKey.toBase58());
+      expect(voterAccount.voteCount.toNumber()).to.equal(1);
+      const projectAccount = await program.account.projectData.fetch(accounts.project);
+      expect(projectAccount.voteCount.toNumber()).to.equal(1);
+
+      expect(await getTokenBalance(provider.connection, voterAAta)).to.equal(initialVoterBalance - voteFee);
+      expect(await getTokenBalance(provider.connection, treasuryTokenAccount)).to.equal(
+        initialTreasuryBalance + voteFee
+      );
+    });
+
+    it("Re-entering do_vote in one transaction fails with AlreadyVoted and rolls back", async () => {
+      const accounts = await relayAccountsForNewProject(voterB, voterBAta);
+      const initialVoterBalance = await getTokenBalance(provider.connection, voterBAta);
+
+      // Two CPIs from the same relayed instruction.
+      const twice = await composerProgram.methods.relayVote(2).accounts(accounts).transaction();
+      await expectComposedError(twice, [voterB], "AlreadyVoted");
+
+      // A relayed vote followed by a direct vote.
+      const { governanceProgram, ...doVoteAccounts } = accounts;
+      const relayThenDirect = new anchor.web3.Transaction().add(
+        await composerProgram.methods.relayVote(1).accounts(accounts).instruction(),
+        await program.methods.doVote(null, { ttt: {} }).accounts(doVoteAccounts).instruction()
+      );
+      await expectComposedError(relayThenDirect, [voterB], "AlreadyVoted");
+
+      // Neither transaction left a VoterData account or moved tokens.
+      expect(await provider.connection.getAccountInfo(accounts.voterData)).to.be.null;
+      expect(await getTokenBalance(provider.connection, voterBAta)).to.equal(initialVoterBalance);
+    });
+
+    it("Relayed accounts are checked in the callee's order", async () => {
+      const accounts = await relayAccountsForNewProject(voterA, voterAAta);
+
+      // The relay forwards accounts positionally, so swapped accounts reach the wrong fields.
+      const swapped = await composerProgram.methods
+        .relayVote(1)
+        .accounts({ ...accounts, voteManager: treasuryPda, treasury: voteManagerPda })
+        .transaction();
+      await expectComposedError(swapped, [voterA], "AccountDiscriminatorMismatch");
+      expect(await provider.connection.getAccountInfo(accounts.voterData)).to.be.null;
+    });
+
+    it("TTT transfers resolve no extra account metas and compose with relayed votes", async () => {
+      // No transfer hook program until the lock is enabled: extra metas are never resolved.
+      const mintAccount = await getMint(provider.connection, tokenMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(getTransferHook(mintAccount)?.programId.equals(PublicKey.default)).to.be.true;
+      const extraMetas = await provider.connection.getAccountInfo(extraMetasAccount);
+      expect(extraMetas?.owner.toBase58()).to.equal(tokenProgram.programId.toBase58());
+
+      const amount = 1_000;
+      const transferIx = await createTransferCheckedWithTransferHookInstruction(
+        provider.connection,
+        mintTokenAccount,
+        tokenMint.publicKey,
+        voterBAta,
+        admin.publicKey,
+        BigInt(amount),
+        0,
+        [],
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      // Source, mint, destination and authority only.
+      expect(transferIx.keys.length).to.equal(4);
+
+      // Fund voter B and relay their vote in the same transaction.
+      const accounts = await relayAccountsForNewProject(voterB, voterBAta);
+      const voteFee = (await program.account.voteManager.fetch(voteManagerPda)).voteFee.toNumber();
+      const initialVoterBalance = await getTokenBalance(provider.connection, voterBAta);
+      const composed = new anchor.web3.Transaction().add(
+        transferIx,
+        await composerProgram.methods.relayVote(1).accounts(accounts).instruction()
+      );
+      await provider.sendAndConfirm(composed, [admin, voterB]);
+
+      expect(await getTokenBalance(provider.connection, voterBAta)).to.equal(initialVoterBalance + amount - voteFee);
+      const voterAccount = await program.account.voterData.fetch(accounts.voterData);
+      expect(voterAccount.voteCount.toNumber()).to.equal(1);
+    });
+  });
+
+  describe("Transfer lock", () => {
+    /**
+     * Returns the accounts the transfer hook reads for a transfer out of `owner`'s account.
+     */
+    function hookAccounts(owner: PublicKey) {
+      const extraAccountMetaList = PublicKey.findProgramAddressSync(
+        [Buffer.from(EXTRA_ACCOUNT_METAS), tokenMint.publicKey.toBuffer()],
+        hookProgram.programId
+      )[0];
+      const voteLock = PublicKey.findProgramAddressSync(
+        [Buffer.from("vote_lock"), voteManagerPda.toBuffer(), owner.toBuffer()],
+        program.programId
+      )[0];
+      return [hookProgram.programId, extraAccountMetaList, program.programId, voteManagerPda, voteLock].map(
+        (pubkey) => ({ pubkey, isSigner: false, isWritable: false })
+      );
+    }
+
+    /**
+     * Toggles the mint's transfer hook as its transfer-hook authority.
+     */
+    async function setTransferLock(enabled: boolean) {
+      await hookProgram.methods
+        .setTransferLock(enabled)
+        .accounts({
+          payer: provider.publicKey,
+          authority: adminWallet.publicKey,
+          mint: tokenMint.publicKey,
+          voteManager: voteManagerPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    }
+
+    it("Voters' TTT stays in their wallet until the round they voted in closes", async () => {
+      // Fund a fresh voter while the lock is still off.
+      const voter = Keypair.generate();
+      await airdropIfNeeded(provider.connection, voter.publicKey, DEFAULT_AIRDROP_SOL);
+      const voterAta = getAssociatedTokenAddressSync(tokenMint.publicKey, voter.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            voterAta,
+            voter.publicKey,
+            tokenMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        )
+      );
+      await tokenProgram.methods
+        .transferTokens(new anchor.BN(10_000))
+        .accounts({ fromAta: mintTokenAccount, toAta: voterAta, mint: tokenMint.publicKey, tokenProgram: TOKEN_2022_PROGRAM_ID })
+        .signers([admin])
+        .rpc();
+
+      await ensurePendingRound();
+      const round = (await program.account.voteManager.fetch(voteManagerPda)).voteRound.toNumber();
+      const lockProjectId = generateProjectId(10);
+      const lockProjectPda = deriveProjectPda(lockProjectId, round, adminWallet.publicKey);
+      await program.methods
+        .addProject(lockProjectId)
+        .accounts({
+          projectData: lockProjectPda,
+          voteManager: voteManagerPda,
+          owner: adminWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await ensureOpenRound();
+
+      await setTransferLock(true);
+      const mintAccount = await getMint(provider.connection, tokenMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(getTransferHook(mintAccount)?.programId.toBase58()).to.equal(hookProgram.programId.toBase58());
+
+      try {
+        // The vote fee reaches the treasury through the hook, then records the voter's lock.
+        await program.methods
+          .doVote(null, { ttt: {} })
+          .accounts({
+            voterData: deriveVoterPda(round, voter.publicKey, lockProjectId),
+            signer: voter.publicKey,
+            voteManager: voteManagerPda,
+            treasury: treasuryPda,
+            treasuryTokenAccount,
+            project: lockProjectPda,
+            mint: tokenMint.publicKey,
+            token: voterAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts(hookAccounts(voter.publicKey))
+          .signers([voter])
+          .rpc();
+        const voteLock = await program.account.voteLock.fetch(hookAccounts(voter.publicKey)[4].pubkey);
+        expect(voteLock.round.toNumber()).to.equal(round);
+
+        const transfer = async () =>
+          provider.sendAndConfirm(
+            new anchor.web3.Transaction().add(
+              await createTransferCheckedWithTransferHookInstruction(
+                provider.connection,
+                voterAta,
+                tokenMint.publicKey,
+                voterBAta,
+                voter.publicKey,
+                BigInt(1_000),
+                0,
+                [],
+                "confirmed",
+                TOKEN_2022_PROGRAM_ID
+              )
+            ),
+            [voter]
+          );
+
+        // Dumping the TTT while the round is open fails in the hook.
+        try {
+          await transfer();
+          throw new Error("Expected TransferLocked error, but transaction succeeded.");
+        } catch (err: any) {
+          expect(anchor.AnchorError.parse(err.logs)?.error.errorCode.code).to.equal("TransferLocked");
+        }
+
+        // Once the round closes, the same transfer goes through.
+        await program.methods
+          .closeRound()
+          .accounts({ voteData: voteManagerPda, owner: adminWallet.publicKey })
+          .rpc();
+        const initialBalance = await getTokenBalance(provider.connection, voterBAta);
+        await transfer();
+        expect(await getTokenBalance(provider.connection, voterBAta)).to.equal(initialBalance + 1_000);
+      } finally {
+        await setTransferLock(false);
+      }
+    });
+  });
+
+  describe("Optional mint extensions", () => {
+    /**
+     * Creates a TTT-like mint with the given optional extensions.
+     */
+    async function createExtendedMint(
+      mint: Keypair,
+      options: {
+        transferFeeBasisPoints?: number;
+        maxFee?: anchor.BN;
+        interestRate?: number;
+        soulbound?: boolean;
+        decimals?: number;
+        initialSupply?: anchor.BN;
+        maxSupply?: anchor.BN;
+        revokeMintAuthority?: boolean;
+        mintTimelock?: number;
+        defaultFrozen?: boolean;
+      }
+    ) {
+      await tokenProgram.methods
+        .createMintAccount({
+          name: "TTT Extended Token",
+          symbol: "ttte",
+          uri: "https://my-token-data.com/metadata.json",
+          decimals: options.decimals ?? 0,
+          initialSupply: options.initialSupply ?? new anchor.BN(1_000_000),
+          maxSupply: options.maxSupply ?? null,
+          revokeMintAuthority: options.revokeMintAuthority ?? !options.soulbound,
+          mintTimelock: options.mintTimelock ?? 0,
+          transferFeeBasisPoints: options.transferFeeBasisPoints ?? null,
+          maxFee: options.maxFee ?? null,
+          interestRate: options.interestRate ?? null,
+          soulbound: options.soulbound ?? false,
+          defaultFrozen: options.defaultFrozen ?? false,
+          freezeAuthority: null,
+        })
+        .accountsStrict({
+          payer: provider.publicKey,
+          authority: provider.publicKey,
+          mint: mint.publicKey,
+          mintTokenAccount: deriveMintTokenAccount(mint.publicKey, adminWallet.publicKey),
+          extraMetasAccount: PublicKey.findProgramAddressSync(
+            [Buffer.from(EXTRA_ACCOUNT_METAS), mint.publicKey.toBuffer()],
+            tokenProgram.programId
+          )[0],
+          mintConfig: deriveMintConfig(mint.publicKey),
+          systemProgram: anchor.web3.SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([mint, admin])
+        .rpc();
+    }
 
-    const addProjectAccounts = {
-      projectData: insufficientProjectPda,
-      voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+    it("Withheld transfer fees are harvested into the treasury", async () => {
+      const feeMint = Keypair.generate();
+      await createExtendedMint(feeMint, { transferFeeBasisPoints: 100, maxFee: new anchor.BN(50) });
 
-    // Add a project
-    await program.methods
-      .addProject(insufficientProjectId)
-      .accounts(addProjectAccounts)
-      .rpc();
+      // Voter A receives 10,000 tokens; 1% is withheld, capped at 50.
+      const voterFeeAta = getAssociatedTokenAddressSync(feeMint.publicKey, voterA.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      const treasuryFeeAta = getAssociatedTokenAddressSync(feeMint.publicKey, treasuryPda, true, TOKEN_2022_PROGRAM_ID);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            voterFeeAta,
+            voterA.publicKey,
+            feeMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          ),
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            treasuryFeeAta,
+            treasuryPda,
+            feeMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        )
+      );
+      await tokenProgram.methods
+        .transferTokens(new anchor.BN(10_000))
+        .accounts({
+          fromAta: deriveMintTokenAccount(feeMint.publicKey, adminWallet.publicKey),
+          toAta: voterFeeAta,
+          mint: feeMint.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+      expect(await getTokenBalance(provider.connection, voterFeeAta)).to.equal(10_000 - 50);
+
+      // Only the admin collects the fees.
+      try {
+        await tokenProgram.methods
+          .harvestAndWithdrawFees()
+          .accounts({
+            authority: unauthorizedAttacker.publicKey,
+            mint: feeMint.publicKey,
+            destination: treasuryFeeAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts([{ pubkey: voterFeeAta, isSigner: false, isWritable: true }])
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected Unauthorized error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
 
-    // Create a user with no ttt tokens
-    const insufficientUserAta = await getAssociatedTokenAddress(
-      tokenMint.publicKey,
-      insufficientUser.publicKey,
-      true,
-      TOKEN_2022_PROGRAM_ID,
-      ASSOCIATED_PROGRAM_ID
-    );
+      await tokenProgram.methods
+        .harvestAndWithdrawFees()
+        .accounts({
+          authority: adminWallet.publicKey,
+          mint: feeMint.publicKey,
+          destination: treasuryFeeAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts([{ pubkey: voterFeeAta, isSigner: false, isWritable: true }])
+        .rpc();
+      expect(await getTokenBalance(provider.connection, treasuryFeeAta)).to.equal(50);
+    });
+
+    it("A maximum fee without a transfer fee is rejected", async () => {
+      try {
+        await createExtendedMint(Keypair.generate(), { maxFee: new anchor.BN(50) });
+        throw new Error("Expected MaxFeeWithoutTransferFee error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MaxFeeWithoutTransferFee");
+      }
+    });
+
+    it("The admin sets and updates the interest rate of an interest-bearing mint", async () => {
+      const interestMint = Keypair.generate();
+      await createExtendedMint(interestMint, { interestRate: 500 });
+      const currentRate = async () =>
+        getInterestBearingMintConfigState(
+          await getMint(provider.connection, interestMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID)
+        )?.currentRate;
+      expect(await currentRate()).to.equal(500);
+
+      // Only the admin changes the rate.
+      try {
+        await tokenProgram.methods
+          .updateInterestRate(10_000)
+          .accounts({
+            authority: unauthorizedAttacker.publicKey,
+            mint: interestMint.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected Unauthorized error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
 
-    const ataTransaction = new anchor.web3.Transaction().add(
-      createAssociatedTokenAccountInstruction(
-        provider.publicKey,
-        insufficientUserAta,
-        insufficientUser.publicKey,
-        tokenMint.publicKey,
-        TOKEN_2022_PROGRAM_ID,
-        ASSOCIATED_PROGRAM_ID
-      )
-    );
+      await tokenProgram.methods
+        .updateInterestRate(250)
+        .accounts({
+          authority: adminWallet.publicKey,
+          mint: interestMint.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      expect(await currentRate()).to.equal(250);
+    });
 
-    await provider.sendAndConfirm(ataTransaction); // Send and confirm the ATA creation transaction.
+    it("Soulbound TTT cannot be transferred", async () => {
+      const soulboundMint = Keypair.generate();
+      await createExtendedMint(soulboundMint, { soulbound: true });
 
-    // (No token transfer from admin, so user has 0 ttt)
+      // The admin keeps the mint authority to issue credentials.
+      const mintInfo = await getMint(provider.connection, soulboundMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(mintInfo.mintAuthority?.equals(adminWallet.publicKey)).to.be.true;
 
-    // Attempt to vote
-    const doVoteAccounts = {
-      voterData: deriveVoterPda(currentRound, insufficientUser.publicKey, insufficientProjectId),
-      signer: insufficientUser.publicKey,
-      voteManager: voteManagerPda,
-      adminTokenAccount: mintTokenAccount,
-      project: insufficientProjectPda,
-      mint: tokenMint.publicKey,
-      token: insufficientUserAta,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+      const voterSoulboundAta = getAssociatedTokenAddressSync(
+        soulboundMint.publicKey,
+        voterA.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            voterSoulboundAta,
+            voterA.publicKey,
+            soulboundMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        )
+      );
+      try {
+        await tokenProgram.methods
+          .transferTokens(new anchor.BN(1_000))
+          .accounts({
+            fromAta: deriveMintTokenAccount(soulboundMint.publicKey, adminWallet.publicKey),
+            toAta: voterSoulboundAta,
+            mint: soulboundMint.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([admin])
+          .rpc();
+        throw new Error("Expected SoulboundMint error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SoulboundMint");
+      }
+    });
+
+    it("The update authority edits and removes metadata fields", async () => {
+      const metadataMint = Keypair.generate();
+      await createExtendedMint(metadataMint, {});
+      const accounts = (authority: PublicKey) => ({
+        payer: provider.publicKey,
+        authority,
+        mint: metadataMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      });
+
+      // Only the update authority edits the metadata.
+      try {
+        await tokenProgram.methods
+          .updateTokenMetadata("uri", "https://attacker.example/metadata.json")
+          .accountsStrict(accounts(unauthorizedAttacker.publicKey))
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected Unauthorized error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
 
-    try {
-      await program.methods
-        .doVote()
-        .accounts(doVoteAccounts)
-        .signers([insufficientUser])
+      // Rotate the URI and add a field; the mint grows and stays rent-exempt.
+      await tokenProgram.methods
+        .updateTokenMetadata("uri", "https://my-token-data.com/metadata-v2.json")
+        .accountsStrict(accounts(adminWallet.publicKey))
+        .signers([admin])
         .rpc();
+      await tokenProgram.methods
+        .updateTokenMetadata("website", "https://ttt-labs.example")
+        .accountsStrict(accounts(adminWallet.publicKey))
+        .signers([admin])
+        .rpc();
+      let metadata = await getTokenMetadata(provider.connection, metadataMint.publicKey, "confirmed");
+      expect(metadata?.uri).to.equal("https://my-token-data.com/metadata-v2.json");
+      expect(metadata?.additionalMetadata).to.deep.equal([["website", "https://ttt-labs.example"]]);
 
-      throw new Error("Expected InsufficientTokens error, but transaction succeeded.");
-    } catch (err: any) {
-      expect(err.error.errorCode.code).to.equal("InsufficientTokens");
-    }
-  });
-
-  /**
-   * Test Case: Voting on a previous round's project fails with WrongRound
-   * Purpose: Ensure that user is unable to vote for project from other rounds.
-   */
-  it("Voting on a previous round's project fails with WrongRound", async () => {
-    // 1) Add a project in round1
-    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const oldRound = voteManagerAccount.voteRound;
-    const oldRoundProjectId = generateProjectId(10);
-    const oldRoundProjectPda = deriveProjectPda(oldRoundProjectId, oldRound, adminWallet.publicKey);
+      await tokenProgram.methods
+        .removeMetadataField("website")
+        .accountsStrict(accounts(adminWallet.publicKey))
+        .signers([admin])
+        .rpc();
+      metadata = await getTokenMetadata(provider.connection, metadataMint.publicKey, "confirmed");
+      expect(metadata?.additionalMetadata).to.be.empty;
+    });
+
+    it("The admin freezes and thaws a voter's token account", async () => {
+      const freezeMint = Keypair.generate();
+      await createExtendedMint(freezeMint, {});
+      const voterFreezeAta = getAssociatedTokenAddressSync(freezeMint.publicKey, voterA.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            voterFreezeAta,
+            voterA.publicKey,
+            freezeMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        )
+      );
+      const accounts = (authority: PublicKey) => ({
+        authority,
+        mint: freezeMint.publicKey,
+        tokenAccount: voterFreezeAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      });
+      const sendToVoter = () =>
+        tokenProgram.methods
+          .transferTokens(new anchor.BN(1_000))
+          .accounts({
+            fromAta: deriveMintTokenAccount(freezeMint.publicKey, adminWallet.publicKey),
+            toAta: voterFreezeAta,
+            mint: freezeMint.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([admin])
+          .rpc();
+
+      // Only the admin freezes accounts.
+      try {
+        await tokenProgram.methods
+          .freezeAccount()
+          .accountsStrict(accounts(unauthorizedAttacker.publicKey))
+          .signers([unauthorizedAttacker])
+          .rpc();
+        throw new Error("Expected Unauthorized error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
 
-    const addProjectAccounts = {
-      projectData: oldRoundProjectPda,
-      voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+      await tokenProgram.methods.freezeAccount().accountsStrict(accounts(adminWallet.publicKey)).rpc();
+      expect((await getAccount(provider.connection, voterFreezeAta, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen).to.be.true;
+      try {
+        await sendToVoter();
+        throw new Error("Expected the transfer to a frozen account to fail, but it succeeded.");
+      } catch (err: any) {
+        expect(err.message).to.not.include("Expected the transfer");
+      }
 
-    await program.methods
-      .addProject(oldRoundProjectId)
-      .accounts(addProjectAccounts)
-      .rpc();
+      await tokenProgram.methods.thawAccount().accountsStrict(accounts(adminWallet.publicKey)).rpc();
+      await sendToVoter();
+      expect(await getTokenBalance(provider.connection, voterFreezeAta)).to.equal(1_000);
+    });
+
+    it("A finished election token is burned, its accounts closed and the mint closed", async () => {
+      const sunsetMint = Keypair.generate();
+      await createExtendedMint(sunsetMint, {});
+      const adminSunsetAta = deriveMintTokenAccount(sunsetMint.publicKey, adminWallet.publicKey);
+      const voterSunsetAta = getAssociatedTokenAddressSync(sunsetMint.publicKey, voterA.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            voterSunsetAta,
+            voterA.publicKey,
+            sunsetMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        )
+      );
+      await tokenProgram.methods
+        .transferTokens(new anchor.BN(1_000))
+        .accounts({
+          fromAta: adminSunsetAta,
+          toAta: voterSunsetAta,
+          mint: sunsetMint.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+      const closeMint = () =>
+        tokenProgram.methods
+          .closeMint()
+          .accountsStrict({
+            authority: adminWallet.publicKey,
+            mint: sunsetMint.publicKey,
+            destination: adminWallet.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+      // The mint stays open while TTT is left.
+      try {
+        await closeMint();
+        throw new Error("Expected SupplyNotZero error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SupplyNotZero");
+      }
 
-    const incrementAccounts = {
-      voteData: voteManagerPda,
-      owner: adminWallet.publicKey,
-    };
+      await tokenProgram.methods
+        .burnRemainingSupply()
+        .accountsStrict({
+          authority: adminWallet.publicKey,
+          mint: sunsetMint.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: adminSunsetAta, isSigner: false, isWritable: true },
+          { pubkey: voterSunsetAta, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+      const mintInfo = await getMint(provider.connection, sunsetMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(Number(mintInfo.supply)).to.equal(0);
+
+      // Each owner closes their empty account.
+      for (const [owner, tokenAccount, signers] of [
+        [voterA.publicKey, voterSunsetAta, [voterA]],
+        [adminWallet.publicKey, adminSunsetAta, [admin]],
+      ] as [PublicKey, PublicKey, Keypair[]][]) {
+        await tokenProgram.methods
+          .closeAta()
+          .accountsStrict({
+            owner,
+            mint: sunsetMint.publicKey,
+            tokenAccount,
+            destination: owner,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers(signers)
+          .rpc();
+        expect(await provider.connection.getAccountInfo(tokenAccount, "confirmed")).to.be.null;
+      }
 
-    // 2) Increment the round
-    await program.methods
-      .incrementRound()
-      .accounts(incrementAccounts)
-      .rpc();
+      await closeMint();
+      expect(await provider.connection.getAccountInfo(sunsetMint.publicKey, "confirmed")).to.be.null;
+    });
+
+    it("Minting more respects the supply cap and the chosen decimals", async () => {
+      try {
+        await createExtendedMint(Keypair.generate(), {
+          initialSupply: new anchor.BN(2_000),
+          maxSupply: new anchor.BN(1_000),
+        });
+        throw new Error("Expected SupplyCapExceeded error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SupplyCapExceeded");
+      }
 
-    // The project is from the old round. Let's attempt to vote:
-    const doVoteAccounts = {
-      voterData: deriveVoterPda(oldRound, voterA.publicKey, oldRoundProjectId),
-      signer: voterA.publicKey,
-      voteManager: voteManagerPda,
-      adminTokenAccount: mintTokenAccount,
-      project: oldRoundProjectPda,
-      mint: tokenMint.publicKey,
-      token: voterAAta,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+      // A soulbound mint keeps its mint authority, so more can be minted up to the cap.
+      const cappedMint = Keypair.generate();
+      await createExtendedMint(cappedMint, {
+        soulbound: true,
+        decimals: 6,
+        initialSupply: new anchor.BN(1_000),
+        maxSupply: new anchor.BN(1_500),
+      });
+      const mintMore = (amount: number) =>
+        tokenProgram.methods
+          .mintMore(new anchor.BN(amount))
+          .accountsStrict({
+            authority: adminWallet.publicKey,
+            mint: cappedMint.publicKey,
+            mintConfig: deriveMintConfig(cappedMint.publicKey),
+            destination: deriveMintTokenAccount(cappedMint.publicKey, adminWallet.publicKey),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+      await mintMore(500);
+      try {
+        await mintMore(1);
+        throw new Error("Expected SupplyCapExceeded error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SupplyCapExceeded");
+      }
 
-    try {
-      await program.methods.doVote().accounts(doVoteAccounts).signers([voterA]).rpc();
-      throw new Error("Expected WrongRound error, but transaction succeeded.");
-    } catch (err: any) {
-      expect(err.error.errorCode.code).to.equal("WrongRound");
-    }
-  });
+      const mintInfo = await getMint(provider.connection, cappedMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(mintInfo.decimals).to.equal(6);
+      expect(Number(mintInfo.supply)).to.equal(1_500);
+    });
+
+    it("A retained mint authority mints only after the timelock", async () => {
+      try {
+        await createExtendedMint(Keypair.generate(), { soulbound: true, revokeMintAuthority: true });
+        throw new Error("Expected SoulboundMintRevoked error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SoulboundMintRevoked");
+      }
 
-  /**
-   * Test Case: User votes in two consecutive rounds successfully
-   * Purpose: Ensure successfull consecutive voting.
-   */
-  it("User votes in two consecutive rounds successfully", async () => {
-    // Round #1
-    let voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const round1 = voteManagerAccount.voteRound;
-    const projectIdRound1 = generateProjectId(10);
-    const pdaProjectRound1 = deriveProjectPda(projectIdRound1, round1, adminWallet.publicKey);
+      const scheduledMint = async (mint: PublicKey, amount: number) =>
+        tokenProgram.methods
+          .scheduledMint(new anchor.BN(amount))
+          .accountsStrict({
+            authority: adminWallet.publicKey,
+            mint,
+            mintAuthority: PublicKey.findProgramAddressSync(
+              [Buffer.from("mint-authority"), mint.toBuffer()],
+              tokenProgram.programId
+            )[0],
+            mintConfig: deriveMintConfig(mint),
+            destination: deriveMintTokenAccount(mint, adminWallet.publicKey),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+      // An hour-long timelock holds the first emission back.
+      const lockedMint = Keypair.generate();
+      await createExtendedMint(lockedMint, { revokeMintAuthority: false, mintTimelock: 3600 });
+      try {
+        await scheduledMint(lockedMint.publicKey, 1_000);
+        throw new Error("Expected MintTimelocked error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MintTimelocked");
+      }
 
-    const addProjectAccounts = {
-      projectData: pdaProjectRound1,
-      voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+      // Without a timelock the PDA mints right away.
+      const openMint = Keypair.generate();
+      await createExtendedMint(openMint, { revokeMintAuthority: false });
+      await scheduledMint(openMint.publicKey, 1_000);
+      const mintInfo = await getMint(provider.connection, openMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(Number(mintInfo.supply)).to.equal(1_001_000);
+    });
 
-    // Add project for Round 1
-    await program.methods
-      .addProject(projectIdRound1)
-      .accounts(addProjectAccounts)
-      .rpc();
+    it("Season tokens are grouped under one collection", async () => {
+      const groupMint = Keypair.generate();
+      await tokenProgram.methods
+        .createGroupMint(2)
+        .accountsStrict({
+          payer: provider.publicKey,
+          authority: adminWallet.publicKey,
+          groupMint: groupMint.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([groupMint])
+        .rpc();
+      const addMember = (memberMint: PublicKey) =>
+        tokenProgram.methods
+          .addGroupMember()
+          .accountsStrict({
+            payer: provider.publicKey,
+            authority: adminWallet.publicKey,
+            groupMint: groupMint.publicKey,
+            memberMint,
+            memberMintAuthority: PublicKey.findProgramAddressSync(
+              [Buffer.from("mint-authority"), memberMint.toBuffer()],
+              tokenProgram.programId
+            )[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+      // A season whose supply is fixed by its cap, rather than a revoked authority, can join.
+      const seasonMint = Keypair.generate();
+      await createExtendedMint(seasonMint, {
+        revokeMintAuthority: false,
+        maxSupply: new anchor.BN(1_000_000),
+      });
+      await addMember(seasonMint.publicKey);
+
+      const member = getTokenGroupMemberState(
+        await getMint(provider.connection, seasonMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID)
+      );
+      expect(member?.group.equals(groupMint.publicKey)).to.be.true;
+      expect(Number(member?.memberNumber)).to.equal(1);
+      const group = getTokenGroupState(
+        await getMint(provider.connection, groupMint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID)
+      );
+      expect(Number(group?.size)).to.equal(1);
+
+      const revokedMint = Keypair.generate();
+      await createExtendedMint(revokedMint, {});
+      try {
+        await addMember(revokedMint.publicKey);
+        throw new Error("Expected MintAuthorityRevoked error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MintAuthorityRevoked");
+      }
+    });
 
-    // Do Vote in Round 1
-    const doVoteAccountsRound1 = {
-      voterData: deriveVoterPda(round1, voterA.publicKey, projectIdRound1),
-      signer: voterA.publicKey,
-      voteManager: voteManagerPda,
-      adminTokenAccount: mintTokenAccount,
-      project: pdaProjectRound1,
-      mint: tokenMint.publicKey,
-      token: voterAAta,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
-    await program.methods.doVote().accounts(doVoteAccountsRound1).signers([voterA]).rpc();
+    it("Accounts of a frozen-by-default mint wait for the admin to thaw them", async () => {
+      const kycMint = Keypair.generate();
+      await createExtendedMint(kycMint, { defaultFrozen: true });
 
-    const incrementAccounts = {
-      voteData: voteManagerPda,
-      owner: adminWallet.publicKey,
-    };
+      // The admin's own account was thawed to receive the initial supply.
+      const adminKycAta = deriveMintTokenAccount(kycMint.publicKey, adminWallet.publicKey);
+      expect((await getAccount(provider.connection, adminKycAta, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen).to.be.false;
+      expect(await getTokenBalance(provider.connection, adminKycAta)).to.equal(1_000_000);
 
-    // Increment Round
-    await program.methods
-      .incrementRound()
-      .accounts(incrementAccounts)
-      .rpc();
+      const voterKycAta = getAssociatedTokenAddressSync(kycMint.publicKey, voterA.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            voterKycAta,
+            voterA.publicKey,
+            kycMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        )
+      );
+      expect((await getAccount(provider.connection, voterKycAta, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen).to.be.true;
 
-    voteManagerAccount = await program.account.voteManager.fetch(voteManagerPda);
-    const round2 = voteManagerAccount.voteRound;
-    const projectIdRound2 = generateProjectId(10);
-    const pdaProjectRound2 = deriveProjectPda(projectIdRound2, round2, adminWallet.publicKey);
+      // Once cleared, the voter is thawed and can receive TTT.
+      await tokenProgram.methods
+        .thawAccount()
+        .accountsStrict({
+          authority: adminWallet.publicKey,
+          mint: kycMint.publicKey,
+          tokenAccount: voterKycAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      await tokenProgram.methods
+        .transferTokens(new anchor.BN(1_000))
+        .accounts({
+          fromAta: adminKycAta,
+          toAta: voterKycAta,
+          mint: kycMint.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+      expect(await getTokenBalance(provider.connection, voterKycAta)).to.equal(1_000);
+    });
+
+    it("Airdrops TTT to many wallets in one transaction", async () => {
+      const dropMint = Keypair.generate();
+      await createExtendedMint(dropMint, {});
+      const adminDropAta = deriveMintTokenAccount(dropMint.publicKey, adminWallet.publicKey);
+      const wallets = [voterA.publicKey, voterB.publicKey];
+      const recipients = wallets.map((wallet) =>
+        getAssociatedTokenAddressSync(dropMint.publicKey, wallet, false, TOKEN_2022_PROGRAM_ID)
+      );
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          ...recipients.map((ata, i) =>
+            createAssociatedTokenAccountInstruction(
+              provider.publicKey,
+              ata,
+              wallets[i],
+              dropMint.publicKey,
+              TOKEN_2022_PROGRAM_ID,
+              ASSOCIATED_PROGRAM_ID
+            )
+          )
+        )
+      );
+      const airdrop = (amounts: number[]) =>
+        tokenProgram.methods
+          .airdrop(amounts.map((amount) => new anchor.BN(amount)))
+          .accountsStrict({
+            authority: adminWallet.publicKey,
+            mint: dropMint.publicKey,
+            source: adminDropAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts(recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+          .rpc();
+
+      await airdrop([1_000, 2_500]);
+      expect(await getTokenBalance(provider.connection, recipients[0])).to.equal(1_000);
+      expect(await getTokenBalance(provider.connection, recipients[1])).to.equal(2_500);
+      expect(await getTokenBalance(provider.connection, adminDropAta)).to.equal(996_500);
+
+      // Every recipient account needs its amount.
+      try {
+        await airdrop([1_000]);
+        throw new Error("Expected AirdropLengthMismatch error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AirdropLengthMismatch");
+      }
+    });
 
-    const addProjectAccounts1 = {
-      projectData: pdaProjectRound2,
-      voteManager: voteManagerPda,
-      owner: adminWallet.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
+    it("Releases vested TTT to the beneficiary once unlocked", async () => {
+      const vestedMint = Keypair.generate();
+      await createExtendedMint(vestedMint, {});
+      const [vesting] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vesting"), vestedMint.publicKey.toBuffer(), voterA.publicKey.toBuffer()],
+        tokenProgram.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(vestedMint.publicKey, vesting, true, TOKEN_2022_PROGRAM_ID);
+      const createVesting = (cliffTs: number, endTs: number) =>
+        tokenProgram.methods
+          .createVesting(voterA.publicKey, new anchor.BN(10_000), new anchor.BN(cliffTs), new anchor.BN(endTs))
+          .accountsStrict({
+            payer: provider.publicKey,
+            authority: adminWallet.publicKey,
+            mint: vestedMint.publicKey,
+            source: deriveMintTokenAccount(vestedMint.publicKey, adminWallet.publicKey),
+            vesting,
+            escrow,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await createVesting(now - 10, now - 5);
+        throw new Error("Expected InvalidVestingSchedule error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidVestingSchedule");
+      }
 
-    // Add project for Round 2
-    await program.methods
-      .addProject(projectIdRound2)
-      .accounts(addProjectAccounts1)
-      .rpc();
+      // Everything unlocks at a cliff a few seconds out.
+      await createVesting(now + 3, now + 3);
+      expect(await getTokenBalance(provider.connection, escrow)).to.equal(10_000);
 
-    // Do Vote in Round 2
-    const doVoteAccountsRound2 = {
-      voterData: deriveVoterPda(round2, voterA.publicKey, projectIdRound2),
-      signer: voterA.publicKey,
-      voteManager: voteManagerPda,
-      adminTokenAccount: mintTokenAccount,
-      project: pdaProjectRound2,
-      mint: tokenMint.publicKey,
-      token: voterAAta,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
-      systemProgram: anchor.web3.SystemProgram.programId,
-    };
-    await program.methods.doVote().accounts(doVoteAccountsRound2).signers([voterA]).rpc();
+      const beneficiaryAta = getAssociatedTokenAddressSync(vestedMint.publicKey, voterA.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            provider.publicKey,
+            beneficiaryAta,
+            voterA.publicKey,
+            vestedMint.publicKey,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_PROGRAM_ID
+          )
+        )
+      );
+      // Releasing needs no signature from the admin or the beneficiary.
+      const releaseVested = () =>
+        tokenProgram.methods
+          .releaseVested()
+          .accountsStrict({
+            vesting,
+            mint: vestedMint.publicKey,
+            escrow,
+            beneficiaryTokenAccount: beneficiaryAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+      try {
+        await releaseVested();
+        throw new Error("Expected NothingToRelease error, but transaction succeeded.");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NothingToRelease");
+      }
 
-    // Validate results for Round 1 and Round 2
-    const projectRound1 = await program.account.projectData.fetch(pdaProjectRound1);
-    const projectRound2 = await program.account.projectData.fetch(pdaProjectRound2);
-    expect(projectRound1.voteCount.toNumber()).to.equal(1, "Round 1 project has 1 vote");
-    expect(projectRound2.voteCount.toNumber()).to.equal(1, "Round 2 project has 1 vote");
+      await new Promise((resolve) => setTimeout(resolve, 5_000));
+      const tx = await releaseVested();
+      expect(await getTokenBalance(provider.connection, beneficiaryAta)).to.equal(10_000);
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const events = Array.from(
+        new anchor.EventParser(tokenProgram.programId, tokenProgram.coder).parseLogs(txInfo?.meta?.logMessages ?? [])
+      );
+      expect(events.map((event) => event.name)).to.include("vestedReleased");
+      expect((await tokenProgram.account.vesting.fetch(vesting)).released.toNumber()).to.equal(10_000);
+    });
   });
 });
 
@@ -0,0 +1,141 @@
+use std::error::Error;
+
+use anchor_client::{anchor_lang::AccountDeserialize, solana_sdk::pubkey::Pubkey};
+use anchor_spl::token_interface::Mint;
+
+use crate::{clients, error::CliError};
+
+/// Unit suffix of TTT amounts, optional when parsing.
+const UNIT: &str = "TTT";
+
+/// Reads the decimals of `mint` from the chain, so amounts are never scaled by a guess.
+pub async fn mint_decimals(mint: &Pubkey) -> Result<u8, Box<dyn Error>> {
+    let data = clients().rpc().get_account_data(mint).await?;
+    Ok(Mint::try_deserialize(&mut data.as_slice())?.decimals)
+}
+
+/// Parses a human amount such as `1.5` or `1.5 TTT` into base units of a mint with `decimals`.
+///
+/// Rejects negative amounts, more fractional digits than the mint has, and amounts that do not
+/// fit in a `u64` once scaled.
+pub fn parse_amount(input: &str, decimals: u8) -> Result<u64, CliError> {
+    let invalid = || CliError::config(format!("Invalid TTT amount: {input}"));
+    let number = input.trim();
+    let number = number.strip_suffix(UNIT).unwrap_or(number).trim_end();
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !whole
+        .bytes()
+        .chain(fraction.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    if fraction.len() > usize::from(decimals) {
+        return Err(CliError::config(format!(
+            "Invalid TTT amount: {input} has more than {decimals} decimals"
+        )));
+    }
+
+    // Pad the fraction to `decimals` digits and read both parts as one integer.
+    let digits = format!("{whole}{fraction:0<width$}", width = usize::from(decimals));
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    digits.parse::<u64>().map_err(|_| invalid())
+}
+
+/// Formats `amount` base units of a mint with `decimals` as a human amount, e.g. `1.5 TTT`.
+///
+/// Trailing fractional zeros are dropped, so `parse_amount` reads the output back unchanged.
+pub fn format_amount(amount: u64, decimals: u8) -> String {
+    let decimals = usize::from(decimals);
+    let digits = format!("{amount:0>width$}", width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{whole} {UNIT}")
+    } else {
+        format!("{whole}.{fraction} {UNIT}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_whole_amounts_of_a_mint_without_decimals() {
+        assert_eq!(parse_amount("42", 0).unwrap(), 42);
+        assert_eq!(parse_amount("42 TTT", 0).unwrap(), 42);
+        assert_eq!(parse_amount("42.", 0).unwrap(), 42);
+        assert!(parse_amount("42.5", 0).is_err());
+    }
+
+    #[test]
+    fn parses_amounts_of_a_mint_with_nine_decimals() {
+        assert_eq!(parse_amount("1.5", 9).unwrap(), 1_500_000_000);
+        assert_eq!(parse_amount("1.5 TTT", 9).unwrap(), 1_500_000_000);
+        assert_eq!(parse_amount(".000000001", 9).unwrap(), 1);
+        assert_eq!(parse_amount("0.000000000", 9).unwrap(), 0);
+        assert_eq!(parse_amount("007", 9).unwrap(), 7_000_000_000);
+    }
+
+    #[test]
+    fn rejects_more_fractional_digits_than_the_mint_has() {
+        assert!(parse_amount("0.0000000001", 9).is_err());
+        assert!(parse_amount("1.25", 1).is_err());
+        assert_eq!(parse_amount("1.2", 1).unwrap(), 12);
+    }
+
+    #[test]
+    fn rejects_malformed_amounts() {
+        for input in ["", ".", "TTT", "-1", "+1", "1.2.3", "1,5", "1e9", "0x10"] {
+            assert!(parse_amount(input, 9).is_err(), "{input:?} parsed");
+        }
+    }
+
+    #[test]
+    fn rejects_amounts_overflowing_u64() {
+        assert_eq!(parse_amount("18446744073709551615", 0).unwrap(), u64::MAX);
+        assert!(parse_amount("18446744073709551616", 0).is_err());
+        assert_eq!(parse_amount("18446744073.709551615", 9).unwrap(), u64::MAX);
+        assert!(parse_amount("18446744073.709551616", 9).is_err());
+        assert!(parse_amount("18446744074", 9).is_err());
+    }
+
+    #[test]
+    fn formats_amounts_without_trailing_zeros() {
+        assert_eq!(format_amount(42, 0), "42 TTT");
+        assert_eq!(format_amount(0, 9), "0 TTT");
+        assert_eq!(format_amount(1, 9), "0.000000001 TTT");
+        assert_eq!(format_amount(1_500_000_000, 9), "1.5 TTT");
+        assert_eq!(format_amount(u64::MAX, 9), "18446744073.709551615 TTT");
+    }
+
+    #[test]
+    fn formatting_a_parsed_amount_round_trips() {
+        for (input, decimals) in [
+            ("0 TTT", 0),
+            ("42 TTT", 0),
+            ("18446744073709551615 TTT", 0),
+            ("0 TTT", 9),
+            ("1 TTT", 9),
+            ("1.5 TTT", 9),
+            ("0.000000001 TTT", 9),
+            ("18446744073.709551615 TTT", 9),
+        ] {
+            let amount = parse_amount(input, decimals).unwrap();
+            assert_eq!(format_amount(amount, decimals), input);
+        }
+        for amount in [0, 1, 10, 999_999_999, 1_000_000_000, u64::MAX] {
+            for decimals in [0, 6, 9] {
+                let formatted = format_amount(amount, decimals);
+                assert_eq!(parse_amount(&formatted, decimals).unwrap(), amount);
+            }
+        }
+    }
+}
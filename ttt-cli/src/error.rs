@@ -0,0 +1,141 @@
+use std::{error::Error, fmt};
+
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError as SolanaClientError, ClientErrorKind},
+        rpc_request::{RpcError, RpcResponseErrorData},
+    },
+    solana_sdk::{instruction::InstructionError, transaction::TransactionError},
+    ClientError,
+};
+
+/// Failure classes, each with a stable process exit code scripts can branch on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// Bad arguments, unreadable keypairs or malformed transaction files.
+    Config,
+    /// The RPC node could not be reached, or an account could not be fetched.
+    Rpc,
+    /// The governance program rejected the transaction (an Anchor constraint or `require!`).
+    Constraint,
+    /// The transaction failed in simulation for another reason, e.g. missing SOL for fees.
+    Simulation,
+}
+
+impl FailureClass {
+    /// Process exit code of the class; 0 is success and 1 is left to panics and the runtime.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            FailureClass::Config => 2,
+            FailureClass::Rpc => 3,
+            FailureClass::Constraint => 4,
+            FailureClass::Simulation => 5,
+        }
+    }
+
+    /// Name of the class in machine-parsable output.
+    pub fn name(self) -> &'static str {
+        match self {
+            FailureClass::Config => "config",
+            FailureClass::Rpc => "rpc",
+            FailureClass::Constraint => "constraint",
+            FailureClass::Simulation => "simulation",
+        }
+    }
+}
+
+/// How failures are printed on stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    Text,
+    Json,
+}
+
+/// An error raised by the CLI itself rather than by the RPC client.
+#[derive(Debug)]
+pub struct CliError {
+    pub class: FailureClass,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn config(message: impl Into<String>) -> Self {
+        CliError {
+            class: FailureClass::Config,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for CliError {}
+
+/// Classifies `e`, returning its failure class and the program logs of a failed simulation.
+///
+/// Errors that are neither CLI nor RPC client errors (argument parsing, keypair and file I/O)
+/// are configuration errors.
+pub fn classify(e: &(dyn Error + 'static)) -> (FailureClass, Vec<String>) {
+    if let Some(e) = e.downcast_ref::<CliError>() {
+        return (e.class, Vec::new());
+    }
+    if let Some(e) = e.downcast_ref::<SolanaClientError>() {
+        return classify_rpc(&e.kind);
+    }
+    match e.downcast_ref::<ClientError>() {
+        Some(ClientError::SolanaClientError(e)) => classify_rpc(&e.kind),
+        Some(_) => (FailureClass::Rpc, Vec::new()),
+        None => (FailureClass::Config, Vec::new()),
+    }
+}
+
+fn classify_rpc(kind: &ClientErrorKind) -> (FailureClass, Vec<String>) {
+    match kind {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation),
+            ..
+        }) => {
+            let logs = simulation.logs.clone().unwrap_or_default();
+            (classify_transaction(simulation.err.as_ref()), logs)
+        }
+        ClientErrorKind::TransactionError(err) => (classify_transaction(Some(err)), Vec::new()),
+        _ => (FailureClass::Rpc, Vec::new()),
+    }
+}
+
+fn classify_transaction(err: Option<&TransactionError>) -> FailureClass {
+    // Anchor reports constraint and `require!` failures as custom instruction errors.
+    match err {
+        Some(TransactionError::InstructionError(_, InstructionError::Custom(_))) => {
+            FailureClass::Constraint
+        }
+        _ => FailureClass::Simulation,
+    }
+}
+
+/// Prints `e` on stderr in `format` and returns the exit code for it.
+pub fn report(e: &(dyn Error + 'static), format: ErrorFormat) -> i32 {
+    let (class, logs) = classify(e);
+    match format {
+        ErrorFormat::Text => {
+            eprintln!("error ({}): {e}", class.name());
+            for log in &logs {
+                eprintln!("  {log}");
+            }
+        }
+        ErrorFormat::Json => eprintln!(
+            "{}",
+            serde_json::json!({
+                "class": class.name(),
+                "exit_code": class.exit_code(),
+                "message": e.to_string(),
+                "logs": logs,
+            })
+        ),
+    }
+    class.exit_code()
+}
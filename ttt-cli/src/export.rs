@@ -0,0 +1,158 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Arc,
+};
+
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+
+/// A vote of the exported round, one row of the export.
+pub struct VoteRow {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>, // Unix timestamp, unknown for transactions without one.
+    pub voter: String,
+    pub project: String,
+    pub round: u64,
+    pub weight: u64,
+    pub tx_fee: u64, // Base units of TTT the vote's transaction paid into the treasury.
+    pub memo: Option<String>, // The vote's fee memo, unknown for votes sent without one.
+}
+
+/// File formats `index export` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "csv" => Some(ExportFormat::Csv),
+            "parquet" => Some(ExportFormat::Parquet),
+            _ => None,
+        }
+    }
+}
+
+/// Writes rows chunk by chunk, so only the current chunk is held in memory.
+///
+/// CSV chunks are flushed to the file as they are written, Parquet chunks each become a row group.
+pub enum ExportWriter {
+    Csv(BufWriter<File>),
+    Parquet(Box<ArrowWriter<File>>, Arc<Schema>),
+}
+
+const COLUMNS: [&str; 9] = [
+    "signature",
+    "slot",
+    "block_time",
+    "voter",
+    "project",
+    "round",
+    "weight",
+    "tx_fee",
+    "memo",
+];
+
+impl ExportWriter {
+    /// Creates `path` and writes the header (CSV) or schema (Parquet).
+    pub fn create(path: &str, format: ExportFormat) -> Result<Self, Box<dyn Error>> {
+        let file = File::create(path)?;
+        match format {
+            ExportFormat::Csv => {
+                let mut out = BufWriter::new(file);
+                writeln!(out, "{}", COLUMNS.join(","))?;
+                Ok(ExportWriter::Csv(out))
+            }
+            ExportFormat::Parquet => {
+                let schema = Arc::new(Schema::new(vec![
+                    Field::new(COLUMNS[0], DataType::Utf8, false),
+                    Field::new(COLUMNS[1], DataType::UInt64, false),
+                    Field::new(COLUMNS[2], DataType::Int64, true),
+                    Field::new(COLUMNS[3], DataType::Utf8, false),
+                    Field::new(COLUMNS[4], DataType::Utf8, false),
+                    Field::new(COLUMNS[5], DataType::UInt64, false),
+                    Field::new(COLUMNS[6], DataType::UInt64, false),
+                    Field::new(COLUMNS[7], DataType::UInt64, false),
+                    Field::new(COLUMNS[8], DataType::Utf8, true),
+                ]));
+                let writer = ArrowWriter::try_new(file, schema.clone(), None)?;
+                Ok(ExportWriter::Parquet(Box::new(writer), schema))
+            }
+        }
+    }
+
+    /// Appends `rows` to the file.
+    pub fn write_chunk(&mut self, rows: &[VoteRow]) -> Result<(), Box<dyn Error>> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        match self {
+            ExportWriter::Csv(out) => {
+                for row in rows {
+                    let block_time = row.block_time.map(|t| t.to_string()).unwrap_or_default();
+                    let memo = row.memo.as_deref().map(csv_field).unwrap_or_default();
+                    writeln!(
+                        out,
+                        "{},{},{block_time},{},{},{},{},{},{memo}",
+                        row.signature,
+                        row.slot,
+                        row.voter,
+                        row.project,
+                        row.round,
+                        row.weight,
+                        row.tx_fee
+                    )?;
+                }
+                out.flush()?;
+            }
+            ExportWriter::Parquet(writer, schema) => {
+                let columns: Vec<ArrayRef> = vec![
+                    Arc::new(StringArray::from_iter_values(
+                        rows.iter().map(|r| &r.signature),
+                    )),
+                    Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.slot))),
+                    Arc::new(Int64Array::from_iter(rows.iter().map(|r| r.block_time))),
+                    Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.voter))),
+                    Arc::new(StringArray::from_iter_values(
+                        rows.iter().map(|r| &r.project),
+                    )),
+                    Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.round))),
+                    Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.weight))),
+                    Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.tx_fee))),
+                    Arc::new(StringArray::from_iter(
+                        rows.iter().map(|r| r.memo.as_deref()),
+                    )),
+                ];
+                writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Completes the file; a Parquet file is unreadable until its footer is written.
+    pub fn finish(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            ExportWriter::Csv(mut out) => out.flush()?,
+            ExportWriter::Parquet(writer, _) => {
+                writer.close()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Quotes `field` when it holds a comma, quote or line break; project ids in memos are free text.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
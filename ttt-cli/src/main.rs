@@ -1,14 +1,49 @@
-use std::{env, error::Error, rc::Rc};
+mod amount;
+mod error;
+mod export;
+mod memo;
+mod report;
+mod serve;
+mod spec;
 
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    error::Error,
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    process::{self, Command},
+    rc::Rc,
+    sync::OnceLock,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use amount::{format_amount, mint_decimals, parse_amount};
 use anchor_client::{
-    solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, system_program},
-    Client, Cluster,
+    anchor_lang::{AnchorDeserialize, Discriminator, InstructionData, Space, ToAccountMetas},
+    solana_sdk::{
+        ed25519_program,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Signature},
+        system_instruction, system_program, sysvar,
+    },
+    Cluster,
 };
+use base64::{prelude::BASE64_STANDARD, Engine};
+use error::{CliError, ErrorFormat, FailureClass};
+use export::{ExportFormat, ExportWriter, VoteRow};
+use governance::seeds;
+use memo::{transaction_fee_memos, FeeMemo};
+use report::{ProjectStanding, ReportTemplate, RoundReport};
+use spec::ElectionSpec;
+use ttt_plugin::rpc::{ClientFactory, RpcAuth, SendVia};
 
 use anchor_client::{
     solana_client::{
         client_error::ClientErrorKind::RpcError,
-        rpc_request::{RpcError as SolanaRpcError, RpcResponseErrorData},
+        rpc_request::{RpcError as SolanaRpcError, RpcRequest, RpcResponseErrorData},
     },
     solana_sdk::signature::{Keypair, Signer},
     ClientError::SolanaClientError,
@@ -16,358 +51,8529 @@ use anchor_client::{
 
 const ADMIN_SECRET: &str = "";
 const GOVERNANCE_PROGRAM_ID: &str = "";
+const TTT_TOKEN_PROGRAM_ID: &str = "";
 const TOKEN_MINT: &str = "";
 const VOUTER_SECRET: &str = "";
 const TOKEN_PROGRAM: &str = "";
 const ASSOCIATED_TOKEN_PROGRAM: &str = "";
+// Vote fee `init_force` starts the VoteManager with, in TTT.
+const INIT_VOTE_FEE: &str = "100";
+// Ballots counted per `tally_ranked_round` transaction, keeping it under the size limit.
+const BALLOT_PAGE_SIZE: usize = 20;
+// Projects counted per `tally_page` transaction, keeping it under the size limit.
+const TALLY_PAGE_SIZE: usize = 20;
+// Projects archived per `finalize_round` or `archive_projects` transaction, leaving room for the
+// campaign instruction sealed along with the round.
+const ARCHIVE_PAGE_SIZE: usize = 16;
+// Projects created per `add_projects` transaction, the program's batch maximum.
+const PROJECT_BATCH_SIZE: usize = governance::PROJECT_BATCH_MAX_LEN;
+// Signatures fetched per `getSignaturesForAddress` call, the RPC maximum; also the export chunk.
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+// Recipients per `airdrop` transaction, each with its idempotent ATA creation, keeping it under
+// the size limit.
+const AIRDROP_CHUNK_SIZE: usize = 8;
+// Voters listed in a round report, highest weight first.
+const REPORT_TOP_VOTERS: usize = 10;
+
+// Client factory of the cluster selected in `main`, shared by every command.
+static CLIENTS: OnceLock<ClientFactory> = OnceLock::new();
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
+async fn main() {
+    // `--yes` / `-y` skips the confirmation prompt shown before sending transactions,
+    // `--error-format json` prints failures as one JSON object on stderr, `--election <id>`
+    // selects the admin's election the commands act on (0 by default), and
+    // `--idempotency-key <key>` guards an admin mutation against being applied twice, and
+    // `--url <cluster>` overrides the cluster of `TTT_CLUSTER` (devnet by default) for one command.
+    let mut yes = false;
+    let mut error_format = ErrorFormat::Text;
+    let mut election = 0;
+    let mut idempotency_key = None;
+    let mut url = None;
+    let mut args = Vec::new();
+    let mut raw_args = env::args();
+    while let Some(arg) = raw_args.next() {
+        match arg.as_str() {
+            "--yes" | "-y" => yes = true,
+            "--error-format" => match raw_args.next().as_deref() {
+                Some("text") => error_format = ErrorFormat::Text,
+                Some("json") => error_format = ErrorFormat::Json,
+                _ => {
+                    let e = CliError::config("--error-format expects `text` or `json`");
+                    process::exit(error::report(&e, ErrorFormat::Text));
+                }
+            },
+            "--election" => match raw_args.next().map(|id| id.parse::<u64>()) {
+                Some(Ok(id)) => election = id,
+                _ => {
+                    let e = CliError::config("--election expects an election id");
+                    process::exit(error::report(&e, ErrorFormat::Text));
+                }
+            },
+            "--idempotency-key" => match raw_args.next() {
+                Some(key) => idempotency_key = Some(key),
+                None => {
+                    let e = CliError::config("--idempotency-key expects a key");
+                    process::exit(error::report(&e, ErrorFormat::Text));
+                }
+            },
+            "--url" => match raw_args.next().map(|url| url.parse::<Cluster>()) {
+                Some(Ok(cluster)) => url = Some(cluster),
+                _ => {
+                    let e = CliError::config("--url expects a cluster name or an RPC URL");
+                    process::exit(error::report(&e, ErrorFormat::Text));
+                }
+            },
+            _ => args.push(arg),
+        }
+    }
+
+    match client_factory(url) {
+        Ok(clients) => CLIENTS.get_or_init(|| clients),
+        Err(e) => process::exit(error::report(e.as_ref(), error_format)),
+    };
+
+    if let Err(e) = run(&args, yes, election, idempotency_key.as_deref()).await {
+        process::exit(error::report(e.as_ref(), error_format));
+    }
+}
+
+/// Builds the client factory for `url`, else `TTT_CLUSTER`, else devnet.
+///
+/// An API key in `TTT_RPC_API_KEY` authenticates every request, sent the way `TTT_RPC_AUTH`
+/// (`query:<param>` or `header:<name>`) says.
+fn client_factory(url: Option<Cluster>) -> Result<ClientFactory, Box<dyn Error>> {
+    let cluster = match (url, env::var(ttt_plugin::CLUSTER_ENV)) {
+        (Some(cluster), _) => cluster,
+        (None, Ok(cluster)) => cluster
+            .parse()
+            .map_err(|e| CliError::config(format!("{}: {e}", ttt_plugin::CLUSTER_ENV)))?,
+        (None, Err(_)) => Cluster::Devnet,
+    };
+    ClientFactory::new(cluster, RpcAuth::from_env()?)
+}
+
+/// The client factory `main` set up.
+fn clients() -> &'static ClientFactory {
+    CLIENTS
+        .get()
+        .expect("the client factory is set up before any command runs")
+}
 
+async fn run(
+    args: &[String],
+    yes: bool,
+    election: u64,
+    idempotency_key: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
     if args.len() < 2 {
-        eprintln!("Usage:");
+        eprintln!(
+            "Usage: {} [--yes] [--error-format text|json] [--election <id>] \
+             [--idempotency-key <key>] [--url <cluster>] <command>",
+            args[0]
+        );
+        eprintln!();
+        eprintln!("Commands:");
         eprintln!("  {} init_force", args[0]);
         eprintln!("  {} add_project <project_key> <round>", args[0]);
+        eprintln!("  {} add_projects --file <projects.json> <round>", args[0]);
+        eprintln!("  {} submit_project <project_key>", args[0]);
+        eprintln!("  {} approve_project <project_key> <round>", args[0]);
+        eprintln!("  {} reject_project <project_key> <round> <refund|slash>", args[0]);
+        eprintln!("  {} claim_deposit_tranche <project_key> <round>", args[0]);
+        eprintln!(
+            "  {} update_project_metadata <project_key> <round> <name> <uri> <category>",
+            args[0]
+        );
         eprintln!("  {} change_fee <new_fee>", args[0]);
+        eprintln!("  {} change_sol_fee <lamports|off>", args[0]);
         eprintln!("  {} get_round", args[0]);
         eprintln!("  {} increment_round", args[0]);
-        eprintln!("  {} do_vote  <project_name> <round>", args[0]);
+        eprintln!("  {} open_round", args[0]);
+        eprintln!("  {} close_round", args[0]);
+        eprintln!("  {} invalidate_round <reason>", args[0]);
+        eprintln!("  {} migrate_vote_manager", args[0]);
+        eprintln!("  {} migrate_account [address]", args[0]);
+        eprintln!(
+            "  {} do_vote  <project_name> <round> [snapshot_file] [--sol]",
+            args[0]
+        );
+        eprintln!("  {} sponsored_vote <project_name> <round>", args[0]);
+        eprintln!("  {} approve_votes <amount>", args[0]);
+        eprintln!(
+            "  {} authorize_vote <project_name> <round> <authorization_file>",
+            args[0]
+        );
+        eprintln!("  {} relay_vote <authorization_file>", args[0]);
+        eprintln!("  {} compressed_vote <project_name> <round>", args[0]);
+        eprintln!("  {} ranked_vote <round> <project_name>..", args[0]);
+        eprintln!("  {} tally_ranked", args[0]);
+        eprintln!("  {} tally", args[0]);
+        eprintln!(
+            "  {} serve --rpc-proxy [<listen_addr>] [<requests_per_minute>]",
+            args[0]
+        );
+        eprintln!("  {} daemon <poll_secs> [alert_cmd]", args[0]);
+        eprintln!("  {} claim_refund <project_name> <round>", args[0]);
+        eprintln!("  {} claim_reward <round>", args[0]);
+        eprintln!("  {} claim_compressed_reward <round>", args[0]);
+        eprintln!("  {} retract_vote <project_name> <round>", args[0]);
+        eprintln!("  {} init_leaderboard", args[0]);
+        eprintln!("  {} leaderboard [round]", args[0]);
+        eprintln!("  {} cleanup <round>", args[0]);
+        eprintln!("  {} history <round>", args[0]);
+        eprintln!("  {} init_treasury", args[0]);
+        eprintln!("  {} treasury_balance", args[0]);
+        eprintln!("  {} withdraw_treasury <amount>", args[0]);
+        eprintln!("  {} withdraw_treasury_sol <lamports>", args[0]);
+        eprintln!("  {} fund_top_ups <amount>", args[0]);
+        eprintln!("  {} withdraw_top_ups <amount>", args[0]);
+        eprintln!("  {} init_sponsor_vault <max_per_wallet>", args[0]);
+        eprintln!("  {} fund_sponsor_vault <lamports>", args[0]);
+        eprintln!("  {} withdraw_sponsor_vault <lamports>", args[0]);
+        eprintln!("  {} init_vote_tree <max_depth> <max_buffer_size>", args[0]);
+        eprintln!("  {} fund_rewards <round> <amount>", args[0]);
+        eprintln!("  {} sweep_rewards <round>", args[0]);
+        eprintln!("  {} attest_round <round>", args[0]);
+        eprintln!("  {} campaign create <plan_file>", args[0]);
+        eprintln!("  {} campaign status", args[0]);
+        eprintln!("  {} snapshot <snapshot_file>", args[0]);
+        eprintln!("  {} set_eligibility_root <snapshot_file|none>", args[0]);
+        eprintln!("  {} apply <spec.yaml>", args[0]);
+        eprintln!("  {} set_vote_receipts <receipt_mint|none>", args[0]);
+        eprintln!("  {} set_escrow_mode <max_lock_secs|off>", args[0]);
+        eprintln!("  {} set_paused <on|off>", args[0]);
+        eprintln!("  {} set_fee_mode <collect|burn>", args[0]);
+        eprintln!("  {} set_retract_refund <on|off>", args[0]);
+        eprintln!("  {} set_max_projects <max|off>", args[0]);
+        eprintln!(
+            "  {} set_tie_break <earliest|lowest_pda|runoff> [secs]",
+            args[0]
+        );
+        eprintln!("  {} grant_role <wallet> <operator|auditor>", args[0]);
+        eprintln!("  {} revoke_role <wallet>", args[0]);
+        eprintln!("  {} freeze_account <wallet>", args[0]);
+        eprintln!("  {} thaw_account <wallet>", args[0]);
+        eprintln!("  {} thaw_voter <wallet>", args[0]);
+        eprintln!("  {} airdrop --csv <recipients.csv>", args[0]);
+        eprintln!("  {} vesting status <beneficiary>", args[0]);
+        eprintln!("  {} set_access_mode <open|allowlist|blocklist>", args[0]);
+        eprintln!("  {} allowlist <add|remove> <wallet>", args[0]);
+        eprintln!("  {} blocklist <add|remove> <wallet>", args[0]);
+        eprintln!("  {} stake <amount> <lock_secs>", args[0]);
+        eprintln!("  {} unstake", args[0]);
+        eprintln!(
+            "  {} set_holding_requirement <min_balance> <min_slots>|off",
+            args[0]
+        );
+        eprintln!("  {} register_holding", args[0]);
+        eprintln!("  {} set_fee_discounts <min_balance:bps>..|off", args[0]);
+        eprintln!("  {} set_categories <name>..|off", args[0]);
+        eprintln!("  {} propose_fee <new_fee>", args[0]);
+        eprintln!(
+            "  {} vote_proposal <proposal_id> <yes|no> <amount>",
+            args[0]
+        );
+        eprintln!("  {} release_proposal_vote <proposal_id>", args[0]);
+        eprintln!("  {} execute_proposal <proposal_id>", args[0]);
+        eprintln!(
+            "  {} create_referendum <question_uri> <duration_secs>",
+            args[0]
+        );
+        eprintln!(
+            "  {} vote_referendum <referendum_id> <yes|no|abstain> [snapshot_file]",
+            args[0]
+        );
+        eprintln!("  {} referendum <referendum_id>", args[0]);
+        eprintln!("  {} council create <threshold> <member>..", args[0]);
+        eprintln!(
+            "  {} council propose <member_keypair> <action> [args..]",
+            args[0]
+        );
+        eprintln!("  {} council approve <member_keypair> <action_id>", args[0]);
+        eprintln!("  {} council execute <action_id>", args[0]);
+        eprintln!("  {} council status", args[0]);
+        eprintln!("  {} watch", args[0]);
+        eprintln!("  {} receipt <signature>", args[0]);
+        eprintln!("  {} receipt verify <receipt_file>", args[0]);
+        eprintln!(
+            "  {} index export --round <round> [--format csv|parquet] <out_file>",
+            args[0]
+        );
+        eprintln!(
+            "  {} report --round <round> [--template md|html] [out_file]",
+            args[0]
+        );
+        eprintln!("  {} plugins", args[0]);
+        eprintln!("  {} <plugin> [args..]  (runs ttt-<plugin> from PATH)", args[0]);
         return Ok(());
     }
 
-    match args[1].as_str() {
-        "init_force" => init_force().await?,
-        "change_fee" => {
-            if args.len() < 3 {
-                eprintln!("Usage: {} change_fee <new_fee>", args[0]);
-                return Ok(());
+    if idempotency_key.is_some()
+        && !matches!(
+            args[1].as_str(),
+            "add_project" | "add_projects" | "change_fee"
+        )
+    {
+        return Err(CliError::config(
+            "--idempotency-key only guards add_project, add_projects and change_fee",
+        )
+        .into());
+    }
+
+    match args[1].as_str() {
+        "init_force" => init_force(election, yes).await?,
+        "change_fee" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} change_fee <new_fee>", args[0]);
+                return Ok(());
+            }
+            change_fee(&args[2], election, idempotency_key, yes).await?;
+        }
+        "get_round" => {
+            get_round(election).await?;
+        }
+        "increment_round" => {
+            increment_round(election, yes).await?;
+        }
+        "open_round" => set_round_state(governance::RoundState::Open, election, yes).await?,
+        "close_round" => set_round_state(governance::RoundState::Closed, election, yes).await?,
+        "invalidate_round" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} invalidate_round <reason>", args[0]);
+                return Ok(());
+            }
+            invalidate_round(&args[2..].join(" "), election, yes).await?;
+        }
+        "migrate_vote_manager" => migrate_vote_manager(election, yes).await?,
+        "migrate_account" => {
+            let address = args.get(2).map(|a| a.parse::<Pubkey>()).transpose()?;
+            migrate_account(address, election, yes).await?;
+        }
+        "add_project" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} add_project <project_key> <round>", args[0]);
+                return Ok(());
+            }
+            let project_key = &args[2];
+            let round = &args[3];
+            add_project(
+                project_key,
+                round.parse().unwrap(),
+                election,
+                idempotency_key,
+                yes,
+            )
+            .await?;
+        }
+        "add_projects" => {
+            if args.len() < 5 || args[2] != "--file" {
+                eprintln!(
+                    "Usage: {} add_projects --file <projects.json> <round>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let projects_file = &args[3];
+            let round = &args[4];
+            add_projects(
+                projects_file,
+                round.parse().unwrap(),
+                election,
+                idempotency_key,
+                yes,
+            )
+            .await?;
+        }
+        "submit_project" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} submit_project <project_key>", args[0]);
+                return Ok(());
+            }
+            submit_project(&args[2], election, yes).await?;
+        }
+        "approve_project" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} approve_project <project_key> <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            approve_project(&args[2], round, election, yes).await?;
+        }
+        "claim_deposit_tranche" => {
+            if args.len() < 4 {
+                eprintln!(
+                    "Usage: {} claim_deposit_tranche <project_key> <round>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            claim_deposit_tranche(&args[2], round, election, yes).await?;
+        }
+        "reject_project" => {
+            if args.len() < 5 {
+                eprintln!(
+                    "Usage: {} reject_project <project_key> <round> <refund|slash>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            let slash = match args[4].as_str() {
+                "refund" => false,
+                "slash" => true,
+                other => {
+                    return Err(CliError::config(format!(
+                        "Expected refund or slash, got: {other}"
+                    ))
+                    .into());
+                }
+            };
+            reject_project(&args[2], round, slash, election, yes).await?;
+        }
+        "update_project_metadata" => {
+            if args.len() < 7 {
+                eprintln!(
+                    "Usage: {} update_project_metadata <project_key> <round> <name> <uri> <category>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            let category = args[6].parse::<u8>()?;
+            update_project_metadata(&args[2], round, &args[4], &args[5], category, election, yes)
+                .await?;
+        }
+        "do_vote" | "sponsored_vote" => {
+            // `--sol` pays the vote's fee in lamports instead of TTT.
+            let (sol, args): (Vec<String>, Vec<String>) =
+                args.iter().cloned().partition(|arg| arg == "--sol");
+            if args.len() < 4 {
+                eprintln!("Usage: {} {}  <project_name> <round>", args[0], args[1]);
+                return Ok(());
+            }
+            let project_key = &args[2];
+            let round = args[3].parse::<u64>()?;
+            let sponsored = args[1] == "sponsored_vote";
+            let snapshot_file = args.get(4).map(String::as_str);
+            let fee_currency = if sol.is_empty() {
+                governance::FeeCurrency::Ttt
+            } else {
+                governance::FeeCurrency::Sol
+            };
+            do_vote(
+                project_key,
+                round,
+                sponsored,
+                snapshot_file,
+                fee_currency,
+                election,
+                yes,
+            )
+            .await?;
+        }
+        "approve_votes" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} approve_votes <amount>", args[0]);
+                return Ok(());
+            }
+            approve_votes(&args[2], election, yes).await?;
+        }
+        "authorize_vote" => {
+            if args.len() < 5 {
+                eprintln!(
+                    "Usage: {} authorize_vote <project_name> <round> <authorization_file>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            authorize_vote(&args[2], round, &args[4], election).await?;
+        }
+        "relay_vote" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} relay_vote <authorization_file>", args[0]);
+                return Ok(());
+            }
+            relay_vote(&args[2], election, yes).await?;
+        }
+        "compressed_vote" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} compressed_vote <project_name> <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            compressed_vote(&args[2], round, election, yes).await?;
+        }
+        "ranked_vote" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} ranked_vote <round> <project_name>..", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            ranked_vote(round, &args[3..], election, yes).await?;
+        }
+        "tally_ranked" => tally_ranked(election, yes).await?,
+        "tally" => tally(election, yes).await?,
+        "serve" => {
+            if args.get(2).map(String::as_str) != Some("--rpc-proxy") {
+                eprintln!(
+                    "Usage: {} serve --rpc-proxy [<listen_addr>] [<requests_per_minute>]",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let listen = args.get(3).map_or("127.0.0.1:8080", String::as_str).parse()?;
+            let per_minute = args.get(4).map_or(Ok(60), |n| n.parse::<u32>())?;
+            serve::rpc_proxy(
+                listen,
+                clients().rpc(),
+                GOVERNANCE_PROGRAM_ID.parse()?,
+                per_minute,
+            )
+            .await?;
+        }
+        "daemon" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} daemon <poll_secs> [alert_cmd]", args[0]);
+                return Ok(());
+            }
+            let poll_secs = args[2].parse::<u64>()?;
+            let alert_cmd = args.get(3).map(String::as_str);
+            daemon(poll_secs, alert_cmd, election).await?;
+        }
+        "claim_refund" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} claim_refund <project_name> <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            claim_refund(&args[2], round, election, yes).await?;
+        }
+        "claim_reward" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} claim_reward <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            claim_reward(round, election, yes).await?;
+        }
+        "claim_compressed_reward" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} claim_compressed_reward <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            claim_compressed_reward(round, election, yes).await?;
+        }
+        "retract_vote" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} retract_vote <project_name> <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[3].parse::<u64>()?;
+            retract_vote(&args[2], round, election, yes).await?;
+        }
+        "init_leaderboard" => init_leaderboard(election, yes).await?,
+        "leaderboard" => {
+            let round = args.get(2).map(|round| round.parse::<u64>()).transpose()?;
+            leaderboard(round, election).await?;
+        }
+        "cleanup" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} cleanup <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            cleanup(round, election, yes).await?;
+        }
+        "history" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} history <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            history(round, election).await?;
+        }
+        "init_treasury" => init_treasury(election, yes).await?,
+        "treasury_balance" => treasury_balance(election).await?,
+        "withdraw_treasury" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} withdraw_treasury <amount>", args[0]);
+                return Ok(());
+            }
+            withdraw_treasury(&args[2], election, yes).await?;
+        }
+        "withdraw_treasury_sol" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} withdraw_treasury_sol <lamports>", args[0]);
+                return Ok(());
+            }
+            let lamports = args[2].parse::<u64>()?;
+            withdraw_treasury_sol(lamports, election, yes).await?;
+        }
+        "fund_top_ups" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} fund_top_ups <amount>", args[0]);
+                return Ok(());
+            }
+            fund_top_ups(&args[2], election, yes).await?;
+        }
+        "withdraw_top_ups" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} withdraw_top_ups <amount>", args[0]);
+                return Ok(());
+            }
+            withdraw_top_ups(&args[2], election, yes).await?;
+        }
+        "init_sponsor_vault" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} init_sponsor_vault <max_per_wallet>", args[0]);
+                return Ok(());
+            }
+            let max_per_wallet = args[2].parse::<u64>()?;
+            init_sponsor_vault(max_per_wallet, election, yes).await?;
+        }
+        "fund_sponsor_vault" | "withdraw_sponsor_vault" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} {} <lamports>", args[0], args[1]);
+                return Ok(());
+            }
+            let lamports = args[2].parse::<u64>()?;
+            let fund = args[1] == "fund_sponsor_vault";
+            manage_sponsor_vault(lamports, fund, election, yes).await?;
+        }
+        "init_vote_tree" => {
+            if args.len() < 4 {
+                eprintln!(
+                    "Usage: {} init_vote_tree <max_depth> <max_buffer_size>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let max_depth = args[2].parse::<u32>()?;
+            let max_buffer_size = args[3].parse::<u32>()?;
+            init_vote_tree(max_depth, max_buffer_size, election, yes).await?;
+        }
+        "fund_rewards" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} fund_rewards <round> <amount>", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            fund_rewards(round, &args[3], election, yes).await?;
+        }
+        "sweep_rewards" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} sweep_rewards <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            sweep_rewards(round, election, yes).await?;
+        }
+        "attest_round" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} attest_round <round>", args[0]);
+                return Ok(());
+            }
+            let round = args[2].parse::<u64>()?;
+            attest_round(round, election, yes).await?;
+        }
+        "campaign" => match (args.get(2).map(String::as_str), args.get(3)) {
+            (Some("create"), Some(plan_file)) => campaign_create(plan_file, election, yes).await?,
+            (Some("status"), _) => campaign_status(election).await?,
+            _ => eprintln!("Usage: {} campaign create <plan_file> | status", args[0]),
+        },
+        "snapshot" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} snapshot <snapshot_file>", args[0]);
+                return Ok(());
+            }
+            snapshot(&args[2], election).await?;
+        }
+        "set_eligibility_root" => {
+            if args.len() < 3 {
+                eprintln!(
+                    "Usage: {} set_eligibility_root <snapshot_file|none>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            set_eligibility_root(&args[2], election, yes).await?;
+        }
+        "apply" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} apply <spec.yaml>", args[0]);
+                return Ok(());
+            }
+            apply(&args[2], election, yes).await?;
+        }
+        "set_escrow_mode" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} set_escrow_mode <max_lock_secs|off>", args[0]);
+                return Ok(());
+            }
+            let max_lock_duration = match args[2].as_str() {
+                "off" => None,
+                secs => Some(secs.parse::<i64>()?),
+            };
+            set_escrow_mode(max_lock_duration, election, yes).await?;
+        }
+        "set_paused" => {
+            let paused = match args.get(2).map(String::as_str) {
+                Some("on") => true,
+                Some("off") => false,
+                _ => {
+                    eprintln!("Usage: {} set_paused <on|off>", args[0]);
+                    return Ok(());
+                }
+            };
+            set_paused(paused, election, yes).await?;
+        }
+        "set_fee_mode" => {
+            let fee_mode = match args.get(2).map(String::as_str) {
+                Some("collect") => governance::FeeMode::Collect,
+                Some("burn") => governance::FeeMode::Burn,
+                _ => {
+                    eprintln!("Usage: {} set_fee_mode <collect|burn>", args[0]);
+                    return Ok(());
+                }
+            };
+            set_fee_mode(fee_mode, election, yes).await?;
+        }
+        "set_retract_refund" => {
+            let retract_refund = match args.get(2).map(String::as_str) {
+                Some("on") => true,
+                Some("off") => false,
+                _ => {
+                    eprintln!("Usage: {} set_retract_refund <on|off>", args[0]);
+                    return Ok(());
+                }
+            };
+            set_retract_refund(retract_refund, election, yes).await?;
+        }
+        "change_sol_fee" => {
+            let sol_vote_fee = match args.get(2).map(String::as_str) {
+                Some("off") => Some(0),
+                Some(lamports) => lamports.parse::<u64>().ok().filter(|fee| *fee > 0),
+                None => None,
+            };
+            let Some(sol_vote_fee) = sol_vote_fee else {
+                eprintln!("Usage: {} change_sol_fee <lamports|off>", args[0]);
+                return Ok(());
+            };
+            change_sol_fee(sol_vote_fee, election, yes).await?;
+        }
+        "set_max_projects" => {
+            let max_projects = match args.get(2).map(String::as_str) {
+                Some("off") => Some(0),
+                Some(max) => max.parse::<u16>().ok().filter(|max| *max > 0),
+                None => None,
+            };
+            let Some(max_projects) = max_projects else {
+                eprintln!("Usage: {} set_max_projects <max|off>", args[0]);
+                return Ok(());
+            };
+            set_max_projects(max_projects, election, yes).await?;
+        }
+        "set_tie_break" => {
+            let policy = match (args.get(2).map(String::as_str), args.get(3)) {
+                (Some("earliest"), None) => Some((governance::TieBreakPolicy::EarliestCreated, 0)),
+                (Some("lowest_pda"), None) => Some((governance::TieBreakPolicy::LowestPda, 0)),
+                (Some("runoff"), Some(secs)) => secs
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .map(|secs| (governance::TieBreakPolicy::Runoff, secs)),
+                _ => None,
+            };
+            let Some((tie_break, runoff_duration)) = policy else {
+                eprintln!(
+                    "Usage: {} set_tie_break <earliest|lowest_pda|runoff> [secs]",
+                    args[0]
+                );
+                return Ok(());
+            };
+            set_tie_break(tie_break, runoff_duration, election, yes).await?;
+        }
+        "grant_role" => {
+            let role = match args.get(3).map(String::as_str) {
+                Some("operator") => governance::Role::Operator,
+                Some("auditor") => governance::Role::Auditor,
+                _ => {
+                    eprintln!("Usage: {} grant_role <wallet> <operator|auditor>", args[0]);
+                    return Ok(());
+                }
+            };
+            grant_role(args[2].parse::<Pubkey>()?, role, election, yes).await?;
+        }
+        "revoke_role" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} revoke_role <wallet>", args[0]);
+                return Ok(());
+            }
+            revoke_role(args[2].parse::<Pubkey>()?, election, yes).await?;
+        }
+        "freeze_account" | "thaw_account" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} {} <wallet>", args[0], args[1]);
+                return Ok(());
+            }
+            let frozen = args[1] == "freeze_account";
+            set_account_frozen(args[2].parse::<Pubkey>()?, frozen, yes).await?;
+        }
+        "thaw_voter" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} thaw_voter <wallet>", args[0]);
+                return Ok(());
+            }
+            thaw_voter(args[2].parse::<Pubkey>()?, election, yes).await?;
+        }
+        "airdrop" => {
+            if args.len() < 4 || args[2] != "--csv" {
+                eprintln!("Usage: {} airdrop --csv <recipients.csv>", args[0]);
+                return Ok(());
+            }
+            airdrop(&args[3], yes).await?;
+        }
+        "vesting" => match (args.get(2).map(String::as_str), args.get(3)) {
+            (Some("status"), Some(beneficiary)) => vesting_status(beneficiary.parse()?).await?,
+            _ => eprintln!("Usage: {} vesting status <beneficiary>", args[0]),
+        },
+        "set_access_mode" => {
+            let access_mode = match args.get(2).map(String::as_str) {
+                Some("open") => governance::AccessMode::Open,
+                Some("allowlist") => governance::AccessMode::Allowlist,
+                Some("blocklist") => governance::AccessMode::Blocklist,
+                _ => {
+                    eprintln!(
+                        "Usage: {} set_access_mode <open|allowlist|blocklist>",
+                        args[0]
+                    );
+                    return Ok(());
+                }
+            };
+            set_access_mode(access_mode, election, yes).await?;
+        }
+        "allowlist" | "blocklist" => {
+            let list = if args[1] == "allowlist" {
+                governance::AccessMode::Allowlist
+            } else {
+                governance::AccessMode::Blocklist
+            };
+            let listed = match args.get(2).map(String::as_str) {
+                Some("add") if args.len() > 3 => true,
+                Some("remove") if args.len() > 3 => false,
+                _ => {
+                    eprintln!("Usage: {} {} <add|remove> <wallet>", args[0], args[1]);
+                    return Ok(());
+                }
+            };
+            update_access_list(list, args[3].parse::<Pubkey>()?, listed, election, yes).await?;
+        }
+        "stake" => {
+            if args.len() < 4 {
+                eprintln!("Usage: {} stake <amount> <lock_secs>", args[0]);
+                return Ok(());
+            }
+            let lock_duration = args[3].parse::<i64>()?;
+            stake(&args[2], lock_duration, election, yes).await?;
+        }
+        "unstake" => unstake(election, yes).await?,
+        "set_holding_requirement" => {
+            let requirement = match args.get(2).map(String::as_str) {
+                Some("off") => None,
+                Some(min_balance) if args.len() >= 4 => {
+                    Some((min_balance, args[3].parse::<u64>()?))
+                }
+                _ => {
+                    eprintln!(
+                        "Usage: {} set_holding_requirement <min_balance> <min_slots>|off",
+                        args[0]
+                    );
+                    return Ok(());
+                }
+            };
+            set_holding_requirement(requirement, election, yes).await?;
+        }
+        "register_holding" => register_holding(election, yes).await?,
+        "set_fee_discounts" => {
+            if args.len() < 3 {
+                eprintln!(
+                    "Usage: {} set_fee_discounts <min_balance:bps>..|off",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let tiers = if args[2] == "off" { &[] } else { &args[2..] };
+            set_fee_discounts(tiers, election, yes).await?;
+        }
+        "set_categories" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} set_categories <name>..|off", args[0]);
+                return Ok(());
+            }
+            let names = if args[2] == "off" { &[] } else { &args[2..] };
+            set_categories(names, election, yes).await?;
+        }
+        "set_vote_receipts" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} set_vote_receipts <receipt_mint|none>", args[0]);
+                return Ok(());
+            }
+            set_vote_receipts(&args[2], election, yes).await?;
+        }
+        "propose_fee" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} propose_fee <new_fee>", args[0]);
+                return Ok(());
+            }
+            propose_fee(&args[2], election, yes).await?;
+        }
+        "vote_proposal" => {
+            if args.len() < 5 {
+                eprintln!(
+                    "Usage: {} vote_proposal <proposal_id> <yes|no> <amount>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let proposal_id = args[2].parse::<u64>()?;
+            let approve = match args[3].as_str() {
+                "yes" => true,
+                "no" => false,
+                other => {
+                    return Err(
+                        CliError::config(format!("Expected yes or no, got: {other}")).into(),
+                    );
+                }
+            };
+            vote_proposal(proposal_id, approve, &args[4], election, yes).await?;
+        }
+        "release_proposal_vote" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} release_proposal_vote <proposal_id>", args[0]);
+                return Ok(());
+            }
+            let proposal_id = args[2].parse::<u64>()?;
+            release_proposal_vote(proposal_id, election, yes).await?;
+        }
+        "execute_proposal" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} execute_proposal <proposal_id>", args[0]);
+                return Ok(());
+            }
+            let proposal_id = args[2].parse::<u64>()?;
+            execute_proposal(proposal_id, election, yes).await?;
+        }
+        "create_referendum" => {
+            if args.len() < 4 {
+                eprintln!(
+                    "Usage: {} create_referendum <question_uri> <duration_secs>",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let duration = args[3].parse::<i64>()?;
+            create_referendum(&args[2], duration, election, yes).await?;
+        }
+        "vote_referendum" => {
+            if args.len() < 4 {
+                eprintln!(
+                    "Usage: {} vote_referendum <referendum_id> <yes|no|abstain> [snapshot_file]",
+                    args[0]
+                );
+                return Ok(());
+            }
+            let referendum_id = args[2].parse::<u64>()?;
+            let choice = match args[3].as_str() {
+                "yes" => governance::ReferendumChoice::Yes,
+                "no" => governance::ReferendumChoice::No,
+                "abstain" => governance::ReferendumChoice::Abstain,
+                other => {
+                    return Err(CliError::config(format!(
+                        "Expected yes, no or abstain, got: {other}"
+                    ))
+                    .into());
+                }
+            };
+            let snapshot_file = args.get(4).map(String::as_str);
+            vote_referendum(referendum_id, choice, snapshot_file, election, yes).await?;
+        }
+        "referendum" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} referendum <referendum_id>", args[0]);
+                return Ok(());
+            }
+            let referendum_id = args[2].parse::<u64>()?;
+            referendum(referendum_id, election).await?;
+        }
+        "council" => match (args.get(2).map(String::as_str), args.get(3)) {
+            (Some("create"), Some(threshold)) if args.len() > 4 => {
+                let members = args[4..]
+                    .iter()
+                    .map(|member| member.parse::<Pubkey>())
+                    .collect::<Result<Vec<_>, _>>()?;
+                council_create(threshold.parse::<u8>()?, members, election, yes).await?
+            }
+            (Some("propose"), Some(member_keypair)) if args.len() > 4 => {
+                council_propose(member_keypair, &args[4..], election, yes).await?
+            }
+            (Some("approve"), Some(member_keypair)) if args.len() > 4 => {
+                council_approve(member_keypair, args[4].parse::<u64>()?, election, yes).await?
+            }
+            (Some("execute"), Some(action_id)) => {
+                council_execute(action_id.parse::<u64>()?, election, yes).await?
+            }
+            (Some("status"), _) => council_status(election).await?,
+            _ => eprintln!(
+                "Usage: {} council create <threshold> <member>.. | propose <member_keypair> \
+                 <action> [args..] | approve <member_keypair> <action_id> | execute <action_id> | \
+                 status",
+                args[0]
+            ),
+        },
+        "watch" => watch().await?,
+        "receipt" => match (args.get(2).map(String::as_str), args.get(3)) {
+            (Some("verify"), Some(receipt_file)) => receipt_verify(receipt_file).await?,
+            (Some(signature), None) if signature != "verify" => receipt(signature).await?,
+            _ => eprintln!(
+                "Usage: {} receipt <signature> | verify <receipt_file>",
+                args[0]
+            ),
+        },
+        "index" => {
+            let export_args = match args.get(2).map(String::as_str) {
+                Some("export") => parse_export_args(&args[3..])?,
+                _ => None,
+            };
+            let Some((round, format, out_file)) = export_args else {
+                eprintln!(
+                    "Usage: {} index export --round <round> [--format csv|parquet] <out_file>",
+                    args[0]
+                );
+                return Ok(());
+            };
+            index_export(round, format, &out_file, election).await?;
+        }
+        "report" => {
+            let Some((round, template, out_file)) = parse_report_args(&args[2..])? else {
+                eprintln!(
+                    "Usage: {} report --round <round> [--template md|html] [out_file]",
+                    args[0]
+                );
+                return Ok(());
+            };
+            report(round, template, out_file.as_deref(), election).await?;
+        }
+        "plugins" => {
+            for plugin in list_plugins() {
+                println!("{plugin}");
+            }
+        }
+        other => match find_plugin(other) {
+            Some(path) => run_plugin(&path, &args[2..], election, yes)?,
+            None => {
+                return Err(CliError::config(format!("Unknown command: {other}")).into());
+            }
+        },
+    }
+
+    Ok(())
+}
+
+async fn init_force(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let admin_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &program.payer(),
+            &mint,
+            &token_program,
+        );
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::Initialize {
+        election_id: election,
+        init_vote_fee: parse_amount(INIT_VOTE_FEE, decimals)?,
+    };
+
+    let summary = format!(
+        "initialize VoteManager {vote_data_pda} of election {} with mint {mint}, token program \
+         {token_program} and a vote fee of {}, admin {}",
+        args.election_id,
+        format_amount(args.init_vote_fee, decimals),
+        program.payer()
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Initialize {
+            vote_data: vote_data_pda,
+            owner: program.payer(),
+            mint,
+            admin_token_account,
+            token_program,
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Transaction signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+async fn change_fee(
+    new_fee: &str,
+    election: u64,
+    idempotency_key: Option<&str>,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let decimals = mint_decimals(&TOKEN_MINT.parse()?).await?;
+    let args = governance::instruction::ChangeFee {
+        new_vote_fee: parse_amount(new_fee, decimals)?,
+    };
+
+    let operation = format!("change_fee:{}", args.new_vote_fee);
+    let Guard::Send(marker) =
+        guard_operation(&program, &vote_data_pda, idempotency_key, &operation).await?
+    else {
+        return Ok(());
+    };
+
+    let vote_manager: governance::VoteManager = clients().account(vote_data_pda).await?;
+    // A round that already opened keeps charging the fee it froze.
+    let applies = match vote_manager.round_state {
+        governance::RoundState::Pending => "when the current round opens",
+        _ => "from the next round",
+    };
+    let summary = format!(
+        "change the vote fee from {} to {} on VoteManager {vote_data_pda}, {applies}",
+        format_amount(vote_manager.vote_fee, decimals),
+        format_amount(args.new_vote_fee, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let mut request = program.request();
+    if let Some(marker) = marker {
+        request = request.instruction(marker);
+    }
+    let send_res = request
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_data_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Fee changed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Sets the lamports a vote paid in SOL costs; zero stops votes from being paid in SOL.
+async fn change_sol_fee(sol_vote_fee: u64, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::ChangeSolFee {
+        new_sol_vote_fee: sol_vote_fee,
+    };
+    let summary = match args.new_sol_vote_fee {
+        0 => format!("stop {vote_manager_pda} from accepting vote fees in SOL from the next round"),
+        fee => format!(
+            "charge votes of {vote_manager_pda} paid in SOL {fee} lamports from the next round"
+        ),
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! SOL vote fee changed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Rewrites a VoteManager created before round numbers were widened to `u64` in the current layout.
+async fn migrate_vote_manager(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::MigrateVoteManager {
+        _creator: program.payer(),
+    };
+    let summary = format!(
+        "migrate VoteManager {vote_data_pda} created by {} to the u64 round layout",
+        args._creator
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::MigrateVoteManager {
+            vote_manager: vote_data_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! VoteManager migrated. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Upgrades a VoteManager, ProjectData or VoterData account, the election's VoteManager by
+/// default, to the current layout version.
+async fn migrate_account(
+    address: Option<Pubkey>,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let address = address.unwrap_or_else(|| {
+        seeds::vote_manager_address(&program.payer(), election, &program.id()).0
+    });
+
+    let summary = format!("migrate account {address} to the current layout version");
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::MigrateAccount {
+            account: address,
+            payer: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(governance::instruction::MigrateAccount)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Account migrated. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+async fn get_round(election: u64) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let vote_manager: governance::VoteManager = clients().account(vote_data_pda).await?;
+    let current_round = vote_manager.vote_round;
+    let state = round_state_name(vote_manager.round_state);
+
+    println!("Current round: {current_round} ({state})");
+    match vote_manager.max_projects {
+        0 => println!("Projects: {}", vote_manager.project_count),
+        max => println!("Projects: {} of {max}", vote_manager.project_count),
+    }
+    if vote_manager.round_state != governance::RoundState::Pending {
+        let decimals = mint_decimals(&vote_manager.tk_mint).await?;
+        let round_config = vote_manager.round_config;
+        let deadline = round_config
+            .deadline
+            .map_or("none".to_string(), |deadline| deadline.to_string());
+        println!(
+            "Vote fee: {} (frozen when the round opened)",
+            format_amount(round_config.vote_fee, decimals)
+        );
+        if round_config.sol_vote_fee > 0 {
+            println!(
+                "Vote fee in SOL: {} lamports (frozen when the round opened)",
+                round_config.sol_vote_fee
+            );
+        }
+        println!("Deadline: {deadline}");
+    }
+    if vote_manager.fee_mode == governance::FeeMode::Burn {
+        println!("Vote fees are burned");
+    }
+
+    Ok(())
+}
+
+async fn increment_round(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let vote_manager: governance::VoteManager = clients().account(vote_data_pda).await?;
+    let summary = format!(
+        "increment the round from {} to {} on VoteManager {vote_data_pda}",
+        vote_manager.vote_round,
+        vote_manager.vote_round + 1
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::IncrementRound {
+            vote_data: vote_data_pda,
+            owner: program.payer(),
+            role: None,
+        })
+        .args(governance::instruction::IncrementRound)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Round incremented. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Opens the current round for voting, or closes it to new votes, depending on `state`.
+async fn set_round_state(
+    state: governance::RoundState,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let vote_manager: governance::VoteManager = clients().account(vote_data_pda).await?;
+    let (action, done, expected) = match state {
+        governance::RoundState::Open => ("open", "opened", governance::RoundState::Pending),
+        _ => ("close", "closed", governance::RoundState::Open),
+    };
+    if vote_manager.round_state != expected {
+        return Err(CliError::config(format!(
+            "Round {} is {}, only {} rounds can be {done}",
+            vote_manager.vote_round,
+            round_state_name(vote_manager.round_state),
+            round_state_name(expected)
+        ))
+        .into());
+    }
+
+    let summary = format!(
+        "{action} round {} on VoteManager {vote_data_pda}",
+        vote_manager.vote_round
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let request = program.request().accounts(governance::accounts::Admin {
+        vote_data: vote_data_pda,
+        owner: program.payer(),
+    });
+    let request = match state {
+        governance::RoundState::Open => request.args(governance::instruction::OpenRound),
+        _ => request.args(governance::instruction::CloseRound),
+    };
+    let send_res = request.signer(&*payer).send_via(clients()).await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Round {done}. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Voids the current round, refunding its voters, and moves on to the next round.
+async fn invalidate_round(reason: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let round = vote_manager.vote_round;
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let (round_archive_pda, _) =
+        seeds::round_archive_address(&vote_manager_pda, round, &program.id());
+
+    let args = governance::instruction::InvalidateRound {
+        reason: reason.to_string(),
+    };
+    let summary = format!(
+        "invalidate {} round {round} ({} votes) on VoteManager {vote_manager_pda}, refunding its \
+         voters, because: {}",
+        round_state_name(vote_manager.round_state),
+        vote_manager.round_total_votes,
+        args.reason
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::InvalidateRound {
+            round_result: round_result_pda,
+            round_archive: round_archive_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Round {round} invalidated. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Returns the lowercase name of a role, as printed by the CLI.
+fn role_name(role: governance::Role) -> &'static str {
+    match role {
+        governance::Role::Admin => "admin",
+        governance::Role::Operator => "operator",
+        governance::Role::Auditor => "auditor",
+    }
+}
+
+/// Returns the lowercase name of a weight mode, as spelled in election specs.
+fn weight_mode_name(mode: governance::WeightMode) -> &'static str {
+    match mode {
+        governance::WeightMode::Flat => "flat",
+        governance::WeightMode::Linear => "linear",
+        governance::WeightMode::Sqrt => "sqrt",
+    }
+}
+
+/// Returns the lowercase name of a round state, as printed by the CLI.
+fn round_state_name(state: governance::RoundState) -> &'static str {
+    match state {
+        governance::RoundState::Pending => "pending",
+        governance::RoundState::Open => "open",
+        governance::RoundState::Closed => "closed",
+        governance::RoundState::Finalized => "finalized",
+    }
+}
+
+/// Describes how a vote's `price` leaves `voter_ata` under the VoteManager's `fee_mode`.
+fn fee_charge(
+    fee_mode: governance::FeeMode,
+    price: &str,
+    voter_ata: &Pubkey,
+    treasury_token_account: &Pubkey,
+) -> String {
+    match fee_mode {
+        governance::FeeMode::Collect => {
+            format!("transfer {price} from {voter_ata} to {treasury_token_account}")
+        }
+        governance::FeeMode::Burn => format!("burn {price} from {voter_ata}"),
+    }
+}
+
+async fn add_project(
+    project_key: &str,
+    round: u64,
+    election: u64,
+    idempotency_key: Option<&str>,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let (project_data_pda, _project_bump) = seeds::project_address(
+        project_key,
+        round,
+        &program.payer(),
+        election,
+        &program.id(),
+    );
+
+    let operation = format!("add_project:{round}:{project_key}");
+    let Guard::Send(marker) =
+        guard_operation(&program, &vote_data_pda, idempotency_key, &operation).await?
+    else {
+        return Ok(());
+    };
+
+    let args = governance::instruction::AddProject {
+        id: project_key.to_owned(),
+    };
+    let summary = format!(
+        "add project '{}' to round {round} as account {project_data_pda}",
+        args.id
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let mut request = program.request();
+    if let Some(marker) = marker {
+        request = request.instruction(marker);
+    }
+    let send_res = request
+        .accounts(governance::accounts::NewVoteProject {
+            project_data: project_data_pda,
+            vote_manager: vote_data_pda,
+            owner: program.payer(),
+            role: None,
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Project added. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Adds the project ids listed in `projects_file`, a JSON array of strings, to `round`.
+///
+/// The ids are sent `PROJECT_BATCH_SIZE` per transaction, so a full round takes one or two. Under
+/// an idempotency key each batch is guarded on its own, so a re-run sends only the batches that
+/// did not land.
+async fn add_projects(
+    projects_file: &str,
+    round: u64,
+    election: u64,
+    idempotency_key: Option<&str>,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let ids: Vec<String> = serde_json::from_str(&fs::read_to_string(projects_file)?)
+        .map_err(|_| CliError::config("The projects file must be a JSON array of project ids"))?;
+    if ids.is_empty() {
+        return Err(CliError::config("The projects file lists no projects").into());
+    }
+
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_data_pda, _) = seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let batches: Vec<governance::instruction::AddProjects> = ids
+        .chunks(PROJECT_BATCH_SIZE)
+        .map(|batch| governance::instruction::AddProjects {
+            ids: batch.to_vec(),
+        })
+        .collect();
+    let summary = format!(
+        "add {} projects to round {round} in {} transaction(s): {}",
+        batches.iter().map(|args| args.ids.len()).sum::<usize>(),
+        batches.len(),
+        batches
+            .iter()
+            .map(|args| args.ids.join(", "))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    for args in batches {
+        let operation = format!("add_projects:{round}:{}", args.ids.join(","));
+        let Guard::Send(marker) =
+            guard_operation(&program, &vote_data_pda, idempotency_key, &operation).await?
+        else {
+            continue;
+        };
+
+        let project_accounts: Vec<AccountMeta> = args
+            .ids
+            .iter()
+            .map(|id| {
+                let (project_data_pda, _) =
+                    seeds::project_address(id, round, &program.payer(), election, &program.id());
+                AccountMeta::new(project_data_pda, false)
+            })
+            .collect();
+        let added = project_accounts.len();
+
+        let mut request = program.request();
+        if let Some(marker) = marker {
+            request = request.instruction(marker);
+        }
+        let send_res = request
+            .accounts(governance::accounts::NewVoteProjects {
+                vote_manager: vote_data_pda,
+                owner: program.payer(),
+                role: None,
+                system_program: system_program::ID,
+            })
+            .accounts(project_accounts)
+            .args(args)
+            .signer(&*payer)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!("Success! {added} projects added. Tx signature: {sig}"),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Submits `project_key` for the current round with the voter's keypair, escrowing the deposit.
+async fn submit_project(project_key: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let admin = get_keypair(ADMIN_SECRET)?.pubkey();
+    let keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) = seeds::vote_manager_address(&admin, election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let round = vote_manager.vote_round;
+
+    let (project_data_pda, _) =
+        seeds::project_address(project_key, round, &admin, election, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let submitter_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &program.payer(),
+            &mint,
+            &token_program,
+        );
+
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::SubmitProject {
+        id: project_key.to_owned(),
+    };
+    let summary = format!(
+        "submit project '{}' to round {round} as account {project_data_pda}, escrowing {}",
+        args.id,
+        format_amount(vote_manager.project_deposit, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::SubmitProject {
+            project_data: project_data_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            submitter_token_account,
+            vote_manager: vote_manager_pda,
+            mint,
+            submitter: program.payer(),
+            token_program,
+            system_program: system_program::ID,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &program.payer()).await?)
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Project submitted. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Approves the submitted project `project_key` of `round`, refunding its deposit.
+async fn approve_project(
+    project_key: &str,
+    round: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (project_pda, _) = seeds::project_address(
+        project_key,
+        round,
+        &program.payer(),
+        election,
+        &program.id(),
+    );
+    let project: governance::ProjectData = clients().versioned_account(project_pda).await?;
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let submitter_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &project.payer,
+            &mint,
+            &token_program,
+        );
+
+    let decimals = mint_decimals(&mint).await?;
+    let deposit = format_amount(project.deposit, decimals);
+    let summary = if project.refund_votes == 0 {
+        format!(
+            "approve project '{project_key}' of round {round}, refunding {deposit} to {}",
+            project.payer
+        )
+    } else {
+        format!(
+            "approve project '{project_key}' of round {round}, keeping {deposit} as a listing \
+             bond refunded every {} votes until {} votes",
+            project.tranche_votes, project.refund_votes
+        )
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ApproveProject {
+            project: project_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            submitter_token_account,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: program.payer(),
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?)
+        .args(governance::instruction::ApproveProject {})
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Project approved. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Rejects the submitted project `project_key` of `round`, refunding or slashing its deposit.
+async fn reject_project(
+    project_key: &str,
+    round: u64,
+    slash: bool,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (project_pda, _) = seeds::project_address(
+        project_key,
+        round,
+        &program.payer(),
+        election,
+        &program.id(),
+    );
+    let project: governance::ProjectData = clients().versioned_account(project_pda).await?;
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let submitter_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &project.payer,
+            &mint,
+            &token_program,
+        );
+
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::RejectProject { slash };
+    let action = if args.slash { "slashing" } else { "refunding" };
+    let summary = format!(
+        "reject project '{project_key}' of round {round}, {action} its {} deposit",
+        format_amount(project.deposit, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::RejectProject {
+            project: project_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            submitter_token_account,
+            vote_manager: vote_manager_pda,
+            mint,
+            payer: project.payer,
+            owner: program.payer(),
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?)
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Project rejected. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Claims the refund tranches of `project_key`'s listing bond with the voter's keypair, as its
+/// submitter.
+async fn claim_deposit_tranche(
+    project_key: &str,
+    round: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let admin = get_keypair(ADMIN_SECRET)?.pubkey();
+    let keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) = seeds::vote_manager_address(&admin, election, &program.id());
+    let (project_pda, _) =
+        seeds::project_address(project_key, round, &admin, election, &program.id());
+    let project: governance::ProjectData = clients().versioned_account(project_pda).await?;
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let submitter_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &program.payer(),
+            &mint,
+            &token_program,
+        );
+
+    let claimable = project.claimable_deposit();
+    if claimable == 0 {
+        return Err(CliError::config(format!(
+            "Project '{project_key}' has not earned a new deposit tranche with {} votes",
+            project.vote_count
+        ))
+        .into());
+    }
+    let decimals = mint_decimals(&mint).await?;
+    let summary = format!(
+        "claim {} of the listing bond of project '{project_key}', leaving {} escrowed",
+        format_amount(claimable, decimals),
+        format_amount(project.deposit - claimable, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ClaimDepositTranche {
+            project: project_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            submitter_token_account,
+            vote_manager: vote_manager_pda,
+            mint,
+            submitter: program.payer(),
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?)
+        .args(governance::instruction::ClaimDepositTranche {})
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Deposit tranche claimed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Sets the display name, description URI and category of `project_key` in `round`.
+async fn update_project_metadata(
+    project_key: &str,
+    round: u64,
+    name: &str,
+    uri: &str,
+    category: u8,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    if name.len() > governance::PROJECT_NAME_MAX_LEN || uri.len() > governance::PROJECT_URI_MAX_LEN
+    {
+        return Err(CliError::config(format!(
+            "The name is limited to {} bytes and the URI to {}",
+            governance::PROJECT_NAME_MAX_LEN,
+            governance::PROJECT_URI_MAX_LEN
+        ))
+        .into());
+    }
+
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (project_pda, _) = seeds::project_address(
+        project_key,
+        round,
+        &program.payer(),
+        election,
+        &program.id(),
+    );
+
+    let args = governance::instruction::UpdateProjectMetadata {
+        name: name.to_owned(),
+        uri: uri.to_owned(),
+        category,
+    };
+    let summary = format!(
+        "set the metadata of project '{project_key}' of round {round}: name '{}', uri {}, \
+         category {}",
+        args.name, args.uri, args.category
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::UpdateProjectMetadata {
+            project: project_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Project metadata updated. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+async fn do_vote(
+    project_key: &str,
+    round: u64,
+    sponsored: bool,
+    snapshot_file: Option<&str>,
+    fee_currency: governance::FeeCurrency,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let pay_in_sol = fee_currency == governance::FeeCurrency::Sol;
+    if sponsored && pay_in_sol {
+        return Err(CliError::config("Sponsored votes are paid in TTT").into());
+    }
+
+    // Only the voter's key is loaded: top-ups are signed by the VoteManager PDA.
+    let mint = "GgQuhpBUxy7LaD56c2vbxk5hSgoBuNwxxev6U9iqyMXZ".parse::<Pubkey>()?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(vouter.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&governance::ADMIN_PUBKEY, election, &program.id());
+
+    let (voter_data_pda, _) = seeds::voter_address(
+        election,
+        round,
+        &vouter.pubkey(),
+        project_key,
+        &program.id(),
+    );
+
+    let (project_data_pda, _project_bump) = seeds::project_address(
+        project_key,
+        round,
+        &governance::ADMIN_PUBKEY,
+        election,
+        &program.id(),
+    );
+
+    let top_up_account = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vote_manager_pda,
+        &mint,
+        &TOKEN_PROGRAM.parse::<Pubkey>()?,
+    );
+
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &TOKEN_PROGRAM.parse::<Pubkey>()?,
+        );
+
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &TOKEN_PROGRAM.parse::<Pubkey>()?,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    // The price grows with the voter's prior votes on the project under quadratic pricing.
+    let prior_votes = clients()
+        .versioned_account::<governance::VoterData>(voter_data_pda)
+        .await
+        .map_or(0, |voter_data| voter_data.votes_for_project);
+    let mut vote_fee = vote_manager.vote_price_in(fee_currency, prior_votes)?;
+    // A direct vote carries the fee discount schedule, if any, quoting the discount of the
+    // voter's current balance; topping up only raises the balance, and so the discount.
+    let (fee_discounts_pda, _) = seeds::fee_discount_address(&vote_manager_pda, &program.id());
+    let fee_discounts = match clients()
+        .account::<governance::FeeDiscountSchedule>(fee_discounts_pda)
+        .await
+    {
+        Ok(schedule) if !sponsored => {
+            let balance = clients()
+                .rpc()
+                .get_token_account_balance(&vouter_ata)
+                .await
+                .map_or(Ok(0), |balance| balance.amount.parse())?;
+            vote_fee = schedule.discounted(vote_fee, balance);
+            Some(fee_discounts_pda)
+        }
+        _ => None,
+    };
+    if pay_in_sol && vote_manager.round_config.sol_vote_fee == 0 {
+        return Err(CliError::config("The round does not accept vote fees in SOL").into());
+    }
+
+    // While a snapshot is set, the vote carries the proof of the voter's entry in it.
+    let proof = match snapshot_file {
+        Some(_) if sponsored => {
+            return Err(CliError::config("Sponsored votes cannot carry a snapshot proof").into());
+        }
+        Some(snapshot_file) => Some(snapshot_proof(
+            snapshot_file,
+            &vote_manager,
+            &vouter.pubkey(),
+        )?),
+        None if vote_manager.eligibility_snapshot() => {
+            return Err(CliError::config(
+                "Votes are restricted to an eligibility snapshot; pass the snapshot file",
+            )
+            .into());
+        }
+        None => None,
+    };
+    // Under a holding requirement, the vote carries the voter's HoldingAttestation.
+    if sponsored && vote_manager.holding_requirement() {
+        return Err(CliError::config(
+            "Sponsored votes are disabled while a holding requirement is set",
+        )
+        .into());
+    }
+    let holding_attestation = vote_manager.holding_requirement().then(|| {
+        seeds::holding_attestation_address(&vote_manager_pda, &vouter.pubkey(), &program.id()).0
+    });
+    // Under an access list, the vote carries the address of the voter's entry, listed or not.
+    if sponsored && vote_manager.access_mode != governance::AccessMode::Open {
+        return Err(
+            CliError::config("Sponsored votes are disabled while an access list is set").into(),
+        );
+    }
+    let access_entry = match vote_manager.access_mode {
+        governance::AccessMode::Open => None,
+        governance::AccessMode::Allowlist => Some(
+            seeds::allowlist_entry_address(&vote_manager_pda, &vouter.pubkey(), &program.id()).0,
+        ),
+        governance::AccessMode::Blocklist => Some(
+            seeds::blocklist_entry_address(&vote_manager_pda, &vouter.pubkey(), &program.id()).0,
+        ),
+    };
+
+    let (sponsor_vault_pda, _) = seeds::sponsor_vault_address(&vote_manager_pda, &program.id());
+    let (sponsor_record_pda, _) =
+        seeds::sponsor_record_address(&vote_manager_pda, &vouter.pubkey(), &program.id());
+    let (vote_lock_pda, _) =
+        seeds::vote_lock_address(&vote_manager_pda, &vouter.pubkey(), &program.id());
+    let (round_votes_pda, _) = seeds::round_votes_address(
+        &vote_manager_pda,
+        round,
+        vouter.pubkey().as_ref(),
+        &program.id(),
+    );
+    // Once the round has a leaderboard, every vote updates it.
+    let leaderboard = vote_manager
+        .round_leaderboard
+        .then(|| seeds::leaderboard_address(&vote_manager_pda, round, &program.id()).0);
+
+    println!("Mint Pubkey: {}", mint);
+    println!("Top-up Pool: {}", top_up_account);
+    println!("Treasury Token Account: {}", treasury_token_account);
+    println!("Vouter ATA: {}", vouter_ata);
+
+    // Direct votes mint a receipt while receipts are enabled; sponsored votes never do.
+    let receipt = !sponsored && vote_manager.vote_receipts;
+
+    let top_up_args = if pay_in_sol {
+        governance::TopUpArgs::new(0, governance::TopUpReason::AccountOnly)
+    } else if sponsored {
+        governance::TopUpArgs::new(vote_fee, governance::TopUpReason::SponsoredVote)
+    } else {
+        governance::TopUpArgs::new(vote_fee, governance::TopUpReason::Vote)
+    };
+    let vote_args = governance::instruction::DoVote {
+        proof,
+        fee_currency,
+    };
+
+    let (top_up_note, charge) = if vote_args.fee_currency == governance::FeeCurrency::Sol {
+        (
+            format!("create the token account {vouter_ata} if needed"),
+            format!("transfer {vote_fee} lamports to {treasury_pda}"),
+        )
+    } else {
+        let price = format_amount(top_up_args.amount, mint_decimals(&mint).await?);
+        (
+            format!(
+                "top up {} to at least {price} from {top_up_account} if needed",
+                vouter.pubkey()
+            ),
+            fee_charge(
+                vote_manager.fee_mode,
+                &price,
+                &vouter_ata,
+                &treasury_token_account,
+            ),
+        )
+    };
+    let summary = format!(
+        "in one transaction, {top_up_note}, then {charge} and increment votes on project \
+         '{project_key}' round {round}{}",
+        if sponsored {
+            format!(", reimbursing the rent from {sponsor_vault_pda}")
+        } else if receipt {
+            format!(", minting a vote receipt of {}", vote_manager.receipt_mint)
+        } else {
+            String::new()
+        }
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    // The top-up goes into the vote's transaction, so the vote never reads a token account or
+    // balance the RPC node does not show yet, and a failed vote leaves no top-up behind. A vote
+    // paid in SOL tops up nothing, the instruction only creates the token account the vote reads.
+    let top_up = program
+        .request()
+        .accounts(governance::accounts::EnsureCanVote {
+            signer: vouter.pubkey(),
+            vote_manager: vote_manager_pda,
+            top_up_account,
+            mint,
+            user_ata: vouter_ata,
+            instructions: sysvar::instructions::ID,
+            voter_data: voter_data_pda,
+            round_votes: round_votes_pda,
+            token_program: TOKEN_PROGRAM.parse::<Pubkey>()?,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
+            system_program: system_program::ID,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &vote_manager_pda).await?)
+        .args(governance::instruction::EnsureUserCanVote { args: top_up_args })
+        .instructions()?;
+
+    // In escrow mode the voter's StakePosition weighs the vote, ahead of the receipt accounts.
+    let mut remaining_accounts = Vec::new();
+    if vote_manager.escrow_mode {
+        let (stake_position_pda, _) =
+            seeds::stake_position_address(&vote_manager_pda, &vouter.pubkey(), &program.id());
+        remaining_accounts.push(AccountMeta::new_readonly(stake_position_pda, false));
+    }
+    if receipt {
+        remaining_accounts.extend(receipt_accounts(
+            &vote_manager_pda,
+            &vote_manager,
+            &vouter.pubkey(),
+            &program.id(),
+        )?);
+    }
+    remaining_accounts
+        .extend(transfer_hook_accounts(&mint, &vote_manager_pda, &vouter.pubkey()).await?);
+
+    // Attributes the fee transfer of the vote for bookkeeping tools reading token transfers only.
+    let memo = FeeMemo {
+        round,
+        voter: vouter.pubkey(),
+        project_id: project_key.to_owned(),
+    }
+    .instruction()?;
+
+    // The top-up only funds the instruction right after it, so the memo goes first.
+    let request = top_up
+        .into_iter()
+        .fold(program.request().instruction(memo), |request, ix| {
+            request.instruction(ix)
+        });
+    let request = if sponsored {
+        request
+            .accounts(governance::accounts::SponsoredVoter {
+                voter_data: voter_data_pda,
+                sponsor_vault: sponsor_vault_pda,
+                sponsor_record: sponsor_record_pda,
+                signer: vouter.pubkey(),
+                vote_manager: vote_manager_pda,
+                treasury: treasury_pda,
+                treasury_token_account,
+                project: project_data_pda,
+                mint,
+                token: vouter_ata,
+                token_program: TOKEN_PROGRAM.parse::<Pubkey>()?,
+                system_program: system_program::ID,
+                leaderboard,
+                round_votes: round_votes_pda,
+            })
+            .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &vouter.pubkey()).await?)
+            .args(governance::instruction::SponsoredVote)
+    } else {
+        request
+            .accounts(governance::accounts::Voter {
+                voter_data: voter_data_pda,
+                signer: vouter.pubkey(),
+                vote_manager: vote_manager_pda,
+                treasury: treasury_pda,
+                treasury_token_account,
+                project: project_data_pda,
+                mint,
+                token: vouter_ata,
+                token_program: TOKEN_PROGRAM.parse::<Pubkey>()?,
+                system_program: system_program::ID,
+                leaderboard,
+                holding_attestation,
+                access_entry,
+                fee_discounts,
+                vote_lock: vote_lock_pda,
+                round_votes: round_votes_pda,
+            })
+            .accounts(remaining_accounts)
+            .args(vote_args)
+    };
+    let send_res = request.signer(&*vouter).send_via(clients()).await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Vote casted. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Lets the VoteManager PDA spend up to `amount` of the voter's TTT on relayed votes, by
+/// approving it as the delegate of the voter's token account.
+///
+/// The admin pays the transaction, so the voter needs no SOL.
+async fn approve_votes(amount: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let decimals = mint_decimals(&mint).await?;
+    let amount = parse_amount(amount, decimals)?;
+    let summary = format!(
+        "let {vote_manager_pda} spend up to {} of {vouter_ata} on relayed votes",
+        format_amount(amount, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    // `ApproveChecked`: the instruction tag, the amount and the mint's decimals.
+    let mut data = vec![13];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    let approve = Instruction {
+        program_id: token_program,
+        accounts: vec![
+            AccountMeta::new(vouter_ata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(vote_manager_pda, false),
+            AccountMeta::new_readonly(vouter.pubkey(), true),
+        ],
+        data,
+    };
+
+    let send_res = program
+        .request()
+        .instruction(approve)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Vote allowance approved. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Signs an authorization for a relayer to vote for `project_key` on the voter's behalf, and
+/// writes it to `authorization_file` for `relay_vote`.
+///
+/// Nothing is sent: the authorization carries the voter's next nonce, so it is only valid until
+/// the voter's next relayed vote.
+async fn authorize_vote(
+    project_key: &str,
+    round: u64,
+    authorization_file: &str,
+    election: u64,
+) -> Result<(), Box<dyn Error>> {
+    let vouter = get_keypair(VOUTER_SECRET)?;
+    let program_id = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&governance::ADMIN_PUBKEY, election, &program_id);
+    let (project_pda, _) = seeds::project_address(
+        project_key,
+        round,
+        &governance::ADMIN_PUBKEY,
+        election,
+        &program_id,
+    );
+    let (vote_nonce_pda, _) =
+        seeds::vote_nonce_address(&vote_manager_pda, &vouter.pubkey(), &program_id);
+    let nonce = clients()
+        .account::<governance::VoteNonce>(vote_nonce_pda)
+        .await
+        .map_or(0, |vote_nonce| vote_nonce.next_nonce);
+
+    let message =
+        governance::vote_authorization_message(&vouter.pubkey(), &project_pda, round, nonce);
+    let signature = vouter.sign_message(&message);
+    fs::write(
+        authorization_file,
+        serde_json::to_string_pretty(&serde_json::json!({
+            "election": election,
+            "voter": vouter.pubkey().to_string(),
+            "project": project_key,
+            "round": round,
+            "nonce": nonce,
+            "signature": signature.to_string(),
+        }))?,
+    )?;
+    println!(
+        "Vote for '{project_key}' round {round} authorized with nonce {nonce}, written to \
+         {authorization_file}"
+    );
+
+    Ok(())
+}
+
+/// Sends the vote authorized in `authorization_file`, written by `authorize_vote`, paying its
+/// transaction and rent with the admin key.
+async fn relay_vote(
+    authorization_file: &str,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let authorization: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(authorization_file)?)?;
+    let field = |name: &str| {
+        authorization[name]
+            .as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| CliError::config(format!("The authorization has no `{name}` string")))
+    };
+    let number = |name: &str| {
+        authorization[name]
+            .as_u64()
+            .ok_or_else(|| CliError::config(format!("The authorization has no `{name}` number")))
+    };
+    if number("election")? != election {
+        return Err(CliError::config("The authorization is for another election").into());
+    }
+    let voter = field("voter")?.parse::<Pubkey>()?;
+    let project_key = field("project")?;
+    let round = number("round")?;
+    let nonce = number("nonce")?;
+    let signature = field("signature")?.parse::<Signature>()?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (project_pda, _) = seeds::project_address(
+        &project_key,
+        round,
+        &program.payer(),
+        election,
+        &program.id(),
+    );
+    let (voter_data_pda, _) =
+        seeds::voter_address(election, round, &voter, &project_key, &program.id());
+    let (vote_nonce_pda, _) = seeds::vote_nonce_address(&vote_manager_pda, &voter, &program.id());
+    let (round_votes_pda, _) =
+        seeds::round_votes_address(&vote_manager_pda, round, voter.as_ref(), &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let voter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &voter,
+        &mint,
+        &token_program,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let leaderboard = vote_manager
+        .round_leaderboard
+        .then(|| seeds::leaderboard_address(&vote_manager_pda, round, &program.id()).0);
+
+    let message = governance::vote_authorization_message(&voter, &project_pda, round, nonce);
+    if !signature.verify(voter.as_ref(), &message) {
+        return Err(CliError::config("The authorization signature does not verify").into());
+    }
+
+    let args = governance::instruction::VoteWithAuthorization { nonce };
+    let summary = format!(
+        "relay the vote of {voter} for '{project_key}' round {round} (nonce {}), paying its fees \
+         from {}",
+        args.nonce,
+        program.payer()
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .instruction(ed25519_instruction(&voter, signature.as_ref(), &message))
+        .accounts(governance::accounts::AuthorizedVoter {
+            voter_data: voter_data_pda,
+            vote_nonce: vote_nonce_pda,
+            voter,
+            relayer: program.payer(),
+            vote_manager: vote_manager_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            project: project_pda,
+            mint,
+            token: voter_ata,
+            instructions: sysvar::instructions::ID,
+            token_program,
+            system_program: system_program::ID,
+            leaderboard,
+            round_votes: round_votes_pda,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &voter).await?)
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Vote relayed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Sets up compressed votes: allocates a concurrent Merkle tree of `max_depth` and
+/// `max_buffer_size` for the compression program and initializes it under the VoteTree PDA.
+async fn init_vote_tree(
+    max_depth: u32,
+    max_buffer_size: u32,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (vote_tree_pda, _) = seeds::vote_tree_address(&vote_manager_pda, &program.id());
+    let merkle_tree = Keypair::new();
+    let space = merkle_tree_space(max_depth, max_buffer_size);
+    let lamports = clients()
+        .rpc()
+        .get_minimum_balance_for_rent_exemption(space)
+        .await?;
+
+    let args = governance::instruction::InitVoteTree {
+        max_depth,
+        max_buffer_size,
+    };
+    let summary = format!(
+        "create the vote tree {vote_tree_pda} with a Merkle tree {} of depth {} and buffer {}, \
+         holding up to {} votes for {lamports} lamports of rent",
+        merkle_tree.pubkey(),
+        args.max_depth,
+        args.max_buffer_size,
+        1u64 << args.max_depth.min(63)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .instruction(system_instruction::create_account(
+            &program.payer(),
+            &merkle_tree.pubkey(),
+            lamports,
+            space as u64,
+            &governance::ACCOUNT_COMPRESSION_ID,
+        ))
+        .accounts(governance::accounts::InitVoteTree {
+            vote_tree: vote_tree_pda,
+            merkle_tree: merkle_tree.pubkey(),
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            compression_program: governance::ACCOUNT_COMPRESSION_ID,
+            noop_program: governance::NOOP_PROGRAM_ID,
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&merkle_tree)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Vote tree created. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Returns the size of an spl-account-compression tree account of `max_depth` and
+/// `max_buffer_size` without a canopy: its header, then the tree's counters, changelog buffer
+/// and rightmost path.
+fn merkle_tree_space(max_depth: u32, max_buffer_size: u32) -> usize {
+    let path = 40 + 32 * max_depth as usize;
+    56 + 24 + max_buffer_size as usize * path + path
+}
+
+/// Casts a vote recorded as a leaf of the VoteManager's Merkle tree, from the voter's TTT
+/// balance.
+async fn compressed_vote(
+    project_key: &str,
+    round: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(vouter.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&governance::ADMIN_PUBKEY, election, &program.id());
+    let (project_pda, _) = seeds::project_address(
+        project_key,
+        round,
+        &governance::ADMIN_PUBKEY,
+        election,
+        &program.id(),
+    );
+    let (vote_tree_pda, _) = seeds::vote_tree_address(&vote_manager_pda, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let vote_tree: governance::VoteTree = clients().account(vote_tree_pda).await?;
+    let leaderboard = vote_manager
+        .round_leaderboard
+        .then(|| seeds::leaderboard_address(&vote_manager_pda, round, &program.id()).0);
+
+    let price = format_amount(vote_manager.vote_price(0)?, mint_decimals(&mint).await?);
+    let summary = format!(
+        "{} and append the vote for project '{project_key}' round {round} to the Merkle tree {}",
+        fee_charge(
+            vote_manager.fee_mode,
+            &price,
+            &vouter_ata,
+            &treasury_token_account,
+        ),
+        vote_tree.merkle_tree
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::CompressedVoter {
+            vote_tree: vote_tree_pda,
+            merkle_tree: vote_tree.merkle_tree,
+            signer: vouter.pubkey(),
+            vote_manager: vote_manager_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            project: project_pda,
+            mint,
+            token: vouter_ata,
+            token_program,
+            compression_program: governance::ACCOUNT_COMPRESSION_ID,
+            noop_program: governance::NOOP_PROGRAM_ID,
+            leaderboard,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &vouter.pubkey()).await?)
+        .args(governance::instruction::CompressedVote)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Compressed vote casted. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Claims back the fees the voter paid for a disqualified project or in an invalidated round.
+async fn claim_refund(
+    project_key: &str,
+    round: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (project_data_pda, _) = seeds::project_address(
+        project_key,
+        round,
+        &program.payer(),
+        election,
+        &program.id(),
+    );
+    let (voter_data_pda, _) = seeds::voter_address(
+        election,
+        round,
+        &vouter.pubkey(),
+        project_key,
+        &program.id(),
+    );
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    // Projects of an invalidated round are refunded against the round's RoundResult.
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let round_result = clients()
+        .account::<governance::RoundResult>(round_result_pda)
+        .await
+        .ok()
+        .filter(|result| result.invalidated)
+        .map(|_| round_result_pda);
+
+    let voter_data: governance::VoterData = clients().versioned_account(voter_data_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let summary = format!(
+        "claim a refund of {} for project '{project_key}' of round {round} to {vouter_ata}",
+        format_amount(voter_data.fees_paid, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ClaimRefund {
+            voter_data: voter_data_pda,
+            signer: vouter.pubkey(),
+            project: project_data_pda,
+            round_result,
+            vote_manager: vote_manager_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            mint,
+            token: vouter_ata,
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?)
+        .args(governance::instruction::ClaimRefund)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Refund claimed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Claims the voter's share of a finalized round's fees for backing its winner.
+async fn claim_reward(round: u64, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let round_result: governance::RoundResult = clients().account(round_result_pda).await?;
+    if round_result.winner == Pubkey::default() {
+        return Err(CliError::config(format!("Round {round} has no winner to reward")).into());
+    }
+    let (voter_data_pda, _) = seeds::voter_address(
+        election,
+        round,
+        &vouter.pubkey(),
+        &round_result.winner_id,
+        &program.id(),
+    );
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let voter_data: governance::VoterData = clients().versioned_account(voter_data_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let summary = format!(
+        "claim a reward of {} for backing '{}' in round {round} to {vouter_ata}",
+        format_amount(voter_data.reward_due(&round_result), decimals),
+        round_result.winner_id
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ClaimReward {
+            voter_data: voter_data_pda,
+            signer: vouter.pubkey(),
+            round_result: round_result_pda,
+            vote_manager: vote_manager_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            mint,
+            token: vouter_ata,
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?)
+        .args(governance::instruction::ClaimReward)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Reward claimed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Claims the voter's share of a finalized round's fees for their compressed votes for its
+/// winner, proving each vote against the tree rebuilt from the program's events.
+async fn claim_compressed_reward(
+    round: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let round_result: governance::RoundResult = clients().account(round_result_pda).await?;
+    if round_result.winner == Pubkey::default() {
+        return Err(CliError::config(format!("Round {round} has no winner to reward")).into());
+    }
+    let (vote_tree_pda, _) = seeds::vote_tree_address(&vote_manager_pda, &program.id());
+    let vote_tree: governance::VoteTree = clients().account(vote_tree_pda).await?;
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let mut leaves = vote_tree_leaves(&program.id(), &vote_manager_pda, &vote_tree).await?;
+    let claims: Vec<(usize, governance::CompressedVoteRecord)> = leaves
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (_, vote))| {
+            let vote = (*vote)?;
+            (vote.voter == vouter.pubkey()
+                && vote.round == round
+                && vote.project == round_result.winner
+                && !vote.reward_claimed)
+                .then_some((index, vote))
+        })
+        .collect();
+    if claims.is_empty() {
+        return Err(CliError::config(format!(
+            "No unclaimed compressed vote for '{}' in round {round}",
+            round_result.winner_id
+        ))
+        .into());
+    }
+
+    let decimals = mint_decimals(&mint).await?;
+    let total: u64 = claims
+        .iter()
+        .map(|(_, vote)| round_result.reward_share(vote.weight))
+        .sum();
+    let summary = format!(
+        "claim a reward of {} for {} compressed vote(s) backing '{}' in round {round} to \
+         {vouter_ata}",
+        format_amount(total, decimals),
+        claims.len(),
+        round_result.winner_id
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let hook_accounts = transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?;
+    // Each claim replaces its leaf, so the next proof is computed on the updated tree.
+    for (index, vote) in claims {
+        let nodes: Vec<[u8; 32]> = leaves.iter().map(|(leaf, _)| *leaf).collect();
+        let (root, proof) = governance::vote_tree_proof(&nodes, vote_tree.max_depth, index);
+        let send_res = program
+            .request()
+            .accounts(governance::accounts::ClaimCompressedReward {
+                vote_tree: vote_tree_pda,
+                merkle_tree: vote_tree.merkle_tree,
+                signer: vouter.pubkey(),
+                round_result: round_result_pda,
+                vote_manager: vote_manager_pda,
+                treasury: treasury_pda,
+                treasury_token_account,
+                mint,
+                token: vouter_ata,
+                token_program,
+                compression_program: governance::ACCOUNT_COMPRESSION_ID,
+                noop_program: governance::NOOP_PROGRAM_ID,
+            })
+            .accounts(
+                proof
+                    .iter()
+                    .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false))
+                    .collect::<Vec<_>>(),
+            )
+            .accounts(hook_accounts.clone())
+            .args(governance::instruction::ClaimCompressedReward {
+                vote,
+                root,
+                index: index as u32,
+            })
+            .signer(&*vouter)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!("Success! Reward claimed for leaf {index}. Tx signature: {sig}"),
+            Err(e) => return Err(e.into()),
+        }
+        let claimed = governance::CompressedVoteRecord {
+            reward_claimed: true,
+            ..vote
+        };
+        leaves[index] = (claimed.leaf(&vote_manager_pda), Some(claimed));
+    }
+
+    Ok(())
+}
+
+/// Retracts the voter's votes for a project of the open round, refunding their fees when the
+/// VoteManager refunds retractions.
+async fn retract_vote(
+    project_key: &str,
+    round: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (project_data_pda, _) = seeds::project_address(
+        project_key,
+        round,
+        &program.payer(),
+        election,
+        &program.id(),
+    );
+    let (voter_data_pda, _) = seeds::voter_address(
+        election,
+        round,
+        &vouter.pubkey(),
+        project_key,
+        &program.id(),
+    );
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let voter_data: governance::VoterData = clients().versioned_account(voter_data_pda).await?;
+    if voter_data.retracted != 0 {
+        println!("Votes for project '{project_key}' of round {round} were already retracted.");
+        return Ok(());
+    }
+    let fees = if vote_manager.retract_refund {
+        let decimals = mint_decimals(&mint).await?;
+        format!(
+            "refunding {} to {vouter_ata}",
+            format_amount(voter_data.fees_paid, decimals)
+        )
+    } else {
+        "leaving their fees in the treasury".to_owned()
+    };
+    let summary = format!(
+        "retract {} vote(s) for project '{project_key}' of round {round}, {fees}",
+        voter_data.votes_for_project
+    );
+    let leaderboard = vote_manager
+        .round_leaderboard
+        .then(|| seeds::leaderboard_address(&vote_manager_pda, round, &program.id()).0);
+    let (round_votes_pda, _) = seeds::round_votes_address(
+        &vote_manager_pda,
+        round,
+        vouter.pubkey().as_ref(),
+        &program.id(),
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::RetractVote {
+            voter_data: voter_data_pda,
+            signer: vouter.pubkey(),
+            project: project_data_pda,
+            vote_manager: vote_manager_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            mint,
+            token: vouter_ata,
+            token_program,
+            leaderboard,
+            round_votes: round_votes_pda,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?)
+        .args(governance::instruction::RetractVote)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Votes retracted. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Creates the leaderboard of the pending round, which every vote of the round then updates.
+async fn init_leaderboard(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let round = vote_manager.vote_round;
+    let (leaderboard_pda, _) = seeds::leaderboard_address(&vote_manager_pda, round, &program.id());
+
+    let summary = format!("create leaderboard {leaderboard_pda} for round {round}");
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::InitLeaderboard {
+            leaderboard: leaderboard_pda,
+            vote_manager: vote_manager_pda,
+            signer: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(governance::instruction::InitLeaderboard)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Leaderboard created. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Prints the standings of a round, the current one by default, from its leaderboard.
+async fn leaderboard(round: Option<u64>, election: u64) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let round = match round {
+        Some(round) => round,
+        None => {
+            let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+            vote_manager.vote_round
+        }
+    };
+    let (leaderboard_pda, _) = seeds::leaderboard_address(&vote_manager_pda, round, &program.id());
+
+    // Zero-copy accounts are not Borsh-encoded; the data after the discriminator is the struct.
+    let data = clients().rpc().get_account_data(&leaderboard_pda).await?;
+    let leaderboard = data
+        .strip_prefix(&governance::Leaderboard::DISCRIMINATOR[..])
+        .and_then(|data| data.get(..std::mem::size_of::<governance::Leaderboard>()))
+        .map(bytemuck::pod_read_unaligned::<governance::Leaderboard>)
+        .ok_or_else(|| CliError::config(format!("{leaderboard_pda} is not a leaderboard")))?;
+
+    println!("Leaderboard {leaderboard_pda} of round {round}:");
+    for (rank, entry) in leaderboard.entries[..usize::from(leaderboard.len)]
+        .iter()
+        .enumerate()
+    {
+        println!(
+            "  {}. {}: {} votes, weight {}",
+            rank + 1,
+            entry.id(),
+            entry.votes,
+            entry.weight
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the remaining accounts of a `do_vote` minting a receipt to `voter`, in the order the
+/// program reads them.
+fn receipt_accounts(
+    vote_manager_pda: &Pubkey,
+    vote_manager: &governance::VoteManager,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<AccountMeta>, Box<dyn Error>> {
+    let (receipt_authority, _) = seeds::receipt_authority_address(vote_manager_pda, program_id);
+    let receipt_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            voter,
+            &vote_manager.receipt_mint,
+            &anchor_spl::token_2022::ID,
+        );
+    Ok(vec![
+        AccountMeta::new(vote_manager.receipt_mint, false),
+        AccountMeta::new_readonly(receipt_authority, false),
+        AccountMeta::new(receipt_token_account, false),
+        AccountMeta::new_readonly(anchor_spl::token_2022::ID, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM.parse()?, false),
+        AccountMeta::new_readonly(TTT_TOKEN_PROGRAM_ID.parse()?, false),
+    ])
+}
+
+/// Returns the accounts the TTT transfer hook reads for a transfer out of `owner`'s account, none
+/// while the mint has no hook program. They go after an instruction's other remaining accounts.
+async fn transfer_hook_accounts(
+    mint: &Pubkey,
+    vote_manager_pda: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Vec<AccountMeta>, Box<dyn Error>> {
+    use anchor_spl::token_2022::spl_token_2022::{
+        extension::{transfer_hook, StateWithExtensions},
+        state::Mint,
+    };
+
+    let data = clients().rpc().get_account_data(mint).await?;
+    let Some(hook_program) =
+        transfer_hook::get_program_id(&StateWithExtensions::<Mint>::unpack(&data)?)
+    else {
+        return Ok(Vec::new());
+    };
+    let governance_program = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let (meta_list, _) =
+        Pubkey::find_program_address(&[b"extra-account-metas", mint.as_ref()], &hook_program);
+    let (vote_lock, _) = seeds::vote_lock_address(vote_manager_pda, owner, &governance_program);
+    // The hook program comes first: governance splits positional accounts off at it.
+    Ok(vec![
+        AccountMeta::new_readonly(hook_program, false),
+        AccountMeta::new_readonly(meta_list, false),
+        AccountMeta::new_readonly(governance_program, false),
+        AccountMeta::new_readonly(*vote_manager_pda, false),
+        AccountMeta::new_readonly(vote_lock, false),
+    ])
+}
+
+async fn ranked_vote(
+    round: u64,
+    project_keys: &[String],
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (ballot_pda, _) =
+        seeds::ballot_address(&vote_manager_pda, round, &vouter.pubkey(), &program.id());
+    let (ranked_tally_pda, _) =
+        seeds::ranked_tally_address(&vote_manager_pda, round, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+
+    // The ranked projects, most preferred first.
+    let rankings: Vec<AccountMeta> = project_keys
+        .iter()
+        .map(|project_key| {
+            let (project_pda, _) = seeds::project_address(
+                project_key,
+                round,
+                &program.payer(),
+                election,
+                &program.id(),
+            );
+            AccountMeta::new_readonly(project_pda, false)
+        })
+        .collect();
+
+    let decimals = mint_decimals(&mint).await?;
+    let charge = fee_charge(
+        vote_manager.fee_mode,
+        &format_amount(vote_manager.round_config.vote_fee, decimals),
+        &vouter_ata,
+        &treasury_token_account,
+    );
+    let summary = format!(
+        "{charge} and cast a ballot for round {round} ranking {}",
+        project_keys.join(" > ")
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::CastRankedBallot {
+            ballot: ballot_pda,
+            ranked_tally: ranked_tally_pda,
+            signer: vouter.pubkey(),
+            vote_manager: vote_manager_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            mint,
+            token: vouter_ata,
+            token_program,
+            system_program: system_program::ID,
+        })
+        .accounts(rankings)
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &vouter.pubkey()).await?)
+        .args(governance::instruction::CastRankedBallot)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Ballot cast. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+async fn tally_ranked(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let round = vote_manager.vote_round;
+
+    let summary = format!(
+        "run the instant-runoff tally of round {round} ({} ballots) and finalize it",
+        vote_manager.round_participants
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let seal = run_ranked_tally(&program, &payer, vote_manager_pda, round).await?;
+    let send_res = program
+        .request()
+        .instruction(seal)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Round {round} finalized. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Counts every project of the ended current round with `tally_page`, in ascending address order,
+/// and seals the round with the winner found by `tally_commit`.
+///
+/// A tally interrupted between pages resumes after the last project counted on-chain.
+async fn tally(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let round = vote_manager.vote_round;
+
+    let mut projects: Vec<Pubkey> = round_projects(&program.id(), vote_manager_pda, round)
+        .await?
+        .into_iter()
+        .filter(|(_, project)| project.pending == 0)
+        .map(|(pubkey, _)| pubkey)
+        .collect();
+    projects.sort();
+    if projects.len() != usize::from(vote_manager.project_count) {
+        return Err(CliError {
+            class: FailureClass::Rpc,
+            message: format!(
+                "Found {} of the round's {} projects, the RPC node did not return every project",
+                projects.len(),
+                vote_manager.project_count
+            ),
+        }
+        .into());
+    }
+
+    let counted = tally_progress(&program.id(), vote_manager_pda, &vote_manager).await;
+    let summary = format!(
+        "tally {} of the {} projects of round {round} and finalize it",
+        projects.len() - counted,
+        projects.len()
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let commit = run_tally(&program, &payer, vote_manager_pda, &vote_manager, &projects).await?;
+    let send_res = program
+        .request()
+        .instruction(commit)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Round {round} finalized. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Returns how many projects of the current round a tally started with its current deadline
+/// already counted.
+async fn tally_progress(
+    program_id: &Pubkey,
+    vote_manager_pda: Pubkey,
+    vote_manager: &governance::VoteManager,
+) -> usize {
+    let (tally_state_pda, _) =
+        seeds::tally_state_address(&vote_manager_pda, vote_manager.vote_round, program_id);
+    match clients()
+        .account::<governance::TallyState>(tally_state_pda)
+        .await
+    {
+        Ok(tally) if Some(tally.deadline) == vote_manager.tally_deadline() => {
+            usize::from(tally.counted)
+        }
+        _ => 0,
+    }
+}
+
+/// Counts the current round's `projects`, in ascending address order, with `tally_page`, and
+/// returns the `tally_commit` instruction sealing the round.
+///
+/// A tally interrupted between pages resumes after the last project counted on-chain.
+async fn run_tally(
+    program: &anchor_client::Program<Rc<Keypair>>,
+    payer: &Keypair,
+    vote_manager_pda: Pubkey,
+    vote_manager: &governance::VoteManager,
+    projects: &[Pubkey],
+) -> Result<Instruction, Box<dyn Error>> {
+    let round = vote_manager.vote_round;
+    let (tally_state_pda, _) = seeds::tally_state_address(&vote_manager_pda, round, &program.id());
+
+    let mut counted = tally_progress(&program.id(), vote_manager_pda, vote_manager).await;
+    for page in projects[counted..].chunks(TALLY_PAGE_SIZE) {
+        let send_res = program
+            .request()
+            .accounts(governance::accounts::TallyPage {
+                tally_state: tally_state_pda,
+                vote_manager: vote_manager_pda,
+                signer: program.payer(),
+                system_program: system_program::ID,
+            })
+            .accounts(
+                page.iter()
+                    .map(|project| AccountMeta::new_readonly(*project, false))
+                    .collect::<Vec<_>>(),
+            )
+            .args(governance::instruction::TallyPage {
+                start_index: counted as u16,
+                count: page.len() as u16,
+            })
+            .signer(payer)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!("Counted {} projects. Tx signature: {sig}", page.len()),
+            Err(e) => return Err(e.into()),
+        }
+        counted += page.len();
+    }
+
+    let tally: governance::TallyState = clients().account(tally_state_pda).await?;
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let (round_archive_pda, _) =
+        seeds::round_archive_address(&vote_manager_pda, round, &program.id());
+    Ok(Instruction {
+        program_id: program.id(),
+        accounts: governance::accounts::TallyCommit {
+            tally_state: tally_state_pda,
+            round_result: round_result_pda,
+            round_archive: round_archive_pda,
+            vote_manager: vote_manager_pda,
+            winner: (tally.leader != Pubkey::default()).then_some(tally.leader),
+            signer: program.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: governance::instruction::TallyCommit.data(),
+    })
+}
+
+/// Counts every ballot of the ranked `round` until its instant-runoff tally found the winner, and
+/// returns the instruction sealing the round.
+///
+/// Each pass counts all ballots again, one page per transaction; pages already counted in the
+/// current pass are skipped on-chain, so an interrupted tally can simply be run again.
+async fn run_ranked_tally(
+    program: &anchor_client::Program<Rc<Keypair>>,
+    payer: &Keypair,
+    vote_manager_pda: Pubkey,
+    round: u64,
+) -> Result<Instruction, Box<dyn Error>> {
+    let (ranked_tally_pda, _) =
+        seeds::ranked_tally_address(&vote_manager_pda, round, &program.id());
+    let ballots: Vec<Pubkey> = clients()
+        .accounts::<governance::Ballot>(&program.id(), vec![])
+        .await?
+        .into_iter()
+        .filter(|(_, ballot)| ballot.vote_manager == vote_manager_pda && ballot.round == round)
+        .map(|(pubkey, _)| pubkey)
+        .collect();
+
+    let mut tally: governance::RankedTally = clients().account(ranked_tally_pda).await?;
+    while !tally.complete {
+        let pass = tally.pass;
+        for page in ballots.chunks(BALLOT_PAGE_SIZE) {
+            let send_res = program
+                .request()
+                .accounts(governance::accounts::TallyRankedRound {
+                    ranked_tally: ranked_tally_pda,
+                    round_result: None,
+                    round_archive: None,
+                    vote_manager: vote_manager_pda,
+                    winner: None,
+                    signer: program.payer(),
+                    system_program: system_program::ID,
+                })
+                .accounts(
+                    page.iter()
+                        .map(|ballot| AccountMeta::new(*ballot, false))
+                        .collect::<Vec<_>>(),
+                )
+                .args(governance::instruction::TallyRankedRound)
+                .signer(payer)
+                .send_via(clients())
+                .await;
+
+            match send_res {
+                Ok(sig) => println!(
+                    "Counted {} ballots of pass {pass}. Tx signature: {sig}",
+                    page.len()
+                ),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        tally = clients().account(ranked_tally_pda).await?;
+        if !tally.complete && tally.pass == pass {
+            return Err(CliError {
+                class: FailureClass::Rpc,
+                message: format!(
+                    "Pass {pass} counted {} of {} ballots, the RPC node did not return every ballot",
+                    tally.counted, tally.ballots
+                ),
+            }
+            .into());
+        }
+    }
+
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let (round_archive_pda, _) =
+        seeds::round_archive_address(&vote_manager_pda, round, &program.id());
+    let winner = (tally.winner != Pubkey::default()).then_some(tally.winner);
+    Ok(Instruction {
+        program_id: program.id(),
+        accounts: governance::accounts::TallyRankedRound {
+            ranked_tally: ranked_tally_pda,
+            round_result: Some(round_result_pda),
+            round_archive: Some(round_archive_pda),
+            vote_manager: vote_manager_pda,
+            winner,
+            signer: program.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: governance::instruction::TallyRankedRound.data(),
+    })
+}
+
+async fn init_treasury(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+
+    let summary = format!(
+        "create treasury {treasury_pda} with fee account {treasury_token_account} for \
+         VoteManager {vote_manager_pda}"
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::InitTreasury {
+            treasury: treasury_pda,
+            treasury_token_account,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: program.payer(),
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
+            system_program: system_program::ID,
+        })
+        .args(governance::instruction::InitTreasury)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Treasury created. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+async fn treasury_balance(election: u64) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &TOKEN_MINT.parse::<Pubkey>()?,
+            &TOKEN_PROGRAM.parse::<Pubkey>()?,
+        );
+
+    let treasury: governance::Treasury = clients().account(treasury_pda).await?;
+    let balance = clients()
+        .rpc()
+        .get_token_account_balance(&treasury_token_account)
+        .await?;
+    let decimals = mint_decimals(&TOKEN_MINT.parse()?).await?;
+
+    println!("Treasury: {treasury_pda}");
+    println!(
+        "Balance: {}",
+        format_amount(balance.amount.parse()?, decimals)
+    );
+    println!(
+        "Total collected: {}",
+        format_amount(treasury.total_collected, decimals)
+    );
+    println!(
+        "Total withdrawn: {}",
+        format_amount(treasury.total_withdrawn, decimals)
+    );
+    println!(
+        "Total rewarded: {}",
+        format_amount(treasury.total_rewarded, decimals)
+    );
+    let rent_exempt = clients()
+        .rpc()
+        .get_minimum_balance_for_rent_exemption(8 + governance::Treasury::INIT_SPACE)
+        .await?;
+    let lamports = clients().rpc().get_balance(&treasury_pda).await?;
+    println!(
+        "SOL fees: {} lamports held, {} collected",
+        lamports.saturating_sub(rent_exempt),
+        treasury.total_collected_sol
+    );
+
+    Ok(())
+}
+
+/// Withdraws `amount` TTT from the treasury to the admin's token account.
+async fn withdraw_treasury(amount: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let destination = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &program.payer(),
+        &mint,
+        &token_program,
+    );
+
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::WithdrawTreasury {
+        amount: parse_amount(amount, decimals)?,
+    };
+    let summary = format!(
+        "withdraw {} from {treasury_token_account} to {destination}",
+        format_amount(args.amount, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::WithdrawTreasury {
+            treasury: treasury_pda,
+            treasury_token_account,
+            destination,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: program.payer(),
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &treasury_pda).await?)
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Treasury withdrawn. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Withdraws `lamports` of the vote fees paid in SOL from the treasury to the admin.
+async fn withdraw_treasury_sol(
+    lamports: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+
+    let args = governance::instruction::WithdrawTreasurySol { amount: lamports };
+    let summary = format!(
+        "withdraw {} lamports from treasury {treasury_pda}",
+        args.amount
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::WithdrawTreasurySol {
+            treasury: treasury_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Treasury SOL withdrawn. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Funds the VoteManager's top-up pool with `amount` TTT from the admin's token account.
+async fn fund_top_ups(amount: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let top_up_account = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vote_manager_pda,
+        &mint,
+        &token_program,
+    );
+    let source = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &program.payer(),
+        &mint,
+        &token_program,
+    );
+
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::FundTopUps {
+        amount: parse_amount(amount, decimals)?,
+    };
+    let summary = format!(
+        "fund the top-up pool {top_up_account} with {} from {source}",
+        format_amount(args.amount, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::FundTopUps {
+            top_up_account,
+            source,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: program.payer(),
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
+            system_program: system_program::ID,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &program.payer()).await?)
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Top-up pool funded. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Withdraws `amount` TTT from the VoteManager's top-up pool to the admin's token account.
+async fn withdraw_top_ups(amount: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let top_up_account = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vote_manager_pda,
+        &mint,
+        &token_program,
+    );
+    let destination = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &program.payer(),
+        &mint,
+        &token_program,
+    );
+
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::WithdrawTopUps {
+        amount: parse_amount(amount, decimals)?,
+    };
+    let summary = format!(
+        "withdraw {} from the top-up pool {top_up_account} to {destination}",
+        format_amount(args.amount, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::WithdrawTopUps {
+            vote_manager: vote_manager_pda,
+            top_up_account,
+            destination,
+            mint,
+            owner: program.payer(),
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &vote_manager_pda).await?)
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Top-up pool withdrawn. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Creates the sponsor vault, limiting each wallet to `max_per_wallet` reimbursed lamports.
+async fn init_sponsor_vault(
+    max_per_wallet: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (sponsor_vault_pda, _) = seeds::sponsor_vault_address(&vote_manager_pda, &program.id());
+
+    let args = governance::instruction::InitSponsorVault { max_per_wallet };
+    let summary = format!(
+        "create sponsor vault {sponsor_vault_pda}, reimbursing up to {} lamports per wallet",
+        args.max_per_wallet
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::InitSponsorVault {
+            sponsor_vault: sponsor_vault_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Sponsor vault created. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Deposits `lamports` into the sponsor vault with `fund`, or withdraws them to the admin.
+async fn manage_sponsor_vault(
+    lamports: u64,
+    fund: bool,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (sponsor_vault_pda, _) = seeds::sponsor_vault_address(&vote_manager_pda, &program.id());
+
+    let accounts = governance::accounts::ManageSponsorVault {
+        sponsor_vault: sponsor_vault_pda,
+        vote_manager: vote_manager_pda,
+        owner: program.payer(),
+        system_program: system_program::ID,
+    };
+    let request = program.request().accounts(accounts);
+    let (summary, request) = if fund {
+        let args = governance::instruction::FundSponsorVault { amount: lamports };
+        (
+            format!(
+                "deposit {} lamports into sponsor vault {sponsor_vault_pda}",
+                args.amount
+            ),
+            request.args(args),
+        )
+    } else {
+        let args = governance::instruction::WithdrawSponsorVault { amount: lamports };
+        (
+            format!(
+                "withdraw {} lamports from sponsor vault {sponsor_vault_pda}",
+                args.amount
+            ),
+            request.args(args),
+        )
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = request.signer(&*payer).send_via(clients()).await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Sponsor vault updated. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Deposits `amount` TTT from the admin's token account into the reward vault of `round`.
+async fn fund_rewards(
+    round: u64,
+    amount: &str,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (reward_vault_pda, _) =
+        seeds::reward_vault_address(&vote_manager_pda, round, &program.id());
+    let vault_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &reward_vault_pda,
+            &mint,
+            &token_program,
+        );
+    let source = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &program.payer(),
+        &mint,
+        &token_program,
+    );
+
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::FundRewardVault {
+        round,
+        amount: parse_amount(amount, decimals)?,
+    };
+    let summary = format!(
+        "fund round {} rewards with {} from {source}",
+        args.round,
+        format_amount(args.amount, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::FundRewardVault {
+            reward_vault: reward_vault_pda,
+            vault_token_account,
+            source,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: program.payer(),
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
+            system_program: system_program::ID,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &program.payer()).await?)
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Reward vault funded. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Returns the unclaimed rewards of `round` to the admin's token account.
+async fn sweep_rewards(round: u64, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (reward_vault_pda, _) =
+        seeds::reward_vault_address(&vote_manager_pda, round, &program.id());
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let vault_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &reward_vault_pda,
+            &mint,
+            &token_program,
+        );
+    let destination = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &program.payer(),
+        &mint,
+        &token_program,
+    );
+
+    let reward_vault: governance::RewardVault = clients().account(reward_vault_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::SweepUnclaimedRewards { round };
+    let summary = format!(
+        "sweep round {} rewards ({} funded, {} claimed) to {destination}",
+        args.round,
+        format_amount(reward_vault.total_funded, decimals),
+        format_amount(reward_vault.total_claimed, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::SweepUnclaimedRewards {
+            reward_vault: reward_vault_pda,
+            vault_token_account,
+            round_result: round_result_pda,
+            destination,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: program.payer(),
+            token_program,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Unclaimed rewards swept. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Signs the canonical JSON of a finalized round with the admin key and records the signature.
+///
+/// The signature is verified on-chain by an Ed25519 program instruction sent in the same
+/// transaction, right before `attest_round`.
+async fn attest_round(round: u64, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let (round_attestation_pda, _) =
+        seeds::round_attestation_address(&round_result_pda, &program.id());
+
+    let round_result: governance::RoundResult = clients().account(round_result_pda).await?;
+    let message = round_result.canonical_json();
+    let signature = payer.sign_message(message.as_bytes());
+
+    println!("{message}");
+    let summary = format!("attest round {round} with signature {signature}");
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .instruction(ed25519_instruction(
+            &payer.pubkey(),
+            signature.as_ref(),
+            message.as_bytes(),
+        ))
+        .accounts(governance::accounts::AttestRound {
+            round_attestation: round_attestation_pda,
+            round_result: round_result_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        })
+        .args(governance::instruction::AttestRound)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Round attested. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Builds an Ed25519 program instruction verifying `signature` of `message` by `pubkey`, with the
+/// three of them stored in the instruction data.
+fn ed25519_instruction(pubkey: &Pubkey, signature: &[u8], message: &[u8]) -> Instruction {
+    const PUBKEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBKEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+
+    // One signature, padding, then the offsets; `u16::MAX` points into this instruction.
+    let mut data = vec![1, 0];
+    for offset in [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        PUBKEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+/// Creates a campaign from `plan_file`, a JSON array of `{"start_ts", "end_ts", "theme"}` objects
+/// with unix timestamps.
+async fn campaign_create(plan_file: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let plan: serde_json::Value = serde_json::from_str(&fs::read_to_string(plan_file)?)?;
+    let rounds = plan
+        .as_array()
+        .ok_or_else(|| CliError::config("The campaign plan must be a JSON array"))?
+        .iter()
+        .map(|round| {
+            let timestamp = |key: &str| {
+                round[key].as_i64().ok_or_else(|| {
+                    CliError::config(format!("Every campaign round needs an integer `{key}`"))
+                })
+            };
+            Ok(governance::CampaignRound {
+                start_ts: timestamp("start_ts")?,
+                end_ts: timestamp("end_ts")?,
+                theme: round["theme"].as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>, CliError>>()?;
+
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (campaign_pda, _) = seeds::campaign_address(&vote_manager_pda, &program.id());
+
+    let args = governance::instruction::CreateCampaign { rounds };
+    let mut summary = format!(
+        "create campaign {campaign_pda} with {} rounds:",
+        args.rounds.len()
+    );
+    for (i, round) in args.rounds.iter().enumerate() {
+        summary.push_str(&format!(
+            "\n  #{i} {} - {} '{}'",
+            round.start_ts, round.end_ts, round.theme
+        ));
+    }
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::CreateCampaign {
+            campaign: campaign_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Campaign created. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Prints the campaign's scheduled rounds and which of them were opened.
+async fn campaign_status(election: u64) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (campaign_pda, _) = seeds::campaign_address(&vote_manager_pda, &program.id());
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let campaign: governance::Campaign = clients().account(campaign_pda).await?;
+
+    println!(
+        "Campaign {campaign_pda}: {} of {} rounds opened or skipped, current round {}",
+        campaign.next_round,
+        campaign.rounds.len(),
+        vote_manager.vote_round
+    );
+    for (i, round) in campaign.rounds.iter().enumerate() {
+        let status = match i.cmp(&(campaign.next_round as usize)) {
+            std::cmp::Ordering::Less if i + 1 == campaign.next_round as usize => {
+                format!("opened on round {}", campaign.last_opened_round)
+            }
+            std::cmp::Ordering::Less => "done".to_string(),
+            std::cmp::Ordering::Equal => "next".to_string(),
+            std::cmp::Ordering::Greater => "scheduled".to_string(),
+        };
+        println!(
+            "  #{i} {} - {} '{}': {status}",
+            round.start_ts, round.end_ts, round.theme
+        );
+    }
+
+    Ok(())
+}
+
+/// Freezes the eligible wallets and their vote weights at the current slot into `snapshot_file`.
+///
+/// Every wallet holding TTT is eligible with the weight the VoteManager's `weight_curve` gives
+/// its total balance. The file lists the entries in leaf order with the slot and the Merkle root.
+async fn snapshot(snapshot_file: &str, election: u64) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+
+    // Token accounts store their mint in the first 32 bytes.
+    let config = serde_json::json!({
+        "encoding": "jsonParsed",
+        "commitment": "confirmed",
+        "withContext": true,
+        "filters": [{ "memcmp": { "offset": 0, "bytes": mint.to_string() } }],
+    });
+    let accounts: serde_json::Value = clients()
+        .rpc()
+        .send(
+            RpcRequest::GetProgramAccounts,
+            serde_json::json!([TOKEN_PROGRAM, config]),
+        )
+        .await?;
+    let slot = accounts["context"]["slot"].as_u64().unwrap_or_default();
+
+    let mut balances: Vec<(Pubkey, u64)> = Vec::new();
+    for account in accounts["value"].as_array().into_iter().flatten() {
+        let parsed = &account["account"]["data"]["parsed"];
+        if parsed["type"] != "account" {
+            continue;
+        }
+        let owner = parsed["info"]["owner"]
+            .as_str()
+            .unwrap_or_default()
+            .parse::<Pubkey>()?;
+        let amount = parsed["info"]["tokenAmount"]["amount"]
+            .as_str()
+            .unwrap_or_default()
+            .parse::<u64>()?;
+        match balances.iter_mut().find(|(wallet, _)| *wallet == owner) {
+            Some((_, balance)) => *balance = balance.saturating_add(amount),
+            None => balances.push((owner, amount)),
+        }
+    }
+    let mut entries: Vec<(Pubkey, u64)> = balances
+        .into_iter()
+        .filter(|(_, balance)| *balance > 0)
+        .map(|(wallet, balance)| (wallet, vote_manager.weight_curve.weight(balance)))
+        .collect();
+    entries.sort();
+    if entries.is_empty() {
+        return Err(CliError::config(format!("No wallet holds {mint}")).into());
+    }
+
+    let root = snapshot_root(&entries);
+    let total_weight = snapshot_weight(&entries)?;
+    fs::write(
+        snapshot_file,
+        serde_json::to_string_pretty(&serde_json::json!({
+            "slot": slot,
+            "root": governance::hex_encode(&root),
+            "total_weight": total_weight,
+            "entries": entries
+                .iter()
+                .map(|(voter, weight)| {
+                    serde_json::json!({ "voter": voter.to_string(), "weight": weight })
+                })
+                .collect::<Vec<_>>(),
+        }))?,
+    )?;
+    println!(
+        "Snapshot of {} wallets at slot {slot} written to {snapshot_file}, root {}, total weight \
+         {total_weight}",
+        entries.len(),
+        governance::hex_encode(&root)
+    );
+
+    Ok(())
+}
+
+/// Sets the eligibility root to the one of `snapshot_file`, or disables the snapshot with `none`.
+async fn set_vote_receipts(
+    receipt_mint: &str,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (receipt_authority, _) = seeds::receipt_authority_address(&vote_manager_pda, &program.id());
+
+    // Disabling keeps the recorded mint, which the instruction still validates.
+    let (vote_receipts, receipt_mint) = if receipt_mint == "none" {
+        let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+        if vote_manager.receipt_mint == Pubkey::default() {
+            return Err(CliError::config("Vote receipts were never enabled").into());
+        }
+        (false, vote_manager.receipt_mint)
+    } else {
+        (true, receipt_mint.parse::<Pubkey>()?)
+    };
+
+    let args = governance::instruction::SetVoteReceipts { vote_receipts };
+    let summary = if args.vote_receipts {
+        format!(
+            "mint a receipt of {receipt_mint} on every do_vote of {vote_manager_pda} (mint \
+             authority must be {receipt_authority})"
+        )
+    } else {
+        format!("stop minting vote receipts on {vote_manager_pda}")
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::SetVoteReceipts {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+            receipt_authority,
+            receipt_mint,
+            token_program: anchor_spl::token_2022::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Vote receipts set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+async fn set_eligibility_root(
+    snapshot_file: &str,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let (root, eligible_weight) = if snapshot_file == "none" {
+        ([0; 32], 0)
+    } else {
+        let entries = read_snapshot(snapshot_file)?;
+        (snapshot_root(&entries), snapshot_weight(&entries)?)
+    };
+
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::SetEligibilityRoot {
+        root,
+        eligible_weight,
+    };
+    let summary = if args.root == [0; 32] {
+        format!("disable the eligibility snapshot of {vote_manager_pda}")
+    } else {
+        format!(
+            "restrict votes on {vote_manager_pda} to the snapshot {snapshot_file} (root {}, total \
+             weight {})",
+            governance::hex_encode(&args.root),
+            args.eligible_weight
+        )
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Eligibility root set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Converges the election to the YAML spec at `spec_file`; see `ElectionSpec` for its keys.
+///
+/// The spec is diffed against the VoteManager and only what differs is sent, one transaction per
+/// step: the fee, weight curve, schedule and eligibility snapshot, then the projects missing from
+/// the current round in `PROJECT_BATCH_SIZE` batches. A spec the chain already matches sends
+/// nothing, so re-applying it after a partial failure only sends the remaining steps.
+async fn apply(spec_file: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let spec = ElectionSpec::read(spec_file)?;
+
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let decimals = mint_decimals(&TOKEN_MINT.parse()?).await?;
+    let admin = || governance::accounts::Admin {
+        vote_data: vote_manager_pda,
+        owner: program.payer(),
+    };
+
+    // Each step is its summary line and the instructions of its transaction.
+    let mut steps: Vec<(String, Vec<Instruction>)> = Vec::new();
+
+    if let Some(fee) = &spec.fee {
+        let args = governance::instruction::ChangeFee {
+            new_vote_fee: parse_amount(fee, decimals)?,
+        };
+        if args.new_vote_fee != vote_manager.vote_fee {
+            steps.push((
+                format!(
+                    "change the vote fee from {} to {}",
+                    format_amount(vote_manager.vote_fee, decimals),
+                    format_amount(args.new_vote_fee, decimals)
+                ),
+                program
+                    .request()
+                    .accounts(admin())
+                    .args(args)
+                    .instructions()?,
+            ));
+        }
+    }
+
+    if let Some(weight_curve) = spec.weight_curve {
+        if weight_curve != vote_manager.weight_curve {
+            let args = governance::instruction::SetWeightCurve { weight_curve };
+            steps.push((
+                format!(
+                    "set the weight curve to {} with multiplier {} and cap {}",
+                    weight_mode_name(args.weight_curve.mode),
+                    args.weight_curve.multiplier,
+                    args.weight_curve.cap
+                ),
+                program
+                    .request()
+                    .accounts(admin())
+                    .args(args)
+                    .instructions()?,
+            ));
+        }
+    }
+
+    if let Some((round_start_ts, round_duration)) = spec.schedule {
+        if (round_start_ts, round_duration)
+            != (vote_manager.round_start_ts, vote_manager.round_duration)
+        {
+            let args = governance::instruction::SetRoundSchedule {
+                round_start_ts,
+                round_duration,
+            };
+            steps.push((
+                format!(
+                    "schedule the round from {} for {} seconds",
+                    args.round_start_ts, args.round_duration
+                ),
+                program
+                    .request()
+                    .accounts(admin())
+                    .args(args)
+                    .instructions()?,
+            ));
+        }
+    }
+
+    if let Some(allowlist) = &spec.allowlist {
+        let (root, eligible_weight) = if allowlist == "none" {
+            ([0; 32], 0)
+        } else {
+            let entries = read_snapshot(allowlist)?;
+            (snapshot_root(&entries), snapshot_weight(&entries)?)
+        };
+        if (root, eligible_weight) != (vote_manager.eligibility_root, vote_manager.eligible_weight)
+        {
+            let args = governance::instruction::SetEligibilityRoot {
+                root,
+                eligible_weight,
+            };
+            let description = if args.root == [0; 32] {
+                "disable the eligibility snapshot".to_owned()
+            } else {
+                format!(
+                    "restrict votes to the snapshot {allowlist} (root {}, total weight {})",
+                    governance::hex_encode(&args.root),
+                    args.eligible_weight
+                )
+            };
+            steps.push((
+                description,
+                program
+                    .request()
+                    .accounts(admin())
+                    .args(args)
+                    .instructions()?,
+            ));
+        }
+    }
+
+    let round = vote_manager.vote_round;
+    let project_pdas: Vec<Pubkey> = spec
+        .projects
+        .iter()
+        .map(|id| seeds::project_address(id, round, &program.payer(), election, &program.id()).0)
+        .collect();
+    let mut missing = Vec::new();
+    // `getMultipleAccounts` takes at most 100 accounts per call.
+    for (ids, pdas) in spec.projects.chunks(100).zip(project_pdas.chunks(100)) {
+        let accounts = clients().rpc().get_multiple_accounts(pdas).await?;
+        for ((id, pda), account) in ids.iter().zip(pdas).zip(accounts) {
+            if account.is_none() {
+                missing.push((id.clone(), *pda));
+            }
+        }
+    }
+    if !missing.is_empty() && vote_manager.round_state != governance::RoundState::Pending {
+        return Err(CliError::config(format!(
+            "Round {round} is {}, so its missing projects can no longer be added: {}",
+            round_state_name(vote_manager.round_state),
+            missing
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .into());
+    }
+    for batch in missing.chunks(PROJECT_BATCH_SIZE) {
+        let args = governance::instruction::AddProjects {
+            ids: batch.iter().map(|(id, _)| id.clone()).collect(),
+        };
+        steps.push((
+            format!("add projects {} to round {round}", args.ids.join(", ")),
+            program
+                .request()
+                .accounts(governance::accounts::NewVoteProjects {
+                    vote_manager: vote_manager_pda,
+                    owner: program.payer(),
+                    role: None,
+                    system_program: system_program::ID,
+                })
+                .accounts(
+                    batch
+                        .iter()
+                        .map(|(_, pda)| AccountMeta::new(*pda, false))
+                        .collect::<Vec<_>>(),
+                )
+                .args(args)
+                .instructions()?,
+        ));
+    }
+
+    if steps.is_empty() {
+        println!("VoteManager {vote_manager_pda} already matches {spec_file}");
+        return Ok(());
+    }
+
+    let mut summary = format!(
+        "apply {spec_file} to VoteManager {vote_manager_pda} in {} transaction(s):",
+        steps.len()
+    );
+    for (description, _) in &steps {
+        summary.push_str(&format!("\n  - {description}"));
+    }
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    for (description, instructions) in steps {
+        let send_res = instructions
+            .into_iter()
+            .fold(program.request(), |request, ix| request.instruction(ix))
+            .signer(&*payer)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!("Success! Applied: {description}. Tx signature: {sig}"),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Enables escrow mode with locks of up to `max_lock_duration` seconds, or disables it on `None`.
+async fn set_escrow_mode(
+    max_lock_duration: Option<i64>,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::SetEscrowMode {
+        escrow_mode: max_lock_duration.is_some(),
+        max_lock_duration: max_lock_duration.unwrap_or_default(),
+    };
+    let summary = if args.escrow_mode {
+        format!(
+            "weigh votes on {vote_manager_pda} with staked TTT, locked for up to {} seconds",
+            args.max_lock_duration
+        )
+    } else {
+        format!("disable escrow mode on {vote_manager_pda}")
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Escrow mode set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Pauses votes and project additions of the VoteManager, or resumes them.
+async fn set_paused(paused: bool, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::SetPaused { paused };
+    let summary = if args.paused {
+        format!("pause votes and project additions on {vote_manager_pda}")
+    } else {
+        format!("resume votes and project additions on {vote_manager_pda}")
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) if paused => println!("Success! VoteManager paused. Tx signature: {sig}"),
+        Ok(sig) => println!("Success! VoteManager resumed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Switches the VoteManager between collecting vote fees in the treasury and burning them.
+async fn set_fee_mode(
+    fee_mode: governance::FeeMode,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::SetFeeMode { fee_mode };
+    let summary = match args.fee_mode {
+        governance::FeeMode::Collect => {
+            format!("collect vote fees of {vote_manager_pda} in its treasury from the next round")
+        }
+        governance::FeeMode::Burn => {
+            format!("burn vote fees of {vote_manager_pda} from the next round")
+        }
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Fee mode set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Sets whether retracted votes are refunded from the next round.
+async fn set_retract_refund(
+    retract_refund: bool,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::SetRetractRefund { retract_refund };
+    let summary = if args.retract_refund {
+        format!("refund the fees of votes retracted from {vote_manager_pda} from the next round")
+    } else {
+        format!("keep the fees of votes retracted from {vote_manager_pda} from the next round")
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Retraction refunds set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Caps the projects a round may hold at `max_projects`, zero lifting the cap.
+async fn set_max_projects(
+    max_projects: u16,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::ChangeMaxProjects { max_projects };
+    let summary = match args.max_projects {
+        0 => format!("lift the per-round project cap of {vote_manager_pda}"),
+        max => format!("cap the rounds of {vote_manager_pda} at {max} projects"),
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Project cap set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Sets how rounds tied for the lead are decided, with runoffs of `runoff_duration` seconds.
+async fn set_tie_break(
+    tie_break: governance::TieBreakPolicy,
+    runoff_duration: i64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::SetTieBreak {
+        tie_break,
+        runoff_duration,
+    };
+    let rule = match args.tie_break {
+        governance::TieBreakPolicy::EarliestCreated => "the earliest created project".to_owned(),
+        governance::TieBreakPolicy::LowestPda => "the project with the lowest PDA".to_owned(),
+        governance::TieBreakPolicy::Runoff => format!("a runoff of {}s", args.runoff_duration),
+    };
+    let summary = format!("decide ties for the lead on {vote_manager_pda} by {rule}");
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Tie-break policy set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Grants `holder` the Operator or Auditor role on the VoteManager.
+async fn grant_role(
+    holder: Pubkey,
+    role: governance::Role,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (role_pda, _) = seeds::role_address(&vote_manager_pda, &holder, &program.id());
+
+    let args = governance::instruction::GrantRole { holder, role };
+    let summary = format!(
+        "grant {} the {} role on {vote_manager_pda} as account {role_pda}",
+        args.holder,
+        role_name(args.role)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::GrantRole {
+            role: role_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Role granted. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Revokes the role `holder` holds on the VoteManager.
+async fn revoke_role(holder: Pubkey, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (role_pda, _) = seeds::role_address(&vote_manager_pda, &holder, &program.id());
+    let grant: governance::RoleGrant = clients().account(role_pda).await?;
+
+    let summary = format!(
+        "revoke the {} role of {holder} on {vote_manager_pda}, closing {role_pda}",
+        role_name(grant.role)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::RevokeRole {
+            role: role_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(governance::instruction::RevokeRole)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Role revoked. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Freezes or thaws the TTT account of `wallet`, keeping a misbehaving voter out between rounds.
+async fn set_account_frozen(wallet: Pubkey, frozen: bool, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let token_program_pubkey = TTT_TOKEN_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(token_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let token_account = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &wallet,
+        &mint,
+        &token_program,
+    );
+
+    let summary = if frozen {
+        format!("freeze the TTT account {token_account} of {wallet}")
+    } else {
+        format!("thaw the TTT account {token_account} of {wallet}")
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let accounts = ttt_token::accounts::SetAccountFrozen {
+        authority: program.payer(),
+        mint,
+        token_account,
+        token_program,
+    };
+    let request = program.request().accounts(accounts);
+    let request = if frozen {
+        request.args(ttt_token::instruction::FreezeAccount)
+    } else {
+        request.args(ttt_token::instruction::ThawAccount)
+    };
+    let send_res = request.signer(&*payer).send_via(clients()).await;
+
+    match send_res {
+        Ok(sig) if frozen => println!("Success! Account frozen. Tx signature: {sig}"),
+        Ok(sig) => println!("Success! Account thawed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Thaws the TTT account of `wallet` through the VoteManager, the freeze authority of mints whose
+/// accounts start frozen.
+async fn thaw_voter(wallet: Pubkey, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let token_account = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &wallet,
+        &mint,
+        &token_program,
+    );
+
+    let summary =
+        format!("thaw the TTT account {token_account} of {wallet} via {vote_manager_pda}");
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ThawVoterAccount {
+            vote_manager: vote_manager_pda,
+            mint,
+            token_account,
+            owner: program.payer(),
+            token_program,
+        })
+        .args(governance::instruction::ThawVoterAccount)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Voter account thawed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Sends TTT from the admin's account to every `wallet,amount` line of `recipients_file`, creating
+/// missing token accounts on the way.
+async fn airdrop(recipients_file: &str, yes: bool) -> Result<(), Box<dyn Error>> {
+    use anchor_spl::associated_token::spl_associated_token_account::instruction as ata_instruction;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let decimals = mint_decimals(&mint).await?;
+
+    let mut recipients = Vec::new();
+    for (index, line) in fs::read_to_string(recipients_file)?.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || CliError::config(format!("Line {}: expected `wallet,amount`", index + 1));
+        let (wallet, amount) = line.split_once(',').ok_or_else(invalid)?;
+        let Ok(wallet) = wallet.trim().parse::<Pubkey>() else {
+            return Err(invalid().into());
+        };
+        recipients.push((wallet, parse_amount(amount.trim(), decimals)?));
+    }
+    if recipients.is_empty() {
+        return Err(CliError::config("The recipients file lists no recipients").into());
+    }
+
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let token_program_pubkey = TTT_TOKEN_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(token_program_pubkey)?;
+
+    let source = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &program.payer(),
+        &mint,
+        &token_program,
+    );
+
+    let batches: Vec<(&[(Pubkey, u64)], ttt_token::instruction::Airdrop)> = recipients
+        .chunks(AIRDROP_CHUNK_SIZE)
+        .map(|batch| {
+            let args = ttt_token::instruction::Airdrop {
+                amounts: batch.iter().map(|(_, amount)| *amount).collect(),
+            };
+            (batch, args)
+        })
+        .collect();
+    let total: u64 = batches
+        .iter()
+        .flat_map(|(_, args)| args.amounts.iter())
+        .sum();
+    let summary = format!(
+        "airdrop {} to {} wallets in {} transaction(s)",
+        format_amount(total, decimals),
+        recipients.len(),
+        batches.len()
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    for (batch, args) in batches {
+        let mut request = program.request();
+        let mut recipient_accounts = Vec::with_capacity(batch.len());
+        for (wallet, _) in batch {
+            let create_ata = ata_instruction::create_associated_token_account_idempotent(
+                &program.payer(),
+                wallet,
+                &mint,
+                &token_program,
+            );
+            request = request.instruction(create_ata);
+            let token_account =
+                anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                    wallet,
+                    &mint,
+                    &token_program,
+                );
+            recipient_accounts.push(AccountMeta::new(token_account, false));
+        }
+
+        let send_res = request
+            .accounts(ttt_token::accounts::Airdrop {
+                authority: program.payer(),
+                mint,
+                source,
+                token_program,
+            })
+            .accounts(recipient_accounts)
+            .args(args)
+            .signer(&*payer)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!(
+                "Success! {} wallets airdropped. Tx signature: {sig}",
+                batch.len()
+            ),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the vesting schedule of `beneficiary` and how much of it can be released now.
+async fn vesting_status(beneficiary: Pubkey) -> Result<(), Box<dyn Error>> {
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let (vesting_pda, _) = Pubkey::find_program_address(
+        &[ttt_token::VESTING_SEED, mint.as_ref(), beneficiary.as_ref()],
+        &TTT_TOKEN_PROGRAM_ID.parse()?,
+    );
+    let vesting: ttt_token::Vesting = clients().account(vesting_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    println!(
+        "Vesting {vesting_pda} of {beneficiary}: {} of {} TTT released",
+        format_amount(vesting.released, decimals),
+        format_amount(vesting.total, decimals)
+    );
+    println!(
+        "  unlocks {} - {}, cliff at {}",
+        vesting.start_ts, vesting.end_ts, vesting.cliff_ts
+    );
+    println!(
+        "  unlocked now: {} TTT, releasable: {} TTT",
+        format_amount(vesting.unlocked(now), decimals),
+        format_amount(vesting.releasable(now), decimals)
+    );
+
+    Ok(())
+}
+
+/// Sets which wallets may vote from the next round.
+async fn set_access_mode(
+    access_mode: governance::AccessMode,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let args = governance::instruction::SetAccessMode { access_mode };
+    let summary = match args.access_mode {
+        governance::AccessMode::Open => {
+            format!("open votes on {vote_manager_pda} to every wallet from the next round")
+        }
+        governance::AccessMode::Allowlist => {
+            format!("restrict votes on {vote_manager_pda} to its allowlist from the next round")
+        }
+        governance::AccessMode::Blocklist => {
+            format!("reject votes on {vote_manager_pda} from its blocklist from the next round")
+        }
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Access mode set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Adds `voter` to, or with `listed` false removes it from, the allowlist or the blocklist.
+async fn update_access_list(
+    list: governance::AccessMode,
+    voter: Pubkey,
+    listed: bool,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (name, (entry_pda, _)) = if list == governance::AccessMode::Allowlist {
+        (
+            "allowlist",
+            seeds::allowlist_entry_address(&vote_manager_pda, &voter, &program.id()),
+        )
+    } else {
+        (
+            "blocklist",
+            seeds::blocklist_entry_address(&vote_manager_pda, &voter, &program.id()),
+        )
+    };
+
+    let request = program.request();
+    let (summary, request) = match (listed, list) {
+        (true, governance::AccessMode::Allowlist) => {
+            let args = governance::instruction::AddToAllowlist { voter };
+            (
+                format!(
+                    "add {} to the {name} of {vote_manager_pda} as account {entry_pda}",
+                    args.voter
+                ),
+                request
+                    .accounts(governance::accounts::AddToAllowlist {
+                        entry: entry_pda,
+                        vote_manager: vote_manager_pda,
+                        owner: program.payer(),
+                        system_program: system_program::ID,
+                    })
+                    .args(args),
+            )
+        }
+        (true, _) => {
+            let args = governance::instruction::AddToBlocklist { voter };
+            (
+                format!(
+                    "add {} to the {name} of {vote_manager_pda} as account {entry_pda}",
+                    args.voter
+                ),
+                request
+                    .accounts(governance::accounts::AddToBlocklist {
+                        entry: entry_pda,
+                        vote_manager: vote_manager_pda,
+                        owner: program.payer(),
+                        system_program: system_program::ID,
+                    })
+                    .args(args),
+            )
+        }
+        (false, _) => (
+            format!("remove {voter} from the {name} of {vote_manager_pda}, closing {entry_pda}"),
+            request
+                .accounts(governance::accounts::RemoveAccessListEntry {
+                    entry: entry_pda,
+                    vote_manager: vote_manager_pda,
+                    owner: program.payer(),
+                })
+                .args(governance::instruction::RemoveFromAccessList),
+        ),
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = request.signer(&*payer).send_via(clients()).await;
+
+    match send_res {
+        Ok(sig) => println!("Success! The {name} was updated. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Locks `amount` TTT of the voter for `lock_duration` seconds for voting power.
+async fn stake(
+    amount: &str,
+    lock_duration: i64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (stake_position_pda, _) =
+        seeds::stake_position_address(&vote_manager_pda, &vouter.pubkey(), &program.id());
+    let stake_vault = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &stake_position_pda,
+        &mint,
+        &token_program,
+    );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::StakeForPower {
+        amount: parse_amount(amount, decimals)?,
+        lock_duration,
+    };
+    let summary = format!(
+        "lock {} from {vouter_ata} in {stake_vault} for {} seconds, for {} voting power",
+        format_amount(args.amount, decimals),
+        args.lock_duration,
+        vote_manager.escrow_power(args.amount, args.lock_duration)?
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::StakeForPower {
+            stake_position: stake_position_pda,
+            stake_vault,
+            owner_token_account: vouter_ata,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: vouter.pubkey(),
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! TTT staked. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Releases the voter's expired stake position.
+async fn unstake(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (stake_position_pda, _) =
+        seeds::stake_position_address(&vote_manager_pda, &vouter.pubkey(), &program.id());
+    let stake_vault = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &stake_position_pda,
+        &mint,
+        &token_program,
+    );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let stake_position: governance::StakePosition = clients().account(stake_position_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let summary = format!(
+        "release {} from {stake_vault} to {vouter_ata} and close the stake position",
+        format_amount(stake_position.amount, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Unstake {
+            stake_position: stake_position_pda,
+            stake_vault,
+            owner_token_account: vouter_ata,
+            vote_manager: vote_manager_pda,
+            mint,
+            owner: vouter.pubkey(),
+            token_program,
+        })
+        .args(governance::instruction::Unstake)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! TTT unstaked. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Sets the minimum token holding age votes require, `min_balance` TTT held `min_slots` slots
+/// before a round opens, or lifts it.
+async fn set_holding_requirement(
+    requirement: Option<(&str, u64)>,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+
+    let (args, summary) = match requirement {
+        Some((min_balance, min_slots)) => {
+            let decimals = mint_decimals(&TOKEN_MINT.parse::<Pubkey>()?).await?;
+            let args = governance::instruction::SetHoldingRequirement {
+                min_balance: parse_amount(min_balance, decimals)?,
+                min_holding_slots: min_slots,
+            };
+            let summary = format!(
+                "require votes on {vote_manager_pda} to come from wallets that registered {} at \
+                 least {} slots before the round opened",
+                format_amount(args.min_balance, decimals),
+                args.min_holding_slots
+            );
+            (args, summary)
+        }
+        None => (
+            governance::instruction::SetHoldingRequirement {
+                min_balance: 0,
+                min_holding_slots: 0,
+            },
+            format!("lift the holding requirement of {vote_manager_pda}"),
+        ),
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Holding requirement set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Replaces the fee discount schedule with `tiers`, each `<min_balance>:<bps>`, or removes every
+/// discount when empty.
+async fn set_fee_discounts(
+    tiers: &[String],
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (fee_discounts_pda, _) = seeds::fee_discount_address(&vote_manager_pda, &program.id());
+
+    let decimals = mint_decimals(&TOKEN_MINT.parse::<Pubkey>()?).await?;
+    let args = governance::instruction::SetFeeDiscounts {
+        tiers: tiers
+            .iter()
+            .map(|tier| {
+                let (min_balance, discount_bps) = tier.split_once(':').ok_or_else(|| {
+                    CliError::config(format!("Discount tier '{tier}' is not <min_balance>:<bps>"))
+                })?;
+                Ok(governance::DiscountTier {
+                    min_balance: parse_amount(min_balance, decimals)?,
+                    discount_bps: discount_bps.parse()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?,
+    };
+
+    let summary = if args.tiers.is_empty() {
+        format!("remove the vote fee discounts of {vote_manager_pda}")
+    } else {
+        let tiers = args
+            .tiers
+            .iter()
+            .map(|tier| {
+                format!(
+                    "{}.{:02}% from {}",
+                    tier.discount_bps / 100,
+                    tier.discount_bps % 100,
+                    format_amount(tier.min_balance, decimals)
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "discount votes on {vote_manager_pda} for holders: {}",
+            tiers.join(", ")
+        )
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::SetFeeDiscounts {
+            fee_discounts: fee_discounts_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Fee discounts set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Names the project categories, category code 1 first, so rounds record a winner per category.
+async fn set_categories(names: &[String], election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (category_registry_pda, _) =
+        seeds::category_registry_address(&vote_manager_pda, &program.id());
+
+    let args = governance::instruction::SetCategories {
+        names: names.to_vec(),
+    };
+    let summary = if args.names.is_empty() {
+        format!("remove the category names of {vote_manager_pda}")
+    } else {
+        let names = args
+            .names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| format!("{} = {name}", i + 1))
+            .collect::<Vec<_>>();
+        format!(
+            "name the project categories of {vote_manager_pda}: {}",
+            names.join(", ")
+        )
+    };
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::SetCategories {
+            category_registry: category_registry_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Categories set. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Registers the voter's token holding, starting the age the holding requirement counts.
+async fn register_holding(election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (holding_attestation_pda, _) =
+        seeds::holding_attestation_address(&vote_manager_pda, &vouter.pubkey(), &program.id());
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &TOKEN_PROGRAM.parse::<Pubkey>()?,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let summary = format!(
+        "register the holding of {vouter_ata} with {vote_manager_pda} as account \
+         {holding_attestation_pda}, voting in rounds opened {} slots from now or later",
+        vote_manager.min_holding_slots
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::RegisterHolding {
+            holding_attestation: holding_attestation_pda,
+            token: vouter_ata,
+            vote_manager: vote_manager_pda,
+            owner: vouter.pubkey(),
+            system_program: system_program::ID,
+        })
+        .args(governance::instruction::RegisterHolding)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Holding registered. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Reads the `(voter, weight)` entries of a snapshot written by `snapshot`, checking its root.
+fn read_snapshot(snapshot_file: &str) -> Result<Vec<(Pubkey, u64)>, Box<dyn Error>> {
+    let snapshot: serde_json::Value = serde_json::from_str(&fs::read_to_string(snapshot_file)?)?;
+    let entries = snapshot["entries"]
+        .as_array()
+        .ok_or_else(|| CliError::config("The snapshot must list its `entries`"))?
+        .iter()
+        .map(|entry| {
+            let voter = entry["voter"]
+                .as_str()
+                .unwrap_or_default()
+                .parse::<Pubkey>()?;
+            let weight = entry["weight"].as_u64().ok_or_else(|| {
+                CliError::config("Every snapshot entry needs an integer `weight`")
+            })?;
+            Ok((voter, weight))
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    if entries.is_empty() {
+        return Err(CliError::config("The snapshot has no entries").into());
+    }
+    let root = governance::hex_encode(&snapshot_root(&entries));
+    if snapshot["root"].as_str() != Some(root.as_str()) {
+        return Err(CliError::config("The snapshot's root does not match its entries").into());
+    }
+    Ok(entries)
+}
+
+/// Returns the Merkle root of the snapshot `entries`, in the order they are listed.
+/// Builds the proof linking `voter`'s entry in `snapshot_file` to the VoteManager's eligibility
+/// root.
+fn snapshot_proof(
+    snapshot_file: &str,
+    vote_manager: &governance::VoteManager,
+    voter: &Pubkey,
+) -> Result<governance::EligibilityProof, Box<dyn Error>> {
+    let entries = read_snapshot(snapshot_file)?;
+    if snapshot_root(&entries) != vote_manager.eligibility_root {
+        return Err(CliError::config(format!(
+            "{snapshot_file} is not the VoteManager's eligibility snapshot"
+        ))
+        .into());
+    }
+    let index = entries
+        .iter()
+        .position(|(entry, _)| entry == voter)
+        .ok_or_else(|| CliError::config(format!("{voter} is not in {snapshot_file}")))?;
+    Ok(governance::EligibilityProof {
+        weight: entries[index].1,
+        path: governance::eligibility_proof(&snapshot_levels(&entries), index),
+    })
+}
+
+fn snapshot_root(entries: &[(Pubkey, u64)]) -> [u8; 32] {
+    let levels = snapshot_levels(entries);
+    levels[levels.len() - 1][0]
+}
+
+/// Returns the total weight of the snapshot `entries`, which turnout is reported against.
+fn snapshot_weight(entries: &[(Pubkey, u64)]) -> Result<u64, CliError> {
+    entries
+        .iter()
+        .try_fold(0u64, |total, (_, weight)| total.checked_add(*weight))
+        .ok_or_else(|| CliError::config("The snapshot's total weight overflows"))
+}
+
+/// Builds the Merkle tree levels of the snapshot `entries`.
+fn snapshot_levels(entries: &[(Pubkey, u64)]) -> Vec<Vec<[u8; 32]>> {
+    governance::eligibility_tree(
+        entries
+            .iter()
+            .map(|(voter, weight)| governance::eligibility_leaf(voter, *weight))
+            .collect(),
+    )
+}
+
+/// Creates a proposal that changes the voting fee to `new_fee` once it passes.
+///
+/// The proposal's `change_fee` is signed by the governance authority, so it only succeeds after
+/// the admin rights were handed to that PDA with `propose_admin` and an executed `accept_admin`
+/// proposal.
+async fn propose_fee(new_fee: &str, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (governance_authority, _) =
+        seeds::governance_authority_address(&vote_manager_pda, &program.id());
+
+    let decimals = mint_decimals(&mint).await?;
+    let change_fee_args = governance::instruction::ChangeFee {
+        new_vote_fee: parse_amount(new_fee, decimals)?,
+    };
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let (proposal_pda, _) =
+        seeds::proposal_address(&vote_manager_pda, vote_manager.proposal_count, &program.id());
+    let deposit_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &proposal_pda,
+            &mint,
+            &token_program,
+        );
+    let proposer_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &program.payer(),
+            &mint,
+            &token_program,
+        );
+
+    let change_fee = stored_instruction(
+        program.id(),
+        governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: governance_authority,
+        },
+        change_fee_args.data(),
+    );
+
+    let summary = format!(
+        "create proposal {} to change the vote fee to {}, locking {} from \
+         {proposer_token_account}",
+        vote_manager.proposal_count,
+        format_amount(change_fee_args.new_vote_fee, decimals),
+        format_amount(vote_manager.proposal_deposit, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::CreateProposal {
+            proposal: proposal_pda,
+            deposit_token_account,
+            proposer_token_account,
+            vote_manager: vote_manager_pda,
+            mint,
+            proposer: program.payer(),
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
+            system_program: system_program::ID,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &program.payer()).await?)
+        .args(governance::instruction::CreateProposal {
+            instructions: vec![change_fee],
+        })
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!(
+            "Success! Proposal {} created. Tx signature: {sig}",
+            vote_manager.proposal_count
+        ),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Votes for or against a proposal, locking `amount` of the voter's TTT until voting ends.
+async fn vote_proposal(
+    proposal_id: u64,
+    approve: bool,
+    amount: &str,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (proposal_pda, _) = seeds::proposal_address(&vote_manager_pda, proposal_id, &program.id());
+    let (proposal_vote_pda, _) =
+        seeds::proposal_vote_address(&proposal_pda, &vouter.pubkey(), &program.id());
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let vote_vault = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &proposal_vote_pda,
+        &mint,
+        &token_program,
+    );
+    let token = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let proposal: governance::Proposal = clients().account(proposal_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let args = governance::instruction::VoteOnProposal {
+        approve,
+        amount: parse_amount(amount, decimals)?,
+    };
+    let choice = if args.approve { "for" } else { "against" };
+    let summary = format!(
+        "vote {choice} proposal {proposal_id} as {}, locking {} in {vote_vault} until {}",
+        vouter.pubkey(),
+        format_amount(args.amount, decimals),
+        proposal.voting_ends_at
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ProposalVoter {
+            proposal: proposal_pda,
+            proposal_vote: proposal_vote_pda,
+            vote_vault,
+            token,
+            vote_manager: vote_manager_pda,
+            mint,
+            signer: vouter.pubkey(),
+            token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
+            system_program: system_program::ID,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &vouter.pubkey()).await?)
+        .args(args)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Proposal vote cast. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Returns the TTT the voter locked in a proposal vote, once the proposal's voting ended.
+async fn release_proposal_vote(
+    proposal_id: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (proposal_pda, _) = seeds::proposal_address(&vote_manager_pda, proposal_id, &program.id());
+    let (proposal_vote_pda, _) =
+        seeds::proposal_vote_address(&proposal_pda, &vouter.pubkey(), &program.id());
+    let vote_vault = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &proposal_vote_pda,
+        &mint,
+        &token_program,
+    );
+    let token = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let proposal_vote: governance::ProposalVote = clients().account(proposal_vote_pda).await?;
+    let decimals = mint_decimals(&mint).await?;
+    let summary = format!(
+        "release {} from {vote_vault} to {token} and close the vote on proposal {proposal_id}",
+        format_amount(proposal_vote.locked, decimals)
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ReleaseProposalVote {
+            proposal_vote: proposal_vote_pda,
+            vote_vault,
+            token,
+            mint,
+            voter: vouter.pubkey(),
+            token_program,
+        })
+        .accounts(transfer_hook_accounts(&mint, &vote_manager_pda, &proposal_vote_pda).await?)
+        .args(governance::instruction::ReleaseProposalVote)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Proposal vote released. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Executes a passed proposal, passing the accounts of its stored instructions.
+async fn execute_proposal(
+    proposal_id: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (proposal_pda, _) = seeds::proposal_address(&vote_manager_pda, proposal_id, &program.id());
+    let (governance_authority, _) =
+        seeds::governance_authority_address(&vote_manager_pda, &program.id());
+
+    let proposal: governance::Proposal = clients().account(proposal_pda).await?;
+    let deposit_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &proposal_pda,
+            &mint,
+            &token_program,
+        );
+    let proposer_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &proposal.proposer,
+            &mint,
+            &token_program,
+        );
+
+    // The governance authority signs the stored instructions on-chain.
+    let remaining_accounts = stored_instruction_accounts(&proposal.instructions);
+
+    let summary = format!(
+        "execute proposal {proposal_id} ({} instructions, {} for / {} against)",
+        proposal.instructions.len(),
+        proposal.yes_weight,
+        proposal.no_weight
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ExecuteProposal {
+            proposal: proposal_pda,
+            deposit_token_account,
+            proposer_token_account,
+            governance_authority,
+            vote_manager: vote_manager_pda,
+            mint,
+            signer: program.payer(),
+            token_program,
+        })
+        .accounts(remaining_accounts)
+        .args(governance::instruction::ExecuteProposal {})
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Proposal executed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Creates a referendum on the question at `question_uri`, open for `duration` seconds.
+async fn create_referendum(
+    question_uri: &str,
+    duration: i64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let referendum_id = vote_manager.referendum_count;
+    let (referendum_pda, _) =
+        seeds::referendum_address(&vote_manager_pda, referendum_id, &program.id());
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let deadline = now.checked_add(duration).ok_or("duration out of range")?;
+
+    let price = format_amount(
+        vote_manager.vote_fee,
+        mint_decimals(&TOKEN_MINT.parse()?).await?,
+    );
+    let args = governance::instruction::CreateReferendum {
+        question_uri: question_uri.to_owned(),
+        deadline,
+    };
+    let summary = format!(
+        "create referendum {referendum_id} on {}, open until {} at {price} per vote",
+        args.question_uri, args.deadline
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::CreateReferendum {
+            referendum: referendum_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Referendum {referendum_id} created. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Answers a referendum as the voter, paying its fee like a project vote.
+async fn vote_referendum(
+    referendum_id: u64,
+    choice: governance::ReferendumChoice,
+    snapshot_file: Option<&str>,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let payer = Rc::new(keypair);
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (referendum_pda, _) =
+        seeds::referendum_address(&vote_manager_pda, referendum_id, &program.id());
+    let (referendum_vote_pda, _) =
+        seeds::referendum_vote_address(&referendum_pda, &vouter.pubkey(), &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let treasury_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &treasury_pda,
+            &mint,
+            &token_program,
+        );
+    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        &vouter.pubkey(),
+        &mint,
+        &token_program,
+    );
+
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let referendum: governance::Referendum = clients().account(referendum_pda).await?;
+
+    // While a snapshot is set, the vote carries the proof of the voter's entry in it.
+    let proof = match snapshot_file {
+        Some(snapshot_file) => Some(snapshot_proof(
+            snapshot_file,
+            &vote_manager,
+            &vouter.pubkey(),
+        )?),
+        None if vote_manager.eligibility_snapshot() => {
+            return Err(CliError::config(
+                "Votes are restricted to an eligibility snapshot; pass the snapshot file",
+            )
+            .into());
+        }
+        None => None,
+    };
+    // In escrow mode the voter's StakePosition weighs the vote.
+    let mut remaining_accounts = Vec::new();
+    if vote_manager.escrow_mode {
+        let (stake_position_pda, _) =
+            seeds::stake_position_address(&vote_manager_pda, &vouter.pubkey(), &program.id());
+        remaining_accounts.push(AccountMeta::new_readonly(stake_position_pda, false));
+    }
+
+    let price = format_amount(referendum.vote_fee, mint_decimals(&mint).await?);
+    let charge = fee_charge(
+        vote_manager.fee_mode,
+        &price,
+        &vouter_ata,
+        &treasury_token_account,
+    );
+    let args = governance::instruction::VoteReferendum { choice, proof };
+    let answer = match args.choice {
+        governance::ReferendumChoice::Yes => "yes",
+        governance::ReferendumChoice::No => "no",
+        governance::ReferendumChoice::Abstain => "abstain",
+    };
+    let summary = format!(
+        "{charge} and answer {answer} to referendum {referendum_id} ({}) as {}",
+        referendum.question_uri,
+        vouter.pubkey()
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ReferendumVoter {
+            referendum: referendum_pda,
+            referendum_vote: referendum_vote_pda,
+            signer: vouter.pubkey(),
+            vote_manager: vote_manager_pda,
+            treasury: treasury_pda,
+            treasury_token_account,
+            mint,
+            token: vouter_ata,
+            token_program,
+            system_program: system_program::ID,
+        })
+        .accounts(remaining_accounts)
+        .args(args)
+        .signer(&*vouter)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Referendum vote cast. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Prints a referendum's question, deadline and tallies.
+async fn referendum(referendum_id: u64, election: u64) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (referendum_pda, _) =
+        seeds::referendum_address(&vote_manager_pda, referendum_id, &program.id());
+    let referendum: governance::Referendum = clients().account(referendum_pda).await?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    println!("Referendum {referendum_id}: {}", referendum.question_uri);
+    println!(
+        "Deadline: {} ({})",
+        referendum.deadline,
+        if now <= referendum.deadline {
+            "open"
+        } else {
+            "closed"
+        }
+    );
+    println!("Yes: {}", referendum.yes_weight);
+    println!("No: {}", referendum.no_weight);
+    println!("Abstain: {}", referendum.abstain_weight);
+    println!("Voters: {}", referendum.voters);
+
+    Ok(())
+}
+
+/// Converts an instruction into the form proposals and admin actions store.
+fn stored_instruction(
+    program_id: Pubkey,
+    accounts: impl ToAccountMetas,
+    data: Vec<u8>,
+) -> governance::ProposalInstruction {
+    governance::ProposalInstruction {
+        program_id,
+        accounts: accounts
+            .to_account_metas(None)
+            .into_iter()
+            .map(|meta| governance::ProposalAccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data,
+    }
+}
+
+/// Returns the remaining accounts executing stored `instructions` takes: every program and the
+/// accounts of its instruction, none of them signing, as the executing PDA signs on-chain.
+fn stored_instruction_accounts(
+    instructions: &[governance::ProposalInstruction],
+) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+    for instruction in instructions {
+        accounts.push(AccountMeta::new_readonly(instruction.program_id, false));
+        for meta in &instruction.accounts {
+            accounts.push(if meta.is_writable {
+                AccountMeta::new(meta.pubkey, false)
+            } else {
+                AccountMeta::new_readonly(meta.pubkey, false)
+            });
+        }
+    }
+    accounts
+}
+
+/// Creates the admin council of `members` and proposes its PDA as the VoteManager's admin.
+///
+/// The council takes over once its members execute an `accept_admin` action, see
+/// `council_propose`.
+async fn council_create(
+    threshold: u8,
+    members: Vec<Pubkey>,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (council_pda, _) = seeds::admin_council_address(&vote_manager_pda, &program.id());
+
+    let propose_args = governance::instruction::ProposeAdmin {
+        new_admin: council_pda,
+    };
+    let args = governance::instruction::CreateAdminCouncil { members, threshold };
+    let mut summary = format!(
+        "create admin council {council_pda} requiring {} of {} approvals and propose {} as the \
+         admin of {vote_manager_pda}:",
+        args.threshold,
+        args.members.len(),
+        propose_args.new_admin
+    );
+    for member in &args.members {
+        summary.push_str(&format!("\n  - {member}"));
+    }
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let propose_admin = program
+        .request()
+        .accounts(governance::accounts::Admin {
+            vote_data: vote_manager_pda,
+            owner: program.payer(),
+        })
+        .args(propose_args)
+        .instructions()?;
+    let send_res = propose_admin
+        .into_iter()
+        .fold(program.request(), |request, ix| request.instruction(ix))
+        .accounts(governance::accounts::CreateAdminCouncil {
+            council: council_pda,
+            vote_manager: vote_manager_pda,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(args)
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!(
+            "Success! Admin council created. Have its members propose and approve `accept_admin` \
+             to take over. Tx signature: {sig}"
+        ),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Proposes an admin action to the council as the member whose keypair is at `member_keypair`.
+///
+/// `action` is `change_fee <new_fee>`, `increment_round`, `withdraw_treasury <amount> <wallet>`
+/// or `accept_admin`; the AdminCouncil PDA stands in for the admin in the stored instruction.
+async fn council_propose(
+    member_keypair: &str,
+    action: &[String],
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let creator = get_keypair(ADMIN_SECRET)?.pubkey();
+    let keypair = get_keypair(member_keypair)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let token_program = TOKEN_PROGRAM.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) = seeds::vote_manager_address(&creator, election, &program.id());
+    let (council_pda, _) = seeds::admin_council_address(&vote_manager_pda, &program.id());
+    let council: governance::AdminCouncil = clients().account(council_pda).await?;
+    let (action_pda, _) =
+        seeds::admin_action_address(&council_pda, council.action_count, &program.id());
+    let decimals = mint_decimals(&mint).await?;
+
+    let admin = governance::accounts::Admin {
+        vote_data: vote_manager_pda,
+        owner: council_pda,
+    };
+    let (description, instruction) = match action
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["change_fee", new_fee] => {
+            let args = governance::instruction::ChangeFee {
+                new_vote_fee: parse_amount(new_fee, decimals)?,
+            };
+            (
+                format!(
+                    "change the vote fee to {}",
+                    format_amount(args.new_vote_fee, decimals)
+                ),
+                stored_instruction(program.id(), admin, args.data()),
+            )
+        }
+        ["increment_round"] => (
+            "increment the round".to_owned(),
+            stored_instruction(
+                program.id(),
+                governance::accounts::IncrementRound {
+                    vote_data: vote_manager_pda,
+                    owner: council_pda,
+                    role: None,
+                },
+                governance::instruction::IncrementRound.data(),
+            ),
+        ),
+        ["withdraw_treasury", amount, wallet] => {
+            let args = governance::instruction::WithdrawTreasury {
+                amount: parse_amount(amount, decimals)?,
+            };
+            let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+            let treasury_token_account =
+                anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                    &treasury_pda,
+                    &mint,
+                    &token_program,
+                );
+            let destination =
+                anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                    &wallet.parse::<Pubkey>()?,
+                    &mint,
+                    &token_program,
+                );
+            (
+                format!(
+                    "withdraw {} from the treasury to {destination}",
+                    format_amount(args.amount, decimals)
+                ),
+                stored_instruction(
+                    program.id(),
+                    governance::accounts::WithdrawTreasury {
+                        treasury: treasury_pda,
+                        treasury_token_account,
+                        destination,
+                        vote_manager: vote_manager_pda,
+                        mint,
+                        owner: council_pda,
+                        token_program,
+                    },
+                    args.data(),
+                ),
+            )
+        }
+        ["accept_admin"] => (
+            format!("make the council the admin of {vote_manager_pda}"),
+            stored_instruction(
+                program.id(),
+                admin,
+                governance::instruction::AcceptAdmin.data(),
+            ),
+        ),
+        _ => {
+            return Err(CliError::config(
+                "Expected change_fee <new_fee>, increment_round, withdraw_treasury <amount> \
+                 <wallet> or accept_admin",
+            )
+            .into())
+        }
+    };
+
+    let summary = format!(
+        "propose admin action {} to {description}, counting as the first of {} approvals needed",
+        council.action_count, council.threshold
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ProposeAdminAction {
+            action: action_pda,
+            council: council_pda,
+            signer: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(governance::instruction::ProposeAdminAction {
+            instructions: vec![instruction],
+        })
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!(
+            "Success! Admin action {} proposed. Tx signature: {sig}",
+            council.action_count
+        ),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Approves admin action `action_id` as the member whose keypair is at `member_keypair`.
+async fn council_approve(
+    member_keypair: &str,
+    action_id: u64,
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let creator = get_keypair(ADMIN_SECRET)?.pubkey();
+    let keypair = get_keypair(member_keypair)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) = seeds::vote_manager_address(&creator, election, &program.id());
+    let (council_pda, _) = seeds::admin_council_address(&vote_manager_pda, &program.id());
+    let council: governance::AdminCouncil = clients().account(council_pda).await?;
+    let (action_pda, _) = seeds::admin_action_address(&council_pda, action_id, &program.id());
+    let action: governance::AdminAction = clients().account(action_pda).await?;
+
+    let summary = format!(
+        "approve admin action {action_id} ({} instructions) as {}, the {} of {} approvals needed",
+        action.instructions.len(),
+        program.payer(),
+        action.approvals.len() + 1,
+        council.threshold
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ApproveAdminAction {
+            action: action_pda,
+            council: council_pda,
+            signer: program.payer(),
+        })
+        .args(governance::instruction::ApproveAdminAction {})
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Admin action {action_id} approved. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Executes admin action `action_id` once the council's threshold approved it.
+async fn council_execute(action_id: u64, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (council_pda, _) = seeds::admin_council_address(&vote_manager_pda, &program.id());
+    let council: governance::AdminCouncil = clients().account(council_pda).await?;
+    let (action_pda, _) = seeds::admin_action_address(&council_pda, action_id, &program.id());
+    let action: governance::AdminAction = clients().account(action_pda).await?;
+
+    // The AdminCouncil PDA signs the stored instructions on-chain.
+    let remaining_accounts = stored_instruction_accounts(&action.instructions);
+
+    let summary = format!(
+        "execute admin action {action_id} ({} instructions, {} of {} approvals)",
+        action.instructions.len(),
+        action.approvals.len(),
+        council.threshold
+    );
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    let send_res = program
+        .request()
+        .accounts(governance::accounts::ExecuteAdminAction {
+            action: action_pda,
+            council: council_pda,
+            signer: program.payer(),
+        })
+        .accounts(remaining_accounts)
+        .args(governance::instruction::ExecuteAdminAction {})
+        .signer(&*payer)
+        .send_via(clients())
+        .await;
+
+    match send_res {
+        Ok(sig) => println!("Success! Admin action {action_id} executed. Tx signature: {sig}"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Prints the admin council's members and the approvals of its actions.
+async fn council_status(election: u64) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let vote_manager: governance::VoteManager = clients().account(vote_manager_pda).await?;
+    let (council_pda, _) = seeds::admin_council_address(&vote_manager_pda, &program.id());
+    let council: governance::AdminCouncil = clients().account(council_pda).await?;
+
+    println!(
+        "Admin council {council_pda}: {} of {} members, {}",
+        council.threshold,
+        council.members.len(),
+        if vote_manager.admin == council_pda {
+            "admin of the VoteManager"
+        } else {
+            "not the VoteManager's admin yet"
+        }
+    );
+    for member in &council.members {
+        println!("  member {member}");
+    }
+    for action_id in 0..council.action_count {
+        let (action_pda, _) = seeds::admin_action_address(&council_pda, action_id, &program.id());
+        let action: governance::AdminAction = clients().account(action_pda).await?;
+        println!(
+            "  action {action_id}: {} instructions, {} of {} approvals, {}",
+            action.instructions.len(),
+            action.approvals.len(),
+            council.threshold,
+            if action.executed {
+                "executed"
+            } else {
+                "pending"
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// Subscribes to the governance program logs and prints every state transition event.
+async fn watch() -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let decimals = mint_decimals(&TOKEN_MINT.parse()?).await?;
+
+    // The subscriptions stay active for as long as their handles are alive.
+    let _subscriptions = [
+        program
+            .on(|ctx, e: governance::RoundIncremented| {
+                println!(
+                    "[slot {}] RoundIncremented: round {} started at {} ({})",
+                    ctx.slot, e.round, e.round_start_ts, ctx.signature
+                );
+            })
+            .await?,
+        program
+            .on(|ctx, e: governance::RoundStateChanged| {
+                println!(
+                    "[slot {}] RoundStateChanged: round {} is {} ({})",
+                    ctx.slot,
+                    e.round,
+                    round_state_name(e.state),
+                    ctx.signature
+                );
+            })
+            .await?,
+        program
+            .on(|ctx, e: governance::ProjectAdded| {
+                println!(
+                    "[slot {}] ProjectAdded: '{}' ({}) in round {} ({})",
+                    ctx.slot, e.id, e.project, e.round, ctx.signature
+                );
+            })
+            .await?,
+        program
+            .on(|ctx, e: governance::VoteCast| {
+                println!(
+                    "[slot {}] VoteCast: {} voted for {} in round {} with weight {} ({})",
+                    ctx.slot, e.voter, e.project, e.round, e.weight, ctx.signature
+                );
+            })
+            .await?,
+        program
+            .on(move |ctx, e: governance::FeeChanged| {
+                println!(
+                    "[slot {}] FeeChanged: {} -> {} ({})",
+                    ctx.slot,
+                    format_amount(e.old_vote_fee, decimals),
+                    format_amount(e.new_vote_fee, decimals),
+                    ctx.signature
+                );
+            })
+            .await?,
+        program
+            .on(|ctx, e: governance::RoundFinalized| {
+                let tie = if e.tied { " after a tie" } else { "" };
+                println!(
+                    "[slot {}] RoundFinalized: round {} won by '{}'{tie} with {} votes from {} \
+                     participants, turnout {} bps ({})",
+                    ctx.slot,
+                    e.round,
+                    e.winner_id,
+                    e.total_votes,
+                    e.participation_count,
+                    e.turnout_bps,
+                    ctx.signature
+                );
+            })
+            .await?,
+        program
+            .on(|ctx, e: governance::RoundInvalidated| {
+                println!(
+                    "[slot {}] RoundInvalidated: round {} voided by {} with {} votes: {} ({})",
+                    ctx.slot, e.round, e.invalidated_by, e.total_votes, e.reason, ctx.signature
+                );
+            })
+            .await?,
+        program
+            .on(move |ctx, e: governance::TreasuryWithdrawn| {
+                println!(
+                    "[slot {}] TreasuryWithdrawn: {} to {}, {} left ({})",
+                    ctx.slot,
+                    format_amount(e.amount, decimals),
+                    e.destination,
+                    format_amount(e.remaining, decimals),
+                    ctx.signature
+                );
+            })
+            .await?,
+    ];
+
+    println!("Watching governance events, press Ctrl+C to stop.");
+    std::future::pending::<()>().await;
+
+    Ok(())
+}
+
+/// Prints a proof-of-vote receipt for the voter's vote in transaction `signature`.
+///
+/// The vote is checked against the `VoteCast` event of the confirmed transaction and the voter's
+/// VoterData, then the receipt's claims are signed with the voter key, so anyone holding the JSON
+/// can check it with `receipt verify`.
+async fn receipt(signature: &str) -> Result<(), Box<dyn Error>> {
+    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
+    let vouter = Rc::new(vouter_keypair);
+    let client = clients().client(vouter.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    let vote = fetch_vote(&program, signature, &vouter.pubkey()).await?;
+
+    // The vote must be on the voter's record.
+    let project: governance::ProjectData = clients().versioned_account(vote.event.project).await?;
+    let vote_manager: governance::VoteManager = clients().account(project.vote_manager).await?;
+    let (voter_data_pda, _) = seeds::voter_address(
+        vote_manager.election_id,
+        vote.event.round,
+        &vote.event.voter,
+        project.id(),
+        &program.id(),
+    );
+    let voter_data: governance::VoterData = clients().versioned_account(voter_data_pda).await?;
+    if voter_data.voter != vote.event.voter || voter_data.vote_count == 0 {
+        return Err(CliError::config(format!(
+            "VoterData {voter_data_pda} does not record this vote"
+        ))
+        .into());
+    }
+
+    let claims = serde_json::json!({
+        "version": 1,
+        "program_id": program.id().to_string(),
+        "vote_manager": project.vote_manager.to_string(),
+        "signature": signature,
+        "slot": vote.slot,
+        "block_time": vote.block_time,
+        "voter": vote.event.voter.to_string(),
+        "voter_record": voter_data_pda.to_string(),
+        "project": vote.event.project.to_string(),
+        "project_id": project.id(),
+        "round": vote.event.round,
+        "weight": vote.event.weight,
+    });
+    let proof = vouter.sign_message(serde_json::to_string(&claims)?.as_bytes());
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&serde_json::json!({
+            "claims": claims,
+            "proof": proof.to_string(),
+        }))?
+    );
+
+    Ok(())
+}
+
+/// Verifies a receipt printed by `receipt`.
+///
+/// The proof must be the voter's signature over the claims, and the claims must match the
+/// `VoteCast` event of the confirmed transaction, which stays on the ledger after the round's
+/// accounts are closed.
+async fn receipt_verify(receipt_file: &str) -> Result<(), Box<dyn Error>> {
+    let receipt: serde_json::Value = serde_json::from_str(&fs::read_to_string(receipt_file)?)?;
+    let claims = &receipt["claims"];
+    let claim = |key: &str| {
+        claims[key]
+            .as_str()
+            .ok_or_else(|| CliError::config(format!("The receipt needs a string claim `{key}`")))
+    };
+
+    // Check the proof offline first.
+    let voter = claim("voter")?.parse::<Pubkey>()?;
+    let proof = receipt["proof"]
+        .as_str()
+        .ok_or_else(|| CliError::config("The receipt needs a string `proof`"))?
+        .parse::<Signature>()?;
+    if !proof.verify(voter.as_ref(), serde_json::to_string(claims)?.as_bytes()) {
+        return Err(CliError::config("The receipt proof is not the voter's signature").into());
+    }
+
+    let keypair = get_keypair(ADMIN_SECRET)?;
+    let payer = Rc::new(keypair);
+    let client = clients().client(payer.clone());
+
+    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
+    let program = client.program(governance_program_pubkey)?;
+
+    if claim("program_id")? != program.id().to_string() {
+        return Err(CliError::config("The receipt is for another governance program").into());
+    }
+
+    // Check the claims against the confirmed transaction.
+    let vote = fetch_vote(&program, claim("signature")?, &voter).await?;
+    if claim("project")? != vote.event.project.to_string()
+        || claims["round"].as_u64() != Some(vote.event.round)
+        || claims["weight"].as_u64() != Some(vote.event.weight)
+        || claims["slot"].as_u64() != Some(vote.slot)
+    {
+        return Err(CliError::config("The receipt claims do not match the transaction").into());
+    }
+
+    println!(
+        "Receipt verified: {voter} voted for '{}' ({}) in round {} with weight {} at slot {}",
+        claims["project_id"].as_str().unwrap_or_default(),
+        vote.event.project,
+        vote.event.round,
+        vote.event.weight,
+        vote.slot
+    );
+
+    Ok(())
+}
+
+/// A `VoteCast` event of a confirmed transaction.
+struct ConfirmedVote {
+    event: governance::VoteCast,
+    slot: u64,
+    block_time: Option<i64>,
+}
+
+/// Fetches the confirmed transaction `signature` and returns the `VoteCast` event it emitted for
+/// `voter`.
+async fn fetch_vote(
+    program: &anchor_client::Program<Rc<Keypair>>,
+    signature: &str,
+    voter: &Pubkey,
+) -> Result<ConfirmedVote, Box<dyn Error>> {
+    let signature = signature.parse::<Signature>()?;
+    let tx = fetch_transaction(&signature).await?;
+    if !tx["meta"]["err"].is_null() {
+        return Err(CliError::config(format!("Transaction {signature} failed")).into());
+    }
+
+    let event = program_events::<governance::VoteCast>(&transaction_logs(&tx), &program.id())
+        .into_iter()
+        .find(|event| event.voter == *voter)
+        .ok_or_else(|| {
+            CliError::config(format!("Transaction {signature} casts no vote by {voter}"))
+        })?;
+
+    Ok(ConfirmedVote {
+        event,
+        slot: tx["slot"].as_u64().unwrap_or_default(),
+        block_time: tx["blockTime"].as_i64(),
+    })
+}
+
+/// Fetches the confirmed transaction `signature` as JSON.
+async fn fetch_transaction(signature: &Signature) -> Result<serde_json::Value, Box<dyn Error>> {
+    let config = serde_json::json!({
+        "encoding": "json",
+        "commitment": "confirmed",
+        "maxSupportedTransactionVersion": 0,
+    });
+    let tx: serde_json::Value = clients()
+        .rpc()
+        .send(
+            RpcRequest::GetTransaction,
+            serde_json::json!([signature.to_string(), config]),
+        )
+        .await?;
+    if tx.is_null() {
+        return Err(CliError {
+            class: FailureClass::Rpc,
+            message: format!("Transaction {signature} was not found"),
+        }
+        .into());
+    }
+    Ok(tx)
+}
+
+/// Returns the log messages of a transaction fetched by `fetch_transaction`.
+fn transaction_logs(tx: &serde_json::Value) -> Vec<&str> {
+    tx["meta"]["logMessages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_str)
+        .collect()
+}
+
+/// Returns the `mint` tokens a transaction moved into the token accounts of `owner`.
+fn token_balance_delta(tx: &serde_json::Value, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    let (owner, mint) = (owner.to_string(), mint.to_string());
+    let total = |key: &str| -> u64 {
+        tx["meta"][key]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|balance| {
+                balance["owner"] == owner.as_str() && balance["mint"] == mint.as_str()
+            })
+            .filter_map(|balance| {
+                balance["uiTokenAmount"]["amount"]
+                    .as_str()?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .sum()
+    };
+    total("postTokenBalances").saturating_sub(total("preTokenBalances"))
+}
+
+/// Decodes the `E` events logged by the governance program in `logs`.
+///
+/// Events are only taken from `Program data:` lines logged while `program_id` is the innermost
+/// invoked program, so another program in the transaction cannot forge one.
+fn program_events<E: Discriminator + AnchorDeserialize>(
+    logs: &[&str],
+    program_id: &Pubkey,
+) -> Vec<E> {
+    let program_id = program_id.to_string();
+    let mut invoked: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for log in logs {
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = rest.strip_prefix("data: ") {
+            if invoked.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let Ok(bytes) = BASE64_STANDARD.decode(data) else {
+                continue;
+            };
+            if let Some(payload) = bytes.strip_prefix(&E::DISCRIMINATOR) {
+                if let Ok(event) = E::try_from_slice(payload) {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+
+        // Track the invocation stack from `invoke`, `success` and `failed` lines.
+        let mut words = rest.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some(program), Some("invoke")) => invoked.push(program),
+            (Some(_), Some("success" | "failed:")) => {
+                invoked.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Rebuilds the leaves of the VoteManager's compressed vote tree from the `CompressedVoteRecorded`
+/// events of the transactions that touched it, with the vote behind each leaf.
+///
+/// Transactions are replayed oldest first, so a leaf replaced by a reward claim holds its latest
+/// version.
+async fn vote_tree_leaves(
+    program_id: &Pubkey,
+    vote_manager_pda: &Pubkey,
+    vote_tree: &governance::VoteTree,
+) -> Result<Vec<([u8; 32], Option<governance::CompressedVoteRecord>)>, Box<dyn Error>> {
+    let mut signatures = Vec::new();
+    let mut before: Option<String> = None;
+    loop {
+        let config = serde_json::json!({
+            "limit": SIGNATURE_PAGE_SIZE,
+            "before": before,
+            "commitment": "confirmed",
+        });
+        let page: Vec<serde_json::Value> = clients()
+            .rpc()
+            .send(
+                RpcRequest::GetSignaturesForAddress,
+                serde_json::json!([vote_tree.merkle_tree.to_string(), config]),
+            )
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = last["signature"].as_str().map(str::to_owned);
+        signatures.extend(
+            page.iter()
+                .filter(|entry| entry["err"].is_null())
+                .filter_map(|entry| entry["signature"].as_str().map(str::to_owned)),
+        );
+    }
+
+    let mut leaves = vec![([0; 32], None); vote_tree.leaf_count as usize];
+    for signature in signatures.iter().rev() {
+        let tx = fetch_transaction(&signature.parse()?).await?;
+        let logs = transaction_logs(&tx);
+        for event in program_events::<governance::CompressedVoteRecorded>(&logs, program_id) {
+            if event.vote_manager != *vote_manager_pda || event.merkle_tree != vote_tree.merkle_tree
+            {
+                continue;
             }
-            let new_fee = args[2].parse::<u64>()?;
-            change_fee(new_fee).await?;
-        }
-        "get_round" => {
-            get_round().await?;
-        }
-        "increment_round" => {
-            increment_round().await?;
-        }
-        "add_project" => {
-            if args.len() < 4 {
-                eprintln!("Usage: {} add_project <project_key> <round>", args[0]);
-                return Ok(());
+            if let Some(slot) = leaves.get_mut(event.index as usize) {
+                *slot = (event.leaf, Some(event.vote));
             }
-            let project_key = &args[2];
-            let round = &args[3];
-            add_project(project_key, round.parse().unwrap()).await?;
         }
-        "do_vote" => {
-            if args.len() < 4 {
-                eprintln!("Usage: {} do_vote  <project_name> <round>", args[0]);
-                return Ok(());
+    }
+    Ok(leaves)
+}
+
+/// Round, format and output file of `index export`.
+type ExportArgs = (u64, ExportFormat, String);
+
+/// Parses `--round <round> [--format csv|parquet] <out_file>`, `None` if an argument is missing.
+fn parse_export_args(args: &[String]) -> Result<Option<ExportArgs>, Box<dyn Error>> {
+    let mut round = None;
+    let mut format = ExportFormat::Csv;
+    let mut out_file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--round" => match args.next() {
+                Some(value) => round = Some(value.parse::<u64>()?),
+                None => return Ok(None),
+            },
+            "--format" => {
+                format = args
+                    .next()
+                    .and_then(|value| ExportFormat::parse(value))
+                    .ok_or_else(|| CliError::config("--format expects `csv` or `parquet`"))?;
             }
-            let project_key = &args[2];
-            let round = args[3].parse::<u8>()?;
-            do_vote(project_key, round).await?;
-        }
-        other => {
-            eprintln!("Unknown command: {}", other);
+            _ => out_file = Some(arg.clone()),
         }
     }
-
-    Ok(())
+    Ok(round
+        .zip(out_file)
+        .map(|(round, out_file)| (round, format, out_file)))
 }
 
-async fn init_force() -> Result<(), Box<dyn Error>> {
+/// Exports the votes of `round` to `out_file`, newest first.
+///
+/// Each page of votes read by `index_round_votes` is written out before the next is fetched.
+async fn index_export(
+    round: u64,
+    format: ExportFormat,
+    out_file: &str,
+    election: u64,
+) -> Result<(), Box<dyn Error>> {
     let keypair = get_keypair(ADMIN_SECRET)?;
-    let cluster = Cluster::Devnet;
     let payer = Rc::new(keypair);
-    let client = Client::new(cluster, payer.clone());
+    let client = clients().client(payer.clone());
+
     let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
     let program = client.program(governance_program_pubkey)?;
 
-    let (vote_data_pda, _) = derive_vote_manager_pda(&program.payer(), &program.id());
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
 
-    let send_res = program
-        .request()
-        .accounts(governance::accounts::Admin {
-            vote_data: vote_data_pda,
-            owner: program.payer(),
-            system_program: system_program::ID,
-        })
-        .args(governance::instruction::InitializeForce {
-            token_mint: TOKEN_MINT.parse()?,
-            token_program: TOKEN_PROGRAM.parse()?,
-            init_vote_fee: 100,
-        })
-        .signer(&*payer)
-        .send()
-        .await;
+    let mut writer = ExportWriter::create(out_file, format)?;
+    let mut exported = 0;
+    index_round_votes(
+        &program.id(),
+        vote_manager_pda,
+        treasury_pda,
+        mint,
+        round,
+        |rows| {
+            writer.write_chunk(&rows)?;
+            exported += rows.len();
+            eprintln!("Exported {exported} votes so far...");
+            Ok(())
+        },
+    )
+    .await?;
+    writer.finish()?;
 
-    match send_res {
-        Ok(sig) => println!("Success! Transaction signature: {sig}"),
-        Err(e) => print_transaction_logs(&e),
-    }
+    println!("Exported {exported} votes of round {round} to {out_file}");
 
     Ok(())
 }
 
-async fn change_fee(new_fee: u64) -> Result<(), Box<dyn Error>> {
-    let keypair = get_keypair(ADMIN_SECRET)?;
+/// Reads the votes of `round` back from the VoteManager's transaction history, newest first.
+///
+/// The history is fetched one signature page at a time and each page's votes are handed to
+/// `on_page` before the next is fetched, so memory use does not grow with the number of votes.
+async fn index_round_votes(
+    program_id: &Pubkey,
+    vote_manager_pda: Pubkey,
+    treasury_pda: Pubkey,
+    mint: Pubkey,
+    round: u64,
+    mut on_page: impl FnMut(Vec<VoteRow>) -> Result<(), Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    // The id of each project seen if it belongs to this VoteManager: a transaction touching it may
+    // also vote in another election.
+    let mut projects: HashMap<Pubkey, Option<String>> = HashMap::new();
+    let mut before: Option<String> = None;
+    'pages: loop {
+        let config = serde_json::json!({
+            "limit": SIGNATURE_PAGE_SIZE,
+            "before": before,
+            "commitment": "confirmed",
+        });
+        let page: Vec<serde_json::Value> = clients()
+            .rpc()
+            .send(
+                RpcRequest::GetSignaturesForAddress,
+                serde_json::json!([vote_manager_pda.to_string(), config]),
+            )
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = last["signature"].as_str().map(str::to_owned);
+
+        let mut rows = Vec::new();
+        for entry in &page {
+            let Some(signature) = entry["signature"].as_str() else {
+                continue;
+            };
+            if !entry["err"].is_null() {
+                continue;
+            }
+            let tx = fetch_transaction(&signature.parse()?).await?;
+            let tx_fee = token_balance_delta(&tx, &treasury_pda, &mint);
+            let memos = transaction_fee_memos(&tx);
+            for event in program_events::<governance::VoteCast>(&transaction_logs(&tx), program_id)
+            {
+                // Rounds only move forward, so no older transaction votes in `round`.
+                if event.round < round {
+                    on_page(rows)?;
+                    break 'pages;
+                }
+                if event.round > round {
+                    continue;
+                }
+                let project_id = match projects.get(&event.project) {
+                    Some(project_id) => project_id.clone(),
+                    None => {
+                        let project: governance::ProjectData =
+                            clients().versioned_account(event.project).await?;
+                        let project_id = (project.vote_manager == vote_manager_pda)
+                            .then(|| project.id().to_string());
+                        projects.insert(event.project, project_id.clone());
+                        project_id
+                    }
+                };
+                let Some(project_id) = project_id else {
+                    continue;
+                };
+                let memo = memos.iter().find(|memo| {
+                    memo.round == event.round
+                        && memo.voter == event.voter
+                        && memo.project_id == project_id
+                });
+                rows.push(VoteRow {
+                    signature: signature.to_owned(),
+                    slot: tx["slot"].as_u64().unwrap_or_default(),
+                    block_time: tx["blockTime"].as_i64(),
+                    voter: event.voter.to_string(),
+                    project: event.project.to_string(),
+                    round: event.round,
+                    weight: event.weight,
+                    tx_fee,
+                    memo: memo.map(FeeMemo::encode),
+                });
+            }
+        }
+        on_page(rows)?;
+    }
+    Ok(())
+}
 
-    let cluster = Cluster::Devnet;
+/// Round, template and optional output file of `report`.
+type ReportArgs = (u64, ReportTemplate, Option<String>);
+
+/// Parses `--round <round> [--template md|html] [out_file]`, `None` if the round is missing.
+fn parse_report_args(args: &[String]) -> Result<Option<ReportArgs>, Box<dyn Error>> {
+    let mut round = None;
+    let mut template = ReportTemplate::Markdown;
+    let mut out_file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--round" => match args.next() {
+                Some(value) => round = Some(value.parse::<u64>()?),
+                None => return Ok(None),
+            },
+            "--template" => {
+                template = args
+                    .next()
+                    .and_then(|value| ReportTemplate::parse(value))
+                    .ok_or_else(|| CliError::config("--template expects `md` or `html`"))?;
+            }
+            _ => out_file = Some(arg.clone()),
+        }
+    }
+    Ok(round.map(|round| (round, template, out_file)))
+}
 
+/// Renders the recap of the finalized `round` to `out_file`, or to stdout without one.
+///
+/// The result, turnout and standings come from the round's accounts; the fee total and the top
+/// voters from its votes in the VoteManager's transaction history.
+async fn report(
+    round: u64,
+    template: ReportTemplate,
+    out_file: Option<&str>,
+    election: u64,
+) -> Result<(), Box<dyn Error>> {
+    let keypair = get_keypair(ADMIN_SECRET)?;
     let payer = Rc::new(keypair);
-    let client = Client::new(cluster, payer.clone());
+    let client = clients().client(payer.clone());
 
     let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
     let program = client.program(governance_program_pubkey)?;
 
-    let (vote_data_pda, _) = derive_vote_manager_pda(&program.payer(), &program.id());
+    let mint = TOKEN_MINT.parse::<Pubkey>()?;
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
 
-    let send_res = program
-        .request()
-        .accounts(governance::accounts::Admin {
-            vote_data: vote_data_pda,
-            owner: program.payer(),
-            system_program: system_program::ID,
+    let result: governance::RoundResult = clients().account(round_result_pda).await?;
+
+    // Cleaned up rounds have no projects left, their report shows the winner only.
+    let mut projects: Vec<ProjectStanding> = clients()
+        .versioned_accounts::<governance::ProjectData>(&program.id(), vec![])
+        .await?
+        .into_iter()
+        .filter(|(_, project)| {
+            project.vote_manager == vote_manager_pda && project.vote_round == round
         })
-        .args(governance::instruction::ChangeFee {
-            new_vote_fee: new_fee,
+        .map(|(_, project)| ProjectStanding {
+            id: project.id().to_string(),
+            votes: project.vote_count,
+            weight: project.vote_weight,
+            disqualified: project.disqualified != 0,
         })
-        .signer(&*payer)
-        .send()
-        .await;
+        .collect();
+    projects.sort_by(|a, b| b.weight.cmp(&a.weight).then_with(|| a.id.cmp(&b.id)));
 
-    match send_res {
-        Ok(sig) => println!("Success! Fee changed. Tx signature: {sig}"),
-        Err(e) => print_transaction_logs(&e),
+    // A transaction voting more than once pays its fees once, count each signature once.
+    let mut fee_signatures = HashSet::new();
+    let mut fees = 0u64;
+    let mut weights: HashMap<String, u64> = HashMap::new();
+    index_round_votes(
+        &program.id(),
+        vote_manager_pda,
+        treasury_pda,
+        mint,
+        round,
+        |rows| {
+            for row in rows {
+                if fee_signatures.insert(row.signature) {
+                    fees = fees.saturating_add(row.tx_fee);
+                }
+                let weight = weights.entry(row.voter).or_default();
+                *weight = weight.saturating_add(row.weight);
+            }
+            Ok(())
+        },
+    )
+    .await?;
+    let voters = weights.len();
+    let mut top_voters: Vec<(String, u64)> = weights.into_iter().collect();
+    top_voters.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_voters.truncate(REPORT_TOP_VOTERS);
+
+    let report = RoundReport {
+        election,
+        result,
+        projects,
+        top_voters,
+        voters,
+        fees: format_amount(fees, mint_decimals(&mint).await?),
+    };
+    let rendered = report.render(template);
+    match out_file {
+        Some(out_file) => {
+            fs::write(out_file, rendered)?;
+            println!("Wrote the report of round {round} to {out_file}");
+        }
+        None => print!("{rendered}"),
     }
 
     Ok(())
 }
 
-async fn get_round() -> Result<(), Box<dyn Error>> {
+/// Watches the VoteManager and keeps its rounds moving: finalizes each round as soon as its
+/// deadline passes, tallying it page by page when its recount does not fit one transaction,
+/// archives the projects the seal could not carry, and increments past a round closed without a
+/// deadline.
+///
+/// Failures are reported through `alert_cmd` (run via `sh -c` with the message in
+/// `TTT_ALERT_MESSAGE`) and retried on the next poll; a failure is alerted once, until a poll no
+/// longer raises it.
+async fn daemon(
+    poll_secs: u64,
+    alert_cmd: Option<&str>,
+    election: u64,
+) -> Result<(), Box<dyn Error>> {
     let keypair = get_keypair(ADMIN_SECRET)?;
-
-    let cluster = Cluster::Devnet;
-
     let payer = Rc::new(keypair);
-    let client = Client::new(cluster, payer.clone());
+    let client = clients().client(payer.clone());
 
     let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
     let program = client.program(governance_program_pubkey)?;
 
-    let (vote_data_pda, _) = derive_vote_manager_pda(&program.payer(), &program.id());
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
 
-    let vote_manager: governance::governance::VoteManager = program.account(vote_data_pda).await?;
-    let current_round = vote_manager.vote_round;
+    println!("Watching VoteManager {vote_manager_pda} every {poll_secs}s");
 
-    println!("Current round: {current_round}");
+    let mut alerts = Alerts::new(alert_cmd);
+    loop {
+        alerts.settle();
+        match clients()
+            .account::<governance::VoteManager>(vote_manager_pda)
+            .await
+        {
+            Ok(mut vote_manager) => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
 
-    Ok(())
-}
+                // Archive the projects the last seal could not carry, so the round can be
+                // cleaned up.
+                if let Some(round) = vote_manager.vote_round.checked_sub(1) {
+                    if let Err(e) = archive_round(&program, &payer, vote_manager_pda, round).await {
+                        alerts.raise(&format!("Failed to archive round {round}: {e}"));
+                    }
+                }
 
-async fn increment_round() -> Result<(), Box<dyn Error>> {
-    let keypair = get_keypair(ADMIN_SECRET)?;
+                // The campaign, if it still has a round that can be opened.
+                let (campaign_pda, _) = seeds::campaign_address(&vote_manager_pda, &program.id());
+                let campaign = clients()
+                    .account::<governance::Campaign>(campaign_pda)
+                    .await
+                    .ok()
+                    .filter(|campaign| {
+                        campaign.rounds[campaign.next_round as usize..]
+                            .iter()
+                            .any(|round| round.end_ts > now)
+                    });
+                // The last campaign round was closed and the current round is unused and pending.
+                let can_open_campaign_round = campaign.as_ref().is_some_and(|campaign| {
+                    let closed = campaign.next_round == 0
+                        || vote_manager.vote_round > campaign.last_opened_round;
+                    closed
+                        && vote_manager.round_state == governance::RoundState::Pending
+                        && vote_manager.round_total_votes == 0
+                        && vote_manager.round_participants == 0
+                });
+                let open_campaign_round = Instruction {
+                    program_id: program.id(),
+                    accounts: governance::accounts::OpenCampaignRound {
+                        campaign: campaign_pda,
+                        vote_manager: vote_manager_pda,
+                        signer: program.payer(),
+                    }
+                    .to_account_metas(None),
+                    data: governance::instruction::OpenCampaignRound.data(),
+                };
 
-    let cluster = Cluster::Devnet;
+                if let Some(deadline) = vote_manager.tally_deadline().filter(|d| now > *d) {
+                    let round = vote_manager.vote_round;
+                    if vote_manager.config_hash() != vote_manager.round_config.config_hash {
+                        alerts.raise(&format!(
+                            "Round {round} parameters changed since it opened, it cannot be \
+                             finalized until they are restored"
+                        ));
+                        tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+                        continue;
+                    }
 
-    let payer = Rc::new(keypair);
-    let client = Client::new(cluster, payer.clone());
+                    // A leader cleared by a disqualification or a retraction is recounted from
+                    // every project of the round, which the runoff or the seal then carries.
+                    let recounted = if vote_manager.leader_unknown() {
+                        match recount_leader(&program.id(), vote_manager_pda, &mut vote_manager)
+                            .await
+                        {
+                            Ok(projects) => Some(projects),
+                            Err(e) => {
+                                alerts.raise(&format!("Failed to recount round {round}: {e}"));
+                                tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+                                continue;
+                            }
+                        }
+                    } else {
+                        None
+                    };
 
-    let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
-    let program = client.program(governance_program_pubkey)?;
+                    // A tie under the runoff policy extends the round instead.
+                    if vote_manager.runoff_due() {
+                        println!("Round {round} ended tied at {deadline}, opening a runoff...");
+                        let send_res = program
+                            .request()
+                            .accounts(governance::accounts::OpenRunoff {
+                                vote_manager: vote_manager_pda,
+                                signer: program.payer(),
+                            })
+                            .accounts(recounted.unwrap_or_default())
+                            .args(governance::instruction::OpenRunoff)
+                            .signer(&*payer)
+                            .send_via(clients())
+                            .await;
+                        match send_res {
+                            Ok(sig) => println!("Success! Runoff opened. Tx signature: {sig}"),
+                            Err(e) => {
+                                print_transaction_logs(&e);
+                                alerts
+                                    .raise(&format!("Failed to open runoff of round {round}: {e}"));
+                            }
+                        }
+                        tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+                        continue;
+                    }
 
-    let (vote_data_pda, _) = derive_vote_manager_pda(&program.payer(), &program.id());
+                    println!("Round {round} ended at {deadline}, finalizing...");
 
-    let send_res = program
-        .request()
-        .accounts(governance::accounts::Admin {
-            vote_data: vote_data_pda,
-            owner: program.payer(),
-            system_program: system_program::ID,
-        })
-        .args(governance::instruction::IncrementRound)
-        .signer(&*payer)
-        .send()
-        .await;
+                    // Ranked rounds with ballots are sealed by their instant-runoff tally.
+                    let seal = if vote_manager.ranked_choice && vote_manager.round_participants > 0
+                    {
+                        match run_ranked_tally(&program, &payer, vote_manager_pda, round).await {
+                            Ok(seal) => seal,
+                            Err(e) => {
+                                alerts.raise(&format!("Failed to tally round {round}: {e}"));
+                                tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+                                continue;
+                            }
+                        }
+                    } else if let Some(projects) = recounted
+                        .as_ref()
+                        .filter(|projects| projects.len() > TALLY_PAGE_SIZE)
+                    {
+                        // A recount too large for one transaction is counted page by page.
+                        let projects: Vec<Pubkey> =
+                            projects.iter().map(|project| project.pubkey).collect();
+                        match run_tally(
+                            &program,
+                            &payer,
+                            vote_manager_pda,
+                            &vote_manager,
+                            &projects,
+                        )
+                        .await
+                        {
+                            Ok(commit) => commit,
+                            Err(e) => {
+                                alerts.raise(&format!("Failed to tally round {round}: {e}"));
+                                tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+                                continue;
+                            }
+                        }
+                    } else {
+                        // Archive a first page of the round's projects with the seal, the
+                        // following polls archive the rest; a recount carries them all.
+                        let projects = match recounted {
+                            Some(projects) => projects,
+                            None => match round_projects(&program.id(), vote_manager_pda, round)
+                                .await
+                            {
+                                Ok(projects) => projects
+                                    .iter()
+                                    .filter(|(_, project)| project.pending == 0)
+                                    .take(ARCHIVE_PAGE_SIZE)
+                                    .map(|(pubkey, _)| AccountMeta::new_readonly(*pubkey, false))
+                                    .collect(),
+                                Err(e) => {
+                                    alerts.raise(&format!("Failed to fetch projects: {e}"));
+                                    tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+                                    continue;
+                                }
+                            },
+                        };
+                        let (round_result_pda, _) =
+                            seeds::round_result_address(&vote_manager_pda, round, &program.id());
+                        let (round_archive_pda, _) =
+                            seeds::round_archive_address(&vote_manager_pda, round, &program.id());
+                        let winner = (vote_manager.round_leader != Pubkey::default())
+                            .then_some(vote_manager.round_leader);
+                        let mut accounts = governance::accounts::FinalizeRound {
+                            round_result: round_result_pda,
+                            round_archive: round_archive_pda,
+                            vote_manager: vote_manager_pda,
+                            winner,
+                            signer: program.payer(),
+                            system_program: system_program::ID,
+                        }
+                        .to_account_metas(None);
+                        accounts.extend(projects);
+                        Instruction {
+                            program_id: program.id(),
+                            accounts,
+                            data: governance::instruction::FinalizeRound.data(),
+                        }
+                    };
 
-    match send_res {
-        Ok(sig) => println!("Success! Round incremented. Tx signature: {sig}"),
-        Err(e) => print_transaction_logs(&e),
+                    // Open the campaign's next round in the same transaction, before anyone votes.
+                    let mut request = program.request().instruction(seal);
+                    if campaign.is_some() {
+                        request = request.instruction(open_campaign_round);
+                    }
+                    let send_res = request.signer(&*payer).send_via(clients()).await;
+
+                    match send_res {
+                        Ok(sig) => println!("Success! Round {round} finalized. Tx signature: {sig}"),
+                        Err(e) => {
+                            print_transaction_logs(&e);
+                            alerts.raise(&format!("Failed to finalize round {round}: {e}"));
+                        }
+                    }
+                } else if vote_manager.round_state == governance::RoundState::Closed
+                    && vote_manager.tally_deadline().is_none()
+                {
+                    // A round closed without a deadline can never be finalized, only moved on from.
+                    let round = vote_manager.vote_round;
+                    println!("Round {round} was closed without a deadline, incrementing...");
+
+                    let send_res = program
+                        .request()
+                        .accounts(governance::accounts::IncrementRound {
+                            vote_data: vote_manager_pda,
+                            owner: program.payer(),
+                            role: None,
+                        })
+                        .args(governance::instruction::IncrementRound)
+                        .signer(&*payer)
+                        .send_via(clients())
+                        .await;
+
+                    match send_res {
+                        Ok(sig) => println!("Success! Round incremented. Tx signature: {sig}"),
+                        Err(e) => {
+                            print_transaction_logs(&e);
+                            alerts.raise(&format!("Failed to increment round {round}: {e}"));
+                        }
+                    }
+                } else if can_open_campaign_round {
+                    let round = vote_manager.vote_round;
+                    println!("Opening the next campaign round on round {round}...");
+
+                    let send_res = program
+                        .request()
+                        .instruction(open_campaign_round)
+                        .signer(&*payer)
+                        .send_via(clients())
+                        .await;
+
+                    match send_res {
+                        Ok(sig) => println!("Success! Campaign round opened. Tx signature: {sig}"),
+                        Err(e) => {
+                            print_transaction_logs(&e);
+                            alerts.raise(&format!("Failed to open campaign round: {e}"));
+                        }
+                    }
+                }
+            }
+            Err(e) => alerts.raise(&format!("Failed to fetch VoteManager: {e}")),
+        }
+
+        tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+    }
+}
+
+/// Recounts the running leader of `vote_manager`'s round from its projects, as `finalize_round`
+/// and `open_runoff` do once a disqualification or a retraction cleared it, and returns the
+/// projects to pass them, in ascending address order.
+async fn recount_leader(
+    program_id: &Pubkey,
+    vote_manager_pda: Pubkey,
+    vote_manager: &mut governance::VoteManager,
+) -> Result<Vec<AccountMeta>, Box<dyn Error>> {
+    let round = vote_manager.vote_round;
+    let mut projects: Vec<_> = round_projects(program_id, vote_manager_pda, round)
+        .await?
+        .into_iter()
+        .filter(|(_, project)| project.pending == 0)
+        .collect();
+    if projects.len() != usize::from(vote_manager.project_count) {
+        return Err(CliError {
+            class: FailureClass::Rpc,
+            message: format!(
+                "Found {} of the round's {} projects, the RPC node did not return every project",
+                projects.len(),
+                vote_manager.project_count
+            ),
+        }
+        .into());
+    }
+    projects.sort_by_key(|(pubkey, _)| *pubkey);
+
+    vote_manager.clear_leader();
+    for (pubkey, project) in &projects {
+        if project.disqualified == 0 {
+            vote_manager.update_leader(*pubkey, project.vote_weight, project.created_slot);
+        }
+    }
+    Ok(projects
+        .into_iter()
+        .map(|(pubkey, _)| AccountMeta::new_readonly(pubkey, false))
+        .collect())
+}
+
+/// Archives the finalized round's `projects` into `round_archive_pda` with `archive_projects`, one
+/// page per transaction.
+async fn archive_projects(
+    program: &anchor_client::Program<Rc<Keypair>>,
+    payer: &Keypair,
+    round_archive_pda: Pubkey,
+    projects: &[Pubkey],
+) -> Result<(), Box<dyn Error>> {
+    for page in projects.chunks(ARCHIVE_PAGE_SIZE) {
+        let send_res = program
+            .request()
+            .accounts(governance::accounts::ArchiveProjects {
+                round_archive: round_archive_pda,
+                signer: program.payer(),
+                system_program: system_program::ID,
+            })
+            .accounts(
+                page.iter()
+                    .map(|project| AccountMeta::new_readonly(*project, false))
+                    .collect::<Vec<_>>(),
+            )
+            .args(governance::instruction::ArchiveProjects)
+            .signer(payer)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!("Archived {} projects. Tx signature: {sig}", page.len()),
+            Err(e) => return Err(e.into()),
+        }
     }
 
     Ok(())
 }
 
-async fn add_project(project_key: &str, round: u8) -> Result<(), Box<dyn Error>> {
+/// Archives the projects of the finalized `round` missing from its RoundArchive, if it has one.
+async fn archive_round(
+    program: &anchor_client::Program<Rc<Keypair>>,
+    payer: &Keypair,
+    vote_manager_pda: Pubkey,
+    round: u64,
+) -> Result<(), Box<dyn Error>> {
+    let (round_archive_pda, _) =
+        seeds::round_archive_address(&vote_manager_pda, round, &program.id());
+    // A round moved on from by `increment_round` has no archive.
+    let Ok(round_archive) = clients()
+        .account::<governance::RoundArchive>(round_archive_pda)
+        .await
+    else {
+        return Ok(());
+    };
+    if round_archive.is_complete() {
+        return Ok(());
+    }
+
+    let unarchived: Vec<Pubkey> = round_projects(&program.id(), vote_manager_pda, round)
+        .await?
+        .iter()
+        .filter(|(_, project)| project.pending == 0 && !round_archive.is_archived(project.id()))
+        .map(|(pubkey, _)| *pubkey)
+        .collect();
+    archive_projects(program, payer, round_archive_pda, &unarchived).await
+}
+
+/// Returns the open ProjectData accounts of `round`.
+async fn round_projects(
+    program_id: &Pubkey,
+    vote_manager_pda: Pubkey,
+    round: u64,
+) -> Result<Vec<(Pubkey, governance::ProjectData)>, Box<dyn Error>> {
+    // The round sits at different offsets in the legacy and zero-copy layouts, so filter
+    // client-side.
+    Ok(clients()
+        .versioned_accounts(program_id, vec![])
+        .await?
+        .into_iter()
+        .filter(|(_, project): &(Pubkey, governance::ProjectData)| {
+            project.vote_manager == vote_manager_pda && project.vote_round == round
+        })
+        .collect())
+}
+
+/// Archives every project of a finalized round missing from its RoundArchive, then closes every
+/// VoterData and ProjectData account of the round, returning the rent to the original payers.
+async fn cleanup(round: u64, election: u64, yes: bool) -> Result<(), Box<dyn Error>> {
     let keypair = get_keypair(ADMIN_SECRET)?;
-    let cluster = Cluster::Devnet;
     let payer = Rc::new(keypair);
-    let client = Client::new(cluster, payer.clone());
+    let client = clients().client(payer.clone());
 
     let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
     let program = client.program(governance_program_pubkey)?;
 
-    let (vote_data_pda, _) = derive_vote_manager_pda(&program.payer(), &program.id());
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (round_result_pda, _) =
+        seeds::round_result_address(&vote_manager_pda, round, &program.id());
+    let (round_archive_pda, _) =
+        seeds::round_archive_address(&vote_manager_pda, round, &program.id());
 
-    let (project_data_pda, _project_bump) =
-        derive_project_pda(project_key, round, &program.payer(), &program.id());
-
-    let send_res = program
-        .request()
-        .accounts(governance::accounts::NewVoteProject {
-            project_data: project_data_pda,
-            vote_manager: vote_data_pda,
-            owner: program.payer(),
-            system_program: system_program::ID,
+    let projects = round_projects(&program.id(), vote_manager_pda, round).await?;
+    let round_archive: governance::RoundArchive = clients().account(round_archive_pda).await?;
+    let unarchived: Vec<Pubkey> = projects
+        .iter()
+        .filter(|(_, project)| project.pending == 0 && !round_archive.is_archived(project.id()))
+        .map(|(pubkey, _)| *pubkey)
+        .collect();
+    let voters: Vec<(Pubkey, governance::VoterData, Pubkey)> = clients()
+        .versioned_accounts::<governance::VoterData>(&program.id(), vec![])
+        .await?
+        .into_iter()
+        .filter(|(_, voter)| voter.last_voted_round == round)
+        .filter_map(|(pubkey, voter)| {
+            // Project ids repeat across elections, the record's address tells them apart.
+            let (project_pubkey, _) = projects.iter().find(|(_, project)| {
+                project.id() == voter.project_name()
+                    && seeds::voter_address(
+                        election,
+                        round,
+                        &voter.voter,
+                        project.id(),
+                        &program.id(),
+                    )
+                    .0 == pubkey
+            })?;
+            Some((pubkey, voter, *project_pubkey))
         })
-        .args(governance::instruction::AddProject {
-            idx: project_key.to_owned(),
-        })
-        .signer(&*payer)
-        .send()
-        .await;
+        .collect();
 
-    match send_res {
-        Ok(sig) => println!("Success! Project added. Tx signature: {sig}"),
-        Err(e) => print_transaction_logs(&e),
+    // Records of disqualified projects stay open until their voters claimed the refund, records
+    // of the winner until their voters claimed the reward, and so do their projects.
+    let round_result: governance::RoundResult = clients().account(round_result_pda).await?;
+    let (voters, unclaimed): (Vec<_>, Vec<_>) =
+        voters.into_iter().partition(|(_, voter, project_pubkey)| {
+            let refund_kept = voter.refund_due()
+                && projects
+                    .iter()
+                    .all(|(pubkey, project)| pubkey != project_pubkey || project.disqualified != 0);
+            let reward_kept =
+                *project_pubkey == round_result.winner && voter.reward_due(&round_result) > 0;
+            !refund_kept && !reward_kept
+        });
+    // So do projects whose submitter has not claimed the bond tranches they earned.
+    let (projects, bonded): (Vec<_>, Vec<_>) = projects
+        .into_iter()
+        .filter(|(pubkey, _)| !unclaimed.iter().any(|(_, _, project)| project == pubkey))
+        .partition(|(_, project)| project.disqualified != 0 || project.claimable_deposit() == 0);
+    let (treasury_pda, _) = seeds::treasury_address(&vote_manager_pda, &program.id());
+
+    let mut summary = format!(
+        "close {} voter records and {} projects of round {round}, returning their rent to the \
+         original payers",
+        voters.len(),
+        projects.len()
+    );
+    if !unarchived.is_empty() {
+        summary.push_str(&format!(
+            ", after archiving {} projects into {round_archive_pda}",
+            unarchived.len()
+        ));
+    }
+    let forfeited: u64 = projects.iter().map(|(_, project)| project.deposit).sum();
+    if forfeited > 0 {
+        summary.push_str(&format!(
+            ", forfeiting {} of listing bonds to the treasury",
+            format_amount(forfeited, mint_decimals(&TOKEN_MINT.parse()?).await?)
+        ));
+    }
+    if !unclaimed.is_empty() {
+        summary.push_str(&format!(
+            " ({} voter records with unclaimed refunds or rewards are kept)",
+            unclaimed.len()
+        ));
+    }
+    if !bonded.is_empty() {
+        summary.push_str(&format!(
+            " ({} projects with unclaimed deposit tranches are kept)",
+            bonded.len()
+        ));
+    }
+    if !confirm(&summary, yes)? {
+        return Ok(());
+    }
+
+    // A project can only be closed once its final tally is archived.
+    archive_projects(&program, &payer, round_archive_pda, &unarchived).await?;
+
+    // Voter records first: a project can only be closed once all of them are gone.
+    for (voter_data_pubkey, voter_data, project_pubkey) in &voters {
+        let send_res = program
+            .request()
+            .accounts(governance::accounts::CloseVoterData {
+                voter_data: *voter_data_pubkey,
+                project: *project_pubkey,
+                round_result: round_result_pda,
+                vote_manager: vote_manager_pda,
+                voter: voter_data.voter,
+                signer: program.payer(),
+            })
+            .args(governance::instruction::CloseVoterData)
+            .signer(&*payer)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!("Closed voter record {voter_data_pubkey}. Tx signature: {sig}"),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    for (project_pubkey, project) in &projects {
+        let send_res = program
+            .request()
+            .accounts(governance::accounts::CloseProjectData {
+                project: *project_pubkey,
+                round_result: round_result_pda,
+                round_archive: round_archive_pda,
+                vote_manager: vote_manager_pda,
+                payer: project.payer,
+                signer: program.payer(),
+                treasury: (project.deposit > 0).then_some(treasury_pda),
+            })
+            .args(governance::instruction::CloseProjectData)
+            .signer(&*payer)
+            .send_via(clients())
+            .await;
+
+        match send_res {
+            Ok(sig) => println!("Closed project '{}'. Tx signature: {sig}", project.id()),
+            Err(e) => return Err(e.into()),
+        }
     }
 
     Ok(())
 }
 
-async fn do_vote(
-    project_key: &str,
-    round: u8,
-) -> Result<(), Box<dyn Error>> {
+/// Prints the final standings of a finalized round from its RoundArchive, which outlives the
+/// round's ProjectData accounts.
+async fn history(round: u64, election: u64) -> Result<(), Box<dyn Error>> {
     let keypair = get_keypair(ADMIN_SECRET)?;
-    let mint = "GgQuhpBUxy7LaD56c2vbxk5hSgoBuNwxxev6U9iqyMXZ".parse::<Pubkey>()?;
-    let vouter_keypair = get_keypair(VOUTER_SECRET)?;
-
-    let cluster = Cluster::Devnet;
     let payer = Rc::new(keypair);
-    let vouter = Rc::new(vouter_keypair);
-    let client = Client::new(cluster, payer.clone());
+    let client = clients().client(payer.clone());
 
     let governance_program_pubkey = GOVERNANCE_PROGRAM_ID.parse::<Pubkey>()?;
     let program = client.program(governance_program_pubkey)?;
 
-    let (vote_manager_pda, _) = derive_vote_manager_pda(&program.payer(), &program.id());
-
-    let (vouter_pda, _) = derive_vouter_pda(round, &vouter.pubkey(), &program.id());
-
-    let (project_data_pda, _project_bump) =
-        derive_project_pda(project_key, round, &program.payer(), &program.id());
+    let (vote_manager_pda, _) =
+        seeds::vote_manager_address(&program.payer(), election, &program.id());
+    let (round_archive_pda, _) =
+        seeds::round_archive_address(&vote_manager_pda, round, &program.id());
+    let round_archive: governance::RoundArchive = clients().account(round_archive_pda).await?;
 
-    let admin_token_account =
-        anchor_spl::associated_token::get_associated_token_address_with_program_id(
-            &program.payer(),
-            &mint,
-            &TOKEN_PROGRAM.parse::<Pubkey>()?,
+    println!(
+        "Round {round} standings ({} of {} projects archived):",
+        round_archive.entries.len(),
+        round_archive.project_count
+    );
+    for (rank, entry) in round_archive.entries.iter().enumerate() {
+        println!(
+            "  {}. {}: {} votes",
+            rank + 1,
+            entry.project_id,
+            entry.vote_count
         );
+    }
 
-    let vouter_ata = anchor_spl::associated_token::get_associated_token_address_with_program_id(
-        &vouter.pubkey(),
-        &mint,
-        &TOKEN_PROGRAM.parse::<Pubkey>()?,
-    );
+    if !round_archive.category_winners.is_empty() {
+        let (category_registry_pda, _) =
+            seeds::category_registry_address(&vote_manager_pda, &program.id());
+        let registry: Option<governance::CategoryRegistry> =
+            clients().account(category_registry_pda).await.ok();
+        println!("Category winners:");
+        for winner in &round_archive.category_winners {
+            let name = registry
+                .as_ref()
+                .and_then(|registry| registry.name(winner.category))
+                .map_or_else(|| format!("category {}", winner.category), str::to_string);
+            println!(
+                "  {name}: {} with {} weight",
+                winner.project_id, winner.weight
+            );
+        }
+    }
+    if !round_archive.is_complete() {
+        println!("Run `cleanup {round}` to archive the remaining projects.");
+    }
 
-    let vote_manager: governance::governance::VoteManager = program.account(vote_manager_pda).await?;
-    let vote_fee = vote_manager.vote_fee;
+    Ok(())
+}
 
-    println!("Payer Pubkey: {}", payer.pubkey());
-    println!("Mint Pubkey: {}", mint);
-    println!("Admin Token Account: {}", admin_token_account);
-    println!("Vouter ATA: {}", vouter_ata);
+/// Reports the daemon's failures on stderr and through the optional alert command.
+struct Alerts<'a> {
+    cmd: Option<&'a str>,
+    reported: HashSet<String>, // Failures raised by the previous poll.
+    raised: HashSet<String>,   // Failures raised by the current poll.
+}
 
-    let send_res = program
-        .request()
-        .accounts(governance::accounts::EnsureCanVote {
-            signer: vouter.pubkey(),
-            admin_token_account,
-            admin_authority: payer.pubkey(),
-            mint,
-            user_ata: vouter_ata,
-            token_program: TOKEN_PROGRAM.parse::<Pubkey>()?,
-            associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse::<Pubkey>()?,
-            system_program: system_program::ID,
-        })
-        .args(governance::instruction::EnsureUserCanVote {
-            vote_fee,
-            guard: "__granted_access_by__cli".to_owned(),
-        }) 
-        .signer(&*vouter)
-        .send()
-        .await;
+impl<'a> Alerts<'a> {
+    fn new(cmd: Option<&'a str>) -> Self {
+        Self {
+            cmd,
+            reported: HashSet::new(),
+            raised: HashSet::new(),
+        }
+    }
 
-    match send_res {
-        Ok(sig) => println!("Ensured can vote: {sig}"),
-        Err(e) => print_transaction_logs(&e),
+    /// Reports `message`, unless the previous poll already raised it.
+    fn raise(&mut self, message: &str) {
+        if self.reported.contains(message) {
+            eprintln!("Still failing: {message}");
+        } else {
+            eprintln!("ALERT: {message}");
+            if let Some(cmd) = self.cmd {
+                let status = Command::new("sh")
+                    .arg("-c")
+                    .arg(cmd)
+                    .env("TTT_ALERT_MESSAGE", message)
+                    .status();
+                if let Err(e) = status {
+                    eprintln!("Failed to run alert command: {e}");
+                }
+            }
+        }
+        self.raised.insert(message.to_string());
     }
 
-    let send_res = program
-        .request()
-        .accounts(governance::accounts::Vouter {
-            vouter_data: vouter_pda,
-            signer: vouter.pubkey(),
-            vote_manager: vote_manager_pda,
-            admin_token_account,
-            project: project_data_pda,
-            mint,
-            token: vouter_ata,
-            token_program: TOKEN_PROGRAM.parse::<Pubkey>()?,
-            system_program: system_program::ID,
+    /// Starts a new poll, noting the failures of the previous one that were not raised again.
+    fn settle(&mut self) {
+        for message in self.reported.difference(&self.raised) {
+            println!("Resolved: {message}");
+        }
+        self.reported = std::mem::take(&mut self.raised);
+    }
+}
+
+/// Returns the path of the `ttt-<name>` plugin executable, searching `PATH` in order.
+fn find_plugin(name: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths)
+        .map(|dir| dir.join(format!("ttt-{name}")))
+        .find(|path| path.is_file())
+}
+
+/// Returns the names of the plugins found on `PATH`, without the `ttt-` prefix.
+fn list_plugins() -> Vec<String> {
+    let Some(paths) = env::var_os("PATH") else {
+        return Vec::new();
+    };
+
+    let mut plugins: Vec<String> = env::split_paths(&paths)
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            file_name.strip_prefix("ttt-").map(str::to_owned)
         })
-        .args(governance::instruction::DoVote { round })
-        .signer(&*vouter)
-        .send()
-        .await;
+        .filter(|name| name != "cli")
+        .collect();
+    plugins.sort();
+    plugins.dedup();
+    plugins
+}
 
-    match send_res {
-        Ok(sig) => println!("Success! Vote casted. Tx signature: {sig}"),
-        Err(e) => print_transaction_logs(&e),
+/// Runs a plugin with `args`, exporting the CLI configuration through `ttt_plugin::Config`.
+///
+/// A failing plugin has already reported its error, so its exit code is passed on as is.
+fn run_plugin(
+    path: &Path,
+    args: &[String],
+    election: u64,
+    yes: bool,
+) -> Result<(), Box<dyn Error>> {
+    let config = ttt_plugin::Config {
+        cluster: clients().cluster().clone(),
+        program_id: GOVERNANCE_PROGRAM_ID.parse()?,
+        token_mint: TOKEN_MINT.parse()?,
+        token_program: TOKEN_PROGRAM.parse()?,
+        associated_token_program: ASSOCIATED_TOKEN_PROGRAM.parse()?,
+        admin_keypair: ADMIN_SECRET.to_owned(),
+        voter_keypair: VOUTER_SECRET.to_owned(),
+        yes,
+        election,
+        rpc_auth: clients().auth().cloned(),
+    };
+
+    let status = Command::new(path)
+        .args(args)
+        .envs(config.envs())
+        .status()
+        .map_err(|e| CliError::config(format!("Failed to run {}: {e}", path.display())))?;
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
     }
 
     Ok(())
 }
 
-fn derive_vouter_pda(round: u8, vouter_pubkey: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            b"vouter",
-            &[round, 1, 1, 1, 1, 1],
-            &vouter_pubkey.to_bytes(),
-        ],
-        program_id,
-    )
+/// Prints a summary of the transaction about to be sent and asks the operator to confirm it.
+///
+/// Returns `Ok(true)` without prompting when `yes` is set.
+/// Outcome of guarding an admin operation with `--idempotency-key`.
+enum Guard {
+    /// Send the operation, preceded by the instruction recording its marker when a key was given.
+    Send(Option<Instruction>),
+    /// An earlier run already applied the operation.
+    Applied,
 }
 
-fn derive_project_pda(
-    project_key: &str,
-    round: u8,
-    admin_pubkey: &Pubkey,
-    program_id: &Pubkey,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            project_key.as_bytes(),
-            &[round],
-            &admin_pubkey.to_bytes(),
-        ],
-        program_id,
-    )
+/// Guards `operation` with `idempotency_key`, deriving its OperationMarker PDA.
+///
+/// The marker is recorded in the operation's own transaction, so even when this check races a
+/// run whose transaction is still in flight, only one of them lands.
+async fn guard_operation(
+    program: &anchor_client::Program<Rc<Keypair>>,
+    vote_manager: &Pubkey,
+    idempotency_key: Option<&str>,
+    operation: &str,
+) -> Result<Guard, Box<dyn Error>> {
+    let Some(idempotency_key) = idempotency_key else {
+        return Ok(Guard::Send(None));
+    };
+    let key = governance::operation_key(idempotency_key, operation);
+    let (marker, _) = seeds::operation_marker_address(vote_manager, &key, &program.id());
+    if clients()
+        .account::<governance::OperationMarker>(marker)
+        .await
+        .is_ok()
+    {
+        println!("Skipping {operation}: already applied under idempotency key '{idempotency_key}'");
+        return Ok(Guard::Applied);
+    }
+
+    Ok(Guard::Send(Some(Instruction {
+        program_id: program.id(),
+        accounts: governance::accounts::RecordOperation {
+            marker,
+            vote_manager: *vote_manager,
+            owner: program.payer(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: governance::instruction::RecordOperation { key }.data(),
+    })))
 }
 
-fn derive_vote_manager_pda(admin_pubkey: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            b"vote_manager",
-            &admin_pubkey.to_bytes(),
-        ],
-        program_id,
-    )
+fn confirm(summary: &str, yes: bool) -> Result<bool, Box<dyn Error>> {
+    println!("About to {summary}.");
+    if yes {
+        return Ok(true);
+    }
+
+    print!("Proceed? [y/N] ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    let confirmed = matches!(answer.trim().to_lowercase().as_str(), "y" | "yes");
+    if !confirmed {
+        println!("Aborted.");
+    }
+
+    Ok(confirmed)
 }
 
 fn get_keypair(str: &str) -> Result<Keypair, Box<dyn Error>> {
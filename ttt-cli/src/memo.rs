@@ -0,0 +1,80 @@
+use std::error::Error;
+
+use anchor_client::solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+/// SPL Memo program (v2), which fails unless every account it is passed signed the transaction.
+const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+/// Prefix telling vote fee memos apart from other memos in the same transaction.
+const PREFIX: &str = "ttt:vote_fee";
+
+/// Accounting memo attached to a vote, attributing its fee transfer to a round, voter and project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeMemo {
+    pub round: u64,
+    pub voter: Pubkey,
+    pub project_id: String,
+}
+
+impl FeeMemo {
+    /// Encodes the memo as `ttt:vote_fee:<round>:<voter>:<project_id>`.
+    ///
+    /// The project id comes last, so ids containing `:` still parse back.
+    pub fn encode(&self) -> String {
+        format!("{PREFIX}:{}:{}:{}", self.round, self.voter, self.project_id)
+    }
+
+    /// Parses a memo written by `encode`, `None` for any other memo.
+    pub fn parse(memo: &str) -> Option<Self> {
+        let rest = memo.strip_prefix(PREFIX)?.strip_prefix(':')?;
+        let mut fields = rest.splitn(3, ':');
+        Some(FeeMemo {
+            round: fields.next()?.parse().ok()?,
+            voter: fields.next()?.parse().ok()?,
+            project_id: fields.next()?.to_owned(),
+        })
+    }
+
+    /// Builds the Memo instruction, signed by the voter so the memo cannot name another wallet.
+    pub fn instruction(&self) -> Result<Instruction, Box<dyn Error>> {
+        Ok(Instruction {
+            program_id: MEMO_PROGRAM_ID.parse()?,
+            accounts: vec![AccountMeta::new_readonly(self.voter, true)],
+            data: self.encode().into_bytes(),
+        })
+    }
+}
+
+/// Returns the fee memos of a transaction fetched with the `json` encoding.
+///
+/// Only top-level Memo instructions signed by the voter they name are returned, so a memo
+/// claiming someone else's vote is ignored.
+pub fn transaction_fee_memos(tx: &serde_json::Value) -> Vec<FeeMemo> {
+    let message = &tx["transaction"]["message"];
+    let keys: Vec<&str> = message["accountKeys"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_str)
+        .collect();
+    let key = |index: &serde_json::Value| keys.get(index.as_u64()? as usize).copied();
+
+    message["instructions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|ix| key(&ix["programIdIndex"]) == Some(MEMO_PROGRAM_ID))
+        .filter_map(|ix| {
+            let data = bs58::decode(ix["data"].as_str()?).into_vec().ok()?;
+            let memo = FeeMemo::parse(std::str::from_utf8(&data).ok()?)?;
+            let voter = memo.voter.to_string();
+            let signed = ix["accounts"]
+                .as_array()?
+                .iter()
+                .any(|index| key(index) == Some(voter.as_str()));
+            signed.then_some(memo)
+        })
+        .collect()
+}
@@ -0,0 +1,260 @@
+use std::fmt::Write;
+
+/// Width of the longest bar in the charts, in characters for Markdown and percent for HTML.
+const BAR_WIDTH: u64 = 30;
+
+/// Templates `report` renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportTemplate {
+    Markdown,
+    Html,
+}
+
+impl ReportTemplate {
+    pub fn parse(template: &str) -> Option<Self> {
+        match template {
+            "md" => Some(ReportTemplate::Markdown),
+            "html" => Some(ReportTemplate::Html),
+            _ => None,
+        }
+    }
+}
+
+/// A project's final standing in the reported round.
+pub struct ProjectStanding {
+    pub id: String,
+    pub votes: u64,
+    pub weight: u64,
+    pub disqualified: bool,
+}
+
+/// Everything the recap of a finalized round shows, gathered by `report` from the chain.
+pub struct RoundReport {
+    pub election: u64,
+    pub result: governance::RoundResult,
+    pub projects: Vec<ProjectStanding>, // Highest weight first; empty once the round was cleaned up.
+    pub top_voters: Vec<(String, u64)>, // Voter and weight cast, highest weight first.
+    pub voters: usize,                  // Distinct voters seen in the round's votes.
+    pub fees: String,                   // Vote fees paid into the treasury, formatted.
+}
+
+impl RoundReport {
+    /// Renders the report as a self-contained Markdown or HTML document.
+    pub fn render(&self, template: ReportTemplate) -> String {
+        match template {
+            ReportTemplate::Markdown => self.markdown(),
+            ReportTemplate::Html => self.html(),
+        }
+    }
+
+    /// Label/value pairs of the summary section.
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let result = &self.result;
+        let outcome = if result.invalidated {
+            ("Invalidated", result.invalid_reason.clone())
+        } else {
+            (
+                "Winner",
+                format!("{} ({} weight)", result.winner_id, result.winner_weight),
+            )
+        };
+        let mut summary = vec![
+            outcome,
+            ("Finalized", utc_date(result.finalized_at)),
+            ("Votes cast", result.total_votes.to_string()),
+            ("Weight cast", result.total_weight.to_string()),
+            ("Voters", self.voters.to_string()),
+        ];
+        if result.eligible_weight > 0 {
+            summary.push((
+                "Turnout",
+                format!(
+                    "{}.{:02}% of {} eligible weight",
+                    result.turnout_bps / 100,
+                    result.turnout_bps % 100,
+                    result.eligible_weight
+                ),
+            ));
+        }
+        summary.push(("Fees collected", self.fees.clone()));
+        summary
+    }
+
+    fn markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# Round {} results (election {})\n",
+            self.result.round, self.election
+        );
+        for (label, value) in self.summary() {
+            let _ = writeln!(out, "- **{label}:** {}", markdown_escape(&value));
+        }
+
+        if !self.projects.is_empty() {
+            out.push_str("\n## Standings\n\n");
+            out.push_str("| # | Project | Votes | Weight |\n|---:|---|---:|---:|\n");
+            for (rank, project) in self.projects.iter().enumerate() {
+                let note = if project.disqualified {
+                    " (disqualified)"
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "| {} | {}{note} | {} | {} |",
+                    rank + 1,
+                    markdown_escape(&project.id),
+                    project.votes,
+                    project.weight
+                );
+            }
+            out.push_str("\n```text\n");
+            let max = self.projects.iter().map(|p| p.weight).max().unwrap_or(0);
+            let width = self.projects.iter().map(|p| p.id.chars().count()).max();
+            for project in &self.projects {
+                let _ = writeln!(
+                    out,
+                    "{:<width$}  {} {}",
+                    project.id,
+                    bar(project.weight, max),
+                    project.weight,
+                    width = width.unwrap_or(0)
+                );
+            }
+            out.push_str("```\n");
+        }
+
+        if !self.top_voters.is_empty() {
+            out.push_str("\n## Top voters\n\n| # | Voter | Weight |\n|---:|---|---:|\n");
+            for (rank, (voter, weight)) in self.top_voters.iter().enumerate() {
+                let _ = writeln!(out, "| {} | `{voter}` | {weight} |", rank + 1);
+            }
+        }
+        out
+    }
+
+    fn html(&self) -> String {
+        let mut out = String::new();
+        let title = format!(
+            "Round {} results (election {})",
+            self.result.round, self.election
+        );
+        let _ = writeln!(
+            out,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>\nbody {{ font-family: sans-serif; max-width: 48rem; margin: 2rem auto; }}\n\
+             table {{ border-collapse: collapse; width: 100%; }}\n\
+             td, th {{ padding: 0.25rem 0.5rem; text-align: left; }}\n\
+             .bar {{ background: #4a7bd0; height: 0.9rem; }}\n</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>"
+        );
+        out.push_str("<ul>\n");
+        for (label, value) in self.summary() {
+            let _ = writeln!(
+                out,
+                "<li><strong>{label}:</strong> {}</li>",
+                html_escape(&value)
+            );
+        }
+        out.push_str("</ul>\n");
+
+        if !self.projects.is_empty() {
+            out.push_str(
+                "<h2>Standings</h2>\n<table>\n\
+                 <tr><th>#</th><th>Project</th><th>Votes</th><th>Weight</th><th></th></tr>\n",
+            );
+            let max = self.projects.iter().map(|p| p.weight).max().unwrap_or(0);
+            for (rank, project) in self.projects.iter().enumerate() {
+                let note = if project.disqualified {
+                    " (disqualified)"
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}{note}</td><td>{}</td><td>{}</td>\
+                     <td style=\"width: 40%\"><div class=\"bar\" style=\"width: {}%\"></div></td></tr>",
+                    rank + 1,
+                    html_escape(&project.id),
+                    project.votes,
+                    project.weight,
+                    scaled(project.weight, max, 100)
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        if !self.top_voters.is_empty() {
+            out.push_str(
+                "<h2>Top voters</h2>\n<table>\n<tr><th>#</th><th>Voter</th><th>Weight</th></tr>\n",
+            );
+            for (rank, (voter, weight)) in self.top_voters.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td><code>{voter}</code></td><td>{weight}</td></tr>",
+                    rank + 1
+                );
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+/// Scales `value` against `max` onto `0..=width`, rounding down.
+fn scaled(value: u64, max: u64, width: u64) -> u64 {
+    if max == 0 {
+        return 0;
+    }
+    (u128::from(value) * u128::from(width) / u128::from(max)) as u64
+}
+
+/// Draws `value` as a bar of block characters, the longest bar being `max`.
+fn bar(value: u64, max: u64) -> String {
+    let len = scaled(value, max, BAR_WIDTH);
+    // Keep non-zero values visible next to a dominant winner.
+    let len = if value > 0 { len.max(1) } else { 0 };
+    "█".repeat(len as usize)
+}
+
+/// Formats the Unix timestamp `timestamp` as a UTC date and time, e.g. `2024-05-01 12:00 UTC`.
+fn utc_date(timestamp: i64) -> String {
+    let (days, secs) = (timestamp.div_euclid(86_400), timestamp.rem_euclid(86_400));
+    // Civil date from days since the epoch, after Howard Hinnant's `civil_from_days`.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        secs / 3_600,
+        secs % 3_600 / 60
+    )
+}
+
+/// Escapes the characters Markdown would read as table or emphasis syntax; project ids are free
+/// text.
+fn markdown_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
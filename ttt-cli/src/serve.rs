@@ -0,0 +1,462 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    error::Error,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anchor_client::{
+    anchor_lang::{AccountDeserialize, Discriminator},
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        nonblocking::rpc_client::RpcClient,
+        rpc_request::{RpcError, RpcRequest},
+    },
+    solana_sdk::{bs58, pubkey::Pubkey, transaction::VersionedTransaction},
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chrono::{DateTime, SecondsFormat};
+use governance::{ProjectData, RoundResult, Versioned};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+// Largest request accepted, headers included; a serialized transaction is at most 1232 bytes.
+const MAX_REQUEST_LEN: usize = 16 * 1024;
+// Window over which each client's requests are counted.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+// Clients tracked before windows that already ran out are dropped.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+// Path of the Atom feed of round results and new projects.
+const FEED_PATH: &str = "/feed.atom";
+// How long a rendered feed is served before the program accounts are fetched again.
+const FEED_TTL: Duration = Duration::from_secs(60);
+// Most recent entries listed in the feed.
+const FEED_ENTRIES: usize = 50;
+// Content types of the JSON-RPC replies and of the feed.
+const JSON: &str = "application/json";
+const ATOM: &str = "application/atom+xml";
+
+/// Narrow JSON-RPC proxy frontends can call instead of the operator's paid RPC endpoint.
+///
+/// **Business Logic:**
+/// - Forwards only `getAccountInfo` for program-derived addresses owned by the governance
+///   program, and `sendTransaction` whose every instruction is a governance `do_vote`.
+/// - Refuses any other method, and batch requests, without contacting the upstream node.
+/// - Also serves `GET /feed.atom`, an Atom feed of finalized round results and approved projects,
+///   rendered at most once per `FEED_TTL` whatever the number of subscribers.
+/// - Limits each client IP to `per_minute` requests; the rest get HTTP 429.
+struct RpcProxy {
+    upstream: RpcClient,  // Upstream node holding the operator's RPC key.
+    program_id: Pubkey,   // Governance program whose accounts and votes are proxied.
+    limiter: RateLimiter, // Per-IP request counts.
+    feed: Mutex<Option<(Instant, String)>>, // Last rendered Atom feed.
+    first_seen: Mutex<HashMap<Pubkey, i64>>, // When each approved project was first listed.
+}
+
+/// One `<entry>` of the Atom feed.
+struct FeedEntry {
+    id: String,           // Permanent URN of the entry.
+    title: String,        // One-line headline.
+    summary: String,      // Plain-text details.
+    link: Option<String>, // Page the entry points to, if any.
+    updated: i64,         // Unix timestamp of the entry.
+}
+
+/// Fixed-window request counter per client IP.
+struct RateLimiter {
+    per_minute: u32,                                 // Requests allowed per window.
+    windows: Mutex<HashMap<IpAddr, (Instant, u32)>>, // Window start and count per client.
+}
+
+impl RateLimiter {
+    fn allow(&self, ip: IpAddr) -> bool {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        if windows.len() >= MAX_TRACKED_CLIENTS {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < RATE_WINDOW);
+        }
+        let (start, count) = windows.entry(ip).or_insert((now, 0));
+        if now.duration_since(*start) >= RATE_WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        *count += 1;
+        *count <= self.per_minute
+    }
+}
+
+/// Serves the proxy on `listen` until the process is stopped, forwarding through `upstream`.
+pub async fn rpc_proxy(
+    listen: SocketAddr,
+    upstream: RpcClient,
+    program_id: Pubkey,
+    per_minute: u32,
+) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(listen).await?;
+    let proxy = Arc::new(RpcProxy {
+        upstream,
+        program_id,
+        limiter: RateLimiter {
+            per_minute,
+            windows: Mutex::new(HashMap::new()),
+        },
+        feed: Mutex::new(None),
+        first_seen: Mutex::new(HashMap::new()),
+    });
+    println!("RPC proxy listening on {listen}, {per_minute} requests per minute per client");
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let proxy = proxy.clone();
+        tokio::spawn(async move {
+            if let Err(e) = proxy.handle(stream, peer).await {
+                eprintln!("{peer}: {e}");
+            }
+        });
+    }
+}
+
+impl RpcProxy {
+    async fn handle(&self, mut stream: TcpStream, peer: SocketAddr) -> Result<(), Box<dyn Error>> {
+        let Some((method, path, body)) = read_request(&mut stream).await? else {
+            return write_response(&mut stream, "413 Payload Too Large", JSON, "").await;
+        };
+        match (method.as_str(), path.as_str()) {
+            // CORS preflight sent by browsers ahead of their JSON POSTs.
+            ("OPTIONS", _) => return write_response(&mut stream, "204 No Content", JSON, "").await,
+            ("POST", _) | ("GET", FEED_PATH) => {}
+            _ => return write_response(&mut stream, "405 Method Not Allowed", JSON, "").await,
+        }
+        if !self.limiter.allow(peer.ip()) {
+            let reply = rpc_error(&Value::Null, 429, "rate limit exceeded");
+            let reply = reply.to_string();
+            return write_response(&mut stream, "429 Too Many Requests", JSON, &reply).await;
+        }
+
+        if method == "GET" {
+            let feed = self.feed().await.map_err(|e| e.to_string());
+            return match feed {
+                Ok(feed) => write_response(&mut stream, "200 OK", ATOM, &feed).await,
+                Err(e) => {
+                    eprintln!("{peer}: feed: {e}");
+                    write_response(&mut stream, "502 Bad Gateway", ATOM, "").await
+                }
+            };
+        }
+        let reply = match serde_json::from_slice::<Value>(&body) {
+            Ok(request) => self.forward(&request).await,
+            Err(_) => rpc_error(&Value::Null, -32700, "parse error"),
+        };
+        write_response(&mut stream, "200 OK", JSON, &reply.to_string()).await
+    }
+
+    /// Returns the Atom feed, rendered again from the program accounts once `FEED_TTL` ran out.
+    ///
+    /// **Business Logic:**
+    /// - Lists every RoundResult, dated by its finalization, and every approved project, dated by
+    ///   when this server first listed it; projects existing at startup share the first render.
+    /// - Keeps the `FEED_ENTRIES` most recent entries, newest first.
+    /// - Projects still in an older layout are read through `Versioned`; accounts that do not
+    ///   decode are left out.
+    async fn feed(&self) -> Result<String, Box<dyn Error>> {
+        if let Some((rendered_at, feed)) = &*self.feed.lock().unwrap() {
+            if rendered_at.elapsed() < FEED_TTL {
+                return Ok(feed.clone());
+            }
+        }
+
+        let accounts = self.upstream.get_program_accounts(&self.program_id).await?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let mut entries = Vec::new();
+        let mut first_seen = self.first_seen.lock().unwrap();
+        for (address, account) in accounts {
+            let data = &account.data;
+            if data.starts_with(&RoundResult::DISCRIMINATOR) {
+                let Ok(result) = RoundResult::try_deserialize(&mut &data[..]) else {
+                    continue;
+                };
+                entries.push(result_entry(&address, &result));
+            } else if data.starts_with(&ProjectData::DISCRIMINATOR) {
+                let Ok(project) = ProjectData::try_deserialize_versioned(data) else {
+                    continue;
+                };
+                if project.pending != 0 {
+                    continue;
+                }
+                let updated = *first_seen.entry(address).or_insert(now);
+                entries.push(project_entry(&address, &project, updated));
+            }
+        }
+        drop(first_seen);
+        entries.sort_by_key(|entry| Reverse(entry.updated));
+        entries.truncate(FEED_ENTRIES);
+
+        let feed = render_feed(&self.program_id, &entries, now);
+        *self.feed.lock().unwrap() = Some((Instant::now(), feed.clone()));
+        Ok(feed)
+    }
+
+    /// Forwards an allowed request upstream and returns the JSON-RPC reply for it.
+    async fn forward(&self, request: &Value) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let params = request.get("params").cloned().unwrap_or(json!([]));
+        let rpc_request = match request.get("method").and_then(Value::as_str) {
+            Some("getAccountInfo") => RpcRequest::GetAccountInfo,
+            Some("sendTransaction") => RpcRequest::SendTransaction,
+            _ => return rpc_error(&id, -32601, "method not proxied"),
+        };
+        let allowed = match rpc_request {
+            RpcRequest::GetAccountInfo => self.is_pda(&params),
+            _ => self.is_vote_transaction(&params),
+        };
+        if let Err(reason) = allowed {
+            return rpc_error(&id, -32602, reason);
+        }
+
+        match self.upstream.send::<Value>(rpc_request, params).await {
+            Ok(result) if rpc_request == RpcRequest::GetAccountInfo => {
+                // Existing accounts must belong to the program; missing PDAs read as null.
+                let owner = result.pointer("/value/owner").and_then(Value::as_str);
+                if owner.is_some_and(|owner| owner != self.program_id.to_string()) {
+                    return rpc_error(
+                        &id,
+                        -32602,
+                        "account is not owned by the governance program",
+                    );
+                }
+                json!({ "jsonrpc": "2.0", "id": id, "result": result })
+            }
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => upstream_error(&id, e),
+        }
+    }
+
+    /// Accepts `getAccountInfo` params naming an off-curve address, i.e. a PDA.
+    fn is_pda(&self, params: &Value) -> Result<(), &'static str> {
+        let address = params
+            .get(0)
+            .and_then(Value::as_str)
+            .and_then(|address| address.parse::<Pubkey>().ok())
+            .ok_or("expected an account address")?;
+        if address.is_on_curve() {
+            return Err("only program-derived addresses are proxied");
+        }
+        Ok(())
+    }
+
+    /// Accepts `sendTransaction` params whose transaction only calls governance `do_vote`.
+    fn is_vote_transaction(&self, params: &Value) -> Result<(), &'static str> {
+        let encoded = params
+            .get(0)
+            .and_then(Value::as_str)
+            .ok_or("expected a transaction")?;
+        let encoding = params.pointer("/1/encoding").and_then(Value::as_str);
+        let bytes = match encoding {
+            Some("base64") => BASE64_STANDARD.decode(encoded).ok(),
+            None | Some("base58") => bs58::decode(encoded).into_vec().ok(),
+            Some(_) => None,
+        }
+        .ok_or("transaction is not valid base58 or base64")?;
+        let transaction = bincode::deserialize::<VersionedTransaction>(&bytes)
+            .map_err(|_| "transaction could not be decoded")?;
+
+        let keys = transaction.message.static_account_keys();
+        let instructions = transaction.message.instructions();
+        let only_votes = !instructions.is_empty()
+            && instructions.iter().all(|ix| {
+                keys.get(usize::from(ix.program_id_index)) == Some(&self.program_id)
+                    && ix
+                        .data
+                        .starts_with(&governance::instruction::DoVote::DISCRIMINATOR)
+            });
+        if !only_votes {
+            return Err("only governance vote instructions are proxied");
+        }
+        Ok(())
+    }
+}
+
+/// Builds the feed entry announcing a finalized round.
+fn result_entry(address: &Pubkey, result: &RoundResult) -> FeedEntry {
+    let title = if result.winner == Pubkey::default() {
+        format!("Round {} closed without votes", result.round)
+    } else {
+        format!("Round {} won by {}", result.round, result.winner_id)
+    };
+    FeedEntry {
+        id: format!("urn:ttt:round-result:{address}"),
+        title,
+        summary: format!(
+            "{} votes of total weight {} from {} voter records; the winner drew weight {}. \
+             Election: {}.",
+            result.total_votes,
+            result.total_weight,
+            result.participation_count,
+            result.winner_weight,
+            result.vote_manager
+        ),
+        link: None,
+        updated: result.finalized_at,
+    }
+}
+
+/// Builds the feed entry announcing a project open for votes.
+fn project_entry(address: &Pubkey, project: &ProjectData, updated: i64) -> FeedEntry {
+    let name = if project.name().is_empty() {
+        project.id()
+    } else {
+        project.name()
+    };
+    FeedEntry {
+        id: format!("urn:ttt:project:{address}"),
+        title: format!("New project in round {}: {name}", project.vote_round),
+        summary: format!(
+            "Project {} is open for votes in round {}. Election: {}.",
+            project.id(),
+            project.vote_round,
+            project.vote_manager
+        ),
+        link: (!project.uri().is_empty()).then(|| project.uri().to_owned()),
+        updated,
+    }
+}
+
+/// Renders `entries` as an Atom 1.0 document.
+fn render_feed(program_id: &Pubkey, entries: &[FeedEntry], now: i64) -> String {
+    let updated = entries.first().map_or(now, |entry| entry.updated);
+    let mut feed = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n\
+         <title>TTT round results and projects</title>\n\
+         <id>urn:ttt:feed:{program_id}</id>\n\
+         <updated>{}</updated>\n\
+         <author><name>TTT governance</name></author>\n",
+        atom_time(updated)
+    );
+    for entry in entries {
+        feed.push_str(&format!(
+            "<entry>\n\
+             <id>{}</id>\n\
+             <title>{}</title>\n\
+             <updated>{}</updated>\n\
+             <summary>{}</summary>\n",
+            xml_escape(&entry.id),
+            xml_escape(&entry.title),
+            atom_time(entry.updated),
+            xml_escape(&entry.summary)
+        ));
+        if let Some(link) = &entry.link {
+            feed.push_str(&format!("<link href=\"{}\"/>\n", xml_escape(link)));
+        }
+        feed.push_str("</entry>\n");
+    }
+    feed.push_str("</feed>\n");
+    feed
+}
+
+/// Formats a Unix timestamp as the RFC 3339 date Atom requires.
+fn atom_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Escapes the characters XML reserves in text and attribute values.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reads one HTTP request, returning its method, path and body, or `None` when it is too large.
+async fn read_request(
+    stream: &mut TcpStream,
+) -> Result<Option<(String, String, Vec<u8>)>, Box<dyn Error>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > MAX_REQUEST_LEN {
+            return Ok(None);
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err("connection closed before the request ended".into());
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut request_line = head.split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_owned();
+    let path = request_line.next().unwrap_or_default().to_owned();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    if header_end + content_length > MAX_REQUEST_LEN {
+        return Ok(None);
+    }
+
+    while buf.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err("connection closed before the body ended".into());
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+    buf.truncate(header_end + content_length);
+    Ok(Some((method, path, buf.split_off(header_end))))
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> Result<(), Box<dyn Error>> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(stream.shutdown().await?)
+}
+
+fn rpc_error(id: &Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Passes the upstream node's JSON-RPC error through; transport failures become internal errors.
+fn upstream_error(id: &Value, e: ClientError) -> Value {
+    match e.kind {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) => {
+            rpc_error(id, code, &message)
+        }
+        _ => rpc_error(id, -32603, "upstream request failed"),
+    }
+}
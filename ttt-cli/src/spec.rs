@@ -0,0 +1,137 @@
+use std::{error::Error, fs};
+
+use governance::{WeightCurve, WeightMode, WEIGHT_PRECISION};
+
+use crate::error::CliError;
+
+/// Desired configuration of an election, read from the YAML spec `apply` converges the chain to.
+///
+/// Every key is optional: a key left out of the spec leaves that setting as it is on chain.
+///
+/// ```yaml
+/// fee: 1.5                 # Vote fee in TTT.
+/// weight_curve:
+///   mode: sqrt             # flat, linear or sqrt.
+///   multiplier: 1000000    # Optional, `WEIGHT_PRECISION` == 1.0.
+///   cap: 0                 # Optional, zero for uncapped.
+/// schedule:
+///   start_ts: 1767225600   # Unix timestamp.
+///   duration: 604800       # Seconds, zero for no deadline.
+/// allowlist: snapshot.json # Eligibility snapshot, `none` to lift the restriction.
+/// projects: [alpha, beta]  # Projects of the current round.
+/// ```
+pub struct ElectionSpec {
+    pub fee: Option<String>,               // Human TTT amount, scaled with the mint's decimals.
+    pub weight_curve: Option<WeightCurve>, // Curve `set_weight_curve` installs.
+    pub schedule: Option<(i64, i64)>,      // `round_start_ts` and `round_duration`.
+    pub projects: Vec<String>,             // Ids that must exist in the current round.
+    pub allowlist: Option<String>,         // Snapshot file written by `snapshot`, or `none`.
+}
+
+impl ElectionSpec {
+    /// Reads the spec at `path`.
+    pub fn read(path: &str) -> Result<Self, Box<dyn Error>> {
+        let spec: serde_json::Value = serde_yaml::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| CliError::config(format!("{path} is not a valid YAML spec: {e}")))?;
+        Ok(Self::parse(&spec)?)
+    }
+
+    /// Parses a spec already decoded from YAML, rejecting unknown keys so a typo is not ignored.
+    pub fn parse(spec: &serde_json::Value) -> Result<Self, CliError> {
+        let map = spec
+            .as_object()
+            .ok_or_else(|| CliError::config("The election spec must be a YAML mapping"))?;
+        if let Some(key) = map.keys().find(|key| {
+            !["fee", "weight_curve", "schedule", "projects", "allowlist"].contains(&key.as_str())
+        }) {
+            return Err(CliError::config(format!(
+                "Unknown key `{key}` in the election spec"
+            )));
+        }
+
+        let fee = match &spec["fee"] {
+            serde_json::Value::Null => None,
+            serde_json::Value::Number(fee) => Some(fee.to_string()),
+            serde_json::Value::String(fee) => Some(fee.clone()),
+            _ => return Err(CliError::config("`fee` must be a TTT amount")),
+        };
+
+        let weight_curve = match &spec["weight_curve"] {
+            serde_json::Value::Null => None,
+            curve => {
+                let mode = match curve["mode"].as_str() {
+                    Some("flat") => WeightMode::Flat,
+                    Some("linear") => WeightMode::Linear,
+                    Some("sqrt") => WeightMode::Sqrt,
+                    _ => {
+                        return Err(CliError::config(
+                            "`weight_curve.mode` must be flat, linear or sqrt",
+                        ))
+                    }
+                };
+                let optional = |key: &str| match &curve[key] {
+                    serde_json::Value::Null => Ok(None),
+                    value => value.as_u64().map(Some).ok_or_else(|| {
+                        CliError::config(format!(
+                            "`weight_curve.{key}` must be an unsigned integer"
+                        ))
+                    }),
+                };
+                Some(WeightCurve {
+                    mode,
+                    multiplier: optional("multiplier")?.unwrap_or(WEIGHT_PRECISION),
+                    cap: optional("cap")?.unwrap_or(0),
+                })
+            }
+        };
+
+        let schedule = match &spec["schedule"] {
+            serde_json::Value::Null => None,
+            schedule => {
+                let timestamp = |key: &str| {
+                    schedule[key].as_i64().ok_or_else(|| {
+                        CliError::config(format!("`schedule.{key}` must be an integer"))
+                    })
+                };
+                Some((timestamp("start_ts")?, timestamp("duration")?))
+            }
+        };
+
+        let projects = match &spec["projects"] {
+            serde_json::Value::Null => Vec::new(),
+            projects => projects
+                .as_array()
+                .and_then(|ids| {
+                    ids.iter()
+                        .map(|id| id.as_str().map(str::to_owned))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| CliError::config("`projects` must be a list of project ids"))?,
+        };
+        if let Some(id) = (1..projects.len())
+            .find_map(|i| projects[..i].contains(&projects[i]).then_some(&projects[i]))
+        {
+            return Err(CliError::config(format!(
+                "Project `{id}` is listed twice in the election spec"
+            )));
+        }
+
+        let allowlist = match &spec["allowlist"] {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(file) => Some(file.clone()),
+            _ => {
+                return Err(CliError::config(
+                    "`allowlist` must be a snapshot file or `none`",
+                ))
+            }
+        };
+
+        Ok(ElectionSpec {
+            fee,
+            weight_curve,
+            schedule,
+            projects,
+            allowlist,
+        })
+    }
+}
@@ -0,0 +1,160 @@
+//! API for `ttt-<name>` plugins of the TTT CLI.
+//!
+//! `ttt-cli <name> [args..]` runs the first `ttt-<name>` executable found on `PATH` for commands it
+//! does not know, passing the remaining arguments through and the CLI's configuration in `TTT_*`
+//! environment variables. A plugin reads it back with [`Config::from_env`] and builds its clients
+//! with [`Config::program`], so it talks to the same cluster, program and mint as the CLI.
+//! [`Config::clients`] builds the shared [`rpc::ClientFactory`], authenticated like the CLI's.
+
+pub mod rpc;
+
+use std::{env, error::Error, fmt, rc::Rc};
+
+use anchor_client::{
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+    Cluster, Program,
+};
+
+pub use governance;
+use rpc::{ClientFactory, RpcAuth};
+
+pub const CLUSTER_ENV: &str = "TTT_CLUSTER";
+pub const PROGRAM_ID_ENV: &str = "TTT_PROGRAM_ID";
+pub const TOKEN_MINT_ENV: &str = "TTT_TOKEN_MINT";
+pub const TOKEN_PROGRAM_ENV: &str = "TTT_TOKEN_PROGRAM";
+pub const ASSOCIATED_TOKEN_PROGRAM_ENV: &str = "TTT_ASSOCIATED_TOKEN_PROGRAM";
+pub const ADMIN_KEYPAIR_ENV: &str = "TTT_ADMIN_KEYPAIR";
+pub const VOTER_KEYPAIR_ENV: &str = "TTT_VOTER_KEYPAIR";
+pub const YES_ENV: &str = "TTT_YES";
+pub const ELECTION_ENV: &str = "TTT_ELECTION";
+pub const RPC_AUTH_ENV: &str = "TTT_RPC_AUTH";
+pub const RPC_API_KEY_ENV: &str = "TTT_RPC_API_KEY";
+
+/// The CLI configuration handed to a plugin.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cluster: Cluster,
+    pub program_id: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_program: Pubkey,
+    pub associated_token_program: Pubkey,
+    /// Path of the admin keypair file, `~` is expanded when loading it.
+    pub admin_keypair: String,
+    /// Path of the voter keypair file, `~` is expanded when loading it.
+    pub voter_keypair: String,
+    /// Whether `--yes` was passed, so the plugin should not prompt for confirmation.
+    pub yes: bool,
+    /// The election selected with `--election`, 0 by default.
+    pub election: u64,
+    /// API key of an authenticated RPC provider, `None` for an anonymous endpoint.
+    pub rpc_auth: Option<RpcAuth>,
+}
+
+/// A missing or malformed `TTT_*` variable.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub var: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.var, self.message)
+    }
+}
+
+impl Error for ConfigError {}
+
+impl Config {
+    /// Reads the configuration the CLI exported for this plugin.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Ok(Config {
+            cluster: parse_var(CLUSTER_ENV)?,
+            program_id: parse_var(PROGRAM_ID_ENV)?,
+            token_mint: parse_var(TOKEN_MINT_ENV)?,
+            token_program: parse_var(TOKEN_PROGRAM_ENV)?,
+            associated_token_program: parse_var(ASSOCIATED_TOKEN_PROGRAM_ENV)?,
+            admin_keypair: var(ADMIN_KEYPAIR_ENV)?,
+            voter_keypair: var(VOTER_KEYPAIR_ENV)?,
+            yes: env::var(YES_ENV).is_ok_and(|yes| yes == "1"),
+            election: parse_var(ELECTION_ENV)?,
+            rpc_auth: RpcAuth::from_env()?,
+        })
+    }
+
+    /// Returns the variables [`Config::from_env`] reads, for the CLI to set on a plugin process.
+    pub fn envs(&self) -> Vec<(&'static str, String)> {
+        let mut envs = vec![
+            (CLUSTER_ENV, self.cluster.to_string()),
+            (PROGRAM_ID_ENV, self.program_id.to_string()),
+            (TOKEN_MINT_ENV, self.token_mint.to_string()),
+            (TOKEN_PROGRAM_ENV, self.token_program.to_string()),
+            (
+                ASSOCIATED_TOKEN_PROGRAM_ENV,
+                self.associated_token_program.to_string(),
+            ),
+            (ADMIN_KEYPAIR_ENV, self.admin_keypair.clone()),
+            (VOTER_KEYPAIR_ENV, self.voter_keypair.clone()),
+            (YES_ENV, u8::from(self.yes).to_string()),
+            (ELECTION_ENV, self.election.to_string()),
+        ];
+        if let Some(auth) = &self.rpc_auth {
+            envs.push((RPC_AUTH_ENV, auth.scheme()));
+            envs.push((RPC_API_KEY_ENV, auth.key().to_owned()));
+        }
+        envs
+    }
+
+    /// Loads the admin keypair.
+    pub fn admin(&self) -> Result<Keypair, Box<dyn Error>> {
+        load_keypair(&self.admin_keypair)
+    }
+
+    /// Loads the voter keypair.
+    pub fn voter(&self) -> Result<Keypair, Box<dyn Error>> {
+        load_keypair(&self.voter_keypair)
+    }
+
+    /// Builds the client factory of the configured cluster.
+    pub fn clients(&self) -> Result<ClientFactory, Box<dyn Error>> {
+        ClientFactory::new(self.cluster.clone(), self.rpc_auth.clone())
+    }
+
+    /// Builds a client for the governance program paid by `payer`.
+    pub fn program(&self, payer: Rc<Keypair>) -> Result<Program<Rc<Keypair>>, Box<dyn Error>> {
+        let client = self.clients()?.client(payer);
+        Ok(client.program(self.program_id)?)
+    }
+
+    /// Derives the VoteManager PDA of the selected election, created by the admin.
+    pub fn vote_manager(&self) -> Result<Pubkey, Box<dyn Error>> {
+        let admin = self.admin()?.pubkey();
+        Ok(governance::seeds::vote_manager_address(&admin, self.election, &self.program_id).0)
+    }
+}
+
+fn var(name: &'static str) -> Result<String, ConfigError> {
+    env::var(name).map_err(|e| ConfigError {
+        var: name,
+        message: e.to_string(),
+    })
+}
+
+fn parse_var<T>(name: &'static str) -> Result<T, ConfigError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    var(name)?.parse().map_err(|e: T::Err| ConfigError {
+        var: name,
+        message: e.to_string(),
+    })
+}
+
+fn load_keypair(path: &str) -> Result<Keypair, Box<dyn Error>> {
+    let file = String::from_utf8(tilde_expand::tilde_expand(path.as_bytes()))?;
+    read_keypair_file(file)
+}
@@ -0,0 +1,373 @@
+use std::{
+    env, error::Error, fmt, future::Future, iter, ops::Deref, rc::Rc, str::FromStr, time::Duration,
+};
+
+use anchor_client::{
+    anchor_lang::{self, AccountDeserialize, Discriminator},
+    solana_client::{
+        nonblocking::rpc_client::RpcClient,
+        rpc_client::RpcClientConfig,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature},
+    },
+    Client, ClientError, Cluster, RequestBuilder,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client::http_sender::HttpSender;
+
+use crate::{
+    governance::{ProjectData, Versioned, VoteManager, VoterData},
+    ConfigError, RPC_API_KEY_ENV, RPC_AUTH_ENV,
+};
+
+/// Idle connections kept open to the RPC endpoint between requests.
+const POOL_MAX_IDLE: usize = 8;
+/// How long an idle pooled connection is kept before it is closed.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Timeout of a single RPC request, matching the Solana client's default.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the API key of an authenticated RPC provider is sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcAuth {
+    /// Appended to the HTTP and websocket URLs as `?<param>=<key>`, e.g. Helius' `api-key`.
+    Query { param: String, key: String },
+    /// Sent as the `<name>: <key>` header of every HTTP request, e.g. `x-token`.
+    Header { name: String, key: String },
+}
+
+impl RpcAuth {
+    /// Builds the auth from a `query:<param>` or `header:<name>` scheme and the API key.
+    pub fn new(scheme: &str, key: String) -> Result<Self, String> {
+        match scheme.split_once(':') {
+            Some(("query", param)) if !param.is_empty() => Ok(RpcAuth::Query {
+                param: param.to_owned(),
+                key,
+            }),
+            Some(("header", name)) if HeaderName::from_str(name).is_ok() => Ok(RpcAuth::Header {
+                name: name.to_owned(),
+                key,
+            }),
+            _ => Err(format!(
+                "invalid RPC auth scheme `{scheme}`, expected `query:<param>` or `header:<name>`"
+            )),
+        }
+    }
+
+    /// Reads the auth from `TTT_RPC_API_KEY` and `TTT_RPC_AUTH`, `None` without an API key.
+    ///
+    /// The scheme defaults to `query:api-key`, the parameter Helius reads.
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let Ok(key) = env::var(RPC_API_KEY_ENV) else {
+            return Ok(None);
+        };
+        let scheme = env::var(RPC_AUTH_ENV).unwrap_or_else(|_| "query:api-key".to_owned());
+        RpcAuth::new(&scheme, key)
+            .map(Some)
+            .map_err(|message| ConfigError {
+                var: RPC_AUTH_ENV,
+                message,
+            })
+    }
+
+    /// The scheme [`RpcAuth::new`] reads back.
+    pub fn scheme(&self) -> String {
+        match self {
+            RpcAuth::Query { param, .. } => format!("query:{param}"),
+            RpcAuth::Header { name, .. } => format!("header:{name}"),
+        }
+    }
+
+    /// The API key.
+    pub fn key(&self) -> &str {
+        match self {
+            RpcAuth::Query { key, .. } | RpcAuth::Header { key, .. } => key,
+        }
+    }
+}
+
+/// The key is left out, so the auth can be logged.
+impl fmt::Display for RpcAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.scheme())
+    }
+}
+
+/// How [`ClientFactory::account_with_config`] reads an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchConfig {
+    /// The commitment the account is read at.
+    pub commitment: CommitmentConfig,
+    /// The slot the node must have reached, `None` to read whatever it has.
+    pub min_context_slot: Option<u64>,
+}
+
+impl FetchConfig {
+    /// Reads at `confirmed` commitment from a node that reached `slot`.
+    pub fn at_slot(slot: u64) -> Self {
+        FetchConfig {
+            commitment: CommitmentConfig::confirmed(),
+            min_context_slot: Some(slot),
+        }
+    }
+}
+
+/// Reads at `confirmed` commitment from any slot.
+impl Default for FetchConfig {
+    fn default() -> Self {
+        FetchConfig {
+            commitment: CommitmentConfig::confirmed(),
+            min_context_slot: None,
+        }
+    }
+}
+
+/// An account decoded along with the slot the node served it at.
+#[derive(Debug, Clone)]
+pub struct Observed<T> {
+    pub slot: u64,
+    pub account: T,
+}
+
+/// Builds every client of one cluster, all sharing a single pool of authenticated HTTP
+/// connections.
+///
+/// Anchor's own clients open a connection pool each and know nothing of auth headers, so accounts
+/// are fetched and transactions sent through [`ClientFactory::account`],
+/// [`ClientFactory::accounts`] and [`SendVia::send_via`] instead. Log subscriptions go through Anchor's websocket, which only
+/// carries query-param keys.
+#[derive(Debug, Clone)]
+pub struct ClientFactory {
+    cluster: Cluster,
+    auth: Option<RpcAuth>,
+    http: reqwest::Client, // Clones share the connection pool.
+}
+
+impl ClientFactory {
+    pub fn new(cluster: Cluster, auth: Option<RpcAuth>) -> Result<Self, Box<dyn Error>> {
+        let mut headers = HeaderMap::new();
+        if let Some(RpcAuth::Header { name, key }) = &auth {
+            let mut value = HeaderValue::from_str(key)?;
+            value.set_sensitive(true);
+            headers.insert(HeaderName::from_str(name)?, value);
+        }
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .pool_max_idle_per_host(POOL_MAX_IDLE)
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(ClientFactory {
+            cluster,
+            auth,
+            http,
+        })
+    }
+
+    /// The cluster as configured, without the API key.
+    pub fn cluster(&self) -> &Cluster {
+        &self.cluster
+    }
+
+    pub fn auth(&self) -> Option<&RpcAuth> {
+        self.auth.as_ref()
+    }
+
+    /// Builds an Anchor client paid by `payer`, with a query-param key added to its URLs.
+    pub fn client(&self, payer: Rc<Keypair>) -> Client<Rc<Keypair>> {
+        let cluster = match &self.auth {
+            Some(RpcAuth::Query { param, key }) => Cluster::Custom(
+                with_query(self.cluster.url(), param, key),
+                with_query(self.cluster.ws_url(), param, key),
+            ),
+            _ => self.cluster.clone(),
+        };
+        Client::new(cluster, payer)
+    }
+
+    /// Builds an RPC client on the shared connection pool.
+    pub fn rpc(&self) -> RpcClient {
+        let url = match &self.auth {
+            Some(RpcAuth::Query { param, key }) => with_query(self.cluster.url(), param, key),
+            _ => self.cluster.url().to_owned(),
+        };
+        RpcClient::new_sender(
+            HttpSender::new_with_client(url, self.http.clone()),
+            RpcClientConfig::with_commitment(CommitmentConfig::default()),
+        )
+    }
+
+    /// Fetches and deserializes the Anchor account at `address`.
+    pub async fn account<T: AccountDeserialize>(&self, address: Pubkey) -> Result<T, ClientError> {
+        let data = self.rpc().get_account_data(&address).await?;
+        Ok(T::try_deserialize(&mut data.as_slice())?)
+    }
+
+    /// Fetches and deserializes the versioned account at `address`, also accepting the layout
+    /// preceding its `version` byte while the account awaits `migrate_account`.
+    pub async fn versioned_account<T: Versioned>(&self, address: Pubkey) -> Result<T, ClientError> {
+        let data = self.rpc().get_account_data(&address).await?;
+        Ok(T::try_deserialize_versioned(&data)?)
+    }
+
+    /// Fetches and deserializes the Anchor account at `address` as `config` says, along with the
+    /// slot it was observed at.
+    ///
+    /// The node rejects the request until it reaches `config.min_context_slot`, so a report can
+    /// pass the slot of its first read to every later one and state the slot range it covers.
+    pub async fn account_with_config<T: AccountDeserialize>(
+        &self,
+        address: Pubkey,
+        config: FetchConfig,
+    ) -> Result<Observed<T>, ClientError> {
+        self.observe(address, config, |data| T::try_deserialize(&mut &data[..]))
+            .await
+    }
+
+    /// Like [`Self::account_with_config`] for a versioned account, accepting the layout preceding
+    /// its `version` byte like [`Self::versioned_account`].
+    pub async fn versioned_account_with_config<T: Versioned>(
+        &self,
+        address: Pubkey,
+        config: FetchConfig,
+    ) -> Result<Observed<T>, ClientError> {
+        self.observe(address, config, T::try_deserialize_versioned)
+            .await
+    }
+
+    async fn observe<T>(
+        &self,
+        address: Pubkey,
+        config: FetchConfig,
+        decode: impl FnOnce(&[u8]) -> anchor_lang::Result<T>,
+    ) -> Result<Observed<T>, ClientError> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(config.commitment),
+            min_context_slot: config.min_context_slot,
+            ..RpcAccountInfoConfig::default()
+        };
+        let response = self.rpc().get_account_with_config(&address, config).await?;
+        let account = response.value.ok_or(ClientError::AccountNotFound)?;
+        Ok(Observed {
+            slot: response.context.slot,
+            account: decode(&account.data)?,
+        })
+    }
+
+    /// Fetches the VoteManager at `address` from a node that reached `slot`.
+    pub async fn fetch_vote_manager_at_slot(
+        &self,
+        address: Pubkey,
+        slot: u64,
+    ) -> Result<Observed<VoteManager>, ClientError> {
+        self.versioned_account_with_config(address, FetchConfig::at_slot(slot))
+            .await
+    }
+
+    /// Fetches the project at `address` from a node that reached `slot`.
+    pub async fn fetch_project_at_slot(
+        &self,
+        address: Pubkey,
+        slot: u64,
+    ) -> Result<Observed<ProjectData>, ClientError> {
+        self.versioned_account_with_config(address, FetchConfig::at_slot(slot))
+            .await
+    }
+
+    /// Fetches the voter record at `address` from a node that reached `slot`.
+    pub async fn fetch_voter_at_slot(
+        &self,
+        address: Pubkey,
+        slot: u64,
+    ) -> Result<Observed<VoterData>, ClientError> {
+        self.versioned_account_with_config(address, FetchConfig::at_slot(slot))
+            .await
+    }
+
+    /// Fetches every `T` account of `program_id` matching `filters`, like Anchor's
+    /// `Program::accounts`.
+    pub async fn accounts<T: AccountDeserialize + Discriminator>(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, T)>, ClientError> {
+        self.program_accounts(program_id, filters, |data| {
+            T::try_deserialize(&mut &data[..])
+        })
+        .await
+    }
+
+    /// Like [`Self::accounts`] for a versioned account, accepting the layouts preceding the
+    /// current one like [`Self::versioned_account`].
+    pub async fn versioned_accounts<T: Versioned>(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, T)>, ClientError> {
+        self.program_accounts(program_id, filters, T::try_deserialize_versioned)
+            .await
+    }
+
+    async fn program_accounts<T: Discriminator>(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+        decode: impl Fn(&[u8]) -> anchor_lang::Result<T>,
+    ) -> Result<Vec<(Pubkey, T)>, ClientError> {
+        let discriminator = Memcmp::new_base58_encoded(0, &T::DISCRIMINATOR);
+        let config = RpcProgramAccountsConfig {
+            filters: Some(
+                iter::once(RpcFilterType::Memcmp(discriminator))
+                    .chain(filters)
+                    .collect(),
+            ),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self
+            .rpc()
+            .get_program_accounts_with_config(program_id, config)
+            .await?;
+        let mut decoded = Vec::with_capacity(accounts.len());
+        for (address, account) in accounts {
+            decoded.push((address, decode(&account.data)?));
+        }
+        Ok(decoded)
+    }
+}
+
+/// Sends an Anchor request through a [`ClientFactory`] rather than the client it was built with.
+///
+/// The request still fetches its blockhash from the cluster it was built for, which
+/// [`ClientFactory::client`] points at the authenticated URL of query-param providers.
+pub trait SendVia {
+    fn send_via(
+        self,
+        clients: &ClientFactory,
+    ) -> impl Future<Output = Result<Signature, ClientError>>;
+}
+
+impl<C> SendVia for RequestBuilder<'_, C>
+where
+    C: Deref<Target = Keypair> + Clone,
+{
+    async fn send_via(self, clients: &ClientFactory) -> Result<Signature, ClientError> {
+        let tx = self.signed_transaction().await?;
+        Ok(clients.rpc().send_and_confirm_transaction(&tx).await?)
+    }
+}
+
+fn with_query(url: &str, param: &str, key: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}{param}={key}")
+}